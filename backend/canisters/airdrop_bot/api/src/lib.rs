@@ -1,12 +1,14 @@
 mod lifecycle;
+mod queries;
 mod updates;
 
 use candid::CandidType;
 pub use lifecycle::*;
+pub use queries::*;
 pub use updates::*;
 
 use serde::{Deserialize, Serialize};
-use types::{ChannelId, CommunityId, TimestampMillis};
+use types::{ChannelId, CommunityId, Milliseconds, TimestampMillis, UserId};
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct AirdropConfig {
@@ -14,6 +16,27 @@ pub struct AirdropConfig {
     pub channel_id: ChannelId,
     pub start: TimestampMillis,
     pub algorithm: AirdropAlgorithm,
+    #[serde(default)]
+    pub criteria: Option<AirdropCriteria>,
+    #[serde(default)]
+    pub recurrence: Option<AirdropRecurrence>,
+}
+
+// Participants must satisfy all of the specified criteria to be included in an airdrop
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AirdropCriteria {
+    pub min_chit: Option<u32>,
+    pub min_streak: Option<u16>,
+    pub diamond_members_only: bool,
+    pub unique_persons_only: bool,
+    pub min_channel_membership: Option<Milliseconds>,
+}
+
+// Once a recurring airdrop has executed, the next occurrence is scheduled `interval_months` later
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AirdropRecurrence {
+    pub interval_months: u8,
+    pub remaining_occurrences: Option<u32>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -39,3 +62,32 @@ pub struct V2Algorithm {
     pub lottery_min_streak: u16,
     pub min_minutes_online: u16,
 }
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AirdropReport {
+    pub id: u32,
+    pub config: AirdropConfig,
+    pub budget: u128,
+    pub total_allocated: u128,
+    pub total_transferred: u128,
+    pub ineligible: u32,
+    pub participants: Vec<ParticipantReport>,
+    pub lottery_winners: Vec<LotteryWinnerReport>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ParticipantReport {
+    pub user_id: UserId,
+    pub chit: u32,
+    pub shares: u32,
+    pub amount: u128,
+    pub block_index: Option<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct LotteryWinnerReport {
+    pub user_id: UserId,
+    pub position: u32,
+    pub amount: u128,
+    pub block_index: Option<u64>,
+}
@@ -0,0 +1,2 @@
+pub mod airdrop_report;
+pub mod scheduled_airdrops;
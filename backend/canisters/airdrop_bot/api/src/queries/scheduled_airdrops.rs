@@ -0,0 +1,17 @@
+use crate::AirdropConfig;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::Empty;
+
+pub type Args = Empty;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(Vec<ScheduledAirdrop>),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct ScheduledAirdrop {
+    pub id: u32,
+    pub config: AirdropConfig,
+}
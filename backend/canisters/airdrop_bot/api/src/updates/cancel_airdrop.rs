@@ -1,10 +1,14 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
-use types::Empty;
 
-pub type Args = Empty;
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // If `None`, all scheduled airdrops are cancelled
+    pub id: Option<u32>,
+}
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum Response {
     Success,
+    NotFound,
 }
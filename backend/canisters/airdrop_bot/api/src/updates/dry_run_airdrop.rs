@@ -0,0 +1,16 @@
+use crate::AirdropReport;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub id: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(AirdropReport),
+    NotFound,
+    NoEligibleParticipants,
+    InternalError(String),
+}
@@ -6,8 +6,9 @@ pub type Args = AirdropConfig;
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum Response {
-    Success,
+    Success(u32),
     ChannelUsed,
     InThePast,
     ClashesWithPrevious,
+    InvalidRecurrence,
 }
@@ -5,7 +5,7 @@ use constants::{CHAT_TRANSFER_FEE, MEMO_CHIT_FOR_CHAT_AIRDROP, MEMO_CHIT_FOR_CHA
 use icrc_ledger_types::icrc1::transfer::{TransferArg, TransferError};
 use rand::Rng;
 use serde::Serialize;
-use timer_job_queues::TimerJobItem;
+use timer_job_queues::{TimerJobItem, TimerJobOutcome};
 use tracing::{error, info, trace};
 use types::icrc1::{self, Account};
 use types::{
@@ -16,15 +16,16 @@ use utils::canister::should_retry_failed_c2c_call;
 use utils::time::{MONTHS, MonthKey};
 
 impl TimerJobItem for Action {
-    async fn process(&self) -> Result<(), bool> {
-        match self.clone() {
+    async fn process(&self) -> TimerJobOutcome {
+        let result = match self.clone() {
             Action::JoinChannel(community_id, channel_id) => join_channel(community_id, channel_id).await,
             Action::SendMessage(a) if matches!(a.airdrop_type, AirdropType::Lottery(_)) => {
                 handle_lottery_message_action(*a).await
             }
             Action::SendMessage(a) => handle_main_message_action(*a).await,
             Action::Transfer(a) => handle_transfer_action(*a).await,
-        }
+        };
+        result.into()
     }
 }
 
@@ -37,6 +38,8 @@ pub enum Action {
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct AirdropTransfer {
+    #[serde(default)]
+    pub airdrop_id: Option<u32>,
     pub recipient: UserId,
     pub amount: u128,
     pub airdrop_type: AirdropType,
@@ -44,6 +47,8 @@ pub struct AirdropTransfer {
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct AirdropMessage {
+    #[serde(default)]
+    pub airdrop_id: Option<u32>,
     pub recipient: UserId,
     pub transaction: CompletedCryptoTransaction,
     pub airdrop_type: AirdropType,
@@ -153,6 +158,7 @@ async fn handle_transfer_action(action: AirdropTransfer) -> Result<(), bool> {
                 let block_index = block_index.0.try_into().unwrap();
 
                 let message_action = Action::SendMessage(Box::new(AirdropMessage {
+                    airdrop_id: action.airdrop_id,
                     recipient: action.recipient,
                     transaction: CompletedCryptoTransaction::ICRC1(icrc1::CompletedCryptoTransaction {
                         ledger: ledger_canister_id,
@@ -173,9 +179,17 @@ async fn handle_transfer_action(action: AirdropTransfer) -> Result<(), bool> {
 
                 match action.airdrop_type {
                     AirdropType::Lottery(LotteryAirdrop { position }) => {
-                        state.data.airdrops.set_lottery_transaction(position, block_index)
+                        state
+                            .data
+                            .airdrops
+                            .set_lottery_transaction(action.airdrop_id, position, block_index)
+                    }
+                    AirdropType::Main(_) => {
+                        state
+                            .data
+                            .airdrops
+                            .set_main_transaction(action.airdrop_id, &action.recipient, block_index)
                     }
-                    AirdropType::Main(_) => state.data.airdrops.set_main_transaction(&action.recipient, block_index),
                 }
             });
         }
@@ -202,8 +216,8 @@ async fn handle_main_message_action(action: AirdropMessage) -> Result<(), bool>
     };
 
     let Some(month) = read_state(|state| {
-        state.data.airdrops.current(state.env.now()).map(|c| {
-            let mk = MonthKey::from_timestamp(c.start).previous();
+        state.data.airdrops.get(action.airdrop_id).map(|a| {
+            let mk = MonthKey::from_timestamp(a.config.start).previous();
             MONTHS[mk.month() as usize - 1]
         })
     }) else {
@@ -252,8 +266,8 @@ async fn handle_lottery_message_action(action: AirdropMessage) -> Result<(), boo
         state
             .data
             .airdrops
-            .current(state.env.now())
-            .map(|c| (c.community_id, c.channel_id, state.env.rng().r#gen()))
+            .get(action.airdrop_id)
+            .map(|a| (a.config.community_id, a.config.channel_id, state.env.rng().r#gen()))
     }) else {
         return Err(false);
     };
@@ -1,16 +1,31 @@
 use crate::actions::{Action, AirdropTransfer, AirdropType, LotteryAirdrop, MainAirdrop};
+use crate::model::airdrops::{Candidate, is_eligible};
 use crate::{RuntimeState, mutate_state, read_state};
-use airdrop_bot_canister::{AirdropAlgorithm, AirdropConfig};
+use airdrop_bot_canister::AirdropConfig;
+use constants::CHAT_TRANSFER_FEE;
 use ic_cdk_timers::TimerId;
-use std::cell::Cell;
+use icrc_ledger_types::icrc1::account::Account;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::iter::zip;
 use std::time::Duration;
 use tracing::{error, trace};
-use types::{AccessGate, CanisterId, Chit, GroupRole, OptionUpdate, UserId};
+use types::{AccessGate, CanisterId, Chit, DiamondMembershipStatus, GroupRole, OptionUpdate, UserId};
 use utils::time::MonthKey;
 
 thread_local! {
     static TIMER_ID: Cell<Option<TimerId>> = Cell::default();
+    static IN_PROGRESS: RefCell<HashSet<u32>> = RefCell::default();
+}
+
+pub(crate) struct EligibleParticipants {
+    pub participants: Vec<(UserId, Chit)>,
+    pub ineligible: u32,
+}
+
+pub(crate) struct FetchParticipantsError {
+    pub message: String,
+    pub retry: bool,
 }
 
 pub(crate) fn start_job_if_required(state: &RuntimeState) -> bool {
@@ -20,9 +35,19 @@ pub(crate) fn start_job_if_required(state: &RuntimeState) -> bool {
 pub(crate) fn start_airdrop_timer(state: &RuntimeState) -> bool {
     clear_airdrop_timer();
 
-    if let Some(config) = state.data.airdrops.next() {
+    let next_start = IN_PROGRESS.with_borrow(|in_progress| {
+        state
+            .data
+            .airdrops
+            .iter_scheduled()
+            .filter(|(id, config)| !in_progress.contains(id) && is_channel_joined(config, state))
+            .map(|(_, config)| config.start)
+            .min()
+    });
+
+    if let Some(start) = next_start {
         // Start the airdrop now if the start date is in the past
-        let delay = config.start.saturating_sub(state.env.now());
+        let delay = start.saturating_sub(state.env.now());
         let timer_id = ic_cdk_timers::set_timer(Duration::from_millis(delay), run);
         TIMER_ID.set(Some(timer_id));
         true
@@ -37,21 +62,65 @@ pub(crate) fn clear_airdrop_timer() {
     }
 }
 
+pub(crate) fn on_airdrop_cancelled(id: u32, state: &RuntimeState) {
+    IN_PROGRESS.with_borrow_mut(|in_progress| in_progress.remove(&id));
+    start_airdrop_timer(state);
+}
+
+fn is_channel_joined(config: &AirdropConfig, state: &RuntimeState) -> bool {
+    state.data.channels_joined.contains(&(config.community_id, config.channel_id))
+}
+
 fn run() {
     trace!("'execute_airdrop' running");
     TIMER_ID.set(None);
 
+    let (due, user_index_canister_id) = read_state(|state| {
+        let now = state.env.now();
+        let due: Vec<_> = IN_PROGRESS.with_borrow(|in_progress| {
+            state
+                .data
+                .airdrops
+                .iter_scheduled()
+                .filter(|(id, config)| config.start <= now && !in_progress.contains(id) && is_channel_joined(config, state))
+                .map(|(id, config)| (id, config.clone()))
+                .collect()
+        });
+        (due, state.data.user_index_canister_id)
+    });
+
+    if due.is_empty() {
+        trace!("No airdrop due");
+    }
+
+    for (id, config) in due {
+        IN_PROGRESS.with_borrow_mut(|in_progress| in_progress.insert(id));
+        ic_cdk::futures::spawn(prepare_airdrop(id, config, user_index_canister_id));
+    }
+
+    read_state(start_airdrop_timer);
+}
+
+fn retry(id: u32) {
     let (config, user_index_canister_id) =
-        read_state(|state| (state.data.airdrops.next().cloned(), state.data.user_index_canister_id));
+        read_state(|state| (state.data.airdrops.scheduled(id).cloned(), state.data.user_index_canister_id));
 
     if let Some(config) = config {
-        ic_cdk::futures::spawn(prepare_airdrop(config, user_index_canister_id));
+        ic_cdk::futures::spawn(prepare_airdrop(id, config, user_index_canister_id));
     } else {
-        trace!("No airdrop configured");
-    };
+        IN_PROGRESS.with_borrow_mut(|in_progress| in_progress.remove(&id));
+    }
+}
+
+fn retry_in_a_minute(id: u32) {
+    ic_cdk_timers::set_timer(Duration::from_secs(60), move || retry(id));
+}
+
+fn retry_in_an_hour(id: u32) {
+    ic_cdk_timers::set_timer(Duration::from_secs(60 * 60), move || retry(id));
 }
 
-async fn prepare_airdrop(config: AirdropConfig, user_index_canister_id: CanisterId) {
+async fn prepare_airdrop(id: u32, config: AirdropConfig, user_index_canister_id: CanisterId) {
     // Call the configured community canister to set the `locked` gate on the configured channel
     match community_canister_c2c_client::update_channel(
         config.community_id.into(),
@@ -73,36 +142,103 @@ async fn prepare_airdrop(config: AirdropConfig, user_index_canister_id: Canister
     {
         Ok(community_canister::update_channel::Response::SuccessV2(_)) => (),
         Ok(resp) => {
-            error!(?resp, "Failed to set `locked` gate");
+            error!(id, ?resp, "Failed to set `locked` gate");
+            fail_airdrop(id, format!("Failed to set `locked` gate: {resp:?}"));
             return;
         }
         Err(err) => {
-            error!("{err:?}");
-            let timer_id = ic_cdk_timers::set_timer(Duration::from_millis(60_000), run);
-            TIMER_ID.set(Some(timer_id));
+            error!(id, "{err:?}");
+            retry_in_a_minute(id);
             return;
         }
     }
 
+    let eligible = match fetch_eligible_participants(&config, user_index_canister_id).await {
+        Ok(eligible) => eligible,
+        Err(error) => {
+            error!(id, "Failed to fetch airdrop participants: {}", error.message);
+            if error.retry {
+                retry_in_a_minute(id);
+            } else {
+                fail_airdrop(id, format!("Failed to fetch airdrop participants: {}", error.message));
+            }
+            return;
+        }
+    };
+
+    // Check the canister holds enough CHAT to fund this occurrence as well as any transfers still outstanding
+    let (chat_ledger_canister_id, this_canister_id) =
+        read_state(|state| (state.data.chat_ledger_canister_id, state.env.canister_id()));
+
+    let balance =
+        match icrc_ledger_canister_c2c_client::icrc1_balance_of(chat_ledger_canister_id, &Account::from(this_canister_id))
+            .await
+            .map(|b| u128::try_from(b.0).unwrap())
+        {
+            Ok(balance) => balance,
+            Err(err) => {
+                error!(id, "{err:?}");
+                retry_in_a_minute(id);
+                return;
+            }
+        };
+
+    let required = read_state(|state| {
+        state
+            .data
+            .airdrops
+            .funds_required(id, eligible.participants.len(), CHAT_TRANSFER_FEE)
+    });
+
+    match required {
+        Some(required) if balance < required => {
+            error!(id, ?balance, ?required, "Insufficient CHAT balance to fund airdrop");
+            retry_in_an_hour(id);
+        }
+        Some(_) => mutate_state(|state| execute_airdrop(id, eligible, state)),
+        None => {
+            // The airdrop was cancelled while its participants were being fetched
+            IN_PROGRESS.with_borrow_mut(|in_progress| in_progress.remove(&id));
+        }
+    }
+}
+
+// Records the occurrence as failed so that it is no longer considered in progress and the timer moves on to the next
+// scheduled airdrop
+fn fail_airdrop(id: u32, reason: String) {
+    mutate_state(|state| {
+        let now = state.env.now();
+        state.data.airdrops.fail(id, reason, now);
+        IN_PROGRESS.with_borrow_mut(|in_progress| in_progress.remove(&id));
+        start_airdrop_timer(state);
+    });
+}
+
+pub(crate) async fn fetch_eligible_participants(
+    config: &AirdropConfig,
+    user_index_canister_id: CanisterId,
+) -> Result<EligibleParticipants, FetchParticipantsError> {
     // Call the configured community canister to fetch the participants of the configured channel
-    let members = match community_canister_c2c_client::selected_channel_initial(
+    let members = match community_canister_c2c_client::c2c_channel_members(
         config.community_id.into(),
-        &community_canister::selected_channel_initial::Args {
+        &community_canister::c2c_channel_members::Args {
             channel_id: config.channel_id,
         },
     )
     .await
     {
-        Ok(community_canister::selected_channel_initial::Response::Success(success)) => success.members(),
+        Ok(community_canister::c2c_channel_members::Response::Success(members)) => members,
         Ok(resp) => {
-            error!(?resp, "Failed to get channel members");
-            return;
+            return Err(FetchParticipantsError {
+                message: format!("Failed to get channel members: {resp:?}"),
+                retry: false,
+            });
         }
         Err(err) => {
-            error!("{err:?}");
-            let timer_id = ic_cdk_timers::set_timer(Duration::from_secs(60), run);
-            TIMER_ID.set(Some(timer_id));
-            return;
+            return Err(FetchParticipantsError {
+                message: format!("{err:?}"),
+                retry: true,
+            });
         }
     };
 
@@ -110,17 +246,8 @@ async fn prepare_airdrop(config: AirdropConfig, user_index_canister_id: Canister
     let mk = MonthKey::from_timestamp(config.start).previous();
 
     // Exclude channel owners from the airdrop
-    let mut users: Vec<UserId> = members
-        .into_iter()
-        .filter(|m| !matches!(m.role, GroupRole::Owner))
-        .map(|m| m.user_id)
-        .collect();
-
-    if let AirdropAlgorithm::V2(v2) = &config.algorithm {
-        if v2.min_minutes_online > 0 {
-            read_state(|state| users.retain(|u| state.data.user_minutes_online.get(u, &mk) >= v2.min_minutes_online));
-        }
-    }
+    let members: Vec<_> = members.into_iter().filter(|m| !matches!(m.role, GroupRole::Owner)).collect();
+    let users: Vec<UserId> = members.iter().map(|m| m.user_id).collect();
 
     let chit = match user_index_canister_c2c_client::users_chit(
         user_index_canister_id,
@@ -134,23 +261,90 @@ async fn prepare_airdrop(config: AirdropConfig, user_index_canister_id: Canister
     {
         Ok(user_index_canister::users_chit::Response::Success(result)) => result.chit,
         Err(err) => {
-            error!("{err:?}");
-            let timer_id = ic_cdk_timers::set_timer(Duration::from_secs(60), run);
-            TIMER_ID.set(Some(timer_id));
-            return;
+            return Err(FetchParticipantsError {
+                message: format!("{err:?}"),
+                retry: true,
+            });
         }
     };
 
-    let participants = zip(users, chit).collect();
+    // Only look up the users' statuses if the airdrop's criteria depend on them
+    let statuses = if config
+        .criteria
+        .as_ref()
+        .is_some_and(|c| c.diamond_members_only || c.unique_persons_only)
+    {
+        fetch_user_statuses(users, user_index_canister_id).await?
+    } else {
+        HashMap::new()
+    };
+
+    let total = members.len() as u32;
+    let participants: Vec<_> = read_state(|state| {
+        zip(members, chit)
+            .map(|(member, chit)| {
+                let (is_diamond_member, is_unique_person) = statuses.get(&member.user_id).copied().unwrap_or_default();
+
+                Candidate {
+                    user_id: member.user_id,
+                    date_added: member.date_added,
+                    chit,
+                    minutes_online: state.data.user_minutes_online.get(&member.user_id, &mk),
+                    is_diamond_member,
+                    is_unique_person,
+                }
+            })
+            .filter(|candidate| is_eligible(config, candidate))
+            .map(|candidate| (candidate.user_id, candidate.chit))
+            .collect()
+    });
 
-    // Execute the airdrop
-    mutate_state(|state| execute_airdrop(participants, state));
+    Ok(EligibleParticipants {
+        ineligible: total - participants.len() as u32,
+        participants,
+    })
 }
 
-fn execute_airdrop(participants: Vec<(UserId, Chit)>, state: &mut RuntimeState) {
+async fn fetch_user_statuses(
+    users: Vec<UserId>,
+    user_index_canister_id: CanisterId,
+) -> Result<HashMap<UserId, (bool, bool)>, FetchParticipantsError> {
+    match user_index_canister_c2c_client::users(
+        user_index_canister_id,
+        &user_index_canister::users::Args {
+            user_groups: vec![user_index_canister::users::UserGroup { users, updated_since: 0 }],
+            users_suspended_since: None,
+        },
+    )
+    .await
+    {
+        Ok(user_index_canister::users::Response::Success(result)) => Ok(result
+            .users
+            .into_iter()
+            .filter_map(|u| {
+                u.stable.map(|s| {
+                    let is_diamond_member = !matches!(s.diamond_membership_status, DiamondMembershipStatus::Inactive);
+                    (u.user_id, (is_diamond_member, s.is_unique_person))
+                })
+            })
+            .collect()),
+        Err(err) => Err(FetchParticipantsError {
+            message: format!("{err:?}"),
+            retry: true,
+        }),
+    }
+}
+
+fn execute_airdrop(id: u32, eligible: EligibleParticipants, state: &mut RuntimeState) {
+    IN_PROGRESS.with_borrow_mut(|in_progress| in_progress.remove(&id));
+
     let rng = state.env.rng();
 
-    if let Some(airdrop) = state.data.airdrops.execute(participants, rng) {
+    if let Some(airdrop) = state
+        .data
+        .airdrops
+        .execute(id, eligible.participants, eligible.ineligible, rng)
+    {
         // Add the CHAT transfer actions to the queue. When each transfer has succeeded
         // the corresponding message action will be added to the queue.
 
@@ -164,6 +358,7 @@ fn execute_airdrop(participants: Vec<(UserId, Chit)>, state: &mut RuntimeState)
             if actions.len() % 500 == 0 {
                 if let Some((user_id, prize)) = lottery_winners.pop() {
                     actions.push(Action::Transfer(Box::new(AirdropTransfer {
+                        airdrop_id: Some(id),
                         recipient: user_id,
                         amount: prize.chat_won,
                         airdrop_type: AirdropType::Lottery(LotteryAirdrop {
@@ -175,6 +370,7 @@ fn execute_airdrop(participants: Vec<(UserId, Chit)>, state: &mut RuntimeState)
 
             if let Some(prize) = &participant.prize {
                 actions.push(Action::Transfer(Box::new(AirdropTransfer {
+                    airdrop_id: Some(id),
                     recipient: *user_id,
                     amount: prize.chat_won,
                     airdrop_type: AirdropType::Main(MainAirdrop {
@@ -187,6 +383,7 @@ fn execute_airdrop(participants: Vec<(UserId, Chit)>, state: &mut RuntimeState)
 
         while let Some((user_id, prize)) = lottery_winners.pop() {
             actions.push(Action::Transfer(Box::new(AirdropTransfer {
+                airdrop_id: Some(id),
                 recipient: user_id,
                 amount: prize.chat_won,
                 airdrop_type: AirdropType::Lottery(LotteryAirdrop {
@@ -197,4 +394,7 @@ fn execute_airdrop(participants: Vec<(UserId, Chit)>, state: &mut RuntimeState)
 
         state.data.pending_actions_queue.push_many(actions.into_iter());
     }
+
+    // If the airdrop is recurring, its next occurrence will now be scheduled
+    start_airdrop_timer(state);
 }
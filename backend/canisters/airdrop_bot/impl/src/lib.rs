@@ -1,12 +1,15 @@
 use crate::actions::Action;
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::user_minutes_online::UserMinutesOnline;
 use candid::Principal;
 use canister_state_macros::canister_state;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use model::airdrops::{Airdrops, AirdropsMetrics};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashSet};
-use timer_job_queues::TimerJobQueue;
+use timer_job_queues::{TimerJobQueue, TimerJobQueueMetrics};
 use types::{BuildVersion, CanisterId, ChannelId, CommunityId, Cycles, Document, TimestampMillis, Timestamped};
 use utils::env::Environment;
 use utils::idempotency_checker::IdempotencyChecker;
@@ -60,6 +63,11 @@ impl RuntimeState {
             pending_actions: self.data.pending_actions_queue.len(),
             channels_joined: self.data.channels_joined.iter().cloned().collect(),
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
+            timer_job_queues: BTreeMap::from([(
+                "pending_actions_queue".to_string(),
+                self.data.pending_actions_queue.metrics(),
+            )]),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 local_user_index: self.data.local_user_index_canister_id,
@@ -85,6 +93,12 @@ struct Data {
     pub idempotency_checker: IdempotencyChecker,
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -110,8 +124,18 @@ impl Data {
             idempotency_checker: IdempotencyChecker::default(),
             rng_seed: [0; 32],
             test_mode,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -122,11 +146,20 @@ pub struct Metrics {
     pub cycles_balance: Cycles,
     pub wasm_version: BuildVersion,
     pub git_commit_id: String,
+    pub timer_job_queues: BTreeMap<String, TimerJobQueueMetrics>,
     pub canister_ids: CanisterIds,
     pub airdrops: AirdropsMetrics,
     pub pending_actions: usize,
     pub channels_joined: Vec<(CommunityId, ChannelId)>,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
+}
+
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -1,10 +1,11 @@
-use crate::Data;
 use crate::lifecycle::{init_env, init_state};
-use crate::memory::get_upgrades_memory;
+use crate::memory::{get_canister_logs_memory, get_upgrades_memory};
+use crate::{Data, read_state};
 use airdrop_bot_canister::post_upgrade::Args;
 use canister_logger::LogEntry;
 use canister_tracing_macros::trace;
 use ic_cdk::post_upgrade;
+use instruction_counts_log::InstructionCountFunctionId;
 use stable_memory::get_reader;
 use tracing::info;
 
@@ -14,14 +15,23 @@ fn post_upgrade(args: Args) {
     let memory = get_upgrades_memory();
     let reader = get_reader(&memory);
 
-    let (data, errors, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>, Vec<LogEntry>) =
+    let (mut data, errors, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>, Vec<LogEntry>) =
         msgpack::deserialize(reader).unwrap();
 
-    canister_logger::init_with_logs(data.test_mode, errors, logs, traces);
+    canister_logger::init_with_logs(data.test_mode, get_canister_logs_memory(), errors, logs, traces);
+
+    data.airdrops.migrate_legacy_next();
 
     let env = init_env(data.rng_seed);
     init_state(env, data, args.wasm_version);
 
     let total_instructions = ic_cdk::api::call_context_instruction_counter();
     info!(version = %args.wasm_version, total_instructions, "Post-upgrade complete");
+
+    read_state(|state| {
+        let now = state.env.now();
+        state
+            .data
+            .record_instructions_count(InstructionCountFunctionId::PostUpgrade, now)
+    });
 }
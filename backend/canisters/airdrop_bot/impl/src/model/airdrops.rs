@@ -1,18 +1,46 @@
-use airdrop_bot_canister::{AirdropAlgorithm, AirdropConfig, V1Algorithm, V2Algorithm};
+use airdrop_bot_canister::{
+    AirdropAlgorithm, AirdropConfig, AirdropReport, LotteryWinnerReport, ParticipantReport, V1Algorithm, V2Algorithm,
+};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
 use types::{Chit, TimestampMillis, UserId};
 use utils::time::MonthKey;
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct Airdrops {
     past: Vec<Airdrop>,
+    #[serde(default)]
+    scheduled: BTreeMap<u32, AirdropConfig>,
+    // Only populated by versions prior to multiple airdrops being supported, see `migrate_legacy_next`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     next: Option<AirdropConfig>,
+    #[serde(default)]
+    next_id: u32,
+    // Maps each scheduled follow-up occurrence of a recurring airdrop to the id of its first occurrence
+    #[serde(default)]
+    series: BTreeMap<u32, u32>,
+    // Occurrences which could not be executed, eg. because the channel could not be locked
+    #[serde(default)]
+    failed: Vec<FailedAirdrop>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FailedAirdrop {
+    pub id: u32,
+    pub series_id: Option<u32>,
+    pub config: AirdropConfig,
+    pub reason: String,
+    pub failed_at: TimestampMillis,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Airdrop {
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series_id: Option<u32>,
     pub config: AirdropConfig,
     pub outcome: AirdropOutcome,
 }
@@ -21,6 +49,8 @@ pub struct Airdrop {
 pub struct AirdropOutcome {
     pub participants: HashMap<UserId, Participant>,
     pub lottery_winners: Vec<(UserId, Prize)>,
+    #[serde(default)]
+    pub ineligible: u32,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -36,21 +66,33 @@ pub struct Prize {
     pub block_index: Option<u64>,
 }
 
-pub enum SetNextResult {
-    Success,
+pub struct Candidate {
+    pub user_id: UserId,
+    pub date_added: TimestampMillis,
+    pub chit: Chit,
+    pub minutes_online: u16,
+    pub is_diamond_member: bool,
+    pub is_unique_person: bool,
+}
+
+pub enum ScheduleResult {
+    Success(u32),
     ChannelUsed,
     InThePast,
     ClashesWithPrevious,
+    InvalidRecurrence,
 }
 
 #[derive(Serialize, Debug)]
 pub struct AirdropsMetrics {
     past: Vec<AirdropMetrics>,
-    next: Option<AirdropConfig>,
+    scheduled: Vec<(u32, AirdropConfig)>,
+    failed: Vec<FailedAirdrop>,
 }
 
 #[derive(Serialize, Debug)]
 pub struct AirdropMetrics {
+    pub id: u32,
     pub config: AirdropConfig,
     pub outcome: AirdropOutcomeMetrics,
 }
@@ -58,13 +100,18 @@ pub struct AirdropMetrics {
 #[derive(Serialize, Debug)]
 pub struct AirdropOutcomeMetrics {
     pub participants: u32,
+    pub ineligible: u32,
     pub lottery_winners: Vec<(UserId, Prize)>,
 }
 
 impl Airdrops {
-    pub fn set_next(&mut self, config: AirdropConfig, now: TimestampMillis) -> SetNextResult {
+    pub fn schedule(&mut self, config: AirdropConfig, now: TimestampMillis) -> ScheduleResult {
         if config.start < now {
-            return SetNextResult::InThePast;
+            return ScheduleResult::InThePast;
+        }
+
+        if config.recurrence.as_ref().is_some_and(|r| r.interval_months == 0) {
+            return ScheduleResult::InvalidRecurrence;
         }
 
         if self
@@ -72,40 +119,123 @@ impl Airdrops {
             .iter()
             .any(|a| a.config.community_id == config.community_id && a.config.channel_id == config.channel_id)
         {
-            return SetNextResult::ChannelUsed;
+            return ScheduleResult::ChannelUsed;
         }
 
-        if let Some(previous) = self.past.last() {
-            if MonthKey::from_timestamp(previous.config.start) == MonthKey::from_timestamp(config.start) {
-                return SetNextResult::ClashesWithPrevious;
-            }
+        let month = MonthKey::from_timestamp(config.start);
+        if self.scheduled.values().any(|c| {
+            c.community_id == config.community_id
+                && c.channel_id == config.channel_id
+                && MonthKey::from_timestamp(c.start) == month
+        }) {
+            return ScheduleResult::ClashesWithPrevious;
         }
 
-        self.next = Some(config);
+        ScheduleResult::Success(self.push_scheduled(config))
+    }
+
+    pub fn cancel(&mut self, id: u32) -> Option<AirdropConfig> {
+        self.series.remove(&id);
+        self.scheduled.remove(&id)
+    }
+
+    pub fn cancel_all(&mut self) -> Vec<u32> {
+        let ids = self.scheduled.keys().copied().collect();
+        self.scheduled.clear();
+        self.series.clear();
+        ids
+    }
 
-        SetNextResult::Success
+    // The CHAT balance needed to execute the given airdrop, including a transfer fee for each potential recipient,
+    // on top of whatever is still owed by previously executed airdrops
+    pub fn funds_required(&self, id: u32, eligible_participants: usize, transfer_fee: u128) -> Option<u128> {
+        let config = self.scheduled.get(&id)?;
+        let prizes = match &config.algorithm {
+            AirdropAlgorithm::V1(c) => c.lottery_prizes.len(),
+            AirdropAlgorithm::V2(c) => c.lottery_prizes.len(),
+        };
+        let fees = transfer_fee.saturating_mul((eligible_participants + prizes) as u128);
+
+        Some(
+            budget(&config.algorithm)
+                .saturating_add(fees)
+                .saturating_add(self.outstanding(transfer_fee)),
+        )
     }
 
-    pub fn cancel(&mut self) -> Option<AirdropConfig> {
-        self.next.take()
+    // The sum of the prizes (plus fees) which have been allocated but not yet transferred
+    fn outstanding(&self, transfer_fee: u128) -> u128 {
+        self.past
+            .iter()
+            .flat_map(|a| {
+                a.outcome
+                    .participants
+                    .values()
+                    .filter_map(|p| p.prize.as_ref())
+                    .chain(a.outcome.lottery_winners.iter().map(|(_, p)| p))
+            })
+            .filter(|p| p.block_index.is_none())
+            .map(|p| p.chat_won + transfer_fee)
+            .sum()
     }
 
-    pub fn execute<R: RngCore>(&mut self, users: Vec<(UserId, Chit)>, rng: &mut R) -> Option<&Airdrop> {
-        let config = self.next.take()?;
+    pub fn execute<R: RngCore>(
+        &mut self,
+        id: u32,
+        users: Vec<(UserId, Chit)>,
+        ineligible: u32,
+        rng: &mut R,
+    ) -> Option<&Airdrop> {
+        let config = self.scheduled.remove(&id)?;
+        let series_id = self.series.remove(&id);
+        self.schedule_next_occurrence(&config, series_id.unwrap_or(id));
+
+        let mut outcome = Airdrops::compute_outcome(&config, users, rng)?;
+        outcome.ineligible = ineligible;
+
+        self.past.push(Airdrop {
+            id,
+            series_id,
+            config,
+            outcome,
+        });
+
+        self.past.last()
+    }
 
-        let outcome = match &config.algorithm {
+    // Records that the occurrence could not be executed. If it is part of a recurring series, the next occurrence is
+    // still scheduled.
+    pub fn fail(&mut self, id: u32, reason: String, now: TimestampMillis) -> bool {
+        let Some(config) = self.scheduled.remove(&id) else {
+            return false;
+        };
+        let series_id = self.series.remove(&id);
+        self.schedule_next_occurrence(&config, series_id.unwrap_or(id));
+
+        self.failed.push(FailedAirdrop {
+            id,
+            series_id,
+            config,
+            reason,
+            failed_at: now,
+        });
+        true
+    }
+
+    pub fn compute_outcome<R: RngCore>(
+        config: &AirdropConfig,
+        users: Vec<(UserId, Chit)>,
+        rng: &mut R,
+    ) -> Option<AirdropOutcome> {
+        match &config.algorithm {
             AirdropAlgorithm::V1(c) => Airdrops::execute_v1(c.clone(), users, rng),
             AirdropAlgorithm::V2(c) => Airdrops::execute_v2(c.clone(), users, rng),
-        }?;
-
-        self.past.push(Airdrop { config, outcome });
-
-        Some(self.past.last().as_ref().unwrap())
+        }
     }
 
-    pub fn set_main_transaction(&mut self, user_id: &UserId, block_index: u64) -> bool {
-        if let Some(last) = self.past.last_mut() {
-            if let Some(participant) = last.outcome.participants.get_mut(user_id) {
+    pub fn set_main_transaction(&mut self, airdrop_id: Option<u32>, user_id: &UserId, block_index: u64) -> bool {
+        if let Some(airdrop) = self.get_mut(airdrop_id) {
+            if let Some(participant) = airdrop.outcome.participants.get_mut(user_id) {
                 if let Some(prize) = &mut participant.prize {
                     if prize.block_index.is_none() {
                         prize.block_index = Some(block_index);
@@ -118,9 +248,9 @@ impl Airdrops {
         false
     }
 
-    pub fn set_lottery_transaction(&mut self, winning_index: usize, block_index: u64) -> bool {
-        if let Some(last) = self.past.last_mut() {
-            if let Some((_, prize)) = last.outcome.lottery_winners.get_mut(winning_index) {
+    pub fn set_lottery_transaction(&mut self, airdrop_id: Option<u32>, winning_index: usize, block_index: u64) -> bool {
+        if let Some(airdrop) = self.get_mut(airdrop_id) {
+            if let Some((_, prize)) = airdrop.outcome.lottery_winners.get_mut(winning_index) {
                 if prize.block_index.is_none() {
                     prize.block_index = Some(block_index);
                     return true;
@@ -131,14 +261,44 @@ impl Airdrops {
         false
     }
 
-    pub fn current(&self, now: TimestampMillis) -> Option<&AirdropConfig> {
-        if let Some(last) = self.past.last() {
-            if MonthKey::from_timestamp(now) == MonthKey::from_timestamp(last.config.start) {
-                return Some(&last.config);
+    // Actions queued before airdrops had ids don't specify one, in which case they relate to the latest airdrop
+    pub fn get(&self, airdrop_id: Option<u32>) -> Option<&Airdrop> {
+        match airdrop_id {
+            Some(id) => self.past.iter().rev().find(|a| a.id == id),
+            None => self.past.last(),
+        }
+    }
+
+    fn get_mut(&mut self, airdrop_id: Option<u32>) -> Option<&mut Airdrop> {
+        match airdrop_id {
+            Some(id) => self.past.iter_mut().rev().find(|a| a.id == id),
+            None => self.past.last_mut(),
+        }
+    }
+
+    pub fn scheduled(&self, id: u32) -> Option<&AirdropConfig> {
+        self.scheduled.get(&id)
+    }
+
+    pub fn iter_scheduled(&self) -> impl Iterator<Item = (u32, &AirdropConfig)> {
+        self.scheduled.iter().map(|(id, config)| (*id, config))
+    }
+
+    pub fn report(&self, id: u32) -> Option<AirdropReport> {
+        self.get(Some(id)).map(|a| build_report(a.id, &a.config, &a.outcome))
+    }
+
+    pub fn migrate_legacy_next(&mut self) {
+        if self.next_id == 0 {
+            for (index, airdrop) in self.past.iter_mut().enumerate() {
+                airdrop.id = index as u32;
             }
+            self.next_id = self.past.len() as u32;
         }
 
-        None
+        if let Some(config) = self.next.take() {
+            self.push_scheduled(config);
+        }
     }
 
     pub fn metrics(&self) -> AirdropsMetrics {
@@ -147,19 +307,43 @@ impl Airdrops {
                 .past
                 .iter()
                 .map(|a| AirdropMetrics {
+                    id: a.id,
                     config: a.config.clone(),
                     outcome: AirdropOutcomeMetrics {
                         participants: a.outcome.participants.len() as u32,
+                        ineligible: a.outcome.ineligible,
                         lottery_winners: a.outcome.lottery_winners.clone(),
                     },
                 })
                 .collect(),
-            next: self.next.clone(),
+            scheduled: self.scheduled.iter().map(|(id, c)| (*id, c.clone())).collect(),
+            failed: self.failed.clone(),
+        }
+    }
+
+    // Only schedule the next occurrence if no other airdrop has used the channel in the meantime
+    fn schedule_next_occurrence(&mut self, config: &AirdropConfig, series_id: u32) {
+        if !self.channel_used_by_other_series(config, series_id) {
+            if let Some(next) = next_occurrence(config) {
+                let next_id = self.push_scheduled(next);
+                self.series.insert(next_id, series_id);
+            }
         }
     }
 
-    pub fn next(&self) -> Option<&AirdropConfig> {
-        self.next.as_ref()
+    fn channel_used_by_other_series(&self, config: &AirdropConfig, series_id: u32) -> bool {
+        self.past.iter().any(|a| {
+            a.config.community_id == config.community_id
+                && a.config.channel_id == config.channel_id
+                && a.series_id.unwrap_or(a.id) != series_id
+        })
+    }
+
+    fn push_scheduled(&mut self, config: AirdropConfig) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.scheduled.insert(id, config);
+        id
     }
 
     fn execute_v1<R: RngCore>(config: V1Algorithm, users: Vec<(UserId, Chit)>, rng: &mut R) -> Option<AirdropOutcome> {
@@ -184,6 +368,7 @@ impl Airdrops {
         Some(AirdropOutcome {
             participants,
             lottery_winners,
+            ineligible: 0,
         })
     }
 
@@ -205,6 +390,7 @@ impl Airdrops {
         Some(AirdropOutcome {
             participants,
             lottery_winners,
+            ineligible: 0,
         })
     }
 
@@ -279,8 +465,118 @@ impl Airdrops {
     }
 }
 
+pub fn is_eligible(config: &AirdropConfig, candidate: &Candidate) -> bool {
+    if let AirdropAlgorithm::V2(v2) = &config.algorithm {
+        if candidate.minutes_online < v2.min_minutes_online {
+            return false;
+        }
+    }
+
+    let Some(criteria) = &config.criteria else {
+        return true;
+    };
+
+    criteria.min_chit.is_none_or(|min| candidate.chit.balance >= min as i32)
+        && criteria.min_streak.is_none_or(|min| candidate.chit.streak >= min)
+        && (!criteria.diamond_members_only || candidate.is_diamond_member)
+        && (!criteria.unique_persons_only || candidate.is_unique_person)
+        && criteria
+            .min_channel_membership
+            .is_none_or(|min| candidate.date_added.saturating_add(min) <= config.start)
+}
+
+pub fn build_report(id: u32, config: &AirdropConfig, outcome: &AirdropOutcome) -> AirdropReport {
+    let mut participants: Vec<_> = outcome
+        .participants
+        .iter()
+        .map(|(user_id, p)| ParticipantReport {
+            user_id: *user_id,
+            chit: p.chit,
+            shares: p.shares,
+            amount: p.prize.as_ref().map(|p| p.chat_won).unwrap_or_default(),
+            block_index: p.prize.as_ref().and_then(|p| p.block_index),
+        })
+        .collect();
+
+    participants.sort_unstable_by_key(|p| (Reverse(p.amount), p.user_id));
+
+    let lottery_winners: Vec<_> = outcome
+        .lottery_winners
+        .iter()
+        .enumerate()
+        .map(|(index, (user_id, prize))| LotteryWinnerReport {
+            user_id: *user_id,
+            position: index as u32 + 1,
+            amount: prize.chat_won,
+            block_index: prize.block_index,
+        })
+        .collect();
+
+    let amounts = participants
+        .iter()
+        .map(|p| (p.amount, p.block_index))
+        .chain(lottery_winners.iter().map(|w| (w.amount, w.block_index)));
+
+    let mut total_allocated = 0;
+    let mut total_transferred = 0;
+    for (amount, block_index) in amounts {
+        total_allocated += amount;
+        if block_index.is_some() {
+            total_transferred += amount;
+        }
+    }
+
+    AirdropReport {
+        id,
+        config: config.clone(),
+        budget: budget(&config.algorithm),
+        total_allocated,
+        total_transferred,
+        ineligible: outcome.ineligible,
+        participants,
+        lottery_winners,
+    }
+}
+
+fn budget(algorithm: &AirdropAlgorithm) -> u128 {
+    match algorithm {
+        AirdropAlgorithm::V1(c) => c.main_chat_fund + c.lottery_prizes.iter().sum::<u128>(),
+        AirdropAlgorithm::V2(c) => c.main_chat_fund + c.lottery_prizes.iter().sum::<u128>(),
+    }
+}
+
+// The next occurrence starts at the same offset into its month as the current one, capped at the end of that month
+fn next_occurrence(config: &AirdropConfig) -> Option<AirdropConfig> {
+    let recurrence = config.recurrence.as_ref()?;
+
+    let remaining_occurrences = match recurrence.remaining_occurrences {
+        Some(0) => return None,
+        Some(n) => Some(n - 1),
+        None => None,
+    };
+
+    let month = MonthKey::from_timestamp(config.start);
+    let offset = config.start - month.start_timestamp();
+
+    let mut next_month = month;
+    for _ in 0..recurrence.interval_months {
+        next_month = next_month.next();
+    }
+
+    let start = (next_month.start_timestamp() + offset).min(next_month.next().start_timestamp() - 1);
+
+    let mut next = config.clone();
+    next.start = start;
+    if let Some(r) = next.recurrence.as_mut() {
+        r.remaining_occurrences = remaining_occurrences;
+    }
+    Some(next)
+}
+
 #[cfg(test)]
 mod tests {
+    use airdrop_bot_canister::{AirdropCriteria, AirdropRecurrence};
+    use constants::DAY_IN_MS;
     use testing::rng::random_principal;
     use utils::env::{Environment, test::TestEnv};
 
@@ -289,10 +585,13 @@ mod tests {
     #[test]
     fn execute_v1_airdrop_expected() {
         let mut env = TestEnv::default();
-        let mut airdrops = setup(env.now);
+        let mut airdrops = Airdrops::default();
+        let ScheduleResult::Success(id) = airdrops.schedule(test_config(env.now), env.now) else {
+            panic!("Expected airdrop to be scheduled");
+        };
         let users = generate_random_users();
 
-        let airdrop = airdrops.execute(users, env.rng()).expect("Expected some airdrop");
+        let airdrop = airdrops.execute(id, users, 0, env.rng()).expect("Expected some airdrop");
 
         println!("{:#?}", airdrop.outcome);
 
@@ -308,25 +607,204 @@ mod tests {
         )
     }
 
-    fn setup(now: TimestampMillis) -> Airdrops {
+    #[test]
+    fn concurrent_airdrops_executed_independently() {
+        let mut env = TestEnv::default();
         let mut airdrops = Airdrops::default();
+        let ScheduleResult::Success(id1) = airdrops.schedule(test_config(env.now), env.now) else {
+            panic!();
+        };
+        let ScheduleResult::Success(id2) = airdrops.schedule(test_config(env.now), env.now) else {
+            panic!();
+        };
+
+        airdrops.execute(id2, generate_random_users(), 5, env.rng()).unwrap();
+
+        assert!(airdrops.scheduled(id1).is_some());
+        assert!(airdrops.scheduled(id2).is_none());
+        assert_eq!(airdrops.report(id2).unwrap().ineligible, 5);
+        assert!(airdrops.report(id1).is_none());
+    }
 
-        airdrops.set_next(
-            AirdropConfig {
-                community_id: random_principal().into(),
-                channel_id: 1u32.into(),
-                start: now + 1_000,
-                algorithm: AirdropAlgorithm::V1(V1Algorithm {
-                    main_chat_fund: 80_000,
-                    main_chit_band: 10_000,
-                    lottery_prizes: vec![12_000, 5_000, 3_000],
-                    lottery_chit_band: 50_000,
-                }),
-            },
-            now,
+    #[test]
+    fn recurring_airdrop_schedules_next_occurrence() {
+        let mut env = TestEnv::default();
+        let mut airdrops = Airdrops::default();
+        // 2024-01-31 12:00
+        let start = 1706702400000;
+        let mut config = test_config(start);
+        config.start = start;
+        config.recurrence = Some(AirdropRecurrence {
+            interval_months: 1,
+            remaining_occurrences: Some(1),
+        });
+        let ScheduleResult::Success(id) = airdrops.schedule(config, start - 1) else {
+            panic!();
+        };
+
+        airdrops.execute(id, generate_random_users(), 0, env.rng());
+        let (next_id, next) = airdrops.iter_scheduled().next().map(|(id, c)| (id, c.clone())).unwrap();
+
+        // February 2024 has no 31st, so the next occurrence is capped at the end of the month
+        assert_eq!(
+            next.start,
+            MonthKey::from_timestamp(start).next().next().start_timestamp() - 1
         );
+        assert_eq!(next.recurrence.as_ref().unwrap().remaining_occurrences, Some(0));
 
-        airdrops
+        airdrops.execute(next_id, generate_random_users(), 0, env.rng());
+        assert_eq!(airdrops.iter_scheduled().count(), 0);
+    }
+
+    #[test]
+    fn failed_occurrence_recorded_and_next_occurrence_scheduled() {
+        let now = 1706702400000;
+        let mut airdrops = Airdrops::default();
+        let mut config = test_config(now);
+        config.start = now;
+        config.recurrence = Some(AirdropRecurrence {
+            interval_months: 1,
+            remaining_occurrences: None,
+        });
+        let ScheduleResult::Success(id) = airdrops.schedule(config, now - 1) else {
+            panic!();
+        };
+
+        assert!(airdrops.fail(id, "Failed to set `locked` gate".to_string(), now));
+        assert!(!airdrops.fail(id, "Failed again".to_string(), now));
+
+        assert!(airdrops.scheduled(id).is_none());
+        assert_eq!(airdrops.failed.len(), 1);
+        assert_eq!(airdrops.failed[0].id, id);
+
+        let (next_id, _) = airdrops.iter_scheduled().next().unwrap();
+        assert_eq!(airdrops.series.get(&next_id), Some(&id));
+    }
+
+    #[test]
+    fn eligibility_criteria_applied() {
+        let now = 100 * DAY_IN_MS;
+        let mut config = test_config(now);
+        config.criteria = Some(AirdropCriteria {
+            min_chit: Some(1_000),
+            min_streak: None,
+            diamond_members_only: true,
+            unique_persons_only: false,
+            min_channel_membership: Some(30 * DAY_IN_MS),
+        });
+
+        let mut candidate = Candidate {
+            user_id: random_principal().into(),
+            date_added: now - 60 * DAY_IN_MS,
+            chit: Chit {
+                balance: 2_000,
+                streak: 0,
+            },
+            minutes_online: 0,
+            is_diamond_member: true,
+            is_unique_person: false,
+        };
+        assert!(is_eligible(&config, &candidate));
+
+        candidate.date_added = now - DAY_IN_MS;
+        assert!(!is_eligible(&config, &candidate));
+
+        candidate.date_added = 0;
+        candidate.is_diamond_member = false;
+        assert!(!is_eligible(&config, &candidate));
+
+        candidate.is_diamond_member = true;
+        candidate.chit.balance = 999;
+        assert!(!is_eligible(&config, &candidate));
+    }
+
+    #[test]
+    fn report_totals_expected() {
+        let mut env = TestEnv::default();
+        let mut airdrops = Airdrops::default();
+        let ScheduleResult::Success(id) = airdrops.schedule(test_config(env.now), env.now) else {
+            panic!();
+        };
+        let airdrop = airdrops.execute(id, generate_random_users(), 0, env.rng()).unwrap();
+        let (winner, _) = airdrop.outcome.lottery_winners[0];
+
+        airdrops.set_lottery_transaction(Some(id), 0, 1);
+
+        let report = airdrops.report(id).unwrap();
+        assert_eq!(report.budget, 100_000);
+        assert!(report.total_allocated <= report.budget);
+        assert_eq!(report.total_transferred, 12_000);
+        assert_eq!(report.lottery_winners[0].user_id, winner);
+        assert_eq!(report.lottery_winners[0].position, 1);
+    }
+
+    #[test]
+    fn recurring_airdrop_stops_if_channel_used_by_another_airdrop() {
+        let mut env = TestEnv::default();
+        let mut airdrops = Airdrops::default();
+        let mut recurring = test_config(env.now);
+        recurring.recurrence = Some(AirdropRecurrence {
+            interval_months: 1,
+            remaining_occurrences: None,
+        });
+        let mut other = recurring.clone();
+        other.start += 40 * DAY_IN_MS;
+        other.recurrence = None;
+
+        let ScheduleResult::Success(recurring_id) = airdrops.schedule(recurring, env.now) else {
+            panic!();
+        };
+        let ScheduleResult::Success(other_id) = airdrops.schedule(other, env.now) else {
+            panic!();
+        };
+
+        airdrops.execute(other_id, generate_random_users(), 0, env.rng());
+        airdrops.execute(recurring_id, generate_random_users(), 0, env.rng());
+
+        assert_eq!(airdrops.iter_scheduled().count(), 0);
+    }
+
+    #[test]
+    fn funds_required_includes_fees_and_outstanding_transfers() {
+        let mut env = TestEnv::default();
+        let mut airdrops = Airdrops::default();
+        let ScheduleResult::Success(id1) = airdrops.schedule(test_config(env.now), env.now) else {
+            panic!();
+        };
+        let ScheduleResult::Success(id2) = airdrops.schedule(test_config(env.now), env.now) else {
+            panic!();
+        };
+
+        // Budget of 100,000 plus a fee for each of the 10 participants and 3 lottery winners
+        assert_eq!(airdrops.funds_required(id1, 10, 100), Some(101_300));
+
+        let airdrop = airdrops.execute(id1, generate_random_users(), 0, env.rng()).unwrap();
+        let recipients = airdrop.outcome.participants.values().filter(|p| p.prize.is_some()).count() as u128
+            + airdrop.outcome.lottery_winners.len() as u128;
+        let allocated = airdrops.report(id1).unwrap().total_allocated;
+        let outstanding = allocated + recipients * 100;
+
+        assert_eq!(airdrops.funds_required(id2, 10, 100), Some(101_300 + outstanding));
+
+        airdrops.set_lottery_transaction(Some(id1), 0, 1);
+        assert_eq!(airdrops.funds_required(id2, 10, 100), Some(101_300 + outstanding - 12_100));
+        assert_eq!(airdrops.funds_required(id1, 10, 100), None);
+    }
+
+    fn test_config(now: TimestampMillis) -> AirdropConfig {
+        AirdropConfig {
+            community_id: random_principal().into(),
+            channel_id: 1u32.into(),
+            start: now + 1_000,
+            algorithm: AirdropAlgorithm::V1(V1Algorithm {
+                main_chat_fund: 80_000,
+                main_chit_band: 10_000,
+                lottery_prizes: vec![12_000, 5_000, 3_000],
+                lottery_chit_band: 50_000,
+            }),
+            criteria: None,
+            recurrence: None,
+        }
     }
 
     fn generate_random_users() -> Vec<(UserId, Chit)> {
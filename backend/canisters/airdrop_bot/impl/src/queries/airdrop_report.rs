@@ -0,0 +1,12 @@
+use crate::guards::caller_is_admin;
+use crate::read_state;
+use airdrop_bot_canister::airdrop_report::{Response::*, *};
+use ic_cdk::query;
+
+#[query(guard = "caller_is_admin")]
+fn airdrop_report(args: Args) -> Response {
+    match read_state(|state| state.data.airdrops.report(args.id)) {
+        Some(report) => Success(report),
+        None => NotFound,
+    }
+}
@@ -1 +1,3 @@
+mod airdrop_report;
 mod http_request;
+mod scheduled_airdrops;
@@ -0,0 +1,19 @@
+use crate::guards::caller_is_admin;
+use crate::read_state;
+use airdrop_bot_canister::scheduled_airdrops::{Response::*, *};
+use ic_cdk::query;
+
+#[query(guard = "caller_is_admin")]
+fn scheduled_airdrops(_args: Args) -> Response {
+    Success(read_state(|state| {
+        state
+            .data
+            .airdrops
+            .iter_scheduled()
+            .map(|(id, config)| ScheduledAirdrop {
+                id,
+                config: config.clone(),
+            })
+            .collect()
+    }))
+}
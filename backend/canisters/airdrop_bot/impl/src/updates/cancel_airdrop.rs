@@ -1,5 +1,5 @@
 use crate::guards::caller_is_admin;
-use crate::jobs::execute_airdrop::clear_airdrop_timer;
+use crate::jobs::execute_airdrop::on_airdrop_cancelled;
 use crate::{RuntimeState, mutate_state};
 use airdrop_bot_canister::cancel_airdrop::*;
 use canister_tracing_macros::trace;
@@ -7,13 +7,23 @@ use ic_cdk::update;
 
 #[update(guard = "caller_is_admin")]
 #[trace]
-fn cancel_airdrop(_args: Args) -> Response {
-    mutate_state(cancel_airdrop_impl)
+fn cancel_airdrop(args: Args) -> Response {
+    mutate_state(|state| cancel_airdrop_impl(args, state))
 }
 
-fn cancel_airdrop_impl(state: &mut RuntimeState) -> Response {
-    if state.data.airdrops.cancel().is_some() {
-        clear_airdrop_timer();
+fn cancel_airdrop_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let cancelled = match args.id {
+        Some(id) => {
+            if state.data.airdrops.cancel(id).is_none() {
+                return Response::NotFound;
+            }
+            vec![id]
+        }
+        None => state.data.airdrops.cancel_all(),
+    };
+
+    for id in cancelled {
+        on_airdrop_cancelled(id, state);
     }
 
     Response::Success
@@ -0,0 +1,42 @@
+use crate::guards::caller_is_admin;
+use crate::jobs::execute_airdrop::fetch_eligible_participants;
+use crate::model::airdrops::{Airdrops, build_report};
+use crate::{mutate_state, read_state};
+use airdrop_bot_canister::dry_run_airdrop::{Response::*, *};
+use canister_tracing_macros::trace;
+use ic_cdk::update;
+
+// This is an update rather than a query because it needs to call the community and user_index canisters, which may
+// be on other subnets. No state is changed.
+#[update(guard = "caller_is_admin")]
+#[trace]
+async fn dry_run_airdrop(args: Args) -> Response {
+    let Some((config, user_index_canister_id)) = read_state(|state| {
+        state
+            .data
+            .airdrops
+            .scheduled(args.id)
+            .map(|c| (c.clone(), state.data.user_index_canister_id))
+    }) else {
+        return NotFound;
+    };
+
+    let eligible = match fetch_eligible_participants(&config, user_index_canister_id).await {
+        Ok(eligible) => eligible,
+        Err(error) => return InternalError(error.message),
+    };
+
+    // The lottery winners are drawn using a clone of the rng so that the dry run doesn't affect the real airdrop
+    let outcome = mutate_state(|state| {
+        let mut rng = state.env.rng().clone();
+        Airdrops::compute_outcome(&config, eligible.participants, &mut rng)
+    });
+
+    match outcome {
+        Some(mut outcome) => {
+            outcome.ineligible = eligible.ineligible;
+            Success(build_report(args.id, &config, &outcome))
+        }
+        None => NoEligibleParticipants,
+    }
+}
@@ -1,5 +1,6 @@
 pub mod c2c_online_users;
 pub mod cancel_airdrop;
+pub mod dry_run_airdrop;
 pub mod handle_direct_message;
 pub mod set_airdrop;
 pub mod set_avatar;
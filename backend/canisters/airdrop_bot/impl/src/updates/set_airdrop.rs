@@ -1,7 +1,7 @@
 use crate::actions::Action;
 use crate::guards::caller_is_admin;
 use crate::jobs::execute_airdrop::start_airdrop_timer;
-use crate::model::airdrops::SetNextResult;
+use crate::model::airdrops::ScheduleResult;
 use crate::{RuntimeState, mutate_state};
 use airdrop_bot_canister::set_airdrop::*;
 use canister_tracing_macros::trace;
@@ -17,8 +17,8 @@ fn set_airdrop_impl(args: Args, state: &mut RuntimeState) -> Response {
     let community_id = args.community_id;
     let channel_id = args.channel_id;
 
-    match state.data.airdrops.set_next(args, state.env.now()) {
-        SetNextResult::Success => {
+    match state.data.airdrops.schedule(args, state.env.now()) {
+        ScheduleResult::Success(id) => {
             if state.data.channels_joined.contains(&(community_id, channel_id)) {
                 start_airdrop_timer(state);
             } else {
@@ -27,10 +27,11 @@ fn set_airdrop_impl(args: Args, state: &mut RuntimeState) -> Response {
                     .pending_actions_queue
                     .push(Action::JoinChannel(community_id, channel_id));
             }
-            Response::Success
+            Response::Success(id)
         }
-        SetNextResult::ChannelUsed => Response::ChannelUsed,
-        SetNextResult::InThePast => Response::InThePast,
-        SetNextResult::ClashesWithPrevious => Response::ClashesWithPrevious,
+        ScheduleResult::ChannelUsed => Response::ChannelUsed,
+        ScheduleResult::InThePast => Response::InThePast,
+        ScheduleResult::ClashesWithPrevious => Response::ClashesWithPrevious,
+        ScheduleResult::InvalidRecurrence => Response::InvalidRecurrence,
     }
 }
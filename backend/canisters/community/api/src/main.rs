@@ -8,8 +8,12 @@ fn main() {
     }
 
     generate_ts_method!(community, active_proposal_tallies);
+    generate_ts_method!(community, audit_log);
+    generate_ts_method!(community, channel_automod_details);
+    generate_ts_method!(community, channel_encryption_key_epochs);
     generate_ts_method!(community, channel_summary_updates);
     generate_ts_method!(community, channel_summary);
+    generate_ts_method!(community, chit_leaderboard);
     generate_ts_method!(community, deleted_message);
     generate_ts_method!(community, events_by_index);
     generate_ts_method!(community, events_window);
@@ -19,6 +23,7 @@ fn main() {
     generate_ts_method!(community, local_user_index);
     generate_ts_method!(community, lookup_members);
     generate_ts_method!(community, messages_by_message_index);
+    generate_ts_method!(community, moderation_queue);
     generate_ts_method!(community, search_channel);
     generate_ts_method!(community, selected_channel_initial);
     generate_ts_method!(community, selected_channel_updates_v2);
@@ -48,6 +53,7 @@ fn main() {
     generate_ts_method!(community, delete_webhook);
     generate_ts_method!(community, disable_invite_code);
     generate_ts_method!(community, edit_message);
+    generate_ts_method!(community, enable_channel_encryption);
     generate_ts_method!(community, enable_invite_code);
     generate_ts_method!(community, follow_thread);
     generate_ts_method!(community, import_group);
@@ -64,9 +70,14 @@ fn main() {
     generate_ts_method!(community, remove_reaction);
     generate_ts_method!(community, report_message);
     generate_ts_method!(community, reset_invite_code);
+    generate_ts_method!(community, resolve_report);
+    generate_ts_method!(community, review_channel_held_message);
     generate_ts_method!(community, send_message);
+    generate_ts_method!(community, set_audit_log_retention);
+    generate_ts_method!(community, set_channel_automod_rules);
     generate_ts_method!(community, set_member_display_name);
     generate_ts_method!(community, set_video_call_presence);
+    generate_ts_method!(community, timeout_channel_member);
     generate_ts_method!(community, toggle_mute_notifications);
     generate_ts_method!(community, unblock_user);
     generate_ts_method!(community, undelete_messages);
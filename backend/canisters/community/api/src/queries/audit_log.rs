@@ -0,0 +1,29 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AuditLogEntry, AuditLogRetention, ChannelId};
+
+#[ts_export(community, audit_log)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    // If set, only entries for this channel are returned. Channel admins may only view their own channel's entries.
+    pub channel_id: Option<ChannelId>,
+    // Only entries with an index lower than this are returned
+    pub before: Option<u32>,
+    pub max_results: u32,
+}
+
+#[ts_export(community, audit_log)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(community, audit_log)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub entries: Vec<AuditLogEntry>,
+    pub latest_index: Option<u32>,
+    pub retention: AuditLogRetention,
+}
@@ -0,0 +1,15 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use types::{BotStorageEntry, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub keys: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(Vec<BotStorageEntry>),
+    Error(OCError),
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub user_id: UserId,
+    pub epoch: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    Failure,
+}
@@ -0,0 +1,14 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, GroupMember};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(Vec<GroupMember>),
+    Error(OCError),
+}
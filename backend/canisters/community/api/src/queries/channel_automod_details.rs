@@ -0,0 +1,17 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AutomodDetails, ChannelId};
+
+#[ts_export(community, channel_automod_details)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+}
+
+#[ts_export(community, channel_automod_details)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(AutomodDetails),
+    Error(OCError),
+}
@@ -0,0 +1,23 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, EncryptionKeyEpoch};
+
+#[ts_export(community, channel_encryption_key_epochs)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+}
+
+#[ts_export(community, channel_encryption_key_epochs)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(community, channel_encryption_key_epochs)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub epochs: Vec<EncryptionKeyEpoch>,
+}
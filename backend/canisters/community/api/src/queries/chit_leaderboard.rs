@@ -0,0 +1,27 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, ChitLeaderboardEntry, ChitLeaderboardWindow, TimestampMillis};
+
+#[ts_export(community, chit_leaderboard)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: Option<ChannelId>,
+    pub window: ChitLeaderboardWindow,
+    pub max_results: u32,
+}
+
+#[ts_export(community, chit_leaderboard)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(community, chit_leaderboard)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub start: TimestampMillis,
+    pub end: TimestampMillis,
+    pub leaders: Vec<ChitLeaderboardEntry>,
+}
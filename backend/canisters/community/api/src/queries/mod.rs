@@ -1,17 +1,24 @@
 pub mod active_proposal_tallies;
+pub mod audit_log;
 pub mod c2c_active_proposal_tallies;
 pub mod c2c_bot_channel_details;
 pub mod c2c_bot_community_events;
 pub mod c2c_bot_community_summary;
 pub mod c2c_bot_members;
+pub mod c2c_bot_storage_entries;
+pub mod c2c_can_access_encryption_key;
 pub mod c2c_can_issue_access_token;
+pub mod c2c_channel_members;
 pub mod c2c_events;
 pub mod c2c_events_by_index;
 pub mod c2c_events_window;
 pub mod c2c_summary;
 pub mod c2c_summary_updates;
+pub mod channel_automod_details;
+pub mod channel_encryption_key_epochs;
 pub mod channel_summary;
 pub mod channel_summary_updates;
+pub mod chit_leaderboard;
 pub mod community_events;
 pub mod deleted_message;
 pub mod events;
@@ -22,6 +29,7 @@ pub mod invite_code;
 pub mod local_user_index;
 pub mod lookup_members;
 pub mod messages_by_message_index;
+pub mod moderation_queue;
 pub mod search_channel;
 pub mod selected_channel_initial;
 pub mod selected_channel_updates_v2;
@@ -0,0 +1,38 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, ModerationQueueItem, ResolvedModerationItem, TimestampMillis};
+
+#[ts_export(community, moderation_queue)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: Option<ChannelId>,
+    // Pending items are returned in the order they were reported, starting after this id (only applies if
+    // `channel_id` is set)
+    pub pending_after: Option<u32>,
+    pub max_pending: u32,
+    pub resolved_before: Option<TimestampMillis>,
+    pub max_resolved: u32,
+}
+
+#[ts_export(community, moderation_queue)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(community, moderation_queue)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub channels: Vec<ChannelModerationQueue>,
+}
+
+#[ts_export(community, moderation_queue)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChannelModerationQueue {
+    pub channel_id: ChannelId,
+    pub pending: Vec<ModerationQueueItem>,
+    pub pending_count: u32,
+    pub resolved: Vec<ResolvedModerationItem>,
+}
@@ -43,6 +43,7 @@ impl SuccessResult {
                 date_added: 0,
                 role: GroupRole::Participant,
                 lapsed: false,
+                timed_out_until: None,
             }))
             .collect()
     }
@@ -6,6 +6,8 @@ use types::{UnitResult, UserId};
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
     pub user_id: UserId,
+    // Recorded in the community's audit log
+    pub reason: Option<String>,
 }
 
 pub type Response = UnitResult;
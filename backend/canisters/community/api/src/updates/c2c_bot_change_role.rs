@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use types::{BotInitiator, ChannelId, GroupRole, UnitResult, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub initiator: BotInitiator,
+    pub channel_id: Option<ChannelId>,
+    pub user_id: UserId,
+    pub new_role: GroupRole,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, Milliseconds, UnitResult, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub name: String,
+    // If not set, the task is cancelled
+    pub interval: Option<Milliseconds>,
+    // If set, the bot is told which channel the task relates to when it is woken
+    pub channel_id: Option<ChannelId>,
+}
+
+pub type Response = UnitResult;
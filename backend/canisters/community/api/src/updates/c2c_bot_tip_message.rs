@@ -0,0 +1,40 @@
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use types::{
+    BotInitiator, CanisterId, ChannelId, IdempotentArgs, MessageId, MessageIndex, UnitResult, UserId, idempotency_key,
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub bot_principal: Principal,
+    pub channel_id: ChannelId,
+    pub initiator: BotInitiator,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub ledger: CanisterId,
+    pub token_symbol: String,
+    pub amount: u128,
+    pub fee: u128,
+    pub decimals: u8,
+    pub bot_name: String,
+}
+
+// Tips are identified by who initiated them and what they are for, so a bot repeating an identical
+// tip within the idempotency retention period is treated as a retry rather than as a second tip
+impl IdempotentArgs for Args {
+    fn idempotency_key(&self) -> Option<u64> {
+        Some(idempotency_key((
+            "c2c_bot_tip_message",
+            self.bot_id,
+            self.initiator.user(),
+            self.channel_id,
+            self.thread_root_message_index,
+            self.message_id,
+            self.ledger,
+            self.amount,
+        )))
+    }
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use types::{BotStorageEntry, UnitResult, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub set: Vec<BotStorageEntry>,
+    pub remove: Vec<String>,
+}
+
+pub type Response = UnitResult;
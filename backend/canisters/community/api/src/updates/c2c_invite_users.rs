@@ -1,12 +1,22 @@
 use ic_principal::Principal;
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
-use types::UserId;
+use types::{IdempotentArgs, UserId};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
     pub caller: UserId,
     pub users: Vec<(UserId, Principal)>,
+    // Derived by the local_user_index from the original request, so that if it retries the call
+    // after an uncertain reply the request is only applied once
+    #[serde(default)]
+    pub idempotency_key: Option<u64>,
+}
+
+impl IdempotentArgs for Args {
+    fn idempotency_key(&self) -> Option<u64> {
+        self.idempotency_key
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -15,6 +25,12 @@ pub enum Response {
     Error(OCError),
 }
 
+impl From<OCError> for Response {
+    fn from(value: OCError) -> Self {
+        Response::Error(value)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SuccessResult {
     pub invited_users: Vec<UserId>,
@@ -2,8 +2,8 @@ use ic_principal::Principal;
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use types::{
-    ChannelId, CommunityCanisterCommunitySummary, GateCheckFailedReason, TimestampMillis, UniquePersonProof, UserId, UserType,
-    VerifiedCredentialGateArgs,
+    ChannelId, CommunityCanisterCommunitySummary, GateCheckFailedReason, IdempotentArgs, TimestampMillis, UniquePersonProof,
+    UserId, UserType, VerifiedCredentialGateArgs,
 };
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -18,6 +18,16 @@ pub struct Args {
     pub diamond_membership_expires_at: Option<TimestampMillis>,
     pub verified_credential_args: Option<VerifiedCredentialGateArgs>,
     pub unique_person_proof: Option<UniquePersonProof>,
+    // Derived by the local_user_index from the original request, so that if it retries the call
+    // after an uncertain reply the request is only applied once
+    #[serde(default)]
+    pub idempotency_key: Option<u64>,
+}
+
+impl IdempotentArgs for Args {
+    fn idempotency_key(&self) -> Option<u64> {
+        self.idempotency_key
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -27,3 +37,9 @@ pub enum Response {
     GateCheckFailed(GateCheckFailedReason),
     Error(OCError),
 }
+
+impl From<OCError> for Response {
+    fn from(value: OCError) -> Self {
+        Response::Error(value)
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, UnitResult};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Applied to every channel the user is a member of
+    pub keywords: Vec<String>,
+    // Additional keywords for specific channels
+    pub channel_keywords: Vec<(ChannelId, Vec<String>)>,
+}
+
+pub type Response = UnitResult;
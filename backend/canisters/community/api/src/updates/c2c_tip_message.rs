@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use types::{CanisterId, ChannelId, MessageId, MessageIndex, UnitResult, UserId};
+use types::{CanisterId, ChannelId, IdempotentArgs, MessageId, MessageIndex, UnitResult, UserId, idempotency_key};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
@@ -13,6 +13,16 @@ pub struct Args {
     pub decimals: u8,
     pub username: String,
     pub display_name: Option<String>,
+    // The index of the tip's transfer on the ledger, this identifies the tip so is used as the idempotency key
+    #[serde(default)]
+    pub block_index: Option<u64>,
+}
+
+impl IdempotentArgs for Args {
+    fn idempotency_key(&self) -> Option<u64> {
+        self.block_index
+            .map(|block_index| idempotency_key((self.ledger, block_index)))
+    }
 }
 
 pub type Response = UnitResult;
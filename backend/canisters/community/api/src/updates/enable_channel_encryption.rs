@@ -0,0 +1,17 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, EncryptionKeyEpoch};
+
+#[ts_export(community, enable_channel_encryption)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+}
+
+#[ts_export(community, enable_channel_encryption)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(EncryptionKeyEpoch),
+    Error(OCError),
+}
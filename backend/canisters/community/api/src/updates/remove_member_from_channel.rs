@@ -1,12 +1,16 @@
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{ChannelId, UnitResult, UserId};
+use types::{ChannelId, Milliseconds, UnitResult, UserId};
 
 #[ts_export(community, remove_member_from_channel)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
     pub channel_id: ChannelId,
     pub user_id: UserId,
+    // If set, the user is also blocked from rejoining the channel until the duration has passed
+    pub ban_duration: Option<Milliseconds>,
+    // Recorded in the community's audit log
+    pub reason: Option<String>,
 }
 
 pub type Response = UnitResult;
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, ModerationAction, UnitResult};
+
+#[ts_export(community, resolve_report)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub report_id: u32,
+    pub action: ModerationAction,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, MessageId, MessageIndex, UnitResult};
+
+#[ts_export(community, review_channel_held_message)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub release: bool,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AutomodRule, ChannelId, UnitResult};
+
+#[ts_export(community, set_channel_automod_rules)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub rules: Vec<AutomodRule>,
+}
+
+pub type Response = UnitResult;
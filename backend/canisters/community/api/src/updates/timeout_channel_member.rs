@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, Milliseconds, UnitResult, UserId};
+
+#[ts_export(community, timeout_channel_member)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub user_id: UserId,
+    // Passing `None` lifts any existing timeout
+    pub duration: Option<Milliseconds>,
+    // Recorded in the community's audit log
+    pub reason: Option<String>,
+}
+
+pub type Response = UnitResult;
@@ -7,7 +7,10 @@ generate_c2c_call!(c2c_bot_channel_details);
 generate_c2c_call!(c2c_bot_community_events);
 generate_c2c_call!(c2c_bot_community_summary);
 generate_c2c_call!(c2c_bot_members);
+generate_c2c_call!(c2c_bot_storage_entries);
+generate_c2c_call!(c2c_can_access_encryption_key);
 generate_c2c_call!(c2c_can_issue_access_token);
+generate_c2c_call!(c2c_channel_members);
 generate_c2c_call!(c2c_events);
 generate_c2c_call!(c2c_events_by_index);
 generate_c2c_call!(c2c_events_window);
@@ -18,13 +21,25 @@ generate_c2c_call!(summary_updates);
 
 // Updates
 generate_c2c_call!(c2c_bot_add_reaction);
+generate_c2c_call!(c2c_bot_change_role);
 generate_c2c_call!(c2c_bot_create_channel);
 generate_c2c_call!(c2c_bot_delete_channel);
 generate_c2c_call!(c2c_bot_delete_messages);
+generate_c2c_call!(c2c_bot_edit_message);
+generate_c2c_call!(c2c_bot_follow_thread);
+generate_c2c_call!(c2c_bot_pin_message);
 generate_c2c_call!(c2c_bot_remove_user);
 generate_c2c_call!(c2c_bot_invite_users);
+generate_c2c_call!(c2c_bot_schedule_task);
+generate_c2c_call!(c2c_bot_send_crypto);
 generate_c2c_call!(c2c_bot_send_message);
+generate_c2c_call!(c2c_bot_send_prize);
 generate_c2c_call!(c2c_bot_subscribe_to_events);
+generate_c2c_call!(c2c_bot_tip_message);
+generate_c2c_call!(c2c_bot_unfollow_thread);
+generate_c2c_call!(c2c_bot_unpin_message);
+generate_c2c_call!(c2c_bot_update_chat_details);
+generate_c2c_call!(c2c_bot_update_storage);
 generate_c2c_call!(c2c_create_proposals_channel);
 generate_c2c_call!(c2c_delete_community);
 generate_c2c_call!(c2c_freeze_community);
@@ -48,6 +48,14 @@ pub fn caller_is_escrow_canister() -> Result<(), String> {
     }
 }
 
+pub fn caller_is_identity_canister() -> Result<(), String> {
+    if read_state(|state| state.is_caller_identity_canister()) {
+        Ok(())
+    } else {
+        Err("Caller is not the identity canister".to_string())
+    }
+}
+
 pub fn caller_is_video_call_operator() -> Result<(), String> {
     if read_state(|state| state.is_caller_video_call_operator()) {
         Ok(())
@@ -316,7 +316,7 @@ pub(crate) async fn process_channel_members(group_id: ChatId, channel_id: Channe
                             let channel = state.data.channels.get_mut(&channel_id).unwrap();
                             let _ = channel
                                 .chat
-                                .remove_member(Caller::OCBot(OPENCHAT_BOT_USER_ID), user_id, false, now);
+                                .remove_member(Caller::OCBot(OPENCHAT_BOT_USER_ID), user_id, false, None, now);
                         }
                     }
                 }
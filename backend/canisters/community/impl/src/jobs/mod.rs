@@ -4,6 +4,7 @@ pub mod expire_members;
 pub mod garbage_collect_stable_memory;
 pub mod import_groups;
 pub mod make_pending_payments;
+pub mod migrate_stable_memory;
 pub mod process_expire_member_actions;
 
 pub(crate) fn start(state: &RuntimeState) {
@@ -11,5 +12,6 @@ pub(crate) fn start(state: &RuntimeState) {
     garbage_collect_stable_memory::start_job_if_required(state);
     import_groups::start_job_if_required(state);
     make_pending_payments::start_job_if_required(state);
+    migrate_stable_memory::start_job_if_required(state);
     process_expire_member_actions::start_job_if_required(state);
 }
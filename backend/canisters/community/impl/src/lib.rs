@@ -1,9 +1,13 @@
+use crate::activity_notifications::handle_activity_notification;
 use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::channels::Channels;
 use crate::model::groups_being_imported::{GroupBeingImportedSummary, GroupsBeingImported};
 use crate::model::local_user_index_event_batch::LocalUserIndexEventBatch;
 use crate::model::members::CommunityMembers;
-use crate::timer_job_types::{DeleteFileReferencesJob, MakeTransferJob, RemoveExpiredEventsJob, TimerJob};
+use crate::timer_job_types::{
+    DeleteFileReferencesJob, LiftExpiredMemberRestrictionsJob, MakeTransferJob, RemoveExpiredEventsJob, RunBotScheduledTaskJob,
+    TimerJob,
+};
 use activity_notification_state::ActivityNotificationState;
 use canister_state_macros::canister_state;
 use canister_timer_jobs::{Job, TimerJobs};
@@ -15,11 +19,12 @@ use fire_and_forget_handler::FireAndForgetHandler;
 use gated_groups::{GatePayment, calculate_gate_payments};
 use group_chat_core::{AccessRulesInternal, AddResult};
 use group_community_common::{
-    Achievements, ExpiringMember, ExpiringMemberActions, ExpiringMembers, Members, PaymentReceipts, PendingPaymentsQueue,
-    UserCache,
+    Achievements, AuditLog, ChitLeaderboard, ExpiringMember, ExpiringMemberActions, ExpiringMembers, Members, PaymentReceipts,
+    PendingPaymentsQueue, UserCache,
 };
+use http_request::{OpenMetrics, OpenMetricsEncoder};
 use ic_principal::Principal;
-use installed_bots::InstalledBots;
+use installed_bots::{BotSpendReservation, InstalledBots};
 use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use model::events::CommunityEventInternal;
 use model::user_event_batch::UserEventBatch;
@@ -30,18 +35,19 @@ use rand::RngCore;
 use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
-use stable_memory_map::{BaseKeyPrefix, ChatEventKeyPrefix};
+use stable_memory_map::{BaseKeyPrefix, ChatEventKeyPrefix, ValueMigrationMetrics, ValueMigrations};
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashSet};
 use std::ops::Deref;
-use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue};
+use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue, TimerJobQueueMetrics};
 use types::{
-    AccessGate, AccessGateConfigInternal, Achievement, BotCommunityEvent, BotEventsCaller, BotInitiator, BotNotification,
-    BotPermissions, BuildVersion, Caller, CanisterId, ChannelCreated, ChannelId, ChatEventCategory, ChatEventType, ChatMetrics,
-    ChatPermission, CommunityCanisterCommunitySummary, CommunityEvent, CommunityEventCategory, CommunityEventType,
-    CommunityMembership, CommunityPermissions, Cycles, Document, Empty, EventIndex, EventsCaller, FcmData, FrozenGroupInfo,
-    GroupRole, IdempotentEnvelope, MembersAdded, Milliseconds, Notification, Rules, TimestampMillis, Timestamped, UserId,
-    UserNotification, UserNotificationPayload, UserType,
+    AccessGate, AccessGateConfigInternal, Achievement, AuditLogAction, AuditLogRetention, BotCommunityEvent, BotEvent,
+    BotEventsCaller, BotInitiator, BotInstallationLocation, BotLifecycleEvent, BotNotification, BotPermissions,
+    BotScheduledTaskEvent, BotSpendAllowance, BuildVersion, Caller, CanisterId, ChannelCreated, ChannelId, ChatEventCategory,
+    ChatEventType, ChatMetrics, ChatPermission, CommunityCanisterCommunitySummary, CommunityEvent, CommunityEventCategory,
+    CommunityEventType, CommunityMembership, CommunityPermissions, Cycles, Document, Empty, EventIndex, EventsCaller, FcmData,
+    FrozenGroupInfo, GroupRole, IdempotentEnvelope, MembersAdded, Milliseconds, Notification, OCResult, Rules, TimestampMillis,
+    Timestamped, UserId, UserNotification, UserNotificationPayload, UserType,
 };
 use types::{BotSubscriptions, CommunityId};
 use user_canister::CommunityCanisterEvent;
@@ -97,6 +103,10 @@ impl RuntimeState {
         self.env.caller() == self.data.escrow_canister_id
     }
 
+    pub fn is_caller_identity_canister(&self) -> bool {
+        self.env.caller() == self.data.identity_canister_id
+    }
+
     pub fn is_caller_video_call_operator(&self) -> bool {
         let caller = self.env.caller();
         self.data.video_call_operators.contains(&caller)
@@ -274,6 +284,70 @@ impl RuntimeState {
         }
     }
 
+    pub fn run_bot_scheduled_task(&mut self, bot_id: UserId, name: String) {
+        let now = self.env.now();
+        let Some((scheduled_at, task)) = self.data.bots.take_due_task(&bot_id, &name, now) else {
+            return;
+        };
+
+        // If the task's channel has since been deleted or the bot's autonomous permissions have been revoked, the task can
+        // no longer run
+        if task
+            .channel_id
+            .is_some_and(|channel_id| self.data.channels.get(&channel_id).is_none())
+            || !self.data.can_bot_run_scheduled_tasks(&bot_id, task.channel_id)
+        {
+            self.data.bots.cancel_task(&bot_id, &name);
+            return;
+        }
+
+        self.data.timer_jobs.enqueue_job(
+            TimerJob::RunBotScheduledTask(RunBotScheduledTaskJob { bot_id, name }),
+            task.next_run,
+            now,
+        );
+
+        if self.data.frozen.value.is_none() {
+            self.push_bot_notification(Some(BotNotification {
+                event: BotEvent::Lifecycle(BotLifecycleEvent::ScheduledTask(BotScheduledTaskEvent {
+                    name: task.name,
+                    location: BotInstallationLocation::Community(self.env.canister_id().into()),
+                    channel_id: task.channel_id,
+                    scheduled_at,
+                })),
+                recipients: vec![bot_id],
+                timestamp: now,
+            }));
+        }
+    }
+
+    pub fn run_member_restriction_expiry_job(&mut self) {
+        let now = self.env.now();
+        let mut next_expiry = None;
+        let mut bot_notifications = Vec::new();
+        for channel in self.data.channels.iter_mut() {
+            bot_notifications.extend(channel.chat.lift_expired_restrictions(now));
+            if let Some(expiry) = channel.chat.members.next_restriction_expiry() {
+                if next_expiry.is_none_or(|current| expiry < current) {
+                    next_expiry = Some(expiry);
+                }
+            }
+        }
+
+        self.data.next_member_restriction_expiry = next_expiry;
+        if let Some(expiry) = next_expiry {
+            self.data.timer_jobs.enqueue_job(
+                TimerJob::LiftExpiredMemberRestrictions(LiftExpiredMemberRestrictionsJob),
+                expiry,
+                now,
+            );
+        }
+        if !bot_notifications.is_empty() {
+            self.push_bot_notifications(bot_notifications);
+            handle_activity_notification(self);
+        }
+    }
+
     pub fn run_event_expiry_job(&mut self) {
         let now = self.env.now();
         let mut next_event_expiry = None;
@@ -367,6 +441,14 @@ impl RuntimeState {
             queued_user_events: self.data.user_event_sync_queue.len() as u32,
             queued_local_index_events: self.data.local_user_index_event_sync_queue.len() as u32,
             stable_memory_sizes: memory::memory_sizes(),
+            stable_memory_migrations: self.data.stable_memory_migrations.metrics(),
+            timer_job_queues: BTreeMap::from([
+                ("user_event_sync_queue".to_string(), self.data.user_event_sync_queue.metrics()),
+                (
+                    "local_user_index_event_sync_queue".to_string(),
+                    self.data.local_user_index_event_sync_queue.metrics(),
+                ),
+            ]),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 group_index: self.data.group_index_canister_id,
@@ -375,6 +457,7 @@ impl RuntimeState {
                 escrow: self.data.escrow_canister_id,
                 icp_ledger: ICP_LEDGER_CANISTER_ID,
                 internet_identity: self.data.internet_identity_canister_id,
+                identity: self.data.identity_canister_id,
             },
         }
     }
@@ -439,6 +522,8 @@ struct Data {
     proposals_bot_user_id: UserId,
     escrow_canister_id: CanisterId,
     internet_identity_canister_id: CanisterId,
+    #[serde(default = "Principal::anonymous")]
+    identity_canister_id: CanisterId,
     date_created: TimestampMillis,
     members: CommunityMembers,
     channels: Channels,
@@ -454,6 +539,8 @@ struct Data {
     #[serde(skip, default = "init_instruction_counts_log")]
     instruction_counts_log: InstructionCountsLog,
     next_event_expiry: Option<TimestampMillis>,
+    #[serde(default)]
+    next_member_restriction_expiry: Option<TimestampMillis>,
     video_call_operators: Vec<Principal>,
     test_mode: bool,
     cached_chat_metrics: Timestamped<ChatMetrics>,
@@ -466,6 +553,8 @@ struct Data {
     expiring_members: ExpiringMembers,
     expiring_member_actions: ExpiringMemberActions,
     user_cache: UserCache,
+    #[serde(default)]
+    chit_leaderboard: ChitLeaderboard,
     user_event_sync_queue: GroupedTimerJobQueue<UserEventBatch>,
     local_user_index_event_sync_queue: BatchedTimerJobQueue<LocalUserIndexEventBatch>,
     stable_memory_keys_to_garbage_collect: Vec<BaseKeyPrefix>,
@@ -473,6 +562,10 @@ struct Data {
     verified: Timestamped<bool>,
     idempotency_checker: IdempotencyChecker,
     public_channel_list_updated: TimestampMillis,
+    #[serde(default)]
+    audit_log: AuditLog,
+    #[serde(default)]
+    stable_memory_migrations: ValueMigrations,
 }
 
 impl Data {
@@ -496,6 +589,7 @@ impl Data {
         proposals_bot_user_id: UserId,
         escrow_canister_id: CanisterId,
         internet_identity_canister_id: CanisterId,
+        identity_canister_id: CanisterId,
         gate: Option<AccessGateConfigInternal>,
         channels: Vec<(ChannelId, String)>,
         default_channel_rules: Option<Rules>,
@@ -553,6 +647,7 @@ impl Data {
             proposals_bot_user_id,
             escrow_canister_id,
             internet_identity_canister_id,
+            identity_canister_id,
             date_created: now,
             members,
             channels,
@@ -567,6 +662,7 @@ impl Data {
             groups_being_imported: GroupsBeingImported::default(),
             instruction_counts_log: init_instruction_counts_log(),
             next_event_expiry: None,
+            next_member_restriction_expiry: None,
             test_mode,
             cached_chat_metrics: Timestamped::default(),
             rng_seed: [0; 32],
@@ -578,6 +674,7 @@ impl Data {
             expiring_members: ExpiringMembers::default(),
             expiring_member_actions: ExpiringMemberActions::default(),
             user_cache: UserCache::default(),
+            chit_leaderboard: ChitLeaderboard::default(),
             user_event_sync_queue: GroupedTimerJobQueue::new(5, true),
             local_user_index_event_sync_queue: BatchedTimerJobQueue::new(local_user_index_canister_id, true),
             stable_memory_keys_to_garbage_collect: Vec::new(),
@@ -585,6 +682,8 @@ impl Data {
             verified: Timestamped::default(),
             idempotency_checker: IdempotencyChecker::default(),
             public_channel_list_updated: now,
+            audit_log: AuditLog::default(),
+            stable_memory_migrations: ValueMigrations::default(),
         }
     }
 
@@ -664,6 +763,47 @@ impl Data {
             .unwrap_or_default()
     }
 
+    pub fn audit_log(&self) -> &AuditLog {
+        &self.audit_log
+    }
+
+    // Pass `channel_id` for actions taken within a channel, or `None` for community level actions
+    pub fn record_audit_log_entry(
+        &mut self,
+        channel_id: Option<ChannelId>,
+        actor: UserId,
+        target: Option<UserId>,
+        action: AuditLogAction,
+        reason: Option<String>,
+        now: TimestampMillis,
+    ) {
+        self.audit_log.record(channel_id, actor, target, action, reason, now);
+    }
+
+    pub fn set_audit_log_retention(&mut self, retention: AuditLogRetention, now: TimestampMillis) -> OCResult {
+        self.audit_log.set_retention(retention, now)
+    }
+
+    pub fn handle_member_restriction_expiry(&mut self, channel_id: ChannelId, now: TimestampMillis) {
+        if let Some(expiry) = self
+            .channels
+            .get(&channel_id)
+            .and_then(|c| c.chat.members.next_restriction_expiry())
+        {
+            if self.next_member_restriction_expiry.is_none_or(|ex| expiry < ex) {
+                self.next_member_restriction_expiry = Some(expiry);
+
+                let timer_jobs = &mut self.timer_jobs;
+                timer_jobs.cancel_jobs(|j| matches!(j, TimerJob::LiftExpiredMemberRestrictions(_)));
+                timer_jobs.enqueue_job(
+                    TimerJob::LiftExpiredMemberRestrictions(LiftExpiredMemberRestrictionsJob),
+                    expiry,
+                    now,
+                );
+            }
+        }
+    }
+
     pub fn handle_event_expiry(&mut self, expiry: TimestampMillis, now: TimestampMillis) {
         if self.next_event_expiry.is_none_or(|ex| expiry < ex) {
             self.next_event_expiry = Some(expiry);
@@ -698,6 +838,7 @@ impl Data {
         self.expiring_member_actions.remove_member(user_id, None);
         self.achievements.remove_user(&user_id);
         self.user_cache.delete(user_id);
+        self.chit_leaderboard.remove_user(&user_id);
         removed
     }
 
@@ -975,12 +1116,17 @@ impl Data {
         bot_id: UserId,
         permissions: BotPermissions,
         autonomous_permissions: Option<BotPermissions>,
+        spend_allowances: Option<Vec<BotSpendAllowance>>,
         now: TimestampMillis,
     ) -> bool {
         if !self.bots.update(bot_id, permissions, autonomous_permissions.clone(), now) {
             return false;
         }
 
+        if let Some(spend_allowances) = spend_allowances {
+            self.bots.set_spend_allowances(bot_id, spend_allowances, now);
+        }
+
         let bot = self.bots.get(&bot_id).unwrap();
         let permissions = autonomous_permissions.unwrap_or_default();
         let permitted_categories = permissions.permitted_chat_event_categories_to_read();
@@ -1010,6 +1156,9 @@ impl Data {
             channel.chat.events.unsubscribe_bot_from_events(bot_id);
         }
 
+        self.timer_jobs
+            .cancel_jobs(|j| matches!(j, TimerJob::RunBotScheduledTask(job) if job.bot_id == bot_id));
+
         // TODO: Notify UserIndex
         true
     }
@@ -1042,6 +1191,68 @@ impl Data {
         Some(bot_permissions)
     }
 
+    // Adds or replaces the bot's task with the given name, or cancels it if no interval is specified
+    pub fn schedule_bot_task(
+        &mut self,
+        bot_id: UserId,
+        name: String,
+        interval: Option<Milliseconds>,
+        channel_id: Option<ChannelId>,
+        now: TimestampMillis,
+    ) -> OCResult {
+        let next_run = match interval {
+            Some(interval) => {
+                if let Some(channel_id) = channel_id {
+                    self.channels.get_or_err(&channel_id)?;
+                }
+                if !self.can_bot_run_scheduled_tasks(&bot_id, channel_id) {
+                    return Err(OCErrorCode::InitiatorNotAuthorized
+                        .with_message("Scheduled tasks require the bot to be granted autonomous permissions"));
+                }
+                Some(self.bots.schedule_task(&bot_id, name.clone(), interval, channel_id, now)?)
+            }
+            None => {
+                self.bots.cancel_task(&bot_id, &name);
+                None
+            }
+        };
+
+        self.timer_jobs
+            .cancel_jobs(|j| matches!(j, TimerJob::RunBotScheduledTask(job) if job.bot_id == bot_id && job.name == name));
+
+        if let Some(next_run) = next_run {
+            self.timer_jobs.enqueue_job(
+                TimerJob::RunBotScheduledTask(RunBotScheduledTaskJob { bot_id, name }),
+                next_run,
+                now,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn reserve_bot_spend(
+        &mut self,
+        bot_id: &UserId,
+        ledger: CanisterId,
+        amount: u128,
+        now: TimestampMillis,
+    ) -> OCResult<BotSpendReservation> {
+        self.bots
+            .reserve_spend(bot_id, ledger, amount, now)
+            .ok_or(OCErrorCode::BotSpendAllowanceExceeded.into())
+    }
+
+    pub fn release_bot_spend(&mut self, reservation: BotSpendReservation) {
+        self.bots.release_spend(reservation)
+    }
+
+    // Scheduled tasks wake the bot so that it can act autonomously
+    pub fn can_bot_run_scheduled_tasks(&self, bot_id: &UserId, channel_id: Option<ChannelId>) -> bool {
+        self.granted_bot_permissions(bot_id, &BotInitiator::Autonomous, channel_id)
+            .is_some_and(|granted| !granted.is_empty())
+    }
+
     pub fn is_bot_permitted(
         &self,
         bot_id: &UserId,
@@ -1064,7 +1275,7 @@ impl Data {
 
         // Get the permissions granted to the bot when initiated by command or autonomously
         let granted_to_bot = match initiator {
-            BotInitiator::Command(_) => &bot.permissions,
+            BotInitiator::Command(_) | BotInitiator::Component(_) => &bot.permissions,
             BotInitiator::Autonomous => bot.autonomous_permissions.as_ref()?,
         };
 
@@ -1086,9 +1297,9 @@ impl Data {
         };
 
         match initiator {
-            BotInitiator::Command(command) => {
+            BotInitiator::Command(_) | BotInitiator::Component(_) => {
                 // Intersect the permissions granted to the bot with the user's permissions
-                self.get_user_permissions(&command.initiator, channel_id)
+                self.get_user_permissions(&initiator.user()?, channel_id)
                     .map(|u| BotPermissions::intersect(granted_to_bot, &u))
             }
             BotInitiator::Autonomous => {
@@ -1195,9 +1406,18 @@ pub struct Metrics {
     pub queued_user_events: u32,
     pub queued_local_index_events: u32,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub stable_memory_migrations: BTreeMap<String, ValueMigrationMetrics>,
+    pub timer_job_queues: BTreeMap<String, TimerJobQueueMetrics>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct CanisterIds {
     pub user_index: CanisterId,
@@ -1207,6 +1427,7 @@ pub struct CanisterIds {
     pub escrow: CanisterId,
     pub icp_ledger: CanisterId,
     pub internet_identity: CanisterId,
+    pub identity: CanisterId,
 }
 
 pub struct AddUsersToChannelResult {
@@ -1,5 +1,5 @@
 use crate::lifecycle::{init_env, init_state};
-use crate::memory::get_stable_memory_map_memory;
+use crate::memory::{get_canister_logs_memory, get_stable_memory_map_memory};
 use crate::updates::import_group::commit_group_to_import;
 use crate::{Data, mutate_state};
 use canister_api_macros::init;
@@ -12,7 +12,7 @@ use utils::env::Environment;
 #[init(msgpack = true)]
 #[trace]
 fn init(args: Args) {
-    canister_logger::init(args.test_mode);
+    canister_logger::init(args.test_mode, get_canister_logs_memory());
     stable_memory_map::init(get_stable_memory_map_memory());
 
     let mut env = init_env([0; 32]);
@@ -39,6 +39,7 @@ fn init(args: Args) {
         args.proposals_bot_user_id,
         args.escrow_canister_id,
         args.internet_identity_canister_id,
+        args.identity_canister_id,
         args.gate_config.map(|g| g.into()),
         args.channels,
         args.default_channel_rules,
@@ -1,6 +1,7 @@
 use crate::jobs::import_groups::finalize_group_import;
+use crate::jobs::migrate_stable_memory;
 use crate::lifecycle::{init_env, init_state};
-use crate::memory::{get_stable_memory_map_memory, get_upgrades_memory};
+use crate::memory::{get_canister_logs_memory, get_stable_memory_map_memory, get_upgrades_memory};
 use crate::{Data, read_state};
 use canister_api_macros::post_upgrade;
 use canister_logger::LogEntry;
@@ -9,6 +10,8 @@ use community_canister::post_upgrade::Args;
 use instruction_counts_log::InstructionCountFunctionId;
 use stable_memory::get_reader;
 use tracing::info;
+use types::CanisterId;
+use utils::env::Environment;
 
 #[post_upgrade(msgpack = true)]
 #[trace]
@@ -18,12 +21,22 @@ fn post_upgrade(args: Args) {
     let memory = get_upgrades_memory();
     let reader = get_reader(&memory);
 
-    let (data, errors, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>, Vec<LogEntry>) =
+    let (mut data, errors, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>, Vec<LogEntry>) =
         msgpack::deserialize(reader).unwrap();
 
-    canister_logger::init_with_logs(data.test_mode, errors, logs, traces);
+    // One-off migration for canisters created before the identity canister id was passed in on init
+    if data.identity_canister_id == CanisterId::anonymous() {
+        data.identity_canister_id = if data.test_mode {
+            CanisterId::from_text("rejcv-jqaaa-aaaak-afj5q-cai").unwrap()
+        } else {
+            CanisterId::from_text("6klfq-niaaa-aaaar-qadbq-cai").unwrap()
+        };
+    }
+
+    canister_logger::init_with_logs(data.test_mode, get_canister_logs_memory(), errors, logs, traces);
 
     let env = init_env(data.rng_seed);
+    migrate_stable_memory::start_migrations(&mut data, env.now());
     init_state(env, data, args.wasm_version);
 
     let completed_imports = read_state(|state| state.data.groups_being_imported.completed_imports());
@@ -19,6 +19,8 @@ use types::{
 mod proptests;
 mod stable_memory;
 
+pub use stable_memory::{COMMUNITY_MEMBER_VALUE_VERSION, migrate_community_member_bytes};
+
 const MAX_MEMBERS_PER_COMMUNITY: u32 = 100_000;
 
 #[derive(Serialize, Deserialize)]
@@ -209,7 +211,7 @@ impl CommunityMembers {
     #[expect(clippy::too_many_arguments)]
     pub fn change_role(
         &mut self,
-        user_id: UserId,
+        user_id: Option<UserId>,
         target_user_id: UserId,
         new_role: CommunityRole,
         permissions: &CommunityPermissions,
@@ -217,12 +219,17 @@ impl CommunityMembers {
         is_user_platform_moderator: bool,
         now: TimestampMillis,
     ) -> OCResult<ChangeRoleSuccess> {
-        // Is the caller authorized to change the user to this role
-        let initiator = self.get_verified_member(user_id.into())?;
-
-        // Platform moderators can always promote themselves to owner
-        if !(initiator.role.can_change_roles(new_role, permissions) || (is_caller_platform_moderator && new_role.is_owner())) {
-            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        // Is the caller authorized to change the user to this role. If there is no caller then the
+        // role is being changed by an autonomous bot whose permissions have already been checked.
+        if let Some(user_id) = user_id {
+            let initiator = self.get_verified_member(user_id.into())?;
+
+            // Platform moderators can always promote themselves to owner
+            if !(initiator.role.can_change_roles(new_role, permissions)
+                || (is_caller_platform_moderator && new_role.is_owner()))
+            {
+                return Err(OCErrorCode::InitiatorNotAuthorized.into());
+            }
         }
 
         let mut member = self
@@ -231,7 +238,7 @@ impl CommunityMembers {
             .ok_or(OCErrorCode::TargetUserNotInCommunity)?;
 
         // Platform moderators cannot be demoted from owner except by themselves
-        if is_user_platform_moderator && member.role.is_owner() && target_user_id != user_id {
+        if is_user_platform_moderator && member.role.is_owner() && user_id != Some(target_user_id) {
             return Err(OCErrorCode::InitiatorNotAuthorized.into());
         }
 
@@ -1,6 +1,6 @@
 use crate::CommunityMemberInternal;
 use serde::{Deserialize, Serialize};
-use stable_memory_map::{StableMemoryMap, UserIdKeyPrefix};
+use stable_memory_map::{StableMemoryMap, UserIdKeyPrefix, unwrap_versioned, wrap_versioned};
 use std::collections::BTreeSet;
 use types::{CommunityRole, TimestampMillis, Timestamped, UserId, UserType, Version, is_default};
 
@@ -111,10 +111,19 @@ impl From<CommunityMemberInternal> for CommunityMemberStableStorage {
     }
 }
 
-fn member_to_bytes(member: CommunityMemberStableStorage) -> Vec<u8> {
+// Version 0 covers members written before the versioned envelope
+pub const COMMUNITY_MEMBER_VALUE_VERSION: u8 = 1;
+
+// Rewrites a member in the latest format, after which the aliases for old field names are no longer needed
+pub fn migrate_community_member_bytes(_version: u8, payload: &[u8]) -> Vec<u8> {
+    let member: CommunityMemberStableStorage = msgpack::deserialize_then_unwrap(payload);
     msgpack::serialize_then_unwrap(member)
 }
 
+fn member_to_bytes(member: CommunityMemberStableStorage) -> Vec<u8> {
+    wrap_versioned(COMMUNITY_MEMBER_VALUE_VERSION, msgpack::serialize_then_unwrap(member))
+}
+
 fn bytes_to_member(bytes: &[u8]) -> CommunityMemberStableStorage {
-    msgpack::deserialize_then_unwrap(bytes)
+    msgpack::deserialize_then_unwrap(unwrap_versioned(bytes).1)
 }
@@ -0,0 +1,46 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use community_canister::audit_log::{Response::*, *};
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+const MAX_RESULTS: u32 = 100;
+
+#[query(msgpack = true)]
+fn audit_log(args: Args) -> Response {
+    match read_state(|state| audit_log_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn audit_log_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
+    let member = state.get_calling_member(true)?;
+    let is_community_admin = member.role().can_view_audit_log();
+
+    if let Some(channel_id) = args.channel_id {
+        if !is_community_admin {
+            let channel = state.data.channels.get_or_err(&channel_id)?;
+            let channel_member = channel.chat.members.get_verified_member(member.user_id)?;
+            if !channel_member.role().can_view_audit_log() {
+                return Err(OCErrorCode::InitiatorNotAuthorized.into());
+            }
+        }
+    } else if !is_community_admin {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let audit_log = state.data.audit_log();
+    let entries = audit_log.entries(
+        |c| args.channel_id.is_none_or(|channel_id| c == Some(channel_id)),
+        args.before,
+        args.max_results.min(MAX_RESULTS) as usize,
+        state.env.now(),
+    );
+
+    Ok(SuccessResult {
+        entries,
+        latest_index: audit_log.latest_index(),
+        retention: audit_log.retention(),
+    })
+}
@@ -0,0 +1,12 @@
+use crate::guards::caller_is_local_user_index;
+use crate::read_state;
+use canister_api_macros::query;
+use community_canister::c2c_bot_storage_entries::{Response::*, *};
+
+#[query(guard = "caller_is_local_user_index", msgpack = true)]
+fn c2c_bot_storage_entries(args: Args) -> Response {
+    read_state(|state| match state.data.bots.storage_entries(&args.bot_id, &args.keys) {
+        Ok(entries) => Success(entries),
+        Err(error) => Error(error),
+    })
+}
@@ -0,0 +1,26 @@
+use crate::guards::caller_is_identity_canister;
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use community_canister::c2c_can_access_encryption_key::*;
+
+#[query(guard = "caller_is_identity_canister", msgpack = true)]
+fn c2c_can_access_encryption_key(args: Args) -> Response {
+    read_state(|state| c2c_can_access_encryption_key_impl(args, state))
+}
+
+fn c2c_can_access_encryption_key_impl(args: Args, state: &RuntimeState) -> Response {
+    if state.data.is_frozen() || !state.data.members.contains(&args.user_id) {
+        return Response::Failure;
+    }
+
+    if state
+        .data
+        .channels
+        .get(&args.channel_id)
+        .is_some_and(|c| c.chat.can_access_encryption_key(args.user_id, args.epoch))
+    {
+        Response::Success
+    } else {
+        Response::Failure
+    }
+}
@@ -3,8 +3,8 @@ use crate::guards::caller_is_local_user_index;
 use crate::read_state;
 use canister_api_macros::query;
 use community_canister::c2c_can_issue_access_token::*;
-use types::BotPermissions;
 use types::c2c_can_issue_access_token::AccessTypeArgs;
+use types::{BotPermissions, Chat, CommunityId};
 
 #[query(guard = "caller_is_local_user_index", msgpack = true)]
 fn c2c_can_issue_access_token(args: Args) -> Response {
@@ -33,7 +33,90 @@ fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Re
 
         let granted = BotPermissions::intersect(granted_to_bot, &granted_to_user);
 
-        return if args.requested_permissions.is_subset(&granted) { Response::Success } else { Response::Failure };
+        if !args.requested_permissions.is_subset(&granted) {
+            return Response::Failure;
+        }
+
+        // Chat args can only reference channels in this community which the initiator is a member of
+        let community_id: CommunityId = state.env.canister_id().into();
+        if !args.referenced_chats.iter().all(|c| match c {
+            Chat::Channel(c_id, channel_id) => {
+                *c_id == community_id
+                    && state
+                        .data
+                        .channels
+                        .get(channel_id)
+                        .is_some_and(|channel| channel.chat.members.get_verified_member(args.initiator).is_ok())
+            }
+            _ => false,
+        }) {
+            return Response::Failure;
+        }
+
+        // Message args must be in the command's channel and visible to the initiator
+        if !args.referenced_messages.is_empty() {
+            let Some(channel) = args_outer
+                .channel_id
+                .and_then(|channel_id| state.data.channels.get(&channel_id))
+            else {
+                return Response::Failure;
+            };
+
+            if !args.referenced_messages.iter().all(|m| {
+                channel
+                    .chat
+                    .is_message_visible_to(args.initiator, m.thread, m.message_index, m.message_id)
+            }) {
+                return Response::Failure;
+            }
+        }
+
+        return Response::Success;
+    }
+
+    if let AccessTypeArgs::BotActionByComponent(args) = &args_outer.access_type {
+        // Components can only be attached to messages, so the channel must be specified
+        let Some(channel_id) = args_outer.channel_id else {
+            return Response::Failure;
+        };
+
+        // Ensure the component is attached to one of the bot's messages which the initiator can see
+        let Some(component) = state.data.channels.get(&channel_id).and_then(|c| {
+            c.chat
+                .bot_message_component(args.initiator, args.bot_id, args.thread, args.message_id, &args.component_id)
+        }) else {
+            return Response::Failure;
+        };
+
+        if !component.is_valid_value(&args.value) {
+            return Response::Failure;
+        }
+
+        // Ensure the initiator has the necessary seniority according to the component's required role
+        if !state.data.is_same_or_senior(
+            args.initiator.into(),
+            Some(channel_id),
+            component.default_role().unwrap_or_default(),
+        ) {
+            return Response::Failure;
+        }
+
+        let Some(granted_to_bot) = state.data.bots.get(&args.bot_id).map(|b| &b.permissions) else {
+            return Response::Failure;
+        };
+
+        let Some(granted_to_user) = state.data.get_user_permissions(&args.initiator, Some(channel_id)) else {
+            return Response::Failure;
+        };
+
+        let granted = BotPermissions::intersect(granted_to_bot, &granted_to_user);
+        let requested = component.permissions();
+
+        return if requested.is_subset(&granted) {
+            Response::SuccessWithPermissions(requested.clone())
+        } else {
+            Response::Failure
+        };
     }
 
     let Some(channel) = args_outer
@@ -0,0 +1,31 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use community_canister::c2c_channel_members::{Response::*, *};
+use types::{GroupMember, OCResult};
+
+// Unlike `selected_channel_initial`, this returns the full details (including the date added) of every member
+#[query(msgpack = true)]
+fn c2c_channel_members(args: Args) -> Response {
+    match read_state(|state| c2c_channel_members_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn c2c_channel_members_impl(args: Args, state: &RuntimeState) -> OCResult<Vec<GroupMember>> {
+    let caller = state.env.caller();
+    state.data.verify_is_accessible(caller, None)?;
+
+    let channel = state.data.channels.get_or_err(&args.channel_id)?;
+    let user_id = state.data.members.lookup_user_id(caller);
+    channel.chat.verify_is_accessible(user_id)?;
+
+    let members = &channel.chat.members;
+
+    Ok(members
+        .member_ids()
+        .iter()
+        .filter_map(|user_id| members.get(user_id))
+        .map(|member| GroupMember::from(&member))
+        .collect())
+}
@@ -0,0 +1,18 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use community_canister::channel_automod_details::{Response::*, *};
+use types::{AutomodDetails, OCResult};
+
+#[query(msgpack = true)]
+fn channel_automod_details(args: Args) -> Response {
+    match read_state(|state| channel_automod_details_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn channel_automod_details_impl(args: Args, state: &RuntimeState) -> OCResult<AutomodDetails> {
+    let member = state.get_calling_member(true)?;
+    let channel = state.data.channels.get_or_err(&args.channel_id)?;
+    channel.chat.automod_details(member.user_id)
+}
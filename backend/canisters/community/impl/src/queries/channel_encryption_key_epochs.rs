@@ -0,0 +1,20 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use community_canister::channel_encryption_key_epochs::{Response::*, *};
+use types::OCResult;
+
+#[query(msgpack = true)]
+fn channel_encryption_key_epochs(args: Args) -> Response {
+    match read_state(|state| channel_encryption_key_epochs_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn channel_encryption_key_epochs_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
+    let member = state.get_calling_member(true)?;
+    let channel = state.data.channels.get_or_err(&args.channel_id)?;
+    let epochs = channel.chat.encryption_key_epochs(member.user_id)?;
+
+    Ok(SuccessResult { epochs })
+}
@@ -0,0 +1,49 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use community_canister::chit_leaderboard::{Response::*, *};
+use group_community_common::chit_leaderboard_range;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[query(msgpack = true)]
+fn chit_leaderboard(args: Args) -> Response {
+    match read_state(|state| chit_leaderboard_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn chit_leaderboard_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
+    let caller = state.env.caller();
+    state.data.verify_is_accessible(caller, None)?;
+
+    let range = chit_leaderboard_range(args.window, state.env.now())?;
+    let max_results = args.max_results as usize;
+
+    let leaders = if let Some(channel_id) = args.channel_id {
+        let channel = state.data.channels.get_or_err(&channel_id)?;
+        if !channel.chat.is_public.value {
+            let user_id = state.get_caller_user_id()?;
+            if !channel.chat.members.contains(&user_id) {
+                return Err(OCErrorCode::InitiatorNotInChat.into());
+            }
+        }
+        let members = &channel.chat.members;
+        state
+            .data
+            .chit_leaderboard
+            .leaderboard(range.clone(), max_results, |user_id| members.contains(user_id))
+    } else {
+        let members = &state.data.members;
+        state
+            .data
+            .chit_leaderboard
+            .leaderboard(range.clone(), max_results, |user_id| members.contains(user_id))
+    };
+
+    Ok(SuccessResult {
+        start: range.start,
+        end: range.end,
+        leaders,
+    })
+}
@@ -1,7 +1,8 @@
 use crate::{RuntimeState, read_state};
-use http_request::{AvatarRoute, Route, build_json_response, encode_logs, extract_route, get_document};
+use canister_logger::LogQuery;
+use http_request::{AvatarRoute, Route, build_json_response, encode_logs, encode_metrics, extract_route, get_document};
 use ic_cdk::query;
-use types::{HttpRequest, HttpResponse, TimestampMillis};
+use types::{HttpRequest, HttpResponse};
 
 #[query]
 fn http_request(request: HttpRequest) -> HttpResponse {
@@ -30,20 +31,20 @@ fn http_request(request: HttpRequest) -> HttpResponse {
         get_document(requested_banner_id, state.data.banner.as_ref(), "banner")
     }
 
-    fn get_errors_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_errors(), since.unwrap_or(0))
+    fn get_errors_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_errors(), query)
     }
 
-    fn get_logs_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_logs(), since.unwrap_or(0))
+    fn get_logs_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_logs(), query)
     }
 
-    fn get_traces_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_traces(), since.unwrap_or(0))
+    fn get_traces_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_traces(), query)
     }
 
-    fn get_metrics_impl(state: &RuntimeState) -> HttpResponse {
-        build_json_response(&state.metrics())
+    fn get_metrics_impl(request: &HttpRequest, state: &RuntimeState) -> HttpResponse {
+        encode_metrics(request, &state.metrics())
     }
 
     fn get_timer_jobs(state: &RuntimeState) -> HttpResponse {
@@ -64,10 +65,10 @@ fn http_request(request: HttpRequest) -> HttpResponse {
     match extract_route(&request.url) {
         Route::Avatar(route) => read_state(|state| get_avatar_impl(route, state)),
         Route::Banner(requested_banner_id) => read_state(|state| get_banner_impl(requested_banner_id, state)),
-        Route::Errors(since) => get_errors_impl(since),
-        Route::Logs(since) => get_logs_impl(since),
-        Route::Traces(since) => get_traces_impl(since),
-        Route::Metrics => read_state(get_metrics_impl),
+        Route::Errors(query) => get_errors_impl(query),
+        Route::Logs(query) => get_logs_impl(query),
+        Route::Traces(query) => get_traces_impl(query),
+        Route::Metrics => read_state(|state| get_metrics_impl(&request, state)),
         Route::Other(p, _) if p == "timer_jobs" => read_state(get_timer_jobs),
         Route::Webhook(_) if request.method.eq_ignore_ascii_case("POST") => HttpResponse::upgrade(),
         _ => HttpResponse::not_found(),
@@ -2,13 +2,20 @@ use crate::RuntimeState;
 use types::TimestampMillis;
 
 mod active_proposal_tallies;
+mod audit_log;
 mod c2c_bot_channel_details;
 mod c2c_bot_community_events;
 mod c2c_bot_community_summary;
 mod c2c_bot_members;
+mod c2c_bot_storage_entries;
+mod c2c_can_access_encryption_key;
 mod c2c_can_issue_access_token;
+mod c2c_channel_members;
+mod channel_automod_details;
+mod channel_encryption_key_epochs;
 mod channel_summary;
 mod channel_summary_updates;
+mod chit_leaderboard;
 mod community_events;
 mod deleted_message;
 mod events;
@@ -20,6 +27,7 @@ mod invite_code;
 mod local_user_index;
 mod lookup_members;
 mod messages_by_message_index;
+mod moderation_queue;
 mod search_channel;
 mod selected_channel_initial;
 mod selected_channel_updates;
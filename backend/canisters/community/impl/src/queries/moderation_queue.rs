@@ -0,0 +1,64 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use community_canister::moderation_queue::{Response::*, *};
+use types::OCResult;
+
+const MAX_PENDING: u32 = 100;
+const MAX_RESOLVED: u32 = 100;
+
+#[query(msgpack = true)]
+fn moderation_queue(args: Args) -> Response {
+    match read_state(|state| moderation_queue_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn moderation_queue_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
+    let member = state.get_calling_member(true)?;
+    let max_pending = args.max_pending.min(MAX_PENDING) as usize;
+    let max_resolved = args.max_resolved.min(MAX_RESOLVED) as usize;
+
+    if let Some(channel_id) = args.channel_id {
+        let channel = state.data.channels.get_or_err(&channel_id)?;
+        let summary = channel.chat.moderation_queue(
+            member.user_id,
+            args.pending_after,
+            max_pending,
+            args.resolved_before,
+            max_resolved,
+        )?;
+
+        return Ok(SuccessResult {
+            channels: vec![ChannelModerationQueue {
+                channel_id,
+                pending: summary.pending,
+                pending_count: summary.pending_count,
+                resolved: summary.resolved,
+            }],
+        });
+    }
+
+    // Include every channel in which the caller is able to act on reported messages
+    let channels = state
+        .data
+        .members
+        .channels_for_member(member.user_id)
+        .iter()
+        .filter_map(|channel_id| state.data.channels.get(channel_id))
+        .filter_map(|channel| {
+            channel
+                .chat
+                .moderation_queue(member.user_id, None, max_pending, args.resolved_before, max_resolved)
+                .ok()
+                .map(|summary| ChannelModerationQueue {
+                    channel_id: channel.id,
+                    pending: summary.pending,
+                    pending_count: summary.pending_count,
+                    resolved: summary.resolved,
+                })
+        })
+        .collect();
+
+    Ok(SuccessResult { channels })
+}
@@ -32,6 +32,7 @@ fn selected_channel_initial_impl(args: Args, state: &RuntimeState) -> OCResult<S
     non_basic_members.extend(chat.members.admins().iter().copied());
     non_basic_members.extend(chat.members.moderators().iter().copied());
     non_basic_members.extend(chat.members.lapsed().iter().copied());
+    non_basic_members.extend(chat.members.timed_out().keys().copied());
 
     let mut members = Vec::new();
     let mut basic_members = Vec::new();
@@ -51,6 +51,7 @@ fn selected_initial_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessRe
             added_by: bot.added_by,
             permissions: bot.permissions.clone(),
             autonomous_permissions: bot.autonomous_permissions.clone(),
+            spend_allowances: bot.spend_allowances(),
         })
         .collect();
 
@@ -87,7 +87,7 @@ fn selected_updates_impl(args: Args, state: &RuntimeState) -> Response {
             MemberUpdate::Unblocked => {
                 user_updates_handler.mark_user_blocked_updated(&mut result, user_id, false);
             }
-            MemberUpdate::Lapsed | MemberUpdate::Unlapsed | MemberUpdate::DisplayNameChanged => {
+            MemberUpdate::Lapsed | MemberUpdate::Unlapsed | MemberUpdate::DisplayNameChanged | MemberUpdate::TimedOut => {
                 user_updates_handler.mark_member_updated(&mut result, user_id, false, false);
             }
         }
@@ -104,6 +104,7 @@ fn selected_updates_impl(args: Args, state: &RuntimeState) -> Response {
                             added_by: bot.added_by,
                             permissions: bot.permissions.clone(),
                             autonomous_permissions: bot.autonomous_permissions.clone(),
+                            spend_allowances: bot.spend_allowances(),
                         });
                     }
                 }
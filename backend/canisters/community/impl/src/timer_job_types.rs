@@ -30,6 +30,8 @@ pub enum TimerJob {
     MarkP2PSwapExpired(MarkP2PSwapExpiredJob),
     MarkVideoCallEnded(MarkVideoCallEndedJob),
     JoinMembersToPublicChannel(JoinMembersToPublicChannelJob),
+    LiftExpiredMemberRestrictions(LiftExpiredMemberRestrictionsJob),
+    RunBotScheduledTask(RunBotScheduledTaskJob),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -54,6 +56,15 @@ pub struct EndPollJob {
 #[derive(Serialize, Deserialize, Clone)]
 pub struct RemoveExpiredEventsJob;
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LiftExpiredMemberRestrictionsJob;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RunBotScheduledTaskJob {
+    pub bot_id: UserId,
+    pub name: String,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct FinalizeGroupImportJob {
     pub group_id: ChatId,
@@ -168,6 +179,8 @@ impl Job for TimerJob {
             TimerJob::MarkP2PSwapExpired(job) => job.execute(),
             TimerJob::MarkVideoCallEnded(job) => job.execute(),
             TimerJob::JoinMembersToPublicChannel(job) => job.execute(),
+            TimerJob::LiftExpiredMemberRestrictions(job) => job.execute(),
+            TimerJob::RunBotScheduledTask(job) => job.execute(),
         }
 
         if can_borrow_state {
@@ -287,6 +300,18 @@ impl Job for RemoveExpiredEventsJob {
     }
 }
 
+impl Job for LiftExpiredMemberRestrictionsJob {
+    fn execute(self) {
+        mutate_state(|state| state.run_member_restriction_expiry_job());
+    }
+}
+
+impl Job for RunBotScheduledTaskJob {
+    fn execute(self) {
+        mutate_state(|state| state.run_bot_scheduled_task(self.bot_id, self.name));
+    }
+}
+
 impl Job for FinalizeGroupImportJob {
     fn execute(self) {
         finalize_group_import(self.group_id);
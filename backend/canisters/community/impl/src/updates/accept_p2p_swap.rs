@@ -68,6 +68,8 @@ async fn accept_p2p_swap_impl(args: Args) -> Response {
                                     activity: MessageActivity::P2PSwapAccepted,
                                     timestamp: now,
                                     user_id: Some(user_id),
+                                    sender_name: None,
+                                    message_text: None,
                                 }),
                                 now,
                             );
@@ -135,6 +135,8 @@ fn add_reaction_impl(args: Args, ext_caller: Option<Caller>, state: &mut Runtime
                         activity: MessageActivity::Reaction,
                         timestamp: now,
                         user_id: Some(agent),
+                        sender_name: None,
+                        message_text: None,
                     }),
                     now,
                 );
@@ -0,0 +1,21 @@
+use crate::guards::caller_is_local_user_index;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::c2c_bot_schedule_task::*;
+use types::OCResult;
+
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_schedule_task(args: Args) -> Response {
+    execute_update(|state| c2c_bot_schedule_task_impl(args, state)).into()
+}
+
+fn c2c_bot_schedule_task_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let now = state.env.now();
+    state
+        .data
+        .schedule_bot_task(args.bot_id, args.name, args.interval, args.channel_id, now)
+}
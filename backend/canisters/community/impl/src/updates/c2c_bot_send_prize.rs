@@ -0,0 +1,214 @@
+use crate::guards::caller_is_local_user_index;
+use crate::timer_job_types::{MakeTransferJob, TimerJob};
+use crate::updates::send_message::send_prevalidated_message;
+use crate::{RuntimeState, execute_update_async, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use chat_events::{MessageContentInternal, PrizeContentInternal, ValidateNewMessageContentResult};
+use community_canister::c2c_bot_send_prize::*;
+use community_canister::c2c_send_message::Args as C2CArgs;
+use community_canister::send_message::{Response::*, SuccessResult};
+use constants::{MEMO_PRIZE, MEMO_PRIZE_REFUND, PRIZE_FEE_PERCENT};
+use installed_bots::{BotSpendReservation, PreparedBotTransfer, process_bot_transfer};
+use oc_error_codes::OCErrorCode;
+use types::icrc1::Account;
+use types::{
+    BotCaller, BotPermissions, Caller, CryptoTransaction, MessageContentInitial, MessageContentType, MessagePermission,
+    OCResult, PendingCryptoTransaction, PrizeContentInitial, TimestampNanos, icrc1, icrc2,
+};
+
+#[update(guard = "caller_is_local_user_index", msgpack = true, idempotent = true)]
+#[trace]
+async fn c2c_bot_send_prize(args: Args) -> Response {
+    execute_update_async(|| c2c_bot_send_prize_impl(args)).await
+}
+
+async fn c2c_bot_send_prize_impl(args: Args) -> Response {
+    let prepared = match mutate_state(|state| prepare(&args, state)) {
+        Ok(prepared) => prepared,
+        Err(error) => return Error(error),
+    };
+
+    match process_bot_transfer(
+        prepared,
+        |reservation| mutate_state(|state| state.data.release_bot_spend(reservation)),
+        |transaction, reservation| mutate_state(|state| commit(args, transaction, reservation, state)),
+    )
+    .await
+    {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn prepare(args: &Args, state: &mut RuntimeState) -> OCResult<PreparedBotTransfer> {
+    state.data.verify_not_frozen()?;
+
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        Some(args.channel_id),
+        &args.initiator,
+        &BotPermissions::from_message_permission(MessagePermission::Prize),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    if args.prizes.contains(&0) {
+        return Err(OCErrorCode::InvalidRequest.with_message("Prizes must be non-zero"));
+    }
+
+    let amount =
+        total_amount(&args.prizes, args.fee).ok_or(OCErrorCode::InvalidRequest.with_message("Total prize amount overflows"))?;
+
+    let caller = state.verified_caller(Some(bot_caller(args)))?;
+    let now = state.env.now();
+    let now_nanos = state.env.now_nanos();
+    let this_canister_id = state.env.canister_id();
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    channel.chat.verify_caller(&caller, |_, _| true)?;
+
+    let transaction = icrc2::PendingCryptoTransaction {
+        ledger: args.ledger,
+        token_symbol: args.token_symbol.clone(),
+        amount,
+        from: args.bot_principal.into(),
+        to: this_canister_id.into(),
+        fee: args.fee,
+        memo: Some(MEMO_PRIZE.to_vec().into()),
+        created: now_nanos,
+    };
+
+    let content = MessageContentInitial::Prize(prize_content(
+        args,
+        CryptoTransaction::Pending(PendingCryptoTransaction::ICRC2(transaction.clone())),
+    ));
+    if let ValidateNewMessageContentResult::Error(error) =
+        MessageContentInternal::validate_new_message(content, false, (&caller).into(), false, now)
+    {
+        return Err(error.into());
+    }
+
+    channel.chat.check_can_send_funded_message(
+        &caller,
+        None,
+        args.message_id,
+        MessageContentType::Prize,
+        args.caption.as_deref(),
+        now,
+    )?;
+
+    // The bot also pays the ledger fee for transferring the prizes into this canister
+    let total = amount
+        .checked_add(args.fee)
+        .ok_or(OCErrorCode::InvalidRequest.with_message("Total prize amount overflows"))?;
+    let reservation = state.data.reserve_bot_spend(&args.bot_id, args.ledger, total, now)?;
+
+    Ok(PreparedBotTransfer {
+        transaction,
+        reservation,
+        this_canister_id,
+    })
+}
+
+// The prizes plus the transfer fee for each prize plus OpenChat's fee, or None if this overflows
+fn total_amount(prizes: &[u128], fee: u128) -> Option<u128> {
+    let total_prizes = prizes.iter().try_fold(0u128, |total, prize| total.checked_add(*prize))?;
+    let total_transfer_fees = (prizes.len() as u128).checked_mul(fee)?;
+    let oc_fee = total_prizes.checked_mul(PRIZE_FEE_PERCENT as u128)? / 100;
+    total_prizes.checked_add(total_transfer_fees)?.checked_add(oc_fee)
+}
+
+fn commit(
+    args: Args,
+    transaction: icrc2::CompletedCryptoTransaction,
+    reservation: BotSpendReservation,
+    state: &mut RuntimeState,
+) -> OCResult<SuccessResult> {
+    let caller = bot_caller(&args);
+    let refund = refund_transaction(&args, &transaction, state.env.now_nanos());
+
+    let mut content = PrizeContentInternal::new(
+        prize_content(&args, CryptoTransaction::Completed(transaction.clone().into())),
+        transaction.into(),
+    );
+    // Any unclaimed prizes are refunded to the bot's own account rather than to its user id
+    content.set_refund_account(args.bot_principal.into());
+
+    let result = send_prevalidated_message(
+        C2CArgs {
+            channel_id: args.channel_id,
+            thread_root_message_index: None,
+            message_id: args.message_id,
+            content: MessageContentInternal::Prize(content),
+            sender_name: args.bot_name,
+            sender_display_name: None,
+            replies_to: None,
+            mentioned: Vec::new(),
+            forwarding: false,
+            block_level_markdown: false,
+            community_rules_accepted: None,
+            channel_rules_accepted: None,
+            message_filter_failed: None,
+        },
+        caller,
+        None,
+        state,
+    );
+
+    if result.is_err() {
+        // The prizes are held by this canister, so return them to the bot
+        if let Some(pending_transaction) = refund {
+            let now = state.env.now();
+            state.data.timer_jobs.enqueue_job(
+                TimerJob::MakeTransfer(Box::new(MakeTransferJob {
+                    pending_transaction,
+                    attempt: 0,
+                })),
+                now,
+                now,
+            );
+        }
+        state.data.release_bot_spend(reservation);
+    }
+
+    result
+}
+
+fn prize_content(args: &Args, transfer: CryptoTransaction) -> PrizeContentInitial {
+    PrizeContentInitial {
+        prizes_v2: args.prizes.clone(),
+        transfer,
+        end_date: args.end_date,
+        caption: args.caption.clone(),
+        diamond_only: args.diamond_only,
+        lifetime_diamond_only: args.lifetime_diamond_only,
+        unique_person_only: args.unique_person_only,
+        streak_only: args.streak_only,
+        requires_captcha: args.requires_captcha,
+    }
+}
+
+fn refund_transaction(
+    args: &Args,
+    transaction: &icrc2::CompletedCryptoTransaction,
+    now_nanos: TimestampNanos,
+) -> Option<PendingCryptoTransaction> {
+    let amount = transaction.amount.checked_sub(transaction.fee).filter(|a| *a > 0)?;
+
+    Some(PendingCryptoTransaction::ICRC1(icrc1::PendingCryptoTransaction {
+        ledger: transaction.ledger,
+        token_symbol: transaction.token.token_symbol().to_string(),
+        amount,
+        to: Account::from(args.bot_principal),
+        fee: transaction.fee,
+        memo: Some(MEMO_PRIZE_REFUND.to_vec().into()),
+        created: now_nanos,
+    }))
+}
+
+fn bot_caller(args: &Args) -> Caller {
+    Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator.clone(),
+    })
+}
@@ -13,7 +13,7 @@ use types::{BotPermissions, Caller, CommunityPermission, OCResult, UserId, Users
 
 const MAX_INVITES: usize = 100;
 
-#[update(guard = "caller_is_user_index_or_local_user_index", msgpack = true)]
+#[update(guard = "caller_is_user_index_or_local_user_index", msgpack = true, idempotent = true)]
 #[trace]
 fn c2c_invite_users(args: Args) -> Response {
     match execute_update(|state| invite_users_to_community_impl(args.users, Caller::User(args.caller), state)) {
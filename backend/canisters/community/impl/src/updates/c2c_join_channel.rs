@@ -48,6 +48,7 @@ async fn c2c_join_channel_impl(args: Args) -> Response {
             diamond_membership_expires_at: args.diamond_membership_expires_at,
             verified_credential_args: args.verified_credential_args.clone(),
             unique_person_proof: args.unique_person_proof.clone(),
+            idempotency_key: None,
         })
         .await
         {
@@ -14,7 +14,7 @@ use group_community_common::ExpiringMember;
 use oc_error_codes::OCErrorCode;
 use types::{AccessGate, ChannelId, CommunityCanisterCommunitySummary, OCResult, UsersUnblocked};
 
-#[update(guard = "caller_is_user_index_or_local_user_index", msgpack = true)]
+#[update(guard = "caller_is_user_index_or_local_user_index", msgpack = true, idempotent = true)]
 #[trace]
 async fn c2c_join_community(args: Args) -> Response {
     execute_update_async(|| join_community(args)).await
@@ -0,0 +1,34 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::c2c_set_keyword_alerts::*;
+use oc_error_codes::OCErrorCode;
+use types::{OCResult, UserId};
+
+#[update(msgpack = true)]
+#[trace]
+fn c2c_set_keyword_alerts(args: Args) -> Response {
+    execute_update(|state| c2c_set_keyword_alerts_impl(args, state)).into()
+}
+
+fn c2c_set_keyword_alerts_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let user_id: UserId = state.env.caller().into();
+    if !state.data.members.contains(&user_id) {
+        return Err(OCErrorCode::InitiatorNotInCommunity.into());
+    }
+
+    for channel_id in state.data.members.channels_for_member(user_id).to_vec() {
+        if let Some(channel) = state.data.channels.get_mut(&channel_id) {
+            let mut keywords = args.keywords.clone();
+            if let Some((_, channel_keywords)) = args.channel_keywords.iter().find(|(c, _)| *c == channel_id) {
+                for keyword in channel_keywords {
+                    if !keywords.contains(keyword) {
+                        keywords.push(keyword.clone());
+                    }
+                }
+            }
+            channel.chat.members.set_keyword_alerts(user_id, keywords);
+        }
+    }
+    Ok(())
+}
@@ -1,23 +1,196 @@
 use crate::activity_notifications::handle_activity_notification;
-use crate::{CommunityEventPusher, RuntimeState, execute_update};
+use crate::guards::caller_is_local_user_index;
+use crate::{CommunityEventPusher, RuntimeState, execute_update, execute_update_async, mutate_state};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
-use chat_events::TipMessageArgs;
+use chat_events::{CryptoContentInternal, TipMessageArgs};
+use community_canister::c2c_bot_tip_message;
 use community_canister::c2c_tip_message::*;
+use constants::MEMO_TIP;
+use installed_bots::{PreparedBotTransfer, process_bot_transfer};
 use ledger_utils::format_crypto_amount_with_symbol;
-use types::{Achievement, ChannelMessageTipped, Chat, EventIndex, FcmData, OCResult, UserNotificationPayload};
+use oc_error_codes::OCErrorCode;
+use rand::Rng;
+use tracing::error;
+use types::{
+    Achievement, BotCaller, BotPermissions, Caller, ChannelMessageTipped, Chat, EventIndex, FcmData, MessagePermission,
+    OCResult, UserId, UserNotificationPayload, icrc2,
+};
 use user_canister::{CommunityCanisterEvent, MessageActivity, MessageActivityEvent};
 
-#[update(msgpack = true)]
+#[update(msgpack = true, idempotent = true)]
 #[trace]
 fn c2c_tip_message(args: Args) -> Response {
     execute_update(|state| c2c_tip_message_impl(args, state)).into()
 }
 
+#[update(guard = "caller_is_local_user_index", msgpack = true, idempotent = true)]
+#[trace]
+async fn c2c_bot_tip_message(args: c2c_bot_tip_message::Args) -> c2c_bot_tip_message::Response {
+    execute_update_async(|| c2c_bot_tip_message_impl(args)).await.into()
+}
+
+async fn c2c_bot_tip_message_impl(args: c2c_bot_tip_message::Args) -> OCResult {
+    let prepared = mutate_state(|state| prepare(&args, state))?;
+    let recipient = UserId::from(prepared.transaction.to.owner);
+
+    process_bot_transfer(
+        prepared,
+        |reservation| mutate_state(|state| state.data.release_bot_spend(reservation)),
+        |transaction, _| {
+            mutate_state(|state| commit(args, recipient, transaction, state));
+            Ok(())
+        },
+    )
+    .await
+}
+
+fn prepare(args: &c2c_bot_tip_message::Args, state: &mut RuntimeState) -> OCResult<PreparedBotTransfer> {
+    state.data.verify_not_frozen()?;
+
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        Some(args.channel_id),
+        &args.initiator,
+        &BotPermissions::from_message_permission(MessagePermission::Crypto),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    if args.amount == 0 {
+        return Err(OCErrorCode::TransferCannotBeZero.into());
+    }
+
+    let caller = state.verified_caller(Some(Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator.clone(),
+    })))?;
+    let channel = state.data.channels.get_or_err(&args.channel_id)?;
+    let min_visible_event_index = channel
+        .chat
+        .verify_caller(&caller, |role, permissions| role.can_react_to_messages(permissions))?;
+
+    let now = state.env.now();
+    if let Some(initiator) = caller.initiator() {
+        if channel.chat.members.timed_out_until(&initiator, now).is_some() {
+            return Err(OCErrorCode::InitiatorTimedOut.into());
+        }
+    }
+
+    let Some((message, _)) = channel.chat.events.message_internal(
+        min_visible_event_index,
+        args.thread_root_message_index,
+        args.message_id.into(),
+    ) else {
+        return Err(OCErrorCode::MessageNotFound.into());
+    };
+
+    if message.deleted_by.is_some() {
+        return Err(OCErrorCode::MessageNotFound.into());
+    }
+
+    let recipient = message.sender;
+    if recipient == args.bot_id {
+        return Err(OCErrorCode::TransferCannotBeToSelf.into());
+    }
+
+    // Run the remaining tip checks now since once the transfer is made the funds can't be returned
+    channel
+        .chat
+        .validate_tip(&caller, recipient, args.thread_root_message_index, args.message_id)?;
+
+    // The bot also pays the ledger fee
+    let total = args
+        .amount
+        .checked_add(args.fee)
+        .ok_or(OCErrorCode::InvalidRequest.with_message("Amount plus fee overflows"))?;
+    let reservation = state.data.reserve_bot_spend(&args.bot_id, args.ledger, total, now)?;
+
+    Ok(PreparedBotTransfer {
+        transaction: icrc2::PendingCryptoTransaction {
+            ledger: args.ledger,
+            token_symbol: args.token_symbol.clone(),
+            amount: args.amount,
+            from: args.bot_principal.into(),
+            to: recipient.into(),
+            fee: args.fee,
+            memo: Some(MEMO_TIP.to_vec().into()),
+            created: state.env.now_nanos(),
+        },
+        reservation,
+        this_canister_id: state.env.canister_id(),
+    })
+}
+
+// The transfer has already been made so this always records it, either as the tip or, if the message can no longer be
+// tipped, as a crypto message to the recipient
+fn commit(
+    args: c2c_bot_tip_message::Args,
+    recipient: UserId,
+    transaction: icrc2::CompletedCryptoTransaction,
+    state: &mut RuntimeState,
+) {
+    let bot = BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    };
+
+    let tip_args = Args {
+        channel_id: args.channel_id,
+        recipient,
+        thread_root_message_index: args.thread_root_message_index,
+        message_id: args.message_id,
+        ledger: args.ledger,
+        token_symbol: args.token_symbol,
+        amount: args.amount,
+        decimals: args.decimals,
+        username: args.bot_name,
+        display_name: None,
+        block_index: None,
+    };
+
+    if let Err(error) = tip_message_impl(tip_args, Caller::BotV2(bot.clone()), true, state) {
+        let Some(channel) = state.data.channels.get_mut(&args.channel_id) else {
+            error!(?error, ?transaction, "Failed to record bot tip, channel not found");
+            return;
+        };
+
+        error!(
+            ?error,
+            "Failed to record bot tip, recording the transfer as a message instead"
+        );
+        let now = state.env.now();
+        let message_id = state.env.rng().r#gen::<u64>().into();
+        channel.chat.push_bot_transfer_message(
+            &bot,
+            message_id,
+            CryptoContentInternal {
+                recipient,
+                transfer: transaction.into(),
+                caption: None,
+            },
+            CommunityEventPusher {
+                now,
+                rng: state.env.rng(),
+                queue: &mut state.data.local_user_index_event_sync_queue,
+            },
+            now,
+        );
+        handle_activity_notification(state);
+    }
+}
+
 fn c2c_tip_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     state.data.verify_not_frozen()?;
 
     let user_id = state.get_calling_member(true)?.user_id;
+    tip_message_impl(args, Caller::User(user_id), false, state)
+}
+
+// `prevalidated` is set for bot tips, which are validated before the transfer is made, so that a change to the bot's
+// permissions while the transfer was in flight doesn't stop the tip from being recorded
+fn tip_message_impl(args: Args, caller: Caller, prevalidated: bool, state: &mut RuntimeState) -> OCResult {
+    let user_id = caller.agent();
     let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
     let now = state.env.now();
 
@@ -32,14 +205,16 @@ fn c2c_tip_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         now,
     };
 
-    let result = channel.chat.tip_message(
-        tip_message_args,
-        CommunityEventPusher {
-            now,
-            rng: state.env.rng(),
-            queue: &mut state.data.local_user_index_event_sync_queue,
-        },
-    )?;
+    let event_pusher = CommunityEventPusher {
+        now,
+        rng: state.env.rng(),
+        queue: &mut state.data.local_user_index_event_sync_queue,
+    };
+    let result = if prevalidated {
+        channel.chat.record_prevalidated_tip(tip_message_args, event_pusher)
+    } else {
+        channel.chat.tip_message(&caller, tip_message_args, event_pusher)
+    }?;
 
     if let Some((message, event_index)) =
         channel
@@ -59,6 +234,8 @@ fn c2c_tip_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
                     activity: MessageActivity::Tip,
                     timestamp: now,
                     user_id: Some(user_id),
+                    sender_name: None,
+                    message_text: None,
                 });
 
                 let channel_avatar_id = channel.chat.avatar.as_ref().map(|a| a.id);
@@ -4,24 +4,25 @@ use canister_tracing_macros::trace;
 use community_canister::change_channel_role::*;
 use group_chat_core::GroupRoleInternal;
 use group_community_common::ExpiringMember;
-use types::{GroupRole, OCResult};
+use types::{AuditLogAction, Caller, GroupRole, OCResult};
 
 #[update(msgpack = true)]
 #[trace]
 fn change_channel_role(args: Args) -> Response {
-    execute_update(|state| change_channel_role_impl(args, state)).into()
+    execute_update(|state| change_channel_role_impl(args, None, state)).into()
 }
 
-fn change_channel_role_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+pub(crate) fn change_channel_role_impl(args: Args, ext_caller: Option<Caller>, state: &mut RuntimeState) -> OCResult {
     state.data.verify_not_frozen()?;
 
-    let member = state.get_calling_member(true)?;
+    let caller = state.verified_caller(ext_caller)?;
+    let agent = caller.agent();
     let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
     let now = state.env.now();
 
     let result = channel
         .chat
-        .change_role(member.user_id, args.user_id, args.new_role, false, false, now)?;
+        .change_role(caller, args.user_id, args.new_role, false, false, now)?;
 
     // Owners can't "lapse" so either add or remove user from expiry list if they lose or gain owner status
     if let Some(gate_expiry) = channel.chat.gate_config.value.as_ref().and_then(|gc| gc.expiry()) {
@@ -36,6 +37,14 @@ fn change_channel_role_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         }
     }
 
+    state.data.record_audit_log_entry(
+        Some(args.channel_id),
+        agent,
+        Some(args.user_id),
+        AuditLogAction::GroupRoleChanged(args.new_role),
+        None,
+        now,
+    );
     state.push_bot_notification(result.bot_notification);
     handle_activity_notification(state);
     Ok(())
@@ -1,13 +1,17 @@
+use crate::updates::change_channel_role::change_channel_role_impl;
 use crate::{
-    RuntimeState, activity_notifications::handle_activity_notification, execute_update_async, jobs,
-    model::events::CommunityEventInternal, mutate_state, read_state,
+    RuntimeState, activity_notifications::handle_activity_notification, execute_update, execute_update_async,
+    guards::caller_is_local_user_index, jobs, model::events::CommunityEventInternal, mutate_state, read_state,
 };
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
-use community_canister::change_role::*;
+use community_canister::{c2c_bot_change_role, change_channel_role, change_role::*};
 use group_community_common::ExpiringMember;
 use oc_error_codes::OCErrorCode;
-use types::{CanisterId, CommunityRole, CommunityRoleChanged, OCResult, UserId};
+use types::{
+    AuditLogAction, BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, CommunityPermission, CommunityRole,
+    CommunityRoleChanged, GroupRole, OCResult, UserId,
+};
 use user_index_canister_c2c_client::lookup_user;
 
 #[update(msgpack = true)]
@@ -16,6 +20,69 @@ async fn change_role(args: Args) -> Response {
     execute_update_async(|| change_role_impl(args)).await
 }
 
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_change_role(args: c2c_bot_change_role::Args) -> c2c_bot_change_role::Response {
+    execute_update(|state| c2c_bot_change_role_impl(args, state)).into()
+}
+
+fn c2c_bot_change_role_impl(args: c2c_bot_change_role::Args, state: &mut RuntimeState) -> OCResult {
+    let required_permissions = if args.channel_id.is_some() {
+        BotPermissions::from_chat_permission(ChatPermission::ChangeRoles)
+    } else {
+        BotPermissions::from_community_permission(CommunityPermission::ChangeRoles)
+    };
+
+    if !state
+        .data
+        .is_bot_permitted(&args.bot_id, args.channel_id, &args.initiator, &required_permissions)
+    {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    });
+
+    if let Some(channel_id) = args.channel_id {
+        return change_channel_role_impl(
+            change_channel_role::Args {
+                channel_id,
+                user_id: args.user_id,
+                new_role: args.new_role,
+            },
+            Some(caller),
+            state,
+        );
+    }
+
+    let new_role = match args.new_role {
+        GroupRole::Owner => CommunityRole::Owner,
+        GroupRole::Admin => CommunityRole::Admin,
+        GroupRole::Participant => CommunityRole::Member,
+        GroupRole::Moderator => {
+            return Err(OCErrorCode::InvalidRequest.with_message("Communities do not have a moderator role"));
+        }
+    };
+
+    // Bots can't make users owners or change the role of existing owners, so there is no need to
+    // look up platform moderators
+    if new_role.is_owner()
+        || state
+            .data
+            .members
+            .get_by_user_id(&args.user_id)
+            .is_some_and(|m| m.role().is_owner())
+    {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = state.verified_caller(Some(caller))?;
+
+    commit(args.user_id, new_role, caller, false, false, state)
+}
+
 async fn change_role_impl(args: Args) -> Response {
     let PrepareResult {
         caller_id,
@@ -53,8 +120,9 @@ async fn change_role_impl(args: Args) -> Response {
 
     mutate_state(|state| {
         commit(
-            args,
-            caller_id,
+            args.user_id,
+            args.new_role,
+            Caller::User(caller_id),
             is_caller_platform_moderator,
             is_user_platform_moderator,
             state,
@@ -86,19 +154,21 @@ fn prepare(user_id: UserId, state: &RuntimeState) -> OCResult<PrepareResult> {
 }
 
 fn commit(
-    args: Args,
-    caller_id: UserId,
+    user_id: UserId,
+    new_role: CommunityRole,
+    caller: Caller,
     is_caller_platform_moderator: bool,
     is_user_platform_moderator: bool,
     state: &mut RuntimeState,
 ) -> OCResult {
     state.data.verify_not_frozen()?;
 
+    let caller_id = caller.agent();
     let now = state.env.now();
     let result = state.data.members.change_role(
-        caller_id,
-        args.user_id,
-        args.new_role,
+        caller.initiator(),
+        user_id,
+        new_role,
         &state.data.permissions,
         is_caller_platform_moderator,
         is_user_platform_moderator,
@@ -107,27 +177,35 @@ fn commit(
 
     // Owners can't "lapse" so either add or remove user from expiry list if they lose or gain owner status
     if let Some(gate_expiry) = state.data.gate_config.value.as_ref().and_then(|gc| gc.expiry()) {
-        if matches!(args.new_role, CommunityRole::Owner) {
-            state.data.expiring_members.remove_member(args.user_id, None);
+        if matches!(new_role, CommunityRole::Owner) {
+            state.data.expiring_members.remove_member(user_id, None);
         } else if matches!(result.prev_role, CommunityRole::Owner) {
             state.data.expiring_members.push(ExpiringMember {
                 expires: now + gate_expiry,
                 channel_id: None,
-                user_id: args.user_id,
+                user_id,
             });
         }
     }
 
     let event = CommunityRoleChanged {
-        user_ids: vec![args.user_id],
+        user_ids: vec![user_id],
         old_role: result.prev_role,
-        new_role: args.new_role,
+        new_role: new_role,
         changed_by: caller_id,
     };
     state.push_community_event(CommunityEventInternal::RoleChanged(Box::new(event)));
 
     jobs::expire_members::start_job_if_required(state);
 
+    state.data.record_audit_log_entry(
+        None,
+        caller_id,
+        Some(user_id),
+        AuditLogAction::CommunityRoleChanged(new_role),
+        None,
+        now,
+    );
     handle_activity_notification(state);
     Ok(())
 }
@@ -8,7 +8,9 @@ use community_canister::{c2c_bot_delete_messages, delete_messages::*};
 use constants::MINUTE_IN_MS;
 use ic_principal::Principal;
 use oc_error_codes::OCErrorCode;
-use types::{Achievement, BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, OCResult};
+use types::{
+    Achievement, AuditLogAction, AuditLogMessage, BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, OCResult,
+};
 use user_index_canister_c2c_client::lookup_user;
 
 #[update(msgpack = true)]
@@ -55,6 +57,7 @@ fn c2c_bot_delete_messages_impl(args: c2c_bot_delete_messages::Args, state: &mut
         message_ids: args.message_ids,
         as_platform_moderator: None,
         new_achievement: false,
+        reason: None,
     };
 
     if !state.data.is_bot_permitted(
@@ -127,7 +130,23 @@ fn commit(args: Args, ext_caller: Option<Caller>, state: &mut RuntimeState) -> O
         state.notify_user_of_achievement(agent, Achievement::DeletedMessage, now);
     }
 
-    for result in results.into_iter().filter_map(|(_, result)| result.ok()) {
+    for (message_id, result) in results
+        .into_iter()
+        .filter_map(|(message_id, result)| result.ok().map(|r| (message_id, r)))
+    {
+        if result.sender != agent {
+            state.data.record_audit_log_entry(
+                Some(args.channel_id),
+                agent,
+                Some(result.sender),
+                AuditLogAction::MessageDeleted(AuditLogMessage {
+                    thread_root_message_index: args.thread_root_message_index,
+                    message_id,
+                }),
+                args.reason.clone(),
+                now,
+            );
+        }
         state.push_bot_notification(result.bot_notification);
     }
 
@@ -1,10 +1,13 @@
-use crate::{CommunityEventPusher, RuntimeState, activity_notifications::handle_activity_notification, execute_update};
+use crate::{
+    CommunityEventPusher, RuntimeState, activity_notifications::handle_activity_notification, execute_update,
+    guards::caller_is_local_user_index,
+};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use chat_events::EditMessageArgs;
-use community_canister::edit_message::*;
+use community_canister::{c2c_bot_edit_message, edit_message::*};
 use oc_error_codes::OCErrorCode;
-use types::{Achievement, OCResult};
+use types::{Achievement, BotCaller, BotPermissions, Caller, MessageContentInitial, OCResult};
 
 #[update(msgpack = true)]
 #[trace]
@@ -12,6 +15,57 @@ fn edit_message(args: Args) -> Response {
     execute_update(|state| edit_message_impl(args, state)).into()
 }
 
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_edit_message(args: c2c_bot_edit_message::Args) -> c2c_bot_edit_message::Response {
+    execute_update(|state| c2c_bot_edit_message_impl(args, state)).into()
+}
+
+fn c2c_bot_edit_message_impl(args: c2c_bot_edit_message::Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let content = MessageContentInitial::from(args.content);
+
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        Some(args.channel_id),
+        &args.initiator,
+        &BotPermissions::from_message_permission((&content).into()),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = state.verified_caller(Some(Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    })))?;
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let min_visible_event_index = channel.chat.verify_caller(&caller, |_, _| true)?;
+    let now = state.env.now();
+
+    let result = channel.chat.edit_message(
+        EditMessageArgs {
+            sender: args.bot_id,
+            min_visible_event_index,
+            thread_root_message_index: args.thread,
+            message_id: args.message_id,
+            content: content.into(),
+            block_level_markdown: Some(args.block_level_markdown),
+            finalise_bot_message: false,
+            now,
+        },
+        Some(CommunityEventPusher {
+            now,
+            rng: state.env.rng(),
+            queue: &mut state.data.local_user_index_event_sync_queue,
+        }),
+    )?;
+
+    state.push_bot_notification(result.bot_notification);
+    handle_activity_notification(state);
+    Ok(())
+}
+
 fn edit_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     state.data.verify_not_frozen()?;
 
@@ -23,12 +77,12 @@ fn edit_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     };
 
     let sender = member.user_id;
-    let channel_member = channel.chat.members.get_verified_member(sender)?;
+    let min_visible_event_index = channel.chat.members.get_verified_member(sender)?.min_visible_event_index();
 
-    let result = channel.chat.events.edit_message(
+    let result = match channel.chat.edit_message(
         EditMessageArgs {
             sender,
-            min_visible_event_index: channel_member.min_visible_event_index(),
+            min_visible_event_index,
             thread_root_message_index: args.thread_root_message_index,
             message_id: args.message_id,
             content: args.content.into(),
@@ -41,7 +95,14 @@ fn edit_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
             rng: state.env.rng(),
             queue: &mut state.data.local_user_index_event_sync_queue,
         }),
-    )?;
+    ) {
+        Ok(result) => result,
+        Err(error) => {
+            // The sender may have been timed out by automod
+            state.data.handle_member_restriction_expiry(args.channel_id, now);
+            return Err(error);
+        }
+    };
 
     if args.new_achievement {
         state.notify_user_of_achievement(sender, Achievement::EditedMessage, now);
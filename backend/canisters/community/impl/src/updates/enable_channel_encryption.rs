@@ -0,0 +1,27 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::enable_channel_encryption::{Response::*, *};
+use types::{EncryptionKeyEpoch, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+fn enable_channel_encryption(args: Args) -> Response {
+    match execute_update(|state| enable_channel_encryption_impl(args, state)) {
+        Ok(epoch) => Success(epoch),
+        Err(error) => Error(error),
+    }
+}
+
+fn enable_channel_encryption_impl(args: Args, state: &mut RuntimeState) -> OCResult<EncryptionKeyEpoch> {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+    let now = state.env.now();
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let epoch = channel.chat.enable_encryption(member.user_id, now)?;
+
+    handle_activity_notification(state);
+    Ok(epoch)
+}
@@ -1,9 +1,9 @@
-use crate::{RuntimeState, execute_update};
+use crate::{RuntimeState, execute_update, guards::caller_is_local_user_index};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
-use community_canister::follow_thread::*;
+use community_canister::{c2c_bot_follow_thread, follow_thread::*};
 use oc_error_codes::OCErrorCode;
-use types::{Achievement, OCResult};
+use types::{Achievement, BotCaller, BotPermissions, Caller, ChatPermission, OCResult};
 
 #[update(msgpack = true)]
 #[trace]
@@ -11,6 +11,34 @@ fn follow_thread(args: Args) -> Response {
     execute_update(|state| follow_thread_impl(args, state)).into()
 }
 
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_follow_thread(args: c2c_bot_follow_thread::Args) -> c2c_bot_follow_thread::Response {
+    execute_update(|state| c2c_bot_follow_thread_impl(args, state)).into()
+}
+
+fn c2c_bot_follow_thread_impl(args: c2c_bot_follow_thread::Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        Some(args.channel_id),
+        &args.initiator,
+        &BotPermissions::from_chat_permission(ChatPermission::FollowThreads),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = state.verified_caller(Some(Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    })))?;
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let now = state.env.now();
+
+    channel.chat.follow_thread(caller, args.thread_root_message_index, now)
+}
+
 fn follow_thread_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     state.data.verify_not_frozen()?;
 
@@ -19,7 +47,9 @@ fn follow_thread_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     let user_id = member.user_id;
 
     if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
-        channel.chat.follow_thread(user_id, args.thread_root_message_index, now)?;
+        channel
+            .chat
+            .follow_thread(Caller::User(user_id), args.thread_root_message_index, now)?;
         state.data.mark_community_updated_in_user_canister(user_id);
 
         if args.new_achievement && !member.user_type.is_bot() {
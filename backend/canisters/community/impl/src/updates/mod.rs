@@ -1,7 +1,11 @@
 pub mod accept_p2p_swap;
 pub mod add_members_to_channel;
 pub mod add_reaction;
+pub mod c2c_bot_schedule_task;
+pub mod c2c_bot_send_crypto;
+pub mod c2c_bot_send_prize;
 pub mod c2c_bot_subscribe_to_events;
+pub mod c2c_bot_update_storage;
 pub mod c2c_delete_community;
 pub mod c2c_freeze_community;
 pub mod c2c_install_bot;
@@ -11,7 +15,9 @@ pub mod c2c_join_channel;
 pub mod c2c_join_community;
 pub mod c2c_leave_community;
 pub mod c2c_local_index;
+pub mod c2c_notify_chit_earned;
 pub mod c2c_notify_p2p_swap_status_change;
+pub mod c2c_set_keyword_alerts;
 pub mod c2c_set_user_suspended;
 pub mod c2c_tip_message;
 pub mod c2c_unfreeze_community;
@@ -31,6 +37,7 @@ pub mod delete_messages;
 pub mod delete_user_groups;
 pub mod disable_invite_code;
 pub mod edit_message;
+pub mod enable_channel_encryption;
 pub mod enable_invite_code;
 pub mod end_video_call;
 pub mod follow_thread;
@@ -50,10 +57,15 @@ pub mod remove_member;
 pub mod remove_member_from_channel;
 pub mod remove_reaction;
 pub mod report_message;
+pub mod resolve_report;
+pub mod review_channel_held_message;
 pub mod send_message;
+pub mod set_audit_log_retention;
+pub mod set_channel_automod_rules;
 pub mod set_member_display_name;
 pub mod set_video_call_presence;
 pub mod start_video_call;
+pub mod timeout_channel_member;
 pub mod toggle_mute_notifications;
 pub mod unblock_user;
 pub mod undelete_messages;
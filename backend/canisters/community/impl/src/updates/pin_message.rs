@@ -1,13 +1,20 @@
-use crate::{RuntimeState, activity_notifications::handle_activity_notification, execute_update};
+use crate::{
+    RuntimeState, activity_notifications::handle_activity_notification, execute_update, guards::caller_is_local_user_index,
+};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use community_canister::pin_message::{Response::*, *};
-use types::{OCResult, PushEventResult};
+use community_canister::{c2c_bot_pin_message, c2c_bot_unpin_message};
+use oc_error_codes::OCErrorCode;
+use types::{
+    AuditLogAction, BotCaller, BotInitiator, BotPermissions, Caller, ChannelId, ChatPermission, MessageIndex, OCResult,
+    PushEventResult, UserId,
+};
 
 #[update(msgpack = true)]
 #[trace]
 fn pin_message(args: Args) -> Response {
-    match execute_update(|state| pin_message_impl(args, true, state)) {
+    match execute_update(|state| pin_message_impl(args, true, None, state)) {
         Ok(result) => Success(result),
         Err(error) => Error(error),
     }
@@ -16,25 +23,85 @@ fn pin_message(args: Args) -> Response {
 #[update(msgpack = true)]
 #[trace]
 fn unpin_message(args: Args) -> Response {
-    match execute_update(|state| pin_message_impl(args, false, state)) {
+    match execute_update(|state| pin_message_impl(args, false, None, state)) {
         Ok(result) => Success(result),
         Err(error) => Error(error),
     }
 }
 
-fn pin_message_impl(args: Args, pin: bool, state: &mut RuntimeState) -> OCResult<PushEventResult> {
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_pin_message(args: c2c_bot_pin_message::Args) -> c2c_bot_pin_message::Response {
+    execute_update(|state| {
+        c2c_bot_pin_message_impl(args.bot_id, args.initiator, args.channel_id, args.message_index, true, state)
+    })
+    .into()
+}
+
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_unpin_message(args: c2c_bot_unpin_message::Args) -> c2c_bot_unpin_message::Response {
+    execute_update(|state| {
+        c2c_bot_pin_message_impl(args.bot_id, args.initiator, args.channel_id, args.message_index, false, state)
+    })
+    .into()
+}
+
+fn c2c_bot_pin_message_impl(
+    bot_id: UserId,
+    initiator: BotInitiator,
+    channel_id: ChannelId,
+    message_index: MessageIndex,
+    pin: bool,
+    state: &mut RuntimeState,
+) -> OCResult {
+    if !state.data.is_bot_permitted(
+        &bot_id,
+        Some(channel_id),
+        &initiator,
+        &BotPermissions::from_chat_permission(ChatPermission::PinMessages),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = Caller::BotV2(BotCaller { bot: bot_id, initiator });
+
+    pin_message_impl(
+        Args {
+            channel_id,
+            message_index,
+        },
+        pin,
+        Some(caller),
+        state,
+    )
+    .map(|_| ())
+}
+
+fn pin_message_impl(args: Args, pin: bool, ext_caller: Option<Caller>, state: &mut RuntimeState) -> OCResult<PushEventResult> {
     state.data.verify_not_frozen()?;
 
-    let user_id = state.get_calling_member(true)?.user_id;
+    let caller = state.verified_caller(ext_caller)?;
+    let agent = caller.agent();
     let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
     let now = state.env.now();
 
-    let result = if pin {
-        channel.chat.pin_message(user_id, args.message_index, now)?
+    let (result, audit_log_action) = if pin {
+        (
+            channel.chat.pin_message(caller, args.message_index, now)?,
+            AuditLogAction::MessagePinned(args.message_index),
+        )
     } else {
-        channel.chat.unpin_message(user_id, args.message_index, now)?
+        (
+            channel.chat.unpin_message(caller, args.message_index, now)?,
+            AuditLogAction::MessageUnpinned(args.message_index),
+        )
     };
 
+    state
+        .data
+        .record_audit_log_entry(Some(args.channel_id), agent, None, audit_log_action, None, now);
+
     state.push_bot_notification(result.bot_notification);
     handle_activity_notification(state);
     Ok(PushEventResult {
@@ -54,6 +54,8 @@ fn register_poll_vote_impl(args: Args, state: &mut RuntimeState) -> OCResult<Pol
                         activity: MessageActivity::PollVote,
                         timestamp: now,
                         user_id: matches!(result.value.votes.total, TotalVotes::Visible(_)).then_some(user_id),
+                        sender_name: None,
+                        message_text: None,
                     });
                     state.push_event_to_user(result.value.poll_creator, event, now);
                 }
@@ -12,21 +12,21 @@ use msgpack::serialize_then_unwrap;
 use oc_error_codes::OCErrorCode;
 use std::collections::HashMap;
 use types::{
-    BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, CommunityMembersRemoved, CommunityPermission, CommunityRole,
-    CommunityUsersBlocked, OCResult, UnitResult, UserId,
+    AuditLogAction, BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, CommunityMembersRemoved,
+    CommunityPermission, CommunityRole, CommunityUsersBlocked, OCResult, UnitResult, UserId,
 };
 use user_canister::c2c_remove_from_community;
 
 #[update(msgpack = true)]
 #[trace]
 async fn block_user(args: community_canister::block_user::Args) -> UnitResult {
-    execute_update_async(|| remove_member_impl(args.user_id, true, None)).await
+    execute_update_async(|| remove_member_impl(args.user_id, true, args.reason, None)).await
 }
 
 #[update(msgpack = true)]
 #[trace]
 async fn remove_member(args: Args) -> UnitResult {
-    execute_update_async(|| remove_member_impl(args.user_id, false, None)).await
+    execute_update_async(|| remove_member_impl(args.user_id, false, args.reason, None)).await
 }
 
 #[update(guard = "caller_is_local_user_index", msgpack = true)]
@@ -44,6 +44,8 @@ async fn c2c_bot_remove_user_impl(args: community_canister::c2c_bot_remove_user:
     if !read_state(|state| {
         let required_permissions = if args.channel_id.is_some() {
             BotPermissions::from_chat_permission(ChatPermission::RemoveMembers)
+        } else if args.block {
+            BotPermissions::from_community_permission(CommunityPermission::BlockUsers)
         } else {
             BotPermissions::from_community_permission(CommunityPermission::RemoveMembers)
         };
@@ -56,14 +58,16 @@ async fn c2c_bot_remove_user_impl(args: community_canister::c2c_bot_remove_user:
     }
 
     if let Some(channel_id) = args.channel_id {
-        mutate_state(|state| remove_member_from_channel_impl(channel_id, args.user_id, Some(Caller::BotV2(bot_caller)), state))
-            .into()
+        mutate_state(|state| {
+            remove_member_from_channel_impl(channel_id, args.user_id, None, None, Some(Caller::BotV2(bot_caller)), state)
+        })
+        .into()
     } else {
-        remove_member_impl(args.user_id, args.block, Some(Caller::BotV2(bot_caller))).await
+        remove_member_impl(args.user_id, args.block, None, Some(Caller::BotV2(bot_caller))).await
     }
 }
 
-async fn remove_member_impl(user_id: UserId, block: bool, ext_caller: Option<Caller>) -> UnitResult {
+async fn remove_member_impl(user_id: UserId, block: bool, reason: Option<String>, ext_caller: Option<Caller>) -> UnitResult {
     // Check the caller can remove the user
     let prepare_result = match read_state(|state| prepare(user_id, block, ext_caller, state)) {
         Ok(ok) => ok,
@@ -82,7 +86,7 @@ async fn remove_member_impl(user_id: UserId, block: bool, ext_caller: Option<Cal
     }
 
     // Remove the user from the community
-    mutate_state(|state| commit(user_id, block, prepare_result.removed_by, state));
+    mutate_state(|state| commit(user_id, block, reason, prepare_result.removed_by, state));
 
     Response::Success
 }
@@ -136,7 +140,7 @@ fn prepare(user_id: UserId, block: bool, ext_caller: Option<Caller>, state: &Run
     }
 }
 
-fn commit(user_id: UserId, block: bool, removed_by: UserId, state: &mut RuntimeState) {
+fn commit(user_id: UserId, block: bool, reason: Option<String>, removed_by: UserId, state: &mut RuntimeState) {
     let now = state.env.now();
 
     // Remove the user from the community
@@ -150,24 +154,33 @@ fn commit(user_id: UserId, block: bool, removed_by: UserId, state: &mut RuntimeS
         .map_or(HashMap::new(), |referred_by| HashMap::from_iter([(user_id, referred_by)]));
 
     // Push relevant event
-    let event = if blocked {
+    let (event, audit_log_action) = if blocked {
         let event = CommunityUsersBlocked {
             user_ids: vec![user_id],
             blocked_by: removed_by,
             referred_by,
         };
-        CommunityEventInternal::UsersBlocked(Box::new(event))
+        (
+            CommunityEventInternal::UsersBlocked(Box::new(event)),
+            AuditLogAction::UserBlocked(None),
+        )
     } else if removed {
         let event = CommunityMembersRemoved {
             user_ids: vec![user_id],
             removed_by,
             referred_by,
         };
-        CommunityEventInternal::MembersRemoved(Box::new(event))
+        (
+            CommunityEventInternal::MembersRemoved(Box::new(event)),
+            AuditLogAction::MemberRemoved,
+        )
     } else {
         return;
     };
     state.push_community_event(event);
+    state
+        .data
+        .record_audit_log_entry(None, removed_by, Some(user_id), audit_log_action, reason, now);
 
     handle_activity_notification(state);
 
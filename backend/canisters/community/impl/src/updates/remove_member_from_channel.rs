@@ -3,23 +3,29 @@ use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use community_canister::remove_member_from_channel::*;
 use oc_error_codes::OCErrorCode;
-use types::{Caller, ChannelId, OCResult, UserId};
+use types::{AuditLogAction, Caller, ChannelId, Milliseconds, OCResult, UserId};
 
 #[update(msgpack = true)]
 #[trace]
 fn remove_member_from_channel(args: Args) -> Response {
-    execute_update(|state| remove_member_from_channel_impl(args.channel_id, args.user_id, None, state)).into()
+    execute_update(|state| {
+        remove_member_from_channel_impl(args.channel_id, args.user_id, args.ban_duration, args.reason, None, state)
+    })
+    .into()
 }
 
 pub(crate) fn remove_member_from_channel_impl(
     channel_id: ChannelId,
     user_id: UserId,
+    ban_duration: Option<Milliseconds>,
+    reason: Option<String>,
     ext_caller: Option<Caller>,
     state: &mut RuntimeState,
 ) -> OCResult {
     state.data.verify_not_frozen()?;
 
     let caller = state.verified_caller(ext_caller)?;
+    let agent = caller.agent();
 
     if !state.data.members.contains(&user_id) {
         return Err(OCErrorCode::TargetUserNotInCommunity.into());
@@ -28,8 +34,25 @@ pub(crate) fn remove_member_from_channel_impl(
     let channel = state.data.channels.get_mut_or_err(&channel_id)?;
     let now = state.env.now();
 
-    let bot_notification = channel.chat.remove_member(caller, user_id, false, now)?;
+    let ban_until = ban_duration.map(|d| now.saturating_add(d));
+
+    let bot_notification = channel
+        .chat
+        .remove_member(caller, user_id, ban_until.is_some(), ban_until, now)?;
     state.data.remove_user_from_channel(user_id, channel_id, now);
+    state.data.handle_member_restriction_expiry(channel_id, now);
+    state.data.record_audit_log_entry(
+        Some(channel_id),
+        agent,
+        Some(user_id),
+        if ban_until.is_some() {
+            AuditLogAction::UserBlocked(ban_until)
+        } else {
+            AuditLogAction::MemberRemoved
+        },
+        reason,
+        now,
+    );
     state.push_bot_notification(bot_notification);
     handle_activity_notification(state);
     Ok(())
@@ -6,7 +6,7 @@ use chat_events::Reader;
 use community_canister::report_message::*;
 use group_index_canister::c2c_report_message;
 use oc_error_codes::OCErrorCode;
-use types::{Caller, CanisterId, MultiUserChat, OCResult, UserId};
+use types::{Caller, CanisterId, ChannelMessageReported, FcmData, MultiUserChat, OCResult, UserId, UserNotificationPayload};
 
 #[update(msgpack = true)]
 #[trace]
@@ -20,6 +20,13 @@ async fn report_message_impl(args: Args) -> Response {
         Err(error) => return Response::Error(error),
     };
 
+    // Reports from moderators who are deleting the message need no further action from the channel's moderators
+    if !args.delete {
+        if let Err(error) = mutate_state(|state| queue_report(&args, c2c_args.reporter, state)) {
+            return Response::Error(error);
+        }
+    }
+
     match group_index_canister_c2c_client::c2c_report_message(group_index_canister, &c2c_args).await {
         Ok(result) => {
             if args.delete {
@@ -73,6 +80,45 @@ fn build_c2c_args(args: &Args, state: &RuntimeState) -> OCResult<(c2c_report_mes
     ))
 }
 
+fn queue_report(args: &Args, reporter: UserId, state: &mut RuntimeState) -> OCResult {
+    let now = state.env.now();
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let result = channel.chat.report_message(
+        reporter,
+        args.thread_root_message_index,
+        args.message_id,
+        args.reason.clone(),
+        now,
+    )?;
+
+    if !result.moderators_to_notify.is_empty() {
+        let community_id = state.env.canister_id().into();
+        let channel_avatar_id = channel.chat.avatar.as_ref().map(|a| a.id);
+
+        // TODO i18n
+        let fcm_data = FcmData::for_channel(community_id, channel.id)
+            .set_body(format!("Message reported ({} awaiting review)", result.pending_reports))
+            .set_avatar_id(channel_avatar_id);
+
+        let notification = UserNotificationPayload::ChannelMessageReported(ChannelMessageReported {
+            community_id,
+            channel_id: channel.id,
+            thread_root_message_index: args.thread_root_message_index,
+            message_index: result.message_index,
+            community_name: state.data.name.value.clone(),
+            channel_name: channel.chat.name.value.clone(),
+            pending_reports: result.pending_reports,
+            community_avatar_id: state.data.avatar.as_ref().map(|a| a.id),
+            channel_avatar_id,
+        });
+
+        state.push_notification(None, result.moderators_to_notify, notification, fcm_data);
+    }
+
+    handle_activity_notification(state);
+    Ok(())
+}
+
 fn delete_message(args: &Args, reporter: UserId, state: &mut RuntimeState) {
     if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
         if let Ok(results) = channel.chat.delete_messages(
@@ -0,0 +1,51 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::updates::remove_member_from_channel::remove_member_from_channel_impl;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::resolve_report::*;
+use types::{AuditLogAction, AuditLogReportResolution, ModerationAction, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+fn resolve_report(args: Args) -> Response {
+    execute_update(|state| resolve_report_impl(args, state)).into()
+}
+
+fn resolve_report_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+    let user_id = member.user_id;
+    let now = state.env.now();
+
+    let sender = state
+        .data
+        .channels
+        .get_or_err(&args.channel_id)?
+        .chat
+        .reported_message_sender(user_id, args.report_id)?;
+
+    if matches!(args.action, ModerationAction::RemoveMember) {
+        remove_member_from_channel_impl(args.channel_id, sender, None, None, None, state)?;
+    }
+
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let bot_notification = channel.chat.resolve_report(user_id, args.report_id, args.action, now)?;
+
+    state.data.handle_member_restriction_expiry(args.channel_id, now);
+    state.data.record_audit_log_entry(
+        Some(args.channel_id),
+        user_id,
+        Some(sender),
+        AuditLogAction::ReportResolved(AuditLogReportResolution {
+            report_id: args.report_id,
+            action: args.action,
+        }),
+        None,
+        now,
+    );
+    state.push_bot_notification(bot_notification);
+    handle_activity_notification(state);
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::updates::send_message::register_timer_jobs;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use chat_events::Reader;
+use community_canister::review_channel_held_message::*;
+use types::{AuditLogAction, AuditLogHeldMessageReview, AuditLogMessage, EventIndex, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+fn review_channel_held_message(args: Args) -> Response {
+    execute_update(|state| review_channel_held_message_impl(args, state)).into()
+}
+
+fn review_channel_held_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+    let now = state.env.now();
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let bot_notification = channel.chat.review_held_message(
+        member.user_id,
+        args.thread_root_message_index,
+        args.message_id,
+        args.release,
+        now,
+    )?;
+
+    // Released messages need the timer jobs which were skipped when they were held
+    if args.release {
+        if let Some(message_event) = channel
+            .chat
+            .events
+            .events_reader(EventIndex::default(), args.thread_root_message_index, None)
+            .and_then(|r| r.message_event(args.message_id.into(), None))
+        {
+            register_timer_jobs(
+                args.channel_id,
+                args.thread_root_message_index,
+                &message_event,
+                now,
+                &mut state.data,
+            );
+        }
+    }
+
+    state.data.record_audit_log_entry(
+        Some(args.channel_id),
+        member.user_id,
+        None,
+        AuditLogAction::HeldMessageReviewed(AuditLogHeldMessageReview {
+            message: AuditLogMessage {
+                thread_root_message_index: args.thread_root_message_index,
+                message_id: args.message_id,
+            },
+            released: args.release,
+        }),
+        None,
+        now,
+    );
+    state.push_bot_notification(bot_notification);
+    handle_activity_notification(state);
+    Ok(())
+}
@@ -20,7 +20,7 @@ use std::str::FromStr;
 use types::{
     Achievement, BotCaller, BotPermissions, Caller, ChannelId, ChannelMessageNotification, Chat, CommunityId, EventIndex,
     EventWrapper, FcmData, IdempotentEnvelope, Message, MessageContent, MessageIndex, OCResult, TimestampMillis, User, UserId,
-    UserNotificationPayload, Version,
+    UserNotificationPayload, Version, validate_bot_message_components,
 };
 use user_canister::{CommunityCanisterEvent, MessageActivity, MessageActivityEvent};
 
@@ -42,19 +42,27 @@ fn c2c_send_message(args: C2CArgs) -> C2CResponse {
     }
 }
 
-#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[update(guard = "caller_is_local_user_index", msgpack = true, idempotent = true)]
 #[trace]
 fn c2c_bot_send_message(args: c2c_bot_send_message::Args) -> c2c_bot_send_message::Response {
     execute_update(|state| c2c_bot_send_message_impl(args, state))
 }
 
-fn c2c_bot_send_message_impl(args: c2c_bot_send_message::Args, state: &mut RuntimeState) -> c2c_bot_send_message::Response {
+fn c2c_bot_send_message_impl(mut args: c2c_bot_send_message::Args, state: &mut RuntimeState) -> c2c_bot_send_message::Response {
+    if let Err(error) = validate_bot_message_components(&args.components) {
+        return Error(OCErrorCode::InvalidRequest.with_message(error));
+    }
+
     let finalised = args.finalised;
+    let components = std::mem::take(&mut args.components);
     let bot_caller = BotCaller {
         bot: args.bot_id,
         initiator: args.initiator.clone(),
     };
     let args: Args = args.into();
+    let channel_id = args.channel_id;
+    let thread_root_message_index = args.thread_root_message_index;
+    let message_id = args.message_id;
 
     if !state.data.is_bot_permitted(
         &bot_caller.bot,
@@ -66,7 +74,18 @@ fn c2c_bot_send_message_impl(args: c2c_bot_send_message::Args, state: &mut Runti
     }
 
     match send_message_impl(args, Some(Caller::BotV2(bot_caller)), finalised, state) {
-        Ok(result) => Success(result),
+        Ok(result) => {
+            if !components.is_empty() {
+                let now = state.env.now();
+                if let Some(channel) = state.data.channels.get_mut(&channel_id) {
+                    channel
+                        .chat
+                        .events
+                        .set_bot_message_components(thread_root_message_index, message_id, components, now);
+                }
+            }
+            Success(result)
+        }
         Err(error) => Error(error),
     }
 }
@@ -96,7 +115,7 @@ pub(crate) fn send_message_impl(
 
     let users_mentioned = extract_users_mentioned(args.mentioned, content.text(), &state.data.members);
 
-    let result = channel.chat.send_message(
+    let result = match channel.chat.send_message(
         &caller,
         args.thread_root_message_index,
         args.message_id,
@@ -114,7 +133,14 @@ pub(crate) fn send_message_impl(
         },
         finalised,
         now,
-    )?;
+    ) {
+        Ok(result) => result,
+        Err(error) => {
+            // The sender may have been timed out by automod
+            state.data.handle_member_restriction_expiry(args.channel_id, now);
+            return Err(error);
+        }
+    };
 
     Ok(process_send_message_result(
         result,
@@ -142,11 +168,21 @@ fn c2c_send_message_impl(args: C2CArgs, state: &mut RuntimeState) -> OCResult<Su
         return Err(OCErrorCode::InitiatorNotAuthorized.into());
     }
 
+    send_prevalidated_message(args, caller, display_name, state)
+}
+
+// Sends a message whose content has already been validated, eg. because it contains a completed crypto transfer
+pub(crate) fn send_prevalidated_message(
+    args: C2CArgs,
+    caller: Caller,
+    display_name: Option<String>,
+    state: &mut RuntimeState,
+) -> OCResult<SuccessResult> {
     if let Some(channel) = state.data.channels.get_mut(&args.channel_id) {
         let now = state.env.now();
         let users_mentioned = extract_users_mentioned(args.mentioned, args.content.text(), &state.data.members);
 
-        let result = channel.chat.send_message(
+        let result = match channel.chat.send_message(
             &caller,
             args.thread_root_message_index,
             args.message_id,
@@ -164,7 +200,14 @@ fn c2c_send_message_impl(args: C2CArgs, state: &mut RuntimeState) -> OCResult<Su
             },
             true,
             now,
-        )?;
+        ) {
+            Ok(result) => result,
+            Err(error) => {
+                // The sender may have been timed out by automod
+                state.data.handle_member_restriction_expiry(args.channel_id, now);
+                return Err(error);
+            }
+        };
 
         Ok(process_send_message_result(
             result,
@@ -238,6 +281,20 @@ fn process_send_message_result(
     let message_index = message_event.event.message_index;
     let message_id = message_event.event.message_id;
     let expires_at = message_event.expires_at;
+
+    // Messages removed by automod are only kept so that they can be restored if released by a moderator, so they
+    // don't trigger any timer jobs, achievements, notifications or activity events
+    if result.removed_by_automod {
+        if let Some(expiry) = expires_at {
+            state.data.handle_event_expiry(expiry, now);
+        }
+        return SuccessResult {
+            event_index,
+            message_index,
+            timestamp: now,
+            expires_at,
+        };
+    }
     let content = &message_event.event.content;
     let community_id: CommunityId = state.env.canister_id().into();
 
@@ -250,9 +307,13 @@ fn process_send_message_result(
         let message_text =
             content.notification_text(&users_mentioned.mentioned_directly, &users_mentioned.user_groups_mentioned);
 
+        // Included in mention, reply and keyword activity events so the user canister can fill in outbound notifications
+        let activity_sender_name = sender_display_name.clone().unwrap_or_else(|| sender_username.clone());
+        let activity_message_text = message_text.clone();
+
         // TODO i18n
         let fcm_data = FcmData::for_channel(community_id, channel_id)
-            .set_body_with_alt(&message_text, &message_type)
+            .set_body_with_alt(&message_text, &content.notification_alt_text())
             .set_sender_id(sender)
             .set_sender_name_with_alt(&sender_display_name, &sender_username)
             .set_avatar_id(channel_avatar_id);
@@ -312,6 +373,10 @@ fn process_send_message_result(
                 }
             }
 
+            for user_id in result.keyword_matches {
+                activity_events.push((user_id, MessageActivity::Keyword));
+            }
+
             if let Some(replying_to_event_index) = message_event
                 .event
                 .replies_to
@@ -338,6 +403,7 @@ fn process_send_message_result(
         }
 
         for (user_id, activity) in activity_events {
+            let include_message_details = activity.has_outbound_notification();
             state.data.user_event_sync_queue.push(
                 user_id,
                 IdempotentEnvelope {
@@ -352,6 +418,8 @@ fn process_send_message_result(
                         activity,
                         timestamp: now,
                         user_id: Some(sender),
+                        sender_name: include_message_details.then(|| activity_sender_name.clone()),
+                        message_text: include_message_details.then(|| activity_message_text.clone()).flatten(),
                     }),
                 },
             );
@@ -370,7 +438,7 @@ fn process_send_message_result(
     }
 }
 
-fn register_timer_jobs(
+pub(crate) fn register_timer_jobs(
     channel_id: ChannelId,
     thread_root_message_index: Option<MessageIndex>,
     message_event: &EventWrapper<Message>,
@@ -0,0 +1,33 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::set_channel_automod_rules::*;
+use types::{AuditLogAction, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+fn set_channel_automod_rules(args: Args) -> Response {
+    execute_update(|state| set_channel_automod_rules_impl(args, state)).into()
+}
+
+fn set_channel_automod_rules_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+    let now = state.env.now();
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    channel.chat.set_automod_rules(member.user_id, args.rules, now)?;
+
+    state.data.record_audit_log_entry(
+        Some(args.channel_id),
+        member.user_id,
+        None,
+        AuditLogAction::AutomodRulesUpdated,
+        None,
+        now,
+    );
+
+    handle_activity_notification(state);
+    Ok(())
+}
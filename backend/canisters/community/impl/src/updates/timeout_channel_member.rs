@@ -0,0 +1,37 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::timeout_channel_member::*;
+use types::{AuditLogAction, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+fn timeout_channel_member(args: Args) -> Response {
+    execute_update(|state| timeout_channel_member_impl(args, state)).into()
+}
+
+fn timeout_channel_member_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+    let now = state.env.now();
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let bot_notification = channel
+        .chat
+        .timeout_member(member.user_id, args.user_id, args.duration, now)?;
+
+    let until = args.duration.map(|d| now.saturating_add(d));
+    state.data.handle_member_restriction_expiry(args.channel_id, now);
+    state.data.record_audit_log_entry(
+        Some(args.channel_id),
+        member.user_id,
+        Some(args.user_id),
+        AuditLogAction::MemberTimedOut(until),
+        args.reason,
+        now,
+    );
+    state.push_bot_notification(bot_notification);
+    handle_activity_notification(state);
+    Ok(())
+}
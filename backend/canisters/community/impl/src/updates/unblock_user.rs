@@ -5,7 +5,7 @@ use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use community_canister::unblock_user::*;
 use oc_error_codes::OCErrorCode;
-use types::{OCResult, UsersUnblocked};
+use types::{AuditLogAction, OCResult, UsersUnblocked};
 
 #[update(msgpack = true)]
 #[trace]
@@ -35,6 +35,14 @@ fn unblock_user_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         };
 
         state.push_community_event(CommunityEventInternal::UsersUnblocked(Box::new(event)));
+        state.data.record_audit_log_entry(
+            None,
+            caller_member.user_id,
+            Some(args.user_id),
+            AuditLogAction::UserUnblocked,
+            None,
+            now,
+        );
 
         handle_activity_notification(state);
         Ok(())
@@ -3,7 +3,7 @@ use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use community_canister::undelete_messages::{Response::*, *};
 use std::collections::HashSet;
-use types::OCResult;
+use types::{AuditLogAction, AuditLogMessage, OCResult};
 
 #[update(msgpack = true)]
 #[trace]
@@ -34,6 +34,20 @@ fn undelete_messages_impl(args: Args, state: &mut RuntimeState) -> OCResult<Succ
         .map(|success| (success.message, success.bot_notification))
         .unzip();
 
+    for message in messages.iter().filter(|m| m.sender != member.user_id) {
+        state.data.record_audit_log_entry(
+            Some(args.channel_id),
+            member.user_id,
+            Some(message.sender),
+            AuditLogAction::MessageUndeleted(AuditLogMessage {
+                thread_root_message_index: args.thread_root_message_index,
+                message_id: message.message_id,
+            }),
+            None,
+            now,
+        );
+    }
+
     let message_ids: HashSet<_> = messages.iter().map(|m| m.message_id).collect();
     state.data.timer_jobs.cancel_jobs(|job| {
         if let TimerJob::HardDeleteMessageContent(j) = job {
@@ -1,8 +1,9 @@
-use crate::{RuntimeState, execute_update};
+use crate::{RuntimeState, execute_update, guards::caller_is_local_user_index};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
-use community_canister::unfollow_thread::*;
-use types::OCResult;
+use community_canister::{c2c_bot_unfollow_thread, unfollow_thread::*};
+use oc_error_codes::OCErrorCode;
+use types::{BotCaller, BotPermissions, Caller, ChatPermission, OCResult};
 
 #[update(msgpack = true)]
 #[trace]
@@ -10,6 +11,34 @@ fn unfollow_thread(args: Args) -> Response {
     execute_update(|state| unfollow_thread_impl(args, state)).into()
 }
 
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_unfollow_thread(args: c2c_bot_unfollow_thread::Args) -> c2c_bot_unfollow_thread::Response {
+    execute_update(|state| c2c_bot_unfollow_thread_impl(args, state)).into()
+}
+
+fn c2c_bot_unfollow_thread_impl(args: c2c_bot_unfollow_thread::Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        Some(args.channel_id),
+        &args.initiator,
+        &BotPermissions::from_chat_permission(ChatPermission::FollowThreads),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = state.verified_caller(Some(Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    })))?;
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let now = state.env.now();
+
+    channel.chat.unfollow_thread(caller, args.thread_root_message_index, now)
+}
+
 fn unfollow_thread_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     state.data.verify_not_frozen()?;
 
@@ -17,7 +46,9 @@ fn unfollow_thread_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
     let now = state.env.now();
 
-    channel.chat.unfollow_thread(user_id, args.thread_root_message_index, now)?;
+    channel
+        .chat
+        .unfollow_thread(Caller::User(user_id), args.thread_root_message_index, now)?;
     state.data.mark_community_updated_in_user_canister(user_id);
     Ok(())
 }
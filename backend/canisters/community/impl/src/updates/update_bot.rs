@@ -5,7 +5,11 @@ use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use community_canister::update_bot::*;
 use oc_error_codes::OCErrorCode;
-use types::{BotEvent, BotInstallationLocation, BotInstalledEvent, BotLifecycleEvent, BotNotification, BotUpdated, OCResult};
+use rand::RngCore;
+use types::{
+    BotEvent, BotInstallationLocation, BotInstalledEvent, BotLifecycleEvent, BotNotification, BotPermissions, BotUpdated,
+    IdempotentEnvelope, OCResult,
+};
 
 #[update(msgpack = true)]
 #[trace]
@@ -28,11 +32,26 @@ fn update_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         args.bot_id,
         args.granted_permissions.clone(),
         args.granted_autonomous_permissions.clone(),
+        args.spend_allowances,
         now,
     ) {
         return Err(OCErrorCode::BotNotFound.into());
     }
 
+    // Let the user index know which permissions the installer has now consented to
+    state.data.local_user_index_event_sync_queue.push(IdempotentEnvelope {
+        created_at: now,
+        idempotency_id: state.env.rng().next_u64(),
+        value: local_user_index_canister::CommunityEvent::BotPermissionsUpdated(
+            args.bot_id,
+            args.granted_permissions.union(
+                args.granted_autonomous_permissions
+                    .as_ref()
+                    .unwrap_or(&BotPermissions::default()),
+            ),
+        ),
+    });
+
     state.push_community_event(CommunityEventInternal::BotUpdated(Box::new(BotUpdated {
         user_id: args.bot_id,
         updated_by: member.user_id,
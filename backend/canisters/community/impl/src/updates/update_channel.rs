@@ -1,23 +1,64 @@
 use crate::jobs;
 use crate::timer_job_types::JoinMembersToPublicChannelJob;
-use crate::{RuntimeState, activity_notifications::handle_activity_notification, execute_update};
+use crate::{
+    RuntimeState, activity_notifications::handle_activity_notification, execute_update, guards::caller_is_local_user_index,
+};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
+use community_canister::c2c_bot_update_chat_details;
 use community_canister::update_channel::{Response::*, *};
 use oc_error_codes::OCErrorCode;
-use types::{OCResult, OptionUpdate};
+use types::{AuditLogAction, BotCaller, BotPermissions, Caller, ChatPermission, OCResult, OptionUpdate};
 use url::Url;
 
 #[update(msgpack = true)]
 #[trace]
 fn update_channel(args: Args) -> Response {
-    match execute_update(|state| update_channel_impl(args, state)) {
+    match execute_update(|state| update_channel_impl(args, None, state)) {
         Ok(result) => SuccessV2(result),
         Err(error) => Error(error),
     }
 }
 
-fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> OCResult<SuccessResult> {
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_update_chat_details(args: c2c_bot_update_chat_details::Args) -> c2c_bot_update_chat_details::Response {
+    execute_update(|state| c2c_bot_update_chat_details_impl(args, state)).into()
+}
+
+fn c2c_bot_update_chat_details_impl(args: c2c_bot_update_chat_details::Args, state: &mut RuntimeState) -> OCResult {
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        Some(args.channel_id),
+        &args.initiator,
+        &BotPermissions::from_chat_permission(ChatPermission::UpdateGroup),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    });
+
+    let args = Args {
+        channel_id: args.channel_id,
+        name: args.name,
+        description: args.description,
+        rules: args.rules,
+        avatar: args.avatar,
+        permissions_v2: None,
+        events_ttl: OptionUpdate::NoChange,
+        gate_config: OptionUpdate::NoChange,
+        public: None,
+        messages_visible_to_non_members: None,
+        external_url: OptionUpdate::NoChange,
+    };
+
+    update_channel_impl(args, Some(caller), state).map(|_| ())
+}
+
+fn update_channel_impl(mut args: Args, ext_caller: Option<Caller>, state: &mut RuntimeState) -> OCResult<SuccessResult> {
     state.data.verify_not_frozen()?;
 
     clean_args(&mut args);
@@ -40,14 +81,16 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> OCResult<Suc
         }
     }
 
-    let member = state.get_calling_member(true)?;
+    let caller = state.verified_caller(ext_caller)?;
+    let agent = caller.agent();
     let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
     let now = state.env.now();
     let has_gate_config_updates = args.gate_config.has_update();
     let prev_gate_config = channel.chat.gate_config.value.clone();
+    let audit_log_actions = audit_log_actions(&args);
 
     let result = channel.chat.update(
-        member.user_id,
+        caller,
         args.name,
         args.description,
         args.rules,
@@ -93,6 +136,12 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> OCResult<Suc
         state.data.public_channel_list_updated = now;
     }
 
+    for action in audit_log_actions {
+        state
+            .data
+            .record_audit_log_entry(Some(args.channel_id), agent, None, action, None, now);
+    }
+
     state.push_bot_notifications(result.bot_notifications);
     handle_activity_notification(state);
 
@@ -101,6 +150,28 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> OCResult<Suc
     })
 }
 
+fn audit_log_actions(args: &Args) -> Vec<AuditLogAction> {
+    let mut actions = Vec::new();
+    if args.name.is_some()
+        || args.description.is_some()
+        || args.avatar.has_update()
+        || args.events_ttl.has_update()
+        || args.gate_config.has_update()
+        || args.public.is_some()
+        || args.messages_visible_to_non_members.is_some()
+        || args.external_url.has_update()
+    {
+        actions.push(AuditLogAction::DetailsUpdated);
+    }
+    if args.rules.is_some() {
+        actions.push(AuditLogAction::RulesUpdated);
+    }
+    if args.permissions_v2.is_some() {
+        actions.push(AuditLogAction::PermissionsUpdated);
+    }
+    actions
+}
+
 fn clean_args(args: &mut Args) {
     args.name = args.name.as_ref().map(|name| name.trim().to_string());
     args.description = args.description.as_ref().map(|desc| desc.trim().to_string());
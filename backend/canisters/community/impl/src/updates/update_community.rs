@@ -9,7 +9,7 @@ use group_index_canister::{c2c_make_community_private, c2c_update_community};
 use oc_error_codes::OCErrorCode;
 use tracing::error;
 use types::{
-    AccessGateConfigInternal, AvatarChanged, BannerChanged, CanisterId, CommunityId, CommunityPermissions,
+    AccessGateConfigInternal, AuditLogAction, AvatarChanged, BannerChanged, CanisterId, CommunityId, CommunityPermissions,
     CommunityPermissionsChanged, CommunityVisibilityChanged, Document, GroupDescriptionChanged, GroupNameChanged,
     GroupRulesChanged, OCResult, OptionUpdate, OptionalCommunityPermissions, PrimaryLanguageChanged, Timestamped, UserId,
 };
@@ -199,6 +199,7 @@ fn commit(my_user_id: UserId, args: Args, state: &mut RuntimeState) -> SuccessRe
     let mut result = SuccessResult { rules_version: None };
 
     let now = state.env.now();
+    let audit_log_actions = audit_log_actions(&args);
 
     // If a verified community changes its name or becomes private it loses it's verified status
     if state.data.verified.value {
@@ -360,10 +361,34 @@ fn commit(my_user_id: UserId, args: Args, state: &mut RuntimeState) -> SuccessRe
 
     jobs::expire_members::restart_job(state);
 
+    for action in audit_log_actions {
+        state.data.record_audit_log_entry(None, my_user_id, None, action, None, now);
+    }
     handle_activity_notification(state);
     result
 }
 
+fn audit_log_actions(args: &Args) -> Vec<AuditLogAction> {
+    let mut actions = Vec::new();
+    if args.name.is_some()
+        || args.description.is_some()
+        || args.avatar.has_update()
+        || args.banner.has_update()
+        || args.gate_config.has_update()
+        || args.public.is_some()
+        || args.primary_language.is_some()
+    {
+        actions.push(AuditLogAction::DetailsUpdated);
+    }
+    if args.rules.is_some() {
+        actions.push(AuditLogAction::RulesUpdated);
+    }
+    if args.permissions.is_some() {
+        actions.push(AuditLogAction::PermissionsUpdated);
+    }
+    actions
+}
+
 fn merge_permissions(new: OptionalCommunityPermissions, old: &CommunityPermissions) -> CommunityPermissions {
     CommunityPermissions {
         change_roles: new.change_roles.unwrap_or(old.change_roles),
@@ -1,7 +1,10 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::canisters::{CanisterMetrics, Canisters};
 use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
 use ic_ledger_types::{BlockIndex, Tokens};
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use ledger_utils::default_ledger_account;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
@@ -58,6 +61,7 @@ impl State {
             min_cycles_balance: self.data.min_cycles_balance,
             icp_burn_amount: self.data.icp_burn_amount,
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
             canister_ids: CanisterIds {
                 registry: self.data.registry_canister_id,
                 ledger: self.data.ledger_canister,
@@ -82,6 +86,12 @@ struct Data {
     pub cycles_top_up_pending_notification: Option<BlockIndex>,
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -113,11 +123,21 @@ impl Data {
             cycles_top_up_pending_notification: None,
             rng_seed: [0; 32],
             test_mode,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
-#[derive(CandidType, Serialize, Debug)]
+#[derive(Serialize, Debug)]
 pub struct Metrics {
     pub now: TimestampMillis,
     pub heap_memory_used: u64,
@@ -134,9 +154,17 @@ pub struct Metrics {
     pub min_cycles_balance: Cycles,
     pub icp_burn_amount: Tokens,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(CandidType, Serialize, Debug)]
 pub struct CanisterIds {
     registry: CanisterId,
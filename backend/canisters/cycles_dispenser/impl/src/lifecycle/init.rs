@@ -1,5 +1,6 @@
 use crate::Data;
 use crate::lifecycle::{init_env, init_state};
+use crate::memory::get_canister_logs_memory;
 use canister_tracing_macros::trace;
 use cycles_dispenser_canister::init::Args;
 use ic_cdk::init;
@@ -10,7 +11,7 @@ use utils::env::Environment;
 #[init]
 #[trace]
 fn init(args: Args) {
-    canister_logger::init(args.test_mode);
+    canister_logger::init(args.test_mode, get_canister_logs_memory());
 
     let env = init_env([0; 32]);
 
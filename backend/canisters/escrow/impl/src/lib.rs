@@ -1,9 +1,12 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::notify_status_change_queue::NotifyStatusChangeQueue;
 use crate::model::pending_payments_queue::PendingPaymentsQueue;
 use crate::model::swaps::Swaps;
 use crate::timer_job_types::TimerJob;
 use canister_state_macros::canister_state;
 use canister_timer_jobs::TimerJobs;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet};
@@ -52,6 +55,7 @@ impl RuntimeState {
             swaps: self.data.swaps.metrics(now),
             notify_status_change_queue_len: self.data.notify_status_change_queue.len() as u32,
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
             disabled_tokens: self.data.disabled_tokens.iter().copied().collect(),
             canister_ids: CanisterIds {
                 registry: self.data.registry_canister_id,
@@ -72,6 +76,12 @@ struct Data {
     pub disabled_tokens: BTreeSet<CanisterId>,
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -86,8 +96,18 @@ impl Data {
             disabled_tokens: BTreeSet::new(),
             rng_seed: [0; 32],
             test_mode,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -101,10 +121,18 @@ pub struct Metrics {
     pub swaps: SwapMetrics,
     pub notify_status_change_queue_len: u32,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
     pub disabled_tokens: Vec<CanisterId>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize, Debug, Default)]
 pub struct SwapMetrics {
     pub total: u32,
@@ -1,26 +1,27 @@
 use crate::{RuntimeState, read_state};
-use http_request::{Route, build_json_response, encode_logs, extract_route};
+use canister_logger::LogQuery;
+use http_request::{Route, build_json_response, encode_logs, encode_metrics, extract_route};
 use ic_cdk::query;
 use std::collections::HashMap;
 use std::str::FromStr;
-use types::{HttpRequest, HttpResponse, TimestampMillis};
+use types::{HttpRequest, HttpResponse};
 
 #[query]
 fn http_request(request: HttpRequest) -> HttpResponse {
-    fn get_errors_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_errors(), since.unwrap_or(0))
+    fn get_errors_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_errors(), query)
     }
 
-    fn get_logs_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_logs(), since.unwrap_or(0))
+    fn get_logs_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_logs(), query)
     }
 
-    fn get_traces_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_traces(), since.unwrap_or(0))
+    fn get_traces_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_traces(), query)
     }
 
-    fn get_metrics_impl(state: &RuntimeState) -> HttpResponse {
-        build_json_response(&state.metrics())
+    fn get_metrics_impl(request: &HttpRequest, state: &RuntimeState) -> HttpResponse {
+        encode_metrics(request, &state.metrics())
     }
 
     fn get_swap_logs(qs: HashMap<String, String>, state: &RuntimeState) -> HttpResponse {
@@ -34,10 +35,10 @@ fn http_request(request: HttpRequest) -> HttpResponse {
     }
 
     match extract_route(&request.url) {
-        Route::Errors(since) => get_errors_impl(since),
-        Route::Logs(since) => get_logs_impl(since),
-        Route::Traces(since) => get_traces_impl(since),
-        Route::Metrics => read_state(get_metrics_impl),
+        Route::Errors(query) => get_errors_impl(query),
+        Route::Logs(query) => get_logs_impl(query),
+        Route::Traces(query) => get_traces_impl(query),
+        Route::Metrics => read_state(|state| get_metrics_impl(&request, state)),
         Route::Other(p, qs) if p == "swap_logs" => read_state(|state| get_swap_logs(qs, state)),
         _ => HttpResponse::not_found(),
     }
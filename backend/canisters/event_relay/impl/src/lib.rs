@@ -1,9 +1,12 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use candid::Principal;
 use canister_state_macros::canister_state;
 use event_store_producer::{EventStoreClient, EventStoreClientBuilder, EventStoreClientInfo};
 use event_store_producer_cdk_runtime::CdkRuntime;
 use event_store_utils::EventDeduper;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
 use icrc_ledger_types::icrc1::account::Account;
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use serde::{Deserialize, Serialize};
 use sha256::sha256;
 use std::cell::RefCell;
@@ -59,6 +62,7 @@ impl RuntimeState {
             event_store_client_info,
             ledger_transaction_processed_up_to: self.data.ledger_transaction_processed_up_to,
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
             canister_ids: CanisterIds {
                 event_sink: event_store_canister_id,
                 cycles_dispenser: self.data.cycles_dispenser_canister_id,
@@ -83,6 +87,12 @@ struct Data {
     pub ledger_transaction_processed_up_to: Option<u64>,
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -109,6 +119,7 @@ impl Data {
             ledger_transaction_processed_up_to: None,
             rng_seed: [0; 32],
             test_mode,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 
@@ -118,6 +129,15 @@ impl Data {
             subaccount: Some(self.chat_treasury_subaccount),
         }
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -132,9 +152,17 @@ pub struct Metrics {
     pub event_store_client_info: EventStoreClientInfo,
     pub ledger_transaction_processed_up_to: Option<u64>,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct CanisterIds {
     pub event_sink: CanisterId,
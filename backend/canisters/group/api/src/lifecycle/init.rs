@@ -26,6 +26,7 @@ pub struct Args {
     pub proposals_bot_user_id: UserId,
     pub escrow_canister_id: CanisterId,
     pub internet_identity_canister_id: CanisterId,
+    pub identity_canister_id: CanisterId,
     pub gate_config: Option<AccessGateConfig>,
     pub video_call_operators: Vec<Principal>,
     #[serde(with = "serde_bytes")]
@@ -8,13 +8,18 @@ fn main() {
     }
 
     generate_ts_method!(group, active_proposal_tallies);
+    generate_ts_method!(group, audit_log);
+    generate_ts_method!(group, automod_details);
+    generate_ts_method!(group, chit_leaderboard);
     generate_ts_method!(group, deleted_message);
+    generate_ts_method!(group, encryption_key_epochs);
     generate_ts_method!(group, events);
     generate_ts_method!(group, events_by_index);
     generate_ts_method!(group, events_window);
     generate_ts_method!(group, invite_code);
     generate_ts_method!(group, local_user_index);
     generate_ts_method!(group, messages_by_message_index);
+    generate_ts_method!(group, moderation_queue);
     generate_ts_method!(group, thread_previews);
     generate_ts_method!(group, public_summary);
     generate_ts_method!(group, rules);
@@ -39,6 +44,7 @@ fn main() {
     generate_ts_method!(group, delete_webhook);
     generate_ts_method!(group, disable_invite_code);
     generate_ts_method!(group, edit_message_v2);
+    generate_ts_method!(group, enable_encryption);
     generate_ts_method!(group, enable_invite_code);
     generate_ts_method!(group, follow_thread);
     generate_ts_method!(group, join_video_call);
@@ -52,8 +58,13 @@ fn main() {
     generate_ts_method!(group, remove_reaction);
     generate_ts_method!(group, report_message);
     generate_ts_method!(group, reset_invite_code);
+    generate_ts_method!(group, resolve_report);
+    generate_ts_method!(group, review_held_message);
     generate_ts_method!(group, send_message_v2);
+    generate_ts_method!(group, set_audit_log_retention);
+    generate_ts_method!(group, set_automod_rules);
     generate_ts_method!(group, set_video_call_presence);
+    generate_ts_method!(group, timeout_member);
     generate_ts_method!(group, toggle_mute_notifications);
     generate_ts_method!(group, unblock_user);
     generate_ts_method!(group, undelete_messages);
@@ -0,0 +1,27 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AuditLogEntry, AuditLogRetention};
+
+#[ts_export(group, audit_log)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Only entries with an index lower than this are returned
+    pub before: Option<u32>,
+    pub max_results: u32,
+}
+
+#[ts_export(group, audit_log)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(group, audit_log)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub entries: Vec<AuditLogEntry>,
+    pub latest_index: Option<u32>,
+    pub retention: AuditLogRetention,
+}
@@ -0,0 +1,13 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AutomodDetails, Empty};
+
+pub type Args = Empty;
+
+#[ts_export(group, automod_details)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(AutomodDetails),
+    Error(OCError),
+}
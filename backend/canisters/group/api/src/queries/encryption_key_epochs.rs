@@ -0,0 +1,19 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Empty, EncryptionKeyEpoch};
+
+pub type Args = Empty;
+
+#[ts_export(group, encryption_key_epochs)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(group, encryption_key_epochs)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub epochs: Vec<EncryptionKeyEpoch>,
+}
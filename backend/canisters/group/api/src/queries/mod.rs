@@ -1,7 +1,11 @@
 pub mod active_proposal_tallies;
+pub mod audit_log;
+pub mod automod_details;
 pub mod c2c_active_proposal_tallies;
 pub mod c2c_bot_group_details;
 pub mod c2c_bot_members;
+pub mod c2c_bot_storage_entries;
+pub mod c2c_can_access_encryption_key;
 pub mod c2c_can_issue_access_token_v2;
 pub mod c2c_events;
 pub mod c2c_events_by_index;
@@ -9,13 +13,16 @@ pub mod c2c_events_window;
 pub mod c2c_name_and_members;
 pub mod c2c_summary;
 pub mod c2c_summary_updates;
+pub mod chit_leaderboard;
 pub mod deleted_message;
+pub mod encryption_key_epochs;
 pub mod events;
 pub mod events_by_index;
 pub mod events_window;
 pub mod invite_code;
 pub mod local_user_index;
 pub mod messages_by_message_index;
+pub mod moderation_queue;
 pub mod public_summary;
 pub mod rules;
 pub mod search_messages;
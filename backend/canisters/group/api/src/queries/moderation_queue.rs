@@ -0,0 +1,29 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ModerationQueueItem, ResolvedModerationItem, TimestampMillis};
+
+#[ts_export(group, moderation_queue)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Pending items are returned in the order they were reported, starting after this id
+    pub pending_after: Option<u32>,
+    pub max_pending: u32,
+    pub resolved_before: Option<TimestampMillis>,
+    pub max_resolved: u32,
+}
+
+#[ts_export(group, moderation_queue)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(group, moderation_queue)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub pending: Vec<ModerationQueueItem>,
+    pub pending_count: u32,
+    pub resolved: Vec<ResolvedModerationItem>,
+}
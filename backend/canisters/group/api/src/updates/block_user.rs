@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{UnitResult, UserId};
+use types::{Milliseconds, UnitResult, UserId};
 
 #[ts_export(group, block_user)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
     pub user_id: UserId,
+    // If set, the user will be unblocked automatically once the duration has passed
+    pub duration: Option<Milliseconds>,
+    // Recorded in the group's audit log
+    pub reason: Option<String>,
 }
 
 pub type Response = UnitResult;
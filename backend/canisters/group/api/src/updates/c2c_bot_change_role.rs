@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use types::{BotInitiator, GroupRole, UnitResult, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub initiator: BotInitiator,
+    pub user_id: UserId,
+    pub new_role: GroupRole,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use types::{BotInitiator, BotMessageContent, MessageId, MessageIndex, UnitResult, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub initiator: BotInitiator,
+    pub thread: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub content: BotMessageContent,
+    pub block_level_markdown: bool,
+}
+
+pub type Response = UnitResult;
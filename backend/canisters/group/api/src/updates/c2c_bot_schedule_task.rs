@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use types::{Milliseconds, UnitResult, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub name: String,
+    // If not set, the task is cancelled
+    pub interval: Option<Milliseconds>,
+}
+
+pub type Response = UnitResult;
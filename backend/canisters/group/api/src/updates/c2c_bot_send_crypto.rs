@@ -0,0 +1,35 @@
+use crate::send_message_v2;
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use types::{BotInitiator, CanisterId, IdempotentArgs, MessageId, MessageIndex, UserId, idempotency_key};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub bot_principal: Principal,
+    pub initiator: BotInitiator,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub recipient: UserId,
+    pub ledger: CanisterId,
+    pub token_symbol: String,
+    pub amount: u128,
+    pub fee: u128,
+    pub caption: Option<String>,
+    pub bot_name: String,
+    pub block_level_markdown: bool,
+}
+
+// Each message Id can only be used once, so a request with the same message Id is a retry
+impl IdempotentArgs for Args {
+    fn idempotency_key(&self) -> Option<u64> {
+        Some(idempotency_key((
+            "c2c_bot_send_crypto",
+            self.bot_id,
+            self.thread_root_message_index,
+            self.message_id,
+        )))
+    }
+}
+
+pub type Response = send_message_v2::Response;
@@ -1,6 +1,9 @@
 use crate::send_message_v2;
 use serde::{Deserialize, Serialize};
-use types::{BotInitiator, BotMessageContent, EventIndex, GroupReplyContext, MessageId, MessageIndex, UserId};
+use types::{
+    BotInitiator, BotMessageComponent, BotMessageContent, EventIndex, GroupReplyContext, IdempotentArgs, MessageId,
+    MessageIndex, UserId, idempotency_key,
+};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
@@ -13,6 +16,16 @@ pub struct Args {
     pub bot_name: String,
     pub block_level_markdown: bool,
     pub finalised: bool,
+    #[serde(default)]
+    pub components: Vec<BotMessageComponent>,
+}
+
+// Unfinalised messages are overwritten by subsequent calls, so only finalised messages need deduplicating
+impl IdempotentArgs for Args {
+    fn idempotency_key(&self) -> Option<u64> {
+        self.finalised
+            .then(|| idempotency_key((self.bot_id, self.thread_root_message_index, self.message_id)))
+    }
 }
 
 impl From<Args> for send_message_v2::Args {
@@ -0,0 +1,33 @@
+use crate::send_message_v2;
+use candid::Principal;
+use serde::{Deserialize, Serialize};
+use types::{BotInitiator, CanisterId, IdempotentArgs, MessageId, TimestampMillis, UserId, idempotency_key};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub bot_principal: Principal,
+    pub initiator: BotInitiator,
+    pub message_id: MessageId,
+    pub ledger: CanisterId,
+    pub token_symbol: String,
+    pub prizes: Vec<u128>,
+    pub fee: u128,
+    pub end_date: TimestampMillis,
+    pub caption: Option<String>,
+    pub diamond_only: bool,
+    pub lifetime_diamond_only: bool,
+    pub unique_person_only: bool,
+    pub streak_only: u16,
+    pub requires_captcha: bool,
+    pub bot_name: String,
+}
+
+// Each message Id can only be used once, so a request with the same message Id is a retry
+impl IdempotentArgs for Args {
+    fn idempotency_key(&self) -> Option<u64> {
+        Some(idempotency_key(("c2c_bot_send_prize", self.bot_id, self.message_id)))
+    }
+}
+
+pub type Response = send_message_v2::Response;
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use types::{BotInitiator, Document, OptionUpdate, UnitResult, UpdatedRules, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub initiator: BotInitiator,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub rules: Option<UpdatedRules>,
+    pub avatar: OptionUpdate<Document>,
+}
+
+pub type Response = UnitResult;
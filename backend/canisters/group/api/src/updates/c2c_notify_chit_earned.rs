@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, UnitResult};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub amount: i32,
+    pub timestamp: TimestampMillis,
+}
+
+pub type Response = UnitResult;
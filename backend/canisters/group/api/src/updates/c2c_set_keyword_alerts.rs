@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use types::UnitResult;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub keywords: Vec<String>,
+}
+
+pub type Response = UnitResult;
@@ -9,6 +9,8 @@ pub struct Args {
     pub message_ids: Vec<MessageId>,
     pub as_platform_moderator: Option<bool>,
     pub new_achievement: bool,
+    // Recorded in the group's audit log when deleting messages sent by other users
+    pub reason: Option<String>,
 }
 
 pub type Response = UnitResult;
@@ -0,0 +1,13 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Empty, EncryptionKeyEpoch};
+
+pub type Args = Empty;
+
+#[ts_export(group, enable_encryption)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(EncryptionKeyEpoch),
+    Error(OCError),
+}
@@ -2,11 +2,23 @@ pub mod accept_p2p_swap;
 pub mod add_reaction;
 pub mod block_user;
 pub mod c2c_bot_add_reaction;
+pub mod c2c_bot_change_role;
 pub mod c2c_bot_delete_messages;
+pub mod c2c_bot_edit_message;
+pub mod c2c_bot_follow_thread;
 pub mod c2c_bot_invite_users;
+pub mod c2c_bot_pin_message;
 pub mod c2c_bot_remove_user;
+pub mod c2c_bot_schedule_task;
+pub mod c2c_bot_send_crypto;
 pub mod c2c_bot_send_message;
+pub mod c2c_bot_send_prize;
 pub mod c2c_bot_subscribe_to_events;
+pub mod c2c_bot_tip_message;
+pub mod c2c_bot_unfollow_thread;
+pub mod c2c_bot_unpin_message;
+pub mod c2c_bot_update_chat_details;
+pub mod c2c_bot_update_storage;
 pub mod c2c_delete_group;
 pub mod c2c_export_group;
 pub mod c2c_export_group_events;
@@ -17,7 +29,9 @@ pub mod c2c_invite_users;
 pub mod c2c_join_group;
 pub mod c2c_leave_group;
 pub mod c2c_local_index;
+pub mod c2c_notify_chit_earned;
 pub mod c2c_send_message;
+pub mod c2c_set_keyword_alerts;
 pub mod c2c_set_user_suspended;
 pub mod c2c_start_import_into_community;
 pub mod c2c_tip_message;
@@ -35,6 +49,7 @@ pub mod delete_messages;
 pub mod delete_webhook;
 pub mod disable_invite_code;
 pub mod edit_message_v2;
+pub mod enable_encryption;
 pub mod enable_invite_code;
 pub mod end_video_call_v2;
 pub mod follow_thread;
@@ -50,9 +65,14 @@ pub mod remove_participant;
 pub mod remove_reaction;
 pub mod report_message;
 pub mod reset_invite_code;
+pub mod resolve_report;
+pub mod review_held_message;
 pub mod send_message_v2;
+pub mod set_audit_log_retention;
+pub mod set_automod_rules;
 pub mod set_video_call_presence;
 pub mod start_video_call_v2;
+pub mod timeout_member;
 pub mod toggle_mute_notifications;
 pub mod unblock_user;
 pub mod undelete_messages;
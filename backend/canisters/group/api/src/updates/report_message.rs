@@ -8,6 +8,7 @@ pub struct Args {
     pub thread_root_message_index: Option<MessageIndex>,
     pub message_id: MessageId,
     pub delete: bool,
+    pub reason: Option<String>,
 }
 
 pub type Response = UnitResult;
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ModerationAction, UnitResult};
+
+#[ts_export(group, resolve_report)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub report_id: u32,
+    pub action: ModerationAction,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{MessageId, MessageIndex, UnitResult};
+
+#[ts_export(group, review_held_message)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub release: bool,
+}
+
+pub type Response = UnitResult;
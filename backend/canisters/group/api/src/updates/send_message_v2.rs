@@ -27,6 +27,12 @@ pub enum Response {
     Error(OCError),
 }
 
+impl From<OCError> for Response {
+    fn from(value: OCError) -> Self {
+        Response::Error(value)
+    }
+}
+
 #[ts_export(group, send_message)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SuccessResult {
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AuditLogRetention, UnitResult};
+
+#[ts_export(group, set_audit_log_retention)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub retention: AuditLogRetention,
+}
+
+pub type Response = UnitResult;
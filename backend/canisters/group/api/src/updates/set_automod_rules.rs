@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AutomodRule, UnitResult};
+
+#[ts_export(group, set_automod_rules)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub rules: Vec<AutomodRule>,
+}
+
+pub type Response = UnitResult;
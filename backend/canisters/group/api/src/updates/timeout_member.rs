@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Milliseconds, UnitResult, UserId};
+
+#[ts_export(group, timeout_member)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub user_id: UserId,
+    // Passing `None` lifts any existing timeout
+    pub duration: Option<Milliseconds>,
+    // Recorded in the group's audit log
+    pub reason: Option<String>,
+}
+
+pub type Response = UnitResult;
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{BotPermissions, UnitResult, UserId};
+use types::{BotPermissions, BotSpendAllowance, UnitResult, UserId};
 
 #[ts_export(group, update_bot)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -8,6 +8,8 @@ pub struct Args {
     pub bot_id: UserId,
     pub granted_permissions: BotPermissions,
     pub granted_autonomous_permissions: Option<BotPermissions>,
+    // If set, replaces the bot's spend allowances
+    pub spend_allowances: Option<Vec<BotSpendAllowance>>,
 }
 
 pub type Response = UnitResult;
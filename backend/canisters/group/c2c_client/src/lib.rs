@@ -5,6 +5,8 @@ use group_canister::*;
 generate_c2c_call!(c2c_active_proposal_tallies);
 generate_c2c_call!(c2c_bot_group_details);
 generate_c2c_call!(c2c_bot_members);
+generate_c2c_call!(c2c_bot_storage_entries);
+generate_c2c_call!(c2c_can_access_encryption_key);
 generate_c2c_call!(c2c_can_issue_access_token_v2);
 generate_c2c_call!(c2c_events);
 generate_c2c_call!(c2c_events_by_index);
@@ -16,11 +18,23 @@ generate_c2c_call!(summary_updates);
 
 // Updates
 generate_c2c_call!(c2c_bot_add_reaction);
+generate_c2c_call!(c2c_bot_change_role);
 generate_c2c_call!(c2c_bot_delete_messages);
+generate_c2c_call!(c2c_bot_edit_message);
+generate_c2c_call!(c2c_bot_follow_thread);
 generate_c2c_call!(c2c_bot_invite_users);
+generate_c2c_call!(c2c_bot_pin_message);
 generate_c2c_call!(c2c_bot_remove_user);
+generate_c2c_call!(c2c_bot_schedule_task);
+generate_c2c_call!(c2c_bot_send_crypto);
 generate_c2c_call!(c2c_bot_send_message);
+generate_c2c_call!(c2c_bot_send_prize);
 generate_c2c_call!(c2c_bot_subscribe_to_events);
+generate_c2c_call!(c2c_bot_tip_message);
+generate_c2c_call!(c2c_bot_unfollow_thread);
+generate_c2c_call!(c2c_bot_unpin_message);
+generate_c2c_call!(c2c_bot_update_chat_details);
+generate_c2c_call!(c2c_bot_update_storage);
 generate_c2c_call!(c2c_delete_group);
 generate_c2c_call!(c2c_export_group);
 generate_c2c_call!(c2c_export_group_events);
@@ -0,0 +1,60 @@
+use crate::{Data, RuntimeState, mutate_state};
+use group_chat_core::{MEMBER_VALUE_VERSION, migrate_member_bytes};
+use ic_cdk_timers::TimerId;
+use stable_memory_map::{BaseKeyPrefix, KeyType};
+use std::cell::Cell;
+use std::time::Duration;
+use tracing::{info, trace};
+use types::TimestampMillis;
+
+thread_local! {
+    static TIMER_ID: Cell<Option<TimerId>> = Cell::default();
+}
+
+const BATCH_SIZE: usize = 100;
+const MEMBERS: &str = "members";
+
+// Called on each upgrade, migrations which have already been started at the same target version are skipped
+pub(crate) fn start_migrations(data: &mut Data, now: TimestampMillis) {
+    data.stable_memory_migrations.start(
+        MEMBERS,
+        BaseKeyPrefix::from_key_type(KeyType::GroupMember),
+        MEMBER_VALUE_VERSION,
+        now,
+    );
+}
+
+pub(crate) fn start_job_if_required(state: &RuntimeState) -> bool {
+    if TIMER_ID.get().is_none() && state.data.stable_memory_migrations.has_pending() {
+        let timer_id = ic_cdk_timers::set_timer(Duration::from_secs(10), run);
+        TIMER_ID.set(Some(timer_id));
+        true
+    } else {
+        false
+    }
+}
+
+fn run() {
+    trace!("'migrate_stable_memory' job running");
+    TIMER_ID.set(None);
+    mutate_state(|state| {
+        let now = state.env.now();
+        // If < 2B instructions have been used so far, migrate another batch, else continue in a new execution
+        while ic_cdk::api::instruction_counter() < 2_000_000_000 {
+            let Some((name, migration)) = state.data.stable_memory_migrations.next_pending_mut() else {
+                break;
+            };
+            if migration.run_batch(migration_fn(name), BATCH_SIZE, now) {
+                info!(name, "Stable memory migration complete");
+            }
+        }
+        start_job_if_required(state);
+    });
+}
+
+fn migration_fn(name: &str) -> fn(u8, &[u8]) -> Vec<u8> {
+    match name {
+        MEMBERS => migrate_member_bytes,
+        _ => unreachable!("Unknown stable memory migration: {name}"),
+    }
+}
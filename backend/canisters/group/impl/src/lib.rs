@@ -1,6 +1,10 @@
+use crate::activity_notifications::handle_activity_notification;
 use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::local_user_index_event_batch::LocalUserIndexEventBatch;
-use crate::timer_job_types::{DeleteFileReferencesJob, MakeTransferJob, RemoveExpiredEventsJob, TimerJob};
+use crate::timer_job_types::{
+    DeleteFileReferencesJob, LiftExpiredMemberRestrictionsJob, MakeTransferJob, RemoveExpiredEventsJob, RunBotScheduledTaskJob,
+    TimerJob,
+};
 use crate::updates::c2c_freeze_group::freeze_group_impl;
 use activity_notification_state::ActivityNotificationState;
 use canister_state_macros::canister_state;
@@ -12,11 +16,12 @@ use fire_and_forget_handler::FireAndForgetHandler;
 use gated_groups::{GatePayment, calculate_gate_payments};
 use group_chat_core::{AddResult as AddMemberResult, GroupChatCore, GroupMemberInternal, InvitedUsersSuccess, UserInvitation};
 use group_community_common::{
-    Achievements, ExpiringMemberActions, ExpiringMembers, PaymentReceipts, PaymentRecipient, PendingPayment,
-    PendingPaymentReason, PendingPaymentsQueue, UserCache,
+    Achievements, AuditLog, ChitLeaderboard, ExpiringMemberActions, ExpiringMembers, PaymentReceipts, PaymentRecipient,
+    PendingPayment, PendingPaymentReason, PendingPaymentsQueue, UserCache,
 };
+use http_request::{OpenMetrics, OpenMetricsEncoder};
 use ic_principal::Principal;
-use installed_bots::InstalledBots;
+use installed_bots::{BotSpendReservation, InstalledBots};
 use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use model::user_event_batch::UserEventBatch;
 use msgpack::serialize_then_unwrap;
@@ -26,19 +31,20 @@ use rand::RngCore;
 use rand::prelude::StdRng;
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
-use stable_memory_map::{BaseKeyPrefix, ChatEventKeyPrefix, StableMemoryMap};
+use stable_memory_map::{BaseKeyPrefix, ChatEventKeyPrefix, StableMemoryMap, ValueMigrationMetrics, ValueMigrations};
 use std::cell::RefCell;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::Deref;
-use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue};
+use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue, TimerJobQueueMetrics};
 use types::{
-    AccessGateConfigInternal, Achievement, BotAdded, BotEventsCaller, BotInitiator, BotNotification, BotPermissions,
-    BotRemoved, BotSubscriptions, BotUpdated, BuildVersion, Caller, CanisterId, ChatEventCategory, ChatId, ChatMetrics,
-    CommunityId, Cycles, Document, Empty, EventIndex, EventsCaller, FcmData, FrozenGroupInfo, GroupCanisterGroupChatSummary,
-    GroupMembership, GroupPermissions, GroupSubtype, IdempotentEnvelope, MAX_THREADS_IN_SUMMARY, MessageIndex, Milliseconds,
-    MultiUserChat, Notification, OCResult, Rules, TimestampMillis, Timestamped, UserId, UserNotification,
-    UserNotificationPayload, UserType,
+    AccessGateConfigInternal, Achievement, AuditLogAction, AuditLogRetention, BotAdded, BotEvent, BotEventsCaller,
+    BotInitiator, BotInstallationLocation, BotLifecycleEvent, BotNotification, BotPermissions, BotRemoved,
+    BotScheduledTaskEvent, BotSpendAllowance, BotSubscriptions, BotUpdated, BuildVersion, Caller, CanisterId,
+    ChatEventCategory, ChatId, ChatMetrics, CommunityId, Cycles, Document, Empty, EventIndex, EventsCaller, FcmData,
+    FrozenGroupInfo, GroupCanisterGroupChatSummary, GroupMembership, GroupPermissions, GroupSubtype, IdempotentEnvelope,
+    MAX_THREADS_IN_SUMMARY, MessageIndex, Milliseconds, MultiUserChat, Notification, OCResult, Rules, TimestampMillis,
+    Timestamped, UserId, UserNotification, UserNotificationPayload, UserType,
 };
 use user_canister::GroupCanisterEvent;
 use utils::env::Environment;
@@ -89,6 +95,10 @@ impl RuntimeState {
         self.env.caller() == self.data.escrow_canister_id
     }
 
+    pub fn is_caller_identity_canister(&self) -> bool {
+        self.env.caller() == self.data.identity_canister_id
+    }
+
     pub fn is_caller_video_call_operator(&self) -> bool {
         let caller = self.env.caller();
         self.data.video_call_operators.contains(&caller)
@@ -340,6 +350,51 @@ impl RuntimeState {
         jobs::make_pending_payments::start_job_if_required(self);
     }
 
+    pub fn run_member_restriction_expiry_job(&mut self) {
+        let now = self.env.now();
+        let bot_notifications = self.data.chat.lift_expired_restrictions(now);
+
+        self.data.next_member_restriction_expiry = None;
+        self.data.handle_member_restriction_expiry(now);
+
+        if !bot_notifications.is_empty() {
+            self.push_bot_notifications(bot_notifications);
+            handle_activity_notification(self);
+        }
+    }
+
+    pub fn run_bot_scheduled_task(&mut self, bot_id: UserId, name: String) {
+        let now = self.env.now();
+        let Some((scheduled_at, task)) = self.data.bots.take_due_task(&bot_id, &name, now) else {
+            return;
+        };
+
+        // If the bot's autonomous permissions have since been revoked, the task can no longer run
+        if !self.data.can_bot_run_scheduled_tasks(&bot_id) {
+            self.data.bots.cancel_task(&bot_id, &name);
+            return;
+        }
+
+        self.data.timer_jobs.enqueue_job(
+            TimerJob::RunBotScheduledTask(RunBotScheduledTaskJob { bot_id, name }),
+            task.next_run,
+            now,
+        );
+
+        if self.data.frozen.value.is_none() {
+            self.push_bot_notification(Some(BotNotification {
+                event: BotEvent::Lifecycle(BotLifecycleEvent::ScheduledTask(BotScheduledTaskEvent {
+                    name: task.name,
+                    location: BotInstallationLocation::Group(self.env.canister_id().into()),
+                    channel_id: None,
+                    scheduled_at,
+                })),
+                recipients: vec![bot_id],
+                timestamp: now,
+            }));
+        }
+    }
+
     pub fn run_event_expiry_job(&mut self) {
         let now = self.env.now();
         let result = self.data.chat.remove_expired_events(now);
@@ -445,12 +500,21 @@ impl RuntimeState {
             queued_user_events: self.data.user_event_sync_queue.len() as u32,
             queued_local_index_events: self.data.local_user_index_event_sync_queue.len() as u32,
             stable_memory_sizes: memory::memory_sizes(),
+            stable_memory_migrations: self.data.stable_memory_migrations.metrics(),
+            timer_job_queues: BTreeMap::from([
+                ("user_event_sync_queue".to_string(), self.data.user_event_sync_queue.metrics()),
+                (
+                    "local_user_index_event_sync_queue".to_string(),
+                    self.data.local_user_index_event_sync_queue.metrics(),
+                ),
+            ]),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 group_index: self.data.group_index_canister_id,
                 local_user_index: self.data.local_user_index_canister_id,
                 proposals_bot: self.data.proposals_bot_user_id.into(),
                 escrow_canister_id: self.data.escrow_canister_id,
+                identity: self.data.identity_canister_id,
                 icp_ledger: ICP_LEDGER_CANISTER_ID,
             },
         }
@@ -494,6 +558,8 @@ struct Data {
     pub proposals_bot_user_id: UserId,
     pub escrow_canister_id: CanisterId,
     pub internet_identity_canister_id: CanisterId,
+    #[serde(default = "Principal::anonymous")]
+    pub identity_canister_id: CanisterId,
     pub invite_code: Option<u64>,
     pub invite_code_enabled: bool,
     pub frozen: Timestamped<Option<FrozenGroupInfo>>,
@@ -506,6 +572,8 @@ struct Data {
     pub community_being_imported_into: Option<CommunityBeingImportedInto>,
     pub serialized_chat_state: Option<ByteBuf>,
     pub next_event_expiry: Option<TimestampMillis>,
+    #[serde(default)]
+    pub next_member_restriction_expiry: Option<TimestampMillis>,
     pub rng_seed: [u8; 32],
     pub pending_payments_queue: PendingPaymentsQueue,
     pub total_payment_receipts: PaymentReceipts,
@@ -516,12 +584,18 @@ struct Data {
     expiring_members: ExpiringMembers,
     expiring_member_actions: ExpiringMemberActions,
     user_cache: UserCache,
+    #[serde(default)]
+    chit_leaderboard: ChitLeaderboard,
     user_event_sync_queue: GroupedTimerJobQueue<UserEventBatch>,
     local_user_index_event_sync_queue: BatchedTimerJobQueue<LocalUserIndexEventBatch>,
     stable_memory_keys_to_garbage_collect: Vec<BaseKeyPrefix>,
     verified: Timestamped<bool>,
     pub bots: InstalledBots,
     idempotency_checker: IdempotencyChecker,
+    #[serde(default)]
+    audit_log: AuditLog,
+    #[serde(default)]
+    stable_memory_migrations: ValueMigrations,
 }
 
 fn init_instruction_counts_log() -> InstructionCountsLog {
@@ -552,6 +626,7 @@ impl Data {
         proposals_bot_user_id: UserId,
         escrow_canister_id: CanisterId,
         internet_identity_canister_id: CanisterId,
+        identity_canister_id: CanisterId,
         test_mode: bool,
         permissions: Option<GroupPermissions>,
         gate_config: Option<AccessGateConfigInternal>,
@@ -591,6 +666,7 @@ impl Data {
             proposals_bot_user_id,
             escrow_canister_id,
             internet_identity_canister_id,
+            identity_canister_id,
             activity_notification_state: ActivityNotificationState::new(now, mark_active_duration),
             test_mode,
             invite_code: None,
@@ -602,6 +678,7 @@ impl Data {
             community_being_imported_into: None,
             serialized_chat_state: None,
             next_event_expiry: None,
+            next_member_restriction_expiry: None,
             rng_seed: [0; 32],
             pending_payments_queue: PendingPaymentsQueue::default(),
             total_payment_receipts: PaymentReceipts::default(),
@@ -611,12 +688,15 @@ impl Data {
             expiring_members: ExpiringMembers::default(),
             expiring_member_actions: ExpiringMemberActions::default(),
             user_cache: UserCache::default(),
+            chit_leaderboard: ChitLeaderboard::default(),
             user_event_sync_queue: GroupedTimerJobQueue::new(5, true),
             local_user_index_event_sync_queue: BatchedTimerJobQueue::new(local_user_index_canister_id, true),
             stable_memory_keys_to_garbage_collect: Vec::new(),
             verified: Timestamped::default(),
             bots: InstalledBots::default(),
             idempotency_checker: IdempotencyChecker::default(),
+            audit_log: AuditLog::default(),
+            stable_memory_migrations: ValueMigrations::default(),
         }
     }
 
@@ -704,6 +784,41 @@ impl Data {
         );
     }
 
+    pub fn handle_member_restriction_expiry(&mut self, now: TimestampMillis) {
+        if let Some(expiry) = self.chat.members.next_restriction_expiry() {
+            if self.next_member_restriction_expiry.is_none_or(|ex| expiry < ex) {
+                self.next_member_restriction_expiry = Some(expiry);
+
+                let timer_jobs = &mut self.timer_jobs;
+                timer_jobs.cancel_jobs(|j| matches!(j, TimerJob::LiftExpiredMemberRestrictions(_)));
+                timer_jobs.enqueue_job(
+                    TimerJob::LiftExpiredMemberRestrictions(LiftExpiredMemberRestrictionsJob),
+                    expiry,
+                    now,
+                );
+            }
+        }
+    }
+
+    pub fn audit_log(&self) -> &AuditLog {
+        &self.audit_log
+    }
+
+    pub fn record_audit_log_entry(
+        &mut self,
+        actor: UserId,
+        target: Option<UserId>,
+        action: AuditLogAction,
+        reason: Option<String>,
+        now: TimestampMillis,
+    ) {
+        self.audit_log.record(None, actor, target, action, reason, now);
+    }
+
+    pub fn set_audit_log_retention(&mut self, retention: AuditLogRetention, now: TimestampMillis) -> OCResult {
+        self.audit_log.set_retention(retention, now)
+    }
+
     pub fn handle_event_expiry(&mut self, expiry: TimestampMillis, now: TimestampMillis) {
         if self.next_event_expiry.is_none_or(|ex| expiry < ex) {
             self.next_event_expiry = Some(expiry);
@@ -736,6 +851,7 @@ impl Data {
         self.expiring_member_actions.remove_member(user_id, None);
         self.achievements.remove_user(&user_id);
         self.user_cache.delete(user_id);
+        self.chit_leaderboard.remove_user(&user_id);
     }
 
     pub fn get_caller_for_events(&self, caller: Principal, bot_initiator: Option<BotInitiator>) -> Option<EventsCaller> {
@@ -775,6 +891,64 @@ impl Data {
         )
     }
 
+    pub fn reserve_bot_spend(
+        &mut self,
+        bot_id: &UserId,
+        ledger: CanisterId,
+        amount: u128,
+        now: TimestampMillis,
+    ) -> OCResult<BotSpendReservation> {
+        self.bots
+            .reserve_spend(bot_id, ledger, amount, now)
+            .ok_or(OCErrorCode::BotSpendAllowanceExceeded.into())
+    }
+
+    pub fn release_bot_spend(&mut self, reservation: BotSpendReservation) {
+        self.bots.release_spend(reservation)
+    }
+
+    // Adds or replaces the bot's task with the given name, or cancels it if no interval is specified
+    pub fn schedule_bot_task(
+        &mut self,
+        bot_id: UserId,
+        name: String,
+        interval: Option<Milliseconds>,
+        now: TimestampMillis,
+    ) -> OCResult {
+        let next_run = match interval {
+            Some(interval) => {
+                if !self.can_bot_run_scheduled_tasks(&bot_id) {
+                    return Err(OCErrorCode::InitiatorNotAuthorized
+                        .with_message("Scheduled tasks require the bot to be granted autonomous permissions"));
+                }
+                Some(self.bots.schedule_task(&bot_id, name.clone(), interval, None, now)?)
+            }
+            None => {
+                self.bots.cancel_task(&bot_id, &name);
+                None
+            }
+        };
+
+        self.timer_jobs
+            .cancel_jobs(|j| matches!(j, TimerJob::RunBotScheduledTask(job) if job.bot_id == bot_id && job.name == name));
+
+        if let Some(next_run) = next_run {
+            self.timer_jobs.enqueue_job(
+                TimerJob::RunBotScheduledTask(RunBotScheduledTaskJob { bot_id, name }),
+                next_run,
+                now,
+            );
+        }
+
+        Ok(())
+    }
+
+    // Scheduled tasks wake the bot so that it can act autonomously
+    pub fn can_bot_run_scheduled_tasks(&self, bot_id: &UserId) -> bool {
+        self.granted_bot_permissions(bot_id, &BotInitiator::Autonomous)
+            .is_some_and(|granted| !granted.is_empty())
+    }
+
     pub fn is_bot_permitted(&self, bot_id: &UserId, initiator: &BotInitiator, required: &BotPermissions) -> bool {
         self.granted_bot_permissions(bot_id, initiator)
             .is_some_and(|granted| required.is_subset(&granted))
@@ -786,8 +960,8 @@ impl Data {
 
         // Get the granted permissions when initiated by command or API key
         match initiator {
-            BotInitiator::Command(command) => self
-                .get_user_permissions(&command.initiator)
+            BotInitiator::Command(_) | BotInitiator::Component(_) => self
+                .get_user_permissions(&initiator.user()?)
                 .map(|u| BotPermissions::intersect(&bot.permissions, &u)),
             BotInitiator::Autonomous => bot.autonomous_permissions.clone(),
         }
@@ -844,12 +1018,17 @@ impl Data {
         bot_id: UserId,
         permissions: BotPermissions,
         autonomous_permissions: Option<BotPermissions>,
+        spend_allowances: Option<Vec<BotSpendAllowance>>,
         now: TimestampMillis,
     ) -> bool {
         if !self.bots.update(bot_id, permissions, autonomous_permissions.clone(), now) {
             return false;
         }
 
+        if let Some(spend_allowances) = spend_allowances {
+            self.bots.set_spend_allowances(bot_id, spend_allowances, now);
+        }
+
         self.chat.events.push_main_event(
             ChatEventInternal::BotUpdated(Box::new(BotUpdated {
                 user_id: bot_id,
@@ -882,6 +1061,8 @@ impl Data {
         }
 
         self.chat.events.unsubscribe_bot_from_events(bot_id);
+        self.timer_jobs
+            .cancel_jobs(|j| matches!(j, TimerJob::RunBotScheduledTask(job) if job.bot_id == bot_id));
 
         self.chat.events.push_main_event(
             ChatEventInternal::BotRemoved(Box::new(BotRemoved {
@@ -951,9 +1132,18 @@ pub struct Metrics {
     pub queued_user_events: u32,
     pub queued_local_index_events: u32,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub stable_memory_migrations: BTreeMap<String, ValueMigrationMetrics>,
+    pub timer_job_queues: BTreeMap<String, TimerJobQueueMetrics>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 fn execute_update<F: FnOnce(&mut RuntimeState) -> R, R>(f: F) -> R {
     mutate_state(|state| {
         state.regular_jobs.run(state.env.deref(), &mut state.data);
@@ -1011,6 +1201,7 @@ pub struct CanisterIds {
     pub local_user_index: CanisterId,
     pub proposals_bot: CanisterId,
     pub escrow_canister_id: CanisterId,
+    pub identity: CanisterId,
     pub icp_ledger: CanisterId,
 }
 
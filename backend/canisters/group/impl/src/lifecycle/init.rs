@@ -1,6 +1,6 @@
 use crate::Data;
 use crate::lifecycle::{init_env, init_state};
-use crate::memory::get_stable_memory_map_memory;
+use crate::memory::{get_canister_logs_memory, get_stable_memory_map_memory};
 use canister_api_macros::init;
 use canister_tracing_macros::trace;
 use group_canister::init::Args;
@@ -11,7 +11,7 @@ use utils::env::Environment;
 #[init(msgpack = true)]
 #[trace]
 fn init(args: Args) {
-    canister_logger::init(args.test_mode);
+    canister_logger::init(args.test_mode, get_canister_logs_memory());
     stable_memory_map::init(get_stable_memory_map_memory());
 
     let mut env = init_env([0; 32]);
@@ -38,6 +38,7 @@ fn init(args: Args) {
         args.proposals_bot_user_id,
         args.escrow_canister_id,
         args.internet_identity_canister_id,
+        args.identity_canister_id,
         args.test_mode,
         args.permissions_v2,
         args.gate_config.map(|g| g.into()),
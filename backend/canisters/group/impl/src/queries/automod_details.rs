@@ -0,0 +1,17 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use group_canister::automod_details::{Response::*, *};
+use types::{AutomodDetails, OCResult};
+
+#[query(msgpack = true)]
+fn automod_details(_args: Args) -> Response {
+    match read_state(automod_details_impl) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn automod_details_impl(state: &RuntimeState) -> OCResult<AutomodDetails> {
+    let member = state.get_calling_member(true)?;
+    state.data.chat.automod_details(member.user_id())
+}
@@ -3,8 +3,8 @@ use crate::guards::caller_is_local_user_index;
 use crate::read_state;
 use canister_api_macros::query;
 use group_canister::c2c_can_issue_access_token_v2::*;
-use types::BotPermissions;
 use types::c2c_can_issue_access_token::AccessTypeArgs;
+use types::{BotPermissions, Chat};
 
 #[query(guard = "caller_is_local_user_index", msgpack = true)]
 fn c2c_can_issue_access_token_v2(args: Args) -> Response {
@@ -35,7 +35,70 @@ fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Re
 
         let granted = BotPermissions::intersect(granted_to_bot, &granted_to_user);
 
-        return if args.requested_permissions.is_subset(&granted) { Response::Success } else { Response::Failure };
+        if !args.requested_permissions.is_subset(&granted) {
+            return Response::Failure;
+        }
+
+        // Chat args can only reference this group and message args must be visible to the initiator
+        let this_chat = Chat::Group(state.env.canister_id().into());
+        if args.referenced_chats.iter().any(|c| *c != this_chat)
+            || !args.referenced_messages.iter().all(|m| {
+                state
+                    .data
+                    .chat
+                    .is_message_visible_to(args.initiator, m.thread, m.message_index, m.message_id)
+            })
+        {
+            return Response::Failure;
+        }
+
+        return Response::Success;
+    }
+
+    if let AccessTypeArgs::BotActionByComponent(args) = &args_outer {
+        // Ensure the component is attached to one of the bot's messages which the initiator can see
+        let Some(component) = state.data.chat.bot_message_component(
+            args.initiator,
+            args.bot_id,
+            args.thread,
+            args.message_id,
+            &args.component_id,
+        ) else {
+            return Response::Failure;
+        };
+
+        if !component.is_valid_value(&args.value) {
+            return Response::Failure;
+        }
+
+        let Some(member) = state.data.get_member(args.initiator.into()) else {
+            return Response::Failure;
+        };
+
+        // Ensure the initiator has the necessary seniority according to the component's required role
+        if !member
+            .role()
+            .is_same_or_senior(component.default_role().unwrap_or_default().into())
+        {
+            return Response::Failure;
+        }
+
+        let Some(granted_to_bot) = state.data.get_bot_permissions(&args.bot_id) else {
+            return Response::Failure;
+        };
+
+        let Some(granted_to_user) = state.data.get_user_permissions(&args.initiator) else {
+            return Response::Failure;
+        };
+
+        let granted = BotPermissions::intersect(granted_to_bot, &granted_to_user);
+        let requested = component.permissions();
+
+        return if requested.is_subset(&granted) {
+            Response::SuccessWithPermissions(requested.clone())
+        } else {
+            Response::Failure
+        };
     }
 
     let initiator = match &args_outer {
@@ -0,0 +1,31 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use group_canister::chit_leaderboard::{Response::*, *};
+use group_community_common::chit_leaderboard_range;
+use types::OCResult;
+
+#[query(msgpack = true)]
+fn chit_leaderboard(args: Args) -> Response {
+    match read_state(|state| chit_leaderboard_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn chit_leaderboard_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
+    let caller = state.env.caller();
+    state.data.verify_is_accessible(caller, None)?;
+
+    let range = chit_leaderboard_range(args.window, state.env.now())?;
+    let members = &state.data.chat.members;
+    let leaders = state
+        .data
+        .chit_leaderboard
+        .leaderboard(range.clone(), args.max_results as usize, |user_id| members.contains(user_id));
+
+    Ok(SuccessResult {
+        start: range.start,
+        end: range.end,
+        leaders,
+    })
+}
@@ -0,0 +1,19 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use group_canister::encryption_key_epochs::{Response::*, *};
+use types::OCResult;
+
+#[query(msgpack = true)]
+fn encryption_key_epochs(_args: Args) -> Response {
+    match read_state(encryption_key_epochs_impl) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn encryption_key_epochs_impl(state: &RuntimeState) -> OCResult<SuccessResult> {
+    let member = state.get_calling_member(true)?;
+    let epochs = state.data.chat.encryption_key_epochs(member.user_id())?;
+
+    Ok(SuccessResult { epochs })
+}
@@ -2,11 +2,17 @@ use crate::RuntimeState;
 use types::TimestampMillis;
 
 mod active_proposal_tallies;
+mod audit_log;
+mod automod_details;
 mod c2c_bot_group_details;
 mod c2c_bot_members;
+mod c2c_bot_storage_entries;
+mod c2c_can_access_encryption_key;
 mod c2c_can_issue_access_token_v2;
 mod c2c_name_and_members;
+mod chit_leaderboard;
 mod deleted_message;
+mod encryption_key_epochs;
 mod events;
 mod events_by_index;
 mod events_window;
@@ -14,6 +20,7 @@ mod http_request;
 mod invite_code;
 mod local_user_index;
 mod messages_by_message_index;
+mod moderation_queue;
 mod public_summary;
 mod rules;
 mod search_messages;
@@ -0,0 +1,32 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use group_canister::moderation_queue::{Response::*, *};
+use types::OCResult;
+
+const MAX_PENDING: u32 = 100;
+const MAX_RESOLVED: u32 = 100;
+
+#[query(msgpack = true)]
+fn moderation_queue(args: Args) -> Response {
+    match read_state(|state| moderation_queue_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn moderation_queue_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
+    let member = state.get_calling_member(true)?;
+    let summary = state.data.chat.moderation_queue(
+        member.user_id(),
+        args.pending_after,
+        args.max_pending.min(MAX_PENDING) as usize,
+        args.resolved_before,
+        args.max_resolved.min(MAX_RESOLVED) as usize,
+    )?;
+
+    Ok(SuccessResult {
+        pending: summary.pending,
+        pending_count: summary.pending_count,
+        resolved: summary.resolved,
+    })
+}
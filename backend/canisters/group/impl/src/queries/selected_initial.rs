@@ -24,6 +24,7 @@ fn selected_initial_impl(state: &RuntimeState) -> OCResult<SuccessResult> {
     non_basic_members.extend(chat.members.admins().iter().copied());
     non_basic_members.extend(chat.members.moderators().iter().copied());
     non_basic_members.extend(chat.members.lapsed().iter().copied());
+    non_basic_members.extend(chat.members.timed_out().keys().copied());
 
     let mut members = Vec::new();
     let mut basic_members = Vec::new();
@@ -46,6 +47,7 @@ fn selected_initial_impl(state: &RuntimeState) -> OCResult<SuccessResult> {
             added_by: bot.added_by,
             permissions: bot.permissions.clone(),
             autonomous_permissions: bot.autonomous_permissions.clone(),
+            spend_allowances: bot.spend_allowances(),
         })
         .collect();
 
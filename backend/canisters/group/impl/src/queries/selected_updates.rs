@@ -35,6 +35,7 @@ fn selected_updates_impl(args: Args, state: &RuntimeState) -> OCResult<Response>
                             added_by: bot.added_by,
                             permissions: bot.permissions.clone(),
                             autonomous_permissions: bot.autonomous_permissions.clone(),
+                            spend_allowances: bot.spend_allowances(),
                         });
                     }
                 }
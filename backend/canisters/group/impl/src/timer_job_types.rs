@@ -23,6 +23,8 @@ pub enum TimerJob {
     CancelP2PSwapInEscrowCanister(CancelP2PSwapInEscrowCanisterJob),
     MarkP2PSwapExpired(MarkP2PSwapExpiredJob),
     MarkVideoCallEnded(MarkVideoCallEndedJob),
+    LiftExpiredMemberRestrictions(LiftExpiredMemberRestrictionsJob),
+    RunBotScheduledTask(RunBotScheduledTaskJob),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -108,6 +110,15 @@ pub struct MarkP2PSwapExpiredJob {
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MarkVideoCallEndedJob(pub group_canister::end_video_call_v2::Args);
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LiftExpiredMemberRestrictionsJob;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RunBotScheduledTaskJob {
+    pub bot_id: UserId,
+    pub name: String,
+}
+
 impl Job for TimerJob {
     fn execute(self) {
         let can_borrow_state = can_borrow_state();
@@ -126,6 +137,8 @@ impl Job for TimerJob {
             TimerJob::CancelP2PSwapInEscrowCanister(job) => job.execute(),
             TimerJob::MarkP2PSwapExpired(job) => job.execute(),
             TimerJob::MarkVideoCallEnded(job) => job.execute(),
+            TimerJob::LiftExpiredMemberRestrictions(job) => job.execute(),
+            TimerJob::RunBotScheduledTask(job) => job.execute(),
         }
 
         if can_borrow_state {
@@ -291,6 +304,18 @@ impl Job for RemoveExpiredEventsJob {
     }
 }
 
+impl Job for LiftExpiredMemberRestrictionsJob {
+    fn execute(self) {
+        mutate_state(|state| state.run_member_restriction_expiry_job());
+    }
+}
+
+impl Job for RunBotScheduledTaskJob {
+    fn execute(self) {
+        mutate_state(|state| state.run_bot_scheduled_task(self.bot_id, self.name));
+    }
+}
+
 impl Job for NotifyEscrowCanisterOfDepositJob {
     fn execute(self) {
         let escrow_canister_id = read_state(|state| state.data.escrow_canister_id);
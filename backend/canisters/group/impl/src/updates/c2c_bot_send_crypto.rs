@@ -0,0 +1,182 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::guards::caller_is_local_user_index;
+use crate::updates::send_message::send_prevalidated_message;
+use crate::{GroupEventPusher, RuntimeState, execute_update_async, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use chat_events::{CryptoContentInternal, MessageContentInternal, ValidateNewMessageContentResult};
+use constants::MEMO_MESSAGE;
+use group_canister::c2c_bot_send_crypto::*;
+use group_canister::c2c_send_message::Args as C2CArgs;
+use group_canister::send_message_v2::{Response::*, SuccessResult};
+use installed_bots::{PreparedBotTransfer, process_bot_transfer};
+use oc_error_codes::OCErrorCode;
+use tracing::error;
+use types::{
+    BotCaller, BotPermissions, Caller, CompletedCryptoTransaction, CryptoContent, CryptoTransaction, MessageContentInitial,
+    MessageContentType, MessagePermission, OCResult, PendingCryptoTransaction, icrc2,
+};
+
+#[update(guard = "caller_is_local_user_index", msgpack = true, idempotent = true)]
+#[trace]
+async fn c2c_bot_send_crypto(args: Args) -> Response {
+    execute_update_async(|| c2c_bot_send_crypto_impl(args)).await
+}
+
+async fn c2c_bot_send_crypto_impl(args: Args) -> Response {
+    let prepared = match mutate_state(|state| prepare(&args, state)) {
+        Ok(prepared) => prepared,
+        Err(error) => return Error(error),
+    };
+
+    match process_bot_transfer(
+        prepared,
+        |reservation| mutate_state(|state| state.data.release_bot_spend(reservation)),
+        |transaction, _| mutate_state(|state| commit(args, transaction.into(), state)),
+    )
+    .await
+    {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn prepare(args: &Args, state: &mut RuntimeState) -> OCResult<PreparedBotTransfer> {
+    state.data.verify_not_frozen()?;
+
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        &args.initiator,
+        &BotPermissions::from_message_permission(MessagePermission::Crypto),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    if args.amount == 0 {
+        return Err(OCErrorCode::TransferCannotBeZero.into());
+    }
+
+    if args.recipient == args.bot_id {
+        return Err(OCErrorCode::TransferCannotBeToSelf.into());
+    }
+
+    if state.data.chat.members.get(&args.recipient).is_none() {
+        return Err(OCErrorCode::TargetUserNotInChat.into());
+    }
+
+    let caller = state.verified_caller(Some(bot_caller(args)))?;
+    state.data.chat.verify_caller(&caller, |_, _| true)?;
+
+    let now = state.env.now();
+    let transaction = icrc2::PendingCryptoTransaction {
+        ledger: args.ledger,
+        token_symbol: args.token_symbol.clone(),
+        amount: args.amount,
+        from: args.bot_principal.into(),
+        to: args.recipient.into(),
+        fee: args.fee,
+        memo: Some(MEMO_MESSAGE.to_vec().into()),
+        created: state.env.now_nanos(),
+    };
+
+    let content = MessageContentInitial::Crypto(CryptoContent {
+        recipient: args.recipient,
+        transfer: CryptoTransaction::Pending(PendingCryptoTransaction::ICRC2(transaction.clone())),
+        caption: args.caption.clone(),
+    });
+    if let ValidateNewMessageContentResult::Error(error) =
+        MessageContentInternal::validate_new_message(content, false, (&caller).into(), false, now)
+    {
+        return Err(error.into());
+    }
+
+    state.data.chat.check_can_send_funded_message(
+        &caller,
+        args.thread_root_message_index,
+        args.message_id,
+        MessageContentType::Crypto,
+        args.caption.as_deref(),
+        now,
+    )?;
+
+    // The bot also pays the ledger fee
+    let total = args
+        .amount
+        .checked_add(args.fee)
+        .ok_or(OCErrorCode::InvalidRequest.with_message("Amount plus fee overflows"))?;
+    let reservation = state.data.reserve_bot_spend(&args.bot_id, args.ledger, total, now)?;
+
+    Ok(PreparedBotTransfer {
+        transaction,
+        reservation,
+        this_canister_id: state.env.canister_id(),
+    })
+}
+
+fn commit(args: Args, transaction: CompletedCryptoTransaction, state: &mut RuntimeState) -> OCResult<SuccessResult> {
+    let content = CryptoContentInternal {
+        recipient: args.recipient,
+        transfer: transaction.into(),
+        caption: args.caption.clone(),
+    };
+
+    let result = send_prevalidated_message(
+        C2CArgs {
+            thread_root_message_index: args.thread_root_message_index,
+            message_id: args.message_id,
+            content: MessageContentInternal::Crypto(content.clone()),
+            sender_name: args.bot_name.clone(),
+            sender_display_name: None,
+            replies_to: None,
+            mentioned: Vec::new(),
+            forwarding: false,
+            block_level_markdown: args.block_level_markdown,
+            rules_accepted: None,
+            message_filter_failed: None,
+        },
+        bot_caller(&args),
+        state,
+    );
+
+    match result {
+        Ok(result) => Ok(result),
+        Err(error) => {
+            // The funds have already reached the recipient so the transfer must still be recorded in the chat
+            error!(
+                ?error,
+                "Failed to send bot crypto message, recording the transfer in the main chat instead"
+            );
+            let bot = BotCaller {
+                bot: args.bot_id,
+                initiator: args.initiator,
+            };
+            let now = state.env.now();
+            let message = state.data.chat.push_bot_transfer_message(
+                &bot,
+                args.message_id,
+                content,
+                GroupEventPusher {
+                    now,
+                    rng: state.env.rng(),
+                    queue: &mut state.data.local_user_index_event_sync_queue,
+                },
+                now,
+            );
+            handle_activity_notification(state);
+
+            Ok(SuccessResult {
+                event_index: message.index,
+                message_index: message.event.message_index,
+                timestamp: message.timestamp,
+                expires_at: message.expires_at,
+            })
+        }
+    }
+}
+
+fn bot_caller(args: &Args) -> Caller {
+    Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator.clone(),
+    })
+}
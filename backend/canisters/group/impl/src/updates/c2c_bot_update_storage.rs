@@ -0,0 +1,17 @@
+use crate::guards::caller_is_local_user_index;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::c2c_bot_update_storage::*;
+use types::OCResult;
+
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_update_storage(args: Args) -> Response {
+    execute_update(|state| c2c_bot_update_storage_impl(args, state)).into()
+}
+
+fn c2c_bot_update_storage_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+    state.data.bots.update_storage(&args.bot_id, args.set, args.remove)
+}
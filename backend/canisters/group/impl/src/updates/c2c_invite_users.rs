@@ -9,7 +9,7 @@ use ic_principal::Principal;
 use oc_error_codes::{OCError, OCErrorCode};
 use types::{BotCaller, BotPermissions, Caller, ChatPermission, OCResult, UserId};
 
-#[update(guard = "caller_is_user_index_or_local_user_index", msgpack = true)]
+#[update(guard = "caller_is_user_index_or_local_user_index", msgpack = true, idempotent = true)]
 #[trace]
 fn c2c_invite_users(args: Args) -> Response {
     match execute_update(|state| c2c_invite_users_impl(args.users, Caller::User(args.caller), state)) {
@@ -13,7 +13,7 @@ use group_community_common::ExpiringMember;
 use oc_error_codes::OCErrorCode;
 use types::{AccessGate, GroupCanisterGroupChatSummary, MemberJoinedInternal, OCResult, UsersUnblocked};
 
-#[update(guard = "caller_is_user_index_or_local_user_index", msgpack = true)]
+#[update(guard = "caller_is_user_index_or_local_user_index", msgpack = true, idempotent = true)]
 #[trace]
 async fn c2c_join_group(args: Args) -> Response {
     execute_update_async(|| c2c_join_group_impl(args)).await
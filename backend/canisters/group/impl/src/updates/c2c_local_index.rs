@@ -53,7 +53,7 @@ fn process_event<F: FnOnce() -> TimestampMillis>(
             state.data.verified = Timestamped::new(ev.verified, **now);
         }
         LocalIndexEvent::UserDeleted(user_id) => {
-            state.data.chat.members.remove(user_id, **now);
+            state.data.chat.remove_deleted_user(user_id, **now);
             state.data.remove_user(user_id, None);
         }
     }
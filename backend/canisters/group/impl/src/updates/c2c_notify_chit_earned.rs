@@ -0,0 +1,23 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::c2c_notify_chit_earned::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[update(msgpack = true)]
+#[trace]
+fn c2c_notify_chit_earned(args: Args) -> Response {
+    execute_update(|state| c2c_notify_chit_earned_impl(args, state)).into()
+}
+
+fn c2c_notify_chit_earned_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let user_id = state.env.caller().into();
+    if !state.data.chat.members.contains(&user_id) {
+        return Err(OCErrorCode::InitiatorNotInChat.into());
+    }
+
+    let now = state.env.now();
+    state.data.chit_leaderboard.record(user_id, args.amount, args.timestamp, now);
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::c2c_set_keyword_alerts::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[update(msgpack = true)]
+#[trace]
+fn c2c_set_keyword_alerts(args: Args) -> Response {
+    execute_update(|state| c2c_set_keyword_alerts_impl(args, state)).into()
+}
+
+fn c2c_set_keyword_alerts_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let user_id = state.env.caller().into();
+    if !state.data.chat.members.set_keyword_alerts(user_id, args.keywords) {
+        return Err(OCErrorCode::InitiatorNotInChat.into());
+    }
+    Ok(())
+}
@@ -1,12 +1,13 @@
 use crate::activity_notifications::handle_activity_notification;
-use crate::{RuntimeState, execute_update_async, jobs, mutate_state, read_state};
+use crate::guards::caller_is_local_user_index;
+use crate::{RuntimeState, execute_update, execute_update_async, jobs, mutate_state, read_state};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
-use group_canister::change_role::*;
+use group_canister::{c2c_bot_change_role, change_role::*};
 use group_chat_core::GroupRoleInternal;
 use group_community_common::ExpiringMember;
 use oc_error_codes::OCErrorCode;
-use types::{CanisterId, GroupRole, OCResult, UserId};
+use types::{AuditLogAction, BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, GroupRole, OCResult, UserId};
 use user_index_canister_c2c_client::lookup_user;
 
 #[update(msgpack = true)]
@@ -15,6 +16,30 @@ async fn change_role(args: Args) -> Response {
     execute_update_async(|| change_role_impl(args)).await
 }
 
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_change_role(args: c2c_bot_change_role::Args) -> c2c_bot_change_role::Response {
+    execute_update(|state| c2c_bot_change_role_impl(args, state)).into()
+}
+
+fn c2c_bot_change_role_impl(args: c2c_bot_change_role::Args, state: &mut RuntimeState) -> OCResult {
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        &args.initiator,
+        &BotPermissions::from_chat_permission(ChatPermission::ChangeRoles),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    });
+
+    // Bots can't change the role of owners so there is no need to look up platform moderators
+    commit(args.user_id, args.new_role, caller, false, false, state)
+}
+
 async fn change_role_impl(args: Args) -> Response {
     let PrepareResult {
         caller_id,
@@ -52,8 +77,9 @@ async fn change_role_impl(args: Args) -> Response {
 
     mutate_state(|state| {
         commit(
-            args,
-            caller_id,
+            args.user_id,
+            args.new_role,
+            Caller::User(caller_id),
             is_caller_platform_moderator,
             is_user_platform_moderator,
             state,
@@ -81,19 +107,21 @@ fn prepare(user_id: UserId, state: &RuntimeState) -> OCResult<PrepareResult> {
 }
 
 fn commit(
-    args: Args,
-    caller_id: UserId,
+    user_id: UserId,
+    new_role: GroupRole,
+    caller: Caller,
     is_caller_platform_moderator: bool,
     is_user_platform_moderator: bool,
     state: &mut RuntimeState,
 ) -> OCResult {
     state.data.verify_not_frozen()?;
 
+    let caller_id = caller.agent();
     let now = state.env.now();
     let result = state.data.chat.change_role(
-        caller_id,
-        args.user_id,
-        args.new_role,
+        caller,
+        user_id,
+        new_role,
         is_caller_platform_moderator,
         is_user_platform_moderator,
         now,
@@ -101,19 +129,26 @@ fn commit(
 
     // Owners can't "lapse" so either add or remove user from expiry list if they lose or gain owner status
     if let Some(gate_expiry) = state.data.chat.gate_config.value.as_ref().and_then(|gc| gc.expiry()) {
-        if matches!(args.new_role, GroupRole::Owner) {
-            state.data.expiring_members.remove_member(args.user_id, None);
+        if matches!(new_role, GroupRole::Owner) {
+            state.data.expiring_members.remove_member(user_id, None);
         } else if matches!(result.prev_role, GroupRoleInternal::Owner) {
             state.data.expiring_members.push(ExpiringMember {
                 expires: now + gate_expiry,
                 channel_id: None,
-                user_id: args.user_id,
+                user_id,
             });
         }
     }
 
     jobs::expire_members::start_job_if_required(state);
 
+    state.data.record_audit_log_entry(
+        caller_id,
+        Some(user_id),
+        AuditLogAction::GroupRoleChanged(new_role),
+        None,
+        now,
+    );
     state.push_bot_notification(result.bot_notification);
     handle_activity_notification(state);
     Ok(())
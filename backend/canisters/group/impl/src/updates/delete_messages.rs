@@ -8,7 +8,9 @@ use constants::MINUTE_IN_MS;
 use group_canister::{c2c_bot_delete_messages, delete_messages::*};
 use ic_principal::Principal;
 use oc_error_codes::OCErrorCode;
-use types::{Achievement, BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, OCResult};
+use types::{
+    Achievement, AuditLogAction, AuditLogMessage, BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, OCResult,
+};
 use user_index_canister_c2c_client::lookup_user;
 
 #[update(msgpack = true)]
@@ -54,6 +56,7 @@ fn c2c_bot_delete_messages_impl(args: c2c_bot_delete_messages::Args, state: &mut
         message_ids: args.message_ids,
         as_platform_moderator: None,
         new_achievement: false,
+        reason: None,
     };
 
     if !state.data.is_bot_permitted(
@@ -117,7 +120,22 @@ fn commit(ext_caller: Option<Caller>, args: Args, state: &mut RuntimeState) -> O
         state.notify_user_of_achievement(agent, Achievement::DeletedMessage, now);
     }
 
-    for result in results.into_iter().filter_map(|(_, result)| result.ok()) {
+    for (message_id, result) in results
+        .into_iter()
+        .filter_map(|(message_id, result)| result.ok().map(|r| (message_id, r)))
+    {
+        if result.sender != agent {
+            state.data.record_audit_log_entry(
+                agent,
+                Some(result.sender),
+                AuditLogAction::MessageDeleted(AuditLogMessage {
+                    thread_root_message_index: args.thread_root_message_index,
+                    message_id,
+                }),
+                args.reason.clone(),
+                now,
+            );
+        }
         state.push_bot_notification(result.bot_notification);
     }
 
@@ -1,10 +1,12 @@
 use crate::activity_notifications::handle_activity_notification;
+use crate::guards::caller_is_local_user_index;
 use crate::{GroupEventPusher, RuntimeState, execute_update};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use chat_events::EditMessageArgs;
-use group_canister::edit_message_v2::*;
-use types::{Achievement, OCResult};
+use group_canister::{c2c_bot_edit_message, edit_message_v2::*};
+use oc_error_codes::OCErrorCode;
+use types::{Achievement, BotCaller, BotPermissions, Caller, MessageContentInitial, OCResult};
 
 #[update(msgpack = true)]
 #[trace]
@@ -12,6 +14,57 @@ fn edit_message_v2(args: Args) -> Response {
     execute_update(|state| edit_message_impl(args, state)).into()
 }
 
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_edit_message(args: c2c_bot_edit_message::Args) -> c2c_bot_edit_message::Response {
+    execute_update(|state| c2c_bot_edit_message_impl(args, state)).into()
+}
+
+fn c2c_bot_edit_message_impl(args: c2c_bot_edit_message::Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let content = MessageContentInitial::from(args.content);
+
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        &args.initiator,
+        &BotPermissions::from_message_permission((&content).into()),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    });
+    let min_visible_event_index = state.data.chat.verify_caller(&caller, |_, _| true)?;
+    let now = state.env.now();
+
+    let edit_message_args = EditMessageArgs {
+        sender: args.bot_id,
+        min_visible_event_index,
+        thread_root_message_index: args.thread,
+        message_id: args.message_id,
+        content: content.into(),
+        block_level_markdown: Some(args.block_level_markdown),
+        finalise_bot_message: false,
+        now,
+    };
+
+    let result = state.data.chat.edit_message(
+        edit_message_args,
+        Some(GroupEventPusher {
+            now,
+            rng: state.env.rng(),
+            queue: &mut state.data.local_user_index_event_sync_queue,
+        }),
+    )?;
+
+    state.push_bot_notification(result.bot_notification);
+    handle_activity_notification(state);
+    Ok(())
+}
+
 fn edit_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     state.data.verify_not_frozen()?;
 
@@ -31,14 +84,18 @@ fn edit_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         now,
     };
 
-    let result = state.data.chat.events.edit_message(
+    let result = state.data.chat.edit_message(
         edit_message_args,
         Some(GroupEventPusher {
             now,
             rng: state.env.rng(),
             queue: &mut state.data.local_user_index_event_sync_queue,
         }),
-    )?;
+    );
+
+    // The sender may have been timed out by automod
+    state.data.handle_member_restriction_expiry(now);
+    let result = result?;
 
     if args.new_achievement && !is_bot {
         state.notify_user_of_achievement(sender, Achievement::EditedMessage, now);
@@ -0,0 +1,26 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::enable_encryption::{Response::*, *};
+use types::{EncryptionKeyEpoch, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+fn enable_encryption(_args: Args) -> Response {
+    match execute_update(enable_encryption_impl) {
+        Ok(epoch) => Success(epoch),
+        Err(error) => Error(error),
+    }
+}
+
+fn enable_encryption_impl(state: &mut RuntimeState) -> OCResult<EncryptionKeyEpoch> {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+    let now = state.env.now();
+    let epoch = state.data.chat.enable_encryption(member.user_id(), now)?;
+
+    handle_activity_notification(state);
+    Ok(epoch)
+}
@@ -1,8 +1,10 @@
+use crate::guards::caller_is_local_user_index;
 use crate::{RuntimeState, execute_update};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
-use group_canister::follow_thread::*;
-use types::{Achievement, OCResult};
+use group_canister::{c2c_bot_follow_thread, follow_thread::*};
+use oc_error_codes::OCErrorCode;
+use types::{Achievement, BotCaller, BotPermissions, Caller, ChatPermission, OCResult};
 
 #[update(msgpack = true)]
 #[trace]
@@ -10,6 +12,32 @@ fn follow_thread(args: Args) -> Response {
     execute_update(|state| follow_thread_impl(args, state)).into()
 }
 
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_follow_thread(args: c2c_bot_follow_thread::Args) -> c2c_bot_follow_thread::Response {
+    execute_update(|state| c2c_bot_follow_thread_impl(args, state)).into()
+}
+
+fn c2c_bot_follow_thread_impl(args: c2c_bot_follow_thread::Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        &args.initiator,
+        &BotPermissions::from_chat_permission(ChatPermission::FollowThreads),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    });
+    let now = state.env.now();
+
+    state.data.chat.follow_thread(caller, args.thread_root_message_index, now)
+}
+
 fn follow_thread_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     state.data.verify_not_frozen()?;
 
@@ -17,7 +45,10 @@ fn follow_thread_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     let user_id = member.user_id();
     let now = state.env.now();
 
-    state.data.chat.follow_thread(user_id, args.thread_root_message_index, now)?;
+    state
+        .data
+        .chat
+        .follow_thread(Caller::User(user_id), args.thread_root_message_index, now)?;
 
     if !member.user_type().is_bot() {
         if args.new_achievement {
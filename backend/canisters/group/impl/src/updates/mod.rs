@@ -1,6 +1,10 @@
 pub mod accept_p2p_swap;
 pub mod add_reaction;
+pub mod c2c_bot_schedule_task;
+pub mod c2c_bot_send_crypto;
+pub mod c2c_bot_send_prize;
 pub mod c2c_bot_subscribe_to_events;
+pub mod c2c_bot_update_storage;
 pub mod c2c_delete_group;
 pub mod c2c_export_group;
 pub mod c2c_export_group_events;
@@ -11,7 +15,9 @@ pub mod c2c_invite_users;
 pub mod c2c_join_group;
 pub mod c2c_leave_group;
 pub mod c2c_local_index;
+pub mod c2c_notify_chit_earned;
 pub mod c2c_notify_p2p_swap_status_change;
+pub mod c2c_set_keyword_alerts;
 pub mod c2c_set_user_suspended;
 pub mod c2c_start_import_into_community;
 pub mod c2c_tip_message;
@@ -29,6 +35,7 @@ pub mod delete_messages;
 pub mod delete_webhook;
 pub mod disable_invite_code;
 pub mod edit_message;
+pub mod enable_encryption;
 pub mod enable_invite_code;
 pub mod end_video_call;
 pub mod follow_thread;
@@ -44,9 +51,14 @@ pub mod register_webhook;
 pub mod remove_participant;
 pub mod remove_reaction;
 pub mod report_message;
+pub mod resolve_report;
+pub mod review_held_message;
 pub mod send_message;
+pub mod set_audit_log_retention;
+pub mod set_automod_rules;
 pub mod set_video_call_presence;
 pub mod start_video_call;
+pub mod timeout_member;
 pub mod toggle_mute_notifications;
 pub mod unblock_user;
 pub mod undelete_messages;
@@ -1,26 +1,63 @@
 use crate::activity_notifications::handle_activity_notification;
+use crate::guards::caller_is_local_user_index;
 use crate::{RuntimeState, execute_update};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
+use group_canister::c2c_bot_pin_message;
 use group_canister::pin_message_v2::{Response::*, *};
-use types::{OCResult, PushEventResult};
+use oc_error_codes::OCErrorCode;
+use types::{AuditLogAction, BotCaller, BotPermissions, Caller, ChatPermission, OCResult, PushEventResult};
 
 #[update(msgpack = true)]
 #[trace]
 fn pin_message_v2(args: Args) -> Response {
-    match execute_update(|state| pin_message_impl(args, state)) {
+    match execute_update(|state| pin_message_impl(args, None, state)) {
         Ok(result) => Success(result),
         Err(error) => Error(error),
     }
 }
 
-fn pin_message_impl(args: Args, state: &mut RuntimeState) -> OCResult<PushEventResult> {
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_pin_message(args: c2c_bot_pin_message::Args) -> c2c_bot_pin_message::Response {
+    execute_update(|state| c2c_bot_pin_message_impl(args, state)).into()
+}
+
+fn c2c_bot_pin_message_impl(args: c2c_bot_pin_message::Args, state: &mut RuntimeState) -> OCResult {
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        &args.initiator,
+        &BotPermissions::from_chat_permission(ChatPermission::PinMessages),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    });
+
+    pin_message_impl(
+        Args {
+            message_index: args.message_index,
+        },
+        Some(caller),
+        state,
+    )
+    .map(|_| ())
+}
+
+fn pin_message_impl(args: Args, ext_caller: Option<Caller>, state: &mut RuntimeState) -> OCResult<PushEventResult> {
     state.data.verify_not_frozen()?;
 
-    let user_id = state.get_caller_user_id()?;
+    let caller = state.verified_caller(ext_caller)?;
+    let agent = caller.agent();
     let now = state.env.now();
-    let result = state.data.chat.pin_message(user_id, args.message_index, now)?;
+    let result = state.data.chat.pin_message(caller, args.message_index, now)?;
 
+    state
+        .data
+        .record_audit_log_entry(agent, None, AuditLogAction::MessagePinned(args.message_index), None, now);
     state.push_bot_notification(result.bot_notification);
     handle_activity_notification(state);
     Ok(PushEventResult {
@@ -59,6 +59,8 @@ fn register_poll_vote_impl(args: Args, state: &mut RuntimeState) -> OCResult<Pol
                             activity: MessageActivity::PollVote,
                             timestamp: now,
                             user_id: matches!(result.value.votes.total, TotalVotes::Visible(_)).then_some(user_id),
+                            sender_name: None,
+                            message_text: None,
                         }),
                         now,
                     );
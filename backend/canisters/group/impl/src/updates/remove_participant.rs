@@ -9,13 +9,15 @@ use group_chat_core::GroupRoleInternal;
 use local_user_index_canister_c2c_client::lookup_user;
 use msgpack::serialize_then_unwrap;
 use oc_error_codes::OCErrorCode;
-use types::{BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, OCResult, UnitResult, UserId};
+use types::{
+    AuditLogAction, BotCaller, BotPermissions, Caller, CanisterId, ChatPermission, Milliseconds, OCResult, UnitResult, UserId,
+};
 use user_canister::c2c_remove_from_group;
 
 #[update(msgpack = true)]
 #[trace]
 async fn block_user(args: group_canister::block_user::Args) -> UnitResult {
-    execute_update_async(|| remove_participant_impl(args.user_id, true, None))
+    execute_update_async(|| remove_participant_impl(args.user_id, true, args.duration, args.reason, None))
         .await
         .into()
 }
@@ -23,7 +25,7 @@ async fn block_user(args: group_canister::block_user::Args) -> UnitResult {
 #[update(msgpack = true)]
 #[trace]
 async fn remove_participant(args: Args) -> UnitResult {
-    execute_update_async(|| remove_participant_impl(args.user_id, false, None))
+    execute_update_async(|| remove_participant_impl(args.user_id, false, None, args.reason, None))
         .await
         .into()
 }
@@ -35,6 +37,8 @@ async fn c2c_bot_remove_user(args: group_canister::c2c_bot_remove_user::Args) ->
         remove_participant_impl(
             args.user_id,
             args.block,
+            None,
+            None,
             Some(Caller::BotV2(BotCaller {
                 bot: args.bot_id,
                 initiator: args.initiator.clone(),
@@ -45,7 +49,13 @@ async fn c2c_bot_remove_user(args: group_canister::c2c_bot_remove_user::Args) ->
     .into()
 }
 
-async fn remove_participant_impl(user_to_remove: UserId, block: bool, ext_caller: Option<Caller>) -> OCResult {
+pub(crate) async fn remove_participant_impl(
+    user_to_remove: UserId,
+    block: bool,
+    block_duration: Option<Milliseconds>,
+    reason: Option<String>,
+    ext_caller: Option<Caller>,
+) -> OCResult {
     // Check the caller can remove the user
     let prepare_result = match read_state(|state| prepare(user_to_remove, block, ext_caller, state))? {
         Some(ok) => ok,
@@ -63,7 +73,16 @@ async fn remove_participant_impl(user_to_remove: UserId, block: bool, ext_caller
     }
 
     // Remove the user from the group
-    mutate_state(|state| commit(user_to_remove, block, prepare_result.removed_by, state))
+    mutate_state(|state| {
+        commit(
+            user_to_remove,
+            block,
+            block_duration,
+            reason,
+            prepare_result.removed_by,
+            state,
+        )
+    })
 }
 
 struct PrepareResult {
@@ -101,10 +120,12 @@ fn prepare(
         };
 
         if let Caller::BotV2(bot_caller) = &caller {
+            let required_permission = if block { ChatPermission::BlockUsers } else { ChatPermission::RemoveMembers };
+
             if !state.data.is_bot_permitted(
                 &bot_caller.bot,
                 &bot_caller.initiator,
-                &BotPermissions::from_chat_permission(ChatPermission::RemoveMembers),
+                &BotPermissions::from_chat_permission(required_permission),
             ) {
                 return Err(OCErrorCode::InitiatorNotAuthorized.into());
             }
@@ -128,15 +149,32 @@ fn prepare(
     }
 }
 
-fn commit(user_to_remove: UserId, block: bool, caller: Caller, state: &mut RuntimeState) -> OCResult {
+fn commit(
+    user_to_remove: UserId,
+    block: bool,
+    block_duration: Option<Milliseconds>,
+    reason: Option<String>,
+    caller: Caller,
+    state: &mut RuntimeState,
+) -> OCResult {
     let agent = caller.agent();
+    let now = state.env.now();
+    let block_until = block_duration.map(|d| now.saturating_add(d));
 
     let bot_notification = state
         .data
         .chat
-        .remove_member(caller, user_to_remove, block, state.env.now())?;
+        .remove_member(caller, user_to_remove, block, block_until, now)?;
 
     state.data.remove_user(user_to_remove, None);
+    state.data.handle_member_restriction_expiry(now);
+    state.data.record_audit_log_entry(
+        agent,
+        Some(user_to_remove),
+        if block { AuditLogAction::UserBlocked(block_until) } else { AuditLogAction::MemberRemoved },
+        reason,
+        now,
+    );
     state.push_bot_notification(bot_notification);
     handle_activity_notification(state);
 
@@ -0,0 +1,51 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::updates::remove_participant::remove_participant_impl;
+use crate::{RuntimeState, execute_update_async, mutate_state, read_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::resolve_report::*;
+use types::{AuditLogAction, AuditLogReportResolution, ModerationAction, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+async fn resolve_report(args: Args) -> Response {
+    execute_update_async(|| resolve_report_impl(args)).await.into()
+}
+
+async fn resolve_report_impl(args: Args) -> OCResult {
+    if matches!(args.action, ModerationAction::RemoveMember) {
+        let sender = read_state(|state| {
+            let member = state.get_calling_member(true)?;
+            state.data.chat.reported_message_sender(member.user_id(), args.report_id)
+        })?;
+
+        remove_participant_impl(sender, false, None, None, None).await?;
+    }
+
+    // The report is only resolved once the sender has been removed
+    mutate_state(|state| commit(args, state))
+}
+
+fn commit(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let caller_id = state.get_calling_member(true)?.user_id();
+    let sender = state.data.chat.reported_message_sender(caller_id, args.report_id)?;
+    let now = state.env.now();
+    let bot_notification = state.data.chat.resolve_report(caller_id, args.report_id, args.action, now)?;
+
+    state.data.handle_member_restriction_expiry(now);
+    state.data.record_audit_log_entry(
+        caller_id,
+        Some(sender),
+        AuditLogAction::ReportResolved(AuditLogReportResolution {
+            report_id: args.report_id,
+            action: args.action,
+        }),
+        None,
+        now,
+    );
+    state.push_bot_notification(bot_notification);
+    handle_activity_notification(state);
+    Ok(())
+}
@@ -13,6 +13,7 @@ use oc_error_codes::OCErrorCode;
 use types::{
     Achievement, BotCaller, BotPermissions, Caller, Chat, ChatId, EventIndex, EventWrapper, FcmData, GroupMessageNotification,
     Message, MessageContent, MessageIndex, OCResult, TimestampMillis, User, UserNotificationPayload,
+    validate_bot_message_components,
 };
 use user_canister::{GroupCanisterEvent, MessageActivity, MessageActivityEvent};
 
@@ -34,19 +35,26 @@ fn c2c_send_message(args: C2CArgs) -> C2CResponse {
     }
 }
 
-#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[update(guard = "caller_is_local_user_index", msgpack = true, idempotent = true)]
 #[trace]
 fn c2c_bot_send_message(args: c2c_bot_send_message::Args) -> c2c_bot_send_message::Response {
     execute_update(|state| c2c_bot_send_message_impl(args, state))
 }
 
-fn c2c_bot_send_message_impl(args: c2c_bot_send_message::Args, state: &mut RuntimeState) -> c2c_bot_send_message::Response {
+fn c2c_bot_send_message_impl(mut args: c2c_bot_send_message::Args, state: &mut RuntimeState) -> c2c_bot_send_message::Response {
+    if let Err(error) = validate_bot_message_components(&args.components) {
+        return Error(OCErrorCode::InvalidRequest.with_message(error));
+    }
+
     let finalised = args.finalised;
+    let components = std::mem::take(&mut args.components);
     let bot_caller = BotCaller {
         bot: args.bot_id,
         initiator: args.initiator.clone(),
     };
     let args: Args = args.into();
+    let thread_root_message_index = args.thread_root_message_index;
+    let message_id = args.message_id;
 
     if !state.data.is_bot_permitted(
         &bot_caller.bot,
@@ -57,7 +65,17 @@ fn c2c_bot_send_message_impl(args: c2c_bot_send_message::Args, state: &mut Runti
     }
 
     match send_message_impl(args, Some(Caller::BotV2(bot_caller)), finalised, state) {
-        Ok(result) => Success(result),
+        Ok(result) => {
+            if !components.is_empty() {
+                let now = state.env.now();
+                state
+                    .data
+                    .chat
+                    .events
+                    .set_bot_message_components(thread_root_message_index, message_id, components, now);
+            }
+            Success(result)
+        }
         Err(error) => Error(error),
     }
 }
@@ -104,7 +122,11 @@ pub(crate) fn send_message_impl(
         },
         finalised,
         now,
-    )?;
+    );
+
+    // The sender may have been timed out by automod
+    state.data.handle_member_restriction_expiry(now);
+    let result = result?;
 
     Ok(process_send_message_result(
         result,
@@ -131,6 +153,11 @@ fn c2c_send_message_impl(args: C2CArgs, state: &mut RuntimeState) -> OCResult<Su
         return Err(OCErrorCode::InitiatorNotAuthorized.into());
     }
 
+    send_prevalidated_message(args, caller, state)
+}
+
+// Sends a message whose content has already been validated, eg. because it contains a completed crypto transfer
+pub(crate) fn send_prevalidated_message(args: C2CArgs, caller: Caller, state: &mut RuntimeState) -> OCResult<SuccessResult> {
     let now = state.env.now();
     let mentioned: Vec<_> = args.mentioned.iter().map(|u| u.user_id).collect();
     let result = state.data.chat.send_message(
@@ -151,7 +178,11 @@ fn c2c_send_message_impl(args: C2CArgs, state: &mut RuntimeState) -> OCResult<Su
         },
         true,
         now,
-    )?;
+    );
+
+    // The sender may have been timed out by automod
+    state.data.handle_member_restriction_expiry(now);
+    let result = result?;
 
     Ok(process_send_message_result(
         result,
@@ -184,6 +215,20 @@ fn process_send_message_result(
     let message_id = message_event.event.message_id;
     let expires_at = message_event.expires_at;
 
+    // Messages removed by automod are only kept so that they can be restored if released by a moderator, so they
+    // don't trigger any timer jobs, achievements, notifications or activity events
+    if result.removed_by_automod {
+        if let Some(expiry) = expires_at {
+            state.data.handle_event_expiry(expiry, now);
+        }
+        return SuccessResult {
+            event_index,
+            message_index,
+            timestamp: now,
+            expires_at,
+        };
+    }
+
     register_timer_jobs(thread_root_message_index, message_event, now, &mut state.data);
 
     if !result.unfinalised_bot_message {
@@ -194,8 +239,12 @@ fn process_send_message_result(
         let message_text = content.notification_text(&mentioned, &[]);
         let group_avatar_id = state.data.chat.avatar.as_ref().map(|d| d.id);
 
+        // Included in mention, reply and keyword activity events so the user canister can fill in outbound notifications
+        let activity_sender_name = sender_display_name.clone().unwrap_or_else(|| sender_username.clone());
+        let activity_message_text = message_text.clone();
+
         // TODO i18n
-        let fcm_body = message_text.clone().unwrap_or(message_type.clone());
+        let fcm_body = message_text.clone().unwrap_or_else(|| content.notification_alt_text());
         let fcm_data = FcmData::for_group(chat_id)
             .set_body(fcm_body)
             .set_sender_name_with_alt(&sender_display_name, &sender_username)
@@ -252,6 +301,10 @@ fn process_send_message_result(
             }
         }
 
+        for user_id in result.keyword_matches {
+            activity_events.push((user_id, MessageActivity::Keyword));
+        }
+
         if let Some(replying_to_event_index) = message_event
             .event
             .replies_to
@@ -278,6 +331,7 @@ fn process_send_message_result(
         }
 
         for (user_id, activity) in activity_events {
+            let include_message_details = activity.has_outbound_notification();
             state.push_event_to_user(
                 user_id,
                 GroupCanisterEvent::MessageActivity(MessageActivityEvent {
@@ -289,6 +343,8 @@ fn process_send_message_result(
                     activity,
                     timestamp: now,
                     user_id: Some(sender),
+                    sender_name: include_message_details.then(|| activity_sender_name.clone()),
+                    message_text: include_message_details.then(|| activity_message_text.clone()).flatten(),
                 }),
                 now,
             );
@@ -306,7 +362,7 @@ fn process_send_message_result(
     }
 }
 
-fn register_timer_jobs(
+pub(crate) fn register_timer_jobs(
     thread_root_message_index: Option<MessageIndex>,
     message_event: &EventWrapper<Message>,
     now: TimestampMillis,
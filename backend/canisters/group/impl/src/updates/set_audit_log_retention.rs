@@ -0,0 +1,29 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::set_audit_log_retention::*;
+use oc_error_codes::OCErrorCode;
+use types::{AuditLogAction, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+fn set_audit_log_retention(args: Args) -> Response {
+    execute_update(|state| set_audit_log_retention_impl(args, state)).into()
+}
+
+fn set_audit_log_retention_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+    if !member.role().can_set_audit_log_retention() {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller_id = member.user_id();
+    let now = state.env.now();
+    state.data.set_audit_log_retention(args.retention, now)?;
+    state
+        .data
+        .record_audit_log_entry(caller_id, None, AuditLogAction::RetentionUpdated(args.retention), None, now);
+    Ok(())
+}
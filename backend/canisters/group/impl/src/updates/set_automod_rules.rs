@@ -0,0 +1,26 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::set_automod_rules::*;
+use types::{AuditLogAction, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+fn set_automod_rules(args: Args) -> Response {
+    execute_update(|state| set_automod_rules_impl(args, state)).into()
+}
+
+fn set_automod_rules_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let caller_id = state.get_calling_member(true)?.user_id();
+    let now = state.env.now();
+    state.data.chat.set_automod_rules(caller_id, args.rules, now)?;
+    state
+        .data
+        .record_audit_log_entry(caller_id, None, AuditLogAction::AutomodRulesUpdated, None, now);
+
+    handle_activity_notification(state);
+    Ok(())
+}
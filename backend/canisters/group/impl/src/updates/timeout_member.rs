@@ -0,0 +1,33 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::timeout_member::*;
+use types::{AuditLogAction, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+fn timeout_member(args: Args) -> Response {
+    execute_update(|state| timeout_member_impl(args, state)).into()
+}
+
+fn timeout_member_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let caller_id = state.get_calling_member(true)?.user_id();
+    let now = state.env.now();
+    let bot_notification = state.data.chat.timeout_member(caller_id, args.user_id, args.duration, now)?;
+
+    let until = args.duration.map(|d| now.saturating_add(d));
+    state.data.handle_member_restriction_expiry(now);
+    state.data.record_audit_log_entry(
+        caller_id,
+        Some(args.user_id),
+        AuditLogAction::MemberTimedOut(until),
+        args.reason,
+        now,
+    );
+    state.push_bot_notification(bot_notification);
+    handle_activity_notification(state);
+    Ok(())
+}
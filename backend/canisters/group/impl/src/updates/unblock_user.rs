@@ -5,7 +5,7 @@ use canister_tracing_macros::trace;
 use chat_events::ChatEventInternal;
 use group_canister::unblock_user::*;
 use oc_error_codes::OCErrorCode;
-use types::{OCResult, UsersUnblocked};
+use types::{AuditLogAction, OCResult, UsersUnblocked};
 
 #[update(msgpack = true)]
 #[trace]
@@ -38,6 +38,13 @@ fn unblock_user_impl(args: Args, state: &mut RuntimeState) -> OCResult {
                 .events
                 .push_main_event(ChatEventInternal::UsersUnblocked(Box::new(event)), now);
 
+            state.data.record_audit_log_entry(
+                caller_member.user_id(),
+                Some(args.user_id),
+                AuditLogAction::UserUnblocked,
+                None,
+                now,
+            );
             state.push_bot_notification(result.bot_notification);
             handle_activity_notification(state);
             Ok(())
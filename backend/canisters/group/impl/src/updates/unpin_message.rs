@@ -1,25 +1,62 @@
 use crate::activity_notifications::handle_activity_notification;
+use crate::guards::caller_is_local_user_index;
 use crate::{RuntimeState, execute_update};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
+use group_canister::c2c_bot_unpin_message;
 use group_canister::unpin_message::{Response::*, *};
-use types::{OCResult, PushEventResult};
+use oc_error_codes::OCErrorCode;
+use types::{AuditLogAction, BotCaller, BotPermissions, Caller, ChatPermission, OCResult, PushEventResult};
 
 #[update(msgpack = true)]
 #[trace]
 fn unpin_message(args: Args) -> Response {
-    match execute_update(|state| unpin_message_impl(args, state)) {
+    match execute_update(|state| unpin_message_impl(args, None, state)) {
         Ok(result) => SuccessV2(result),
         Err(error) => Error(error),
     }
 }
 
-fn unpin_message_impl(args: Args, state: &mut RuntimeState) -> OCResult<PushEventResult> {
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_unpin_message(args: c2c_bot_unpin_message::Args) -> c2c_bot_unpin_message::Response {
+    execute_update(|state| c2c_bot_unpin_message_impl(args, state)).into()
+}
+
+fn c2c_bot_unpin_message_impl(args: c2c_bot_unpin_message::Args, state: &mut RuntimeState) -> OCResult {
+    if !state.data.is_bot_permitted(
+        &args.bot_id,
+        &args.initiator,
+        &BotPermissions::from_chat_permission(ChatPermission::PinMessages),
+    ) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let caller = Caller::BotV2(BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    });
+
+    unpin_message_impl(
+        Args {
+            message_index: args.message_index,
+        },
+        Some(caller),
+        state,
+    )
+    .map(|_| ())
+}
+
+fn unpin_message_impl(args: Args, ext_caller: Option<Caller>, state: &mut RuntimeState) -> OCResult<PushEventResult> {
     state.data.verify_not_frozen()?;
 
-    let user_id = state.get_caller_user_id()?;
+    let caller = state.verified_caller(ext_caller)?;
+    let agent = caller.agent();
     let now = state.env.now();
-    let result = state.data.chat.unpin_message(user_id, args.message_index, now)?;
+    let result = state.data.chat.unpin_message(caller, args.message_index, now)?;
+    state
+        .data
+        .record_audit_log_entry(agent, None, AuditLogAction::MessageUnpinned(args.message_index), None, now);
     state.push_bot_notification(result.bot_notification);
     handle_activity_notification(state);
     Ok(PushEventResult {
@@ -1,27 +1,65 @@
 use crate::activity_notifications::handle_activity_notification;
+use crate::guards::caller_is_local_user_index;
 use crate::updates::update_group_v2::Response::*;
 use crate::{Data, RuntimeState, execute_update_async, jobs, mutate_state, read_state};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
-use group_canister::update_group_v2::*;
+use group_canister::{c2c_bot_update_chat_details, update_group_v2::*};
 use group_community_common::{ExpiringMember, Members};
 use group_index_canister::{c2c_make_private, c2c_update_group};
 use oc_error_codes::OCErrorCode;
 use tracing::error;
 use types::{
-    AccessGateConfigInternal, CanisterId, ChatId, Document, OCResult, OptionUpdate, TimestampMillis, Timestamped, UserId,
+    AccessGateConfigInternal, AuditLogAction, BotCaller, BotPermissions, Caller, CanisterId, ChatId, ChatPermission, Document,
+    OCResult, OptionUpdate, TimestampMillis, Timestamped, UnitResult, UserId,
 };
 
 #[update(msgpack = true)]
 #[trace]
 async fn update_group_v2(args: Args) -> Response {
-    execute_update_async(|| update_group_impl(args)).await
+    execute_update_async(|| update_group_impl(args, None)).await
 }
 
-async fn update_group_impl(mut args: Args) -> Response {
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+async fn c2c_bot_update_chat_details(args: c2c_bot_update_chat_details::Args) -> c2c_bot_update_chat_details::Response {
+    execute_update_async(|| c2c_bot_update_chat_details_impl(args)).await
+}
+
+async fn c2c_bot_update_chat_details_impl(args: c2c_bot_update_chat_details::Args) -> UnitResult {
+    let bot_caller = BotCaller {
+        bot: args.bot_id,
+        initiator: args.initiator,
+    };
+
+    if !read_state(|state| {
+        state.data.is_bot_permitted(
+            &bot_caller.bot,
+            &bot_caller.initiator,
+            &BotPermissions::from_chat_permission(ChatPermission::UpdateGroup),
+        )
+    }) {
+        return OCErrorCode::InitiatorNotAuthorized.into();
+    }
+
+    let args = Args {
+        name: args.name,
+        description: args.description,
+        rules: args.rules,
+        avatar: args.avatar,
+        ..Default::default()
+    };
+
+    match update_group_impl(args, Some(Caller::BotV2(bot_caller))).await {
+        SuccessV2(_) => UnitResult::Success,
+        Error(error) => UnitResult::Error(error),
+    }
+}
+
+async fn update_group_impl(mut args: Args, ext_caller: Option<Caller>) -> Response {
     clean_args(&mut args);
 
-    let prepare_result = match read_state(|state| prepare(&args, state)) {
+    let prepare_result = match read_state(|state| prepare(&args, ext_caller, state)) {
         Ok(ok) => ok,
         Err(response) => return Error(response),
     };
@@ -72,7 +110,7 @@ async fn update_group_impl(mut args: Args) -> Response {
         };
     }
 
-    SuccessV2(mutate_state(|state| commit(prepare_result.my_user_id, args, state)))
+    SuccessV2(mutate_state(|state| commit(prepare_result.caller.agent(), args, state)))
 }
 
 fn clean_args(args: &mut Args) {
@@ -85,7 +123,7 @@ fn clean_args(args: &mut Args) {
 }
 
 struct PrepareResult {
-    my_user_id: UserId,
+    caller: Caller,
     group_index_canister_id: CanisterId,
     is_public: bool,
     chat_id: ChatId,
@@ -95,7 +133,7 @@ struct PrepareResult {
     gate_config: Option<AccessGateConfigInternal>,
 }
 
-fn prepare(args: &Args, state: &RuntimeState) -> OCResult<PrepareResult> {
+fn prepare(args: &Args, ext_caller: Option<Caller>, state: &RuntimeState) -> OCResult<PrepareResult> {
     state.data.verify_not_frozen()?;
 
     if let OptionUpdate::SetToSome(gate_config) = &args.gate_config {
@@ -110,11 +148,11 @@ fn prepare(args: &Args, state: &RuntimeState) -> OCResult<PrepareResult> {
         .map(|gcu| gcu.into())
         .apply_to(state.data.chat.gate_config.value.clone());
 
-    let member = state.get_calling_member(true)?;
+    let caller = state.verified_caller(ext_caller)?;
     let permissions = args.permissions_v2.as_ref();
 
     state.data.chat.can_update(
-        member.user_id(),
+        &caller,
         &args.name,
         &args.description,
         &args.rules,
@@ -126,7 +164,7 @@ fn prepare(args: &Args, state: &RuntimeState) -> OCResult<PrepareResult> {
     let avatar_update = args.avatar.as_ref().expand();
 
     Ok(PrepareResult {
-        my_user_id: member.user_id(),
+        caller,
         group_index_canister_id: state.data.group_index_canister_id,
         is_public: args.public.unwrap_or(state.data.chat.is_public.value),
         chat_id: state.env.canister_id().into(),
@@ -162,6 +200,8 @@ fn commit(my_user_id: UserId, args: Args, state: &mut RuntimeState) -> SuccessRe
         }
     }
 
+    let audit_log_actions = audit_log_actions(&args);
+
     let result = state.data.chat.do_update(
         my_user_id,
         args.name,
@@ -183,6 +223,9 @@ fn commit(my_user_id: UserId, args: Args, state: &mut RuntimeState) -> SuccessRe
 
     jobs::expire_members::restart_job(state);
 
+    for action in audit_log_actions {
+        state.data.record_audit_log_entry(my_user_id, None, action, None, now);
+    }
     state.push_bot_notifications(result.bot_notifications);
     handle_activity_notification(state);
     SuccessResult {
@@ -190,6 +233,27 @@ fn commit(my_user_id: UserId, args: Args, state: &mut RuntimeState) -> SuccessRe
     }
 }
 
+fn audit_log_actions(args: &Args) -> Vec<AuditLogAction> {
+    let mut actions = Vec::new();
+    if args.name.is_some()
+        || args.description.is_some()
+        || args.avatar.has_update()
+        || args.events_ttl.has_update()
+        || args.gate_config.has_update()
+        || args.public.is_some()
+        || args.messages_visible_to_non_members.is_some()
+    {
+        actions.push(AuditLogAction::DetailsUpdated);
+    }
+    if args.rules.is_some() {
+        actions.push(AuditLogAction::RulesUpdated);
+    }
+    if args.permissions_v2.is_some() {
+        actions.push(AuditLogAction::PermissionsUpdated);
+    }
+    actions
+}
+
 pub fn update_member_expiry(data: &mut Data, prev_gate_config: &Option<AccessGateConfigInternal>, now: TimestampMillis) {
     let prev_gate_expiry = prev_gate_config.as_ref().and_then(|gc| gc.expiry());
     let new_gate_config = data.chat.gate_config.value.as_ref();
@@ -1,3 +1,4 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::cached_hot_groups::CachedHotGroups;
 use crate::model::deleted_communities::DeletedCommunities;
 use crate::model::deleted_groups::DeletedGroups;
@@ -12,6 +13,8 @@ use canister_state_macros::canister_state;
 use constants::MINUTE_IN_MS;
 use fire_and_forget_handler::FireAndForgetHandler;
 use group_index_canister::ChildCanisterType;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use local_user_index_canister::{GroupIndexEvent as LocalIndexEvent, NameChanged, VerifiedChanged};
 use model::local_index_event_batch::LocalIndexEventBatch;
 use model::local_index_map::LocalIndexMap;
@@ -19,7 +22,7 @@ use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashSet};
-use timer_job_queues::GroupedTimerJobQueue;
+use timer_job_queues::{GroupedTimerJobQueue, TimerJobQueueMetrics};
 use types::{
     AccessGate, BuildVersion, CanisterId, ChatId, ChildCanisterWasms, CommunityId, Cycles, FrozenGroupInfo, IdempotentEnvelope,
     Milliseconds, TimestampMillis, Timestamped, UserId,
@@ -246,6 +249,11 @@ impl RuntimeState {
                 .map(|(c, h)| (*c, hex::encode(h)))
                 .collect(),
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
+            timer_job_queues: BTreeMap::from([(
+                "local_index_event_sync_queue".to_string(),
+                self.data.local_index_event_sync_queue.metrics(),
+            )]),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 proposals_bot: self.data.proposals_bot_user_id.into(),
@@ -290,6 +298,12 @@ struct Data {
     pub idempotency_checker: IdempotencyChecker,
     #[serde(alias = "local_group_index_event_sync_queue")]
     pub local_index_event_sync_queue: GroupedTimerJobQueue<LocalIndexEventBatch>,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -337,6 +351,7 @@ impl Data {
             rng_seed: [0; 32],
             idempotency_checker: IdempotencyChecker::default(),
             local_index_event_sync_queue: GroupedTimerJobQueue::new(10, false),
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 
@@ -411,6 +426,15 @@ impl Data {
 
         self.cached_metrics = cached_metrics;
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[cfg(test)]
@@ -446,6 +470,7 @@ impl Default for Data {
             rng_seed: [0; 32],
             idempotency_checker: IdempotencyChecker::default(),
             local_index_event_sync_queue: GroupedTimerJobQueue::new(10, false),
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 }
@@ -488,9 +513,18 @@ pub struct Metrics {
     pub upload_wasm_chunks_whitelist: Vec<Principal>,
     pub wasm_chunks_uploaded: Vec<(ChildCanisterType, String)>,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
+    pub timer_job_queues: BTreeMap<String, TimerJobQueueMetrics>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct CachedMetrics {
     pub last_run: TimestampMillis,
@@ -1,5 +1,6 @@
 use crate::Data;
 use crate::lifecycle::{init_env, init_state};
+use crate::memory::get_canister_logs_memory;
 use canister_tracing_macros::trace;
 use group_index_canister::init::Args;
 use ic_cdk::init;
@@ -9,7 +10,7 @@ use utils::cycles::init_cycles_dispenser_client;
 #[init]
 #[trace]
 fn init(args: Args) {
-    canister_logger::init(args.test_mode);
+    canister_logger::init(args.test_mode, get_canister_logs_memory());
     init_cycles_dispenser_client(args.cycles_dispenser_canister_id, args.test_mode);
 
     let env = init_env([0; 32]);
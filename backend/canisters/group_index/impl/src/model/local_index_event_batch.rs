@@ -1,12 +1,12 @@
 use crate::LocalIndexEvent;
-use timer_job_queues::{TimerJobItem, grouped_timer_job_batch};
+use timer_job_queues::{TimerJobItem, TimerJobOutcome, grouped_timer_job_batch};
 use types::{CanisterId, IdempotentEnvelope};
 use utils::canister::should_retry_failed_c2c_call;
 
 grouped_timer_job_batch!(LocalIndexEventBatch, CanisterId, IdempotentEnvelope<LocalIndexEvent>, 1000);
 
 impl TimerJobItem for LocalIndexEventBatch {
-    async fn process(&self) -> Result<(), bool> {
+    async fn process(&self) -> TimerJobOutcome {
         let response = local_user_index_canister_c2c_client::c2c_group_index(
             self.key,
             &local_user_index_canister::c2c_group_index::Args {
@@ -16,10 +16,10 @@ impl TimerJobItem for LocalIndexEventBatch {
         .await;
 
         match response {
-            Ok(_) => Ok(()),
+            Ok(_) => TimerJobOutcome::Success,
             Err(error) => {
                 let retry = should_retry_failed_c2c_call(error.reject_code(), error.message());
-                Err(retry)
+                TimerJobOutcome::retry_if(retry)
             }
         }
     }
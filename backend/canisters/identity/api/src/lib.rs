@@ -8,7 +8,7 @@ mod updates;
 pub use lifecycle::*;
 pub use queries::*;
 use ts_export::ts_export;
-use types::CanisterId;
+use types::{CanisterId, SignedDelegation};
 pub use updates::*;
 
 // zzzxd-webau-thnke-yr7oc-cai
@@ -41,3 +41,35 @@ pub struct WebAuthnKey {
     pub cross_platform: bool,
     pub aaguid: [u8; 16],
 }
+
+// Proves that the caller has recently re-authenticated using one of the credentials already linked to their account.
+// Both variants must be over a challenge returned by `generate_reauthentication_challenge`. For a delegation, the
+// challenge is used as the session key when requesting the delegation.
+#[ts_export(identity)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum ReauthenticationProof {
+    Delegation(DelegationReauthentication),
+    WebAuthn(WebAuthnAssertion),
+}
+
+#[ts_export(identity)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct DelegationReauthentication {
+    #[serde(with = "serde_bytes")]
+    pub public_key: Vec<u8>,
+    pub delegation: SignedDelegation,
+}
+
+// A WebAuthn assertion over a challenge returned by `generate_reauthentication_challenge`
+#[ts_export(identity)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct WebAuthnAssertion {
+    #[serde(with = "serde_bytes")]
+    pub credential_id: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub authenticator_data: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub client_data_json: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
+}
@@ -11,14 +11,20 @@ fn main() {
     generate_ts_method!(identity, check_auth_principal_v2);
     generate_ts_method!(identity, get_delegation);
     generate_ts_method!(identity, lookup_webauthn_pubkey);
+    generate_ts_method!(identity, webauthn_credentials);
 
+    generate_ts_method!(identity, add_webauthn_credential);
     generate_ts_method!(identity, approve_identity_link);
     generate_ts_method!(identity, create_identity);
     generate_ts_method!(identity, delete_user);
     generate_ts_method!(identity, generate_challenge);
+    generate_ts_method!(identity, generate_reauthentication_challenge);
     generate_ts_method!(identity, initiate_identity_link);
     generate_ts_method!(identity, prepare_delegation);
     generate_ts_method!(identity, remove_identity_link);
+    generate_ts_method!(identity, remove_identity_link_v2);
+    generate_ts_method!(identity, remove_webauthn_credential);
+    generate_ts_method!(identity, update_webauthn_credential);
 
     candid::export_service!();
     std::print!("{}", __export_service());
@@ -2,3 +2,4 @@ pub mod auth_principals;
 pub mod check_auth_principal_v2;
 pub mod get_delegation;
 pub mod lookup_webauthn_pubkey;
+pub mod webauthn_credentials;
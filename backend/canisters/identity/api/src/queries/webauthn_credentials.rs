@@ -0,0 +1,29 @@
+use candid::CandidType;
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Empty, TimestampMillis};
+
+pub type Args = Empty;
+
+#[ts_export(identity, webauthn_credentials)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(Vec<WebAuthnCredential>),
+    Error(OCError),
+}
+
+#[ts_export(identity, webauthn_credentials)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct WebAuthnCredential {
+    #[serde(with = "serde_bytes")]
+    pub credential_id: Vec<u8>,
+    pub name: Option<String>,
+    pub origin: String,
+    pub cross_platform: bool,
+    pub aaguid: [u8; 16],
+    pub is_recovery: bool,
+    pub is_current_identity: bool,
+    pub created: TimestampMillis,
+    pub last_used: TimestampMillis,
+}
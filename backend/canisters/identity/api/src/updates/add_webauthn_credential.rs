@@ -0,0 +1,18 @@
+use crate::{ReauthenticationProof, WebAuthnAssertion, WebAuthnKey};
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+use ts_export::ts_export;
+use types::UnitResult;
+
+#[ts_export(identity, add_webauthn_credential)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub webauthn_key: WebAuthnKey,
+    pub name: Option<String>,
+    pub is_recovery: bool,
+    pub reauthentication: ReauthenticationProof,
+    // An assertion from the new credential over the current reauthentication challenge, proving possession of its key
+    pub new_credential_assertion: WebAuthnAssertion,
+}
+
+pub type Response = UnitResult;
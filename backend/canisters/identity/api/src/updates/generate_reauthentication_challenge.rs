@@ -0,0 +1,22 @@
+use candid::{CandidType, Deserialize};
+use oc_error_codes::OCError;
+use serde::Serialize;
+use ts_export::ts_export;
+use types::{Empty, TimestampMillis};
+
+pub type Args = Empty;
+
+#[ts_export(identity, generate_reauthentication_challenge)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(identity, generate_reauthentication_challenge)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    #[serde(with = "serde_bytes")]
+    pub challenge: Vec<u8>,
+    pub expires: TimestampMillis,
+}
@@ -1,5 +1,6 @@
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
+use types::MultiUserChat;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Args {
@@ -16,6 +17,7 @@ pub enum Response {
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
 pub enum KeyType {
     User,
+    Chat { chat: MultiUserChat, epoch: u32 },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
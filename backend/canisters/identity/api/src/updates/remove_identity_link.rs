@@ -1,4 +1,5 @@
 use candid::{CandidType, Deserialize, Principal};
+use oc_error_codes::OCError;
 use serde::Serialize;
 use ts_export::ts_export;
 
@@ -15,4 +16,5 @@ pub enum Response {
     CannotUnlinkActivePrincipal,
     IdentityLinkNotFound,
     UserNotFound,
+    Error(OCError),
 }
@@ -0,0 +1,14 @@
+use crate::ReauthenticationProof;
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+use ts_export::ts_export;
+use types::UnitResult;
+
+#[ts_export(identity, remove_identity_link_v2)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub linked_principal: Principal,
+    pub reauthentication: ReauthenticationProof,
+}
+
+pub type Response = UnitResult;
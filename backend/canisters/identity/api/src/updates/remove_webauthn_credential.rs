@@ -0,0 +1,15 @@
+use crate::ReauthenticationProof;
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+use ts_export::ts_export;
+use types::UnitResult;
+
+#[ts_export(identity, remove_webauthn_credential)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    #[serde(with = "serde_bytes")]
+    pub credential_id: Vec<u8>,
+    pub reauthentication: ReauthenticationProof,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,19 @@
+use crate::ReauthenticationProof;
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+use ts_export::ts_export;
+use types::{OptionUpdate, UnitResult};
+
+#[ts_export(identity, update_webauthn_credential)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    #[serde(with = "serde_bytes")]
+    pub credential_id: Vec<u8>,
+    #[ts(as = "types::OptionUpdateString")]
+    pub name: OptionUpdate<String>,
+    pub is_recovery: Option<bool>,
+    // Only required if `is_recovery` is being changed
+    pub reauthentication: Option<ReauthenticationProof>,
+}
+
+pub type Response = UnitResult;
@@ -1,19 +1,26 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::challenges::Challenges;
 use crate::model::encryption_key_requests::EncryptionKeyRequests;
 use crate::model::identity_link_requests::IdentityLinkRequests;
 use crate::model::identity_link_via_qr_code_requests::IdentityLinkViaQrCodeRequests;
+use crate::model::reauthentication_challenges::ReauthenticationChallenges;
 use crate::model::salt::Salt;
 use crate::model::user_principals::{AuthPrincipal, UserPrincipals};
 use crate::model::webauthn_keys::WebAuthnKeys;
 use candid::Principal;
 use canister_state_macros::canister_state;
-use constants::NANOS_PER_MILLISECOND;
-use ic_canister_sig_creation::CanisterSigPublicKey;
-use ic_canister_sig_creation::signature_map::{LABEL_SIG, SignatureMap};
+use constants::{MINUTE_IN_MS, NANOS_PER_MILLISECOND};
+use http_request::{OpenMetrics, OpenMetricsEncoder};
+use ic_canister_sig_creation::signature_map::{CanisterSigInputs, LABEL_SIG, SignatureMap};
+use ic_canister_sig_creation::{CanisterSigPublicKey, DELEGATION_SIG_DOMAIN, delegation_signature_msg};
 use ic_cdk::api::certified_data_set;
 use ic_certificate_verification::{CertificateVerificationError, VerifyCertificate};
-use identity_canister::{WEBAUTHN_ORIGINATING_CANISTER, WebAuthnKey};
-use identity_utils::extract_certificate;
+use ic_certification::{Certificate, LookupResult};
+use identity_canister::{
+    DelegationReauthentication, ReauthenticationProof, WEBAUTHN_ORIGINATING_CANISTER, WebAuthnAssertion, WebAuthnKey,
+};
+use identity_utils::{extract_certificate, extract_certificate_and_tree};
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use oc_error_codes::OCErrorCode;
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
@@ -31,6 +38,9 @@ mod memory;
 mod model;
 mod queries;
 mod updates;
+mod webauthn;
+
+const REAUTHENTICATION_WINDOW: Milliseconds = 5 * MINUTE_IN_MS;
 
 thread_local! {
     static WASM_VERSION: RefCell<Timestamped<BuildVersion>> = RefCell::default();
@@ -53,6 +63,15 @@ impl RuntimeState {
         self.data.user_index_canister_id == caller
     }
 
+    // Sensitive changes require proof that the caller has recently re-authenticated using one of the credentials
+    // already linked to their account
+    pub fn verify_reauthentication(&mut self, auth_principal: Principal, proof: &ReauthenticationProof) -> OCResult {
+        let now = self.env.now();
+        let challenge = self.data.reauthentication_challenges.take(&auth_principal, now);
+        self.data
+            .verify_reauthentication(auth_principal, proof, challenge.as_ref().map(|c| c.as_slice()), now)
+    }
+
     pub fn der_encode_canister_sig_key(&self, seed: [u8; 32]) -> Vec<u8> {
         let canister_id = self.env.canister_id();
         CanisterSigPublicKey::new(canister_id, seed.to_vec()).to_der()
@@ -154,6 +173,7 @@ impl RuntimeState {
             auth_principals: self.data.user_principals.auth_principals_count(),
             originating_canisters: self.data.user_principals.originating_canisters().clone(),
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 cycles_dispenser: self.data.cycles_dispenser_canister_id,
@@ -173,6 +193,8 @@ struct Data {
     identity_link_requests: IdentityLinkRequests,
     identity_link_via_qr_code_requests: IdentityLinkViaQrCodeRequests,
     webauthn_keys: WebAuthnKeys,
+    #[serde(default)]
+    reauthentication_challenges: ReauthenticationChallenges,
     #[serde(skip)]
     signature_map: SignatureMap,
     #[serde(default)]
@@ -183,6 +205,12 @@ struct Data {
     rng_seed: [u8; 32],
     challenges: Challenges,
     test_mode: bool,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -205,6 +233,7 @@ impl Data {
             identity_link_requests: IdentityLinkRequests::default(),
             identity_link_via_qr_code_requests: IdentityLinkViaQrCodeRequests::default(),
             webauthn_keys: WebAuthnKeys::default(),
+            reauthentication_challenges: ReauthenticationChallenges::default(),
             signature_map: SignatureMap::default(),
             encryption_key_requests: EncryptionKeyRequests::default(),
             ic_root_key,
@@ -212,6 +241,7 @@ impl Data {
             rng_seed: [0; 32],
             challenges: Challenges::default(),
             test_mode,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 
@@ -248,25 +278,160 @@ impl Data {
     ) -> OCResult {
         if auth_principal.originating_canister != WEBAUTHN_ORIGINATING_CANISTER {
             let certificate = extract_certificate(signature).map_err(|e| OCErrorCode::MalformedSignature.with_message(e))?;
-            let now_nanos = (now * NANOS_PER_MILLISECOND) as u128;
-            let max_offset_nanos = (max_offset * NANOS_PER_MILLISECOND) as u128;
-            certificate
-                .verify(
-                    auth_principal.originating_canister.as_slice(),
-                    self.ic_root_key.as_slice(),
-                    &now_nanos,
-                    &max_offset_nanos,
-                )
-                .map_err(|error| match error {
-                    CertificateVerificationError::TimeTooFarInThePast { .. } => OCErrorCode::DelegationTooOld,
-                    _ => OCErrorCode::InvalidSignature,
-                })?;
+            self.verify_certificate(&certificate, auth_principal.originating_canister, now, max_offset)?;
         } else {
             // TODO verify WebAuthn signatures somehow
         }
 
         Ok(())
     }
+
+    fn verify_certificate(
+        &self,
+        certificate: &Certificate,
+        canister_id: CanisterId,
+        now: TimestampMillis,
+        max_offset: Milliseconds,
+    ) -> OCResult {
+        let now_nanos = (now * NANOS_PER_MILLISECOND) as u128;
+        let max_offset_nanos = (max_offset * NANOS_PER_MILLISECOND) as u128;
+        certificate
+            .verify(
+                canister_id.as_slice(),
+                self.ic_root_key.as_slice(),
+                &now_nanos,
+                &max_offset_nanos,
+            )
+            .map_err(|error| match error {
+                CertificateVerificationError::TimeTooFarInThePast { .. } => OCErrorCode::DelegationTooOld.into(),
+                _ => OCErrorCode::InvalidSignature.into(),
+            })
+    }
+
+    fn verify_reauthentication(
+        &self,
+        auth_principal: Principal,
+        proof: &ReauthenticationProof,
+        challenge: Option<&[u8]>,
+        now: TimestampMillis,
+    ) -> OCResult {
+        let user_principal_index = self
+            .user_principals
+            .get_auth_principal(&auth_principal)
+            .ok_or(OCErrorCode::InitiatorNotFound)?
+            .user_principal_index;
+
+        let challenge =
+            challenge.ok_or(OCErrorCode::ReauthenticationRequired.with_message("No reauthentication challenge found"))?;
+
+        match proof {
+            ReauthenticationProof::Delegation(delegation) => {
+                self.verify_delegation_reauthentication(user_principal_index, delegation, challenge, now)
+            }
+            ReauthenticationProof::WebAuthn(assertion) => {
+                self.verify_webauthn_reauthentication(user_principal_index, assertion, challenge)
+            }
+        }
+    }
+
+    // The delegation must have been signed within the last few minutes by the originating canister of an identity which
+    // is linked to the same user. This is a full canister signature check, so it ties the proof to that identity. The
+    // delegation must be to the challenge issued by `generate_reauthentication_challenge`, so that it can't be replayed
+    // and can't be a delegation obtained earlier for some other purpose.
+    fn verify_delegation_reauthentication(
+        &self,
+        user_principal_index: u32,
+        proof: &DelegationReauthentication,
+        challenge: &[u8],
+        now: TimestampMillis,
+    ) -> OCResult {
+        if proof.delegation.delegation.pubkey != challenge {
+            return Err(OCErrorCode::ReauthenticationRequired.with_message("Delegation is not to the issued challenge"));
+        }
+
+        let signer = Principal::self_authenticating(&proof.public_key);
+        let signer_details = self
+            .user_principals
+            .get_auth_principal(&signer)
+            .filter(|a| a.user_principal_index == user_principal_index)
+            .ok_or(OCErrorCode::ReauthenticationRequired.with_message("Signer is not linked to this account"))?;
+
+        if signer_details.originating_canister == WEBAUTHN_ORIGINATING_CANISTER {
+            return Err(
+                OCErrorCode::ReauthenticationRequired.with_message("Passkeys must reauthenticate via a WebAuthn assertion")
+            );
+        }
+
+        let public_key = CanisterSigPublicKey::try_from(proof.public_key.as_slice())
+            .map_err(|e| OCErrorCode::InvalidPublicKey.with_message(e))?;
+        if public_key.canister_id != signer_details.originating_canister {
+            return Err(OCErrorCode::InvalidPublicKey.with_message("Public key not issued by originating canister"));
+        }
+
+        let (certificate, tree) = extract_certificate_and_tree(&proof.delegation.signature)
+            .map_err(|e| OCErrorCode::MalformedSignature.with_message(e))?;
+        self.verify_certificate(&certificate, public_key.canister_id, now, REAUTHENTICATION_WINDOW)?;
+
+        let certified_data_path: [&[u8]; 3] = [b"canister", public_key.canister_id.as_slice(), b"certified_data"];
+        let LookupResult::Found(certified_data) = certificate.tree.lookup_path(&certified_data_path) else {
+            return Err(OCErrorCode::InvalidSignature.with_message("Certified data not found"));
+        };
+        if certified_data != tree.digest().as_slice() {
+            return Err(OCErrorCode::InvalidSignature.with_message("Tree does not match certified data"));
+        }
+
+        let message_hash = CanisterSigInputs {
+            domain: DELEGATION_SIG_DOMAIN,
+            seed: &public_key.seed,
+            message: &delegation_signature_msg(
+                &proof.delegation.delegation.pubkey,
+                proof.delegation.delegation.expiration,
+                None,
+            ),
+        }
+        .message_hash();
+        let seed_hash = sha256(&public_key.seed);
+        let signature_path: [&[u8]; 3] = [b"sig", seed_hash.as_slice(), message_hash.as_slice()];
+
+        if matches!(tree.lookup_path(&signature_path), LookupResult::Found(_)) {
+            Ok(())
+        } else {
+            Err(OCErrorCode::InvalidSignature.with_message("Signature not found in tree"))
+        }
+    }
+
+    fn verify_webauthn_reauthentication(
+        &self,
+        user_principal_index: u32,
+        assertion: &WebAuthnAssertion,
+        challenge: &[u8],
+    ) -> OCResult {
+        let key = self
+            .webauthn_keys
+            .get(assertion.credential_id.clone())
+            .ok_or(OCErrorCode::WebAuthnCredentialNotFound)?;
+
+        let signer = Principal::self_authenticating(&key.public_key);
+        if self
+            .user_principals
+            .get_auth_principal(&signer)
+            .is_none_or(|a| a.user_principal_index != user_principal_index)
+        {
+            return Err(OCErrorCode::WebAuthnCredentialNotFound.into());
+        }
+
+        webauthn::verify_assertion(&key.public_key, &key.origin, assertion, challenge)
+            .map_err(|e| OCErrorCode::InvalidSignature.with_message(e))
+    }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 fn check_public_key(caller: Principal, public_key: &[u8]) -> Result<(), String> {
@@ -301,9 +466,17 @@ pub struct Metrics {
     pub auth_principals: u32,
     pub originating_canisters: HashMap<CanisterId, u32>,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct CanisterIds {
     pub user_index: CanisterId,
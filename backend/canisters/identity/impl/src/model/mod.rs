@@ -2,6 +2,7 @@ pub mod challenges;
 pub mod encryption_key_requests;
 pub mod identity_link_requests;
 pub mod identity_link_via_qr_code_requests;
+pub mod reauthentication_challenges;
 pub mod salt;
 pub mod user_principals;
 pub mod webauthn_keys;
@@ -0,0 +1,48 @@
+use candid::Principal;
+use constants::MINUTE_IN_MS;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::{Milliseconds, TimestampMillis};
+
+const REAUTHENTICATION_CHALLENGE_LIFETIME: Milliseconds = 5 * MINUTE_IN_MS;
+
+// Single use challenges which must be signed by a WebAuthn credential, or delegated to by an identity, in order to
+// re-authenticate
+#[derive(Serialize, Deserialize, Default)]
+pub struct ReauthenticationChallenges {
+    challenges: HashMap<Principal, ReauthenticationChallenge>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ReauthenticationChallenge {
+    challenge: [u8; 32],
+    expires: TimestampMillis,
+}
+
+impl ReauthenticationChallenges {
+    pub fn create<R: RngCore>(
+        &mut self,
+        auth_principal: Principal,
+        now: TimestampMillis,
+        mut rng: R,
+    ) -> ([u8; 32], TimestampMillis) {
+        self.challenges.retain(|_, c| c.expires > now);
+
+        let mut challenge = [0; 32];
+        rng.fill_bytes(&mut challenge);
+        let expires = now + REAUTHENTICATION_CHALLENGE_LIFETIME;
+
+        self.challenges
+            .insert(auth_principal, ReauthenticationChallenge { challenge, expires });
+
+        (challenge, expires)
+    }
+
+    pub fn take(&mut self, auth_principal: &Principal, now: TimestampMillis) -> Option<[u8; 32]> {
+        self.challenges
+            .remove(auth_principal)
+            .filter(|c| c.expires > now)
+            .map(|c| c.challenge)
+    }
+}
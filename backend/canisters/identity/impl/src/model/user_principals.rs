@@ -172,6 +172,23 @@ impl UserPrincipals {
         }
     }
 
+    // Returns the auth principal, linked to the same user as `auth_principal`, which uses the given WebAuthn credential
+    pub fn get_linked_webauthn_principal(&self, auth_principal: &Principal, credential_id: &[u8]) -> Option<Principal> {
+        let user_principal_index = self.auth_principals.get(auth_principal)?.user_principal_index;
+
+        self.user_principals
+            .get(user_principal_index as usize)?
+            .auth_principals
+            .iter()
+            .find(|p| {
+                self.auth_principals
+                    .get(p)
+                    .and_then(|a| a.webauthn_credential_id.as_ref())
+                    .is_some_and(|c| c.as_slice() == credential_id)
+            })
+            .copied()
+    }
+
     pub fn next_index(&self) -> u32 {
         self.user_principals.len().try_into().unwrap()
     }
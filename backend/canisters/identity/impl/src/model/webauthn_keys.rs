@@ -4,7 +4,7 @@ use serde::Serialize;
 use serde_bytes::ByteBuf;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry::Vacant;
-use types::TimestampMillis;
+use types::{TimestampMillis, is_default};
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct WebAuthnKeys {
@@ -13,6 +13,10 @@ pub struct WebAuthnKeys {
 
 impl WebAuthnKeys {
     pub fn add(&mut self, key: WebAuthnKey, now: TimestampMillis) {
+        self.add_named(key, None, false, now);
+    }
+
+    pub fn add_named(&mut self, key: WebAuthnKey, name: Option<String>, is_recovery: bool, now: TimestampMillis) {
         if let Vacant(e) = self.keys.entry(key.credential_id.into()) {
             e.insert(WebAuthnKeyInternal {
                 public_key: key.public_key,
@@ -20,6 +24,8 @@ impl WebAuthnKeys {
                 cross_platform: key.cross_platform,
                 aaguid: key.aaguid,
                 created: now,
+                name,
+                is_recovery,
             });
         } else {
             panic!("WebAuthn credential already exists");
@@ -29,6 +35,14 @@ impl WebAuthnKeys {
     pub fn get(&self, credential_id: Vec<u8>) -> Option<&WebAuthnKeyInternal> {
         self.keys.get(&ByteBuf::from(credential_id))
     }
+
+    pub fn get_mut(&mut self, credential_id: Vec<u8>) -> Option<&mut WebAuthnKeyInternal> {
+        self.keys.get_mut(&ByteBuf::from(credential_id))
+    }
+
+    pub fn remove(&mut self, credential_id: Vec<u8>) -> Option<WebAuthnKeyInternal> {
+        self.keys.remove(&ByteBuf::from(credential_id))
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -43,6 +57,10 @@ pub struct WebAuthnKeyInternal {
     pub aaguid: [u8; 16],
     #[serde(rename = "c")]
     pub created: TimestampMillis,
+    #[serde(rename = "n", default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "r", default, skip_serializing_if = "is_default")]
+    pub is_recovery: bool,
 }
 
 impl WebAuthnKeyInternal {
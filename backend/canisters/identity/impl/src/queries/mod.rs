@@ -3,3 +3,4 @@ mod check_auth_principal_v2;
 mod get_delegation;
 mod http_request;
 mod lookup_webauthn_pubkey;
+mod webauthn_credentials;
@@ -0,0 +1,46 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use identity_canister::webauthn_credentials::{Response::*, *};
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[query(msgpack = true, candid = true)]
+fn webauthn_credentials(_args: Args) -> Response {
+    match read_state(webauthn_credentials_impl) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn webauthn_credentials_impl(state: &RuntimeState) -> OCResult<Vec<WebAuthnCredential>> {
+    let caller = state.env.caller();
+    let auth_principal = state.data.user_principals.unwrap_temp_key_or(caller);
+
+    let user_principal = state
+        .data
+        .user_principals
+        .get_by_auth_principal(&auth_principal)
+        .ok_or(OCErrorCode::InitiatorNotFound)?;
+
+    Ok(user_principal
+        .auth_principals
+        .into_iter()
+        .filter_map(|p| {
+            let a = state.data.user_principals.get_auth_principal(&p)?;
+            let credential_id = a.webauthn_credential_id?;
+            let key = state.data.webauthn_keys.get(credential_id.clone())?;
+
+            Some(WebAuthnCredential {
+                credential_id,
+                name: key.name.clone(),
+                origin: key.origin.clone(),
+                cross_platform: key.cross_platform,
+                aaguid: key.aaguid,
+                is_recovery: key.is_recovery,
+                is_current_identity: p == auth_principal,
+                created: key.created,
+                last_used: a.last_used,
+            })
+        })
+        .collect())
+}
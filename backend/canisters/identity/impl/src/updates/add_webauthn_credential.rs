@@ -0,0 +1,87 @@
+use crate::updates::initiate_identity_link::MAX_LINKED_IDENTITIES;
+use crate::updates::update_webauthn_credential::validate_name;
+use crate::{RuntimeState, mutate_state, webauthn};
+use candid::Principal;
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use identity_canister::WEBAUTHN_ORIGINATING_CANISTER;
+use identity_canister::add_webauthn_credential::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[update(msgpack = true, candid = true)]
+#[trace]
+fn add_webauthn_credential(args: Args) -> Response {
+    mutate_state(|state| add_webauthn_credential_impl(args, state)).into()
+}
+
+fn add_webauthn_credential_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let caller = state.env.caller();
+    let auth_principal = state.data.user_principals.unwrap_temp_key_or(caller);
+
+    let (Some(auth_principal_details), Some(user_principal)) = (
+        state.data.user_principals.get_auth_principal(&auth_principal),
+        state.data.user_principals.get_by_auth_principal(&auth_principal),
+    ) else {
+        return Err(OCErrorCode::InitiatorNotFound.into());
+    };
+
+    // The same challenge must be signed by the new credential, proving possession of its key, and, if the caller is
+    // reauthenticating via a WebAuthn assertion, by one of their existing credentials
+    let now = state.env.now();
+    let challenge = state
+        .data
+        .reauthentication_challenges
+        .take(&auth_principal, now)
+        .ok_or(OCErrorCode::ReauthenticationRequired.with_message("No reauthentication challenge found"))?;
+
+    state
+        .data
+        .verify_reauthentication(auth_principal, &args.reauthentication, Some(&challenge), now)?;
+
+    webauthn::verify_assertion(
+        &args.webauthn_key.public_key,
+        &args.webauthn_key.origin,
+        &args.new_credential_assertion,
+        &challenge,
+    )
+    .map_err(|e| OCErrorCode::InvalidSignature.with_message(e))?;
+
+    let name = validate_name(args.name)?;
+
+    if user_principal.auth_principals.len() >= MAX_LINKED_IDENTITIES {
+        return Err(OCErrorCode::LinkedIdentitiesLimitReached.with_message(MAX_LINKED_IDENTITIES));
+    }
+
+    if !state.data.originating_canisters.contains(&WEBAUTHN_ORIGINATING_CANISTER) {
+        return Err(OCErrorCode::InvalidOriginatingCanister.into());
+    }
+
+    state.assert_key_not_generated_by_this_canister(&args.webauthn_key.public_key);
+
+    let new_principal = Principal::self_authenticating(&args.webauthn_key.public_key);
+    if state.data.user_principals.auth_principal_exists(&new_principal)
+        || state
+            .data
+            .webauthn_keys
+            .get(args.webauthn_key.credential_id.clone())
+            .is_some()
+    {
+        return Err(OCErrorCode::WebAuthnCredentialAlreadyExists.into());
+    }
+
+    state.data.user_principals.link_auth_principal_with_existing_user(
+        new_principal,
+        WEBAUTHN_ORIGINATING_CANISTER,
+        Some(args.webauthn_key.credential_id.clone().into()),
+        false,
+        auth_principal_details.user_principal_index,
+        now,
+    );
+    state
+        .data
+        .webauthn_keys
+        .add_named(args.webauthn_key, name, args.is_recovery, now);
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use identity_canister::generate_reauthentication_challenge::{Response::*, *};
+use oc_error_codes::OCErrorCode;
+
+#[update(msgpack = true, candid = true)]
+#[trace]
+fn generate_reauthentication_challenge(_args: Args) -> Response {
+    mutate_state(generate_reauthentication_challenge_impl)
+}
+
+fn generate_reauthentication_challenge_impl(state: &mut RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let auth_principal = state.data.user_principals.unwrap_temp_key_or(caller);
+
+    if state.data.user_principals.get_auth_principal(&auth_principal).is_none() {
+        return Error(OCErrorCode::InitiatorNotFound.into());
+    }
+
+    let now = state.env.now();
+    let (challenge, expires) = state
+        .data
+        .reauthentication_challenges
+        .create(auth_principal, now, state.env.rng());
+
+    Success(SuccessResult {
+        challenge: challenge.to_vec(),
+        expires,
+    })
+}
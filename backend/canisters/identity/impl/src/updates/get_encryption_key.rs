@@ -5,7 +5,7 @@ use ic_cdk::call::RejectCode;
 use ic_cdk::management_canister::{VetKDCurve, VetKDDeriveKeyArgs, VetKDKeyId};
 use identity_canister::get_encryption_key::{Response::*, *};
 use oc_error_codes::{OCError, OCErrorCode};
-use types::{C2CError, CanisterId, OCResult, UserId};
+use types::{C2CError, CanisterId, MultiUserChat, OCResult, UserId};
 
 #[update(msgpack = true)]
 #[trace]
@@ -15,6 +15,13 @@ async fn get_encryption_key(args: Args) -> Response {
         Err(error) => return Error(error),
     };
 
+    if let KeyType::Chat { chat, epoch } = args.key_type {
+        if let Err(error) = verify_can_access_chat_key(caller_user_id, chat, epoch).await {
+            mutate_state(|state| mark_complete(&caller_user_id, args.key_type, Some(error.clone()), state));
+            return Error(error);
+        }
+    }
+
     let ContextAndInput { context, input } = calculate_context_and_input(caller_user_id, args.key_type);
 
     let derive_key_result = ic_cdk::management_canister::vetkd_derive_key(&VetKDDeriveKeyArgs {
@@ -67,6 +74,33 @@ fn prepare(state: &mut RuntimeState) -> OCResult<UserId> {
     Ok(user_id)
 }
 
+async fn verify_can_access_chat_key(user_id: UserId, chat: MultiUserChat, epoch: u32) -> OCResult {
+    let can_access = match chat {
+        MultiUserChat::Group(chat_id) => matches!(
+            group_canister_c2c_client::c2c_can_access_encryption_key(
+                chat_id.into(),
+                &group_canister::c2c_can_access_encryption_key::Args { user_id, epoch },
+            )
+            .await?,
+            group_canister::c2c_can_access_encryption_key::Response::Success
+        ),
+        MultiUserChat::Channel(community_id, channel_id) => matches!(
+            community_canister_c2c_client::c2c_can_access_encryption_key(
+                community_id.into(),
+                &community_canister::c2c_can_access_encryption_key::Args {
+                    channel_id,
+                    user_id,
+                    epoch,
+                },
+            )
+            .await?,
+            community_canister::c2c_can_access_encryption_key::Response::Success
+        ),
+    };
+
+    if can_access { Ok(()) } else { Err(OCErrorCode::InitiatorNotInChat.into()) }
+}
+
 fn mark_complete(caller_user_id: &UserId, key_type: KeyType, error: Option<OCError>, state: &mut RuntimeState) {
     state
         .data
@@ -89,6 +123,16 @@ fn calculate_context_and_input(caller_user_id: UserId, key_type: KeyType) -> Con
             context = b"oc-user".to_vec();
             input = caller_user_id_bytes.to_vec();
         }
+        KeyType::Chat { chat, epoch } => {
+            // The key is shared by all members of the chat, so it must not depend on the caller
+            context = b"oc-chat".to_vec();
+            let mut bytes = chat.canister_id().as_slice().to_vec();
+            if let MultiUserChat::Channel(_, channel_id) = chat {
+                bytes.extend_from_slice(&channel_id.as_u32().to_be_bytes());
+            }
+            bytes.extend_from_slice(&epoch.to_be_bytes());
+            input = bytes;
+        }
     }
     ContextAndInput { context, input }
 }
@@ -3,7 +3,7 @@ use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use identity_canister::initiate_identity_link::{Response::*, *};
 
-const MAX_LINKED_IDENTITIES: usize = 10;
+pub(crate) const MAX_LINKED_IDENTITIES: usize = 10;
 
 #[update(msgpack = true, candid = true)]
 #[trace]
@@ -1,11 +1,16 @@
 pub mod accept_identity_link_via_qr_code;
+pub mod add_webauthn_credential;
 pub mod approve_identity_link;
 pub mod c2c_set_user_ids;
 pub mod create_identity;
 pub mod delete_user;
 pub mod generate_challenge;
+pub mod generate_reauthentication_challenge;
 pub mod get_encryption_key;
 pub mod initiate_identity_link;
 pub mod initiate_identity_link_via_qr_code;
 pub mod prepare_delegation;
 pub mod remove_identity_link;
+pub mod remove_identity_link_v2;
+pub mod remove_webauthn_credential;
+pub mod update_webauthn_credential;
@@ -0,0 +1,43 @@
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use identity_canister::remove_identity_link::Response as RemovePrincipalResponse;
+use identity_canister::remove_identity_link_v2::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[update(msgpack = true, candid = true)]
+#[trace]
+fn remove_identity_link_v2(args: Args) -> Response {
+    mutate_state(|state| remove_identity_link_impl(args, state)).into()
+}
+
+fn remove_identity_link_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let caller = state.env.caller();
+    let auth_principal = state.data.user_principals.unwrap_temp_key_or(caller);
+
+    state.verify_reauthentication(auth_principal, &args.reauthentication)?;
+
+    let webauthn_credential_id = state
+        .data
+        .user_principals
+        .get_auth_principal(&args.linked_principal)
+        .and_then(|a| a.webauthn_credential_id);
+
+    match state
+        .data
+        .user_principals
+        .remove_auth_principal(auth_principal, args.linked_principal)
+    {
+        RemovePrincipalResponse::Success => {
+            if let Some(credential_id) = webauthn_credential_id {
+                state.data.webauthn_keys.remove(credential_id);
+            }
+            Ok(())
+        }
+        RemovePrincipalResponse::CannotUnlinkActivePrincipal => Err(OCErrorCode::CannotRemoveSelf.into()),
+        RemovePrincipalResponse::IdentityLinkNotFound => Err(OCErrorCode::IdentityLinkNotFound.into()),
+        RemovePrincipalResponse::UserNotFound => Err(OCErrorCode::InitiatorNotFound.into()),
+        RemovePrincipalResponse::Error(error) => Err(error),
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use identity_canister::remove_identity_link::Response as RemovePrincipalResponse;
+use identity_canister::remove_webauthn_credential::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[update(msgpack = true, candid = true)]
+#[trace]
+fn remove_webauthn_credential(args: Args) -> Response {
+    mutate_state(|state| remove_webauthn_credential_impl(args, state)).into()
+}
+
+fn remove_webauthn_credential_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let caller = state.env.caller();
+    let auth_principal = state.data.user_principals.unwrap_temp_key_or(caller);
+
+    state.verify_reauthentication(auth_principal, &args.reauthentication)?;
+
+    let linked_principal = state
+        .data
+        .user_principals
+        .get_linked_webauthn_principal(&auth_principal, &args.credential_id)
+        .ok_or(OCErrorCode::WebAuthnCredentialNotFound)?;
+
+    match state
+        .data
+        .user_principals
+        .remove_auth_principal(auth_principal, linked_principal)
+    {
+        RemovePrincipalResponse::Success => {
+            state.data.webauthn_keys.remove(args.credential_id);
+            Ok(())
+        }
+        RemovePrincipalResponse::CannotUnlinkActivePrincipal => Err(OCErrorCode::CannotRemoveSelf.into()),
+        RemovePrincipalResponse::IdentityLinkNotFound => Err(OCErrorCode::WebAuthnCredentialNotFound.into()),
+        RemovePrincipalResponse::UserNotFound => Err(OCErrorCode::InitiatorNotFound.into()),
+        RemovePrincipalResponse::Error(error) => Err(error),
+    }
+}
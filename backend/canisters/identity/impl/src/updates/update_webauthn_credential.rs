@@ -0,0 +1,65 @@
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use identity_canister::update_webauthn_credential::*;
+use oc_error_codes::OCErrorCode;
+use types::{OCResult, OptionUpdate};
+
+const MAX_NAME_LENGTH: usize = 50;
+
+#[update(msgpack = true, candid = true)]
+#[trace]
+fn update_webauthn_credential(args: Args) -> Response {
+    mutate_state(|state| update_webauthn_credential_impl(args, state)).into()
+}
+
+fn update_webauthn_credential_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let caller = state.env.caller();
+    let auth_principal = state.data.user_principals.unwrap_temp_key_or(caller);
+
+    if state
+        .data
+        .user_principals
+        .get_linked_webauthn_principal(&auth_principal, &args.credential_id)
+        .is_none()
+    {
+        return Err(OCErrorCode::WebAuthnCredentialNotFound.into());
+    }
+
+    if args.is_recovery.is_some() {
+        let proof = args.reauthentication.as_ref().ok_or(OCErrorCode::ReauthenticationRequired)?;
+        state.verify_reauthentication(auth_principal, proof)?;
+    }
+
+    let name = match args.name {
+        OptionUpdate::NoChange => None,
+        OptionUpdate::SetToNone => Some(None),
+        OptionUpdate::SetToSome(name) => Some(validate_name(Some(name))?),
+    };
+
+    let key = state
+        .data
+        .webauthn_keys
+        .get_mut(args.credential_id)
+        .ok_or(OCErrorCode::WebAuthnCredentialNotFound)?;
+
+    if let Some(name) = name {
+        key.name = name;
+    }
+    if let Some(is_recovery) = args.is_recovery {
+        key.is_recovery = is_recovery;
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_name(name: Option<String>) -> OCResult<Option<String>> {
+    let Some(name) = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) else {
+        return Ok(None);
+    };
+
+    if name.chars().count() > MAX_NAME_LENGTH {
+        Err(OCErrorCode::NameTooLong.with_message(MAX_NAME_LENGTH))
+    } else {
+        Ok(Some(name))
+    }
+}
@@ -0,0 +1,262 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use identity_canister::WebAuthnAssertion;
+use p256::EncodedPoint;
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use serde::Deserialize;
+use serde_cbor::Value;
+use sha256::sha256;
+use x509_parser::prelude::{FromDer, SubjectPublicKeyInfo};
+
+const USER_PRESENT_FLAG: u8 = 0x01;
+
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+    origin: String,
+}
+
+// Verifies that the assertion is a signature over the challenge by the WebAuthn key with the given public key, which is
+// a DER wrapped COSE key
+pub fn verify_assertion(
+    public_key: &[u8],
+    origin: &str,
+    assertion: &WebAuthnAssertion,
+    challenge: &[u8],
+) -> Result<(), String> {
+    let client_data: ClientData =
+        serde_json::from_slice(&assertion.client_data_json).map_err(|e| format!("Invalid client data: {e}"))?;
+
+    if client_data.kind != "webauthn.get" {
+        return Err(format!("Unexpected client data type: {}", client_data.kind));
+    }
+    if client_data.origin != origin {
+        return Err(format!("Unexpected origin: {}", client_data.origin));
+    }
+    let signed_challenge = URL_SAFE_NO_PAD
+        .decode(client_data.challenge.trim_end_matches('='))
+        .map_err(|e| format!("Invalid challenge: {e}"))?;
+    if signed_challenge != challenge {
+        return Err("Challenge does not match".to_string());
+    }
+
+    // The authenticator data is the 32 byte RP ID hash, followed by the flags, followed by the 4 byte signature counter
+    if assertion.authenticator_data.len() < 37 {
+        return Err("Authenticator data too short".to_string());
+    }
+    if !is_rp_id_hash_valid_for_origin(&assertion.authenticator_data[..32], origin) {
+        return Err("RP ID does not match origin".to_string());
+    }
+    if assertion.authenticator_data[32] & USER_PRESENT_FLAG == 0 {
+        return Err("User not present".to_string());
+    }
+
+    let verifying_key = extract_verifying_key(public_key)?;
+    let signature = Signature::from_der(&assertion.signature).map_err(|e| format!("Invalid signature: {e}"))?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    let mut message = assertion.authenticator_data.clone();
+    message.extend_from_slice(&sha256(&assertion.client_data_json));
+
+    verifying_key
+        .verify(&message, &signature)
+        .map_err(|_| "Signature verification failed".to_string())
+}
+
+// The RP ID is either the host of the origin or one of its parent domains
+fn is_rp_id_hash_valid_for_origin(rp_id_hash: &[u8], origin: &str) -> bool {
+    let host = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+    let mut rp_id = host.split(['/', ':']).next().unwrap_or(host);
+
+    loop {
+        if sha256(rp_id.as_bytes()).as_slice() == rp_id_hash {
+            return true;
+        }
+        match rp_id.split_once('.') {
+            Some((_, parent)) if parent.contains('.') => rp_id = parent,
+            _ => return false,
+        }
+    }
+}
+
+fn extract_verifying_key(public_key: &[u8]) -> Result<VerifyingKey, String> {
+    let key_info = SubjectPublicKeyInfo::from_der(public_key).map_err(|e| format!("{e:?}"))?.1;
+    let cose_key: Value =
+        serde_cbor::from_slice(&key_info.subject_public_key.data).map_err(|e| format!("Invalid COSE key: {e}"))?;
+
+    let Value::Map(map) = cose_key else {
+        return Err("Expected COSE key to be a map".to_string());
+    };
+    let get = |label: i128| map.get(&Value::Integer(label));
+
+    // Only ES256 keys (kty: EC2, alg: ES256, crv: P-256) are supported
+    if get(1) != Some(&Value::Integer(2)) || get(3) != Some(&Value::Integer(-7)) || get(-1) != Some(&Value::Integer(1)) {
+        return Err("Unsupported COSE key type".to_string());
+    }
+    let (Some(Value::Bytes(x)), Some(Value::Bytes(y))) = (get(-2), get(-3)) else {
+        return Err("COSE key coordinates missing".to_string());
+    };
+    if x.len() != 32 || y.len() != 32 {
+        return Err("COSE key coordinates invalid".to_string());
+    }
+
+    let point = EncodedPoint::from_affine_coordinates(x.as_slice().into(), y.as_slice().into(), false);
+    VerifyingKey::from_encoded_point(&point).map_err(|e| format!("Invalid public key: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::SigningKey;
+    use p256::ecdsa::signature::Signer;
+    use std::collections::BTreeMap;
+    use test_case::test_case;
+
+    const ORIGIN: &str = "https://oc.app";
+    const CHALLENGE: [u8; 32] = [7; 32];
+
+    #[test]
+    fn valid_assertion_succeeds() {
+        let signing_key = signing_key(1);
+        let assertion = sign(&signing_key, "webauthn.get", ORIGIN, &CHALLENGE, 0x05);
+
+        assert!(verify_assertion(&der_encoded_public_key(&signing_key), ORIGIN, &assertion, &CHALLENGE).is_ok());
+    }
+
+    #[test]
+    fn assertion_from_different_key_fails() {
+        let assertion = sign(&signing_key(1), "webauthn.get", ORIGIN, &CHALLENGE, 0x05);
+
+        assert!(verify_assertion(&der_encoded_public_key(&signing_key(2)), ORIGIN, &assertion, &CHALLENGE).is_err());
+    }
+
+    #[test]
+    fn assertion_over_different_challenge_fails() {
+        let signing_key = signing_key(1);
+        let assertion = sign(&signing_key, "webauthn.get", ORIGIN, &[8; 32], 0x05);
+
+        assert!(verify_assertion(&der_encoded_public_key(&signing_key), ORIGIN, &assertion, &CHALLENGE).is_err());
+    }
+
+    #[test]
+    fn assertion_with_wrong_origin_or_type_fails() {
+        let signing_key = signing_key(1);
+        let public_key = der_encoded_public_key(&signing_key);
+
+        let wrong_origin = sign(&signing_key, "webauthn.get", "https://evil.com", &CHALLENGE, 0x05);
+        let wrong_type = sign(&signing_key, "webauthn.create", ORIGIN, &CHALLENGE, 0x05);
+
+        assert!(verify_assertion(&public_key, ORIGIN, &wrong_origin, &CHALLENGE).is_err());
+        assert!(verify_assertion(&public_key, ORIGIN, &wrong_type, &CHALLENGE).is_err());
+    }
+
+    #[test]
+    fn assertion_without_user_present_fails() {
+        let signing_key = signing_key(1);
+        let assertion = sign(&signing_key, "webauthn.get", ORIGIN, &CHALLENGE, 0x04);
+
+        assert!(verify_assertion(&der_encoded_public_key(&signing_key), ORIGIN, &assertion, &CHALLENGE).is_err());
+    }
+
+    #[test]
+    fn assertion_for_parent_domain_rp_id_succeeds() {
+        let signing_key = signing_key(1);
+        let origin = "https://test.oc.app";
+        let assertion = sign_with_rp_id(&signing_key, "oc.app", "webauthn.get", origin, &CHALLENGE, 0x05);
+
+        assert!(verify_assertion(&der_encoded_public_key(&signing_key), origin, &assertion, &CHALLENGE).is_ok());
+    }
+
+    #[test_case("evil.com")]
+    #[test_case("app")]
+    #[test_case("test.oc.app")]
+    fn assertion_with_wrong_rp_id_fails(rp_id: &str) {
+        let signing_key = signing_key(1);
+        let assertion = sign_with_rp_id(&signing_key, rp_id, "webauthn.get", ORIGIN, &CHALLENGE, 0x05);
+
+        assert!(verify_assertion(&der_encoded_public_key(&signing_key), ORIGIN, &assertion, &CHALLENGE).is_err());
+    }
+
+    #[test]
+    fn tampered_authenticator_data_fails() {
+        let signing_key = signing_key(1);
+        let mut assertion = sign(&signing_key, "webauthn.get", ORIGIN, &CHALLENGE, 0x05);
+        assertion.authenticator_data[36] ^= 1;
+
+        assert!(verify_assertion(&der_encoded_public_key(&signing_key), ORIGIN, &assertion, &CHALLENGE).is_err());
+    }
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32].into()).unwrap()
+    }
+
+    fn sign(signing_key: &SigningKey, kind: &str, origin: &str, challenge: &[u8], flags: u8) -> WebAuthnAssertion {
+        sign_with_rp_id(signing_key, "oc.app", kind, origin, challenge, flags)
+    }
+
+    fn sign_with_rp_id(
+        signing_key: &SigningKey,
+        rp_id: &str,
+        kind: &str,
+        origin: &str,
+        challenge: &[u8],
+        flags: u8,
+    ) -> WebAuthnAssertion {
+        let client_data_json = format!(
+            r#"{{"type":"{kind}","challenge":"{}","origin":"{origin}"}}"#,
+            URL_SAFE_NO_PAD.encode(challenge)
+        )
+        .into_bytes();
+
+        let mut authenticator_data = sha256(rp_id.as_bytes()).to_vec();
+        authenticator_data.push(flags);
+        authenticator_data.extend_from_slice(&[0, 0, 0, 1]);
+
+        let mut message = authenticator_data.clone();
+        message.extend_from_slice(&sha256(&client_data_json));
+        let signature: Signature = signing_key.sign(&message);
+
+        WebAuthnAssertion {
+            credential_id: vec![1, 2, 3],
+            authenticator_data,
+            client_data_json,
+            signature: signature.to_der().as_bytes().to_vec(),
+        }
+    }
+
+    fn der_encoded_public_key(signing_key: &SigningKey) -> Vec<u8> {
+        let point = signing_key.verifying_key().to_encoded_point(false);
+        let cose_key = Value::Map(BTreeMap::from([
+            (Value::Integer(1), Value::Integer(2)),
+            (Value::Integer(3), Value::Integer(-7)),
+            (Value::Integer(-1), Value::Integer(1)),
+            (Value::Integer(-2), Value::Bytes(point.x().unwrap().to_vec())),
+            (Value::Integer(-3), Value::Bytes(point.y().unwrap().to_vec())),
+        ]));
+        let cose_bytes = serde_cbor::to_vec(&cose_key).unwrap();
+
+        // SEQUENCE { SEQUENCE { OID 1.3.6.1.4.1.56387.1.1 }, BIT STRING { cose_key } }
+        let oid = [0x06, 0x0a, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0xb8, 0x43, 0x01, 0x01];
+        let algorithm = der(0x30, &oid);
+        let mut bit_string_contents = vec![0];
+        bit_string_contents.extend_from_slice(&cose_bytes);
+        let bit_string = der(0x03, &bit_string_contents);
+
+        der(0x30, &[algorithm, bit_string].concat())
+    }
+
+    fn der(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![tag];
+        if contents.len() < 128 {
+            bytes.push(contents.len() as u8);
+        } else {
+            bytes.push(0x81);
+            bytes.push(contents.len() as u8);
+        }
+        bytes.extend_from_slice(contents);
+        bytes
+    }
+}
@@ -3,11 +3,12 @@ use event_store_types::Event;
 use serde::{Deserialize, Serialize};
 use types::nns::CryptoAmount;
 use types::{
-    AutonomousConfig, BotCommandDefinition, BotDataEncoding, BotDefinition, BotInstallationLocation, BotSubscriptions,
-    BuildVersion, CanisterId, ChannelLatestMessageIndex, ChatId, ChitEarnedReason, CommunityId, CyclesTopUp,
+    AutonomousConfig, BotCommandDefinition, BotDataEncoding, BotDefinition, BotInstallationLocation, BotPermissions,
+    BotSubscriptions, BuildVersion, CanisterId, ChannelLatestMessageIndex, ChatId, ChitEarnedReason, CommunityId, CyclesTopUp,
     DiamondMembershipPlanDuration, MessageContent, MessageContentInitial, MessageId, MessageIndex, Notification, NotifyChit,
     PhoneNumber, ReferralType, SuspensionDuration, TimestampMillis, UniquePersonProof, UpdateUserPrincipalArgs, User,
-    UserCanisterStreakInsuranceClaim, UserCanisterStreakInsurancePayment, UserId, UserType, is_default,
+    UserCanisterStreakInsuranceClaim, UserCanisterStreakInsurancePayment, UserId, UserNotificationPreferences, UserType,
+    is_default,
 };
 
 mod lifecycle;
@@ -50,6 +51,9 @@ pub enum UserIndexEvent {
     SyncExistingUser(UserDetailsFull),
     UserBlocked(UserId, UserId),
     UserUnblocked(UserId, UserId),
+    RegisteredLedgersUpdated(Vec<CanisterId>),
+    BotPinnedVersionsUpdated(BotPinnedVersionsUpdated),
+    NotificationPreferencesUpdated(UserId, Box<UserNotificationPreferences>),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -66,6 +70,7 @@ pub enum GroupEvent {
     MarkActivity(TimestampMillis),
     EventStoreEvent(Event),
     Notification(Box<Notification>),
+    BotPermissionsUpdated(UserId, BotPermissions),
 }
 
 pub type CommunityEvent = GroupEvent;
@@ -147,6 +152,21 @@ pub struct BotUpdated {
     pub definition: BotDefinition,
 }
 
+// The previous versions of a bot's definition which installations remain pinned to until the installer consents to the
+// permissions requested by the latest version. Replaces any previously pinned versions for the bot.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BotPinnedVersionsUpdated {
+    pub bot_id: UserId,
+    pub pinned_versions: Vec<PinnedBotVersion>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PinnedBotVersion {
+    pub version: u32,
+    pub commands: Vec<BotCommandDefinition>,
+    pub installations: Vec<BotInstallationLocation>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct BotRemoved {
     pub user_id: UserId,
@@ -310,6 +330,7 @@ pub enum UserEvent {
     SetMaxStreak(u16),
     EventStoreEvent(Event),
     Notification(Box<Notification>),
+    NotificationPreferencesUpdated(Box<UserNotificationPreferences>),
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -9,23 +9,38 @@ fn main() {
 
     generate_ts_method!(local_user_index, active_proposal_tallies);
     generate_ts_method!(local_user_index, access_token_v2);
+    generate_ts_method!(local_user_index, bot_storage_entries);
     generate_ts_method!(local_user_index, chat_events);
     generate_ts_method!(local_user_index, group_and_community_summary_updates);
     generate_ts_method!(local_user_index, group_and_community_summary_updates_v2);
 
-    generate_ts_method!(local_user_index, bot_add_reaction);
-    generate_ts_method!(local_user_index, bot_remove_user);
+    generate_ts_method!(local_user_index, bot_add_reaction, msgpack, json);
+    generate_ts_method!(local_user_index, bot_block_user, msgpack, json);
+    generate_ts_method!(local_user_index, bot_change_role, msgpack, json);
+    generate_ts_method!(local_user_index, bot_edit_message, msgpack, json);
+    generate_ts_method!(local_user_index, bot_follow_thread, msgpack, json);
+    generate_ts_method!(local_user_index, bot_pin_message, msgpack, json);
+    generate_ts_method!(local_user_index, bot_remove_user, msgpack, json);
+    generate_ts_method!(local_user_index, bot_report_command_outcome, msgpack, json);
     generate_ts_method!(local_user_index, bot_chat_events);
     generate_ts_method!(local_user_index, bot_chat_summary);
     generate_ts_method!(local_user_index, bot_community_events);
     generate_ts_method!(local_user_index, bot_community_summary);
-    generate_ts_method!(local_user_index, bot_create_channel);
-    generate_ts_method!(local_user_index, bot_delete_channel);
-    generate_ts_method!(local_user_index, bot_delete_messages);
-    generate_ts_method!(local_user_index, bot_invite_users);
+    generate_ts_method!(local_user_index, bot_create_channel, msgpack, json);
+    generate_ts_method!(local_user_index, bot_delete_channel, msgpack, json);
+    generate_ts_method!(local_user_index, bot_delete_messages, msgpack, json);
+    generate_ts_method!(local_user_index, bot_invite_users, msgpack, json);
     generate_ts_method!(local_user_index, bot_members);
-    generate_ts_method!(local_user_index, bot_send_message);
-    generate_ts_method!(local_user_index, bot_subscribe_to_events);
+    generate_ts_method!(local_user_index, bot_schedule_task, msgpack, json);
+    generate_ts_method!(local_user_index, bot_send_crypto, msgpack, json);
+    generate_ts_method!(local_user_index, bot_send_message, msgpack, json);
+    generate_ts_method!(local_user_index, bot_send_prize, msgpack, json);
+    generate_ts_method!(local_user_index, bot_subscribe_to_events, msgpack, json);
+    generate_ts_method!(local_user_index, bot_tip_message, msgpack, json);
+    generate_ts_method!(local_user_index, bot_unfollow_thread, msgpack, json);
+    generate_ts_method!(local_user_index, bot_unpin_message, msgpack, json);
+    generate_ts_method!(local_user_index, bot_update_chat_details, msgpack, json);
+    generate_ts_method!(local_user_index, bot_update_storage, msgpack, json);
     generate_ts_method!(local_user_index, install_bot);
     generate_ts_method!(local_user_index, invite_users_to_channel);
     generate_ts_method!(local_user_index, invite_users_to_community);
@@ -33,6 +48,7 @@ fn main() {
     generate_ts_method!(local_user_index, join_channel);
     generate_ts_method!(local_user_index, join_community);
     generate_ts_method!(local_user_index, join_group);
+    generate_ts_method!(local_user_index, record_bot_command_invocation);
     generate_ts_method!(local_user_index, register_user);
     generate_ts_method!(local_user_index, uninstall_bot);
     generate_ts_method!(local_user_index, withdraw_from_icpswap);
@@ -2,7 +2,9 @@ use candid::CandidType;
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{BotActionScope, BotCommandArg, BotCommandMeta, Chat, UserId, VideoCallType};
+use types::{
+    BotActionScope, BotCommandArg, BotCommandMeta, BotComponentValue, Chat, MessageId, MessageIndex, UserId, VideoCallType,
+};
 
 #[ts_export(local_user_index, access_token_v2)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -11,6 +13,8 @@ pub enum Args {
     JoinVideoCall(JoinVideoCallArgs),
     MarkVideoCallAsEnded(MarkVideoCallAsEndedArgs),
     BotActionByCommand(BotActionByCommandArgs),
+    BotActionByComponent(BotActionByComponentArgs),
+    BotAutocomplete(BotAutocompleteArgs),
 }
 
 #[ts_export(local_user_index, access_token_v2)]
@@ -49,6 +53,16 @@ pub struct BotActionByCommandArgs {
     pub scope: BotActionScope,
 }
 
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotAutocompleteArgs {
+    pub bot_id: UserId,
+    pub command: BotCommandInitial,
+    pub param: String,
+    pub partial_input: String,
+    pub scope: BotActionScope,
+}
+
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct BotCommandInitial {
@@ -56,3 +70,15 @@ pub struct BotCommandInitial {
     pub args: Vec<BotCommandArg>,
     pub meta: Option<BotCommandMeta>,
 }
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotActionByComponentArgs {
+    pub bot_id: UserId,
+    pub chat: Chat,
+    pub thread: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub component_id: String,
+    pub value: BotComponentValue,
+    pub meta: Option<BotCommandMeta>,
+}
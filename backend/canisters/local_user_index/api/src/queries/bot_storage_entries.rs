@@ -0,0 +1,20 @@
+use candid::CandidType;
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotStorageEntry, CommunityOrGroup};
+
+#[ts_export(local_user_index, bot_storage_entries)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub location: CommunityOrGroup,
+    // If empty, all of the bot's entries are returned
+    pub keys: Vec<String>,
+}
+
+#[ts_export(local_user_index, bot_storage_entries)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(Vec<BotStorageEntry>),
+    Error(OCError),
+}
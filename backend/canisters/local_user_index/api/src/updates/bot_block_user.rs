@@ -0,0 +1,13 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotCommunityOrGroupContext, UnitResult, UserId};
+
+#[ts_export(local_user_index, bot_block_user)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub community_or_group_context: BotCommunityOrGroupContext,
+    pub user_id: UserId,
+}
+
+pub type Response = UnitResult;
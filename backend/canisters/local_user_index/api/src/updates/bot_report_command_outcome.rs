@@ -0,0 +1,22 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotChatContext, UnitResult};
+
+#[ts_export(local_user_index, bot_report_command_outcome)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub chat_context: BotChatContext,
+    pub outcome: BotCommandOutcome,
+}
+
+#[ts_export(local_user_index, bot_report_command_outcome)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum BotCommandOutcome {
+    // The bot has received the command and is processing it
+    Received,
+    Succeeded,
+    Failed,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,15 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AutonomousBotScope, Milliseconds, UnitResult};
+
+#[ts_export(local_user_index, bot_schedule_task)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub scope: AutonomousBotScope,
+    pub name: String,
+    // If not set, the task is cancelled
+    pub interval: Option<Milliseconds>,
+}
+
+pub type Response = UnitResult;
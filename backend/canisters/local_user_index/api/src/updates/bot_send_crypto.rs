@@ -0,0 +1,21 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotChatContext, CanisterId, MessageId, MessageIndex, UserId};
+
+#[ts_export(local_user_index, bot_send_crypto)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub chat_context: BotChatContext,
+    pub thread: Option<MessageIndex>,
+    pub message_id: Option<MessageId>,
+    pub recipient: UserId,
+    pub ledger: CanisterId,
+    pub token_symbol: String,
+    pub amount: u128,
+    pub fee: u128,
+    pub caption: Option<String>,
+    pub block_level_markdown: bool,
+}
+
+pub type Response = crate::bot_send_message::Response;
@@ -2,7 +2,7 @@ use candid::CandidType;
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{BotChatContext, BotMessageContent, EventIndex, MessageId, MessageIndex, TimestampMillis};
+use types::{BotChatContext, BotMessageComponent, BotMessageContent, EventIndex, MessageId, MessageIndex, TimestampMillis};
 
 #[ts_export(local_user_index, bot_send_message)]
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -14,6 +14,7 @@ pub struct Args {
     pub content: BotMessageContent,
     pub block_level_markdown: bool,
     pub finalised: bool,
+    pub components: Option<Vec<BotMessageComponent>>,
 }
 
 #[ts_export(local_user_index, bot_send_message)]
@@ -0,0 +1,24 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotChatContext, CanisterId, MessageId, TimestampMillis};
+
+#[ts_export(local_user_index, bot_send_prize)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub chat_context: BotChatContext,
+    pub message_id: Option<MessageId>,
+    pub ledger: CanisterId,
+    pub token_symbol: String,
+    pub prizes: Vec<u128>,
+    pub fee: u128,
+    pub end_date: TimestampMillis,
+    pub caption: Option<String>,
+    pub diamond_only: bool,
+    pub lifetime_diamond_only: bool,
+    pub unique_person_only: bool,
+    pub streak_only: u16,
+    pub requires_captcha: bool,
+}
+
+pub type Response = crate::bot_send_message::Response;
@@ -0,0 +1,13 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotChatContext, MessageIndex, UnitResult};
+
+#[ts_export(local_user_index, bot_unfollow_thread)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub chat_context: BotChatContext,
+    pub thread_root_message_index: Option<MessageIndex>,
+}
+
+pub type Response = UnitResult;
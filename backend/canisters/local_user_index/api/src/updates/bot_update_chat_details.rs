@@ -0,0 +1,17 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotChatContext, Document, OptionUpdate, UnitResult, UpdatedRules};
+
+#[ts_export(local_user_index, bot_update_chat_details)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub chat_context: BotChatContext,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub rules: Option<UpdatedRules>,
+    #[ts(as = "types::OptionUpdateDocument")]
+    pub avatar: OptionUpdate<Document>,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,14 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotStorageEntry, CommunityOrGroup, UnitResult};
+
+#[ts_export(local_user_index, bot_update_storage)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub location: CommunityOrGroup,
+    pub set: Vec<BotStorageEntry>,
+    pub remove: Vec<String>,
+}
+
+pub type Response = UnitResult;
@@ -1,11 +1,25 @@
 pub mod bot_add_reaction;
+pub mod bot_block_user;
+pub mod bot_change_role;
 pub mod bot_create_channel;
 pub mod bot_delete_channel;
 pub mod bot_delete_messages;
+pub mod bot_edit_message;
+pub mod bot_follow_thread;
 pub mod bot_invite_users;
+pub mod bot_pin_message;
 pub mod bot_remove_user;
+pub mod bot_report_command_outcome;
+pub mod bot_schedule_task;
+pub mod bot_send_crypto;
 pub mod bot_send_message;
+pub mod bot_send_prize;
 pub mod bot_subscribe_to_events;
+pub mod bot_tip_message;
+pub mod bot_unfollow_thread;
+pub mod bot_unpin_message;
+pub mod bot_update_chat_details;
+pub mod bot_update_storage;
 pub mod c2c_community_canister;
 pub mod c2c_create_community;
 pub mod c2c_create_group;
@@ -37,6 +51,7 @@ pub mod join_channel;
 pub mod join_community;
 pub mod join_group;
 pub mod push_events;
+pub mod record_bot_command_invocation;
 pub mod register_user;
 pub mod remove_notifications;
 pub mod uninstall_bot;
@@ -0,0 +1,14 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::UnitResult;
+
+// Called by the user's client as soon as it receives a command access token from `access_token_v2`, so that the
+// invocation is tracked even if the bot never acts on it
+#[ts_export(local_user_index, record_bot_command_invocation)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub access_token: String,
+}
+
+pub type Response = UnitResult;
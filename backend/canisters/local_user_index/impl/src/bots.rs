@@ -1,16 +1,152 @@
 use crate::RuntimeState;
+use crate::model::bot_command_invocations::CompletedBotCommandInvocation;
+use constants::MINUTE_IN_MS;
 use jwt::Claims;
 use rand::Rng;
 use types::{
-    BotActionByCommandClaims, BotActionChatDetails, BotActionCommunityDetails, BotActionScope, BotChatContext,
-    BotCommunityOrGroupContext, BotInitiator, Chat, CommunityOrGroup, MessageId, User, UserId,
+    BotActionByCommandClaims, BotActionByComponentClaims, BotActionChatDetails, BotActionCommunityDetails, BotActionScope,
+    BotChatContext, BotCommunityOrGroupContext, BotInitiator, Chat, CommunityOrGroup, MessageId, Milliseconds, TimestampMillis,
+    User, UserId,
 };
+use user_index_canister::{BotCommandInvoked, LocalUserIndexEvent as UserIndexEvent};
+
+pub const ACCESS_TOKEN_VALIDITY: Milliseconds = 5 * MINUTE_IN_MS;
 
 pub struct BotAccessContext {
     pub bot_id: UserId,
     pub bot_name: String,
     pub initiator: BotInitiator,
     pub scope: BotActionScope,
+    pub token_expiry: Option<TimestampMillis>,
+}
+
+impl BotAccessContext {
+    pub fn command_invocation(&self) -> Option<BotCommandInvocation> {
+        let (BotInitiator::Command(command), BotActionScope::Chat(details), Some(token_expiry)) =
+            (&self.initiator, &self.scope, self.token_expiry)
+        else {
+            return None;
+        };
+
+        Some(BotCommandInvocation {
+            bot_id: self.bot_id,
+            command: command.name.clone(),
+            message_id: details.message_id,
+            token_expiry,
+        })
+    }
+}
+
+pub struct BotCommandInvocation {
+    bot_id: UserId,
+    command: String,
+    message_id: MessageId,
+    token_expiry: TimestampMillis,
+}
+
+impl BotCommandInvocation {
+    fn issued_at(&self) -> TimestampMillis {
+        self.token_expiry.saturating_sub(ACCESS_TOKEN_VALIDITY)
+    }
+}
+
+// Starts tracking the command invocation so that, if the bot doesn't complete it before the access token expires, it is
+// recorded as having failed
+pub fn record_bot_command_received(invocation: BotCommandInvocation, state: &mut RuntimeState) {
+    let issued_at = invocation.issued_at();
+
+    state.data.bot_command_invocations.record_received(
+        invocation.bot_id,
+        invocation.message_id,
+        invocation.command,
+        issued_at,
+        invocation.token_expiry,
+    );
+}
+
+// Records the outcome of the command invocation, along with the time taken from the access token being issued to the
+// bot completing it. Only the first outcome for each invocation is recorded.
+pub fn record_bot_command_completed(invocation: BotCommandInvocation, success: bool, state: &mut RuntimeState) {
+    let now = state.env.now();
+    let bot_id = invocation.bot_id;
+    let message_id = invocation.message_id;
+
+    record_bot_command_received(invocation, state);
+
+    if let Some(completed) = state
+        .data
+        .bot_command_invocations
+        .record_completed(bot_id, message_id, success, now)
+    {
+        push_bot_command_invoked(completed, now, state);
+    }
+}
+
+// Attributes the outcome of a bot action to the command invocation it was made for, if any. A failed action doesn't
+// complete the invocation since the bot may retry, but if it never succeeds then the invocation will be recorded as
+// having failed once the access token expires.
+pub fn record_bot_action(invocation: Option<BotCommandInvocation>, success: bool, state: &mut RuntimeState) {
+    let Some(invocation) = invocation else {
+        return;
+    };
+
+    if success {
+        record_bot_command_completed(invocation, true, state);
+    } else {
+        record_bot_command_received(invocation, state);
+    }
+}
+
+// Records the command invocations which the bots didn't complete before their access tokens expired as having failed
+pub fn record_timed_out_bot_commands(state: &mut RuntimeState) {
+    let now = state.env.now();
+
+    for timed_out in state.data.bot_command_invocations.take_expired(now) {
+        push_bot_command_invoked(timed_out, now, state);
+    }
+}
+
+fn push_bot_command_invoked(invocation: CompletedBotCommandInvocation, now: TimestampMillis, state: &mut RuntimeState) {
+    state.push_event_to_user_index(
+        UserIndexEvent::BotCommandInvoked(Box::new(BotCommandInvoked {
+            bot_id: invocation.bot_id,
+            command: invocation.command,
+            success: invocation.success,
+            latency: invocation.latency,
+            timestamp: now,
+        })),
+        now,
+    );
+}
+
+// Verifies a command access token issued by this canister, returning the user who initiated the command along with the
+// invocation it represents
+pub fn command_invocation_from_access_token(jwt: &str, state: &RuntimeState) -> Result<(UserId, BotCommandInvocation), String> {
+    const INVALID_MESSAGE: &str = "Not a valid command access token JWT";
+
+    let claims_str = jwt::verify(jwt, state.data.oc_key_pair.public_key_pem()).map_err(|_| INVALID_MESSAGE.to_string())?;
+    let claims =
+        jwt::decode_from_json::<Claims<BotActionByCommandClaims>>(&claims_str).map_err(|_| INVALID_MESSAGE.to_string())?;
+
+    let token_expiry = claims.exp_ms();
+    if token_expiry < state.env.now() {
+        return Err("Access token expired".to_string());
+    }
+
+    let claims = claims.into_custom();
+    let BotActionScope::Chat(details) = claims.scope else {
+        return Err("Only commands invoked in chats are tracked".to_string());
+    };
+
+    Ok((
+        claims.command.initiator,
+        BotCommandInvocation {
+            bot_id: claims.bot,
+            command: claims.command.name,
+            message_id: details.message_id,
+            token_expiry,
+        },
+    ))
 }
 
 pub fn extract_access_context_from_chat_context(
@@ -40,6 +176,7 @@ pub fn extract_access_context_from_chat_context(
                 message_id: state.env.rng().r#gen::<u64>().into(),
                 user_message_id: None,
             }),
+            token_expiry: None,
         }),
     }
 }
@@ -85,6 +222,7 @@ pub fn extract_access_context_from_community_or_group_context(
         bot_name: user.username,
         initiator: BotInitiator::Autonomous,
         scope,
+        token_expiry: None,
     })
 }
 
@@ -103,6 +241,15 @@ fn extract_access_context_from_jwt(jwt: &str, bot: &User, state: &mut RuntimeSta
                 BotInitiator::Command(bot_action_claims.command),
                 bot_action_claims.bot,
             )
+        } else if let Ok(claims) = jwt::decode_from_json::<Claims<BotActionByComponentClaims>>(&claims_str) {
+            let exp = claims.exp_ms();
+            let bot_action_claims = claims.into_custom();
+            (
+                exp,
+                bot_action_claims.scope,
+                BotInitiator::Component(bot_action_claims.interaction),
+                bot_action_claims.bot,
+            )
         } else {
             return Err(INVALID_MESSAGE.to_string());
         };
@@ -120,5 +267,6 @@ fn extract_access_context_from_jwt(jwt: &str, bot: &User, state: &mut RuntimeSta
         bot_name: bot.username.clone(),
         initiator,
         scope,
+        token_expiry: Some(exp_ms),
     })
 }
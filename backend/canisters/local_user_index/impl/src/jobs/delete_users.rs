@@ -44,6 +44,7 @@ async fn process_user(user: UserToDelete) {
             Ok(DeleteUserSuccess::Deleted(canisters_to_notify)) => {
                 state.data.global_users.remove(&user_id);
                 state.data.local_users.remove(&user_id);
+                state.data.notification_digests.remove_user(&user_id);
 
                 let now = state.env.now();
                 if !user.triggered_by_user {
@@ -0,0 +1,15 @@
+use crate::{bots::record_timed_out_bot_commands, mutate_state};
+use constants::MINUTE_IN_MS;
+use std::time::Duration;
+use types::Milliseconds;
+use utils::canister_timers::run_now_then_interval;
+
+const INTERVAL: Milliseconds = MINUTE_IN_MS;
+
+pub fn start_job() {
+    run_now_then_interval(Duration::from_millis(INTERVAL), run);
+}
+
+fn run() {
+    mutate_state(record_timed_out_bot_commands);
+}
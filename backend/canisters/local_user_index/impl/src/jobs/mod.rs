@@ -3,6 +3,8 @@ use ic_cdk::management_canister::ClearChunkStoreArgs;
 use tracing::info;
 
 pub mod delete_users;
+pub mod expire_bot_command_invocations;
+pub mod push_notification_digests;
 pub mod topup_canister_pool;
 pub mod topup_canisters;
 pub mod upgrade_communities;
@@ -11,6 +13,8 @@ pub mod upgrade_users;
 
 pub(crate) fn start(state: &RuntimeState) {
     delete_users::start_job_if_required(state, None);
+    expire_bot_command_invocations::start_job();
+    push_notification_digests::start_job();
     topup_canister_pool::start_job_if_required(state, None);
     topup_canisters::start_job();
     upgrade_communities::start_job_if_required(state);
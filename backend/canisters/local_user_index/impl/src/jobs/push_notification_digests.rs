@@ -0,0 +1,18 @@
+use crate::mutate_state;
+use constants::MINUTE_IN_MS;
+use std::time::Duration;
+use types::Milliseconds;
+use utils::canister_timers::run_now_then_interval;
+
+const INTERVAL: Milliseconds = 5 * MINUTE_IN_MS;
+
+pub fn start_job() {
+    run_now_then_interval(Duration::from_millis(INTERVAL), run);
+}
+
+fn run() {
+    mutate_state(|state| {
+        let now = state.env.now();
+        state.data.push_due_notification_digests(now);
+    });
+}
@@ -1,7 +1,10 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
+use crate::model::bot_command_invocations::BotCommandInvocations;
 use crate::model::community_event_batch::CommunityEventBatch;
 use crate::model::group_event_batch::GroupEventBatch;
 use crate::model::local_community_map::LocalCommunityMap;
 use crate::model::local_group_map::LocalGroupMap;
+use crate::model::notification_digests::NotificationDigests;
 use crate::model::referral_codes::{ReferralCodes, ReferralTypeMetrics};
 use crate::model::user_event_batch::UserEventBatch;
 use crate::model::user_index_event_batch::UserIndexEventBatch;
@@ -15,11 +18,14 @@ use event_store_producer_cdk_runtime::CdkRuntime;
 use event_store_utils::EventDeduper;
 use fire_and_forget_handler::FireAndForgetHandler;
 use group_canister::LocalIndexEvent as GroupEvent;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use jwt::{Claims, verify_and_decode};
 use local_user_index_canister::{ChildCanisterType, GlobalUser};
 use model::bots_map::BotsMap;
 use model::global_user_map::GlobalUserMap;
 use model::local_user_map::LocalUserMap;
+use msgpack::serialize_then_unwrap;
 use p256_key_pair::P256KeyPair;
 use proof_of_unique_personhood::verify_proof_of_unique_personhood;
 use rand::RngCore;
@@ -29,13 +35,13 @@ use stable_memory_map::UserIdsKeyPrefix;
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::time::Duration;
-use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue};
+use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue, TimerJobQueueMetrics};
 use types::{
     BotDataEncoding, BotEventWrapper, BotNotification, BotNotificationEnvelope, BuildVersion, CanisterId,
     ChannelLatestMessageIndex, ChatId, ChildCanisterWasms, CommunityCanisterChannelSummary, CommunityCanisterCommunitySummary,
-    CommunityId, Cycles, DiamondMembershipDetails, IdempotentEnvelope, MessageContent, Milliseconds, Notification,
-    NotificationEnvelope, ReferralType, TimestampMillis, Timestamped, User, UserId, UserNotificationEnvelope,
-    VerifiedCredentialGateArgs,
+    CommunityId, Cycles, DiamondMembershipDetails, DigestNotification, FcmData, IdempotentEnvelope, MessageContent,
+    Milliseconds, Notification, NotificationEnvelope, ReferralType, TimestampMillis, Timestamped, User, UserId,
+    UserNotificationEnvelope, UserNotificationPayload, VerifiedCredentialGateArgs,
 };
 use user_canister::LocalUserIndexEvent as UserEvent;
 use user_ids_set::UserIdsSet;
@@ -413,6 +419,22 @@ impl RuntimeState {
                 })
                 .collect(),
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
+            timer_job_queues: BTreeMap::from([
+                (
+                    "user_index_event_sync_queue".to_string(),
+                    self.data.user_index_event_sync_queue.metrics(),
+                ),
+                ("user_event_sync_queue".to_string(), self.data.user_event_sync_queue.metrics()),
+                (
+                    "group_event_sync_queue".to_string(),
+                    self.data.group_event_sync_queue.metrics(),
+                ),
+                (
+                    "community_event_sync_queue".to_string(),
+                    self.data.community_event_sync_queue.metrics(),
+                ),
+            ]),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 group_index: self.data.group_index_canister_id,
@@ -482,6 +504,19 @@ struct Data {
     pub notifications: EventStream<NotificationEnvelope>,
     pub blocked_users: UserIdsSet,
     pub fcm_token_store: FcmTokenStore,
+    #[serde(default)]
+    pub bot_command_invocations: BotCommandInvocations,
+    #[serde(default)]
+    pub notification_digests: NotificationDigests,
+    // Ledgers of the tokens in the registry, synced from the UserIndex
+    #[serde(default)]
+    pub registered_ledgers: HashSet<CanisterId>,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 #[derive(Serialize, Deserialize)]
@@ -572,6 +607,10 @@ impl Data {
             notifications: EventStream::default(),
             blocked_users: UserIdsSet::new(UserIdsKeyPrefix::new_for_blocked_users()),
             fcm_token_store: FcmTokenStore::default(),
+            bot_command_invocations: BotCommandInvocations::default(),
+            notification_digests: NotificationDigests::default(),
+            registered_ledgers: HashSet::new(),
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 
@@ -583,7 +622,7 @@ impl Data {
                     .map(|s| self.blocked_users.all_linked_users(s))
                     .unwrap_or_default();
 
-                let filtered_recipients: Vec<_> = user_notification
+                let mut filtered_recipients: Vec<_> = user_notification
                     .recipients
                     .into_iter()
                     .filter(|u| {
@@ -592,16 +631,65 @@ impl Data {
                     })
                     .collect();
 
+                // Hold back notifications for users in their quiet hours or who receive this chat as a digest
+                let chat = user_notification.fcm_data.as_ref().map(|d| d.chat_id);
+                filtered_recipients.retain(|u| !self.notification_digests.try_defer(*u, chat, now));
+
                 if !filtered_recipients.is_empty() {
                     self.notifications.add(NotificationEnvelope::User(UserNotificationEnvelope {
                         recipients: filtered_recipients,
                         notification_bytes: user_notification.notification_bytes.clone(),
                         timestamp: now,
                         fcm_data: user_notification.fcm_data,
+                        low_priority: false,
                     }));
                 }
             }
             Notification::Bot(bot_notification) => self.push_bot_notification(bot_notification, this_canister_id, now),
+            Notification::Outbound(outbound_notification) => {
+                // Quiet hours and digests apply to webhooks and emails too, so these are queued and delivered once the
+                // user's digest is due
+                if let Some(notification) = self.notification_digests.try_defer_outbound(outbound_notification, now) {
+                    self.notifications.add(NotificationEnvelope::Outbound(notification));
+                }
+            }
+            Notification::EmailConfirmation(email_confirmation) => {
+                self.notifications
+                    .add(NotificationEnvelope::EmailConfirmation(email_confirmation));
+            }
+        }
+    }
+
+    pub fn push_due_notification_digests(&mut self, now: TimestampMillis) {
+        for digest in self.notification_digests.take_due(now) {
+            for notification in digest.outbound {
+                self.notifications.add(NotificationEnvelope::Outbound(notification));
+            }
+
+            let Some(latest) = digest.chats.iter().max_by_key(|c| c.latest) else {
+                continue;
+            };
+
+            let notification_count: u32 = digest.chats.iter().map(|c| c.notification_count).sum();
+            let fcm_data = FcmData::for_chat(latest.chat).set_body(format!(
+                "{notification_count} new notification{} in {} chat{}",
+                if notification_count == 1 { "" } else { "s" },
+                digest.chats.len(),
+                if digest.chats.len() == 1 { "" } else { "s" },
+            ));
+
+            let notification = UserNotificationPayload::Digest(DigestNotification {
+                chats: digest.chats,
+                since: digest.since,
+            });
+
+            self.notifications.add(NotificationEnvelope::User(UserNotificationEnvelope {
+                recipients: vec![digest.user_id],
+                notification_bytes: ByteBuf::from(serialize_then_unwrap(notification)),
+                timestamp: now,
+                fcm_data: Some(fcm_data),
+                low_priority: true,
+            }));
         }
     }
 
@@ -646,6 +734,15 @@ impl Data {
             notification_bytes,
         }));
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -707,9 +804,18 @@ pub struct Metrics {
     pub cycles_balance_check_queue_len: u32,
     pub bots: Vec<BotMetrics>,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
+    pub timer_job_queues: BTreeMap<String, TimerJobQueueMetrics>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct CanisterIds {
     pub user_index: CanisterId,
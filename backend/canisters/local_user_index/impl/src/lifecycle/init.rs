@@ -1,6 +1,6 @@
 use crate::Data;
 use crate::lifecycle::{init_env, init_state};
-use crate::memory::get_stable_memory_map_memory;
+use crate::memory::{get_canister_logs_memory, get_stable_memory_map_memory};
 use canister_tracing_macros::trace;
 use ic_cdk::init;
 use local_user_index_canister::init::Args;
@@ -12,7 +12,7 @@ const CANISTER_POOL_TARGET_SIZE: u16 = 20;
 #[init]
 #[trace]
 fn init(args: Args) {
-    canister_logger::init(args.test_mode);
+    canister_logger::init(args.test_mode, get_canister_logs_memory());
     stable_memory_map::init(get_stable_memory_map_memory());
     init_cycles_dispenser_client(args.cycles_dispenser_canister_id, args.test_mode);
 
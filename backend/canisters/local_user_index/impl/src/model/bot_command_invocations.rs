@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use types::{MessageId, Milliseconds, TimestampMillis, UserId};
+
+// Tracks each command invocation from the point the bot first reports on it (or acts upon it) until its access token
+// expires, so that each invocation is recorded exactly once. Invocations which haven't completed by the time the token
+// expires are recorded as having failed.
+#[derive(Serialize, Deserialize, Default)]
+pub struct BotCommandInvocations {
+    invocations: HashMap<(UserId, MessageId), Invocation>,
+    expiries: BTreeSet<(TimestampMillis, UserId, MessageId)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Invocation {
+    command: String,
+    issued_at: TimestampMillis,
+    expires: TimestampMillis,
+    completed: bool,
+}
+
+pub struct CompletedBotCommandInvocation {
+    pub bot_id: UserId,
+    pub command: String,
+    pub success: bool,
+    pub latency: Milliseconds,
+}
+
+impl BotCommandInvocations {
+    // Starts tracking the invocation if it isn't already being tracked
+    pub fn record_received(
+        &mut self,
+        bot_id: UserId,
+        message_id: MessageId,
+        command: String,
+        issued_at: TimestampMillis,
+        expires: TimestampMillis,
+    ) {
+        self.invocations.entry((bot_id, message_id)).or_insert_with(|| {
+            self.expiries.insert((expires, bot_id, message_id));
+            Invocation {
+                command,
+                issued_at,
+                expires,
+                completed: false,
+            }
+        });
+    }
+
+    // Marks a tracked invocation as completed, returning the outcome to record if it hadn't already completed
+    pub fn record_completed(
+        &mut self,
+        bot_id: UserId,
+        message_id: MessageId,
+        success: bool,
+        now: TimestampMillis,
+    ) -> Option<CompletedBotCommandInvocation> {
+        let invocation = self.invocations.get_mut(&(bot_id, message_id))?;
+        if invocation.completed {
+            return None;
+        }
+        invocation.completed = true;
+
+        Some(CompletedBotCommandInvocation {
+            bot_id,
+            command: invocation.command.clone(),
+            success,
+            latency: now.saturating_sub(invocation.issued_at),
+        })
+    }
+
+    // Removes the invocations whose access tokens have expired, returning those which never completed so that they can
+    // be recorded as having timed out
+    pub fn take_expired(&mut self, now: TimestampMillis) -> Vec<CompletedBotCommandInvocation> {
+        let mut timed_out = Vec::new();
+
+        while let Some(&(expires, bot_id, message_id)) = self.expiries.first() {
+            if expires > now {
+                break;
+            }
+            self.expiries.pop_first();
+
+            if let Some(invocation) = self.invocations.remove(&(bot_id, message_id)) {
+                if !invocation.completed {
+                    timed_out.push(CompletedBotCommandInvocation {
+                        bot_id,
+                        command: invocation.command,
+                        success: false,
+                        latency: invocation.expires.saturating_sub(invocation.issued_at),
+                    });
+                }
+            }
+        }
+
+        timed_out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUED_AT: TimestampMillis = 1_000_000;
+    const EXPIRES: TimestampMillis = ISSUED_AT + 300_000;
+
+    fn bot() -> UserId {
+        UserId::from(candid::Principal::from_slice(&[1]))
+    }
+
+    fn receive(invocations: &mut BotCommandInvocations, message_id: u64) {
+        invocations.record_received(bot(), message_id.into(), "cmd".to_string(), ISSUED_AT, EXPIRES);
+    }
+
+    #[test]
+    fn invocation_only_completes_once() {
+        let mut invocations = BotCommandInvocations::default();
+        receive(&mut invocations, 1);
+
+        let completed = invocations
+            .record_completed(bot(), 1u64.into(), true, ISSUED_AT + 500)
+            .unwrap();
+        assert!(completed.success);
+        assert_eq!(completed.latency, 500);
+        assert_eq!(completed.command, "cmd");
+
+        receive(&mut invocations, 1);
+        assert!(
+            invocations
+                .record_completed(bot(), 1u64.into(), false, ISSUED_AT + 600)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn untracked_invocation_cannot_complete() {
+        let mut invocations = BotCommandInvocations::default();
+
+        assert!(invocations.record_completed(bot(), 1u64.into(), true, ISSUED_AT).is_none());
+    }
+
+    #[test]
+    fn incomplete_invocations_time_out_once_expired() {
+        let mut invocations = BotCommandInvocations::default();
+        receive(&mut invocations, 1);
+        receive(&mut invocations, 2);
+        invocations.record_completed(bot(), 2u64.into(), true, ISSUED_AT + 100);
+
+        assert!(invocations.take_expired(EXPIRES - 1).is_empty());
+
+        let timed_out = invocations.take_expired(EXPIRES);
+        assert_eq!(timed_out.len(), 1);
+        assert!(!timed_out[0].success);
+        assert_eq!(timed_out[0].latency, EXPIRES - ISSUED_AT);
+
+        assert!(invocations.invocations.is_empty());
+        assert!(invocations.expiries.is_empty());
+        assert!(invocations.take_expired(EXPIRES + 1).is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use candid::Principal;
+use local_user_index_canister::PinnedBotVersion;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use types::{
@@ -24,6 +25,19 @@ pub struct Bot {
     pub principal: Principal,
     pub registration_status: BotRegistrationStatus,
     pub data_encoding: BotDataEncoding,
+    // Previous versions of the definition which some installations remain pinned to
+    #[serde(default)]
+    pub pinned_versions: Vec<PinnedBotVersion>,
+}
+
+impl Bot {
+    // Returns the commands of the version the installation at `location` is pinned to, or else of the latest version
+    pub fn commands(&self, location: &BotInstallationLocation) -> &[BotCommandDefinition] {
+        self.pinned_versions
+            .iter()
+            .find(|v| v.installations.contains(location))
+            .map_or(&self.commands, |v| &v.commands)
+    }
 }
 
 impl BotsMap {
@@ -64,6 +78,7 @@ impl BotsMap {
                 principal: user_principal,
                 registration_status: BotRegistrationStatus::Private(permitted_install_location),
                 data_encoding,
+                pinned_versions: Vec::new(),
             },
         );
         self.principal_to_user_id.insert(user_principal, bot_id);
@@ -85,6 +100,12 @@ impl BotsMap {
         });
     }
 
+    pub fn set_pinned_versions(&mut self, bot_id: UserId, pinned_versions: Vec<PinnedBotVersion>) {
+        self.bots.entry(bot_id).and_modify(|bot| {
+            bot.pinned_versions = pinned_versions;
+        });
+    }
+
     pub fn remove(&mut self, bot_id: &UserId) -> Option<Bot> {
         let bot = self.bots.remove(bot_id)?;
         self.principal_to_user_id.remove(&bot.principal);
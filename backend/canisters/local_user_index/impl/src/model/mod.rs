@@ -1,3 +1,4 @@
+pub mod bot_command_invocations;
 pub mod bots_map;
 pub mod community_event_batch;
 pub mod global_user_map;
@@ -5,6 +6,7 @@ pub mod group_event_batch;
 pub mod local_community_map;
 pub mod local_group_map;
 pub mod local_user_map;
+pub mod notification_digests;
 pub mod referral_codes;
 pub mod user_event_batch;
 pub mod user_index_event_batch;
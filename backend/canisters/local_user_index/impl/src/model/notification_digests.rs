@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use types::{Chat, DigestChatSummary, OutboundNotification, TimestampMillis, UserId, UserNotificationPreferences};
+
+const MAX_PENDING_OUTBOUND_PER_USER: usize = 100;
+
+// Holds back notifications which arrive during a user's quiet hours or which are for chats the user has opted to
+// receive as a digest, so that they can be pushed later as a single summary notification. Webhook and email
+// notifications are held back too and are delivered individually alongside the digest.
+#[derive(Serialize, Deserialize, Default)]
+pub struct NotificationDigests {
+    preferences: HashMap<UserId, UserNotificationPreferences>,
+    pending: HashMap<UserId, PendingDigest>,
+    #[serde(default)]
+    pending_outbound: HashMap<UserId, VecDeque<OutboundNotification>>,
+    last_pushed: HashMap<UserId, TimestampMillis>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PendingDigest {
+    since: TimestampMillis,
+    chats: Vec<DigestChatSummary>,
+}
+
+pub struct DueDigest {
+    pub user_id: UserId,
+    pub since: TimestampMillis,
+    pub chats: Vec<DigestChatSummary>,
+    pub outbound: Vec<OutboundNotification>,
+}
+
+impl NotificationDigests {
+    pub fn set_preferences(&mut self, user_id: UserId, preferences: UserNotificationPreferences) {
+        if preferences.quiet_hours.is_none() && preferences.digest_interval.is_none() {
+            self.preferences.remove(&user_id);
+            self.last_pushed.remove(&user_id);
+        } else {
+            self.preferences.insert(user_id, preferences);
+        }
+    }
+
+    pub fn remove_user(&mut self, user_id: &UserId) {
+        self.preferences.remove(user_id);
+        self.pending.remove(user_id);
+        self.pending_outbound.remove(user_id);
+        self.last_pushed.remove(user_id);
+    }
+
+    // If the notification should be held back, it is added to the user's pending digest and true is returned
+    pub fn try_defer(&mut self, user_id: UserId, chat: Option<Chat>, now: TimestampMillis) -> bool {
+        // Notifications which can't be attributed to a chat can't be summarised, so they are always pushed immediately
+        let Some(chat) = chat else {
+            return false;
+        };
+
+        if !self.should_defer(user_id, chat, now) {
+            return false;
+        }
+
+        let pending = self.pending.entry(user_id).or_insert_with(|| PendingDigest {
+            since: now,
+            chats: Vec::new(),
+        });
+
+        if let Some(summary) = pending.chats.iter_mut().find(|s| s.chat == chat) {
+            summary.notification_count += 1;
+            summary.latest = now;
+        } else {
+            pending.chats.push(DigestChatSummary {
+                chat,
+                notification_count: 1,
+                latest: now,
+            });
+        }
+        true
+    }
+
+    // If the webhook or email notification should be held back, it is queued until the user's digest is due and None
+    // is returned, otherwise the notification is handed back so that it can be delivered immediately
+    pub fn try_defer_outbound(
+        &mut self,
+        notification: OutboundNotification,
+        now: TimestampMillis,
+    ) -> Option<OutboundNotification> {
+        let user_id = notification.payload.recipient;
+        if !self.should_defer(user_id, notification.payload.chat, now) {
+            return Some(notification);
+        }
+
+        let queue = self.pending_outbound.entry(user_id).or_default();
+        if queue.len() >= MAX_PENDING_OUTBOUND_PER_USER {
+            // Keep the most recent notifications if the user has built up a large backlog
+            queue.pop_front();
+        }
+        queue.push_back(notification);
+        None
+    }
+
+    // Whether a notification for the given chat should be held back rather than pushed immediately
+    pub fn should_defer(&self, user_id: UserId, chat: Chat, now: TimestampMillis) -> bool {
+        let Some(preferences) = self.preferences.get(&user_id) else {
+            return false;
+        };
+
+        let in_quiet_hours = preferences
+            .quiet_hours
+            .is_some_and(|q| q.contains(now, preferences.utc_offset_mins));
+
+        let is_digest_chat = preferences.digest_interval.is_some() && preferences.digest_chats.contains(&chat);
+
+        in_quiet_hours || is_digest_chat
+    }
+
+    // Takes the digests which can be pushed now, ie. those whose users aren't in their quiet hours and whose digest
+    // interval has elapsed since their previous digest was pushed. Users who only have webhook or email notifications
+    // pending get a digest with no chats.
+    pub fn take_due(&mut self, now: TimestampMillis) -> Vec<DueDigest> {
+        let due: HashSet<UserId> = self
+            .pending
+            .keys()
+            .chain(self.pending_outbound.keys())
+            .filter(|user_id| self.is_due(user_id, now))
+            .copied()
+            .collect();
+
+        due.into_iter()
+            .map(|user_id| {
+                let pending = self.pending.remove(&user_id);
+                let outbound = self.pending_outbound.remove(&user_id).map(Vec::from).unwrap_or_default();
+                if self.preferences.contains_key(&user_id) {
+                    self.last_pushed.insert(user_id, now);
+                }
+                DueDigest {
+                    user_id,
+                    since: pending.as_ref().map_or(now, |p| p.since),
+                    chats: pending.map(|p| p.chats).unwrap_or_default(),
+                    outbound,
+                }
+            })
+            .collect()
+    }
+
+    fn is_due(&self, user_id: &UserId, now: TimestampMillis) -> bool {
+        let Some(preferences) = self.preferences.get(user_id) else {
+            // The user has cleared their preferences so push whatever is pending straight away
+            return true;
+        };
+
+        if preferences
+            .quiet_hours
+            .is_some_and(|q| q.contains(now, preferences.utc_offset_mins))
+        {
+            return false;
+        }
+
+        match (preferences.digest_interval, self.last_pushed.get(user_id)) {
+            (Some(interval), Some(last_pushed)) => now >= last_pushed.saturating_add(interval),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{MessageIndex, NotificationCategory, NotificationDestination, OutboundNotificationPayload, QuietHours};
+
+    const MINUTE: TimestampMillis = 60 * 1000;
+    const HOUR: TimestampMillis = 60 * MINUTE;
+    // Midnight UTC
+    const DAY_START: TimestampMillis = 20_000 * 24 * HOUR;
+
+    fn user() -> UserId {
+        UserId::from(candid::Principal::from_slice(&[1]))
+    }
+
+    fn chat(id: u8) -> Chat {
+        Chat::Group(candid::Principal::from_slice(&[id]).into())
+    }
+
+    fn quiet_hours_preferences(utc_offset_mins: i16) -> UserNotificationPreferences {
+        UserNotificationPreferences {
+            quiet_hours: Some(QuietHours {
+                start_mins: 22 * 60,
+                end_mins: 7 * 60,
+            }),
+            utc_offset_mins,
+            digest_interval: None,
+            digest_chats: HashSet::new(),
+        }
+    }
+
+    fn email_notification(chat: Chat, timestamp: TimestampMillis) -> OutboundNotification {
+        OutboundNotification {
+            destination: NotificationDestination::Email("user@example.com".to_string()),
+            payload: OutboundNotificationPayload {
+                recipient: user(),
+                category: NotificationCategory::Mention,
+                chat,
+                thread_root_message_index: None,
+                message_index: MessageIndex::from(1),
+                sender: None,
+                sender_name: None,
+                message_text: Some("hello".to_string()),
+                timestamp,
+            },
+            signing_secret: None,
+        }
+    }
+
+    fn digest_preferences(interval: TimestampMillis) -> UserNotificationPreferences {
+        UserNotificationPreferences {
+            quiet_hours: None,
+            utc_offset_mins: 0,
+            digest_interval: Some(interval),
+            digest_chats: [chat(1)].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn notifications_during_quiet_hours_are_deferred_until_they_end() {
+        let mut digests = NotificationDigests::default();
+        digests.set_preferences(user(), quiet_hours_preferences(0));
+
+        assert!(!digests.try_defer(user(), Some(chat(1)), DAY_START + 12 * HOUR));
+        assert!(digests.try_defer(user(), Some(chat(1)), DAY_START + 23 * HOUR));
+        assert!(digests.try_defer(user(), Some(chat(2)), DAY_START + 24 * HOUR + HOUR));
+        assert!(digests.try_defer(user(), Some(chat(1)), DAY_START + 24 * HOUR + 2 * HOUR));
+
+        // Still within quiet hours after midnight
+        assert!(digests.take_due(DAY_START + 24 * HOUR + 6 * HOUR).is_empty());
+
+        let due = digests.take_due(DAY_START + 24 * HOUR + 7 * HOUR);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].since, DAY_START + 23 * HOUR);
+        assert_eq!(due[0].chats.len(), 2);
+
+        let chat1 = due[0].chats.iter().find(|c| c.chat == chat(1)).unwrap();
+        assert_eq!(chat1.notification_count, 2);
+        assert_eq!(chat1.latest, DAY_START + 24 * HOUR + 2 * HOUR);
+
+        assert!(digests.take_due(DAY_START + 24 * HOUR + 8 * HOUR).is_empty());
+    }
+
+    #[test]
+    fn quiet_hours_respect_negative_utc_offset() {
+        let mut digests = NotificationDigests::default();
+        // UTC-5, so quiet hours are 03:00 - 12:00 UTC
+        digests.set_preferences(user(), quiet_hours_preferences(-300));
+
+        assert!(!digests.try_defer(user(), Some(chat(1)), DAY_START + 23 * HOUR));
+        assert!(digests.try_defer(user(), Some(chat(1)), DAY_START + 24 * HOUR + 4 * HOUR));
+        assert!(digests.take_due(DAY_START + 24 * HOUR + 11 * HOUR).is_empty());
+        assert_eq!(digests.take_due(DAY_START + 24 * HOUR + 12 * HOUR).len(), 1);
+    }
+
+    #[test]
+    fn notifications_without_chat_are_never_deferred() {
+        let mut digests = NotificationDigests::default();
+        digests.set_preferences(user(), quiet_hours_preferences(0));
+
+        assert!(!digests.try_defer(user(), None, DAY_START + 23 * HOUR));
+        assert!(digests.take_due(DAY_START + 24 * HOUR + 7 * HOUR).is_empty());
+    }
+
+    #[test]
+    fn digests_are_pushed_at_most_once_per_interval() {
+        let mut digests = NotificationDigests::default();
+        digests.set_preferences(user(), digest_preferences(HOUR));
+
+        // Only chats in the digest are deferred
+        assert!(!digests.try_defer(user(), Some(chat(2)), DAY_START));
+        assert!(digests.try_defer(user(), Some(chat(1)), DAY_START));
+
+        // The first digest is pushed straight away
+        assert_eq!(digests.take_due(DAY_START + MINUTE).len(), 1);
+
+        assert!(digests.try_defer(user(), Some(chat(1)), DAY_START + 2 * MINUTE));
+        assert!(digests.take_due(DAY_START + HOUR).is_empty());
+
+        let due = digests.take_due(DAY_START + HOUR + MINUTE);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].chats[0].notification_count, 1);
+
+        // Nothing is pending so there is nothing to push
+        assert!(digests.take_due(DAY_START + 3 * HOUR).is_empty());
+    }
+
+    #[test]
+    fn pending_digest_is_pushed_once_preferences_are_cleared() {
+        let mut digests = NotificationDigests::default();
+        digests.set_preferences(user(), digest_preferences(HOUR));
+
+        assert!(digests.try_defer(user(), Some(chat(1)), DAY_START));
+        assert_eq!(digests.take_due(DAY_START).len(), 1);
+        assert!(digests.try_defer(user(), Some(chat(1)), DAY_START + MINUTE));
+
+        digests.set_preferences(user(), UserNotificationPreferences::default());
+
+        assert!(!digests.try_defer(user(), Some(chat(1)), DAY_START + 2 * MINUTE));
+        assert_eq!(digests.take_due(DAY_START + 2 * MINUTE).len(), 1);
+    }
+
+    #[test]
+    fn should_defer_does_not_add_to_digest() {
+        let mut digests = NotificationDigests::default();
+        digests.set_preferences(user(), digest_preferences(HOUR));
+
+        assert!(digests.should_defer(user(), chat(1), DAY_START));
+        assert!(!digests.should_defer(user(), chat(2), DAY_START));
+        assert!(digests.take_due(DAY_START).is_empty());
+    }
+
+    #[test]
+    fn email_only_user_receives_notifications_after_quiet_hours_end() {
+        let mut digests = NotificationDigests::default();
+        digests.set_preferences(user(), quiet_hours_preferences(0));
+
+        // The user has no push subscriptions so only their email notifications reach the digests
+        assert!(
+            digests
+                .try_defer_outbound(email_notification(chat(1), DAY_START + 12 * HOUR), DAY_START + 12 * HOUR)
+                .is_some()
+        );
+        assert!(
+            digests
+                .try_defer_outbound(email_notification(chat(1), DAY_START + 23 * HOUR), DAY_START + 23 * HOUR)
+                .is_none()
+        );
+        assert!(
+            digests
+                .try_defer_outbound(email_notification(chat(2), DAY_START + 25 * HOUR), DAY_START + 25 * HOUR)
+                .is_none()
+        );
+
+        assert!(digests.take_due(DAY_START + 24 * HOUR + 6 * HOUR).is_empty());
+
+        let due = digests.take_due(DAY_START + 24 * HOUR + 7 * HOUR);
+        assert_eq!(due.len(), 1);
+        assert!(due[0].chats.is_empty());
+        assert_eq!(due[0].outbound.len(), 2);
+        assert_eq!(due[0].outbound[0].payload.timestamp, DAY_START + 23 * HOUR);
+        assert_eq!(due[0].outbound[1].payload.chat, chat(2));
+
+        assert!(digests.take_due(DAY_START + 24 * HOUR + 8 * HOUR).is_empty());
+    }
+
+    #[test]
+    fn pending_outbound_notifications_are_capped_per_user() {
+        let mut digests = NotificationDigests::default();
+        digests.set_preferences(user(), digest_preferences(HOUR));
+
+        for i in 0..(MAX_PENDING_OUTBOUND_PER_USER as u64 + 5) {
+            let now = DAY_START + i * MINUTE;
+            assert!(digests.try_defer_outbound(email_notification(chat(1), now), now).is_none());
+        }
+
+        let due = digests.take_due(DAY_START + 2 * HOUR);
+        assert_eq!(due[0].outbound.len(), MAX_PENDING_OUTBOUND_PER_USER);
+        assert_eq!(due[0].outbound[0].payload.timestamp, DAY_START + 5 * MINUTE);
+    }
+}
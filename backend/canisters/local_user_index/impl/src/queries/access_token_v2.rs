@@ -1,16 +1,23 @@
+use crate::bots::ACCESS_TOKEN_VALIDITY;
 use crate::{RuntimeState, mutate_state, read_state};
 use canister_api_macros::query;
 use canister_tracing_macros::trace;
 use community_canister::c2c_can_issue_access_token;
 use jwt::Claims;
 use local_user_index_canister::access_token_v2::{self, Response::*, *};
-use rand::SeedableRng;
+use oc_error_codes::OCErrorCode;
 use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
 use serde::Serialize;
 use types::c2c_can_issue_access_token::{
-    AccessTypeArgs, BotActionByCommandArgs, JoinVideoCallArgs, MarkVideoCallAsEndedArgs, StartVideoCallArgs,
+    AccessTypeArgs, BotActionByCommandArgs, BotActionByComponentArgs, JoinVideoCallArgs, MarkVideoCallAsEndedArgs,
+    StartVideoCallArgs,
+};
+use types::{
+    AutonomousBotScope, BotActionByCommandClaims, BotActionByComponentClaims, BotActionChatDetails, BotActionScope,
+    BotAutocompleteClaims, BotAutocompleteQuery, BotCommand, BotCommandArgValue, BotComponentInteraction,
+    BotInstallationLocation, BotPermissions, Chat, JoinOrEndVideoCallClaims, StartVideoCallClaims, UserId,
 };
-use types::{AutonomousBotScope, BotActionByCommandClaims, BotCommand, Chat, JoinOrEndVideoCallClaims, StartVideoCallClaims};
 
 #[query(composite = true, candid = true, msgpack = true)]
 #[trace]
@@ -24,8 +31,9 @@ async fn access_token_v2(args_wrapper: Args) -> Response {
         Err(response) => return response,
     };
 
-    if let Err(error_response) = can_issue_access_token(scope, &access_type_args).await {
-        return error_response;
+    let granted_permissions = match can_issue_access_token(scope, &access_type_args).await {
+        Ok(permissions) => permissions,
+        Err(error_response) => return error_response,
     };
 
     let token_type_name = args_wrapper.type_name().to_string();
@@ -51,6 +59,52 @@ async fn access_token_v2(args_wrapper: Args) -> Response {
             return build_token(token_type_name, custom_claims, state);
         }
 
+        if let ArgsInternal::BotAutocomplete(args) = &args_wrapper {
+            // The token grants no permissions, it only proves the initiator is allowed to use the command in this scope
+            let custom_claims = BotAutocompleteClaims {
+                bot: args.bot_id,
+                scope: args.scope.clone(),
+                bot_api_gateway: state.env.canister_id(),
+                query: BotAutocompleteQuery {
+                    command: args.command.name.clone(),
+                    param: args.param.clone(),
+                    partial_input: args.partial_input.clone(),
+                    args: args.command.args.clone(),
+                    initiator: access_type_args.initiator(),
+                    meta: args.command.meta.clone(),
+                },
+            };
+            return build_token(token_type_name, custom_claims, state);
+        }
+
+        if let ArgsInternal::BotActionByComponent(args) = &args_wrapper {
+            let Some(granted_permissions) = granted_permissions else {
+                return InternalError("Permissions not returned for component".to_string());
+            };
+
+            let custom_claims = BotActionByComponentClaims {
+                bot: args.bot_id,
+                // The bot's response to the interaction is sent as a new message in the same chat and thread
+                scope: BotActionScope::Chat(BotActionChatDetails {
+                    chat: args.chat,
+                    thread: args.thread,
+                    message_id: state.env.rng().next_u64().into(),
+                    user_message_id: None,
+                }),
+                bot_api_gateway: state.env.canister_id(),
+                granted_permissions,
+                interaction: BotComponentInteraction {
+                    component_id: args.component_id.clone(),
+                    thread: args.thread,
+                    message_id: args.message_id,
+                    value: args.value.clone(),
+                    initiator: access_type_args.initiator(),
+                    meta: args.meta.clone(),
+                },
+            };
+            return build_token(token_type_name, custom_claims, state);
+        }
+
         match access_type_args {
             AccessTypeArgs::StartVideoCall(args) => {
                 let custom_claims = StartVideoCallClaims {
@@ -98,12 +152,34 @@ fn prepare(args_outer: &ArgsInternal, state: &RuntimeState) -> Result<PrepareRes
     if let ArgsInternal::BotActionByCommand(args) = args_outer {
         let bot = state.data.bots.get(&args.bot_id).ok_or(Response::NotAuthorized)?;
 
+        let location = installation_location(&args.scope, user.user_id);
         let command = bot
-            .commands
+            .commands(&location)
             .iter()
             .find(|c| c.name == args.command.name)
             .ok_or(Response::NotAuthorized)?;
 
+        if !command.are_valid_args(&args.command.args) {
+            return Err(Response::Error(
+                OCErrorCode::InvalidRequest.with_message("Args don't match the command definition"),
+            ));
+        }
+
+        let mut referenced_chats = Vec::new();
+        let mut referenced_messages = Vec::new();
+        for value in args.command.args.iter().flat_map(|a| a.value.values()) {
+            match value {
+                BotCommandArgValue::Chat(chat) => referenced_chats.push(*chat),
+                BotCommandArgValue::Message(message) => referenced_messages.push(message.clone()),
+                BotCommandArgValue::TokenAmount(amount) if !state.data.registered_ledgers.contains(&amount.ledger) => {
+                    return Err(Response::Error(
+                        OCErrorCode::InvalidRequest.with_message(format!("Ledger not registered: {}", amount.ledger)),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
         return Ok(PrepareResult {
             scope: args.scope.clone().into(),
             access_type_args: AccessTypeArgs::BotActionByCommand(BotActionByCommandArgs {
@@ -111,6 +187,58 @@ fn prepare(args_outer: &ArgsInternal, state: &RuntimeState) -> Result<PrepareRes
                 initiator: user.user_id,
                 initiator_role: command.default_role.unwrap_or_default(),
                 requested_permissions: command.permissions.clone(),
+                referenced_chats,
+                referenced_messages,
+            }),
+        });
+    }
+
+    if let ArgsInternal::BotAutocomplete(args) = args_outer {
+        let bot = state.data.bots.get(&args.bot_id).ok_or(Response::NotAuthorized)?;
+
+        let location = installation_location(&args.scope, user.user_id);
+        let command = bot
+            .commands(&location)
+            .iter()
+            .find(|c| c.name == args.command.name)
+            .ok_or(Response::NotAuthorized)?;
+
+        let param_supports_autocomplete = command
+            .params
+            .iter()
+            .any(|p| p.name == args.param && p.autocomplete.unwrap_or_default());
+
+        if command.autocomplete_endpoint.is_none() || !param_supports_autocomplete {
+            return Err(Response::NotAuthorized);
+        }
+
+        return Ok(PrepareResult {
+            scope: args.scope.clone().into(),
+            access_type_args: AccessTypeArgs::BotActionByCommand(BotActionByCommandArgs {
+                bot_id: args.bot_id,
+                initiator: user.user_id,
+                initiator_role: command.default_role.unwrap_or_default(),
+                requested_permissions: command.permissions.clone(),
+                referenced_chats: Vec::new(),
+                referenced_messages: Vec::new(),
+            }),
+        });
+    }
+
+    if let ArgsInternal::BotActionByComponent(args) = args_outer {
+        if state.data.bots.get(&args.bot_id).is_none() {
+            return Err(Response::NotAuthorized);
+        }
+
+        return Ok(PrepareResult {
+            scope: AutonomousBotScope::Chat(args.chat),
+            access_type_args: AccessTypeArgs::BotActionByComponent(BotActionByComponentArgs {
+                bot_id: args.bot_id,
+                initiator: user.user_id,
+                thread: args.thread,
+                message_id: args.message_id,
+                component_id: args.component_id.clone(),
+                value: args.value.clone(),
             }),
         });
     }
@@ -151,11 +279,7 @@ fn build_token<T: Serialize>(token_type_name: String, custom_claims: T, state: &
 
     let mut rng = StdRng::from_seed(state.env.entropy());
 
-    let claims = Claims::new(
-        state.env.now() + 300_000, // Token valid for 5 mins from now
-        token_type_name,
-        custom_claims,
-    );
+    let claims = Claims::new(state.env.now() + ACCESS_TOKEN_VALIDITY, token_type_name, custom_claims);
 
     match jwt::sign_and_encode_token(state.data.oc_key_pair.secret_key_der(), claims, &mut rng) {
         Ok(token) => Success(token),
@@ -169,6 +293,8 @@ enum ArgsInternal {
     JoinVideoCall(access_token_v2::JoinVideoCallArgs),
     MarkVideoCallAsEnded(access_token_v2::MarkVideoCallAsEndedArgs),
     BotActionByCommand(access_token_v2::BotActionByCommandArgs),
+    BotActionByComponent(access_token_v2::BotActionByComponentArgs),
+    BotAutocomplete(access_token_v2::BotAutocompleteArgs),
 }
 
 impl ArgsInternal {
@@ -178,6 +304,8 @@ impl ArgsInternal {
             Args::JoinVideoCall(args) => Ok(ArgsInternal::JoinVideoCall(args)),
             Args::MarkVideoCallAsEnded(args) => Ok(ArgsInternal::MarkVideoCallAsEnded(args)),
             Args::BotActionByCommand(args) => Ok(ArgsInternal::BotActionByCommand(args)),
+            Args::BotActionByComponent(args) => Ok(ArgsInternal::BotActionByComponent(args)),
+            Args::BotAutocomplete(args) => Ok(ArgsInternal::BotAutocomplete(args)),
         }
     }
 
@@ -187,6 +315,8 @@ impl ArgsInternal {
             Self::JoinVideoCall(_) => "JoinVideoCall",
             Self::MarkVideoCallAsEnded(_) => "MarkVideoCallAsEnded",
             Self::BotActionByCommand(_) => "BotActionByCommand",
+            Self::BotActionByComponent(_) => "BotActionByComponent",
+            Self::BotAutocomplete(_) => "BotAutocomplete",
         }
     }
 
@@ -196,11 +326,16 @@ impl ArgsInternal {
             Self::JoinVideoCall(args) => Some(args.chat),
             Self::MarkVideoCallAsEnded(args) => Some(args.chat),
             Self::BotActionByCommand(args) => args.scope.chat(None),
+            Self::BotActionByComponent(args) => Some(args.chat),
+            Self::BotAutocomplete(args) => args.scope.chat(None),
         }
     }
 }
 
-async fn can_issue_access_token(scope: AutonomousBotScope, access_type_args: &AccessTypeArgs) -> Result<(), Response> {
+async fn can_issue_access_token(
+    scope: AutonomousBotScope,
+    access_type_args: &AccessTypeArgs,
+) -> Result<Option<BotPermissions>, Response> {
     let c2c_response = match scope {
         AutonomousBotScope::Chat(Chat::Direct(chat_id)) => {
             user_canister_c2c_client::c2c_can_issue_access_token_v2(chat_id.into(), access_type_args).await
@@ -231,8 +366,20 @@ async fn can_issue_access_token(scope: AutonomousBotScope, access_type_args: &Ac
     };
 
     match c2c_response {
-        Ok(c2c_can_issue_access_token::Response::Success) => Ok(()),
+        Ok(c2c_can_issue_access_token::Response::Success) => Ok(None),
+        Ok(c2c_can_issue_access_token::Response::SuccessWithPermissions(permissions)) => Ok(Some(permissions)),
         Ok(c2c_can_issue_access_token::Response::Failure) => Err(NotAuthorized),
         Err(err) => Err(InternalError(format!("{err:?}"))),
     }
 }
+
+// Direct chats with bots are scoped to the bot, whereas the installation is keyed by the user who installed it
+fn installation_location(scope: &BotActionScope, initiator: UserId) -> BotInstallationLocation {
+    match scope {
+        BotActionScope::Chat(details) => match details.chat {
+            Chat::Direct(_) => BotInstallationLocation::User(initiator.into()),
+            chat => chat.into(),
+        },
+        BotActionScope::Community(details) => BotInstallationLocation::Community(details.community_id),
+    }
+}
@@ -0,0 +1,37 @@
+use crate::read_state;
+use canister_api_macros::query;
+use canister_tracing_macros::trace;
+use local_user_index_canister::bot_storage_entries::*;
+use oc_error_codes::OCErrorCode;
+use types::CommunityOrGroup;
+
+#[query(composite = true, candid = true, msgpack = true)]
+#[trace]
+async fn bot_storage_entries(args: Args) -> Response {
+    let Some(bot_id) = read_state(|state| state.data.bots.get_by_caller(&state.env.caller()).map(|bot| bot.bot_id)) else {
+        return Response::Error(OCErrorCode::BotNotAuthenticated.into());
+    };
+
+    match args.location {
+        CommunityOrGroup::Group(chat_id) => match group_canister_c2c_client::c2c_bot_storage_entries(
+            chat_id.into(),
+            &group_canister::c2c_bot_storage_entries::Args { bot_id, keys: args.keys },
+        )
+        .await
+        {
+            Ok(group_canister::c2c_bot_storage_entries::Response::Success(entries)) => Response::Success(entries),
+            Ok(group_canister::c2c_bot_storage_entries::Response::Error(error)) => Response::Error(error),
+            Err(error) => Response::Error(error.into()),
+        },
+        CommunityOrGroup::Community(community_id) => match community_canister_c2c_client::c2c_bot_storage_entries(
+            community_id.into(),
+            &community_canister::c2c_bot_storage_entries::Args { bot_id, keys: args.keys },
+        )
+        .await
+        {
+            Ok(community_canister::c2c_bot_storage_entries::Response::Success(entries)) => Response::Success(entries),
+            Ok(community_canister::c2c_bot_storage_entries::Response::Error(error)) => Response::Error(error),
+            Err(error) => Response::Error(error.into()),
+        },
+    }
+}
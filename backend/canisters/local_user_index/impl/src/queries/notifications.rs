@@ -64,6 +64,8 @@ pub(crate) fn notifications_impl(args: Args, state: &RuntimeState) -> Response {
                 }
                 bot_endpoints.extend(bots_to_add);
             }
+            // Outbound notifications and email confirmations carry their own destination
+            NotificationEnvelope::Outbound(_) | NotificationEnvelope::EmailConfirmation(_) => has_subscriptions = true,
         }
 
         if has_subscriptions {
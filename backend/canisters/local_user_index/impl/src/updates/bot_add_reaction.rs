@@ -1,5 +1,5 @@
 use crate::{
-    bots::{BotAccessContext, extract_access_context_from_chat_context},
+    bots::{BotAccessContext, extract_access_context_from_chat_context, record_bot_action},
     mutate_state,
 };
 use canister_api_macros::update;
@@ -14,7 +14,10 @@ async fn bot_add_reaction(args: Args) -> Response {
         Err(_) => return OCErrorCode::BotNotAuthenticated.into(),
     };
 
-    call_chat_canister(context, args.message_id, args.thread, args.reaction).await
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, args.message_id, args.thread, args.reaction).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success), state));
+    response
 }
 
 async fn call_chat_canister(
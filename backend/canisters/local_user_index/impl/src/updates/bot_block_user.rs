@@ -0,0 +1,69 @@
+use crate::{
+    bots::{BotAccessContext, extract_access_context_from_community_or_group_context, record_bot_action},
+    mutate_state,
+};
+use canister_api_macros::update;
+use local_user_index_canister::bot_block_user::*;
+use oc_error_codes::OCErrorCode;
+use types::{BotActionScope, Chat, UserId};
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_block_user(args: Args) -> Response {
+    let context = match mutate_state(|state| {
+        extract_access_context_from_community_or_group_context(args.community_or_group_context, state)
+    }) {
+        Ok(context) => context,
+        Err(_) => return OCErrorCode::BotNotAuthenticated.into(),
+    };
+
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, args.user_id).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success), state));
+    response
+}
+
+// Users are blocked from the whole community rather than from individual channels
+async fn call_chat_canister(context: BotAccessContext, user_id: UserId) -> Response {
+    match context.scope {
+        BotActionScope::Chat(details) => match details.chat {
+            Chat::Channel(community_id, _) => community_canister_c2c_client::c2c_bot_remove_user(
+                community_id.into(),
+                &community_canister::c2c_bot_remove_user::Args {
+                    bot_id: context.bot_id,
+                    initiator: context.initiator,
+                    channel_id: None,
+                    user_id,
+                    block: true,
+                },
+            )
+            .await
+            .into(),
+            Chat::Group(chat_id) => group_canister_c2c_client::c2c_bot_remove_user(
+                chat_id.into(),
+                &group_canister::c2c_bot_remove_user::Args {
+                    bot_id: context.bot_id,
+                    initiator: context.initiator,
+                    user_id,
+                    block: true,
+                },
+            )
+            .await
+            .into(),
+            Chat::Direct(_) => OCErrorCode::InvalidBotActionScope
+                .with_message("Direct chats not supported")
+                .into(),
+        },
+        BotActionScope::Community(details) => community_canister_c2c_client::c2c_bot_remove_user(
+            details.community_id.into(),
+            &community_canister::c2c_bot_remove_user::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                channel_id: None,
+                user_id,
+                block: true,
+            },
+        )
+        .await
+        .into(),
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    bots::{BotAccessContext, extract_access_context_from_community_or_group_context, record_bot_action},
+    mutate_state,
+};
+use canister_api_macros::update;
+use local_user_index_canister::bot_change_role::*;
+use oc_error_codes::OCErrorCode;
+use types::{BotActionScope, ChannelId, Chat, GroupRole, UserId};
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_change_role(args: Args) -> Response {
+    let context = match mutate_state(|state| {
+        extract_access_context_from_community_or_group_context(args.community_or_group_context, state)
+    }) {
+        Ok(context) => context,
+        Err(_) => return OCErrorCode::BotNotAuthenticated.into(),
+    };
+
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, args.channel_id, args.user_id, args.new_role).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success), state));
+    response
+}
+
+async fn call_chat_canister(
+    context: BotAccessContext,
+    channel_id: Option<ChannelId>,
+    user_id: UserId,
+    new_role: GroupRole,
+) -> Response {
+    match context.scope {
+        BotActionScope::Chat(details) => match details.chat {
+            Chat::Channel(community_id, _) => community_canister_c2c_client::c2c_bot_change_role(
+                community_id.into(),
+                &community_canister::c2c_bot_change_role::Args {
+                    bot_id: context.bot_id,
+                    initiator: context.initiator,
+                    channel_id,
+                    user_id,
+                    new_role,
+                },
+            )
+            .await
+            .into(),
+            Chat::Group(chat_id) => group_canister_c2c_client::c2c_bot_change_role(
+                chat_id.into(),
+                &group_canister::c2c_bot_change_role::Args {
+                    bot_id: context.bot_id,
+                    initiator: context.initiator,
+                    user_id,
+                    new_role,
+                },
+            )
+            .await
+            .into(),
+            Chat::Direct(_) => OCErrorCode::InvalidBotActionScope
+                .with_message("Direct chats not supported")
+                .into(),
+        },
+        BotActionScope::Community(details) => community_canister_c2c_client::c2c_bot_change_role(
+            details.community_id.into(),
+            &community_canister::c2c_bot_change_role::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                channel_id,
+                user_id,
+                new_role,
+            },
+        )
+        .await
+        .into(),
+    }
+}
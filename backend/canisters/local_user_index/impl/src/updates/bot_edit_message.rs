@@ -0,0 +1,70 @@
+use crate::{
+    bots::{BotAccessContext, extract_access_context_from_chat_context, record_bot_action},
+    mutate_state,
+};
+use canister_api_macros::update;
+use local_user_index_canister::bot_edit_message::*;
+use oc_error_codes::OCErrorCode;
+use types::{BotMessageContent, Chat, MessageId, MessageIndex};
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_edit_message(args: Args) -> Response {
+    let context = match mutate_state(|state| extract_access_context_from_chat_context(args.chat_context, state)) {
+        Ok(context) => context,
+        Err(_) => return OCErrorCode::BotNotAuthenticated.into(),
+    };
+
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, args.thread, args.message_id, args.content, args.block_level_markdown).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success), state));
+    response
+}
+
+async fn call_chat_canister(
+    context: BotAccessContext,
+    thread: Option<MessageIndex>,
+    message_id: MessageId,
+    content: BotMessageContent,
+    block_level_markdown: bool,
+) -> Response {
+    let Some(chat) = context.scope.chat(None) else {
+        return OCErrorCode::InvalidBotActionScope
+            .with_message("Channel not specified")
+            .into();
+    };
+
+    let thread = thread.or(context.scope.thread());
+
+    match chat {
+        Chat::Direct(_) => OCErrorCode::InvalidBotActionScope
+            .with_message("Direct chats not supported")
+            .into(),
+        Chat::Channel(community_id, channel_id) => community_canister_c2c_client::c2c_bot_edit_message(
+            community_id.into(),
+            &community_canister::c2c_bot_edit_message::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                channel_id,
+                thread,
+                message_id,
+                content,
+                block_level_markdown,
+            },
+        )
+        .await
+        .into(),
+        Chat::Group(chat_id) => group_canister_c2c_client::c2c_bot_edit_message(
+            chat_id.into(),
+            &group_canister::c2c_bot_edit_message::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                thread,
+                message_id,
+                content,
+                block_level_markdown,
+            },
+        )
+        .await
+        .into(),
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    bots::{BotAccessContext, extract_access_context_from_chat_context},
+    mutate_state,
+};
+use canister_api_macros::update;
+use local_user_index_canister::bot_pin_message::*;
+use oc_error_codes::OCErrorCode;
+use types::{Chat, MessageIndex};
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_pin_message(args: Args) -> Response {
+    let context = match mutate_state(|state| extract_access_context_from_chat_context(args.chat_context, state)) {
+        Ok(context) => context,
+        Err(_) => return OCErrorCode::BotNotAuthenticated.into(),
+    };
+
+    call_chat_canister(context, args.message_index).await
+}
+
+async fn call_chat_canister(context: BotAccessContext, message_index: MessageIndex) -> Response {
+    let Some(chat) = context.scope.chat(None) else {
+        return OCErrorCode::InvalidBotActionScope
+            .with_message("Channel not specified")
+            .into();
+    };
+
+    match chat {
+        Chat::Direct(_) => OCErrorCode::InvalidBotActionScope
+            .with_message("Direct chats not supported")
+            .into(),
+        Chat::Channel(community_id, channel_id) => community_canister_c2c_client::c2c_bot_pin_message(
+            community_id.into(),
+            &community_canister::c2c_bot_pin_message::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                channel_id,
+                message_index,
+            },
+        )
+        .await
+        .into(),
+        Chat::Group(chat_id) => group_canister_c2c_client::c2c_bot_pin_message(
+            chat_id.into(),
+            &group_canister::c2c_bot_pin_message::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                message_index,
+            },
+        )
+        .await
+        .into(),
+    }
+}
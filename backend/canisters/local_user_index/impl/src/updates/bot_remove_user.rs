@@ -1,5 +1,5 @@
 use crate::{
-    bots::{BotAccessContext, extract_access_context_from_community_or_group_context},
+    bots::{BotAccessContext, extract_access_context_from_community_or_group_context, record_bot_action},
     mutate_state,
 };
 use canister_api_macros::update;
@@ -16,7 +16,10 @@ async fn bot_remove_user(args: Args) -> Response {
         Err(_) => return OCErrorCode::BotNotAuthenticated.into(),
     };
 
-    call_chat_canister(context, args.channel_id, args.user_id, args.block).await
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, args.channel_id, args.user_id, args.block).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success), state));
+    response
 }
 
 async fn call_chat_canister(
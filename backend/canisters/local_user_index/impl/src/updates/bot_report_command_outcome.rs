@@ -0,0 +1,30 @@
+use crate::{
+    bots::{extract_access_context_from_chat_context, record_bot_command_completed, record_bot_command_received},
+    mutate_state,
+};
+use canister_api_macros::update;
+use local_user_index_canister::bot_report_command_outcome::*;
+use oc_error_codes::OCErrorCode;
+
+#[update(candid = true, json = true, msgpack = true)]
+fn bot_report_command_outcome(args: Args) -> Response {
+    mutate_state(|state| {
+        let Ok(context) = extract_access_context_from_chat_context(args.chat_context, state) else {
+            return OCErrorCode::BotNotAuthenticated.into();
+        };
+
+        let Some(invocation) = context.command_invocation() else {
+            return OCErrorCode::InvalidRequest
+                .with_message("Outcomes can only be reported for commands")
+                .into();
+        };
+
+        match args.outcome {
+            BotCommandOutcome::Received => record_bot_command_received(invocation, state),
+            BotCommandOutcome::Succeeded => record_bot_command_completed(invocation, true, state),
+            BotCommandOutcome::Failed => record_bot_command_completed(invocation, false, state),
+        }
+
+        Response::Success
+    })
+}
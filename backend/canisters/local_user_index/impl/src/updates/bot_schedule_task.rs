@@ -0,0 +1,46 @@
+use crate::read_state;
+use canister_api_macros::update;
+use local_user_index_canister::bot_schedule_task::*;
+use oc_error_codes::OCErrorCode;
+use types::{AutonomousBotScope, Chat};
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_schedule_task(args: Args) -> Response {
+    let Some(bot_id) = read_state(|state| state.data.bots.get_by_caller(&state.env.caller()).map(|bot| bot.bot_id)) else {
+        return OCErrorCode::BotNotAuthenticated.into();
+    };
+
+    let (community_id, channel_id) = match args.scope {
+        AutonomousBotScope::Chat(Chat::Direct(_)) => {
+            return OCErrorCode::InvalidBotActionScope
+                .with_message("Direct chats are not supported yet")
+                .into();
+        }
+        AutonomousBotScope::Chat(Chat::Group(group_id)) => {
+            return group_canister_c2c_client::c2c_bot_schedule_task(
+                group_id.into(),
+                &group_canister::c2c_bot_schedule_task::Args {
+                    bot_id,
+                    name: args.name,
+                    interval: args.interval,
+                },
+            )
+            .await
+            .into();
+        }
+        AutonomousBotScope::Chat(Chat::Channel(community_id, channel_id)) => (community_id, Some(channel_id)),
+        AutonomousBotScope::Community(community_id) => (community_id, None),
+    };
+
+    community_canister_c2c_client::c2c_bot_schedule_task(
+        community_id.into(),
+        &community_canister::c2c_bot_schedule_task::Args {
+            bot_id,
+            name: args.name,
+            interval: args.interval,
+            channel_id,
+        },
+    )
+    .await
+    .into()
+}
@@ -0,0 +1,141 @@
+use crate::{
+    RuntimeState,
+    bots::{BotAccessContext, extract_access_context_from_chat_context, record_bot_action},
+    mutate_state,
+};
+use candid::Principal;
+use canister_api_macros::update;
+use canister_client::retry_if_outcome_unknown;
+use local_user_index_canister::bot_send_crypto::*;
+use local_user_index_canister::bot_send_message::SuccessResult;
+use oc_error_codes::{OCError, OCErrorCode};
+use rand::Rng;
+use types::{Chat, MessageId, MessageIndex};
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_send_crypto(args: Args) -> Response {
+    let context = match mutate_state(|state| extract_access_context_from_chat_context(args.chat_context.clone(), state)) {
+        Ok(context) => context,
+        Err(_) => return Response::Error(OCErrorCode::BotNotAuthenticated.into()),
+    };
+
+    let PrepareResult {
+        chat,
+        bot_principal,
+        thread,
+        message_id,
+    } = match mutate_state(|state| prepare(&context, args.thread, args.message_id, state)) {
+        Ok(result) => result,
+        Err(error) => return Response::Error(error),
+    };
+
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, chat, bot_principal, thread, message_id, args).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success(_)), state));
+    response
+}
+
+pub(crate) struct PrepareResult {
+    pub chat: Chat,
+    pub bot_principal: Principal,
+    pub thread: Option<MessageIndex>,
+    pub message_id: MessageId,
+}
+
+pub(crate) fn prepare(
+    context: &BotAccessContext,
+    thread: Option<MessageIndex>,
+    message_id: Option<MessageId>,
+    state: &mut RuntimeState,
+) -> Result<PrepareResult, OCError> {
+    let Some(chat) = context.scope.chat(None) else {
+        return Err(OCErrorCode::InvalidBotActionScope.with_message("Channel not specified"));
+    };
+
+    let Some(bot) = state.data.bots.get(&context.bot_id) else {
+        return Err(OCErrorCode::BotNotFound.into());
+    };
+
+    Ok(PrepareResult {
+        chat,
+        bot_principal: bot.principal,
+        thread: context.scope.thread().or(thread),
+        message_id: message_id.unwrap_or_else(|| state.env.rng().r#gen::<u64>().into()),
+    })
+}
+
+async fn call_chat_canister(
+    context: BotAccessContext,
+    chat: Chat,
+    bot_principal: Principal,
+    thread: Option<MessageIndex>,
+    message_id: MessageId,
+    args: Args,
+) -> Response {
+    use Response::*;
+
+    match chat {
+        Chat::Direct(_) => Error(OCErrorCode::InvalidBotActionScope.with_message("Direct chats not supported")),
+        Chat::Channel(community_id, channel_id) => {
+            let c2c_args = community_canister::c2c_bot_send_crypto::Args {
+                bot_id: context.bot_id,
+                bot_principal,
+                channel_id,
+                initiator: context.initiator,
+                thread_root_message_index: thread,
+                message_id,
+                recipient: args.recipient,
+                ledger: args.ledger,
+                token_symbol: args.token_symbol,
+                amount: args.amount,
+                fee: args.fee,
+                caption: args.caption,
+                bot_name: context.bot_name,
+                block_level_markdown: args.block_level_markdown,
+            };
+            match retry_if_outcome_unknown(|| {
+                community_canister_c2c_client::c2c_bot_send_crypto(community_id.into(), &c2c_args)
+            })
+            .await
+            {
+                Ok(community_canister::send_message::Response::Success(result)) => Success(SuccessResult {
+                    message_id,
+                    event_index: result.event_index,
+                    message_index: result.message_index,
+                    timestamp: result.timestamp,
+                    expires_at: result.expires_at,
+                }),
+                Ok(community_canister::send_message::Response::Error(error)) => Error(error),
+                Err(error) => Error(error.into()),
+            }
+        }
+        Chat::Group(chat_id) => {
+            let c2c_args = group_canister::c2c_bot_send_crypto::Args {
+                bot_id: context.bot_id,
+                bot_principal,
+                initiator: context.initiator,
+                thread_root_message_index: thread,
+                message_id,
+                recipient: args.recipient,
+                ledger: args.ledger,
+                token_symbol: args.token_symbol,
+                amount: args.amount,
+                fee: args.fee,
+                caption: args.caption,
+                bot_name: context.bot_name,
+                block_level_markdown: args.block_level_markdown,
+            };
+            match retry_if_outcome_unknown(|| group_canister_c2c_client::c2c_bot_send_crypto(chat_id.into(), &c2c_args)).await {
+                Ok(group_canister::send_message_v2::Response::Success(result)) => Success(SuccessResult {
+                    message_id,
+                    event_index: result.event_index,
+                    message_index: result.message_index,
+                    timestamp: result.timestamp,
+                    expires_at: result.expires_at,
+                }),
+                Ok(group_canister::send_message_v2::Response::Error(error)) => Error(error),
+                Err(error) => Error(error.into()),
+            }
+        }
+    }
+}
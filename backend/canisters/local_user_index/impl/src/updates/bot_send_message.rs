@@ -1,15 +1,16 @@
 use crate::{
     RuntimeState,
-    bots::{BotAccessContext, extract_access_context_from_chat_context},
+    bots::{BotAccessContext, extract_access_context_from_chat_context, record_bot_action},
     mutate_state,
 };
 use canister_api_macros::update;
+use canister_client::retry_if_outcome_unknown;
 use local_user_index_canister::bot_send_message::*;
 use oc_error_codes::{OCError, OCErrorCode};
 use rand::Rng;
 use types::{
-    BotActionScope, BotInitiator, BotMessageContent, ChannelId, Chat, ChatId, CommunityId, EventIndex, MessageId, MessageIndex,
-    UserId,
+    BotActionScope, BotInitiator, BotMessageComponent, BotMessageContent, ChannelId, Chat, ChatId, CommunityId, EventIndex,
+    MessageId, MessageIndex, UserId,
 };
 
 #[update(candid = true, json = true, msgpack = true)]
@@ -19,7 +20,9 @@ async fn bot_send_message(args: Args) -> Response {
         Err(_) => return Response::Error(OCErrorCode::BotNotAuthenticated.into()),
     };
 
-    bot_send_message_impl(
+    let invocation = context.command_invocation();
+
+    let response = bot_send_message_impl(
         context,
         None,
         args.thread,
@@ -28,8 +31,12 @@ async fn bot_send_message(args: Args) -> Response {
         args.content,
         args.block_level_markdown,
         args.finalised,
+        args.components.unwrap_or_default(),
     )
-    .await
+    .await;
+
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success(_)), state));
+    response
 }
 
 struct MessageAccessContext {
@@ -52,6 +59,7 @@ async fn bot_send_message_impl(
     content: BotMessageContent,
     block_level_markdown: bool,
     finalised: bool,
+    components: Vec<BotMessageComponent>,
 ) -> Response {
     let context = match mutate_state(|state| extract_message_access_context(context, channel_id, thread, message_id, state)) {
         Ok(context) => context,
@@ -59,6 +67,9 @@ async fn bot_send_message_impl(
     };
 
     match context.chat {
+        Chat::Direct(_) if !components.is_empty() => {
+            Response::Error(OCErrorCode::InvalidRequest.with_message("Message components are not supported in direct chats"))
+        }
         Chat::Direct(chat_id) => {
             send_message_to_user(
                 context.bot_id,
@@ -87,6 +98,7 @@ async fn bot_send_message_impl(
                 content,
                 block_level_markdown,
                 finalised,
+                components,
             )
             .await
         }
@@ -103,6 +115,7 @@ async fn bot_send_message_impl(
                 content,
                 block_level_markdown,
                 finalised,
+                components,
             )
             .await
         }
@@ -119,7 +132,9 @@ fn extract_message_access_context(
     let (chat, thread, message_id, user_message_id) = match context.scope {
         BotActionScope::Chat(details) => {
             if let Some(message_id) = message_id {
-                if matches!(context.initiator, BotInitiator::Command(_)) && message_id != details.message_id {
+                if matches!(context.initiator, BotInitiator::Command(_) | BotInitiator::Component(_))
+                    && message_id != details.message_id
+                {
                     return Err(
                         OCErrorCode::InvalidRequest.with_message("Message id is already specified in the command access token")
                     );
@@ -162,25 +177,25 @@ async fn send_message_to_channel(
     content: BotMessageContent,
     block_level_markdown: bool,
     finalised: bool,
+    components: Vec<BotMessageComponent>,
 ) -> Response {
     use Response::*;
 
-    match community_canister_c2c_client::c2c_bot_send_message(
-        community_id.into(),
-        &community_canister::c2c_bot_send_message::Args {
-            bot_id,
-            initiator,
-            channel_id,
-            thread_root_message_index,
-            message_id,
-            replies_to,
-            content,
-            bot_name,
-            block_level_markdown,
-            finalised,
-        },
-    )
-    .await
+    let c2c_args = community_canister::c2c_bot_send_message::Args {
+        bot_id,
+        initiator,
+        channel_id,
+        thread_root_message_index,
+        message_id,
+        replies_to,
+        content,
+        bot_name,
+        block_level_markdown,
+        finalised,
+        components,
+    };
+
+    match retry_if_outcome_unknown(|| community_canister_c2c_client::c2c_bot_send_message(community_id.into(), &c2c_args)).await
     {
         Ok(response) => match response {
             community_canister::c2c_bot_send_message::Response::Success(result) => Success(SuccessResult {
@@ -208,25 +223,24 @@ async fn send_message_to_group(
     content: BotMessageContent,
     block_level_markdown: bool,
     finalised: bool,
+    components: Vec<BotMessageComponent>,
 ) -> Response {
     use Response::*;
 
-    match group_canister_c2c_client::c2c_bot_send_message(
-        chat_id.into(),
-        &group_canister::c2c_bot_send_message::Args {
-            bot_id,
-            initiator,
-            thread_root_message_index,
-            message_id,
-            replies_to,
-            content,
-            bot_name,
-            block_level_markdown,
-            finalised,
-        },
-    )
-    .await
-    {
+    let c2c_args = group_canister::c2c_bot_send_message::Args {
+        bot_id,
+        initiator,
+        thread_root_message_index,
+        message_id,
+        replies_to,
+        content,
+        bot_name,
+        block_level_markdown,
+        finalised,
+        components,
+    };
+
+    match retry_if_outcome_unknown(|| group_canister_c2c_client::c2c_bot_send_message(chat_id.into(), &c2c_args)).await {
         Ok(response) => match response {
             group_canister::c2c_bot_send_message::Response::Success(result) => Success(SuccessResult {
                 message_id,
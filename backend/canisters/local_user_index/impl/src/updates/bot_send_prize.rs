@@ -0,0 +1,112 @@
+use crate::bots::{BotAccessContext, extract_access_context_from_chat_context, record_bot_action};
+use crate::mutate_state;
+use crate::updates::bot_send_crypto::{PrepareResult, prepare};
+use candid::Principal;
+use canister_api_macros::update;
+use canister_client::retry_if_outcome_unknown;
+use local_user_index_canister::bot_send_message::SuccessResult;
+use local_user_index_canister::bot_send_prize::*;
+use oc_error_codes::OCErrorCode;
+use types::{Chat, MessageId};
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_send_prize(args: Args) -> Response {
+    let context = match mutate_state(|state| extract_access_context_from_chat_context(args.chat_context.clone(), state)) {
+        Ok(context) => context,
+        Err(_) => return Response::Error(OCErrorCode::BotNotAuthenticated.into()),
+    };
+
+    let PrepareResult {
+        chat,
+        bot_principal,
+        message_id,
+        ..
+    } = match mutate_state(|state| prepare(&context, None, args.message_id, state)) {
+        Ok(result) => result,
+        Err(error) => return Response::Error(error),
+    };
+
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, chat, bot_principal, message_id, args).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success(_)), state));
+    response
+}
+
+async fn call_chat_canister(
+    context: BotAccessContext,
+    chat: Chat,
+    bot_principal: Principal,
+    message_id: MessageId,
+    args: Args,
+) -> Response {
+    use Response::*;
+
+    match chat {
+        Chat::Direct(_) => Error(OCErrorCode::InvalidBotActionScope.with_message("Direct chats not supported")),
+        Chat::Channel(community_id, channel_id) => {
+            let c2c_args = community_canister::c2c_bot_send_prize::Args {
+                bot_id: context.bot_id,
+                bot_principal,
+                channel_id,
+                initiator: context.initiator,
+                message_id,
+                ledger: args.ledger,
+                token_symbol: args.token_symbol,
+                prizes: args.prizes,
+                fee: args.fee,
+                end_date: args.end_date,
+                caption: args.caption,
+                diamond_only: args.diamond_only,
+                lifetime_diamond_only: args.lifetime_diamond_only,
+                unique_person_only: args.unique_person_only,
+                streak_only: args.streak_only,
+                requires_captcha: args.requires_captcha,
+                bot_name: context.bot_name,
+            };
+            match retry_if_outcome_unknown(|| community_canister_c2c_client::c2c_bot_send_prize(community_id.into(), &c2c_args))
+                .await
+            {
+                Ok(community_canister::send_message::Response::Success(result)) => Success(SuccessResult {
+                    message_id,
+                    event_index: result.event_index,
+                    message_index: result.message_index,
+                    timestamp: result.timestamp,
+                    expires_at: result.expires_at,
+                }),
+                Ok(community_canister::send_message::Response::Error(error)) => Error(error),
+                Err(error) => Error(error.into()),
+            }
+        }
+        Chat::Group(chat_id) => {
+            let c2c_args = group_canister::c2c_bot_send_prize::Args {
+                bot_id: context.bot_id,
+                bot_principal,
+                initiator: context.initiator,
+                message_id,
+                ledger: args.ledger,
+                token_symbol: args.token_symbol,
+                prizes: args.prizes,
+                fee: args.fee,
+                end_date: args.end_date,
+                caption: args.caption,
+                diamond_only: args.diamond_only,
+                lifetime_diamond_only: args.lifetime_diamond_only,
+                unique_person_only: args.unique_person_only,
+                streak_only: args.streak_only,
+                requires_captcha: args.requires_captcha,
+                bot_name: context.bot_name,
+            };
+            match retry_if_outcome_unknown(|| group_canister_c2c_client::c2c_bot_send_prize(chat_id.into(), &c2c_args)).await {
+                Ok(group_canister::send_message_v2::Response::Success(result)) => Success(SuccessResult {
+                    message_id,
+                    event_index: result.event_index,
+                    message_index: result.message_index,
+                    timestamp: result.timestamp,
+                    expires_at: result.expires_at,
+                }),
+                Ok(group_canister::send_message_v2::Response::Error(error)) => Error(error),
+                Err(error) => Error(error.into()),
+            }
+        }
+    }
+}
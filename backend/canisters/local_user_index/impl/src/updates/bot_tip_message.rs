@@ -0,0 +1,83 @@
+use crate::bots::{BotAccessContext, extract_access_context_from_chat_context, record_bot_action};
+use crate::mutate_state;
+use crate::updates::bot_send_crypto::{PrepareResult, prepare};
+use candid::Principal;
+use canister_api_macros::update;
+use canister_client::retry_if_outcome_unknown;
+use local_user_index_canister::bot_tip_message::*;
+use oc_error_codes::OCErrorCode;
+use types::{Chat, MessageIndex};
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_tip_message(args: Args) -> Response {
+    let context = match mutate_state(|state| extract_access_context_from_chat_context(args.chat_context.clone(), state)) {
+        Ok(context) => context,
+        Err(_) => return OCErrorCode::BotNotAuthenticated.into(),
+    };
+
+    let PrepareResult {
+        chat,
+        bot_principal,
+        thread,
+        ..
+    } = match mutate_state(|state| prepare(&context, args.thread, Some(args.message_id), state)) {
+        Ok(result) => result,
+        Err(error) => return error.into(),
+    };
+
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, chat, bot_principal, thread, args).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success), state));
+    response
+}
+
+async fn call_chat_canister(
+    context: BotAccessContext,
+    chat: Chat,
+    bot_principal: Principal,
+    thread: Option<MessageIndex>,
+    args: Args,
+) -> Response {
+    match chat {
+        Chat::Direct(_) => OCErrorCode::InvalidBotActionScope
+            .with_message("Direct chats not supported")
+            .into(),
+        Chat::Channel(community_id, channel_id) => {
+            let c2c_args = community_canister::c2c_bot_tip_message::Args {
+                bot_id: context.bot_id,
+                bot_principal,
+                channel_id,
+                initiator: context.initiator,
+                thread_root_message_index: thread,
+                message_id: args.message_id,
+                ledger: args.ledger,
+                token_symbol: args.token_symbol,
+                amount: args.amount,
+                fee: args.fee,
+                decimals: args.decimals,
+                bot_name: context.bot_name,
+            };
+            retry_if_outcome_unknown(|| community_canister_c2c_client::c2c_bot_tip_message(community_id.into(), &c2c_args))
+                .await
+                .into()
+        }
+        Chat::Group(chat_id) => {
+            let c2c_args = group_canister::c2c_bot_tip_message::Args {
+                bot_id: context.bot_id,
+                bot_principal,
+                initiator: context.initiator,
+                thread_root_message_index: thread,
+                message_id: args.message_id,
+                ledger: args.ledger,
+                token_symbol: args.token_symbol,
+                amount: args.amount,
+                fee: args.fee,
+                decimals: args.decimals,
+                bot_name: context.bot_name,
+            };
+            retry_if_outcome_unknown(|| group_canister_c2c_client::c2c_bot_tip_message(chat_id.into(), &c2c_args))
+                .await
+                .into()
+        }
+    }
+}
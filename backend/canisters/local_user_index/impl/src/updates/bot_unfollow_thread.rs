@@ -0,0 +1,60 @@
+use crate::{
+    bots::{BotAccessContext, extract_access_context_from_chat_context, record_bot_action},
+    mutate_state,
+};
+use canister_api_macros::update;
+use local_user_index_canister::bot_unfollow_thread::*;
+use oc_error_codes::OCErrorCode;
+use types::{Chat, MessageIndex};
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_unfollow_thread(args: Args) -> Response {
+    let context = match mutate_state(|state| extract_access_context_from_chat_context(args.chat_context, state)) {
+        Ok(context) => context,
+        Err(_) => return OCErrorCode::BotNotAuthenticated.into(),
+    };
+
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, args.thread_root_message_index).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success), state));
+    response
+}
+
+async fn call_chat_canister(context: BotAccessContext, thread_root_message_index: Option<MessageIndex>) -> Response {
+    let Some(chat) = context.scope.chat(None) else {
+        return OCErrorCode::InvalidBotActionScope
+            .with_message("Channel not specified")
+            .into();
+    };
+
+    let Some(thread_root_message_index) = thread_root_message_index.or(context.scope.thread()) else {
+        return OCErrorCode::InvalidRequest.with_message("Thread not specified").into();
+    };
+
+    match chat {
+        Chat::Direct(_) => OCErrorCode::InvalidBotActionScope
+            .with_message("Direct chats not supported")
+            .into(),
+        Chat::Channel(community_id, channel_id) => community_canister_c2c_client::c2c_bot_unfollow_thread(
+            community_id.into(),
+            &community_canister::c2c_bot_unfollow_thread::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                channel_id,
+                thread_root_message_index,
+            },
+        )
+        .await
+        .into(),
+        Chat::Group(chat_id) => group_canister_c2c_client::c2c_bot_unfollow_thread(
+            chat_id.into(),
+            &group_canister::c2c_bot_unfollow_thread::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                thread_root_message_index,
+            },
+        )
+        .await
+        .into(),
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    bots::{BotAccessContext, extract_access_context_from_chat_context, record_bot_action},
+    mutate_state,
+};
+use canister_api_macros::update;
+use local_user_index_canister::bot_update_chat_details::*;
+use oc_error_codes::OCErrorCode;
+use types::Chat;
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_update_chat_details(args: Args) -> Response {
+    let context = match mutate_state(|state| extract_access_context_from_chat_context(args.chat_context.clone(), state)) {
+        Ok(context) => context,
+        Err(_) => return OCErrorCode::BotNotAuthenticated.into(),
+    };
+
+    let invocation = context.command_invocation();
+    let response = call_chat_canister(context, args).await;
+    mutate_state(|state| record_bot_action(invocation, matches!(response, Response::Success), state));
+    response
+}
+
+async fn call_chat_canister(context: BotAccessContext, args: Args) -> Response {
+    let Some(chat) = context.scope.chat(None) else {
+        return OCErrorCode::InvalidBotActionScope
+            .with_message("Channel not specified")
+            .into();
+    };
+
+    match chat {
+        Chat::Direct(_) => OCErrorCode::InvalidBotActionScope
+            .with_message("Direct chats not supported")
+            .into(),
+        Chat::Channel(community_id, channel_id) => community_canister_c2c_client::c2c_bot_update_chat_details(
+            community_id.into(),
+            &community_canister::c2c_bot_update_chat_details::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                channel_id,
+                name: args.name,
+                description: args.description,
+                rules: args.rules,
+                avatar: args.avatar,
+            },
+        )
+        .await
+        .into(),
+        Chat::Group(chat_id) => group_canister_c2c_client::c2c_bot_update_chat_details(
+            chat_id.into(),
+            &group_canister::c2c_bot_update_chat_details::Args {
+                bot_id: context.bot_id,
+                initiator: context.initiator,
+                name: args.name,
+                description: args.description,
+                rules: args.rules,
+                avatar: args.avatar,
+            },
+        )
+        .await
+        .into(),
+    }
+}
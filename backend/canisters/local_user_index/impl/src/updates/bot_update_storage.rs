@@ -0,0 +1,35 @@
+use crate::read_state;
+use canister_api_macros::update;
+use local_user_index_canister::bot_update_storage::*;
+use oc_error_codes::OCErrorCode;
+use types::CommunityOrGroup;
+
+#[update(candid = true, json = true, msgpack = true)]
+async fn bot_update_storage(args: Args) -> Response {
+    let Some(bot_id) = read_state(|state| state.data.bots.get_by_caller(&state.env.caller()).map(|bot| bot.bot_id)) else {
+        return OCErrorCode::BotNotAuthenticated.into();
+    };
+
+    match args.location {
+        CommunityOrGroup::Group(chat_id) => group_canister_c2c_client::c2c_bot_update_storage(
+            chat_id.into(),
+            &group_canister::c2c_bot_update_storage::Args {
+                bot_id,
+                set: args.set,
+                remove: args.remove,
+            },
+        )
+        .await
+        .into(),
+        CommunityOrGroup::Community(community_id) => community_canister_c2c_client::c2c_bot_update_storage(
+            community_id.into(),
+            &community_canister::c2c_bot_update_storage::Args {
+                bot_id,
+                set: args.set,
+                remove: args.remove,
+            },
+        )
+        .await
+        .into(),
+    }
+}
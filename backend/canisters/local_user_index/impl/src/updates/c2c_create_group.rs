@@ -112,6 +112,7 @@ fn prepare(args: Args, state: &mut RuntimeState) -> Result<PrepareOk, Response>
         proposals_bot_user_id: state.data.proposals_bot_canister_id.into(),
         escrow_canister_id: state.data.escrow_canister_id,
         internet_identity_canister_id: state.data.internet_identity_canister_id,
+        identity_canister_id: state.data.identity_canister_id,
         avatar: args.avatar,
         gate_config: args.gate_config,
         video_call_operators: state.data.video_call_operators.clone(),
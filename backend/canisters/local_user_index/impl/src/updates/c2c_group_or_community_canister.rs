@@ -1,5 +1,5 @@
 use crate::guards::{caller_is_local_community_canister, caller_is_local_group_canister};
-use crate::{RuntimeState, mutate_state};
+use crate::{RuntimeState, UserIndexEvent, mutate_state};
 use candid::Principal;
 use canister_api_macros::update;
 use canister_time::now_millis;
@@ -7,7 +7,7 @@ use canister_tracing_macros::trace;
 use local_user_index_canister::GroupEvent;
 use local_user_index_canister::c2c_group_canister::*;
 use std::cell::LazyCell;
-use types::TimestampMillis;
+use types::{BotInstallationLocation, TimestampMillis};
 
 #[update(guard = "caller_is_local_group_canister", msgpack = true)]
 #[trace]
@@ -55,5 +55,20 @@ fn handle_event<F: FnOnce() -> TimestampMillis>(
         }
         GroupEvent::EventStoreEvent(event) => state.data.event_store_client.push(event),
         GroupEvent::Notification(notification) => state.data.handle_notification(*notification, state.env.canister_id(), **now),
+        GroupEvent::BotPermissionsUpdated(bot_id, granted_permissions) => {
+            let location = if is_group {
+                BotInstallationLocation::Group(caller.into())
+            } else {
+                BotInstallationLocation::Community(caller.into())
+            };
+            state.push_event_to_user_index(
+                UserIndexEvent::BotPermissionsUpdated(Box::new(user_index_canister::BotPermissionsUpdated {
+                    bot_id,
+                    location,
+                    granted_permissions,
+                })),
+                **now,
+            );
+        }
     }
 }
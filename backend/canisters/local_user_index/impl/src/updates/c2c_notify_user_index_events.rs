@@ -308,6 +308,15 @@ fn handle_event<F: FnOnce() -> TimestampMillis>(
         UserIndexEvent::UserUnblocked(user_id, unblocked) => {
             state.data.blocked_users.remove(&(user_id, unblocked));
         }
+        UserIndexEvent::RegisteredLedgersUpdated(ledgers) => {
+            state.data.registered_ledgers = ledgers.into_iter().collect();
+        }
+        UserIndexEvent::BotPinnedVersionsUpdated(ev) => {
+            state.data.bots.set_pinned_versions(ev.bot_id, ev.pinned_versions);
+        }
+        UserIndexEvent::NotificationPreferencesUpdated(user_id, preferences) => {
+            state.data.notification_digests.set_preferences(user_id, *preferences);
+        }
     }
 }
 
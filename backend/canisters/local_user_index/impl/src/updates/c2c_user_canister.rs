@@ -93,5 +93,14 @@ fn handle_event<F: FnOnce() -> TimestampMillis>(
         UserEvent::Notification(notification) => {
             state.data.handle_notification(*notification, state.env.canister_id(), **now);
         }
+        UserEvent::NotificationPreferencesUpdated(preferences) => {
+            // Group and community notifications are handled by the LocalUserIndex of the group or community, so the
+            // preferences are shared with every LocalUserIndex via the UserIndex
+            state
+                .data
+                .notification_digests
+                .set_preferences(user_id, (*preferences).clone());
+            state.push_event_to_user_index(UserIndexEvent::NotificationPreferencesUpdated(user_id, preferences), **now);
+        }
     }
 }
@@ -2,21 +2,27 @@ use crate::guards::caller_is_openchat_user;
 use crate::{RuntimeState, mutate_state, read_state};
 use candid::Principal;
 use canister_api_macros::update;
+use canister_client::retry_if_outcome_unknown;
 use canister_tracing_macros::trace;
 use local_user_index_canister::invite_users_to_group::{Response::*, *};
-use types::{ChatId, MessageContent, TextContent, User, UserId};
+use types::{ChatId, MessageContent, TextContent, User, UserId, idempotency_key};
 
 #[update(guard = "caller_is_openchat_user", candid = true, msgpack = true)]
 #[trace]
 async fn invite_users_to_group(args: Args) -> Response {
-    let PrepareResult { invited_by, users } = read_state(|state| prepare(&args, state));
+    let PrepareResult {
+        invited_by,
+        users,
+        idempotency_key,
+    } = read_state(|state| prepare(&args, state));
 
     let c2c_args = group_canister::c2c_invite_users::Args {
         caller: invited_by,
         users,
+        idempotency_key: Some(idempotency_key),
     };
 
-    match group_canister_c2c_client::c2c_invite_users(args.group_id.into(), &c2c_args).await {
+    match retry_if_outcome_unknown(|| group_canister_c2c_client::c2c_invite_users(args.group_id.into(), &c2c_args)).await {
         Ok(response) => match response {
             group_canister::c2c_invite_users::Response::Success(s) => {
                 mutate_state(|state| {
@@ -40,6 +46,7 @@ async fn invite_users_to_group(args: Args) -> Response {
 struct PrepareResult {
     invited_by: UserId,
     users: Vec<(UserId, Principal)>,
+    idempotency_key: u64,
 }
 
 fn prepare(args: &Args, state: &RuntimeState) -> PrepareResult {
@@ -50,7 +57,14 @@ fn prepare(args: &Args, state: &RuntimeState) -> PrepareResult {
         .filter_map(|user_id| state.data.global_users.get(&(*user_id).into()))
         .map(|user| (user.user_id, user.principal))
         .collect();
-    PrepareResult { invited_by, users }
+    // Identifies this request so that if the call is retried the invites are only applied once
+    let idempotency_key = idempotency_key((invited_by, args.group_id, state.env.now_nanos()));
+
+    PrepareResult {
+        invited_by,
+        users,
+        idempotency_key,
+    }
 }
 
 fn commit(
@@ -1,8 +1,10 @@
 use crate::guards::caller_is_openchat_user;
-use crate::mutate_state;
+use crate::{mutate_state, read_state};
 use canister_api_macros::update;
+use canister_client::retry_if_outcome_unknown;
 use canister_tracing_macros::trace;
 use local_user_index_canister::join_community::{Response::*, *};
+use types::idempotency_key;
 
 #[update(guard = "caller_is_openchat_user", candid = true, msgpack = true)]
 #[trace]
@@ -23,8 +25,13 @@ async fn join_community(args: Args) -> Response {
         diamond_membership_expires_at: user_details.diamond_membership_expires_at,
         verified_credential_args: args.verified_credential_args,
         unique_person_proof: user_details.unique_person_proof.clone(),
+        idempotency_key: Some(read_state(|state| {
+            idempotency_key((user_details.user_id, args.community_id, state.env.now_nanos()))
+        })),
     };
-    match community_canister_c2c_client::c2c_join_community(args.community_id.into(), &c2c_args).await {
+    match retry_if_outcome_unknown(|| community_canister_c2c_client::c2c_join_community(args.community_id.into(), &c2c_args))
+        .await
+    {
         Ok(response) => match response {
             community_canister::c2c_join_community::Response::Success(s) => {
                 if !is_bot {
@@ -1,9 +1,10 @@
 use crate::guards::caller_is_openchat_user;
-use crate::{RuntimeState, UserEvent, UserIndexEvent, mutate_state};
+use crate::{RuntimeState, UserEvent, UserIndexEvent, mutate_state, read_state};
 use canister_api_macros::update;
+use canister_client::retry_if_outcome_unknown;
 use canister_tracing_macros::trace;
 use local_user_index_canister::join_group::{Response::*, *};
-use types::{ChatId, MessageIndex, TimestampMillis, UserId};
+use types::{ChatId, MessageIndex, TimestampMillis, UserId, idempotency_key};
 
 #[update(guard = "caller_is_openchat_user", candid = true, msgpack = true)]
 #[trace]
@@ -20,8 +21,11 @@ async fn join_group(args: Args) -> Response {
         diamond_membership_expires_at: user_details.diamond_membership_expires_at,
         verified_credential_args: args.verified_credential_args.clone(),
         unique_person_proof: user_details.unique_person_proof.clone(),
+        idempotency_key: Some(read_state(|state| {
+            idempotency_key((user_details.user_id, args.chat_id, state.env.now_nanos()))
+        })),
     };
-    match group_canister_c2c_client::c2c_join_group(args.chat_id.into(), &c2c_args).await {
+    match retry_if_outcome_unknown(|| group_canister_c2c_client::c2c_join_group(args.chat_id.into(), &c2c_args)).await {
         Ok(response) => match response {
             group_canister::c2c_join_group::Response::Success(s)
             | group_canister::c2c_join_group::Response::AlreadyInGroupV2(s) => {
@@ -0,0 +1,27 @@
+use crate::bots::{command_invocation_from_access_token, record_bot_command_received};
+use crate::guards::caller_is_openchat_user;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use local_user_index_canister::record_bot_command_invocation::*;
+use oc_error_codes::OCErrorCode;
+
+#[update(guard = "caller_is_openchat_user", candid = true, msgpack = true)]
+#[trace]
+fn record_bot_command_invocation(args: Args) -> Response {
+    mutate_state(|state| record_bot_command_invocation_impl(args, state))
+}
+
+fn record_bot_command_invocation_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let (initiator, invocation) = match command_invocation_from_access_token(&args.access_token, state) {
+        Ok(result) => result,
+        Err(error) => return OCErrorCode::InvalidRequest.with_message(error).into(),
+    };
+
+    if initiator != state.calling_user_id() {
+        return OCErrorCode::InitiatorNotAuthorized.into();
+    }
+
+    record_bot_command_received(invocation, state);
+    Response::Success
+}
@@ -7,6 +7,9 @@ use std::collections::BTreeMap;
 const UPGRADES: MemoryId = MemoryId::new(0);
 const ORDERS_LOG_INDEX: MemoryId = MemoryId::new(1);
 const ORDERS_LOG_DATA: MemoryId = MemoryId::new(2);
+const CANISTER_LOGS: MemoryId = MemoryId::new(3);
+const INSTRUCTION_COUNTS_INDEX: MemoryId = MemoryId::new(4);
+const INSTRUCTION_COUNTS_DATA: MemoryId = MemoryId::new(5);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -27,8 +30,20 @@ pub fn get_orders_log_data_memory() -> Memory {
     get_memory(ORDERS_LOG_DATA)
 }
 
+pub fn get_canister_logs_memory() -> Memory {
+    get_memory(CANISTER_LOGS)
+}
+
+pub fn get_instruction_counts_index_memory() -> Memory {
+    get_memory(INSTRUCTION_COUNTS_INDEX)
+}
+
+pub fn get_instruction_counts_data_memory() -> Memory {
+    get_memory(INSTRUCTION_COUNTS_DATA)
+}
+
 pub fn memory_sizes() -> BTreeMap<u8, u64> {
-    (0u8..=2).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
+    (0u8..=5).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
 }
 
 fn get_memory(id: MemoryId) -> Memory {
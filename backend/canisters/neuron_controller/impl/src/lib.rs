@@ -1,8 +1,11 @@
 use crate::ecdsa::{CanisterEcdsaRequest, get_key_id};
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
 use constants::{MINUTE_IN_MS, NANOS_PER_MILLISECOND};
+use http_request::{OpenMetrics, OpenMetricsEncoder};
 use ic_transport_types::EnvelopeContent;
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use k256::PublicKey;
 use k256::pkcs8::EncodePublicKey;
 use nns_governance_canister::types::Neuron;
@@ -97,6 +100,7 @@ impl RuntimeState {
                 .collect(),
             disbursed_neurons: self.data.neurons.disbursed_neurons.clone(),
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
             canister_ids: CanisterIds {
                 nns_governance_canister: self.data.nns_governance_canister_id,
                 nns_ledger_canister: self.data.nns_ledger_canister_id,
@@ -118,6 +122,12 @@ struct Data {
     pub neurons: Neurons,
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -139,6 +149,7 @@ impl Data {
             neurons: Neurons::default(),
             rng_seed: [0; 32],
             test_mode,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 
@@ -153,6 +164,15 @@ impl Data {
     pub fn get_principal(&self) -> Principal {
         Principal::self_authenticating(self.get_public_key_der())
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -171,9 +191,17 @@ pub struct Metrics {
     pub spawning_neurons: Vec<u64>,
     pub disbursed_neurons: Vec<u64>,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct CanisterIds {
     pub nns_governance_canister: CanisterId,
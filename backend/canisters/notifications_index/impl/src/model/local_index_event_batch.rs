@@ -1,5 +1,5 @@
 use notifications_index_canister::NotificationsIndexEvent;
-use timer_job_queues::{TimerJobItem, grouped_timer_job_batch};
+use timer_job_queues::{TimerJobItem, TimerJobOutcome, grouped_timer_job_batch};
 use types::{CanisterId, IdempotentEnvelope};
 use utils::canister::should_retry_failed_c2c_call;
 
@@ -11,7 +11,7 @@ grouped_timer_job_batch!(
 );
 
 impl TimerJobItem for LocalIndexEventBatch {
-    async fn process(&self) -> Result<(), bool> {
+    async fn process(&self) -> TimerJobOutcome {
         let args = local_user_index_canister::c2c_notifications_index::Args {
             events: self.items.clone(),
         };
@@ -19,10 +19,10 @@ impl TimerJobItem for LocalIndexEventBatch {
         let response = local_user_index_canister_c2c_client::c2c_notifications_index(self.key, &args).await;
 
         match response {
-            Ok(_) => Ok(()),
+            Ok(_) => TimerJobOutcome::Success,
             Err(error) => {
                 let retry = should_retry_failed_c2c_call(error.reject_code(), error.message());
-                Err(retry)
+                TimerJobOutcome::retry_if(retry)
             }
         }
     }
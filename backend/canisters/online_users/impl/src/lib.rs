@@ -1,15 +1,18 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::airdrop_bot_event_batch::AirdropBotEventBatch;
 use crate::model::last_online_dates::LastOnlineDates;
 use crate::model::user_online_minutes::UserOnlineMinutes;
 use canister_state_macros::canister_state;
 use event_store_producer::{EventStoreClient, EventStoreClientBuilder, EventStoreClientInfo};
 use event_store_producer_cdk_runtime::CdkRuntime;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use principal_to_user_id_map::PrincipalToUserIdMap;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::time::Duration;
-use timer_job_queues::BatchedTimerJobQueue;
+use timer_job_queues::{BatchedTimerJobQueue, TimerJobQueueMetrics};
 use types::{BuildVersion, CanisterId, Cycles, TimestampMillis, Timestamped};
 use utils::env::Environment;
 
@@ -58,6 +61,11 @@ impl RuntimeState {
             sync_online_minutes_to_airdrop_bot_increment: self.data.sync_online_minutes_to_airdrop_bot_increment,
             event_store_client_info,
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
+            timer_job_queues: BTreeMap::from([(
+                "airdrop_bot_event_sync_queue".to_string(),
+                self.data.airdrop_bot_event_sync_queue.metrics(),
+            )]),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 airdrop_bot: self.data.airdrop_bot_canister_id,
@@ -83,6 +91,12 @@ struct Data {
     pub sync_online_minutes_to_airdrop_bot_increment: u16,
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -110,8 +124,18 @@ impl Data {
             sync_online_minutes_to_airdrop_bot_increment,
             rng_seed: [0; 32],
             test_mode,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -127,9 +151,18 @@ pub struct Metrics {
     pub sync_online_minutes_to_airdrop_bot_increment: u16,
     pub event_store_client_info: EventStoreClientInfo,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
+    pub timer_job_queues: BTreeMap<String, TimerJobQueueMetrics>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct ActiveUsers {
     timestamp: TimestampMillis,
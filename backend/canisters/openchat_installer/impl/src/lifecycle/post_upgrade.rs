@@ -1,10 +1,11 @@
 use crate::lifecycle::{init_env, init_state};
-use crate::memory::get_upgrades_memory;
+use crate::memory::{get_canister_logs_memory, get_upgrades_memory};
 use crate::{Data, read_state};
 use canister_logger::LogEntry;
 use canister_tracing_macros::trace;
 use ic_cdk::management_canister::{CanisterSettings, LogVisibility, UpdateSettingsArgs};
 use ic_cdk::post_upgrade;
+use instruction_counts_log::InstructionCountFunctionId;
 use openchat_installer_canister::post_upgrade::Args;
 use stable_memory::get_reader;
 use std::time::Duration;
@@ -20,7 +21,7 @@ fn post_upgrade(args: Args) {
     let (data, errors, logs, traces): (Data, Vec<LogEntry>, Vec<LogEntry>, Vec<LogEntry>) =
         msgpack::deserialize(reader).unwrap();
 
-    canister_logger::init_with_logs(data.test_mode, errors, logs, traces);
+    canister_logger::init_with_logs(data.test_mode, get_canister_logs_memory(), errors, logs, traces);
 
     let env = init_env(data.rng_seed);
     init_cycles_dispenser_client(data.cycles_dispenser_canister_id, data.test_mode);
@@ -29,6 +30,13 @@ fn post_upgrade(args: Args) {
     let total_instructions = ic_cdk::api::call_context_instruction_counter();
     info!(version = %args.wasm_version, total_instructions, "Post-upgrade complete");
 
+    read_state(|state| {
+        let now = state.env.now();
+        state
+            .data
+            .record_instructions_count(InstructionCountFunctionId::PostUpgrade, now)
+    });
+
     ic_cdk_timers::set_timer(Duration::ZERO, || ic_cdk::futures::spawn(make_logs_public()));
 }
 
@@ -5,6 +5,9 @@ use ic_stable_structures::{
 use std::collections::BTreeMap;
 
 const UPGRADES: MemoryId = MemoryId::new(0);
+const CANISTER_LOGS: MemoryId = MemoryId::new(2);
+const INSTRUCTION_COUNTS_INDEX: MemoryId = MemoryId::new(3);
+const INSTRUCTION_COUNTS_DATA: MemoryId = MemoryId::new(4);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -17,8 +20,20 @@ pub fn get_upgrades_memory() -> Memory {
     get_memory(UPGRADES)
 }
 
+pub fn get_canister_logs_memory() -> Memory {
+    get_memory(CANISTER_LOGS)
+}
+
+pub fn get_instruction_counts_index_memory() -> Memory {
+    get_memory(INSTRUCTION_COUNTS_INDEX)
+}
+
+pub fn get_instruction_counts_data_memory() -> Memory {
+    get_memory(INSTRUCTION_COUNTS_DATA)
+}
+
 pub fn memory_sizes() -> BTreeMap<u8, u64> {
-    (0u8..=1).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
+    (0u8..=4).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
 }
 
 fn get_memory(id: MemoryId) -> Memory {
@@ -3,6 +3,7 @@ use registry_canister::*;
 
 // Queries
 generate_c2c_call!(c2c_nervous_systems);
+generate_c2c_call!(updates);
 
 // Updates
 generate_c2c_call!(c2c_set_submitting_proposals_enabled);
@@ -1,3 +1,4 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::nervous_systems::{NervousSystemMetrics, NervousSystems};
 use crate::model::subnets::Subnets;
 use crate::model::tokens::{TokenMetrics, Tokens};
@@ -5,6 +6,8 @@ use crate::timer_job_types::TimerJob;
 use candid::Principal;
 use canister_state_macros::canister_state;
 use canister_timer_jobs::TimerJobs;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use model::message_filters::MessageFilters;
 use registry_canister::subnets::Subnet;
 use registry_canister::{MessageFilterSummary, NervousSystemDetails};
@@ -66,6 +69,7 @@ impl RuntimeState {
             failed_sns_launches: self.data.failed_sns_launches.iter().copied().collect(),
             airdrop_config: self.data.airdrop_config.value.clone(),
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
             subnets: self.data.subnets.subnets().to_vec(),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
@@ -108,6 +112,12 @@ struct Data {
     timer_jobs: TimerJobs<TimerJob>,
     rng_seed: [u8; 32],
     test_mode: bool,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -150,6 +160,7 @@ impl Data {
             timer_jobs: TimerJobs::default(),
             rng_seed: [0; 32],
             test_mode,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 
@@ -197,6 +208,15 @@ impl Data {
             now,
         );
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[derive(Serialize)]
@@ -214,10 +234,18 @@ pub struct Metrics {
     pub failed_sns_launches: Vec<CanisterId>,
     pub airdrop_config: Option<AirdropConfig>,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
     pub subnets: Vec<Subnet>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize)]
 pub struct CanisterIds {
     pub user_index: CanisterId,
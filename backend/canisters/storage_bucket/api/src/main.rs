@@ -17,10 +17,10 @@ fn main() {
 
     generate_ts_method!(storage_bucket, file_info);
 
-    generate_ts_method!(storage_bucket, delete_file);
-    generate_ts_method!(storage_bucket, delete_files);
-    generate_ts_method!(storage_bucket, forward_file);
-    generate_ts_method!(storage_bucket, upload_chunk_v2);
+    generate_ts_method!(storage_bucket, delete_file, msgpack, json);
+    generate_ts_method!(storage_bucket, delete_files, msgpack, json);
+    generate_ts_method!(storage_bucket, forward_file, msgpack, json);
+    generate_ts_method!(storage_bucket, upload_chunk_v2, msgpack, json);
 
     candid::export_service!();
     std::print!("{}", __export_service());
@@ -2,7 +2,7 @@ use crate::model::users::FileStatusInternal;
 use crate::mutate_state;
 use candid::Deserialize;
 use serde::Serialize;
-use timer_job_queues::{TimerJobItem, timer_job_batch};
+use timer_job_queues::{TimerJobItem, TimerJobOutcome, timer_job_batch};
 use types::{CanisterId, FileAdded, FileRemoved};
 use utils::canister::should_retry_failed_c2c_call;
 
@@ -15,7 +15,7 @@ pub enum EventToSync {
 }
 
 impl TimerJobItem for IndexEventBatch {
-    async fn process(&self) -> Result<(), bool> {
+    async fn process(&self) -> TimerJobOutcome {
         let mut args = storage_index_canister::c2c_sync_bucket::Args {
             heap_memory_used: utils::memory::heap(),
             stable_memory_used: utils::memory::stable(),
@@ -61,11 +61,11 @@ impl TimerJobItem for IndexEventBatch {
                         }
                     }
                 });
-                Ok(())
+                TimerJobOutcome::Success
             }
             Err(error) => {
                 let retry = should_retry_failed_c2c_call(error.reject_code(), error.message());
-                Err(retry)
+                TimerJobOutcome::retry_if(retry)
             }
         }
     }
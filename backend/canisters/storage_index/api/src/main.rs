@@ -12,7 +12,7 @@ fn main() {
         std::fs::remove_dir_all(&directory).unwrap();
     }
 
-    generate_ts_method!(storage_index, allocated_bucket_v2);
+    generate_ts_method!(storage_index, allocated_bucket_v2, msgpack, json);
     generate_ts_method!(storage_index, can_forward);
     generate_ts_method!(storage_index, user);
 
@@ -1,13 +1,16 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::bucket_event_batch::{BucketEventBatch, EventToSync};
 use crate::model::buckets::{BucketRecord, Buckets};
 use crate::model::files::Files;
 use candid::{CandidType, Principal};
 use canister_state_macros::canister_state;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use storage_index_canister::init::CyclesDispenserConfig;
-use timer_job_queues::GroupedTimerJobQueue;
+use timer_job_queues::{GroupedTimerJobQueue, TimerJobQueueMetrics};
 use types::{
     BuildVersion, CanisterId, CanisterWasm, Cycles, FileAdded, FileRejected, FileRejectedReason, FileRemoved, TimestampMillis,
     Timestamped,
@@ -90,6 +93,11 @@ impl RuntimeState {
             bucket_canister_wasm: self.data.bucket_canister_wasm.version,
             cycles_dispenser_config: self.data.cycles_dispenser_config.clone(),
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
+            timer_job_queues: BTreeMap::from([(
+                "bucket_event_sync_queue".to_string(),
+                self.data.bucket_event_sync_queue.metrics(),
+            )]),
         }
     }
 }
@@ -108,6 +116,12 @@ struct Data {
     pub cycles_dispenser_config: CyclesDispenserConfig,
     pub rng_seed: [u8; 32],
     pub test_mode: bool,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -131,6 +145,7 @@ impl Data {
             cycles_dispenser_config,
             rng_seed: [0; 32],
             test_mode,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 
@@ -204,6 +219,15 @@ impl Data {
         );
         self.buckets.add_bucket(bucket, release_creation_lock);
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -213,7 +237,7 @@ struct UserRecordInternal {
     pub delete_oldest_if_limit_exceeded: bool,
 }
 
-#[derive(CandidType, Serialize, Debug)]
+#[derive(Serialize, Debug)]
 pub struct Metrics {
     pub now: TimestampMillis,
     pub heap_memory_used: u64,
@@ -236,6 +260,15 @@ pub struct Metrics {
     pub bucket_canister_wasm: BuildVersion,
     pub cycles_dispenser_config: CyclesDispenserConfig,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
+    pub timer_job_queues: BTreeMap<String, TimerJobQueueMetrics>,
+}
+
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
 }
 
 #[derive(CandidType, Serialize, Debug)]
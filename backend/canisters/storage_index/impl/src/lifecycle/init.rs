@@ -1,5 +1,6 @@
 use crate::Data;
 use crate::lifecycle::{init_cycles_dispenser_client, init_env, init_state};
+use crate::memory::get_canister_logs_memory;
 use canister_tracing_macros::trace;
 use ic_cdk::init;
 use storage_index_canister::init::Args;
@@ -8,7 +9,7 @@ use tracing::info;
 #[init]
 #[trace]
 fn init(args: Args) {
-    canister_logger::init(args.test_mode);
+    canister_logger::init(args.test_mode, get_canister_logs_memory());
     init_cycles_dispenser_client(
         args.cycles_dispenser_config.canister_id,
         args.cycles_dispenser_config.min_cycles_balance,
@@ -10,6 +10,9 @@ const BLOB_REFERENCE_COUNTS: MemoryId = MemoryId::new(2);
 const BLOB_SIZES: MemoryId = MemoryId::new(3);
 const TOTAL_FILE_BYTES: MemoryId = MemoryId::new(4);
 const TOTAL_BLOB_BYTES: MemoryId = MemoryId::new(5);
+const CANISTER_LOGS: MemoryId = MemoryId::new(6);
+const INSTRUCTION_COUNTS_INDEX: MemoryId = MemoryId::new(7);
+const INSTRUCTION_COUNTS_DATA: MemoryId = MemoryId::new(8);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -42,8 +45,20 @@ pub fn get_total_blob_bytes_memory() -> Memory {
     get_memory(TOTAL_BLOB_BYTES)
 }
 
+pub fn get_canister_logs_memory() -> Memory {
+    get_memory(CANISTER_LOGS)
+}
+
+pub fn get_instruction_counts_index_memory() -> Memory {
+    get_memory(INSTRUCTION_COUNTS_INDEX)
+}
+
+pub fn get_instruction_counts_data_memory() -> Memory {
+    get_memory(INSTRUCTION_COUNTS_DATA)
+}
+
 pub fn memory_sizes() -> BTreeMap<u8, u64> {
-    (0u8..=5).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
+    (0u8..=8).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
 }
 
 fn get_memory(id: MemoryId) -> Memory {
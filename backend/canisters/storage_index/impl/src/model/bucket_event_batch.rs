@@ -1,6 +1,6 @@
 use candid::Principal;
 use serde::{Deserialize, Serialize};
-use timer_job_queues::{TimerJobItem, grouped_timer_job_batch};
+use timer_job_queues::{TimerJobItem, TimerJobOutcome, grouped_timer_job_batch};
 use types::{AccessorId, CanisterId, FileId};
 use utils::canister::should_retry_failed_c2c_call;
 
@@ -16,7 +16,7 @@ pub enum EventToSync {
 }
 
 impl TimerJobItem for BucketEventBatch {
-    async fn process(&self) -> Result<(), bool> {
+    async fn process(&self) -> TimerJobOutcome {
         let mut args = storage_bucket_canister::c2c_sync_index::Args::default();
         for event in &self.items {
             match event {
@@ -31,10 +31,10 @@ impl TimerJobItem for BucketEventBatch {
         let response = storage_bucket_canister_c2c_client::c2c_sync_index(self.key, &args).await;
 
         match response {
-            Ok(_) => Ok(()),
+            Ok(_) => TimerJobOutcome::Success,
             Err(error) => {
                 let retry = should_retry_failed_c2c_call(error.reject_code(), error.message());
-                Err(retry)
+                TimerJobOutcome::retry_if(retry)
             }
         }
     }
@@ -1,6 +1,8 @@
 use candid_gen::generate_candid_method;
 
 fn main() {
+    generate_candid_method!(translations, coverage, query);
+    generate_candid_method!(translations, export, query);
     generate_candid_method!(translations, pending_deployment, query);
     generate_candid_method!(translations, proposed, query);
 
@@ -8,6 +10,7 @@ fn main() {
     generate_candid_method!(translations, mark_deployed, update);
     generate_candid_method!(translations, propose, update);
     generate_candid_method!(translations, reject, update);
+    generate_candid_method!(translations, vote, update);
 
     candid::export_service!();
     std::print!("{}", __export_service());
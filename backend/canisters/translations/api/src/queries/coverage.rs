@@ -0,0 +1,28 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use types::Empty;
+
+pub type Args = Empty;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResponse),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResponse {
+    pub locales: Vec<LocaleCoverage>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug, Default)]
+pub struct LocaleCoverage {
+    pub locale: String,
+    // The number of keys in the deployed translation files
+    pub total_keys: u32,
+    // The number of keys which have had translations proposed
+    pub keys: u32,
+    pub deployed: u32,
+    pub pending_deployment: u32,
+    pub awaiting_decision: u32,
+    pub contributors: u32,
+}
@@ -0,0 +1,26 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::{Empty, TimestampMillis};
+
+pub type Args = Empty;
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResponse),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResponse {
+    pub latest_approval: TimestampMillis,
+    pub bundles: Vec<LocaleBundle>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct LocaleBundle {
+    pub locale: String,
+    // A JSON object mapping each dot-separated key to its latest approved value
+    pub json: String,
+    // When each key's latest approved value was approved
+    pub approved_at: HashMap<String, TimestampMillis>,
+}
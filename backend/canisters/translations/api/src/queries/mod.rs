@@ -1,2 +1,4 @@
+pub mod coverage;
+pub mod export;
 pub mod pending_deployment;
 pub mod proposed;
@@ -30,4 +30,6 @@ pub struct CandidateTranslation {
     pub value: String,
     pub proposed_by: UserId,
     pub proposed_at: TimestampMillis,
+    pub upvotes: u32,
+    pub downvotes: u32,
 }
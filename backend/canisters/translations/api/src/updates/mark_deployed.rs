@@ -5,6 +5,8 @@ use types::TimestampMillis;
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
     pub latest_approval: TimestampMillis,
+    // The full set of keys in the deployed translation files, against which coverage is calculated
+    pub keys: Option<Vec<String>>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -2,3 +2,4 @@ pub mod approve;
 pub mod mark_deployed;
 pub mod propose;
 pub mod reject;
+pub mod vote;
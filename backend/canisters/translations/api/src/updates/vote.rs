@@ -0,0 +1,25 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub id: u64,
+    pub approve: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResponse),
+    NotProposed,
+    NotFound,
+    OwnProposal,
+    NotAuthorized,
+    InternalError(String),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResponse {
+    pub upvotes: u32,
+    pub downvotes: u32,
+    pub approved: bool,
+}
@@ -1,16 +1,23 @@
+use constants::OPENCHAT_BOT_USER_ID;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use translations_canister::{
+    coverage::LocaleCoverage,
     proposed::{CandidateTranslation, Record},
     reject::RejectReason,
 };
 use types::{TimestampMillis, UserId};
 
+// A proposed translation is approved automatically once its upvotes exceed its downvotes by this amount
+const VOTING_QUORUM: u32 = 5;
+
 #[derive(Serialize, Deserialize, Default)]
 pub struct Translations {
     translations: Vec<Translation>,
     records: HashMap<(String, String), Vec<usize>>,
+    #[serde(default)]
+    keys: BTreeSet<String>,
 }
 
 impl Translations {
@@ -56,6 +63,7 @@ impl Translations {
                 when: args.when,
             },
             status: TranslationStatus::Proposed,
+            votes: HashMap::new(),
         });
 
         Some(new_index as u64)
@@ -90,6 +98,39 @@ impl Translations {
         })
     }
 
+    pub fn vote(&mut self, id: u64, user_id: UserId, approve: bool, now: TimestampMillis) -> VoteResponse {
+        let Some(translation) = self.translations.get_mut(id as usize) else {
+            return VoteResponse::NotFound;
+        };
+
+        if !matches!(translation.status, TranslationStatus::Proposed) {
+            return VoteResponse::NotProposed;
+        }
+
+        if translation.proposed.who == user_id {
+            return VoteResponse::OwnProposal;
+        }
+
+        translation.votes.insert(user_id, approve);
+
+        let (upvotes, downvotes) = translation.vote_counts();
+
+        let approved = if upvotes >= downvotes + VOTING_QUORUM {
+            match self.approve(id, OPENCHAT_BOT_USER_ID, now) {
+                ApproveResponse::Success(result) => Some(result),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        VoteResponse::Success(VoteSuccess {
+            upvotes,
+            downvotes,
+            approved,
+        })
+    }
+
     pub fn reject(&mut self, id: u64, reason: RejectReason, user_id: UserId, now: TimestampMillis) -> RejectResponse {
         if let Some(translation) = self.translations.get_mut(id as usize) {
             if !matches!(translation.status, TranslationStatus::Proposed) {
@@ -104,6 +145,10 @@ impl Translations {
         }
     }
 
+    pub fn set_keys(&mut self, keys: Vec<String>) {
+        self.keys = keys.into_iter().collect();
+    }
+
     pub fn mark_deployed(&mut self, latest_approval: TimestampMillis, now: TimestampMillis) {
         for indexes in self.records.values() {
             if let Some(t) = self.find_most_recent_approved_or_deployed(indexes) {
@@ -130,12 +175,17 @@ impl Translations {
                 for index in indexes {
                     if let Some(translation) = self.translations.get(*index) {
                         match translation.status {
-                            TranslationStatus::Proposed => candidates.push(CandidateTranslation {
-                                id: *index as u64,
-                                value: translation.value.clone(),
-                                proposed_by: translation.proposed.who,
-                                proposed_at: translation.proposed.when,
-                            }),
+                            TranslationStatus::Proposed => {
+                                let (upvotes, downvotes) = translation.vote_counts();
+                                candidates.push(CandidateTranslation {
+                                    id: *index as u64,
+                                    value: translation.value.clone(),
+                                    proposed_by: translation.proposed.who,
+                                    proposed_at: translation.proposed.when,
+                                    upvotes,
+                                    downvotes,
+                                })
+                            }
                             TranslationStatus::Deployed(_) => deployment_count += 1,
                             _ => (),
                         }
@@ -166,6 +216,85 @@ impl Translations {
             .collect()
     }
 
+    // The latest approved or deployed value for each record, grouped by locale
+    pub fn latest_approved(&self) -> BTreeMap<&str, Vec<&Translation>> {
+        let mut bundles: BTreeMap<&str, Vec<&Translation>> = BTreeMap::new();
+
+        for indexes in self.records.values() {
+            if let Some(t) = self.find_most_recent_approved_or_deployed(indexes) {
+                bundles.entry(t.locale.as_str()).or_default().push(t);
+            }
+        }
+
+        for translations in bundles.values_mut() {
+            translations.sort_unstable_by(|t1, t2| t1.key.cmp(&t2.key));
+        }
+
+        bundles
+    }
+
+    // Coverage is calculated against the full set of deployed keys. Until that has been set, the keys which have had
+    // translations proposed in any locale are used instead.
+    pub fn coverage(&self) -> Vec<LocaleCoverage> {
+        let total_keys = if self.keys.is_empty() {
+            self.records.keys().map(|(_, key)| key).collect::<HashSet<_>>().len() as u32
+        } else {
+            self.keys.len() as u32
+        };
+
+        let mut coverage: BTreeMap<&str, (LocaleCoverage, HashSet<UserId>)> = BTreeMap::new();
+
+        for ((locale, key), indexes) in self.records.iter() {
+            let (stats, contributors) = coverage.entry(locale.as_str()).or_insert_with(|| {
+                (
+                    LocaleCoverage {
+                        locale: locale.clone(),
+                        total_keys,
+                        ..Default::default()
+                    },
+                    HashSet::new(),
+                )
+            });
+
+            stats.keys += 1;
+
+            // Keys which have since been removed from the translation files don't count towards coverage
+            let is_current_key = self.keys.is_empty() || self.keys.contains(key);
+
+            match self
+                .find_most_recent_approved_or_deployed(indexes)
+                .filter(|_| is_current_key)
+                .map(|t| &t.status)
+            {
+                Some(TranslationStatus::Deployed(_)) => stats.deployed += 1,
+                Some(TranslationStatus::Approved(_)) => stats.pending_deployment += 1,
+                _ => {}
+            }
+
+            let mut awaiting_decision = false;
+            for translation in indexes.iter().filter_map(|index| self.translations.get(*index)) {
+                match translation.status {
+                    TranslationStatus::Proposed => awaiting_decision = true,
+                    TranslationStatus::Approved(_) | TranslationStatus::Deployed(_) => {
+                        contributors.insert(translation.proposed.who);
+                    }
+                    _ => {}
+                }
+            }
+            if awaiting_decision {
+                stats.awaiting_decision += 1;
+            }
+        }
+
+        coverage
+            .into_values()
+            .map(|(mut stats, contributors)| {
+                stats.contributors = contributors.len() as u32;
+                stats
+            })
+            .collect()
+    }
+
     pub fn collate_decision_summaries(&self, since: TimestampMillis) -> HashMap<UserId, DecisionSummary> {
         self.translations
             .iter()
@@ -242,6 +371,23 @@ pub struct Translation {
     pub value: String,
     pub proposed: Attribution,
     pub status: TranslationStatus,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub votes: HashMap<UserId, bool>,
+}
+
+impl Translation {
+    pub fn approved_at(&self) -> Option<TimestampMillis> {
+        match &self.status {
+            TranslationStatus::Approved(s) => Some(s.attribution.when),
+            TranslationStatus::Deployed(s) => Some(s.approved.attribution.when),
+            _ => None,
+        }
+    }
+
+    pub fn vote_counts(&self) -> (u32, u32) {
+        let upvotes = self.votes.values().filter(|v| **v).count() as u32;
+        (upvotes, self.votes.len() as u32 - upvotes)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -288,6 +434,19 @@ pub struct ApproveSuccess {
     pub previously_approved: bool,
 }
 
+pub enum VoteResponse {
+    Success(VoteSuccess),
+    NotProposed,
+    NotFound,
+    OwnProposal,
+}
+
+pub struct VoteSuccess {
+    pub upvotes: u32,
+    pub downvotes: u32,
+    pub approved: Option<ApproveSuccess>,
+}
+
 pub enum RejectResponse {
     Success,
     NotProposed,
@@ -528,6 +687,145 @@ mod tests {
         assert_eq!(summary_2.newly_approved, 0);
     }
 
+    #[test]
+    fn proposal_approved_once_voting_quorum_reached() {
+        let mut translations = Translations::default();
+
+        translations.propose(test_proposal_1());
+
+        for i in 0..VOTING_QUORUM {
+            let approved = match translations.vote(0, voter(i as u8), true, 10) {
+                VoteResponse::Success(result) => result.approved.is_some(),
+                _ => panic!("VoteSuccess expected"),
+            };
+            assert_eq!(approved, i == VOTING_QUORUM - 1);
+        }
+
+        assert!(matches!(
+            translations.vote(0, voter(100), true, 11),
+            VoteResponse::NotProposed
+        ));
+        assert_eq!(translations.pending_deployment().len(), 1);
+    }
+
+    #[test]
+    fn downvotes_count_against_quorum() {
+        let mut translations = Translations::default();
+
+        translations.propose(test_proposal_1());
+        translations.vote(0, voter(0), false, 10);
+
+        // Changing a vote replaces the previous one
+        translations.vote(0, voter(1), false, 10);
+        translations.vote(0, voter(1), true, 10);
+
+        for i in 2..(VOTING_QUORUM + 1) {
+            translations.vote(0, voter(i as u8), true, 10);
+        }
+
+        let results = translations.proposed();
+        assert_eq!(results[0].candidates[0].upvotes, VOTING_QUORUM);
+        assert_eq!(results[0].candidates[0].downvotes, 1);
+
+        if let VoteResponse::Success(result) = translations.vote(0, voter(100), true, 11) {
+            assert!(result.approved.is_some());
+        } else {
+            panic!("VoteSuccess expected");
+        }
+    }
+
+    #[test]
+    fn cannot_vote_on_own_proposal() {
+        let mut translations = Translations::default();
+
+        translations.propose(test_proposal_1());
+
+        assert!(matches!(
+            translations.vote(0, user_id(USER1), true, 10),
+            VoteResponse::OwnProposal
+        ));
+    }
+
+    #[test]
+    fn coverage_expected() {
+        let mut translations = Translations::default();
+
+        translations.propose(test_proposal_1());
+        translations.propose(test_proposal_2());
+        translations.approve(0, user_id(USER3), 2);
+        translations.mark_deployed(2, 3);
+
+        let mut p3 = test_proposal_1();
+        p3.key = "abc.xyz".to_string();
+        translations.propose(p3);
+        translations.approve(2, user_id(USER3), 4);
+
+        let mut p4 = test_proposal_1();
+        p4.locale = "fr".to_string();
+        translations.propose(p4);
+
+        let results = translations.coverage();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].locale, "en");
+        assert_eq!(results[0].total_keys, 2);
+        assert_eq!(results[0].keys, 2);
+        assert_eq!(results[0].deployed, 1);
+        assert_eq!(results[0].pending_deployment, 1);
+        assert_eq!(results[0].awaiting_decision, 1);
+        assert_eq!(results[0].contributors, 1);
+        assert_eq!(results[1].locale, "fr");
+        assert_eq!(results[1].total_keys, 2);
+        assert_eq!(results[1].keys, 1);
+        assert_eq!(results[1].awaiting_decision, 1);
+        assert_eq!(results[1].contributors, 0);
+    }
+
+    #[test]
+    fn coverage_calculated_against_full_key_set() {
+        let mut translations = Translations::default();
+        translations.set_keys(vec!["abc.def".to_string(), "abc.ghi".to_string(), "abc.jkl".to_string()]);
+
+        translations.propose(test_proposal_1());
+        translations.approve(0, user_id(USER3), 2);
+
+        // A key which is no longer in the translation files
+        let mut p2 = test_proposal_1();
+        p2.key = "abc.old".to_string();
+        translations.propose(p2);
+        translations.approve(1, user_id(USER3), 3);
+
+        let results = translations.coverage();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].total_keys, 3);
+        assert_eq!(results[0].keys, 2);
+        assert_eq!(results[0].pending_deployment, 1);
+    }
+
+    #[test]
+    fn latest_approved_includes_deployed_and_pending() {
+        let mut translations = Translations::default();
+
+        translations.propose(test_proposal_1());
+        translations.approve(0, user_id(USER3), 2);
+        translations.mark_deployed(2, 3);
+
+        let mut p2 = test_proposal_2();
+        p2.key = "abc.aaa".to_string();
+        translations.propose(p2);
+        translations.approve(1, user_id(USER3), 4);
+
+        let results = translations.latest_approved();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results["en"].iter().map(|t| t.id).collect::<Vec<_>>(), vec![1, 0]);
+    }
+
+    fn voter(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+
     fn user_id(text: &str) -> UserId {
         Principal::from_text(text).unwrap().into()
     }
@@ -0,0 +1,12 @@
+use crate::read_state;
+use canister_api_macros::query;
+use canister_tracing_macros::trace;
+use translations_canister::coverage::{Response::*, *};
+
+#[query(candid = true, msgpack = true)]
+#[trace]
+fn coverage(_args: Args) -> Response {
+    let locales = read_state(|state| state.data.translations.coverage());
+
+    Success(SuccessResponse { locales })
+}
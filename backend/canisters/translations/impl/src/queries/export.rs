@@ -0,0 +1,45 @@
+use crate::RuntimeState;
+use crate::guards::caller_is_deployment_operator;
+use crate::{model::translations::TranslationStatus, read_state};
+use canister_api_macros::query;
+use canister_tracing_macros::trace;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use translations_canister::export::{Response::*, *};
+
+#[query(guard = "caller_is_deployment_operator", candid = true, msgpack = true)]
+#[trace]
+fn export(_args: Args) -> Response {
+    read_state(export_impl)
+}
+
+fn export_impl(state: &RuntimeState) -> Response {
+    let mut latest_approval = 0;
+    let mut bundles = Vec::new();
+
+    for (locale, translations) in state.data.translations.latest_approved() {
+        let mut object = Map::new();
+        let mut approved_at = HashMap::new();
+
+        for translation in translations {
+            if let TranslationStatus::Approved(s) = &translation.status {
+                latest_approval = latest_approval.max(s.attribution.when);
+            }
+            if let Some(when) = translation.approved_at() {
+                approved_at.insert(translation.key.clone(), when);
+            }
+            object.insert(translation.key.clone(), Value::String(translation.value.clone()));
+        }
+
+        bundles.push(LocaleBundle {
+            locale: locale.to_string(),
+            json: Value::Object(object).to_string(),
+            approved_at,
+        });
+    }
+
+    Success(SuccessResponse {
+        latest_approval,
+        bundles,
+    })
+}
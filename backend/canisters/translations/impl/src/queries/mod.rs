@@ -1,3 +1,5 @@
+mod coverage;
+mod export;
 mod http_request;
 mod pending_deployment;
 mod proposed;
@@ -1,4 +1,4 @@
-use crate::read_state;
+use crate::{MIN_CHIT_TO_VOTE, read_state};
 use canister_api_macros::query;
 use canister_tracing_macros::trace;
 use translations_canister::proposed::{Response::*, *};
@@ -10,7 +10,7 @@ async fn proposed(_args: Args) -> Response {
     let (user_index_canister_id, caller) = read_state(|state| (state.data.user_index_canister_id, state.env.caller()));
 
     match lookup_user(caller, user_index_canister_id).await {
-        Ok(Some(user)) if user.is_platform_operator => (),
+        Ok(Some(user)) if user.is_platform_operator || (!user.is_bot && user.total_chit_earned >= MIN_CHIT_TO_VOTE) => (),
         Ok(_) => return NotAuthorized,
         Err(error) => return InternalError(format!("{error:?}")),
     };
@@ -1,7 +1,8 @@
 use crate::{
+    RuntimeState,
     model::{
         pending_payments_queue::{PendingPayment, PendingPaymentReason},
-        translations::ApproveResponse,
+        translations::{ApproveResponse, ApproveSuccess},
     },
     mutate_state, read_state,
 };
@@ -9,6 +10,7 @@ use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use constants::{CHAT_LEDGER_CANISTER_ID, CHAT_TRANSFER_FEE};
 use translations_canister::approve::{Response::*, *};
+use types::TimestampMillis;
 use user_index_canister_c2c_client::lookup_user;
 
 #[update(candid = true, msgpack = true)]
@@ -25,20 +27,24 @@ async fn approve(args: Args) -> Response {
 
     mutate_state(|state| match state.data.translations.approve(args.id, user_id, now) {
         ApproveResponse::Success(result) => {
-            if !result.previously_approved {
-                state.data.pending_payments_queue.push(PendingPayment {
-                    recipient_account: result.proposed_by.into(),
-                    timestamp: now,
-                    ledger: CHAT_LEDGER_CANISTER_ID,
-                    fee: CHAT_TRANSFER_FEE,
-                    amount: 100_000_000, // 1 CHAT
-                    reason: PendingPaymentReason::Approval,
-                });
-                crate::jobs::make_pending_payments::start_job_if_required(state);
-            }
+            reward_proposer_if_newly_approved(&result, now, state);
             Success
         }
         ApproveResponse::NotProposed => NotProposed,
         ApproveResponse::NotFound => NotFound,
     })
 }
+
+pub(crate) fn reward_proposer_if_newly_approved(result: &ApproveSuccess, now: TimestampMillis, state: &mut RuntimeState) {
+    if !result.previously_approved {
+        state.data.pending_payments_queue.push(PendingPayment {
+            recipient_account: result.proposed_by.into(),
+            timestamp: now,
+            ledger: CHAT_LEDGER_CANISTER_ID,
+            fee: CHAT_TRANSFER_FEE,
+            amount: 100_000_000, // 1 CHAT
+            reason: PendingPaymentReason::Approval,
+        });
+        crate::jobs::make_pending_payments::start_job_if_required(state);
+    }
+}
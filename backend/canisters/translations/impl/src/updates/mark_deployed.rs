@@ -17,6 +17,10 @@ fn mark_deployed(args: Args) -> Response {
 fn mark_deployed_impl(args: Args, state: &mut RuntimeState) -> Response {
     state.data.translations.mark_deployed(args.latest_approval, state.env.now());
 
+    if let Some(keys) = args.keys {
+        state.data.translations.set_keys(keys);
+    }
+
     notify_translators_of_decisions(state);
 
     Success
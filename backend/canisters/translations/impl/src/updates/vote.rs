@@ -0,0 +1,37 @@
+use crate::updates::approve::reward_proposer_if_newly_approved;
+use crate::{MIN_CHIT_TO_VOTE, model::translations::VoteResponse, mutate_state, read_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use translations_canister::vote::{Response::*, *};
+use user_index_canister_c2c_client::lookup_user;
+
+#[update(candid = true, msgpack = true)]
+#[trace]
+async fn vote(args: Args) -> Response {
+    let (user_index_canister_id, caller, now) =
+        read_state(|state| (state.data.user_index_canister_id, state.env.caller(), state.env.now()));
+
+    let user_id = match lookup_user(caller, user_index_canister_id).await {
+        Ok(Some(user)) if !user.is_bot && user.total_chit_earned >= MIN_CHIT_TO_VOTE => user.user_id,
+        Ok(_) => return NotAuthorized,
+        Err(error) => return InternalError(format!("{error:?}")),
+    };
+
+    mutate_state(
+        |state| match state.data.translations.vote(args.id, user_id, args.approve, now) {
+            VoteResponse::Success(result) => {
+                if let Some(approved) = &result.approved {
+                    reward_proposer_if_newly_approved(approved, now, state);
+                }
+                Success(SuccessResponse {
+                    upvotes: result.upvotes,
+                    downvotes: result.downvotes,
+                    approved: result.approved.is_some(),
+                })
+            }
+            VoteResponse::NotProposed => NotProposed,
+            VoteResponse::NotFound => NotFound,
+            VoteResponse::OwnProposal => OwnProposal,
+        },
+    )
+}
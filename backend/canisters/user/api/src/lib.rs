@@ -352,6 +352,59 @@ impl Default for WalletConfig {
     }
 }
 
+// Words or phrases which trigger a notification and a message activity event when they appear in a group or channel,
+// including in thread replies. There are no alerts for all activity in a thread, following the thread covers that.
+#[ts_export(user)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct KeywordAlerts {
+    // Apply to every group and channel the user is a member of
+    pub global: Vec<String>,
+    pub chats: Vec<ChatKeywordAlerts>,
+}
+
+#[ts_export(user)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChatKeywordAlerts {
+    pub chat: Chat,
+    pub keywords: Vec<String>,
+}
+
+impl KeywordAlerts {
+    pub fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.chats.is_empty()
+    }
+
+    pub fn for_chat(&self, chat: &Chat) -> &[String] {
+        self.chats
+            .iter()
+            .find(|c| c.chat == *chat)
+            .map(|c| c.keywords.as_slice())
+            .unwrap_or_default()
+    }
+
+    // Returns true if the keywords have changed
+    pub fn set(&mut self, chat: Option<Chat>, keywords: Vec<String>) -> bool {
+        match chat {
+            None => {
+                if self.global == keywords {
+                    return false;
+                }
+                self.global = keywords;
+            }
+            Some(chat) => {
+                if self.for_chat(&chat) == keywords.as_slice() {
+                    return false;
+                }
+                self.chats.retain(|c| c.chat != chat);
+                if !keywords.is_empty() {
+                    self.chats.push(ChatKeywordAlerts { chat, keywords });
+                }
+            }
+        }
+        true
+    }
+}
+
 #[ts_export(user)]
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct Referral {
@@ -382,6 +435,12 @@ pub struct MessageActivityEvent {
     pub activity: MessageActivity,
     pub timestamp: TimestampMillis,
     pub user_id: Option<UserId>,
+    // Only set on events sent by group and community canisters, for use in outbound notifications. Cleared before
+    // the event is stored in the user's activity feed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_text: Option<String>,
 }
 
 impl MessageActivityEvent {
@@ -403,6 +462,16 @@ pub enum MessageActivity {
     Crypto,
     PollVote,
     P2PSwapAccepted,
+    Keyword,
+}
+
+impl MessageActivity {
+    pub fn has_outbound_notification(&self) -> bool {
+        matches!(
+            self,
+            MessageActivity::Mention | MessageActivity::QuoteReply | MessageActivity::Keyword
+        )
+    }
 }
 
 #[ts_export(user)]
@@ -36,6 +36,7 @@ fn main() {
     generate_ts_method!(user, cancel_p2p_swap);
     generate_ts_method!(user, claim_daily_chit);
     generate_ts_method!(user, configure_wallet);
+    generate_ts_method!(user, confirm_notification_email);
     generate_ts_method!(user, create_community);
     generate_ts_method!(user, create_group);
     generate_ts_method!(user, delete_community);
@@ -65,7 +66,10 @@ fn main() {
     generate_ts_method!(user, set_bio);
     generate_ts_method!(user, set_community_indexes);
     generate_ts_method!(user, set_contact);
+    generate_ts_method!(user, set_keyword_alerts);
     generate_ts_method!(user, set_message_reminder_v2);
+    generate_ts_method!(user, set_notification_preferences);
+    generate_ts_method!(user, set_notification_routing);
     generate_ts_method!(user, set_pin_number);
     generate_ts_method!(user, swap_tokens);
     generate_ts_method!(user, tip_message);
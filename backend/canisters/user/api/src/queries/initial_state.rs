@@ -1,10 +1,10 @@
-use crate::{MessageActivitySummary, Referral, WalletConfig};
+use crate::{KeywordAlerts, MessageActivitySummary, Referral, WalletConfig};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
 use types::{
-    CanisterId, Chat, ChatId, ChitEarned, DirectChatSummary, Empty, GroupChatSummary, InstalledBotDetails, PinNumberSettings,
-    StreakInsurance, TimestampMillis, UserId,
+    CanisterId, Chat, ChatId, ChitEarned, DirectChatSummary, Empty, GroupChatSummary, InstalledBotDetails,
+    NotificationPreferences, NotificationRouting, PinNumberSettings, StreakInsurance, TimestampMillis, UserId,
 };
 
 pub type Args = Empty;
@@ -39,6 +39,9 @@ pub struct SuccessResult {
     pub next_daily_claim: TimestampMillis,
     pub is_unique_person: bool,
     pub wallet_config: WalletConfig,
+    pub notification_routing: Option<NotificationRouting>,
+    pub notification_preferences: NotificationPreferences,
+    pub keyword_alerts: KeywordAlerts,
     pub referrals: Vec<Referral>,
     pub message_activity_summary: MessageActivitySummary,
     pub bots: Vec<InstalledBotDetails>,
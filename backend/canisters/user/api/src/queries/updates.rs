@@ -1,10 +1,10 @@
-use crate::{MessageActivitySummary, Referral, WalletConfig};
+use crate::{KeywordAlerts, MessageActivitySummary, Referral, WalletConfig};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
 use types::{
-    Chat, ChatId, ChitEarned, CommunityId, DirectChatSummary, DirectChatSummaryUpdates, InstalledBotDetails, OptionUpdate,
-    PinNumberSettings, StreakInsurance, TimestampMillis, UserId,
+    Chat, ChatId, ChitEarned, CommunityId, DirectChatSummary, DirectChatSummaryUpdates, InstalledBotDetails,
+    NotificationPreferences, NotificationRouting, OptionUpdate, PinNumberSettings, StreakInsurance, TimestampMillis, UserId,
 };
 
 #[ts_export(user, updates)]
@@ -50,6 +50,10 @@ pub struct SuccessResult {
     pub next_daily_claim: TimestampMillis,
     pub is_unique_person: Option<bool>,
     pub wallet_config: Option<WalletConfig>,
+    #[ts(as = "types::OptionUpdateNotificationRouting")]
+    pub notification_routing: OptionUpdate<NotificationRouting>,
+    pub notification_preferences: Option<NotificationPreferences>,
+    pub keyword_alerts: Option<KeywordAlerts>,
     pub referrals: Vec<Referral>,
     pub message_activity_summary: Option<MessageActivitySummary>,
     pub bots_added_or_updated: Vec<InstalledBotDetails>,
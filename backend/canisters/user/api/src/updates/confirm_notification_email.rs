@@ -0,0 +1,12 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::UnitResult;
+
+#[ts_export(user, confirm_notification_email)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub code: String,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,15 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Chat, UnitResult};
+
+#[ts_export(user, set_keyword_alerts)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Set to `None` to update the keywords which apply across all of the user's groups and channels
+    pub chat: Option<Chat>,
+    // Replaces any existing keywords for the chat. Pass an empty list to remove them.
+    pub keywords: Vec<String>,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,12 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{NotificationPreferences, UnitResult};
+
+#[ts_export(user, set_notification_preferences)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub preferences: NotificationPreferences,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,29 @@
+use candid::CandidType;
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::NotificationRouting;
+
+#[ts_export(user, set_notification_routing)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Set to `None` to stop routing notifications anywhere other than the user's push subscriptions
+    pub routing: Option<NotificationRouting>,
+}
+
+#[ts_export(user, set_notification_routing)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(user, set_notification_routing)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    // The secret used to sign each webhook request, set whenever notifications are routed to a new webhook
+    pub webhook_signing_secret: Option<String>,
+    // Email addresses only start receiving notifications once the code sent to them is passed to
+    // `confirm_notification_email`
+    pub email_confirmation_required: bool,
+}
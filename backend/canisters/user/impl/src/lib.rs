@@ -1,3 +1,4 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::communities::Communities;
 use crate::model::community::Community;
 use crate::model::direct_chats::DirectChats;
@@ -6,18 +7,23 @@ use crate::model::group_chats::GroupChats;
 use crate::model::hot_group_exclusions::HotGroupExclusions;
 use crate::model::local_user_index_event_batch::LocalUserIndexEventBatch;
 use crate::model::p2p_swaps::P2PSwaps;
+use crate::model::pending_email_routing::{EmailConfirmationLimits, PendingEmailRouting};
 use crate::model::pin_number::PinNumber;
 use crate::model::token_swaps::TokenSwaps;
 use crate::model::user_canister_event_batch::UserCanisterEventBatch;
-use crate::timer_job_types::{ClaimOrResetStreakInsuranceJob, DeleteFileReferencesJob, RemoveExpiredEventsJob, TimerJob};
+use crate::timer_job_types::{
+    ClaimOrResetStreakInsuranceJob, DeleteFileReferencesJob, NotifyChatsOfChitEarnedJob, RemoveExpiredEventsJob, TimerJob,
+};
 use candid::Principal;
 use canister_state_macros::canister_state;
 use canister_timer_jobs::{Job, TimerJobs};
 use chat_events::EventPusher;
-use constants::{DAY_IN_MS, ICP_LEDGER_CANISTER_ID, LIFETIME_DIAMOND_TIMESTAMP, OPENCHAT_BOT_USER_ID};
+use constants::{DAY_IN_MS, HOUR_IN_MS, ICP_LEDGER_CANISTER_ID, LIFETIME_DIAMOND_TIMESTAMP, OPENCHAT_BOT_USER_ID};
 use event_store_types::{Event, EventBuilder};
 use fire_and_forget_handler::FireAndForgetHandler;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
 use installed_bots::InstalledBots;
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
 use local_user_index_canister::UserEvent as LocalUserIndexEvent;
 use model::chit_earned_events::ChitEarnedEvents;
 use model::contacts::Contacts;
@@ -33,16 +39,19 @@ use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
 use stable_memory_map::{BaseKeyPrefix, ChatEventKeyPrefix};
 use std::cell::RefCell;
+use std::cmp::min;
 use std::collections::{BTreeMap, HashSet};
 use std::ops::Deref;
-use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue};
+use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue, TimerJobQueueMetrics};
 use types::{
     Achievement, BotInitiator, BotNotification, BotPermissions, BuildVersion, CanisterId, Chat, ChatId, ChatMetrics,
-    ChitEarned, ChitEarnedReason, CommunityId, Cycles, Document, FcmData, IdempotentEnvelope, Milliseconds, Notification,
-    NotifyChit, TimestampMillis, Timestamped, UniquePersonProof, UserCanisterStreakInsuranceClaim,
-    UserCanisterStreakInsurancePayment, UserId, UserNotification, UserNotificationPayload,
+    ChitEarned, ChitEarnedReason, CommunityId, Cycles, Document, EmailConfirmation, FcmData, IdempotentEnvelope, Milliseconds,
+    Notification, NotificationCategory, NotificationDestination, NotificationPreferences, NotificationRouting, NotifyChit,
+    OutboundNotification, OutboundNotificationPayload, TimestampMillis, Timestamped, UniquePersonProof,
+    UserCanisterStreakInsuranceClaim, UserCanisterStreakInsurancePayment, UserId, UserNotification, UserNotificationPayload,
+    UserNotificationPreferences,
 };
-use user_canister::{MessageActivityEvent, NamedAccount, UserCanisterEvent, WalletConfig};
+use user_canister::{KeywordAlerts, MessageActivity, MessageActivityEvent, NamedAccount, UserCanisterEvent, WalletConfig};
 use utils::env::Environment;
 use utils::idempotency_checker::IdempotencyChecker;
 use utils::regular_jobs::RegularJobs;
@@ -135,6 +144,111 @@ impl RuntimeState {
         })
     }
 
+    // Sends the notification to the destination the user has configured, if they have opted in to its category
+    pub fn push_outbound_notification(&mut self, payload: OutboundNotificationPayload) {
+        if self.data.suspended.value {
+            return;
+        }
+
+        let Some(destination) = self
+            .data
+            .notification_routing
+            .value
+            .as_ref()
+            .and_then(|r| r.routes(payload.category))
+            .cloned()
+        else {
+            return;
+        };
+
+        let signing_secret = match destination {
+            NotificationDestination::Webhook(_) => self.data.webhook_signing_secret.clone(),
+            NotificationDestination::Email(_) => None,
+        };
+
+        self.data.local_user_index_event_sync_queue.push(IdempotentEnvelope {
+            created_at: self.env.now(),
+            idempotency_id: self.env.rng().next_u64(),
+            value: LocalUserIndexEvent::Notification(Box::new(Notification::Outbound(OutboundNotification {
+                destination,
+                payload,
+                signing_secret,
+            }))),
+        })
+    }
+
+    pub fn push_email_confirmation(&mut self, email: String, code: String) {
+        let now = self.env.now();
+
+        self.data.local_user_index_event_sync_queue.push(IdempotentEnvelope {
+            created_at: now,
+            idempotency_id: self.env.rng().next_u64(),
+            value: LocalUserIndexEvent::Notification(Box::new(Notification::EmailConfirmation(EmailConfirmation {
+                recipient: self.env.canister_id().into(),
+                email,
+                code,
+                timestamp: now,
+            }))),
+        })
+    }
+
+    pub fn push_outbound_notification_for_activity(&mut self, event: &MessageActivityEvent) {
+        let category = match event.activity {
+            MessageActivity::Mention => NotificationCategory::Mention,
+            MessageActivity::QuoteReply => NotificationCategory::Reply,
+            MessageActivity::Keyword => NotificationCategory::Keyword,
+            _ => return,
+        };
+
+        if event.user_id.is_some_and(|u| self.data.blocked_users.value.contains(&u)) {
+            return;
+        }
+
+        self.push_outbound_notification(OutboundNotificationPayload {
+            recipient: self.env.canister_id().into(),
+            category,
+            chat: event.chat,
+            thread_root_message_index: event.thread_root_message_index,
+            message_index: event.message_index,
+            sender: event.user_id,
+            sender_name: event.sender_name.clone(),
+            message_text: event.message_text.clone(),
+            timestamp: event.timestamp,
+        });
+    }
+
+    // Sends the LocalUserIndex what it needs to decide which notifications to hold back for the user's digest. Muted
+    // direct chats are included in the digest, since notifications for those are otherwise dropped entirely. Muted group
+    // chats and channels can't be, since their notifications are never sent to the LocalUserIndex.
+    pub fn sync_notification_preferences(&mut self) {
+        let preferences = &self.data.notification_preferences.value;
+        let digest_chats = preferences
+            .digest
+            .as_ref()
+            .map(|d| {
+                d.low_priority_chats
+                    .iter()
+                    .copied()
+                    .chain(
+                        self.data
+                            .direct_chats
+                            .iter()
+                            .filter(|c| c.notifications_muted.value)
+                            .map(|c| Chat::Direct(c.them.into())),
+                    )
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let event = LocalUserIndexEvent::NotificationPreferencesUpdated(Box::new(UserNotificationPreferences {
+            quiet_hours: preferences.quiet_hours,
+            utc_offset_mins: self.data.streak.utc_offset_mins(),
+            digest_interval: preferences.digest.as_ref().map(|d| d.interval),
+            digest_chats,
+        }));
+        self.push_local_user_index_canister_events(vec![event], self.env.now());
+    }
+
     pub fn run_event_expiry_job(&mut self) {
         let now = self.env.now();
         let mut next_event_expiry = None;
@@ -317,7 +431,103 @@ impl RuntimeState {
                 streak_ends: self.data.streak.ends(),
             }),
             now,
-        )
+        );
+        self.notify_chats_of_chit_earned(now);
+    }
+
+    // Groups and communities maintain their own leaderboards so need to be told of any chit earned. Rather than calling
+    // every chat each time chit is earned, the chit is reported in batches. A batch never spans midnight (UTC) so that
+    // the chats attribute it to the correct day.
+    fn notify_chats_of_chit_earned(&mut self, now: TimestampMillis) {
+        if self.data.chit_events.try_schedule_report_to_chats() {
+            let end_of_day = (now / DAY_IN_MS + 1) * DAY_IN_MS - 1;
+            self.data.timer_jobs.enqueue_job(
+                TimerJob::NotifyChatsOfChitEarned(NotifyChatsOfChitEarnedJob { timestamp: now }),
+                min(now + HOUR_IN_MS, end_of_day),
+                now,
+            );
+        }
+    }
+
+    pub fn report_chit_earned_to_chats(&mut self, timestamp: TimestampMillis) {
+        let amount = self.data.chit_events.take_unreported_to_chats();
+        if amount == 0 {
+            return;
+        }
+
+        let group_args = serialize_then_unwrap(group_canister::c2c_notify_chit_earned::Args { amount, timestamp });
+        for group in self.data.group_chats.iter() {
+            self.data.fire_and_forget_handler.send(
+                group.chat_id.into(),
+                "c2c_notify_chit_earned_msgpack".to_string(),
+                group_args.clone(),
+            );
+        }
+
+        let community_args = serialize_then_unwrap(community_canister::c2c_notify_chit_earned::Args { amount, timestamp });
+        for community in self.data.communities.iter() {
+            self.data.fire_and_forget_handler.send(
+                community.community_id.into(),
+                "c2c_notify_chit_earned_msgpack".to_string(),
+                community_args.clone(),
+            );
+        }
+    }
+
+    // Keyword alerts are evaluated by the groups and communities themselves, so they must be told of any changes
+    pub fn sync_keyword_alerts(&mut self, chat: Option<Chat>) {
+        match chat {
+            None => {
+                let group_ids: Vec<_> = self.data.group_chats.iter().map(|g| g.chat_id).collect();
+                for chat_id in group_ids {
+                    self.sync_keyword_alerts_to_group(chat_id);
+                }
+                let community_ids: Vec<_> = self.data.communities.iter().map(|c| c.community_id).collect();
+                for community_id in community_ids {
+                    self.sync_keyword_alerts_to_community(community_id);
+                }
+            }
+            Some(Chat::Group(chat_id)) => self.sync_keyword_alerts_to_group(chat_id),
+            Some(Chat::Channel(community_id, _)) => self.sync_keyword_alerts_to_community(community_id),
+            Some(Chat::Direct(_)) => {}
+        }
+    }
+
+    pub fn sync_keyword_alerts_to_group(&mut self, chat_id: ChatId) {
+        let alerts = &self.data.keyword_alerts.value;
+        let mut keywords = alerts.global.clone();
+        for keyword in alerts.for_chat(&Chat::Group(chat_id)) {
+            if !keywords.contains(keyword) {
+                keywords.push(keyword.clone());
+            }
+        }
+
+        self.data.fire_and_forget_handler.send(
+            chat_id.into(),
+            "c2c_set_keyword_alerts_msgpack".to_string(),
+            serialize_then_unwrap(group_canister::c2c_set_keyword_alerts::Args { keywords }),
+        );
+    }
+
+    pub fn sync_keyword_alerts_to_community(&mut self, community_id: CommunityId) {
+        let alerts = &self.data.keyword_alerts.value;
+        let channel_keywords = alerts
+            .chats
+            .iter()
+            .filter_map(|c| match c.chat {
+                Chat::Channel(c_id, channel_id) if c_id == community_id => Some((channel_id, c.keywords.clone())),
+                _ => None,
+            })
+            .collect();
+
+        self.data.fire_and_forget_handler.send(
+            community_id.into(),
+            "c2c_set_keyword_alerts_msgpack".to_string(),
+            serialize_then_unwrap(community_canister::c2c_set_keyword_alerts::Args {
+                keywords: alerts.global.clone(),
+                channel_keywords,
+            }),
+        );
     }
 
     pub fn block_user(&mut self, user_id: UserId, now: TimestampMillis) {
@@ -363,6 +573,17 @@ impl RuntimeState {
             unique_person_proof: self.data.unique_person_proof.is_some(),
             referred_by: self.data.referred_by,
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
+            timer_job_queues: BTreeMap::from([
+                (
+                    "user_canister_events_queue".to_string(),
+                    self.data.user_canister_events_queue.metrics(),
+                ),
+                (
+                    "local_user_index_event_sync_queue".to_string(),
+                    self.data.local_user_index_event_sync_queue.metrics(),
+                ),
+            ]),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
                 group_index: self.data.group_index_canister_id,
@@ -442,6 +663,18 @@ struct Data {
     pub achievements_last_seen: TimestampMillis,
     pub unique_person_proof: Option<UniquePersonProof>,
     pub wallet_config: Timestamped<WalletConfig>,
+    #[serde(default)]
+    pub notification_routing: Timestamped<Option<NotificationRouting>>,
+    #[serde(default)]
+    pub pending_email_routing: Option<PendingEmailRouting>,
+    #[serde(default)]
+    pub email_confirmation_limits: EmailConfirmationLimits,
+    #[serde(default)]
+    pub webhook_signing_secret: Option<String>,
+    #[serde(default)]
+    pub notification_preferences: Timestamped<NotificationPreferences>,
+    #[serde(default)]
+    pub keyword_alerts: Timestamped<KeywordAlerts>,
     pub rng_seed: [u8; 32],
     pub referred_by: Option<UserId>,
     pub referrals: Referrals,
@@ -450,6 +683,12 @@ struct Data {
     pub local_user_index_event_sync_queue: BatchedTimerJobQueue<LocalUserIndexEventBatch>,
     pub idempotency_checker: IdempotencyChecker,
     pub bots: InstalledBots,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -510,6 +749,12 @@ impl Data {
             unique_person_proof: None,
             rng_seed: [0; 32],
             wallet_config: Timestamped::default(),
+            notification_routing: Timestamped::default(),
+            pending_email_routing: None,
+            email_confirmation_limits: EmailConfirmationLimits::default(),
+            webhook_signing_secret: None,
+            notification_preferences: Timestamped::default(),
+            keyword_alerts: Timestamped::default(),
             referred_by,
             referrals: Referrals::default(),
             message_activity_events: MessageActivityEvents::default(),
@@ -517,6 +762,7 @@ impl Data {
             local_user_index_event_sync_queue: BatchedTimerJobQueue::new(local_user_index_canister_id, true),
             idempotency_checker: IdempotencyChecker::default(),
             bots: InstalledBots::default(),
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 
@@ -570,8 +816,11 @@ impl Data {
         }
     }
 
-    pub fn push_message_activity(&mut self, event: MessageActivityEvent, now: TimestampMillis) {
+    pub fn push_message_activity(&mut self, mut event: MessageActivityEvent, now: TimestampMillis) {
         if event.user_id.is_none_or(|user_id| !self.blocked_users.contains(&user_id)) {
+            // The sender name and message text are only needed for outbound notifications
+            event.sender_name = None;
+            event.message_text = None;
             self.message_activity_events.push(event, now);
         }
     }
@@ -584,7 +833,7 @@ impl Data {
 
         // Get the granted permissions when initiated by command or API key
         let granted = match initiator {
-            BotInitiator::Command(_) => &bot.permissions,
+            BotInitiator::Command(_) | BotInitiator::Component(_) => &bot.permissions,
             BotInitiator::Autonomous => match bot.autonomous_permissions.as_ref() {
                 Some(permissions) => permissions,
                 None => return false,
@@ -599,6 +848,15 @@ impl Data {
         self.user_canister_events_queue.flush();
         self.local_user_index_event_sync_queue.flush();
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 struct UserEventPusher<'a> {
@@ -645,9 +903,18 @@ pub struct Metrics {
     pub unique_person_proof: bool,
     pub referred_by: Option<UserId>,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
+    pub timer_job_queues: BTreeMap<String, TimerJobQueueMetrics>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 fn execute_update<F: FnOnce(&mut RuntimeState) -> R, R>(f: F) -> R {
     mutate_state(|state| {
         state.regular_jobs.run(state.env.deref(), &mut state.data);
@@ -1,5 +1,5 @@
 use crate::lifecycle::{init_env, init_state};
-use crate::memory::get_stable_memory_map_memory;
+use crate::memory::{get_canister_logs_memory, get_stable_memory_map_memory};
 use crate::{Data, mutate_state, openchat_bot};
 use canister_tracing_macros::trace;
 use ic_cdk::init;
@@ -10,7 +10,7 @@ use utils::env::Environment;
 #[init]
 #[trace]
 fn init(args: Args) {
-    canister_logger::init(args.test_mode);
+    canister_logger::init(args.test_mode, get_canister_logs_memory());
     stable_memory_map::init(get_stable_memory_map_memory());
 
     let env = init_env([0; 32]);
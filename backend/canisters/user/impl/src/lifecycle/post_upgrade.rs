@@ -1,9 +1,10 @@
-use crate::Data;
 use crate::lifecycle::{init_env, init_state};
-use crate::memory::{get_stable_memory_map_memory, get_upgrades_memory};
+use crate::memory::{get_canister_logs_memory, get_stable_memory_map_memory, get_upgrades_memory};
+use crate::{Data, read_state};
 use canister_api_macros::post_upgrade;
 use canister_logger::LogEntry;
 use canister_tracing_macros::trace;
+use instruction_counts_log::InstructionCountFunctionId;
 use stable_memory::get_reader;
 use tracing::info;
 use types::CanisterId;
@@ -26,11 +27,22 @@ fn post_upgrade(args: Args) {
         data.identity_canister_id = CanisterId::from_text("6klfq-niaaa-aaaar-qadbq-cai").unwrap();
     }
 
-    canister_logger::init_with_logs(data.test_mode, errors, logs, traces);
+    // Chit earned before groups and communities tracked leaderboards should not be reported to them. This is a no-op if
+    // a report is already scheduled, since that chit is still to be reported.
+    data.chit_events.mark_all_reported_to_chats();
+
+    canister_logger::init_with_logs(data.test_mode, get_canister_logs_memory(), errors, logs, traces);
 
     let env = init_env(data.rng_seed);
     init_state(env, data, args.wasm_version);
 
     let total_instructions = ic_cdk::api::call_context_instruction_counter();
     info!(version = %args.wasm_version, total_instructions, "Post-upgrade complete");
+
+    read_state(|state| {
+        let now = state.env.now();
+        state
+            .data
+            .record_instructions_count(InstructionCountFunctionId::PostUpgrade, now)
+    });
 }
@@ -7,6 +7,10 @@ use utils::time::MonthKey;
 pub struct ChitEarnedEvents {
     events: Vec<ChitEarned>,
     total_chit_earned: i32,
+    #[serde(default)]
+    total_reported_to_chats: i32,
+    #[serde(default)]
+    report_to_chats_scheduled: bool,
 }
 
 impl ChitEarnedEvents {
@@ -48,6 +52,30 @@ impl ChitEarnedEvents {
         self.total_chit_earned
     }
 
+    // Returns true if there is unreported chit and no report to the user's groups and communities is yet scheduled
+    pub fn try_schedule_report_to_chats(&mut self) -> bool {
+        if self.report_to_chats_scheduled || self.total_chit_earned == self.total_reported_to_chats {
+            false
+        } else {
+            self.report_to_chats_scheduled = true;
+            true
+        }
+    }
+
+    // Returns the chit earned since the user's groups and communities were last notified
+    pub fn take_unreported_to_chats(&mut self) -> i32 {
+        let unreported = self.total_chit_earned - self.total_reported_to_chats;
+        self.total_reported_to_chats = self.total_chit_earned;
+        self.report_to_chats_scheduled = false;
+        unreported
+    }
+
+    pub fn mark_all_reported_to_chats(&mut self) {
+        if !self.report_to_chats_scheduled {
+            self.total_reported_to_chats = self.total_chit_earned;
+        }
+    }
+
     pub fn balance_for_month_by_timestamp(&self, ts: TimestampMillis) -> i32 {
         self.balance_for_month(MonthKey::from_timestamp(ts))
     }
@@ -201,6 +229,7 @@ mod tests {
         ChitEarnedEvents {
             events,
             total_chit_earned,
+            total_reported_to_chats: 0,
         }
     }
 }
@@ -1,5 +1,5 @@
 use crate::LocalUserIndexEvent;
-use timer_job_queues::{TimerJobItem, timer_job_batch};
+use timer_job_queues::{TimerJobItem, TimerJobOutcome, timer_job_batch};
 use types::{CanisterId, IdempotentEnvelope};
 use utils::canister::should_retry_failed_c2c_call;
 
@@ -11,7 +11,7 @@ timer_job_batch!(
 );
 
 impl TimerJobItem for LocalUserIndexEventBatch {
-    async fn process(&self) -> Result<(), bool> {
+    async fn process(&self) -> TimerJobOutcome {
         let response = local_user_index_canister_c2c_client::c2c_user_canister(
             self.state,
             &local_user_index_canister::c2c_user_canister::Args {
@@ -21,10 +21,10 @@ impl TimerJobItem for LocalUserIndexEventBatch {
         .await;
 
         match response {
-            Ok(local_user_index_canister::c2c_notify_user_events::Response::Success) => Ok(()),
+            Ok(local_user_index_canister::c2c_notify_user_events::Response::Success) => TimerJobOutcome::Success,
             Err(error) => {
                 let retry = should_retry_failed_c2c_call(error.reject_code(), error.message());
-                Err(retry)
+                TimerJobOutcome::retry_if(retry)
             }
         }
     }
@@ -11,6 +11,7 @@ pub mod hot_group_exclusions;
 pub mod local_user_index_event_batch;
 pub mod message_activity_events;
 pub mod p2p_swaps;
+pub mod pending_email_routing;
 pub mod pin_number;
 pub mod referrals;
 pub mod streak;
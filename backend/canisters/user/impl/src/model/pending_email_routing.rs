@@ -0,0 +1,174 @@
+use constants::{DAY_IN_MS, HOUR_IN_MS, MINUTE_IN_MS};
+use oc_error_codes::{OCError, OCErrorCode};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use types::{Milliseconds, NotificationDestination, NotificationRouting, OCResult, TimestampMillis};
+
+const CONFIRMATION_CODE_VALIDITY: Milliseconds = HOUR_IN_MS;
+const MAX_FAILED_ATTEMPTS: u8 = 5;
+const RESEND_INTERVAL: Milliseconds = MINUTE_IN_MS;
+const MAX_CONFIRMATIONS_PER_DAY: usize = 10;
+
+// Routing to an email address which only becomes active once the user has entered the code sent to that address
+#[derive(Serialize, Deserialize)]
+pub struct PendingEmailRouting {
+    routing: NotificationRouting,
+    code: String,
+    expires: TimestampMillis,
+    failed_attempts: u8,
+}
+
+impl PendingEmailRouting {
+    pub fn new(routing: NotificationRouting, code: String, now: TimestampMillis) -> Self {
+        PendingEmailRouting {
+            routing,
+            code,
+            expires: now + CONFIRMATION_CODE_VALIDITY,
+            failed_attempts: 0,
+        }
+    }
+
+    pub fn email(&self) -> &str {
+        match &self.routing.destination {
+            NotificationDestination::Email(address) => address,
+            NotificationDestination::Webhook(_) => unreachable!(),
+        }
+    }
+
+    pub fn verify(&mut self, code: &str, now: TimestampMillis) -> Result<(), OCError> {
+        if self.is_spent(now) {
+            return Err(OCErrorCode::Expired.with_message("Confirmation code has expired"));
+        }
+
+        if code != self.code {
+            self.failed_attempts += 1;
+            return Err(OCErrorCode::InvalidRequest.with_message("Incorrect confirmation code"));
+        }
+
+        Ok(())
+    }
+
+    // Once expired or after too many failed attempts, the user must request a new code
+    pub fn is_spent(&self, now: TimestampMillis) -> bool {
+        now > self.expires || self.failed_attempts >= MAX_FAILED_ATTEMPTS
+    }
+
+    pub fn into_routing(self) -> NotificationRouting {
+        self.routing
+    }
+}
+
+// Limits how often confirmation emails can be sent, so that users can't use them to spam arbitrary addresses. This is
+// tracked separately from the pending routing so that cancelling a confirmation or failing it too many times doesn't
+// reset the limits.
+#[derive(Serialize, Deserialize, Default)]
+pub struct EmailConfirmationLimits {
+    last_confirmation_sent_at: Option<TimestampMillis>,
+    sent_in_last_day: VecDeque<TimestampMillis>,
+}
+
+impl EmailConfirmationLimits {
+    pub fn can_send(&self, now: TimestampMillis) -> OCResult {
+        if self
+            .last_confirmation_sent_at
+            .is_some_and(|sent_at| now < sent_at.saturating_add(RESEND_INTERVAL))
+        {
+            return Err(OCErrorCode::InvalidRequest.with_message("Wait before requesting another confirmation code"));
+        }
+
+        let sent_in_last_day = self
+            .sent_in_last_day
+            .iter()
+            .filter(|sent_at| now < sent_at.saturating_add(DAY_IN_MS))
+            .count();
+
+        if sent_in_last_day >= MAX_CONFIRMATIONS_PER_DAY {
+            return Err(OCErrorCode::InvalidRequest.with_message("Too many confirmation codes requested today"));
+        }
+
+        Ok(())
+    }
+
+    pub fn record_sent(&mut self, now: TimestampMillis) {
+        while self
+            .sent_in_last_day
+            .front()
+            .is_some_and(|sent_at| now >= sent_at.saturating_add(DAY_IN_MS))
+        {
+            self.sent_in_last_day.pop_front();
+        }
+
+        self.last_confirmation_sent_at = Some(now);
+        self.sent_in_last_day.push_back(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::NotificationCategory;
+
+    fn pending() -> PendingEmailRouting {
+        PendingEmailRouting::new(
+            NotificationRouting {
+                destination: NotificationDestination::Email("a@b.com".to_string()),
+                categories: vec![NotificationCategory::Mention],
+            },
+            "123456".to_string(),
+            0,
+        )
+    }
+
+    #[test]
+    fn correct_code_verifies() {
+        let mut pending = pending();
+
+        assert!(pending.verify("000000", 1).is_err());
+        assert!(pending.verify("123456", 1).is_ok());
+    }
+
+    #[test]
+    fn resends_are_rate_limited() {
+        let mut limits = EmailConfirmationLimits::default();
+        assert!(limits.can_send(0).is_ok());
+
+        limits.record_sent(0);
+
+        assert!(limits.can_send(RESEND_INTERVAL - 1).is_err());
+        assert!(limits.can_send(RESEND_INTERVAL).is_ok());
+    }
+
+    #[test]
+    fn sends_are_capped_per_day() {
+        let mut limits = EmailConfirmationLimits::default();
+
+        for i in 0..MAX_CONFIRMATIONS_PER_DAY as u64 {
+            limits.record_sent(i * RESEND_INTERVAL);
+        }
+
+        let last_sent_at = (MAX_CONFIRMATIONS_PER_DAY as u64 - 1) * RESEND_INTERVAL;
+        assert!(limits.can_send(last_sent_at + RESEND_INTERVAL).is_err());
+        assert!(limits.can_send(DAY_IN_MS - 1).is_err());
+        assert!(limits.can_send(DAY_IN_MS).is_ok());
+    }
+
+    #[test]
+    fn code_expires() {
+        let mut pending = pending();
+
+        assert!(pending.verify("123456", CONFIRMATION_CODE_VALIDITY + 1).is_err());
+        assert!(pending.is_spent(CONFIRMATION_CODE_VALIDITY + 1));
+    }
+
+    #[test]
+    fn code_spent_after_too_many_failed_attempts() {
+        let mut pending = pending();
+
+        for _ in 0..MAX_FAILED_ATTEMPTS {
+            assert!(pending.verify("000000", 1).is_err());
+        }
+
+        assert!(pending.is_spent(1));
+        assert!(pending.verify("123456", 1).is_err());
+    }
+}
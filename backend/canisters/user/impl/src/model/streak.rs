@@ -97,6 +97,10 @@ impl Streak {
         self.max_streak
     }
 
+    pub fn utc_offset_mins(&self) -> i16 {
+        self.utc_offset_mins
+    }
+
     pub fn set_utc_offset_mins(&mut self, utc_offset_mins: i16, now: TimestampMillis) -> bool {
         if utc_offset_mins != self.utc_offset_mins && utc_offset_mins.abs() < MAX_UTC_OFFSET_MINS {
             self.utc_offset_mins = utc_offset_mins;
@@ -1,5 +1,5 @@
 use crate::{can_borrow_state, run_regular_jobs};
-use timer_job_queues::{TimerJobItem, grouped_timer_job_batch};
+use timer_job_queues::{TimerJobItem, TimerJobOutcome, grouped_timer_job_batch};
 use types::{IdempotentEnvelope, UserId};
 use user_canister::UserCanisterEvent;
 use utils::canister::should_retry_failed_c2c_call;
@@ -7,7 +7,7 @@ use utils::canister::should_retry_failed_c2c_call;
 grouped_timer_job_batch!(UserCanisterEventBatch, UserId, IdempotentEnvelope<UserCanisterEvent>, 100);
 
 impl TimerJobItem for UserCanisterEventBatch {
-    async fn process(&self) -> Result<(), bool> {
+    async fn process(&self) -> TimerJobOutcome {
         if can_borrow_state() {
             run_regular_jobs();
         }
@@ -21,10 +21,10 @@ impl TimerJobItem for UserCanisterEventBatch {
         .await;
 
         match response {
-            Ok(_) => Ok(()),
+            Ok(_) => TimerJobOutcome::Success,
             Err(error) => {
                 let retry = should_retry_failed_c2c_call(error.reject_code(), error.message());
-                Err(retry)
+                TimerJobOutcome::retry_if(retry)
             }
         }
     }
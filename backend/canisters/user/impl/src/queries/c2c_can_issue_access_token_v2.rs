@@ -3,6 +3,7 @@ use crate::guards::caller_is_local_user_index;
 use crate::read_state;
 use canister_api_macros::query;
 use types::c2c_can_issue_access_token::AccessTypeArgs;
+use types::{Chat, EventIndex, UserId};
 use user_canister::c2c_can_issue_access_token_v2::*;
 
 #[query(guard = "caller_is_local_user_index", msgpack = true)]
@@ -12,12 +13,46 @@ fn c2c_can_issue_access_token_v2(args: Args) -> Response {
 
 fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Response {
     if let AccessTypeArgs::BotActionByCommand(args) = &args_outer {
+        // Only the owner of this canister can see the direct chat with the bot
+        let this_chat = Chat::Direct(state.env.canister_id().into());
+        if args.initiator != UserId::from(state.env.canister_id()) {
+            return Response::Failure;
+        }
+
         // Get the permissions the user has granted to the bot
         let Some(granted) = state.data.bots.get(&args.bot_id).map(|b| &b.permissions) else {
             return Response::Failure;
         };
 
-        return if args.requested_permissions.is_subset(granted) { Response::Success } else { Response::Failure };
+        if !args.requested_permissions.is_subset(granted) {
+            return Response::Failure;
+        }
+
+        // Chat args can only reference this chat and message args must be in the direct chat with the bot
+        if args.referenced_chats.iter().any(|c| *c != this_chat) {
+            return Response::Failure;
+        }
+
+        if !args.referenced_messages.is_empty() {
+            let Some(chat) = state.data.direct_chats.get(&args.bot_id.into()) else {
+                return Response::Failure;
+            };
+
+            if !args.referenced_messages.iter().all(|m| {
+                chat.events
+                    .message_internal(EventIndex::default(), m.thread, m.message_index.into())
+                    .is_some_and(|(message, _)| message.message_id == m.message_id && message.deleted_by.is_none())
+            }) {
+                return Response::Failure;
+            }
+        }
+
+        return Response::Success;
+    }
+
+    if let AccessTypeArgs::BotActionByComponent(_) = &args_outer {
+        // Message components are not yet supported in direct chats
+        return Response::Failure;
     }
 
     let initiator = match &args_outer {
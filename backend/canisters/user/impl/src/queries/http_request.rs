@@ -1,9 +1,10 @@
 use crate::model::streak::Streak;
 use crate::{RuntimeState, read_state};
-use http_request::{AvatarRoute, Route, build_json_response, encode_logs, extract_route, get_document};
+use canister_logger::LogQuery;
+use http_request::{AvatarRoute, Route, build_json_response, encode_logs, encode_metrics, extract_route, get_document};
 use ic_cdk::query;
 use itertools::Itertools;
-use types::{ChitEarnedReason, HttpRequest, HttpResponse, TimestampMillis};
+use types::{ChitEarnedReason, HttpRequest, HttpResponse};
 
 #[query]
 fn http_request(request: HttpRequest) -> HttpResponse {
@@ -11,20 +12,20 @@ fn http_request(request: HttpRequest) -> HttpResponse {
         get_document(route.blob_id, state.data.avatar.as_ref(), "avatar")
     }
 
-    fn get_errors_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_errors(), since.unwrap_or(0))
+    fn get_errors_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_errors(), query)
     }
 
-    fn get_logs_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_logs(), since.unwrap_or(0))
+    fn get_logs_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_logs(), query)
     }
 
-    fn get_traces_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_traces(), since.unwrap_or(0))
+    fn get_traces_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_traces(), query)
     }
 
-    fn get_metrics_impl(state: &RuntimeState) -> HttpResponse {
-        build_json_response(&state.metrics())
+    fn get_metrics_impl(request: &HttpRequest, state: &RuntimeState) -> HttpResponse {
+        encode_metrics(request, &state.metrics())
     }
 
     fn get_swaps(state: &RuntimeState) -> HttpResponse {
@@ -54,10 +55,10 @@ fn http_request(request: HttpRequest) -> HttpResponse {
 
     match extract_route(&request.url) {
         Route::Avatar(route) => read_state(|state| get_avatar_impl(route, state)),
-        Route::Errors(since) => get_errors_impl(since),
-        Route::Logs(since) => get_logs_impl(since),
-        Route::Traces(since) => get_traces_impl(since),
-        Route::Metrics => read_state(get_metrics_impl),
+        Route::Errors(query) => get_errors_impl(query),
+        Route::Logs(query) => get_logs_impl(query),
+        Route::Traces(query) => get_traces_impl(query),
+        Route::Metrics => read_state(|state| get_metrics_impl(&request, state)),
         Route::Other(path, _) if path == "swaps" => read_state(get_swaps),
         Route::Other(path, _) if path == "daily_claims" => read_state(daily_claims),
         _ => HttpResponse::not_found(),
@@ -43,6 +43,7 @@ fn initial_state_impl(state: &RuntimeState) -> Response {
             added_by: bot.added_by,
             permissions: bot.permissions.clone(),
             autonomous_permissions: bot.autonomous_permissions.clone(),
+            spend_allowances: Vec::new(),
         })
         .collect();
 
@@ -68,6 +69,9 @@ fn initial_state_impl(state: &RuntimeState) -> Response {
         next_daily_claim: state.data.streak.next_claim(),
         is_unique_person: state.data.unique_person_proof.is_some(),
         wallet_config: state.data.wallet_config.value.clone(),
+        notification_routing: state.data.notification_routing.value.clone(),
+        notification_preferences: state.data.notification_preferences.value.clone(),
+        keyword_alerts: state.data.keyword_alerts.value.clone(),
         referrals: state.data.referrals.list(),
         message_activity_summary: state.data.message_activity_events.summary(),
         bots,
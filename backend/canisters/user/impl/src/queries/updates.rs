@@ -43,6 +43,12 @@ fn updates_impl(updates_since: TimestampMillis, state: &RuntimeState) -> Respons
         .is_some_and(|p| p.timestamp > updates_since);
 
     let wallet_config = state.data.wallet_config.if_set_after(updates_since).cloned();
+    let notification_preferences = state.data.notification_preferences.if_set_after(updates_since).cloned();
+    let keyword_alerts = state.data.keyword_alerts.if_set_after(updates_since).cloned();
+    let notification_routing = match state.data.notification_routing.if_set_after(updates_since) {
+        Some(routing) => OptionUpdate::from_update(routing.clone()),
+        None => OptionUpdate::NoChange,
+    };
     let referrals = state.data.referrals.updated_since(updates_since);
     let streak_insurance_updated = state.data.streak.insurance_last_updated() > updates_since;
     let btc_address_updated = state.data.btc_address.as_ref().is_some_and(|a| a.timestamp > updates_since);
@@ -54,6 +60,9 @@ fn updates_impl(updates_since: TimestampMillis, state: &RuntimeState) -> Respons
         || avatar_id.has_update()
         || suspended.is_some()
         || wallet_config.is_some()
+        || notification_routing.has_update()
+        || notification_preferences.is_some()
+        || keyword_alerts.is_some()
         || pin_number_updated
         || is_unique_person_updated
         || !referrals.is_empty()
@@ -174,6 +183,7 @@ fn updates_impl(updates_since: TimestampMillis, state: &RuntimeState) -> Respons
                             added_by: bot.added_by,
                             permissions: bot.permissions.clone(),
                             autonomous_permissions: bot.autonomous_permissions.clone(),
+                            spend_allowances: Vec::new(),
                         });
                     }
                 }
@@ -209,6 +219,9 @@ fn updates_impl(updates_since: TimestampMillis, state: &RuntimeState) -> Respons
         next_daily_claim: state.data.streak.next_claim(),
         is_unique_person: is_unique_person_updated.then_some(true),
         wallet_config,
+        notification_routing,
+        notification_preferences,
+        keyword_alerts,
         referrals,
         message_activity_summary,
         bots_added_or_updated,
@@ -7,7 +7,9 @@ use chat_events::{MessageContentInternal, MessageReminderContentInternal};
 use constants::{MINUTE_IN_MS, OPENCHAT_BOT_USER_ID, SECOND_IN_MS};
 use serde::{Deserialize, Serialize};
 use tracing::error;
-use types::{BlobReference, Chat, ChatId, CommunityId, EventIndex, MessageId, MessageIndex, P2PSwapStatus, UserId};
+use types::{
+    BlobReference, Chat, ChatId, CommunityId, EventIndex, MessageId, MessageIndex, P2PSwapStatus, TimestampMillis, UserId,
+};
 use user_canister::{C2CReplyContext, UserCanisterEvent};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -25,6 +27,7 @@ pub enum TimerJob {
     SendMessageToChannel(Box<SendMessageToChannelJob>),
     MarkVideoCallEnded(MarkVideoCallEndedJob),
     ClaimOrResetStreakInsurance(ClaimOrResetStreakInsuranceJob),
+    NotifyChatsOfChitEarned(NotifyChatsOfChitEarnedJob),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -120,6 +123,11 @@ pub struct MarkVideoCallEndedJob(pub user_canister::end_video_call_v2::Args);
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ClaimOrResetStreakInsuranceJob;
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NotifyChatsOfChitEarnedJob {
+    pub timestamp: TimestampMillis,
+}
+
 impl Job for TimerJob {
     fn execute(self) {
         let can_borrow_state = can_borrow_state();
@@ -141,6 +149,7 @@ impl Job for TimerJob {
             TimerJob::SendMessageToChannel(job) => job.execute(),
             TimerJob::MarkVideoCallEnded(job) => job.execute(),
             TimerJob::ClaimOrResetStreakInsurance(job) => job.execute(),
+            TimerJob::NotifyChatsOfChitEarned(job) => job.execute(),
         }
 
         if can_borrow_state {
@@ -419,3 +428,9 @@ impl Job for ClaimOrResetStreakInsuranceJob {
         });
     }
 }
+
+impl Job for NotifyChatsOfChitEarnedJob {
+    fn execute(self) {
+        mutate_state(|state| state.report_chit_earned_to_chats(self.timestamp));
+    }
+}
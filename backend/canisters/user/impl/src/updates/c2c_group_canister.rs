@@ -23,7 +23,10 @@ fn c2c_notify_group_canister_events_impl(args: Args, state: &mut RuntimeState) -
             .check(caller, event.created_at, event.idempotency_id)
         {
             match event.value {
-                GroupCanisterEvent::MessageActivity(event) => state.data.push_message_activity(event, now),
+                GroupCanisterEvent::MessageActivity(event) => {
+                    state.push_outbound_notification_for_activity(&event);
+                    state.data.push_message_activity(event, now);
+                }
                 GroupCanisterEvent::Achievement(achievement) => {
                     awarded_achievement |= state.data.award_achievement(achievement, now);
                 }
@@ -74,6 +74,9 @@ fn process_event(event: LocalUserIndexEvent, state: &mut RuntimeState) {
                     .join(ev.chat_id, ev.local_user_index_canister_id, ev.latest_message_index, now);
 
                 state.data.hot_group_exclusions.remove(&ev.chat_id, now);
+                if !state.data.keyword_alerts.value.is_empty() {
+                    state.sync_keyword_alerts_to_group(ev.chat_id);
+                }
                 state.award_achievement_and_notify(Achievement::JoinedGroup, now);
             }
         }
@@ -102,6 +105,10 @@ fn process_event(event: LocalUserIndexEvent, state: &mut RuntimeState) {
                         .collect(),
                     now,
                 );
+                // Joining a channel also triggers this event, so newly joined channels pick up the user's keywords
+                if !state.data.keyword_alerts.value.is_empty() {
+                    state.sync_keyword_alerts_to_community(ev.community_id);
+                }
                 state.award_achievement_and_notify(Achievement::JoinedCommunity, now);
             }
         }
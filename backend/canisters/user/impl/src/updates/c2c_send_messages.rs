@@ -7,8 +7,8 @@ use oc_error_codes::OCErrorCode;
 use rand::Rng;
 use types::{
     BotCaller, BotMessageContext, CanisterId, Chat, ContentValidationError, DirectMessageNotification, EventWrapper, FcmData,
-    Message, MessageContent, MessageId, MessageIndex, SenderContext, TimestampMillis, User, UserId, UserNotificationPayload,
-    UserType,
+    Message, MessageContent, MessageId, MessageIndex, NotificationCategory, OutboundNotificationPayload, SenderContext,
+    TimestampMillis, User, UserId, UserNotificationPayload, UserType,
 };
 use user_canister::{C2CReplyContext, MessageActivity, MessageActivityEvent};
 
@@ -197,7 +197,11 @@ pub(crate) fn handle_message_impl(
         chat.mark_read_up_to(message_event.event.message_index, false, args.now);
     }
 
-    if !args.mute_notification && !chat.notifications_muted.value && !state.data.suspended.value {
+    // Muted chats are still notified if the user receives them as part of their digest
+    let notifications_muted = chat.notifications_muted.value;
+    let digest_only = notifications_muted && state.data.notification_preferences.value.digest.is_some();
+
+    if !args.mute_notification && (!notifications_muted || digest_only) && !state.data.suspended.value {
         let message_type = content.content_type().to_string();
         let message_text = content.notification_text(&args.mentioned, &[]);
         let image_url = content.notification_image_url();
@@ -208,6 +212,22 @@ pub(crate) fn handle_message_impl(
             .set_sender_name_with_alt(&args.sender_display_name, &args.sender_name)
             .set_avatar_id(args.sender_avatar_id);
 
+        let recipient = state.env.canister_id().into();
+
+        if !digest_only {
+            state.push_outbound_notification(OutboundNotificationPayload {
+                recipient,
+                category: NotificationCategory::DirectMessage,
+                chat: Chat::Direct(chat_id),
+                thread_root_message_index,
+                message_index: message_event.event.message_index,
+                sender: Some(args.sender),
+                sender_name: Some(args.sender_display_name.clone().unwrap_or_else(|| args.sender_name.clone())),
+                message_text: message_text.clone(),
+                timestamp: args.now,
+            });
+        }
+
         let notification = UserNotificationPayload::DirectMessage(DirectMessageNotification {
             sender: args.sender,
             thread_root_message_index,
@@ -221,7 +241,6 @@ pub(crate) fn handle_message_impl(
             sender_avatar_id: args.sender_avatar_id,
             crypto_transfer: content.notification_crypto_transfer_details(&[]),
         });
-        let recipient = state.env.canister_id().into();
 
         state.push_notification(Some(args.sender), recipient, notification, fcm_data);
     }
@@ -237,6 +256,8 @@ pub(crate) fn handle_message_impl(
                 activity: MessageActivity::Crypto,
                 timestamp: args.now,
                 user_id: Some(args.sender),
+                sender_name: None,
+                message_text: None,
             },
             args.now,
         );
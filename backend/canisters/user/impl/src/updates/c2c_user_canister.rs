@@ -346,6 +346,8 @@ fn toggle_reaction(args: ToggleReactionArgs, caller_user_id: UserId, state: &mut
                             activity: MessageActivity::Reaction,
                             timestamp: now,
                             user_id: Some(caller_user_id),
+                            sender_name: None,
+                            message_text: None,
                         },
                         now,
                     );
@@ -390,6 +392,8 @@ fn p2p_swap_change_status(args: P2PSwapStatusChange, caller_user_id: UserId, sta
                     activity: MessageActivity::P2PSwapAccepted,
                     timestamp: now,
                     user_id: Some(caller_user_id),
+                    sender_name: None,
+                    message_text: None,
                 },
                 now,
             );
@@ -455,6 +459,8 @@ fn tip_message(args: user_canister::TipMessageArgs, caller_user_id: UserId, stat
                         activity: MessageActivity::Tip,
                         timestamp: now,
                         user_id: Some(caller_user_id),
+                        sender_name: None,
+                        message_text: None,
                     },
                     now,
                 );
@@ -29,6 +29,10 @@ fn claim_daily_chit_impl(args: Args, state: &mut RuntimeState) -> Response {
         if utc_offset_updated {
             // Claim again in case the timezone change has made this possible
             _ = state.data.streak.claim(now);
+
+            if state.data.notification_preferences.value.quiet_hours.is_some() {
+                state.sync_notification_preferences();
+            }
         }
     }
 
@@ -0,0 +1,32 @@
+use crate::guards::caller_is_owner;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use oc_error_codes::OCErrorCode;
+use types::{OCResult, Timestamped};
+use user_canister::confirm_notification_email::*;
+
+#[update(guard = "caller_is_owner", msgpack = true)]
+#[trace]
+fn confirm_notification_email(args: Args) -> Response {
+    execute_update(|state| confirm_notification_email_impl(args, state)).into()
+}
+
+fn confirm_notification_email_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let now = state.env.now();
+
+    let Some(pending) = state.data.pending_email_routing.as_mut() else {
+        return Err(OCErrorCode::InvalidRequest.with_message("No email address awaiting confirmation"));
+    };
+
+    if let Err(error) = pending.verify(&args.code, now) {
+        if pending.is_spent(now) {
+            state.data.pending_email_routing = None;
+        }
+        return Err(error);
+    }
+
+    let routing = state.data.pending_email_routing.take().unwrap().into_routing();
+    state.data.notification_routing = Timestamped::new(Some(routing), now);
+    Ok(())
+}
@@ -30,6 +30,7 @@ pub mod cancel_message_reminder;
 pub mod cancel_p2p_swap;
 pub mod claim_daily_chit;
 pub mod configure_wallet;
+pub mod confirm_notification_email;
 pub mod create_community;
 pub mod create_group;
 pub mod delete_community;
@@ -59,7 +60,10 @@ pub mod set_avatar;
 pub mod set_bio;
 pub mod set_community_indexes;
 pub mod set_contact;
+pub mod set_keyword_alerts;
 pub mod set_message_reminder;
+pub mod set_notification_preferences;
+pub mod set_notification_routing;
 pub mod set_pin_number;
 pub mod start_video_call;
 pub mod swap_tokens;
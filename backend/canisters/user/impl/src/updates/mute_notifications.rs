@@ -20,6 +20,10 @@ fn unmute_notifications(args: Args) -> Response {
 fn toggle_mute_notifications_impl(chat_id: ChatId, mute: bool, state: &mut RuntimeState) -> Response {
     if let Some(direct_chat) = state.data.direct_chats.get_mut(&chat_id) {
         direct_chat.notifications_muted = Timestamped::new(mute, state.env.now());
+
+        if state.data.notification_preferences.value.digest.is_some() {
+            state.sync_notification_preferences();
+        }
     }
 
     Response::Success
@@ -208,8 +208,7 @@ fn c2c_bot_send_message_impl(args: c2c_bot_send_message::Args, state: &mut Runti
             // then edit this message instead of pushing a new one
             if let Some(bot_message) = message.bot_context() {
                 if bot_caller.bot == message.sender
-                    && bot_caller.initiator.user() == bot_message.command.as_ref().map(|c| c.initiator)
-                    && bot_caller.initiator.command() == bot_message.command.as_ref()
+                    && bot_message.matches_initiator(&bot_caller.initiator)
                     && !bot_message.finalised
                 {
                     let edit_message_args = EditMessageArgs {
@@ -0,0 +1,119 @@
+use crate::guards::caller_is_owner;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use oc_error_codes::OCErrorCode;
+use types::{Chat, OCResult};
+use user_canister::KeywordAlerts;
+use user_canister::set_keyword_alerts::*;
+
+const MAX_KEYWORDS: usize = 25;
+const MAX_CHATS_WITH_KEYWORDS: usize = 100;
+const MIN_KEYWORD_LENGTH: usize = 2;
+const MAX_KEYWORD_LENGTH: usize = 50;
+
+#[update(guard = "caller_is_owner", msgpack = true)]
+#[trace]
+fn set_keyword_alerts(args: Args) -> Response {
+    execute_update(|state| set_keyword_alerts_impl(args, state)).into()
+}
+
+fn set_keyword_alerts_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    if matches!(args.chat, Some(Chat::Direct(_))) {
+        return Err(OCErrorCode::InvalidRequest.with_message("Keyword alerts are only supported in groups and channels"));
+    }
+
+    let keywords = normalise(args.keywords)?;
+    check_chat_limit(&state.data.keyword_alerts.value, args.chat, &keywords)?;
+
+    let now = state.env.now();
+    if state.data.keyword_alerts.update(|a| a.set(args.chat, keywords), now) {
+        state.sync_keyword_alerts(args.chat);
+    }
+    Ok(())
+}
+
+fn check_chat_limit(alerts: &KeywordAlerts, chat: Option<Chat>, keywords: &[String]) -> OCResult {
+    if let Some(chat) = chat {
+        if !keywords.is_empty() && alerts.for_chat(&chat).is_empty() && alerts.chats.len() >= MAX_CHATS_WITH_KEYWORDS {
+            return Err(OCErrorCode::InvalidRequest.with_message("Too many chats with keyword alerts"));
+        }
+    }
+    Ok(())
+}
+
+// Keywords are matched case-insensitively against whole words, so they are stored lowercase
+fn normalise(keywords: Vec<String>) -> OCResult<Vec<String>> {
+    let mut normalised: Vec<String> = Vec::with_capacity(keywords.len());
+    for keyword in keywords {
+        let keyword = keyword.trim().to_lowercase();
+        let length = keyword.chars().count();
+        if !(MIN_KEYWORD_LENGTH..=MAX_KEYWORD_LENGTH).contains(&length) {
+            return Err(OCErrorCode::InvalidRequest.with_message("Invalid keyword length"));
+        }
+        if !normalised.contains(&keyword) {
+            normalised.push(keyword);
+        }
+    }
+
+    if normalised.len() > MAX_KEYWORDS {
+        return Err(OCErrorCode::InvalidRequest.with_message("Too many keywords"));
+    }
+    Ok(normalised)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use test_case::test_case;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn group(index: u8) -> Chat {
+        Chat::Group(Principal::from_slice(&[index]).into())
+    }
+
+    #[test]
+    fn keywords_are_trimmed_lowercased_and_deduplicated() {
+        let keywords = normalise(strings(&["  OpenChat ", "openchat", "Open Chat", "CAFÉ"])).unwrap();
+        assert_eq!(keywords, strings(&["openchat", "open chat", "café"]));
+    }
+
+    #[test_case(" a ", false ; "too short after trimming")]
+    #[test_case("ab", true ; "min length")]
+    #[test_case("éé", true ; "min length multibyte")]
+    #[test_case(&"a".repeat(MAX_KEYWORD_LENGTH), true ; "max length")]
+    #[test_case(&"a".repeat(MAX_KEYWORD_LENGTH + 1), false ; "too long")]
+    #[test_case(&"é".repeat(MAX_KEYWORD_LENGTH), true ; "max length multibyte")]
+    fn keyword_length(keyword: &str, valid: bool) {
+        assert_eq!(normalise(strings(&[keyword])).is_ok(), valid);
+    }
+
+    #[test]
+    fn too_many_keywords_rejected() {
+        let keywords: Vec<_> = (0..=MAX_KEYWORDS).map(|i| format!("keyword{i}")).collect();
+        assert!(normalise(keywords.clone()).is_err());
+
+        // Duplicates don't count towards the limit
+        let mut keywords: Vec<_> = keywords.into_iter().take(MAX_KEYWORDS).collect();
+        keywords.push("KEYWORD0".to_string());
+        assert_eq!(normalise(keywords).unwrap().len(), MAX_KEYWORDS);
+    }
+
+    #[test]
+    fn chat_limit_only_applies_to_new_chats() {
+        let mut alerts = KeywordAlerts::default();
+        for index in 0..MAX_CHATS_WITH_KEYWORDS {
+            alerts.set(Some(group(index as u8)), strings(&["openchat"]));
+        }
+        let keywords = strings(&["release"]);
+
+        assert!(check_chat_limit(&alerts, Some(group(MAX_CHATS_WITH_KEYWORDS as u8)), &keywords).is_err());
+        assert!(check_chat_limit(&alerts, Some(group(0)), &keywords).is_ok());
+        assert!(check_chat_limit(&alerts, Some(group(MAX_CHATS_WITH_KEYWORDS as u8)), &[]).is_ok());
+        assert!(check_chat_limit(&alerts, None, &keywords).is_ok());
+    }
+}
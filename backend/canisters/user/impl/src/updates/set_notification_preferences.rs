@@ -0,0 +1,53 @@
+use crate::guards::caller_is_owner;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use constants::{DAY_IN_MS, MINUTE_IN_MS};
+use oc_error_codes::OCErrorCode;
+use types::{Milliseconds, NotificationPreferences, OCResult, Timestamped};
+use user_canister::set_notification_preferences::*;
+
+const MIN_DIGEST_INTERVAL: Milliseconds = 15 * MINUTE_IN_MS;
+const MAX_DIGEST_INTERVAL: Milliseconds = DAY_IN_MS;
+const MAX_LOW_PRIORITY_CHATS: usize = 1000;
+
+#[update(guard = "caller_is_owner", msgpack = true)]
+#[trace]
+fn set_notification_preferences(args: Args) -> Response {
+    execute_update(|state| set_notification_preferences_impl(args, state)).into()
+}
+
+fn set_notification_preferences_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let preferences = validate(args.preferences)?;
+
+    if preferences != state.data.notification_preferences.value {
+        state.data.notification_preferences = Timestamped::new(preferences, state.env.now());
+        state.sync_notification_preferences();
+    }
+    Ok(())
+}
+
+fn validate(mut preferences: NotificationPreferences) -> OCResult<NotificationPreferences> {
+    if preferences.quiet_hours.is_some_and(|q| !q.is_valid()) {
+        return Err(OCErrorCode::InvalidRequest.with_message("Invalid quiet hours"));
+    }
+
+    if let Some(digest) = preferences.digest.as_mut() {
+        if !(MIN_DIGEST_INTERVAL..=MAX_DIGEST_INTERVAL).contains(&digest.interval) {
+            return Err(OCErrorCode::InvalidRequest.with_message("Invalid digest interval"));
+        }
+
+        let mut unique = Vec::with_capacity(digest.low_priority_chats.len());
+        for chat in digest.low_priority_chats.drain(..) {
+            if !unique.contains(&chat) {
+                unique.push(chat);
+            }
+        }
+        if unique.len() > MAX_LOW_PRIORITY_CHATS {
+            return Err(OCErrorCode::InvalidRequest.with_message("Too many low priority chats"));
+        }
+        digest.low_priority_chats = unique;
+    }
+
+    Ok(preferences)
+}
@@ -37,11 +37,11 @@ async fn tip_message_impl(args: Args) -> Response {
         created: now_nanos,
     });
     // Make the crypto transfer
-    match process_transaction(pending_transfer).await {
-        Ok(Ok(_)) => {}
+    let block_index = match process_transaction(pending_transfer).await {
+        Ok(Ok(completed)) => completed.index(),
         Ok(Err(failed)) => return Error(OCErrorCode::TransferFailed.with_message(failed.error_message())),
         Err(error) => return Error(error.into()),
-    }
+    };
 
     mutate_state(|state| state.award_achievement_and_notify(Achievement::TippedMessage, state.env.now()));
 
@@ -49,8 +49,9 @@ async fn tip_message_impl(args: Args) -> Response {
         PrepareResult::Direct(tip_message_args) => {
             mutate_state(|state| tip_direct_chat_message(tip_message_args, args.decimals, state))
         }
-        PrepareResult::Group(group_id, c2c_args) => {
+        PrepareResult::Group(group_id, mut c2c_args) => {
             use group_canister::c2c_tip_message::Response;
+            c2c_args.block_index = Some(block_index);
             match group_canister_c2c_client::c2c_tip_message(group_id.into(), &c2c_args).await {
                 Ok(Response::Success) => Success,
                 Ok(Response::Error(error)) => Error(error),
@@ -60,8 +61,9 @@ async fn tip_message_impl(args: Args) -> Response {
                 }
             }
         }
-        PrepareResult::Channel(community_id, c2c_args) => {
+        PrepareResult::Channel(community_id, mut c2c_args) => {
             use community_canister::c2c_tip_message::Response;
+            c2c_args.block_index = Some(block_index);
             match community_canister_c2c_client::c2c_tip_message(community_id.into(), &c2c_args).await {
                 Ok(Response::Success) => Success,
                 Ok(Response::Error(error)) => Error(error),
@@ -120,6 +122,7 @@ fn prepare(args: &Args, state: &mut RuntimeState) -> OCResult<(PrepareResult, Ti
                         decimals: args.decimals,
                         username: state.data.username.value.clone(),
                         display_name: state.data.display_name.value.clone(),
+                        block_index: None,
                     },
                 ),
                 now_nanos,
@@ -138,6 +141,7 @@ fn prepare(args: &Args, state: &mut RuntimeState) -> OCResult<(PrepareResult, Ti
                         decimals: args.decimals,
                         username: state.data.username.value.clone(),
                         display_name: state.data.display_name.value.clone(),
+                        block_index: None,
                     },
                 ),
                 now_nanos,
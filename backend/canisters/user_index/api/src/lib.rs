@@ -1,9 +1,9 @@
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 use types::{
-    BotInstallationLocation, CanisterId, ChannelLatestMessageIndex, ChatId, CommunityId, MessageContent, MessageContentInitial,
-    MessageId, MessageIndex, NotifyChit, StreakInsuranceClaim, StreakInsurancePayment, TimestampMillis, UniquePersonProof,
-    User, UserId,
+    BotInstallationLocation, BotPermissions, CanisterId, ChannelLatestMessageIndex, ChatId, CommunityId, MessageContent,
+    MessageContentInitial, MessageId, MessageIndex, Milliseconds, NotifyChit, StreakInsuranceClaim, StreakInsurancePayment,
+    TimestampMillis, UniquePersonProof, User, UserId, UserNotificationPreferences,
 };
 
 mod lifecycle;
@@ -28,10 +28,13 @@ pub enum LocalUserIndexEvent {
     NotifyStreakInsuranceClaim(Box<StreakInsuranceClaim>),
     BotInstalled(Box<BotInstalled>),
     BotUninstalled(Box<BotUninstalled>),
+    BotPermissionsUpdated(Box<BotPermissionsUpdated>),
+    BotCommandInvoked(Box<BotCommandInvoked>),
     UserBlocked(UserId, UserId),
     UserUnblocked(UserId, UserId),
     SetMaxStreak(UserId, u16),
     NotifyOfUserDeleted(CanisterId, UserId),
+    NotificationPreferencesUpdated(UserId, Box<UserNotificationPreferences>),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -111,6 +114,15 @@ pub struct BotInstalled {
     pub bot_id: UserId,
     pub location: BotInstallationLocation,
     pub installed_by: UserId,
+    #[serde(default)]
+    pub granted_permissions: Option<BotPermissions>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BotPermissionsUpdated {
+    pub bot_id: UserId,
+    pub location: BotInstallationLocation,
+    pub granted_permissions: BotPermissions,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -119,3 +131,12 @@ pub struct BotUninstalled {
     pub location: BotInstallationLocation,
     pub uninstalled_by: UserId,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BotCommandInvoked {
+    pub bot_id: UserId,
+    pub command: String,
+    pub success: bool,
+    pub latency: Milliseconds,
+    pub timestamp: TimestampMillis,
+}
@@ -35,7 +35,11 @@ fn main() {
         std::fs::remove_dir_all(&directory).unwrap();
     }
 
+    generate_ts_method!(user_index, bot_analytics);
+    generate_ts_method!(user_index, bot_installation);
+    generate_ts_method!(user_index, bot_review_queue);
     generate_ts_method!(user_index, bot_updates);
+    generate_ts_method!(user_index, bot_usage_summary);
     generate_ts_method!(user_index, check_username);
     generate_ts_method!(user_index, chit_leaderboard);
     generate_ts_method!(user_index, current_user);
@@ -46,11 +50,11 @@ fn main() {
     generate_ts_method!(user_index, platform_moderators);
     generate_ts_method!(user_index, platform_moderators_group);
     generate_ts_method!(user_index, platform_operators);
-    generate_ts_method!(user_index, public_key);
+    generate_ts_method!(user_index, public_key, candid);
     generate_ts_method!(user_index, referral_metrics);
-    generate_ts_method!(user_index, reported_messages);
+    generate_ts_method!(user_index, reported_messages, candid);
     generate_ts_method!(user_index, search);
-    generate_ts_method!(user_index, suspected_bots);
+    generate_ts_method!(user_index, suspected_bots, candid);
     generate_ts_method!(user_index, user);
     generate_ts_method!(user_index, user_registration_canister);
     generate_ts_method!(user_index, users);
@@ -60,11 +64,13 @@ fn main() {
     generate_ts_method!(user_index, pay_for_diamond_membership);
     generate_ts_method!(user_index, register_bot);
     generate_ts_method!(user_index, remove_bot);
+    generate_ts_method!(user_index, review_bot);
     generate_ts_method!(user_index, set_diamond_membership_fees);
     generate_ts_method!(user_index, set_display_name);
     generate_ts_method!(user_index, set_user_upgrade_concurrency);
     generate_ts_method!(user_index, set_moderation_flags);
     generate_ts_method!(user_index, set_username);
+    generate_ts_method!(user_index, submit_bot_for_review);
     generate_ts_method!(user_index, submit_proof_of_unique_personhood);
     generate_ts_method!(user_index, suspend_user);
     generate_ts_method!(user_index, unsuspend_user);
@@ -0,0 +1,36 @@
+use candid::CandidType;
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Milliseconds, TimestampMillis, UserId};
+
+#[ts_export(user_index, bot_analytics)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub from: TimestampMillis,
+}
+
+#[ts_export(user_index, bot_analytics)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(user_index, bot_analytics)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub daily: Vec<BotCommandDailyStats>,
+}
+
+#[ts_export(user_index, bot_analytics)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BotCommandDailyStats {
+    pub command: String,
+    pub day: TimestampMillis,
+    pub invocations: u32,
+    pub failures: u32,
+    pub average_latency: Milliseconds,
+    pub max_latency: Milliseconds,
+}
@@ -0,0 +1,31 @@
+use candid::CandidType;
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotInstallationLocation, BotPermissions, TimestampMillis, UserId};
+
+#[ts_export(user_index, bot_installation)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub location: BotInstallationLocation,
+}
+
+#[ts_export(user_index, bot_installation)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(BotInstallationDetails),
+    Error(OCError),
+}
+
+#[ts_export(user_index, bot_installation)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct BotInstallationDetails {
+    pub installed_by: UserId,
+    pub installed_at: TimestampMillis,
+    pub pinned_version: u32,
+    pub latest_version: u32,
+    // The additional permissions requested by the latest version which the installer must consent to before the
+    // installation moves onto it. `None` if the installation is already on the latest version.
+    pub consent_required: Option<BotPermissions>,
+}
@@ -0,0 +1,33 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AutonomousConfig, BotCommandDefinition, BotPermissions, Empty, TimestampMillis, UserId};
+
+pub type Args = Empty;
+
+#[ts_export(user_index, bot_review_queue)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[ts_export(user_index, bot_review_queue)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub bots: Vec<BotPendingReview>,
+}
+
+#[ts_export(user_index, bot_review_queue)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct BotPendingReview {
+    pub bot_id: UserId,
+    pub owner: UserId,
+    pub name: String,
+    pub endpoint: String,
+    pub version: u32,
+    pub description: String,
+    pub commands: Vec<BotCommandDefinition>,
+    pub autonomous_config: Option<AutonomousConfig>,
+    pub required_permissions: BotPermissions,
+    pub submitted_at: TimestampMillis,
+}
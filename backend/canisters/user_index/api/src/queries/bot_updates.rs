@@ -1,7 +1,7 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{AutonomousConfig, BotCommandDefinition, BotRegistrationStatus, TimestampMillis, UserId};
+use types::{AutonomousConfig, BotCommandDefinition, BotRegistrationStatus, BotReview, TimestampMillis, UserId};
 
 #[ts_export(user_index, bot_updates)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -37,4 +37,6 @@ pub struct BotDetails {
     pub autonomous_config: Option<AutonomousConfig>,
     pub last_updated: TimestampMillis,
     pub registration_status: BotRegistrationStatus,
+    pub version: u32,
+    pub review: Option<BotReview>,
 }
@@ -0,0 +1,27 @@
+use candid::CandidType;
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Milliseconds, UserId};
+
+#[ts_export(user_index, bot_usage_summary)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+}
+
+#[ts_export(user_index, bot_usage_summary)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(BotUsageSummary),
+    Error(OCError),
+}
+
+#[ts_export(user_index, bot_usage_summary)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct BotUsageSummary {
+    pub installations: u32,
+    pub invocations_last_30_days: u32,
+    pub failures_last_30_days: u32,
+    pub average_latency_last_30_days: Milliseconds,
+}
@@ -1,4 +1,8 @@
+pub mod bot_analytics;
+pub mod bot_installation;
+pub mod bot_review_queue;
 pub mod bot_updates;
+pub mod bot_usage_summary;
 pub mod c2c_lookup_user;
 pub mod check_username;
 pub mod chit_leaderboard;
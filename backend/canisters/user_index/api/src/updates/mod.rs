@@ -24,12 +24,14 @@ pub mod remove_bot;
 pub mod remove_platform_moderator;
 pub mod remove_platform_operator;
 pub mod remove_sms_messages;
+pub mod review_bot;
 pub mod set_diamond_membership_fees;
 pub mod set_display_name;
 pub mod set_max_concurrent_user_canister_upgrades;
 pub mod set_moderation_flags;
 pub mod set_user_upgrade_concurrency;
 pub mod set_username;
+pub mod submit_bot_for_review;
 pub mod submit_proof_of_unique_personhood;
 pub mod suspend_user;
 pub mod unsuspend_user;
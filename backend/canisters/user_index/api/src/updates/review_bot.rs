@@ -0,0 +1,17 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{UnitResult, UserId};
+
+#[ts_export(user_index, review_bot)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    // The version which was reviewed, so that a decision isn't applied to a definition which has since changed
+    pub version: u32,
+    pub approved: bool,
+    // Required when rejecting, so that the owner knows what to fix
+    pub reason: Option<String>,
+}
+
+pub type Response = UnitResult;
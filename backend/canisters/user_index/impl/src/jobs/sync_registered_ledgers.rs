@@ -0,0 +1,52 @@
+use crate::{mutate_state, read_state};
+use constants::HOUR_IN_MS;
+use local_user_index_canister::UserIndexEvent;
+use std::time::Duration;
+use tracing::{error, info, trace};
+use utils::canister_timers::run_now_then_interval;
+
+pub fn start_job() {
+    run_now_then_interval(Duration::from_millis(HOUR_IN_MS), run);
+}
+
+fn run() {
+    ic_cdk::futures::spawn(run_async());
+}
+
+async fn run_async() {
+    let (registry_canister_id, registry_synced_up_to) =
+        read_state(|state| (state.data.registry_canister_id, state.data.registry_synced_up_to));
+
+    match registry_canister_c2c_client::updates(
+        registry_canister_id,
+        &registry_canister::updates::Args {
+            since: Some(registry_synced_up_to),
+        },
+    )
+    .await
+    {
+        Ok(registry_canister::updates::Response::Success(result)) => mutate_state(|state| {
+            let mut updated = false;
+            for token in result.token_details.unwrap_or_default() {
+                // Disabled tokens can't be transferred so are treated as unregistered
+                updated |= if token.enabled {
+                    state.data.registered_ledgers.insert(token.ledger_canister_id)
+                } else {
+                    state.data.registered_ledgers.remove(&token.ledger_canister_id)
+                };
+            }
+            for ledger in result.tokens_uninstalled.unwrap_or_default() {
+                updated |= state.data.registered_ledgers.remove(&ledger);
+            }
+            state.data.registry_synced_up_to = result.last_updated;
+
+            if updated {
+                let ledgers = state.data.registered_ledgers.iter().copied().collect();
+                state.push_event_to_all_local_user_indexes(UserIndexEvent::RegisteredLedgersUpdated(ledgers), None);
+                info!(count = state.data.registered_ledgers.len(), "Registered ledgers updated");
+            }
+        }),
+        Ok(registry_canister::updates::Response::SuccessNoUpdates) => trace!("No registry updates"),
+        Err(error) => error!(?error, "Failed to get registry updates"),
+    }
+}
@@ -1,3 +1,4 @@
+use crate::memory::{get_instruction_counts_data_memory, get_instruction_counts_index_memory};
 use crate::model::group_index_event_batch::GroupIndexEventBatch;
 use crate::model::local_user_index_map::LocalUserIndex;
 use crate::model::storage_index_user_config_batch::StorageIndexUserConfigBatch;
@@ -12,8 +13,11 @@ use constants::DAY_IN_MS;
 use event_store_producer::{EventBuilder, EventStoreClient, EventStoreClientBuilder, EventStoreClientInfo};
 use event_store_producer_cdk_runtime::CdkRuntime;
 use fire_and_forget_handler::FireAndForgetHandler;
+use http_request::{OpenMetrics, OpenMetricsEncoder};
 use icrc_ledger_types::icrc1::account::{Account, Subaccount};
-use local_user_index_canister::UserIndexEvent as LocalUserIndexEvent;
+use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
+use local_user_index_canister::{BotPinnedVersionsUpdated, UserIndexEvent as LocalUserIndexEvent};
+use model::bot_analytics::BotAnalytics;
 use model::chit_leaderboard::ChitLeaderboard;
 use model::external_achievements::{ExternalAchievementMetrics, ExternalAchievements};
 use model::local_user_index_map::LocalUserIndexMap;
@@ -28,7 +32,7 @@ use stable_memory_map::UserIdsKeyPrefix;
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::time::Duration;
-use timer_job_queues::BatchedTimerJobQueue;
+use timer_job_queues::{BatchedTimerJobQueue, TimerJobQueueMetrics};
 use types::{
     BuildVersion, CanisterId, ChatId, ChildCanisterWasms, Cycles, DiamondMembershipFees, Milliseconds, TimestampMillis,
     Timestamped, UserId, UserType,
@@ -158,6 +162,16 @@ impl RuntimeState {
         jobs::sync_events_to_local_user_index_canisters::try_run_now(self);
     }
 
+    pub fn push_bot_pinned_versions_to_local_user_indexes(&mut self, bot_id: UserId) {
+        if let Some(bot) = self.data.users.get_bot(&bot_id) {
+            let pinned_versions = bot.pinned_versions();
+            self.push_event_to_all_local_user_indexes(
+                LocalUserIndexEvent::BotPinnedVersionsUpdated(BotPinnedVersionsUpdated { bot_id, pinned_versions }),
+                None,
+            );
+        }
+    }
+
     pub fn get_random_local_user_index_canister(&mut self) -> CanisterId {
         let canisters: Vec<CanisterId> = self.data.local_index_map.canisters().copied().collect();
         let index: usize = self.env.rng().next_u32() as usize % canisters.len();
@@ -300,7 +314,22 @@ impl RuntimeState {
                 .map(|(c, h)| (*c, hex::encode(h)))
                 .collect(),
             stable_memory_sizes: memory::memory_sizes(),
+            instruction_counts: self.data.instruction_counts_log.iter().collect(),
             streak_insurance_metrics: self.data.streak_insurance_logs.metrics(),
+            timer_job_queues: BTreeMap::from([
+                (
+                    "storage_index_user_sync_queue".to_string(),
+                    self.data.storage_index_user_sync_queue.metrics(),
+                ),
+                (
+                    "storage_index_users_to_remove_queue".to_string(),
+                    self.data.storage_index_users_to_remove_queue.metrics(),
+                ),
+                (
+                    "group_index_event_sync_queue".to_string(),
+                    self.data.group_index_event_sync_queue.metrics(),
+                ),
+            ]),
             canister_ids: CanisterIds {
                 group_index: self.data.group_index_canister_id,
                 notifications_index: self.data.notifications_index_canister_id,
@@ -407,6 +436,18 @@ struct Data {
     pub streak_insurance_logs: StreakInsuranceLogs,
     pub idempotency_checker: IdempotencyChecker,
     pub blocked_users: UserIdsSet,
+    #[serde(default)]
+    pub bot_analytics: BotAnalytics,
+    #[serde(default)]
+    pub registered_ledgers: HashSet<CanisterId>,
+    #[serde(default)]
+    pub registry_synced_up_to: TimestampMillis,
+    #[serde(skip, default = "init_instruction_counts_log")]
+    pub instruction_counts_log: InstructionCountsLog,
+}
+
+fn init_instruction_counts_log() -> InstructionCountsLog {
+    InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
 impl Data {
@@ -490,6 +531,10 @@ impl Data {
             streak_insurance_logs: StreakInsuranceLogs::default(),
             idempotency_checker: IdempotencyChecker::default(),
             blocked_users: UserIdsSet::new(UserIdsKeyPrefix::new_for_blocked_users()),
+            bot_analytics: BotAnalytics::default(),
+            registered_ledgers: HashSet::new(),
+            registry_synced_up_to: 0,
+            instruction_counts_log: init_instruction_counts_log(),
         };
 
         // Register the ProposalsBot
@@ -545,6 +590,15 @@ impl Data {
 
         bands
     }
+
+    pub fn record_instructions_count(&self, function_id: InstructionCountFunctionId, now: TimestampMillis) {
+        let wasm_version = WASM_VERSION.with_borrow(|v| **v);
+        let instructions_count = ic_cdk::api::instruction_counter();
+
+        let _ = self
+            .instruction_counts_log
+            .record(function_id, instructions_count, wasm_version, now);
+    }
 }
 
 #[cfg(test)]
@@ -605,6 +659,10 @@ impl Default for Data {
             streak_insurance_logs: StreakInsuranceLogs::default(),
             idempotency_checker: IdempotencyChecker::default(),
             blocked_users: UserIdsSet::new(UserIdsKeyPrefix::new_for_blocked_users()),
+            bot_analytics: BotAnalytics::default(),
+            registered_ledgers: HashSet::new(),
+            registry_synced_up_to: 0,
+            instruction_counts_log: init_instruction_counts_log(),
         }
     }
 }
@@ -652,10 +710,19 @@ pub struct Metrics {
     pub upload_wasm_chunks_whitelist: Vec<Principal>,
     pub wasm_chunks_uploaded: Vec<(ChildCanisterType, String)>,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
+    pub instruction_counts: Vec<InstructionCountEntry>,
     pub streak_insurance_metrics: StreakInsuranceMetrics,
+    pub timer_job_queues: BTreeMap<String, TimerJobQueueMetrics>,
     pub canister_ids: CanisterIds,
 }
 
+impl OpenMetrics for Metrics {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+        encoder.instruction_counts(&self.instruction_counts);
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct UserMetrics {
     pub now: TimestampMillis,
@@ -2,6 +2,7 @@ use crate::memory::get_upgrades_memory;
 use crate::take_state;
 use canister_tracing_macros::trace;
 use ic_cdk::pre_upgrade;
+use instruction_counts_log::InstructionCountFunctionId;
 use rand::Rng;
 use stable_memory::get_writer;
 use tracing::info;
@@ -24,4 +25,9 @@ fn pre_upgrade() {
     let writer = get_writer(&mut memory);
 
     msgpack::serialize(stable_state, writer).unwrap();
+
+    let now = state.env.now();
+    state
+        .data
+        .record_instructions_count(InstructionCountFunctionId::PreUpgrade, now);
 }
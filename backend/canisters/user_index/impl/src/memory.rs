@@ -6,6 +6,9 @@ use std::collections::BTreeMap;
 
 const UPGRADES: MemoryId = MemoryId::new(0);
 const STABLE_MEMORY_MAP: MemoryId = MemoryId::new(3);
+const CANISTER_LOGS: MemoryId = MemoryId::new(4);
+const INSTRUCTION_COUNTS_INDEX: MemoryId = MemoryId::new(5);
+const INSTRUCTION_COUNTS_DATA: MemoryId = MemoryId::new(6);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -22,8 +25,20 @@ pub fn get_stable_memory_map_memory() -> Memory {
     get_memory(STABLE_MEMORY_MAP)
 }
 
+pub fn get_canister_logs_memory() -> Memory {
+    get_memory(CANISTER_LOGS)
+}
+
+pub fn get_instruction_counts_index_memory() -> Memory {
+    get_memory(INSTRUCTION_COUNTS_INDEX)
+}
+
+pub fn get_instruction_counts_data_memory() -> Memory {
+    get_memory(INSTRUCTION_COUNTS_DATA)
+}
+
 pub fn memory_sizes() -> BTreeMap<u8, u64> {
-    (0u8..=0).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
+    (0u8..=6).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
 }
 
 fn get_memory(id: MemoryId) -> Memory {
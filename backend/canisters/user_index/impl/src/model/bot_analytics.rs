@@ -0,0 +1,145 @@
+use constants::DAY_IN_MS;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use types::{Milliseconds, TimestampMillis, UserId};
+use user_index_canister::bot_analytics::BotCommandDailyStats;
+
+const RETENTION_PERIOD: Milliseconds = 90 * DAY_IN_MS;
+
+// Per-bot, per-command usage aggregated into daily buckets
+#[derive(Serialize, Deserialize, Default)]
+pub struct BotAnalytics {
+    bots: HashMap<UserId, BTreeMap<(TimestampMillis, String), CommandStats>>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CommandStats {
+    invocations: u32,
+    failures: u32,
+    total_latency: u64,
+    max_latency: Milliseconds,
+}
+
+pub struct BotAnalyticsTotals {
+    pub invocations: u32,
+    pub failures: u32,
+    pub average_latency: Milliseconds,
+}
+
+impl BotAnalytics {
+    pub fn record(&mut self, bot_id: UserId, command: String, success: bool, latency: Milliseconds, now: TimestampMillis) {
+        let days = self.bots.entry(bot_id).or_default();
+
+        let cutoff = now.saturating_sub(RETENTION_PERIOD);
+        while let Some(entry) = days.first_entry() {
+            if entry.key().0 >= cutoff {
+                break;
+            }
+            entry.remove();
+        }
+
+        let stats = days.entry((start_of_day(now), command)).or_default();
+        stats.invocations += 1;
+        if !success {
+            stats.failures += 1;
+        }
+        stats.total_latency += latency;
+        stats.max_latency = stats.max_latency.max(latency);
+    }
+
+    pub fn daily_stats(&self, bot_id: &UserId, from: TimestampMillis) -> Vec<BotCommandDailyStats> {
+        let Some(days) = self.bots.get(bot_id) else {
+            return Vec::new();
+        };
+
+        days.range((start_of_day(from), String::new())..)
+            .map(|((day, command), stats)| BotCommandDailyStats {
+                command: command.clone(),
+                day: *day,
+                invocations: stats.invocations,
+                failures: stats.failures,
+                average_latency: stats.total_latency / stats.invocations as u64,
+                max_latency: stats.max_latency,
+            })
+            .collect()
+    }
+
+    pub fn totals(&self, bot_id: &UserId, from: TimestampMillis) -> BotAnalyticsTotals {
+        let mut invocations = 0;
+        let mut failures = 0;
+        let mut total_latency = 0;
+
+        if let Some(days) = self.bots.get(bot_id) {
+            for stats in days.range((start_of_day(from), String::new())..).map(|(_, s)| s) {
+                invocations += stats.invocations;
+                failures += stats.failures;
+                total_latency += stats.total_latency;
+            }
+        }
+
+        BotAnalyticsTotals {
+            invocations,
+            failures,
+            average_latency: if invocations > 0 { total_latency / invocations as u64 } else { 0 },
+        }
+    }
+
+    pub fn remove(&mut self, bot_id: &UserId) {
+        self.bots.remove(bot_id);
+    }
+}
+
+fn start_of_day(timestamp: TimestampMillis) -> TimestampMillis {
+    timestamp - (timestamp % DAY_IN_MS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn stats_aggregated_per_day_and_command() {
+        let mut analytics = BotAnalytics::default();
+        let bot_id = bot();
+
+        analytics.record(bot_id, "weather".to_string(), true, 100, DAY_IN_MS);
+        analytics.record(bot_id, "weather".to_string(), false, 300, DAY_IN_MS + 1);
+        analytics.record(bot_id, "news".to_string(), true, 50, DAY_IN_MS + 2);
+        analytics.record(bot_id, "weather".to_string(), true, 200, 2 * DAY_IN_MS);
+
+        let stats = analytics.daily_stats(&bot_id, 0);
+
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[0].command, "news");
+        assert_eq!(stats[1].command, "weather");
+        assert_eq!(stats[1].day, DAY_IN_MS);
+        assert_eq!(stats[1].invocations, 2);
+        assert_eq!(stats[1].failures, 1);
+        assert_eq!(stats[1].average_latency, 200);
+        assert_eq!(stats[1].max_latency, 300);
+        assert_eq!(stats[2].day, 2 * DAY_IN_MS);
+
+        let totals = analytics.totals(&bot_id, 2 * DAY_IN_MS);
+        assert_eq!(totals.invocations, 1);
+        assert_eq!(totals.average_latency, 200);
+    }
+
+    #[test]
+    fn stats_older_than_retention_period_removed() {
+        let mut analytics = BotAnalytics::default();
+        let bot_id = bot();
+
+        analytics.record(bot_id, "weather".to_string(), true, 100, 0);
+        analytics.record(bot_id, "weather".to_string(), true, 100, RETENTION_PERIOD + DAY_IN_MS);
+
+        let stats = analytics.daily_stats(&bot_id, 0);
+
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].day, RETENTION_PERIOD + DAY_IN_MS);
+    }
+
+    fn bot() -> UserId {
+        Principal::from_slice(&[1]).into()
+    }
+}
@@ -1,12 +1,12 @@
 use group_index_canister::UserIndexEvent as GroupIndexEvent;
-use timer_job_queues::{TimerJobItem, timer_job_batch};
+use timer_job_queues::{TimerJobItem, TimerJobOutcome, timer_job_batch};
 use types::{CanisterId, IdempotentEnvelope};
 use utils::canister::should_retry_failed_c2c_call;
 
 timer_job_batch!(GroupIndexEventBatch, CanisterId, IdempotentEnvelope<GroupIndexEvent>, 1000);
 
 impl TimerJobItem for GroupIndexEventBatch {
-    async fn process(&self) -> Result<(), bool> {
+    async fn process(&self) -> TimerJobOutcome {
         let response = group_index_canister_c2c_client::c2c_user_index(
             self.state,
             &group_index_canister::c2c_user_index::Args {
@@ -16,10 +16,10 @@ impl TimerJobItem for GroupIndexEventBatch {
         .await;
 
         match response {
-            Ok(_) => Ok(()),
+            Ok(_) => TimerJobOutcome::Success,
             Err(error) => {
                 let retry = should_retry_failed_c2c_call(error.reject_code(), error.message());
-                Err(retry)
+                TimerJobOutcome::retry_if(retry)
             }
         }
     }
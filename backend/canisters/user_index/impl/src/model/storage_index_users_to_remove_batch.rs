@@ -1,12 +1,12 @@
 use candid::Principal;
-use timer_job_queues::{TimerJobItem, timer_job_batch};
+use timer_job_queues::{TimerJobItem, TimerJobOutcome, timer_job_batch};
 use types::CanisterId;
 use utils::canister::should_retry_failed_c2c_call;
 
 timer_job_batch!(StorageIndexUsersToRemoveBatch, CanisterId, Principal, 1000);
 
 impl TimerJobItem for StorageIndexUsersToRemoveBatch {
-    async fn process(&self) -> Result<(), bool> {
+    async fn process(&self) -> TimerJobOutcome {
         let response = storage_index_canister_c2c_client::remove_users(
             self.state,
             &storage_index_canister::remove_users::Args {
@@ -16,10 +16,10 @@ impl TimerJobItem for StorageIndexUsersToRemoveBatch {
         .await;
 
         match response {
-            Ok(_) => Ok(()),
+            Ok(_) => TimerJobOutcome::Success,
             Err(error) => {
                 let retry = should_retry_failed_c2c_call(error.reject_code(), error.message());
-                Err(retry)
+                TimerJobOutcome::retry_if(retry)
             }
         }
     }
@@ -3,14 +3,16 @@ use crate::DiamondMembershipUserMetrics;
 use crate::model::diamond_membership_details::DiamondMembershipDetailsInternal;
 use crate::model::user::User;
 use candid::Principal;
+use local_user_index_canister::PinnedBotVersion;
 use search::weighted::{Document as SearchDocument, Query};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::ops::RangeFrom;
 use tracing::info;
 use types::{
-    AutonomousConfig, BotCommandDefinition, BotInstallationLocation, BotMatch, BotRegistrationStatus, CanisterId, CyclesTopUp,
-    Document, Milliseconds, SuspensionDuration, TimestampMillis, UniquePersonProof, UserId, UserType,
+    AutonomousConfig, BotCommandDefinition, BotInstallationLocation, BotMatch, BotPermissions, BotRegistrationStatus,
+    BotReview, CanisterId, CyclesTopUp, Document, Milliseconds, SuspensionDuration, TimestampMillis, UniquePersonProof, UserId,
+    UserType,
 };
 use user_index_canister::bot_updates::BotDetails;
 use utils::case_insensitive_hash_map::CaseInsensitiveHashMap;
@@ -53,6 +55,27 @@ pub struct Bot {
     pub last_updated: TimestampMillis,
     pub installations: HashMap<BotInstallationLocation, InstalledBotDetails>,
     pub registration_status: BotRegistrationStatus,
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub previous_versions: Vec<BotVersion>,
+    #[serde(default)]
+    pub review: Option<BotReview>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BotVersion {
+    pub version: u32,
+    pub description: String,
+    pub commands: Vec<BotCommandDefinition>,
+    pub autonomous_config: Option<AutonomousConfig>,
+    pub superseded: TimestampMillis,
+}
+
+impl BotVersion {
+    pub fn required_permissions(&self) -> BotPermissions {
+        required_permissions(&self.commands, self.autonomous_config.as_ref())
+    }
 }
 
 impl Bot {
@@ -75,6 +98,7 @@ impl Bot {
         location: BotInstallationLocation,
         local_user_index: CanisterId,
         installed_by: UserId,
+        granted_permissions: Option<BotPermissions>,
         installed_at: TimestampMillis,
     ) -> bool {
         self.installations
@@ -84,11 +108,127 @@ impl Bot {
                     local_user_index,
                     installed_by,
                     installed_at,
+                    version: self.version,
+                    granted_permissions,
                 },
             )
             .is_none()
     }
 
+    pub fn required_permissions(&self) -> BotPermissions {
+        required_permissions(&self.commands, self.autonomous_config.as_ref())
+    }
+
+    pub fn required_permissions_at_version(&self, version: u32) -> Option<BotPermissions> {
+        if version == self.version {
+            Some(self.required_permissions())
+        } else {
+            self.previous_versions
+                .iter()
+                .find(|v| v.version == version)
+                .map(|v| v.required_permissions())
+        }
+    }
+
+    // Records the current definition in the version history and replaces it with the new one. Installations only
+    // move onto the new version if it doesn't request any permissions beyond those already granted or required by
+    // their pinned version, otherwise they remain pinned until the installer consents to the new permissions.
+    pub fn update_definition(
+        &mut self,
+        description: String,
+        commands: Vec<BotCommandDefinition>,
+        autonomous_config: Option<AutonomousConfig>,
+        now: TimestampMillis,
+    ) -> u32 {
+        let previous = BotVersion {
+            version: self.version,
+            description: std::mem::replace(&mut self.description, description),
+            commands: std::mem::replace(&mut self.commands, commands),
+            autonomous_config: std::mem::replace(&mut self.autonomous_config, autonomous_config),
+            superseded: now,
+        };
+        self.previous_versions.push(previous);
+        self.version += 1;
+
+        let required = self.required_permissions();
+        for installation in self.installations.values_mut() {
+            // Installations which pre-date versioning have no record of the permissions granted, so they are only moved
+            // on if the new version doesn't require anything beyond what their pinned version required
+            let granted = installation.granted_permissions.clone().unwrap_or_default();
+            let pinned = self
+                .previous_versions
+                .iter()
+                .find(|v| v.version == installation.version)
+                .map(|v| v.required_permissions())
+                .unwrap_or_default();
+
+            if required.is_subset(&granted.union(&pinned)) {
+                installation.version = self.version;
+            }
+        }
+
+        self.version
+    }
+
+    // Called when the installer updates the permissions granted to the bot, which is taken as consent to the latest version
+    pub fn set_installation_permissions(
+        &mut self,
+        location: &BotInstallationLocation,
+        granted_permissions: BotPermissions,
+    ) -> bool {
+        let Some(installation) = self.installations.get_mut(location) else {
+            return false;
+        };
+
+        installation.granted_permissions = Some(granted_permissions);
+        installation.version = self.version;
+        true
+    }
+
+    pub fn is_pinned(&self, location: &BotInstallationLocation) -> bool {
+        self.installations.get(location).is_some_and(|i| i.version != self.version)
+    }
+
+    // Returns the previous versions which installations remain pinned to, along with those installations
+    pub fn pinned_versions(&self) -> Vec<PinnedBotVersion> {
+        let mut installations_by_version: BTreeMap<u32, Vec<BotInstallationLocation>> = BTreeMap::new();
+        for (location, installation) in self.installations.iter() {
+            if installation.version != self.version {
+                installations_by_version
+                    .entry(installation.version)
+                    .or_default()
+                    .push(*location);
+            }
+        }
+
+        installations_by_version
+            .into_iter()
+            .filter_map(|(version, installations)| {
+                self.previous_versions
+                    .iter()
+                    .find(|v| v.version == version)
+                    .map(|v| PinnedBotVersion {
+                        version,
+                        commands: v.commands.clone(),
+                        installations,
+                    })
+            })
+            .collect()
+    }
+
+    // Returns the permissions requested by the latest version which the installer has not yet consented to
+    pub fn permissions_pending_consent(&self, location: &BotInstallationLocation) -> Option<BotPermissions> {
+        let installation = self.installations.get(location)?;
+        if installation.version == self.version {
+            return None;
+        }
+
+        let granted = installation.granted_permissions.clone().unwrap_or_default();
+        let pinned = self.required_permissions_at_version(installation.version).unwrap_or_default();
+
+        Some(self.required_permissions().difference(&granted.union(&pinned)))
+    }
+
     pub fn remove_installation(&mut self, location: &BotInstallationLocation) -> Option<InstalledBotDetails> {
         self.installations.remove(location)
     }
@@ -105,15 +245,30 @@ impl Bot {
             autonomous_config: self.autonomous_config.clone(),
             last_updated: self.last_updated,
             registration_status: self.registration_status.clone(),
+            version: self.version,
+            review: self.review.clone(),
         }
     }
 }
 
+fn required_permissions(commands: &[BotCommandDefinition], autonomous_config: Option<&AutonomousConfig>) -> BotPermissions {
+    commands
+        .iter()
+        .map(|c| &c.permissions)
+        .chain(autonomous_config.map(|c| &c.permissions))
+        .fold(BotPermissions::default(), |acc, p| acc.union(p))
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct InstalledBotDetails {
     pub local_user_index: CanisterId,
     pub installed_by: UserId,
     pub installed_at: TimestampMillis,
+    #[serde(default)]
+    pub version: u32,
+    // `None` for installations made before bots were versioned
+    #[serde(default)]
+    pub granted_permissions: Option<BotPermissions>,
 }
 
 impl UserMap {
@@ -313,15 +468,39 @@ impl UserMap {
         location: BotInstallationLocation,
         local_user_index: CanisterId,
         installed_by: UserId,
+        granted_permissions: Option<BotPermissions>,
         now: TimestampMillis,
     ) -> bool {
         if let Some(bot) = self.bots.get_mut(&bot_id) {
-            bot.add_installation(location, local_user_index, installed_by, now)
+            bot.add_installation(location, local_user_index, installed_by, granted_permissions, now)
+        } else {
+            false
+        }
+    }
+
+    pub fn set_bot_installation_permissions(
+        &mut self,
+        bot_id: UserId,
+        location: &BotInstallationLocation,
+        granted_permissions: BotPermissions,
+    ) -> bool {
+        if let Some(bot) = self.bots.get_mut(&bot_id) {
+            bot.set_installation_permissions(location, granted_permissions)
         } else {
             false
         }
     }
 
+    pub fn set_bot_review(&mut self, bot_id: UserId, review: BotReview, now: TimestampMillis) -> bool {
+        let Some(bot) = self.bots.get_mut(&bot_id) else {
+            return false;
+        };
+
+        bot.review = Some(review);
+        self.bot_updates.insert((now, BotUpdate::Updated(bot_id)));
+        true
+    }
+
     pub fn remove_bot_installation(&mut self, bot_id: UserId, location: &BotInstallationLocation) -> bool {
         if let Some(bot) = self.bots.get_mut(&bot_id) {
             bot.remove_installation(location).is_some()
@@ -732,6 +911,7 @@ pub enum BotUpdate {
 mod tests {
     use super::*;
     use itertools::Itertools;
+    use types::MessagePermission;
 
     #[test]
     fn register_with_no_clashes() {
@@ -866,4 +1046,96 @@ mod tests {
 
         assert!(matches!(user_map.update(updated, 2, false, None), UpdateUserResult::Success));
     }
+
+    #[test]
+    fn installations_stay_pinned_until_broader_permissions_are_granted() {
+        let text_only = BotPermissions::text_only();
+        let text_and_image = text_only.union(&BotPermissions::from_message_permission(MessagePermission::Image));
+
+        let mut bot = test_bot(text_only.clone());
+
+        let local_user_index = Principal::from_slice(&[2]);
+        let installer = Principal::from_slice(&[3]).into();
+        let narrow = BotInstallationLocation::Group(Principal::from_slice(&[4]).into());
+        let broad = BotInstallationLocation::Group(Principal::from_slice(&[5]).into());
+
+        bot.add_installation(narrow, local_user_index, installer, Some(text_only.clone()), 2);
+        bot.add_installation(broad, local_user_index, installer, Some(text_and_image.clone()), 2);
+
+        let version = bot.update_definition(String::new(), vec![test_command(text_and_image.clone())], None, 3);
+
+        assert_eq!(version, 1);
+        assert_eq!(bot.installations[&broad].version, 1);
+        assert_eq!(bot.installations[&narrow].version, 0);
+        assert!(bot.permissions_pending_consent(&broad).is_none());
+        assert_eq!(
+            bot.permissions_pending_consent(&narrow),
+            Some(BotPermissions::from_message_permission(MessagePermission::Image))
+        );
+
+        assert!(bot.set_installation_permissions(&narrow, text_and_image));
+        assert_eq!(bot.installations[&narrow].version, 1);
+        assert!(bot.permissions_pending_consent(&narrow).is_none());
+    }
+
+    #[test]
+    fn legacy_installations_stay_pinned_until_broader_permissions_are_granted() {
+        let text_only = BotPermissions::text_only();
+        let text_and_image = text_only.union(&BotPermissions::from_message_permission(MessagePermission::Image));
+
+        let mut bot = test_bot(text_only.clone());
+        let location = BotInstallationLocation::Group(Principal::from_slice(&[4]).into());
+        bot.add_installation(
+            location,
+            Principal::from_slice(&[2]),
+            Principal::from_slice(&[3]).into(),
+            None,
+            2,
+        );
+
+        bot.update_definition(String::new(), vec![test_command(text_only)], None, 3);
+        assert_eq!(bot.installations[&location].version, 1);
+        assert!(!bot.is_pinned(&location));
+        assert!(bot.pinned_versions().is_empty());
+
+        bot.update_definition(String::new(), vec![test_command(text_and_image)], None, 4);
+        assert_eq!(bot.installations[&location].version, 1);
+        assert!(bot.is_pinned(&location));
+
+        let pinned_versions = bot.pinned_versions();
+        assert_eq!(pinned_versions.len(), 1);
+        assert_eq!(pinned_versions[0].version, 1);
+        assert_eq!(pinned_versions[0].installations, vec![location]);
+    }
+
+    fn test_bot(permissions: BotPermissions) -> Bot {
+        Bot {
+            name: "bot".to_string(),
+            avatar: None,
+            owner: Principal::from_slice(&[1]).into(),
+            endpoint: "https://bot.xyz".to_string(),
+            description: String::new(),
+            commands: vec![test_command(permissions)],
+            autonomous_config: None,
+            last_updated: 1,
+            installations: HashMap::new(),
+            registration_status: BotRegistrationStatus::Public,
+            version: 0,
+            previous_versions: Vec::new(),
+            review: None,
+        }
+    }
+
+    fn test_command(permissions: BotPermissions) -> BotCommandDefinition {
+        BotCommandDefinition {
+            name: "command".to_string(),
+            description: None,
+            placeholder: None,
+            params: Vec::new(),
+            permissions,
+            default_role: None,
+            direct_messages: None,
+            autocomplete_endpoint: None,
+        }
+    }
 }
@@ -0,0 +1,28 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use oc_error_codes::OCErrorCode;
+use user_index_canister::bot_analytics::{Response::*, *};
+
+#[query(candid = true, msgpack = true)]
+fn bot_analytics(args: Args) -> Response {
+    read_state(|state| bot_analytics_impl(args, state))
+}
+
+fn bot_analytics_impl(args: Args, state: &RuntimeState) -> Response {
+    let caller = state.env.caller();
+    let Some(user_id) = state.data.users.get_by_principal(&caller).map(|u| u.user_id) else {
+        return Error(OCErrorCode::InitiatorNotFound.into());
+    };
+
+    let Some(bot) = state.data.users.get_bot(&args.bot_id) else {
+        return Error(OCErrorCode::BotNotFound.into());
+    };
+
+    if bot.owner != user_id {
+        return Error(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    Success(SuccessResult {
+        daily: state.data.bot_analytics.daily_stats(&args.bot_id, args.from),
+    })
+}
@@ -0,0 +1,27 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use oc_error_codes::OCErrorCode;
+use user_index_canister::bot_installation::{Response::*, *};
+
+#[query(candid = true, msgpack = true)]
+fn bot_installation(args: Args) -> Response {
+    read_state(|state| bot_installation_impl(args, state))
+}
+
+fn bot_installation_impl(args: Args, state: &RuntimeState) -> Response {
+    let Some(bot) = state.data.users.get_bot(&args.bot_id) else {
+        return Error(OCErrorCode::BotNotFound.into());
+    };
+
+    let Some(installation) = bot.installations.get(&args.location) else {
+        return Error(OCErrorCode::BotNotFound.with_message("Bot not installed"));
+    };
+
+    Success(BotInstallationDetails {
+        installed_by: installation.installed_by,
+        installed_at: installation.installed_at,
+        pinned_version: installation.version,
+        latest_version: bot.version,
+        consent_required: bot.permissions_pending_consent(&args.location),
+    })
+}
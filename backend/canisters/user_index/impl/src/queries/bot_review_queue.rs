@@ -0,0 +1,37 @@
+use crate::guards::caller_is_platform_moderator;
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use types::BotReviewStatus;
+use user_index_canister::bot_review_queue::{Response::*, *};
+
+#[query(guard = "caller_is_platform_moderator", candid = true, msgpack = true)]
+fn bot_review_queue(_args: Args) -> Response {
+    read_state(bot_review_queue_impl)
+}
+
+fn bot_review_queue_impl(state: &RuntimeState) -> Response {
+    let mut bots: Vec<_> = state
+        .data
+        .users
+        .iter_bots()
+        .filter_map(|(bot_id, bot)| {
+            let review = bot.review.as_ref()?;
+            matches!(review.status, BotReviewStatus::Submitted).then(|| BotPendingReview {
+                bot_id: *bot_id,
+                owner: bot.owner,
+                name: bot.name.clone(),
+                endpoint: bot.endpoint.clone(),
+                version: bot.version,
+                description: bot.description.clone(),
+                commands: bot.commands.clone(),
+                autonomous_config: bot.autonomous_config.clone(),
+                required_permissions: bot.required_permissions(),
+                submitted_at: review.submitted_at,
+            })
+        })
+        .collect();
+
+    bots.sort_by_key(|b| b.submitted_at);
+
+    Success(SuccessResult { bots })
+}
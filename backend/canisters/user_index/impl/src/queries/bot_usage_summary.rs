@@ -0,0 +1,29 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use constants::DAY_IN_MS;
+use oc_error_codes::OCErrorCode;
+use user_index_canister::bot_usage_summary::{Response::*, *};
+
+#[query(candid = true, msgpack = true)]
+fn bot_usage_summary(args: Args) -> Response {
+    read_state(|state| bot_usage_summary_impl(args, state))
+}
+
+fn bot_usage_summary_impl(args: Args, state: &RuntimeState) -> Response {
+    let Some(bot) = state.data.users.get_bot(&args.bot_id) else {
+        return Error(OCErrorCode::BotNotFound.into());
+    };
+
+    let now = state.env.now();
+    let totals = state
+        .data
+        .bot_analytics
+        .totals(&args.bot_id, now.saturating_sub(30 * DAY_IN_MS));
+
+    Success(BotUsageSummary {
+        installations: bot.installations.len() as u32,
+        invocations_last_30_days: totals.invocations,
+        failures_last_30_days: totals.failures,
+        average_latency_last_30_days: totals.average_latency,
+    })
+}
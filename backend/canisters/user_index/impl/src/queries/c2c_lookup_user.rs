@@ -23,6 +23,7 @@ fn c2c_lookup_user_impl(args: Args, state: &RuntimeState) -> Response {
             is_platform_moderator,
             is_platform_operator,
             is_diamond_member,
+            total_chit_earned: user.total_chit_earned(),
         })
     } else {
         UserNotFound
@@ -1,10 +1,11 @@
 use crate::{RuntimeState, read_state};
 use candid::Principal;
+use canister_logger::LogQuery;
 use dataurl::DataUrl;
-use http_request::{AvatarRoute, Route, build_json_response, encode_logs, extract_route, get_document};
+use http_request::{AvatarRoute, Route, build_json_response, encode_logs, encode_metrics, extract_route, get_document};
 use ic_cdk::query;
 use std::collections::BTreeMap;
-use types::{HeaderField, HttpRequest, HttpResponse, TimestampMillis, UserId};
+use types::{HeaderField, HttpRequest, HttpResponse, UserId};
 use utils::time::MonthKey;
 
 #[query]
@@ -18,20 +19,20 @@ fn http_request(request: HttpRequest) -> HttpResponse {
         get_document(route.blob_id, avatar, &format!("avatar/{bot_id}"))
     }
 
-    fn get_errors_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_errors(), since.unwrap_or(0))
+    fn get_errors_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_errors(), query)
     }
 
-    fn get_logs_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_logs(), since.unwrap_or(0))
+    fn get_logs_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_logs(), query)
     }
 
-    fn get_traces_impl(since: Option<TimestampMillis>) -> HttpResponse {
-        encode_logs(canister_logger::export_traces(), since.unwrap_or(0))
+    fn get_traces_impl(query: LogQuery) -> HttpResponse {
+        encode_logs(canister_logger::export_traces(), query)
     }
 
-    fn get_metrics_impl(state: &RuntimeState) -> HttpResponse {
-        build_json_response(&state.metrics())
+    fn get_metrics_impl(request: &HttpRequest, state: &RuntimeState) -> HttpResponse {
+        encode_metrics(request, &state.metrics())
     }
 
     fn get_bot_users(state: &RuntimeState) -> HttpResponse {
@@ -109,10 +110,10 @@ fn http_request(request: HttpRequest) -> HttpResponse {
 
     match extract_route(&request.url) {
         Route::Avatar(route) => read_state(|state| get_avatar_impl(route, state)),
-        Route::Errors(since) => get_errors_impl(since),
-        Route::Logs(since) => get_logs_impl(since),
-        Route::Traces(since) => get_traces_impl(since),
-        Route::Metrics => read_state(get_metrics_impl),
+        Route::Errors(query) => get_errors_impl(query),
+        Route::Logs(query) => get_logs_impl(query),
+        Route::Traces(query) => get_traces_impl(query),
+        Route::Metrics => read_state(|state| get_metrics_impl(&request, state)),
         Route::Other(path, _) => read_state(|state| handle_other_path(path, state)),
         _ => HttpResponse::not_found(),
     }
@@ -130,6 +130,10 @@ fn commit(canister_id: CanisterId, wasm_version: BuildVersion, state: &mut Runti
                 }),
             )
         }
+        state.data.user_index_event_sync_queue.push(
+            canister_id,
+            UserIndexEvent::RegisteredLedgersUpdated(state.data.registered_ledgers.iter().copied().collect()),
+        );
         crate::jobs::sync_events_to_local_user_index_canisters::try_run_now(state);
         Success
     } else {
@@ -15,7 +15,9 @@ use rand::RngCore;
 use stable_memory_map::StableMemoryMap;
 use std::cell::LazyCell;
 use storage_index_canister::add_or_update_users::UserConfig;
-use types::{CanisterId, IdempotentEnvelope, MessageContent, TextContent, TimestampMillis, UserId, UserType};
+use types::{
+    BotInstallationLocation, CanisterId, IdempotentEnvelope, MessageContent, TextContent, TimestampMillis, UserId, UserType,
+};
 use user_index_canister::LocalUserIndexEvent;
 use user_index_canister::c2c_local_user_index::{Response::*, *};
 
@@ -143,13 +145,37 @@ fn handle_event<F: FnOnce() -> TimestampMillis>(
             value: GroupIndexEvent::NotifyOfUserDeleted(c, u),
         }),
         LocalUserIndexEvent::BotInstalled(ev) => {
+            state.data.users.add_bot_installation(
+                ev.bot_id,
+                ev.location,
+                caller,
+                ev.installed_by,
+                ev.granted_permissions,
+                **now,
+            );
+        }
+        LocalUserIndexEvent::BotPermissionsUpdated(ev) => {
+            let was_pinned = is_bot_installation_pinned(ev.bot_id, &ev.location, state);
             state
                 .data
                 .users
-                .add_bot_installation(ev.bot_id, ev.location, caller, ev.installed_by, **now);
+                .set_bot_installation_permissions(ev.bot_id, &ev.location, ev.granted_permissions);
+            if was_pinned {
+                state.push_bot_pinned_versions_to_local_user_indexes(ev.bot_id);
+            }
         }
         LocalUserIndexEvent::BotUninstalled(ev) => {
+            let was_pinned = is_bot_installation_pinned(ev.bot_id, &ev.location, state);
             state.data.users.remove_bot_installation(ev.bot_id, &ev.location);
+            if was_pinned {
+                state.push_bot_pinned_versions_to_local_user_indexes(ev.bot_id);
+            }
+        }
+        LocalUserIndexEvent::BotCommandInvoked(ev) => {
+            state
+                .data
+                .bot_analytics
+                .record(ev.bot_id, ev.command, ev.success, ev.latency, ev.timestamp);
         }
         LocalUserIndexEvent::UserBlocked(user_id, blocked) => {
             state.data.blocked_users.insert((user_id, blocked), ());
@@ -160,6 +186,12 @@ fn handle_event<F: FnOnce() -> TimestampMillis>(
             state.push_event_to_all_local_user_indexes(UserIndexEvent::UserUnblocked(user_id, unblocked), Some(caller));
         }
         LocalUserIndexEvent::SetMaxStreak(user_id, max_streak) => state.data.users.set_max_streak(&user_id, max_streak),
+        LocalUserIndexEvent::NotificationPreferencesUpdated(user_id, preferences) => {
+            state.push_event_to_all_local_user_indexes(
+                UserIndexEvent::NotificationPreferencesUpdated(user_id, preferences),
+                Some(caller),
+            );
+        }
     }
 }
 
@@ -250,3 +282,7 @@ You can change your username at any time by clicking \"Profile settings\" from t
 
     crate::jobs::sync_users_to_identity_canister::try_run_now(state);
 }
+
+fn is_bot_installation_pinned(bot_id: UserId, location: &BotInstallationLocation, state: &RuntimeState) -> bool {
+    state.data.users.get_bot(&bot_id).is_some_and(|bot| bot.is_pinned(location))
+}
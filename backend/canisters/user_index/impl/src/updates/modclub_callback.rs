@@ -99,6 +99,7 @@ fn delete_channel_message(
         message_ids: vec![message_id],
         as_platform_moderator: Some(true),
         new_achievement: false,
+        reason: None,
     };
     fire_and_forget_handler.send(
         canister_id,
@@ -118,6 +119,7 @@ fn delete_group_message(
         message_ids: vec![message_id],
         as_platform_moderator: Some(true),
         new_achievement: false,
+        reason: None,
     };
     fire_and_forget_handler.send(
         canister_id,
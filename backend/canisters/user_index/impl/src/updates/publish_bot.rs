@@ -21,7 +21,7 @@ fn publish_bot(args: Args) -> Response {
     )
 }
 
-fn publish_bot_impl(args: Args, state: &mut RuntimeState) -> Response {
+pub(crate) fn publish_bot_impl(args: Args, state: &mut RuntimeState) -> Response {
     if !state.data.users.publish_bot(args.bot_id, state.env.now()) {
         return Response::NotFound;
     }
@@ -12,7 +12,7 @@ use rand::Rng;
 use std::collections::HashMap;
 use storage_index_canister::add_or_update_users::UserConfig;
 use types::BotRegistrationStatus;
-use types::{UserId, UserType};
+use types::{UserId, UserType, validate_bot_commands};
 use url::Url;
 use user_index_canister::register_bot::{Response::*, *};
 use utils::document::try_parse_data_url;
@@ -76,6 +76,9 @@ fn register_bot_impl(args: Args, state: &mut RuntimeState) -> Response {
             avatar,
             installations: HashMap::new(),
             registration_status: BotRegistrationStatus::Private(args.permitted_install_location),
+            version: 0,
+            previous_versions: Vec::new(),
+            review: None,
         }),
     );
 
@@ -146,6 +149,8 @@ fn validate_request(args: &Args, owner_id: UserId, state: &RuntimeState) -> Resu
         return Err("too many commands".to_string());
     }
 
+    validate_bot_commands(&args.definition.commands)?;
+
     if Principal::from_text(&args.endpoint).is_err() && Url::parse(&args.endpoint).is_err() {
         return Err("endpoint invalid".to_string());
     }
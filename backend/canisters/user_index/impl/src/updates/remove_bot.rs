@@ -47,6 +47,7 @@ fn remove_bot_impl(args: Args, deleted_by: Option<UserId>, state: &mut RuntimeSt
     };
 
     state.delete_user(args.bot_id, deleted_by.is_some());
+    state.data.bot_analytics.remove(&args.bot_id);
 
     state.push_event_to_all_local_user_indexes(
         UserIndexEvent::BotRemoved(BotRemoved {
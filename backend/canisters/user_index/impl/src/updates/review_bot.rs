@@ -0,0 +1,63 @@
+use crate::guards::caller_is_platform_moderator;
+use crate::updates::publish_bot::publish_bot_impl;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use oc_error_codes::OCErrorCode;
+use types::{BotReviewStatus, OCResult};
+use user_index_canister::review_bot::*;
+
+const MAX_REASON_LENGTH: usize = 1000;
+
+#[update(guard = "caller_is_platform_moderator", candid = true, msgpack = true)]
+#[trace]
+fn review_bot(args: Args) -> Response {
+    mutate_state(|state| review_bot_impl(args, state)).into()
+}
+
+fn review_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let caller = state.env.caller();
+    let reviewer = state
+        .data
+        .users
+        .get_by_principal(&caller)
+        .ok_or(OCErrorCode::InitiatorNotFound)?
+        .user_id;
+
+    let bot = state.data.users.get_bot(&args.bot_id).ok_or(OCErrorCode::BotNotFound)?;
+
+    let Some(mut review) = bot.review.clone().filter(|r| matches!(r.status, BotReviewStatus::Submitted)) else {
+        return Err(OCErrorCode::InvalidRequest.with_message("Bot has not been submitted for review"));
+    };
+
+    // The owner may have updated the bot since it was submitted, in which case it must be resubmitted
+    if args.version != review.version || args.version != bot.version {
+        return Err(OCErrorCode::BotVersionMismatch.into());
+    }
+
+    review.status = if args.approved {
+        BotReviewStatus::Approved
+    } else {
+        match args.reason {
+            Some(reason) if !reason.trim().is_empty() => {
+                if reason.len() > MAX_REASON_LENGTH {
+                    return Err(OCErrorCode::TextTooLong.with_message(MAX_REASON_LENGTH));
+                }
+                BotReviewStatus::Rejected(reason)
+            }
+            _ => return Err(OCErrorCode::InvalidRequest.with_message("A reason must be given when rejecting a bot")),
+        }
+    };
+
+    let now = state.env.now();
+    review.reviewed_by = Some(reviewer);
+    review.reviewed_at = Some(now);
+
+    state.data.users.set_bot_review(args.bot_id, review, now);
+
+    if args.approved {
+        publish_bot_impl(user_index_canister::publish_bot::Args { bot_id: args.bot_id }, state);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::guards::caller_is_openchat_user;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use oc_error_codes::OCErrorCode;
+use types::{BotRegistrationStatus, BotReview, BotReviewStatus, OCResult};
+use user_index_canister::submit_bot_for_review::*;
+
+#[update(guard = "caller_is_openchat_user", candid = true, msgpack = true)]
+#[trace]
+fn submit_bot_for_review(args: Args) -> Response {
+    mutate_state(|state| submit_bot_for_review_impl(args, state)).into()
+}
+
+fn submit_bot_for_review_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let caller = state.env.caller();
+    let owner = state
+        .data
+        .users
+        .get_by_principal(&caller)
+        .ok_or(OCErrorCode::InitiatorNotFound)?;
+
+    if owner.suspension_details.is_some() {
+        return Err(OCErrorCode::InitiatorSuspended.into());
+    }
+
+    let bot = state.data.users.get_bot(&args.bot_id).ok_or(OCErrorCode::BotNotFound)?;
+
+    if bot.owner != owner.user_id {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    if matches!(bot.registration_status, BotRegistrationStatus::Public) {
+        return Err(OCErrorCode::BotAlreadyPublic.into());
+    }
+
+    if bot
+        .review
+        .as_ref()
+        .is_some_and(|r| r.version == bot.version && matches!(r.status, BotReviewStatus::Submitted))
+    {
+        return Err(OCErrorCode::BotReviewPending.into());
+    }
+
+    let now = state.env.now();
+    let review = BotReview {
+        version: bot.version,
+        status: BotReviewStatus::Submitted,
+        submitted_at: now,
+        reviewed_by: None,
+        reviewed_at: None,
+    };
+
+    state.data.users.set_bot_review(args.bot_id, review, now);
+    Ok(())
+}
@@ -7,7 +7,8 @@ use candid::Principal;
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use local_user_index_canister::{BotUpdated, UserIndexEvent};
-use types::OptionUpdate;
+use oc_error_codes::OCErrorCode;
+use types::{OptionUpdate, validate_bot_commands};
 use url::Url;
 use user_index_canister::update_bot::{Response::*, *};
 use utils::document::try_parse_data_url;
@@ -60,9 +61,12 @@ fn update_bot_impl(args: Args, state: &mut RuntimeState) -> Response {
     };
 
     if let Some(definition) = args.definition.as_ref() {
-        bot.description = definition.description.clone();
-        bot.commands = definition.commands.clone();
-        bot.autonomous_config = definition.autonomous_config.clone();
+        bot.update_definition(
+            definition.description.clone(),
+            definition.commands.clone(),
+            definition.autonomous_config.clone(),
+            now,
+        );
     }
 
     let owner_id = bot.owner;
@@ -87,10 +91,9 @@ fn update_bot_impl(args: Args, state: &mut RuntimeState) -> Response {
             }),
             None,
         );
+        state.push_bot_pinned_versions_to_local_user_indexes(args.bot_id);
     }
 
-    // TODO: If there are any new commands or the required permissions have increased for any existing commands,
-    // then notify all the group/communities that have added this bot
     Success
 }
 
@@ -161,6 +164,10 @@ fn validate(args: &Args, state: &RuntimeState) -> Result<(), Response> {
         if definition.commands.len() > MAX_COMMANDS {
             return Err(TooManyCommands);
         }
+
+        if let Err(error) = validate_bot_commands(&definition.commands) {
+            return Err(Error(OCErrorCode::InvalidRequest.with_message(error)));
+        }
     }
 
     Ok(())
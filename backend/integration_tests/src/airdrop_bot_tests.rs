@@ -117,10 +117,12 @@ fn airdrop_end_to_end(v2: bool) {
             channel_id,
             start: start_airdrop,
             algorithm,
+            criteria: None,
+            recurrence: None,
         },
     );
 
-    assert!(matches!(response, airdrop_bot_canister::set_airdrop::Response::Success));
+    assert!(matches!(response, airdrop_bot_canister::set_airdrop::Response::Success(_)));
 
     tick_many(env, 3);
 
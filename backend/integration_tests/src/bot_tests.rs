@@ -6,7 +6,8 @@ use community_canister::c2c_bot_community_events::{
     EventsByIndexArgs as CommunityEventsByIndexArgs, EventsSelectionCriteria as CommunityEventsSelectionCriteria,
 };
 use community_canister::community_events::EventsPageArgs;
-use local_user_index_canister::access_token_v2::{self, BotActionByCommandArgs, BotCommandInitial};
+use constants::MINUTE_IN_MS;
+use local_user_index_canister::access_token_v2::{self, BotActionByCommandArgs, BotActionByComponentArgs, BotCommandInitial};
 use local_user_index_canister::chat_events::{EventsByIndexArgs, EventsSelectionCriteria};
 use pocket_ic::PocketIc;
 use std::collections::HashSet;
@@ -15,11 +16,13 @@ use std::time::Duration;
 use test_case::test_case;
 use testing::rng::{random_from_u128, random_string};
 use types::{
-    AutonomousBotScope, AutonomousConfig, BotActionChatDetails, BotActionScope, BotChatContext, BotCommandArg,
-    BotCommandArgValue, BotCommandDefinition, BotCommandParam, BotCommandParamType, BotDefinition, BotInstallationLocation,
-    BotMessageContent, BotPermissions, CanisterId, Chat, ChatEvent, ChatEventType, ChatPermission, ChatType,
-    CommunityEventType, CommunityPermission, EventIndex, MessageContent, MessageId, MessagePermission, NotificationEnvelope,
-    OptionUpdate, Rules, StringParam, TextContent, UpdatedRules, UserId,
+    AutonomousBotScope, AutonomousConfig, BotActionChatDetails, BotActionScope, BotButton, BotButtonStyle, BotChatContext,
+    BotCommandArg, BotCommandArgValue, BotCommandDefinition, BotCommandMessageRef, BotCommandParam, BotCommandParamType,
+    BotCommandTokenAmount, BotCommunityOrGroupContext, BotComponentValue, BotDefinition, BotInstallationLocation,
+    BotMessageComponent, BotMessageContent, BotPermissions, CanisterId, Chat, ChatEvent, ChatEventType, ChatPermission,
+    ChatType, CommunityEventType, CommunityOrGroup, CommunityPermission, EventIndex, GroupRole, MessageContent, MessageId,
+    MessagePermission, NotificationEnvelope, OptionUpdate, Rules, StringParam, TextContent, TokenAmountParam, UnitResult,
+    UpdatedRules, UserId,
 };
 
 #[test]
@@ -154,6 +157,7 @@ fn e2e_command_bot_test() {
             content: BotMessageContent::Text(TextContent { text: text.clone() }),
             block_level_markdown: false,
             finalised: false,
+            components: None,
         },
     );
 
@@ -191,6 +195,7 @@ fn e2e_command_bot_test() {
             content: BotMessageContent::Text(TextContent { text: text.clone() }),
             block_level_markdown: false,
             finalised: true,
+            components: None,
         },
     );
 
@@ -362,6 +367,7 @@ fn e2e_autonomous_bot_test() {
             content: BotMessageContent::Text(TextContent { text: text.clone() }),
             block_level_markdown: false,
             finalised: true,
+            components: None,
         },
     );
 
@@ -470,6 +476,7 @@ fn create_channel_autonomously() {
             }),
             block_level_markdown: false,
             finalised: true,
+            components: None,
         },
     );
 
@@ -592,6 +599,7 @@ fn read_messages_by_command() {
         permissions: BotPermissions::from_chat_permission(ChatPermission::ReadMessages),
         default_role: None,
         direct_messages: None,
+        autocomplete_endpoint: None,
     }];
 
     let (bot_id, bot_principal) = client::user_index::happy_path::register_bot(
@@ -673,6 +681,232 @@ fn read_messages_by_command() {
     assert!(result.unauthorized.is_empty());
 }
 
+#[test]
+fn command_args_validated_before_issuing_access_token() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let other_group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let local_user_index = canister_ids.local_user_index(env, group_id);
+
+    let command_name = random_string();
+    let (bot_id, _) = client::user_index::happy_path::register_bot(
+        env,
+        owner.principal,
+        canister_ids.user_index,
+        random_string(),
+        "https://my.bot.xyz/".to_string(),
+        BotDefinition {
+            description: random_string(),
+            commands: vec![BotCommandDefinition {
+                name: command_name.clone(),
+                description: None,
+                placeholder: None,
+                params: vec![
+                    BotCommandParam {
+                        name: "text".to_string(),
+                        description: None,
+                        placeholder: None,
+                        required: true,
+                        param_type: BotCommandParamType::StringParam(StringParam {
+                            min_length: 1,
+                            max_length: 10,
+                            choices: Vec::new(),
+                            multi_line: false,
+                        }),
+                        max_values: None,
+                        autocomplete: None,
+                    },
+                    BotCommandParam {
+                        name: "chat".to_string(),
+                        description: None,
+                        placeholder: None,
+                        required: false,
+                        param_type: BotCommandParamType::ChatParam,
+                        max_values: None,
+                        autocomplete: None,
+                    },
+                    BotCommandParam {
+                        name: "message".to_string(),
+                        description: None,
+                        placeholder: None,
+                        required: false,
+                        param_type: BotCommandParamType::MessageParam,
+                        max_values: None,
+                        autocomplete: None,
+                    },
+                    BotCommandParam {
+                        name: "amount".to_string(),
+                        description: None,
+                        placeholder: None,
+                        required: false,
+                        param_type: BotCommandParamType::TokenAmountParam(TokenAmountParam {
+                            ledgers: Vec::new(),
+                            min_amount: None,
+                            max_amount: None,
+                        }),
+                        max_values: None,
+                        autocomplete: None,
+                    },
+                ],
+                permissions: BotPermissions::text_only(),
+                default_role: None,
+                direct_messages: None,
+                autocomplete_endpoint: None,
+            }],
+            autonomous_config: None,
+            default_subscriptions: None,
+            data_encoding: None,
+        },
+    );
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        BotInstallationLocation::Group(group_id),
+        bot_id,
+        BotPermissions::text_only(),
+        None,
+    );
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    let message = client::group::happy_path::send_text_message(env, &owner, group_id, None, random_string(), None);
+    let text_arg = BotCommandArg {
+        name: "text".to_string(),
+        value: BotCommandArgValue::String("hello".to_string()),
+    };
+
+    let access_token = |args: Vec<BotCommandArg>| {
+        client::local_user_index::access_token_v2(
+            env,
+            owner.principal,
+            local_user_index,
+            &access_token_v2::Args::BotActionByCommand(BotActionByCommandArgs {
+                bot_id,
+                command: BotCommandInitial {
+                    name: command_name.clone(),
+                    args,
+                    meta: None,
+                },
+                scope: BotActionScope::Chat(BotActionChatDetails {
+                    chat: Chat::Group(group_id),
+                    thread: None,
+                    message_id: random_from_u128(),
+                    user_message_id: None,
+                }),
+            }),
+        )
+    };
+
+    // Valid args
+    let response = access_token(vec![
+        text_arg.clone(),
+        BotCommandArg {
+            name: "chat".to_string(),
+            value: BotCommandArgValue::Chat(Chat::Group(group_id)),
+        },
+        BotCommandArg {
+            name: "message".to_string(),
+            value: BotCommandArgValue::Message(BotCommandMessageRef {
+                thread: None,
+                message_index: message.message_index,
+                message_id: message.message_id,
+            }),
+        },
+    ]);
+    assert!(matches!(response, access_token_v2::Response::Success(_)), "{response:?}");
+
+    // Missing required arg
+    let response = access_token(Vec::new());
+    assert!(matches!(response, access_token_v2::Response::Error(_)), "{response:?}");
+
+    // Value of the wrong type
+    let response = access_token(vec![BotCommandArg {
+        name: "text".to_string(),
+        value: BotCommandArgValue::Integer(1),
+    }]);
+    assert!(matches!(response, access_token_v2::Response::Error(_)), "{response:?}");
+
+    // Value too long
+    let response = access_token(vec![BotCommandArg {
+        name: "text".to_string(),
+        value: BotCommandArgValue::String("x".repeat(11)),
+    }]);
+    assert!(matches!(response, access_token_v2::Response::Error(_)), "{response:?}");
+
+    // Arg which doesn't match a param
+    let response = access_token(vec![
+        text_arg.clone(),
+        BotCommandArg {
+            name: "unknown".to_string(),
+            value: BotCommandArgValue::Boolean(true),
+        },
+    ]);
+    assert!(matches!(response, access_token_v2::Response::Error(_)), "{response:?}");
+
+    // Ledger which isn't in the registry
+    let response = access_token(vec![
+        text_arg.clone(),
+        BotCommandArg {
+            name: "amount".to_string(),
+            value: BotCommandArgValue::TokenAmount(BotCommandTokenAmount {
+                ledger: Principal::from_slice(&[1, 2, 3]),
+                amount: 100,
+            }),
+        },
+    ]);
+    assert!(matches!(response, access_token_v2::Response::Error(_)), "{response:?}");
+
+    // Chat other than the one the command is used in
+    let response = access_token(vec![
+        text_arg.clone(),
+        BotCommandArg {
+            name: "chat".to_string(),
+            value: BotCommandArgValue::Chat(Chat::Group(other_group_id)),
+        },
+    ]);
+    assert!(matches!(response, access_token_v2::Response::NotAuthorized), "{response:?}");
+
+    // Message id which doesn't match the message index
+    let response = access_token(vec![
+        text_arg.clone(),
+        BotCommandArg {
+            name: "message".to_string(),
+            value: BotCommandArgValue::Message(BotCommandMessageRef {
+                thread: None,
+                message_index: message.message_index,
+                message_id: random_from_u128(),
+            }),
+        },
+    ]);
+    assert!(matches!(response, access_token_v2::Response::NotAuthorized), "{response:?}");
+
+    // Message which doesn't exist
+    let response = access_token(vec![
+        text_arg,
+        BotCommandArg {
+            name: "message".to_string(),
+            value: BotCommandArgValue::Message(BotCommandMessageRef {
+                thread: None,
+                message_index: u32::from(message.message_index).saturating_add(100).into(),
+                message_id: random_from_u128(),
+            }),
+        },
+    ]);
+    assert!(matches!(response, access_token_v2::Response::NotAuthorized), "{response:?}");
+}
+
 #[test]
 fn send_direct_message() {
     let mut wrapper = ENV.deref().get();
@@ -712,10 +946,13 @@ fn send_direct_message() {
                         multi_line: true,
                     }),
                     placeholder: None,
+                    max_values: None,
+                    autocomplete: None,
                 }],
                 permissions: BotPermissions::text_only(),
                 default_role: None,
                 direct_messages: Some(true),
+                autocomplete_endpoint: None,
             }],
             autonomous_config: None,
             default_subscriptions: None,
@@ -779,6 +1016,7 @@ fn send_direct_message() {
             }),
             block_level_markdown: false,
             finalised: true,
+            components: None,
         },
     );
 
@@ -907,6 +1145,7 @@ fn send_multiple_updates_to_same_message(chat_type: ChatType) {
             content: BotMessageContent::Text(TextContent { text: text.clone() }),
             block_level_markdown: false,
             finalised: false,
+            components: None,
         },
     );
 
@@ -928,6 +1167,7 @@ fn send_multiple_updates_to_same_message(chat_type: ChatType) {
             content: BotMessageContent::Text(TextContent { text: text.clone() }),
             block_level_markdown: false,
             finalised: false,
+            components: None,
         },
     );
 
@@ -949,6 +1189,7 @@ fn send_multiple_updates_to_same_message(chat_type: ChatType) {
             content: BotMessageContent::Text(TextContent { text: text.clone() }),
             block_level_markdown: false,
             finalised: true,
+            components: None,
         },
     );
 
@@ -992,6 +1233,7 @@ fn send_multiple_updates_to_same_message(chat_type: ChatType) {
             content: BotMessageContent::Text(TextContent { text: text.clone() }),
             block_level_markdown: false,
             finalised: true,
+            components: None,
         },
     );
 
@@ -1135,6 +1377,717 @@ fn read_community_events() {
     assert!(matches!(events[2], CommunityEventType::RulesChanged));
 }
 
+#[test]
+fn bot_message_components() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let member = client::register_user(env, canister_ids);
+    let non_member = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let local_user_index = canister_ids.local_user_index(env, group_id);
+    client::local_user_index::happy_path::join_group(env, member.principal, local_user_index, group_id);
+
+    let (bot_id, bot_principal) = register_bot(env, &owner, canister_ids.user_index, random_string(), random_string());
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        BotInstallationLocation::Group(group_id),
+        bot_id,
+        BotPermissions::text_only(),
+        Some(BotPermissions::text_only()),
+    );
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    let chat = Chat::Group(group_id);
+    let button = |id: &str, permissions: BotPermissions| {
+        BotMessageComponent::Button(BotButton {
+            id: id.to_string(),
+            label: random_string(),
+            style: BotButtonStyle::Primary,
+            permissions,
+            default_role: None,
+        })
+    };
+
+    // Component ids must be unique
+    let response = client::local_user_index::bot_send_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_send_message::Args {
+            chat_context: BotChatContext::Autonomous(chat),
+            thread: None,
+            message_id: None,
+            replies_to: None,
+            content: BotMessageContent::Text(TextContent { text: random_string() }),
+            block_level_markdown: false,
+            finalised: true,
+            components: Some(vec![
+                button("confirm", BotPermissions::text_only()),
+                button("confirm", BotPermissions::text_only()),
+            ]),
+        },
+    );
+    assert!(matches!(
+        response,
+        local_user_index_canister::bot_send_message::Response::Error(_)
+    ));
+
+    // Send a message with one component the bot can act on and one which requires a permission it hasn't been granted
+    let response = client::local_user_index::bot_send_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_send_message::Args {
+            chat_context: BotChatContext::Autonomous(chat),
+            thread: None,
+            message_id: None,
+            replies_to: None,
+            content: BotMessageContent::Text(TextContent { text: random_string() }),
+            block_level_markdown: false,
+            finalised: true,
+            components: Some(vec![
+                button("confirm", BotPermissions::text_only()),
+                button("promote", BotPermissions::from_chat_permission(ChatPermission::ChangeRoles)),
+            ]),
+        },
+    );
+    let local_user_index_canister::bot_send_message::Response::Success(result) = response else {
+        panic!("'bot_send_message' error: {response:?}");
+    };
+    let message_id = result.message_id;
+
+    let events_response = client::group::happy_path::events_by_index(env, &member, group_id, vec![result.event_index]);
+    let ChatEvent::Message(message) = &events_response.events[0].event else {
+        panic!("Expected event to be a message: {:?}", events_response.events[0]);
+    };
+    assert_eq!(message.bot_context().unwrap().components.len(), 2);
+
+    let access_token_args = |component_id: &str, value: BotComponentValue| {
+        access_token_v2::Args::BotActionByComponent(BotActionByComponentArgs {
+            bot_id,
+            chat,
+            thread: None,
+            message_id,
+            component_id: component_id.to_string(),
+            value,
+            meta: None,
+        })
+    };
+
+    // A component which isn't attached to the message
+    let response = client::local_user_index::access_token_v2(
+        env,
+        member.principal,
+        local_user_index,
+        &access_token_args("unknown", BotComponentValue::Button),
+    );
+    assert!(matches!(response, access_token_v2::Response::NotAuthorized));
+
+    // A value which doesn't match the component type
+    let response = client::local_user_index::access_token_v2(
+        env,
+        member.principal,
+        local_user_index,
+        &access_token_args("confirm", BotComponentValue::Select(vec![random_string()])),
+    );
+    assert!(matches!(response, access_token_v2::Response::NotAuthorized));
+
+    // A component requiring permissions which haven't been granted to the bot
+    let response = client::local_user_index::access_token_v2(
+        env,
+        member.principal,
+        local_user_index,
+        &access_token_args("promote", BotComponentValue::Button),
+    );
+    assert!(matches!(response, access_token_v2::Response::NotAuthorized));
+
+    // A user who isn't a member of the group
+    let response = client::local_user_index::access_token_v2(
+        env,
+        non_member.principal,
+        local_user_index,
+        &access_token_args("confirm", BotComponentValue::Button),
+    );
+    assert!(matches!(response, access_token_v2::Response::NotAuthorized));
+
+    let access_token = match client::local_user_index::access_token_v2(
+        env,
+        member.principal,
+        local_user_index,
+        &access_token_args("confirm", BotComponentValue::Button),
+    ) {
+        access_token_v2::Response::Success(access_token) => access_token,
+        response => panic!("'access_token' error: {response:?}"),
+    };
+
+    // The bot responds to the interaction using the component access token
+    let response = client::local_user_index::bot_send_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_send_message::Args {
+            chat_context: BotChatContext::Command(access_token),
+            thread: None,
+            message_id: None,
+            replies_to: None,
+            content: BotMessageContent::Text(TextContent { text: random_string() }),
+            block_level_markdown: false,
+            finalised: true,
+            components: None,
+        },
+    );
+    let local_user_index_canister::bot_send_message::Response::Success(result) = response else {
+        panic!("'bot_send_message' error: {response:?}");
+    };
+
+    let events_response = client::group::happy_path::events_by_index(env, &member, group_id, vec![result.event_index]);
+    let ChatEvent::Message(message) = &events_response.events[0].event else {
+        panic!("Expected event to be a message: {:?}", events_response.events[0]);
+    };
+    let interaction = message.bot_context().unwrap().interaction.as_ref().unwrap();
+    assert_eq!(interaction.component_id, "confirm");
+    assert_eq!(interaction.message_id, message_id);
+    assert_eq!(interaction.initiator, member.user_id);
+}
+
+#[test]
+fn bot_management_actions() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let member = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let local_user_index = canister_ids.local_user_index(env, group_id);
+    client::local_user_index::happy_path::join_group(env, member.principal, local_user_index, group_id);
+
+    // The bot can ask for every permission it uses but isn't granted `BlockUsers` when installed
+    let requested = BotPermissions::text_only().with_chat(&HashSet::from_iter([
+        ChatPermission::ChangeRoles,
+        ChatPermission::UpdateGroup,
+        ChatPermission::PinMessages,
+        ChatPermission::FollowThreads,
+        ChatPermission::BlockUsers,
+    ]));
+    let granted = BotPermissions::text_only().with_chat(&HashSet::from_iter([
+        ChatPermission::ChangeRoles,
+        ChatPermission::UpdateGroup,
+        ChatPermission::PinMessages,
+        ChatPermission::FollowThreads,
+    ]));
+
+    let (bot_id, bot_principal) = client::user_index::happy_path::register_bot(
+        env,
+        owner.principal,
+        canister_ids.user_index,
+        random_string(),
+        "https://my.bot.xyz/".to_string(),
+        BotDefinition {
+            description: random_string(),
+            commands: Vec::new(),
+            autonomous_config: Some(AutonomousConfig {
+                permissions: requested.clone(),
+            }),
+            default_subscriptions: None,
+            data_encoding: None,
+        },
+    );
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        BotInstallationLocation::Group(group_id),
+        bot_id,
+        BotPermissions::default(),
+        Some(granted),
+    );
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    let chat = Chat::Group(group_id);
+    let chat_context = BotChatContext::Autonomous(chat);
+    let group_context = BotCommunityOrGroupContext::Autonomous(CommunityOrGroup::Group(group_id));
+
+    let response = client::local_user_index::bot_send_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_send_message::Args {
+            chat_context: chat_context.clone(),
+            thread: None,
+            message_id: None,
+            replies_to: None,
+            content: BotMessageContent::Text(TextContent { text: random_string() }),
+            block_level_markdown: false,
+            finalised: true,
+            components: None,
+        },
+    );
+    let local_user_index_canister::bot_send_message::Response::Success(sent) = response else {
+        panic!("'bot_send_message' error: {response:?}");
+    };
+
+    // Edit the message
+    let edited_text = random_string();
+    let response = client::local_user_index::bot_edit_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_edit_message::Args {
+            chat_context: chat_context.clone(),
+            thread: None,
+            message_id: sent.message_id,
+            content: BotMessageContent::Text(TextContent {
+                text: edited_text.clone(),
+            }),
+            block_level_markdown: false,
+        },
+    );
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    let events_response = client::group::happy_path::events_by_index(env, &owner, group_id, vec![sent.event_index]);
+    let ChatEvent::Message(message) = &events_response.events[0].event else {
+        panic!("Expected event to be a message: {:?}", events_response.events[0]);
+    };
+    let MessageContent::Text(text_content) = &message.content else {
+        panic!("Expected message to be text");
+    };
+    assert_eq!(text_content.text, edited_text);
+    assert!(message.edited);
+
+    // The bot can only edit its own messages
+    let user_message = client::group::happy_path::send_text_message(env, &owner, group_id, None, random_string(), None);
+    let response = client::local_user_index::bot_edit_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_edit_message::Args {
+            chat_context: chat_context.clone(),
+            thread: None,
+            message_id: user_message.message_id,
+            content: BotMessageContent::Text(TextContent { text: random_string() }),
+            block_level_markdown: false,
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    // Pin then unpin the message
+    let response = client::local_user_index::bot_pin_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_pin_message::Args {
+            chat_context: chat_context.clone(),
+            message_index: sent.message_index,
+        },
+    );
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    let selected = client::group::happy_path::selected_initial(env, owner.principal, group_id);
+    assert_eq!(selected.pinned_messages, vec![sent.message_index]);
+
+    let response = client::local_user_index::bot_unpin_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_unpin_message::Args {
+            chat_context: chat_context.clone(),
+            message_index: sent.message_index,
+        },
+    );
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    let selected = client::group::happy_path::selected_initial(env, owner.principal, group_id);
+    assert!(selected.pinned_messages.is_empty());
+
+    // Follow then unfollow a thread
+    client::group::happy_path::send_text_message(
+        env,
+        &owner,
+        group_id,
+        Some(user_message.message_index),
+        random_string(),
+        None,
+    );
+
+    let response = client::local_user_index::bot_follow_thread(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_follow_thread::Args {
+            chat_context: chat_context.clone(),
+            thread_root_message_index: Some(user_message.message_index),
+        },
+    );
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    let response = client::local_user_index::bot_unfollow_thread(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_unfollow_thread::Args {
+            chat_context: chat_context.clone(),
+            thread_root_message_index: Some(user_message.message_index),
+        },
+    );
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    // Update the group details
+    let new_name = random_string();
+    let new_description = random_string();
+    let response = client::local_user_index::bot_update_chat_details(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_update_chat_details::Args {
+            chat_context: chat_context.clone(),
+            name: Some(new_name.clone()),
+            description: Some(new_description.clone()),
+            rules: None,
+            avatar: OptionUpdate::NoChange,
+        },
+    );
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    let summary = client::group::happy_path::summary(env, owner.principal, group_id);
+    assert_eq!(summary.name, new_name);
+    assert_eq!(summary.description, new_description);
+
+    // Promote the member to moderator
+    let change_role = |env: &mut PocketIc, user_id: UserId, new_role: GroupRole| {
+        client::local_user_index::bot_change_role(
+            env,
+            bot_principal,
+            local_user_index,
+            &local_user_index_canister::bot_change_role::Args {
+                community_or_group_context: group_context.clone(),
+                channel_id: None,
+                user_id,
+                new_role,
+            },
+        )
+    };
+
+    let response = change_role(env, member.user_id, GroupRole::Moderator);
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    let selected = client::group::happy_path::selected_initial(env, owner.principal, group_id);
+    assert!(
+        selected
+            .participants
+            .iter()
+            .any(|m| m.user_id == member.user_id && matches!(m.role, GroupRole::Moderator))
+    );
+
+    // Bots can't make users owners or change the role of existing owners
+    let response = change_role(env, member.user_id, GroupRole::Owner);
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let response = change_role(env, owner.user_id, GroupRole::Member);
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let selected = client::group::happy_path::selected_initial(env, owner.principal, group_id);
+    assert!(
+        selected
+            .participants
+            .iter()
+            .any(|m| m.user_id == owner.user_id && matches!(m.role, GroupRole::Owner))
+    );
+    assert!(
+        selected
+            .participants
+            .iter()
+            .any(|m| m.user_id == member.user_id && matches!(m.role, GroupRole::Moderator))
+    );
+
+    // The bot hasn't been granted permission to block users
+    let response = client::local_user_index::bot_block_user(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_block_user::Args {
+            community_or_group_context: group_context.clone(),
+            user_id: member.user_id,
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let selected = client::group::happy_path::selected_initial(env, owner.principal, group_id);
+    assert!(selected.blocked_users.is_empty());
+    assert!(selected.participants.iter().any(|m| m.user_id == member.user_id));
+}
+
+#[test]
+fn bot_management_actions_require_permissions() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let member = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let local_user_index = canister_ids.local_user_index(env, group_id);
+    client::local_user_index::happy_path::join_group(env, member.principal, local_user_index, group_id);
+
+    // The bot is only granted permission to send text messages
+    let (bot_id, bot_principal) = register_bot(env, &owner, canister_ids.user_index, random_string(), random_string());
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        BotInstallationLocation::Group(group_id),
+        bot_id,
+        BotPermissions::default(),
+        Some(BotPermissions::text_only()),
+    );
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    let chat_context = BotChatContext::Autonomous(Chat::Group(group_id));
+    let group_context = BotCommunityOrGroupContext::Autonomous(CommunityOrGroup::Group(group_id));
+    let name_before = client::group::happy_path::summary(env, owner.principal, group_id).name;
+    let user_message = client::group::happy_path::send_text_message(env, &owner, group_id, None, random_string(), None);
+    client::group::happy_path::send_text_message(
+        env,
+        &owner,
+        group_id,
+        Some(user_message.message_index),
+        random_string(),
+        None,
+    );
+
+    let response = client::local_user_index::bot_pin_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_pin_message::Args {
+            chat_context: chat_context.clone(),
+            message_index: user_message.message_index,
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let response = client::local_user_index::bot_unpin_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_unpin_message::Args {
+            chat_context: chat_context.clone(),
+            message_index: user_message.message_index,
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let response = client::local_user_index::bot_follow_thread(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_follow_thread::Args {
+            chat_context: chat_context.clone(),
+            thread_root_message_index: Some(user_message.message_index),
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let response = client::local_user_index::bot_unfollow_thread(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_unfollow_thread::Args {
+            chat_context: chat_context.clone(),
+            thread_root_message_index: Some(user_message.message_index),
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let response = client::local_user_index::bot_update_chat_details(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_update_chat_details::Args {
+            chat_context: chat_context.clone(),
+            name: Some(random_string()),
+            description: None,
+            rules: None,
+            avatar: OptionUpdate::NoChange,
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let response = client::local_user_index::bot_change_role(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_change_role::Args {
+            community_or_group_context: group_context.clone(),
+            channel_id: None,
+            user_id: member.user_id,
+            new_role: GroupRole::Moderator,
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let response = client::local_user_index::bot_block_user(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_block_user::Args {
+            community_or_group_context: group_context.clone(),
+            user_id: member.user_id,
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    // A user's message can't be edited by the bot even with permission to send text
+    let response = client::local_user_index::bot_edit_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_edit_message::Args {
+            chat_context: chat_context.clone(),
+            thread: None,
+            message_id: user_message.message_id,
+            content: BotMessageContent::Text(TextContent { text: random_string() }),
+            block_level_markdown: false,
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    // Nothing has changed
+    let selected = client::group::happy_path::selected_initial(env, owner.principal, group_id);
+    assert!(selected.pinned_messages.is_empty());
+    assert!(selected.blocked_users.is_empty());
+    assert!(!selected.participants.iter().any(|m| matches!(m.role, GroupRole::Moderator)));
+    assert_eq!(
+        client::group::happy_path::summary(env, owner.principal, group_id).name,
+        name_before
+    );
+}
+
+#[test]
+fn command_invocation_recorded_when_access_token_issued() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let start = now_millis(env);
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let other_user = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let local_user_index = canister_ids.local_user_index(env, group_id);
+
+    let command_name = random_string();
+    let (bot_id, _) = register_bot(env, &owner, canister_ids.user_index, random_string(), command_name.clone());
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        BotInstallationLocation::Group(group_id),
+        bot_id,
+        BotPermissions::text_only(),
+        None,
+    );
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    let access_token = match client::local_user_index::access_token_v2(
+        env,
+        owner.principal,
+        local_user_index,
+        &access_token_v2::Args::BotActionByCommand(BotActionByCommandArgs {
+            bot_id,
+            command: BotCommandInitial {
+                name: command_name.clone(),
+                args: Vec::new(),
+                meta: None,
+            },
+            scope: BotActionScope::Chat(BotActionChatDetails {
+                chat: Chat::Group(group_id),
+                thread: None,
+                message_id: random_from_u128(),
+                user_message_id: None,
+            }),
+        }),
+    ) {
+        access_token_v2::Response::Success(access_token) => access_token,
+        response => panic!("'access_token' error: {response:?}"),
+    };
+
+    // Only the user who invoked the command can record the invocation
+    let response = client::local_user_index::record_bot_command_invocation(
+        env,
+        other_user.principal,
+        local_user_index,
+        &local_user_index_canister::record_bot_command_invocation::Args {
+            access_token: access_token.clone(),
+        },
+    );
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let response = client::local_user_index::record_bot_command_invocation(
+        env,
+        owner.principal,
+        local_user_index,
+        &local_user_index_canister::record_bot_command_invocation::Args { access_token },
+    );
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    // The bot never acts on the command, so once the access token expires the invocation is recorded as having failed
+    env.advance_time(Duration::from_millis(6 * MINUTE_IN_MS));
+    tick_many(env, 5);
+    env.advance_time(Duration::from_secs(10));
+    tick_many(env, 5);
+
+    let response = client::user_index::bot_analytics(
+        env,
+        owner.principal,
+        canister_ids.user_index,
+        &user_index_canister::bot_analytics::Args { bot_id, from: start },
+    );
+    let user_index_canister::bot_analytics::Response::Success(result) = response else {
+        panic!("'bot_analytics' error: {response:?}");
+    };
+    assert_eq!(result.daily.len(), 1);
+    assert_eq!(result.daily[0].command, command_name);
+    assert_eq!(result.daily[0].invocations, 1);
+    assert_eq!(result.daily[0].failures, 1);
+}
+
 fn register_bot(
     env: &mut PocketIc,
     owner: &User,
@@ -1154,6 +2107,7 @@ fn register_bot(
         permissions: BotPermissions::text_only(),
         default_role: None,
         direct_messages: None,
+        autocomplete_endpoint: None,
     }];
 
     client::user_index::happy_path::register_bot(
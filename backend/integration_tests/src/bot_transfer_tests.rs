@@ -0,0 +1,374 @@
+use crate::env::ENV;
+use crate::utils::{now_millis, tick_many};
+use crate::{CanisterIds, TestEnv, User, client};
+use candid::Principal;
+use constants::{DAY_IN_MS, HOUR_IN_MS, ICP_SYMBOL, ICP_TRANSFER_FEE};
+use oc_error_codes::OCErrorCode;
+use pocket_ic::PocketIc;
+use std::collections::HashSet;
+use std::ops::Deref;
+use std::time::Duration;
+use testing::rng::{random_from_u128, random_string};
+use types::{
+    AutonomousConfig, BotChatContext, BotDefinition, BotInstallationLocation, BotPermissions, BotSpendAllowance, CanisterId,
+    Chat, ChatEvent, ChatId, MessageContent, MessagePermission, UnitResult, UserId,
+};
+
+const ALLOWANCE: u128 = 1_000_000;
+
+#[test]
+fn bot_send_crypto_limited_by_spend_allowance() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        owner,
+        member,
+        group_id,
+        local_user_index,
+        bot_id: _,
+        bot_principal,
+    } = init_test_data(env, canister_ids, *controller);
+
+    let amount = ALLOWANCE / 2 - ICP_TRANSFER_FEE;
+    let send_crypto = |env: &mut PocketIc| {
+        client::local_user_index::bot_send_crypto(
+            env,
+            bot_principal,
+            local_user_index,
+            &local_user_index_canister::bot_send_crypto::Args {
+                chat_context: BotChatContext::Autonomous(Chat::Group(group_id)),
+                thread: None,
+                message_id: None,
+                recipient: member.user_id,
+                ledger: canister_ids.icp_ledger,
+                token_symbol: ICP_SYMBOL.to_string(),
+                amount,
+                fee: ICP_TRANSFER_FEE,
+                caption: None,
+                block_level_markdown: false,
+            },
+        )
+    };
+
+    // The bot hasn't approved the group to spend its funds yet so the transfer fails and the reservation is released
+    let response = send_crypto(env);
+    assert!(
+        matches!(&response, local_user_index_canister::bot_send_message::Response::Error(e) if e.matches_code(OCErrorCode::TransferFailed)),
+        "{response:?}"
+    );
+
+    client::ledger::happy_path::approve(
+        env,
+        bot_principal,
+        canister_ids.icp_ledger,
+        CanisterId::from(group_id),
+        10 * ALLOWANCE,
+    );
+
+    // Both transfers fit within the allowance, which proves that the failed transfer didn't use any of it
+    for _ in 0..2 {
+        let response = send_crypto(env);
+        let local_user_index_canister::bot_send_message::Response::Success(sent) = response else {
+            panic!("'bot_send_crypto' error: {response:?}");
+        };
+
+        let events_response = client::group::happy_path::events_by_index(env, &owner, group_id, vec![sent.event_index]);
+        let ChatEvent::Message(message) = &events_response.events[0].event else {
+            panic!("Expected event to be a message: {:?}", events_response.events[0]);
+        };
+        assert!(matches!(message.content, MessageContent::Crypto(_)));
+    }
+
+    assert_eq!(
+        client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, member.user_id),
+        2 * amount
+    );
+
+    // The allowance is now exhausted so no more funds are transferred
+    let bot_balance = client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, bot_principal);
+    let response = send_crypto(env);
+    assert!(
+        matches!(&response, local_user_index_canister::bot_send_message::Response::Error(e) if e.matches_code(OCErrorCode::BotSpendAllowanceExceeded)),
+        "{response:?}"
+    );
+    assert_eq!(
+        client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, bot_principal),
+        bot_balance
+    );
+    assert_eq!(
+        client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, member.user_id),
+        2 * amount
+    );
+
+    // Once the period has passed the allowance is available again
+    env.advance_time(Duration::from_millis(DAY_IN_MS));
+    let response = send_crypto(env);
+    assert!(
+        matches!(response, local_user_index_canister::bot_send_message::Response::Success(_)),
+        "{response:?}"
+    );
+}
+
+#[test]
+fn bot_tip_message_validated_before_transfer() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        owner,
+        member,
+        group_id,
+        local_user_index,
+        bot_id,
+        bot_principal,
+    } = init_test_data(env, canister_ids, *controller);
+
+    client::ledger::happy_path::approve(
+        env,
+        bot_principal,
+        canister_ids.icp_ledger,
+        CanisterId::from(group_id),
+        10 * ALLOWANCE,
+    );
+
+    let message_id = random_from_u128();
+    let sent = client::group::happy_path::send_text_message(env, &member, group_id, None, random_string(), Some(message_id));
+
+    let amount = 600_000;
+    let tip_message = |env: &mut PocketIc, message_id| {
+        client::local_user_index::bot_tip_message(
+            env,
+            bot_principal,
+            local_user_index,
+            &local_user_index_canister::bot_tip_message::Args {
+                chat_context: BotChatContext::Autonomous(Chat::Group(group_id)),
+                thread: None,
+                message_id,
+                ledger: canister_ids.icp_ledger,
+                token_symbol: ICP_SYMBOL.to_string(),
+                amount,
+                fee: ICP_TRANSFER_FEE,
+                decimals: 8,
+            },
+        )
+    };
+
+    let response = tip_message(env, message_id);
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    let events_response = client::group::happy_path::events_by_index(env, &owner, group_id, vec![sent.event_index]);
+    let ChatEvent::Message(message) = &events_response.events[0].event else {
+        panic!("Expected event to be a message: {:?}", events_response.events[0]);
+    };
+    assert_eq!(message.tips, vec![(canister_ids.icp_ledger, vec![(bot_id, amount)])]);
+    assert_eq!(
+        client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, member.user_id),
+        amount
+    );
+
+    // Tipping a message which doesn't exist is rejected without any funds being transferred
+    let bot_balance = client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, bot_principal);
+    let response = tip_message(env, random_from_u128());
+    assert!(
+        matches!(&response, UnitResult::Error(e) if e.matches_code(OCErrorCode::MessageNotFound)),
+        "{response:?}"
+    );
+    assert_eq!(
+        client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, bot_principal),
+        bot_balance
+    );
+
+    // A second tip would exceed the remaining allowance
+    let response = tip_message(env, message_id);
+    assert!(
+        matches!(&response, UnitResult::Error(e) if e.matches_code(OCErrorCode::BotSpendAllowanceExceeded)),
+        "{response:?}"
+    );
+    assert_eq!(
+        client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, bot_principal),
+        bot_balance
+    );
+    assert_eq!(
+        client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, member.user_id),
+        amount
+    );
+}
+
+#[test]
+fn bot_prize_unclaimed_prizes_refunded_to_bot() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        owner,
+        group_id,
+        local_user_index,
+        bot_principal,
+        ..
+    } = init_test_data(env, canister_ids, *controller);
+
+    client::ledger::happy_path::approve(
+        env,
+        bot_principal,
+        canister_ids.icp_ledger,
+        CanisterId::from(group_id),
+        10 * ALLOWANCE,
+    );
+
+    let send_prize = |env: &mut PocketIc, prizes: Vec<u128>| {
+        client::local_user_index::bot_send_prize(
+            env,
+            bot_principal,
+            local_user_index,
+            &local_user_index_canister::bot_send_prize::Args {
+                chat_context: BotChatContext::Autonomous(Chat::Group(group_id)),
+                message_id: None,
+                ledger: canister_ids.icp_ledger,
+                token_symbol: ICP_SYMBOL.to_string(),
+                prizes,
+                fee: ICP_TRANSFER_FEE,
+                end_date: now_millis(env) + HOUR_IN_MS,
+                caption: None,
+                diamond_only: false,
+                lifetime_diamond_only: false,
+                unique_person_only: false,
+                streak_only: 0,
+                requires_captcha: false,
+            },
+        )
+    };
+
+    // The prizes plus fees exceed the allowance so nothing is transferred
+    let bot_balance = client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, bot_principal);
+    let response = send_prize(env, vec![ALLOWANCE]);
+    assert!(
+        matches!(&response, local_user_index_canister::bot_send_message::Response::Error(e) if e.matches_code(OCErrorCode::BotSpendAllowanceExceeded)),
+        "{response:?}"
+    );
+    assert_eq!(
+        client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, bot_principal),
+        bot_balance
+    );
+
+    let response = send_prize(env, vec![100_000, 200_000]);
+    let local_user_index_canister::bot_send_message::Response::Success(sent) = response else {
+        panic!("'bot_send_prize' error: {response:?}");
+    };
+
+    let events_response = client::group::happy_path::events_by_index(env, &owner, group_id, vec![sent.event_index]);
+    let ChatEvent::Message(message) = &events_response.events[0].event else {
+        panic!("Expected event to be a message: {:?}", events_response.events[0]);
+    };
+    assert!(matches!(message.content, MessageContent::Prize(_)));
+
+    let bot_balance_before_refund = client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, bot_principal);
+    assert!(bot_balance_before_refund < bot_balance);
+
+    // Nobody claims the prizes so once the prize ends they are refunded to the bot's own account
+    env.advance_time(Duration::from_millis(HOUR_IN_MS));
+    tick_many(env, 5);
+
+    let bot_balance_after_refund = client::ledger::happy_path::balance_of(env, canister_ids.icp_ledger, bot_principal);
+    assert!(bot_balance_after_refund > bot_balance_before_refund);
+}
+
+fn init_test_data(env: &mut PocketIc, canister_ids: &CanisterIds, controller: Principal) -> TestData {
+    let owner = client::register_diamond_user(env, canister_ids, controller);
+    let member = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let local_user_index = canister_ids.local_user_index(env, group_id);
+    client::local_user_index::happy_path::join_group(env, member.principal, local_user_index, group_id);
+
+    let permissions = BotPermissions::text_only().with_message(&HashSet::from_iter([
+        MessagePermission::Text,
+        MessagePermission::Crypto,
+        MessagePermission::Prize,
+    ]));
+
+    let (bot_id, bot_principal) = client::user_index::happy_path::register_bot(
+        env,
+        owner.principal,
+        canister_ids.user_index,
+        random_string(),
+        "https://my.bot.xyz/".to_string(),
+        BotDefinition {
+            description: random_string(),
+            commands: Vec::new(),
+            autonomous_config: Some(AutonomousConfig {
+                permissions: permissions.clone(),
+            }),
+            default_subscriptions: None,
+            data_encoding: None,
+        },
+    );
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        BotInstallationLocation::Group(group_id),
+        bot_id,
+        BotPermissions::default(),
+        Some(permissions.clone()),
+    );
+
+    let response = client::group::update_bot(
+        env,
+        owner.principal,
+        group_id.into(),
+        &group_canister::update_bot::Args {
+            bot_id,
+            granted_permissions: BotPermissions::default(),
+            granted_autonomous_permissions: Some(permissions),
+            spend_allowances: Some(vec![BotSpendAllowance {
+                ledger: canister_ids.icp_ledger,
+                amount: ALLOWANCE,
+                period: DAY_IN_MS,
+            }]),
+        },
+    );
+    assert!(
+        matches!(response, group_canister::update_bot::Response::Success),
+        "{response:?}"
+    );
+
+    // Fund the bot's own account, from which it pays for transfers
+    client::ledger::happy_path::transfer(env, controller, canister_ids.icp_ledger, bot_principal, 100 * ALLOWANCE);
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    TestData {
+        owner,
+        member,
+        group_id,
+        local_user_index,
+        bot_id,
+        bot_principal,
+    }
+}
+
+struct TestData {
+    owner: User,
+    member: User,
+    group_id: ChatId,
+    local_user_index: Principal,
+    bot_id: UserId,
+    bot_principal: Principal,
+}
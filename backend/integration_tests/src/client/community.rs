@@ -728,6 +728,7 @@ pub mod happy_path {
                 bot_id,
                 granted_permissions,
                 granted_autonomous_permissions: None,
+                spend_allowances: None,
             },
         );
 
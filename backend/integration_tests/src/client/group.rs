@@ -5,6 +5,7 @@ use ic_stable_structures::memory_manager::MemoryId;
 pub const CHAT_EVENTS_MEMORY_ID: MemoryId = MemoryId::new(3);
 
 // Queries
+generate_msgpack_query_call!(audit_log);
 generate_msgpack_query_call!(events);
 generate_msgpack_query_call!(events_by_index);
 generate_msgpack_query_call!(events_window);
@@ -36,6 +37,7 @@ generate_msgpack_update_call!(remove_participant);
 generate_msgpack_update_call!(remove_reaction);
 generate_msgpack_update_call!(send_message_v2);
 generate_update_call!(start_video_call_v2);
+generate_msgpack_update_call!(timeout_member);
 generate_msgpack_update_call!(toggle_mute_notifications);
 generate_msgpack_update_call!(unblock_user);
 generate_msgpack_update_call!(undelete_messages);
@@ -410,6 +412,7 @@ pub mod happy_path {
                 as_platform_moderator: None,
 
                 new_achievement: false,
+                reason: None,
             },
         );
 
@@ -496,7 +499,11 @@ pub mod happy_path {
             env,
             sender,
             group_chat_id.into(),
-            &group_canister::block_user::Args { user_id },
+            &group_canister::block_user::Args {
+                user_id,
+                duration: None,
+                reason: None,
+            },
         );
 
         match response {
@@ -563,6 +570,7 @@ pub mod happy_path {
                 bot_id,
                 granted_permissions,
                 granted_autonomous_permissions: None,
+                spend_allowances: None,
             },
         );
 
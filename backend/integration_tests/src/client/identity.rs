@@ -11,17 +11,19 @@ generate_msgpack_update_call!(accept_identity_link_via_qr_code);
 generate_msgpack_update_call!(approve_identity_link);
 generate_msgpack_update_call!(create_identity);
 generate_msgpack_update_call!(delete_user);
+generate_msgpack_update_call!(generate_reauthentication_challenge);
 generate_msgpack_update_call!(initiate_identity_link);
 generate_msgpack_update_call!(initiate_identity_link_via_qr_code);
 generate_msgpack_update_call!(prepare_delegation);
 generate_msgpack_update_call!(remove_identity_link);
+generate_msgpack_update_call!(remove_identity_link_v2);
 
 pub mod happy_path {
     use crate::UserAuth;
     use candid::Principal;
     use identity_canister::auth_principals::UserPrincipal;
     use pocket_ic::PocketIc;
-    use types::{CanisterId, SignedDelegation, TimestampMillis};
+    use types::{CanisterId, Empty, SignedDelegation, TimestampMillis};
 
     pub fn create_identity(
         env: &mut PocketIc,
@@ -145,22 +147,16 @@ pub mod happy_path {
         }
     }
 
-    pub fn remove_identity_link(
+    pub fn generate_reauthentication_challenge(
         env: &mut PocketIc,
         sender: Principal,
         identity_canister_id: CanisterId,
-        linked_principal: Principal,
-    ) {
-        let response = super::remove_identity_link(
-            env,
-            sender,
-            identity_canister_id,
-            &identity_canister::remove_identity_link::Args { linked_principal },
-        );
+    ) -> Vec<u8> {
+        let response = super::generate_reauthentication_challenge(env, sender, identity_canister_id, &Empty {});
 
         match response {
-            identity_canister::remove_identity_link::Response::Success => (),
-            response => panic!("'remove_identity_link' error: {response:?}"),
+            identity_canister::generate_reauthentication_challenge::Response::Success(result) => result.challenge,
+            response => panic!("'generate_reauthentication_challenge' error: {response:?}"),
         }
     }
 
@@ -13,10 +13,21 @@ generate_query_call!(notifications);
 generate_query_call!(notifications_v2);
 
 // Updates
+generate_update_call!(bot_block_user);
+generate_update_call!(bot_change_role);
 generate_update_call!(bot_create_channel);
 generate_update_call!(bot_delete_channel);
+generate_update_call!(bot_edit_message);
+generate_update_call!(bot_follow_thread);
+generate_update_call!(bot_pin_message);
+generate_update_call!(bot_send_crypto);
 generate_update_call!(bot_send_message);
+generate_update_call!(bot_send_prize);
 generate_update_call!(bot_subscribe_to_events);
+generate_update_call!(bot_tip_message);
+generate_update_call!(bot_unfollow_thread);
+generate_update_call!(bot_unpin_message);
+generate_update_call!(bot_update_chat_details);
 generate_msgpack_update_call!(install_bot);
 generate_msgpack_update_call!(invite_users_to_channel);
 generate_msgpack_update_call!(invite_users_to_community);
@@ -24,6 +35,7 @@ generate_msgpack_update_call!(invite_users_to_group);
 generate_msgpack_update_call!(join_channel);
 generate_msgpack_update_call!(join_community);
 generate_msgpack_update_call!(join_group);
+generate_update_call!(record_bot_command_invocation);
 generate_msgpack_update_call!(register_user);
 generate_msgpack_update_call!(uninstall_bot);
 
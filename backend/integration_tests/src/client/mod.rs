@@ -1,5 +1,5 @@
 #![allow(dead_code)]
-use crate::identity_tests::sign_in_with_email;
+use crate::identity_tests::sign_in_with_email_using_session_key;
 use crate::utils::tick_many;
 use crate::{CanisterIds, T, User, UserAuth};
 use candid::{CandidType, Principal};
@@ -8,7 +8,7 @@ use rand::random;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use std::time::Duration;
-use testing::rng::random_internet_identity_principal;
+use testing::rng::{random_internet_identity_principal, random_string};
 use types::{CanisterId, CanisterWasm, DiamondMembershipPlanDuration};
 
 mod macros;
@@ -135,9 +135,12 @@ pub fn register_user_with_referrer(env: &mut PocketIc, canister_ids: &CanisterId
 }
 
 pub fn register_user_and_include_auth(env: &mut PocketIc, canister_ids: &CanisterIds) -> (User, UserAuth) {
-    let (auth_principal, public_key, delegation) = sign_in_with_email(env, canister_ids);
+    let email = format!("{}@test.com", random_string());
+    let (auth_principal, public_key, delegation) =
+        sign_in_with_email_using_session_key(env, canister_ids, &email, random::<[u8; 32]>().to_vec());
     let user = register_user_internal(env, canister_ids, None, auth_principal, public_key.clone());
     let user_auth = UserAuth {
+        email,
         auth_principal,
         public_key,
         delegation,
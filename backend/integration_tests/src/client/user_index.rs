@@ -12,6 +12,7 @@ generate_msgpack_query_call!(user);
 generate_msgpack_query_call!(user_registration_canister);
 generate_msgpack_query_call!(users);
 generate_msgpack_query_call!(users_chit);
+generate_query_call!(bot_analytics);
 generate_msgpack_query_call!(bot_updates);
 generate_msgpack_query_call!(explore_bots);
 
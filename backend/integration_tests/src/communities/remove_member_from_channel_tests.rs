@@ -45,6 +45,8 @@ fn remove_member_from_channel_succeeds() {
         &community_canister::remove_member_from_channel::Args {
             channel_id,
             user_id: user2.user_id,
+            ban_duration: None,
+            reason: None,
         },
     );
 
@@ -194,6 +194,7 @@ fn delete_group_message_succeeds() {
             message_ids: vec![message_id],
             as_platform_moderator: None,
             new_achievement: false,
+            reason: None,
         },
     );
     assert!(matches!(
@@ -307,6 +308,7 @@ fn delete_then_undelete_group_message(delay: bool) {
             message_ids: vec![message_id],
             as_platform_moderator: None,
             new_achievement: false,
+            reason: None,
         },
     );
     assert!(matches!(
@@ -385,6 +387,7 @@ fn platform_operators_can_delete_messages(is_platform_moderator: bool) {
             message_ids: vec![message_id],
             as_platform_moderator: Some(true),
             new_achievement: false,
+            reason: None,
         },
     );
     if is_platform_moderator {
@@ -1,8 +1,9 @@
 use crate::client::register_user_and_include_auth;
 use crate::env::ENV;
-use crate::{CanisterIds, TestEnv, client};
+use crate::{CanisterIds, TestEnv, UserAuth, client};
 use candid::Principal;
 use constants::NANOS_PER_MILLISECOND;
+use identity_canister::{DelegationReauthentication, ReauthenticationProof};
 use oc_error_codes::OCErrorCode;
 use pocket_ic::PocketIc;
 use rand::random;
@@ -65,17 +66,56 @@ fn link_and_unlink_auth_identities(delay: bool) {
         return;
     }
 
-    let remove_identity_link_response = client::identity::remove_identity_link(
+    let reauthentication = reauthenticate_via_delegation(env, canister_ids, &user_auth);
+    let remove_identity_link_response = client::identity::remove_identity_link_v2(
         env,
         user_auth.auth_principal,
         canister_ids.identity,
-        &identity_canister::remove_identity_link::Args {
+        &identity_canister::remove_identity_link_v2::Args {
             linked_principal: auth_principal2,
+            reauthentication,
         },
     );
 
     match remove_identity_link_response {
-        identity_canister::remove_identity_link::Response::Success => {
+        identity_canister::remove_identity_link_v2::Response::Success => {
+            let response = client::identity::check_auth_principal_v2(env, auth_principal2, canister_ids.identity, &Empty {});
+
+            assert!(matches!(
+                response,
+                identity_canister::check_auth_principal_v2::Response::NotFound
+            ));
+        }
+        response => panic!("{response:?}"),
+    }
+}
+
+#[test_case(false)]
+#[test_case(true)]
+fn remove_identity_link_v2_requires_recent_authentication(delay: bool) {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let (_, user_auth) = register_user_and_include_auth(env, canister_ids);
+    let auth_principal2 = link_random_identity(env, canister_ids, &user_auth);
+    let reauthentication = reauthenticate_via_delegation(env, canister_ids, &user_auth);
+
+    if delay {
+        env.advance_time(Duration::from_secs(301));
+    }
+
+    let response = client::identity::remove_identity_link_v2(
+        env,
+        user_auth.auth_principal,
+        canister_ids.identity,
+        &identity_canister::remove_identity_link_v2::Args {
+            linked_principal: auth_principal2,
+            reauthentication,
+        },
+    );
+
+    match response {
+        identity_canister::remove_identity_link_v2::Response::Success if !delay => {
             let response = client::identity::check_auth_principal_v2(env, auth_principal2, canister_ids.identity, &Empty {});
 
             assert!(matches!(
@@ -83,10 +123,170 @@ fn link_and_unlink_auth_identities(delay: bool) {
                 identity_canister::check_auth_principal_v2::Response::NotFound
             ));
         }
+        identity_canister::remove_identity_link_v2::Response::Error(e)
+            if delay && e.matches_code(OCErrorCode::ReauthenticationRequired) => {}
         response => panic!("{response:?}"),
     }
 }
 
+#[test]
+fn remove_identity_link_v2_rejects_delegation_not_to_issued_challenge() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let (_, user_auth) = register_user_and_include_auth(env, canister_ids);
+    let auth_principal2 = link_random_identity(env, canister_ids, &user_auth);
+    client::identity::happy_path::generate_reauthentication_challenge(env, user_auth.auth_principal, canister_ids.identity);
+
+    // A recent delegation to some other session key must not be accepted
+    let (_, public_key, delegation) =
+        sign_in_with_email_using_session_key(env, canister_ids, &user_auth.email, random::<[u8; 32]>().to_vec());
+
+    let response = client::identity::remove_identity_link_v2(
+        env,
+        user_auth.auth_principal,
+        canister_ids.identity,
+        &identity_canister::remove_identity_link_v2::Args {
+            linked_principal: auth_principal2,
+            reauthentication: ReauthenticationProof::Delegation(DelegationReauthentication { public_key, delegation }),
+        },
+    );
+
+    assert!(matches!(
+        response,
+        identity_canister::remove_identity_link_v2::Response::Error(e) if e.matches_code(OCErrorCode::ReauthenticationRequired)
+    ));
+}
+
+#[test]
+fn reauthentication_challenge_can_only_be_used_once() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let (_, user_auth) = register_user_and_include_auth(env, canister_ids);
+    let auth_principal2 = link_random_identity(env, canister_ids, &user_auth);
+    let auth_principal3 = link_random_identity(env, canister_ids, &user_auth);
+    let ReauthenticationProof::Delegation(proof) = reauthenticate_via_delegation(env, canister_ids, &user_auth) else {
+        unreachable!()
+    };
+
+    let mut remove = |linked_principal| {
+        client::identity::remove_identity_link_v2(
+            env,
+            user_auth.auth_principal,
+            canister_ids.identity,
+            &identity_canister::remove_identity_link_v2::Args {
+                linked_principal,
+                reauthentication: ReauthenticationProof::Delegation(DelegationReauthentication {
+                    public_key: proof.public_key.clone(),
+                    delegation: proof.delegation.clone(),
+                }),
+            },
+        )
+    };
+
+    assert!(matches!(
+        remove(auth_principal2),
+        identity_canister::remove_identity_link_v2::Response::Success
+    ));
+    assert!(matches!(
+        remove(auth_principal3),
+        identity_canister::remove_identity_link_v2::Response::Error(e) if e.matches_code(OCErrorCode::ReauthenticationRequired)
+    ));
+}
+
+#[test]
+fn remove_identity_link_v1_is_retired() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let (_, user_auth) = register_user_and_include_auth(env, canister_ids);
+    let auth_principal2 = link_random_identity(env, canister_ids, &user_auth);
+
+    let response = client::identity::remove_identity_link(
+        env,
+        user_auth.auth_principal,
+        canister_ids.identity,
+        &identity_canister::remove_identity_link::Args {
+            linked_principal: auth_principal2,
+        },
+    );
+
+    assert!(matches!(
+        response,
+        identity_canister::remove_identity_link::Response::Error(e) if e.matches_code(OCErrorCode::ReauthenticationRequired)
+    ));
+    assert_eq!(
+        client::identity::happy_path::auth_principals(env, user_auth.auth_principal, canister_ids.identity).len(),
+        2
+    );
+}
+
+#[test]
+fn remove_identity_link_v2_rejects_reauthentication_by_another_user() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let (_, user_auth) = register_user_and_include_auth(env, canister_ids);
+    let (_, other_user_auth) = register_user_and_include_auth(env, canister_ids);
+    let auth_principal2 = link_random_identity(env, canister_ids, &user_auth);
+    let challenge =
+        client::identity::happy_path::generate_reauthentication_challenge(env, user_auth.auth_principal, canister_ids.identity);
+    let (_, public_key, delegation) =
+        sign_in_with_email_using_session_key(env, canister_ids, &other_user_auth.email, challenge);
+
+    let response = client::identity::remove_identity_link_v2(
+        env,
+        user_auth.auth_principal,
+        canister_ids.identity,
+        &identity_canister::remove_identity_link_v2::Args {
+            linked_principal: auth_principal2,
+            reauthentication: ReauthenticationProof::Delegation(DelegationReauthentication { public_key, delegation }),
+        },
+    );
+
+    assert!(matches!(
+        response,
+        identity_canister::remove_identity_link_v2::Response::Error(e) if e.matches_code(OCErrorCode::ReauthenticationRequired)
+    ));
+}
+
+// Signs in again using the issued challenge as the session key, which proves the user has just re-authenticated
+fn reauthenticate_via_delegation(
+    env: &mut PocketIc,
+    canister_ids: &CanisterIds,
+    user_auth: &UserAuth,
+) -> ReauthenticationProof {
+    let challenge =
+        client::identity::happy_path::generate_reauthentication_challenge(env, user_auth.auth_principal, canister_ids.identity);
+    let (_, public_key, delegation) = sign_in_with_email_using_session_key(env, canister_ids, &user_auth.email, challenge);
+
+    ReauthenticationProof::Delegation(DelegationReauthentication { public_key, delegation })
+}
+
+fn link_random_identity(env: &mut PocketIc, canister_ids: &CanisterIds, user_auth: &UserAuth) -> Principal {
+    let (auth_principal, public_key) = random_internet_identity_principal();
+
+    client::identity::happy_path::initiate_identity_link(
+        env,
+        auth_principal,
+        canister_ids.identity,
+        public_key,
+        true,
+        user_auth.auth_principal,
+    );
+    client::identity::happy_path::approve_identity_link(
+        env,
+        user_auth.auth_principal,
+        canister_ids.identity,
+        user_auth.delegation.clone(),
+        user_auth.public_key.clone(),
+        auth_principal,
+    );
+
+    auth_principal
+}
+
 #[test]
 fn link_identities_via_qr_code() {
     let mut wrapper = ENV.deref().get();
@@ -143,14 +343,21 @@ fn flag_ii_principal(is_ii_principal: bool) {
 
 pub(crate) fn sign_in_with_email(env: &mut PocketIc, canister_ids: &CanisterIds) -> (Principal, Vec<u8>, SignedDelegation) {
     let email = format!("{}@test.com", random_string());
-    let session_key = random::<[u8; 32]>().to_vec();
+    sign_in_with_email_using_session_key(env, canister_ids, &email, random::<[u8; 32]>().to_vec())
+}
 
+pub(crate) fn sign_in_with_email_using_session_key(
+    env: &mut PocketIc,
+    canister_ids: &CanisterIds,
+    email: &str,
+    session_key: Vec<u8>,
+) -> (Principal, Vec<u8>, SignedDelegation) {
     let generate_magic_link_response = client::sign_in_with_email::generate_magic_link(
         env,
         Principal::anonymous(),
         canister_ids.sign_in_with_email,
         &sign_in_with_email_canister::GenerateMagicLinkArgs {
-            email: email.clone(),
+            email: email.to_string(),
             session_key: session_key.clone(),
             max_time_to_live: None,
         },
@@ -163,7 +370,7 @@ pub(crate) fn sign_in_with_email(env: &mut PocketIc, canister_ids: &CanisterIds)
     };
 
     let magic_link = sign_in_with_email_canister_test_utils::generate_magic_link(
-        &email,
+        email,
         session_key.clone(),
         generate_magic_link_success.created * NANOS_PER_MILLISECOND,
         generate_magic_link_success.expiration,
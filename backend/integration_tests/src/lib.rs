@@ -10,6 +10,7 @@ use types::{CanisterId, Cycles, SignedDelegation, UserId};
 mod airdrop_bot_tests;
 mod batched_summary_and_event_tests;
 mod bot_tests;
+mod bot_transfer_tests;
 mod change_group_role_tests;
 mod chit_tests;
 mod client;
@@ -74,6 +75,7 @@ pub struct User {
 
 #[derive(Debug)]
 pub struct UserAuth {
+    pub email: String,
     pub auth_principal: Principal,
     pub public_key: Vec<u8>,
     pub delegation: SignedDelegation,
@@ -421,6 +421,7 @@ fn cancel_p2p_swap_in_group_chat_succeeds(delete_message: bool) {
                 message_ids: vec![message_id],
                 as_platform_moderator: None,
                 new_achievement: false,
+                reason: None,
             },
         );
 
@@ -2,11 +2,13 @@ use crate::env::ENV;
 use crate::utils::tick_many;
 use crate::{CanisterIds, TestEnv, User, client};
 use candid::Principal;
+use constants::HOUR_IN_MS;
 use oc_error_codes::OCErrorCode;
 use pocket_ic::PocketIc;
 use std::ops::Deref;
+use std::time::Duration;
 use testing::rng::random_string;
-use types::ChatId;
+use types::{AuditLogAction, ChatId};
 
 #[test]
 fn remove_group_member_succeeds() {
@@ -24,7 +26,10 @@ fn remove_group_member_succeeds() {
         env,
         user1.principal,
         group_id.into(),
-        &group_canister::remove_participant::Args { user_id: user2.user_id },
+        &group_canister::remove_participant::Args {
+            user_id: user2.user_id,
+            reason: None,
+        },
     );
 
     assert!(matches!(
@@ -55,7 +60,11 @@ fn block_user_who_is_no_longer_group_member_succeeds() {
         env,
         user1.principal,
         group_id.into(),
-        &group_canister::block_user::Args { user_id: user2.user_id },
+        &group_canister::block_user::Args {
+            user_id: user2.user_id,
+            duration: None,
+            reason: None,
+        },
     );
 
     assert!(matches!(block_user_response, group_canister::block_user::Response::Success));
@@ -81,6 +90,137 @@ fn block_user_who_is_no_longer_group_member_succeeds() {
     ));
 }
 
+#[test]
+fn temporary_block_is_lifted_once_expired() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2, group_id } = init_test_data(env, canister_ids, *controller, true);
+
+    let block_user_response = client::group::block_user(
+        env,
+        user1.principal,
+        group_id.into(),
+        &group_canister::block_user::Args {
+            user_id: user2.user_id,
+            duration: Some(HOUR_IN_MS),
+            reason: None,
+        },
+    );
+
+    assert!(matches!(block_user_response, group_canister::block_user::Response::Success));
+
+    let blocked_users = client::group::happy_path::selected_initial(env, user1.principal, group_id).blocked_users;
+    assert!(blocked_users.contains(&user2.user_id));
+
+    env.advance_time(Duration::from_millis(HOUR_IN_MS + 1));
+    tick_many(env, 3);
+
+    let blocked_users = client::group::happy_path::selected_initial(env, user1.principal, group_id).blocked_users;
+    assert!(!blocked_users.contains(&user2.user_id));
+
+    client::local_user_index::happy_path::join_group(
+        env,
+        user2.principal,
+        canister_ids.local_user_index(env, group_id),
+        group_id,
+    );
+}
+
+#[test]
+fn timeout_is_visible_to_members_and_lifted_once_expired() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2, group_id } = init_test_data(env, canister_ids, *controller, true);
+
+    let timeout_member_response = client::group::timeout_member(
+        env,
+        user1.principal,
+        group_id.into(),
+        &group_canister::timeout_member::Args {
+            user_id: user2.user_id,
+            duration: Some(HOUR_IN_MS),
+            reason: None,
+        },
+    );
+
+    assert!(matches!(
+        timeout_member_response,
+        group_canister::timeout_member::Response::Success
+    ));
+
+    let members = client::group::happy_path::selected_initial(env, user1.principal, group_id).participants;
+    assert!(
+        members
+            .iter()
+            .any(|m| m.user_id == user2.user_id && m.timed_out_until.is_some())
+    );
+
+    env.advance_time(Duration::from_millis(HOUR_IN_MS + 1));
+    tick_many(env, 3);
+
+    // Once the timeout is lifted the member is returned as a basic member again
+    let selected_initial = client::group::happy_path::selected_initial(env, user1.principal, group_id);
+    assert!(!selected_initial.participants.iter().any(|m| m.user_id == user2.user_id));
+    assert!(selected_initial.basic_members.contains(&user2.user_id));
+}
+
+#[test]
+fn block_is_recorded_in_audit_log() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2, group_id } = init_test_data(env, canister_ids, *controller, true);
+
+    let block_user_response = client::group::block_user(
+        env,
+        user1.principal,
+        group_id.into(),
+        &group_canister::block_user::Args {
+            user_id: user2.user_id,
+            duration: None,
+            reason: Some("Spamming links".to_string()),
+        },
+    );
+    assert!(matches!(block_user_response, group_canister::block_user::Response::Success));
+
+    let audit_log_response = client::group::audit_log(
+        env,
+        user1.principal,
+        group_id.into(),
+        &group_canister::audit_log::Args {
+            before: None,
+            max_results: 10,
+        },
+    );
+
+    let group_canister::audit_log::Response::Success(result) = audit_log_response else {
+        panic!("'audit_log' error: {audit_log_response:?}");
+    };
+
+    let entry = result.entries.first().unwrap();
+    assert_eq!(entry.actor, user1.user_id);
+    assert_eq!(entry.target, Some(user2.user_id));
+    assert_eq!(entry.action, AuditLogAction::UserBlocked(None));
+    assert_eq!(entry.reason.as_deref(), Some("Spamming links"));
+}
+
 fn init_test_data(env: &mut PocketIc, canister_ids: &CanisterIds, controller: Principal, public: bool) -> TestData {
     let user1 = client::register_diamond_user(env, canister_ids, controller);
     let user2 = client::register_user(env, canister_ids);
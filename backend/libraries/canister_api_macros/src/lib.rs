@@ -39,6 +39,8 @@ struct AttributeInput {
     pub json: bool,
     #[serde(default)]
     pub manual_reply: bool,
+    #[serde(default)]
+    pub idempotent: bool,
 }
 
 #[proc_macro_attribute]
@@ -63,7 +65,7 @@ pub fn query(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 fn canister_api_method(method_type: MethodType, attr: TokenStream, item: TokenStream) -> TokenStream {
     let attr: AttributeInput = from_tokenstream(&attr.into()).unwrap();
-    let item = parse_macro_input!(item as ItemFn);
+    let mut item = parse_macro_input!(item as ItemFn);
     let is_lifecycle = matches!(method_type, MethodType::Init | MethodType::PostUpgrade);
     let method_type = Ident::new(method_type.to_string().as_str(), Span::call_site());
 
@@ -85,6 +87,9 @@ fn canister_api_method(method_type: MethodType, attr: TokenStream, item: TokenSt
     if attr.manual_reply {
         attrs.push(quote! { manual_reply = "true" });
     }
+    if attr.idempotent {
+        item = make_idempotent(item, &name);
+    }
 
     let empty_args = item.sig.inputs.is_empty();
     let empty_return = matches!(item.sig.output, ReturnType::Default);
@@ -260,6 +265,48 @@ pub fn proposal_validation(input: TokenStream) -> TokenStream {
     TokenStream::from(tokens)
 }
 
+// Wraps the method body so that if the args contain an idempotency key, the response is stored
+// against (caller, key) and any retries of the same request return the stored response. Retries
+// received while the original request is still in progress return `AlreadyInProgress`, so the
+// response type must implement `From<OCError>`.
+fn make_idempotent(mut item: ItemFn, method_name: &str) -> ItemFn {
+    let arg_names = get_arg_names(&item.sig);
+    let [args] = arg_names.as_slice() else {
+        panic!("Idempotent methods must take a single args parameter");
+    };
+    let ReturnType::Type(_, return_type) = &item.sig.output else {
+        panic!("Idempotent methods must return a response");
+    };
+
+    let block = &item.block;
+    let invoke = if item.sig.asyncness.is_some() {
+        quote! { async move #block.await }
+    } else {
+        quote! { (move || -> #return_type #block)() }
+    };
+
+    let idempotent_block: Block = syn::parse2(quote! {
+        {
+            let idempotency_guard = match idempotency_store::start(#method_name, types::IdempotentArgs::idempotency_key(&#args)) {
+                idempotency_store::StartResult::Started(guard) => guard,
+                idempotency_store::StartResult::Completed(response) => return msgpack::deserialize_owned_then_unwrap(response),
+                idempotency_store::StartResult::InProgress => {
+                    return <#return_type as From<oc_error_codes::OCError>>::from(
+                        oc_error_codes::OCErrorCode::AlreadyInProgress.into(),
+                    );
+                }
+            };
+            let response: #return_type = #invoke;
+            idempotency_guard.complete(msgpack::serialize_then_unwrap(&response));
+            response
+        }
+    })
+    .unwrap();
+
+    item.block = Box::new(idempotent_block);
+    item
+}
+
 fn convert_to_validate_fn(original: ItemFn) -> ItemFn {
     let mut sig = original.sig;
     let name = format!("{}_validate", sig.ident);
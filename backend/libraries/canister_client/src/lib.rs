@@ -1,6 +1,7 @@
 use candid::Principal;
 use ic_cdk::call::{CallFailed, RejectCode};
 use std::fmt::Debug;
+use std::future::Future;
 use tracing::Level;
 
 pub use canister_client_macros::*;
@@ -72,6 +73,20 @@ where
     })
 }
 
+// Makes the call and, if the outcome is unknown (eg. the call timed out), retries it once. Only use
+// this for calls to endpoints marked as `idempotent` where the args include an idempotency key,
+// otherwise the request may be applied twice.
+pub async fn retry_if_outcome_unknown<F, Fut, R>(f: F) -> Result<R, C2CError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<R, C2CError>>,
+{
+    match f().await {
+        Err(error) if matches!(error.reject_code(), RejectCode::SysUnknown) => f().await,
+        result => result,
+    }
+}
+
 pub async fn make_c2c_call_raw(
     canister_id: Principal,
     method_name: &str,
@@ -1,10 +1,17 @@
 // Inspired by https://github.com/dfinity/ic/blob/master/rs/rust_canisters/canister_log/src/lib.rs
 
 use candid::CandidType;
+use ic_stable_structures::memory_manager::VirtualMemory;
+use ic_stable_structures::storable::Bound;
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, Storable};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
 use std::cell::{Cell, RefCell};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::io::Write;
+use std::str::FromStr;
+use std::thread::LocalKey;
 use tracing::Level;
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::fmt::format::Writer;
@@ -14,14 +21,55 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{Layer, Registry, fmt};
 
+pub type Memory = VirtualMemory<DefaultMemoryImpl>;
+
 thread_local! {
     static INITIALIZED: Cell<bool> = Cell::default();
     static ERRORS: RefCell<LogBuffer> = RefCell::new(LogBuffer::default());
     static LOG: RefCell<LogBuffer> = RefCell::new(LogBuffer::default());
     static TRACE: RefCell<LogBuffer> = RefCell::new(LogBuffer::default());
+    static STABLE_LOGS: RefCell<Option<StableLogs>> = RefCell::default();
+}
+
+/// Initializes the logger, persisting each log entry to the given stable memory as it is written.
+pub fn init(enable_trace: bool, memory: Memory) {
+    init_tracing(enable_trace);
+    STABLE_LOGS.set(Some(StableLogs::init(memory)));
 }
 
-pub fn init(enable_trace: bool) {
+/// Initializes the logger after an upgrade, restoring the entries persisted to stable memory. If there are none, which
+/// is the case when upgrading from a version which didn't persist them, the entries passed in are used instead.
+pub fn init_with_logs(enable_trace: bool, memory: Memory, errors: Vec<LogEntry>, logs: Vec<LogEntry>, traces: Vec<LogEntry>) {
+    init_tracing(enable_trace);
+
+    let stable_logs = StableLogs::init(memory);
+
+    if stable_logs.is_empty() {
+        STABLE_LOGS.set(Some(stable_logs));
+
+        // Entries persisted by older versions only contain the raw message, so parse their structured fields now
+        for error in errors {
+            append(&ERRORS, LogKind::Error, error.with_structured_fields());
+        }
+        for log in logs {
+            append(&LOG, LogKind::Log, log.with_structured_fields());
+        }
+        if enable_trace {
+            for trace in traces {
+                append(&TRACE, LogKind::Trace, trace.with_structured_fields());
+            }
+        }
+    } else {
+        ERRORS.set(stable_logs.load(LogKind::Error));
+        LOG.set(stable_logs.load(LogKind::Log));
+        if enable_trace {
+            TRACE.set(stable_logs.load(LogKind::Trace));
+        }
+        STABLE_LOGS.set(Some(stable_logs));
+    }
+}
+
+fn init_tracing(enable_trace: bool) {
     if INITIALIZED.replace(true) {
         panic!("Logger already initialized");
     }
@@ -43,32 +91,18 @@ pub fn init(enable_trace: bool) {
                 .with_file(true)
                 .with_line_number(true)
                 .with_current_span(false)
-                .with_span_list(false)
+                .with_span_list(true)
                 .with_filter(level_filter),
         )
         .init();
 }
 
-pub fn init_with_logs(enable_trace: bool, errors: Vec<LogEntry>, logs: Vec<LogEntry>, traces: Vec<LogEntry>) {
-    init(enable_trace);
-
-    for error in errors {
-        ERRORS.with_borrow_mut(|l| l.append(error));
-    }
-    for log in logs {
-        LOG.with_borrow_mut(|l| l.append(log));
-    }
-    if enable_trace {
-        for trace in traces {
-            TRACE.with_borrow_mut(|t| t.append(trace));
-        }
-    }
-}
-
 /// A circular buffer for log messages.
 pub struct LogBuffer {
     max_capacity: usize,
     entries: VecDeque<LogEntry>,
+    // The index of the oldest entry, entries are keyed by their index when persisted to stable memory
+    first_index: u64,
 }
 
 impl LogBuffer {
@@ -77,6 +111,7 @@ impl LogBuffer {
         Self {
             max_capacity,
             entries: VecDeque::with_capacity(max_capacity),
+            first_index: 0,
         }
     }
 
@@ -84,10 +119,15 @@ impl LogBuffer {
     pub fn append(&mut self, entry: LogEntry) {
         while self.entries.len() >= self.max_capacity {
             self.entries.pop_front();
+            self.first_index += 1;
         }
         self.entries.push_back(entry);
     }
 
+    fn next_index(&self) -> u64 {
+        self.first_index + self.entries.len() as u64
+    }
+
     /// Returns an iterator over entries in the order of their insertion.
     pub fn iter(&self) -> impl Iterator<Item = &LogEntry> {
         self.entries.iter()
@@ -99,8 +139,120 @@ impl Default for LogBuffer {
         LogBuffer {
             max_capacity: 100,
             entries: VecDeque::new(),
+            first_index: 0,
+        }
+    }
+}
+
+// Appends the entry to the buffer and mirrors the change to stable memory, so that the entry survives upgrades without
+// relying on the buffers being serialized in `pre_upgrade`, which misses anything logged after that point
+fn append(buffer: &'static LocalKey<RefCell<LogBuffer>>, kind: LogKind, entry: LogEntry) {
+    buffer.with_borrow_mut(|b| STABLE_LOGS.with_borrow_mut(|s| append_to(b, s.as_mut(), kind, entry)));
+}
+
+fn append_to(buffer: &mut LogBuffer, stable_logs: Option<&mut StableLogs>, kind: LogKind, entry: LogEntry) {
+    let Some(stable_logs) = stable_logs else {
+        buffer.append(entry);
+        return;
+    };
+
+    let first_index = buffer.first_index;
+    stable_logs.insert(kind, buffer.next_index(), entry.clone());
+    buffer.append(entry);
+
+    for evicted_index in first_index..buffer.first_index {
+        stable_logs.remove(kind, evicted_index);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[repr(u8)]
+enum LogKind {
+    Error = 0,
+    Log = 1,
+    Trace = 2,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct LogKey {
+    kind: LogKind,
+    index: u64,
+}
+
+impl Storable for LogKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        let mut bytes = Vec::with_capacity(9);
+        bytes.push(self.kind as u8);
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let kind = match bytes[0] {
+            0 => LogKind::Error,
+            1 => LogKind::Log,
+            _ => LogKind::Trace,
+        };
+        LogKey {
+            kind,
+            index: u64::from_be_bytes(bytes[1..9].try_into().unwrap()),
         }
     }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 9,
+        is_fixed_size: true,
+    };
+}
+
+impl Storable for LogEntry {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(msgpack::serialize_then_unwrap(self))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        msgpack::deserialize_then_unwrap(bytes.as_ref())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+struct StableLogs {
+    entries: StableBTreeMap<LogKey, LogEntry, Memory>,
+}
+
+impl StableLogs {
+    fn init(memory: Memory) -> StableLogs {
+        StableLogs {
+            entries: StableBTreeMap::init(memory),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn insert(&mut self, kind: LogKind, index: u64, entry: LogEntry) {
+        self.entries.insert(LogKey { kind, index }, entry);
+    }
+
+    fn remove(&mut self, kind: LogKind, index: u64) {
+        self.entries.remove(&LogKey { kind, index });
+    }
+
+    fn load(&self, kind: LogKind) -> LogBuffer {
+        let mut buffer = LogBuffer::default();
+        let start = LogKey { kind, index: 0 };
+        let end = LogKey { kind, index: u64::MAX };
+
+        for (key, entry) in self.entries.range(start..=end) {
+            if buffer.entries.is_empty() {
+                buffer.first_index = key.index;
+            }
+            buffer.append(entry);
+        }
+        buffer
+    }
 }
 
 pub fn export_errors() -> Vec<LogEntry> {
@@ -119,6 +271,151 @@ pub fn export_traces() -> Vec<LogEntry> {
 pub struct LogEntry {
     pub timestamp: u64,
     pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<LogLevel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    // The event's fields merged with the fields of each of its enclosing spans
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl LogEntry {
+    pub fn new(timestamp: u64, message: String) -> LogEntry {
+        LogEntry {
+            timestamp,
+            message,
+            level: None,
+            target: None,
+            fields: BTreeMap::new(),
+        }
+        .with_structured_fields()
+    }
+
+    fn with_structured_fields(mut self) -> LogEntry {
+        if self.level.is_some() {
+            return self;
+        }
+
+        let Ok(Value::Object(json)) = serde_json::from_str::<Value>(&self.message) else {
+            return self;
+        };
+
+        self.level = json
+            .get("level")
+            .and_then(|l| l.as_str())
+            .and_then(|l| LogLevel::from_str(l).ok());
+        self.target = json.get("target").and_then(|t| t.as_str()).map(|t| t.to_string());
+
+        // Fields on the event take precedence over those on the spans, and inner spans over outer spans
+        if let Some(Value::Object(fields)) = json.get("fields") {
+            for (key, value) in fields {
+                self.fields.insert(key.clone(), value_to_string(value));
+            }
+        }
+        if let Some(Value::Array(spans)) = json.get("spans") {
+            for span in spans.iter().rev().filter_map(|s| s.as_object()) {
+                for (key, value) in span.iter().filter(|(k, _)| k.as_str() != "name") {
+                    self.fields.entry(key.clone()).or_insert_with(|| value_to_string(value));
+                }
+            }
+        }
+        self
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl FromStr for LogLevel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Filters applied when exporting log entries. All text comparisons are case-insensitive.
+#[derive(Default, Debug)]
+pub struct LogQuery {
+    /// Only entries logged after this timestamp are returned.
+    pub since: Option<u64>,
+    /// Only entries of this level or above are returned.
+    pub min_level: Option<LogLevel>,
+    /// Only entries whose target starts with this value are returned.
+    pub target: Option<String>,
+    /// Only entries whose raw message contains this value are returned.
+    pub contains: Option<String>,
+    /// Only entries having each of these field values are returned.
+    pub fields: Vec<(String, String)>,
+    /// The number of matching entries to skip, starting from the most recent.
+    pub offset: usize,
+    /// The maximum number of entries to return.
+    pub limit: Option<usize>,
+}
+
+impl LogQuery {
+    /// Returns the matching entries in the order in which they were logged.
+    pub fn apply(&self, entries: Vec<LogEntry>) -> Vec<LogEntry> {
+        let mut matching: Vec<_> = entries
+            .into_iter()
+            .rev()
+            .filter(|e| self.matches(e))
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+
+        matching.reverse();
+        matching
+    }
+
+    fn matches(&self, entry: &LogEntry) -> bool {
+        if self.since.is_some_and(|s| entry.timestamp <= s) {
+            return false;
+        }
+        if let Some(min_level) = self.min_level {
+            if entry.level.is_none_or(|l| l < min_level) {
+                return false;
+            }
+        }
+        if let Some(target) = &self.target {
+            if !entry
+                .target
+                .as_ref()
+                .is_some_and(|t| t.to_lowercase().starts_with(&target.to_lowercase()))
+            {
+                return false;
+            }
+        }
+        if let Some(contains) = &self.contains {
+            if !entry.message.to_lowercase().contains(&contains.to_lowercase()) {
+                return false;
+            }
+        }
+        self.fields
+            .iter()
+            .all(|(key, value)| entry.fields.get(key.as_str()).is_some_and(|v| v.eq_ignore_ascii_case(value)))
+    }
 }
 
 struct LogWriter {
@@ -152,20 +449,22 @@ impl Write for LogWriter {
             let buffer = std::mem::take(&mut self.buffer);
             let json = String::from_utf8(buffer).unwrap();
 
-            let log_entry = LogEntry {
-                timestamp: canister_time::now_millis(),
-                message: json,
-            };
+            // Errors are also written to the canister's system log, which is retained even if the current
+            // execution traps, meaning failures such as those during `post_upgrade` can still be diagnosed
+            #[cfg(target_arch = "wasm32")]
+            if self.level == Level::ERROR {
+                ic_cdk::api::debug_print(&json);
+            }
 
-            let sink = if self.level == Level::TRACE {
-                &TRACE
+            let log_entry = LogEntry::new(canister_time::now_millis(), json);
+
+            if self.level == Level::TRACE {
+                append(&TRACE, LogKind::Trace, log_entry);
             } else if self.level == Level::INFO {
-                &LOG
+                append(&LOG, LogKind::Log, log_entry);
             } else {
-                &ERRORS
-            };
-
-            sink.with_borrow_mut(|s| s.append(log_entry));
+                append(&ERRORS, LogKind::Error, log_entry);
+            }
         }
         Ok(())
     }
@@ -184,3 +483,75 @@ impl FormatTime for Timer {
         w.write_str(&format!("{now}"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
+
+    const MESSAGE: &str = r#"{"timestamp":"1000","level":"WARN","fields":{"message":"Transfer failed","ledger":"ryjl3"},"target":"user_canister::updates::send","spans":[{"name":"send_message","user_id":"abc"},{"name":"transfer","ledger":"mxzaz"}]}"#;
+
+    #[test]
+    fn structured_fields_are_parsed() {
+        let entry = LogEntry::new(1000, MESSAGE.to_string());
+
+        assert_eq!(entry.level, Some(LogLevel::Warn));
+        assert_eq!(entry.target.as_deref(), Some("user_canister::updates::send"));
+        assert_eq!(entry.fields.get("message").map(|s| s.as_str()), Some("Transfer failed"));
+        assert_eq!(entry.fields.get("user_id").map(|s| s.as_str()), Some("abc"));
+        // The event's own field takes precedence over the span's
+        assert_eq!(entry.fields.get("ledger").map(|s| s.as_str()), Some("ryjl3"));
+    }
+
+    #[test]
+    fn stable_logs_round_trip() {
+        let memory_manager = MemoryManager::init(DefaultMemoryImpl::default());
+        let mut stable_logs = StableLogs::init(memory_manager.get(MemoryId::new(0)));
+        let mut buffer = LogBuffer::with_capacity(3);
+
+        for timestamp in 1..=5 {
+            let entry = LogEntry::new(timestamp * 1000, MESSAGE.to_string());
+            append_to(&mut buffer, Some(&mut stable_logs), LogKind::Log, entry);
+        }
+        let error = LogEntry::new(6000, MESSAGE.to_string());
+        append_to(&mut LogBuffer::default(), Some(&mut stable_logs), LogKind::Error, error);
+
+        let restored = StableLogs::init(memory_manager.get(MemoryId::new(0)));
+        let logs = restored.load(LogKind::Log);
+        let errors = restored.load(LogKind::Error);
+
+        // The entries evicted from the buffer are removed from stable memory too
+        assert_eq!(logs.iter().map(|e| e.timestamp).collect::<Vec<_>>(), vec![3000, 4000, 5000]);
+        assert_eq!(logs.first_index, 2);
+        assert_eq!(logs.next_index(), 5);
+        assert_eq!(errors.iter().map(|e| e.timestamp).collect::<Vec<_>>(), vec![6000]);
+        assert!(restored.load(LogKind::Trace).iter().next().is_none());
+
+        let entry = logs.iter().last().unwrap();
+        assert_eq!(entry.level, Some(LogLevel::Warn));
+        assert_eq!(entry.fields.get("user_id").map(|s| s.as_str()), Some("abc"));
+    }
+
+    #[test]
+    fn query_filters_and_paginates_from_most_recent() {
+        let entries: Vec<_> = (1..=5).map(|i| LogEntry::new(i * 1000, MESSAGE.to_string())).collect();
+
+        let query = LogQuery {
+            min_level: Some(LogLevel::Info),
+            target: Some("user_canister".to_string()),
+            contains: Some("transfer FAILED".to_string()),
+            fields: vec![("user_id".to_string(), "ABC".to_string())],
+            offset: 1,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let timestamps: Vec<_> = query.apply(entries.clone()).iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, vec![3000, 4000]);
+
+        let query = LogQuery {
+            min_level: Some(LogLevel::Error),
+            ..Default::default()
+        };
+        assert!(query.apply(entries).is_empty());
+    }
+}
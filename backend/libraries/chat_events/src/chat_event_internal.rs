@@ -8,10 +8,10 @@ use types::{
     ChatEventCategory, ChatEventType, ChatId, CommunityId, DeletedBy, DirectChatCreated, EventIndex, EventWrapperInternal,
     EventsTimeToLiveUpdated, ExternalUrlUpdated, GroupCreated, GroupDescriptionChanged, GroupFrozen, GroupGateUpdated,
     GroupInviteCodeChanged, GroupNameChanged, GroupReplyContext, GroupRulesChanged, GroupUnfrozen, GroupVisibilityChanged,
-    MemberJoinedInternal, MemberLeft, MembersAdded, MembersAddedToDefaultChannel, MembersRemoved, Message, MessageContent,
-    MessageContentType, MessageId, MessageIndex, MessagePinned, MessageUnpinned, MultiUserChat, PermissionsChanged,
-    PushIfNotContains, Reaction, ReplyContext, RoleChanged, SenderContext, ThreadSummary, TimestampMillis, Tips, UserId,
-    UsersBlocked, UsersInvited, UsersUnblocked, is_default,
+    MemberJoinedInternal, MemberLeft, MemberTimedOut, MembersAdded, MembersAddedToDefaultChannel, MembersRemoved, Message,
+    MessageContent, MessageContentType, MessageId, MessageIndex, MessagePinned, MessageUnpinned, MultiUserChat,
+    PermissionsChanged, PushIfNotContains, Reaction, ReplyContext, RoleChanged, SenderContext, ThreadSummary, TimestampMillis,
+    Tips, UserId, UsersBlocked, UsersInvited, UsersUnblocked, is_default,
 };
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -44,6 +44,8 @@ pub enum ChatEventInternal {
     UsersBlocked(Box<UsersBlocked>),
     #[serde(rename = "uub")]
     UsersUnblocked(Box<UsersUnblocked>),
+    #[serde(rename = "mto")]
+    MemberTimedOut(Box<MemberTimedOut>),
     #[serde(rename = "mp")]
     MessagePinned(Box<MessagePinned>),
     #[serde(rename = "mup")]
@@ -109,6 +111,7 @@ impl ChatEventInternal {
                 | ChatEventInternal::RoleChanged(_)
                 | ChatEventInternal::UsersBlocked(_)
                 | ChatEventInternal::UsersUnblocked(_)
+                | ChatEventInternal::MemberTimedOut(_)
                 | ChatEventInternal::MessagePinned(_)
                 | ChatEventInternal::MessageUnpinned(_)
                 | ChatEventInternal::PermissionsChanged(_)
@@ -159,6 +162,7 @@ impl ChatEventInternal {
             ChatEventInternal::RoleChanged(r) => ChatEvent::RoleChanged(*r),
             ChatEventInternal::UsersBlocked(u) => ChatEvent::UsersBlocked(*u),
             ChatEventInternal::UsersUnblocked(u) => ChatEvent::UsersUnblocked(*u),
+            ChatEventInternal::MemberTimedOut(t) => ChatEvent::MemberTimedOut(*t),
             ChatEventInternal::MessagePinned(p) => ChatEvent::MessagePinned(*p),
             ChatEventInternal::PermissionsChanged(p) => ChatEvent::PermissionsChanged(*p),
             ChatEventInternal::MessageUnpinned(u) => ChatEvent::MessageUnpinned(*u),
@@ -205,6 +209,7 @@ impl ChatEventInternal {
             | ChatEventInternal::RoleChanged(_)
             | ChatEventInternal::UsersBlocked(_)
             | ChatEventInternal::UsersUnblocked(_)
+            | ChatEventInternal::MemberTimedOut(_)
             | ChatEventInternal::UsersInvited(_)
             | ChatEventInternal::MembersAddedToPublicChannel(_)
             | ChatEventInternal::BotAdded(_)
@@ -230,6 +235,7 @@ impl ChatEventInternal {
             ChatEventInternal::RoleChanged(_) => Some(ChatEventType::RoleChanged),
             ChatEventInternal::UsersBlocked(_) => Some(ChatEventType::UsersBlocked),
             ChatEventInternal::UsersUnblocked(_) => Some(ChatEventType::UsersUnblocked),
+            ChatEventInternal::MemberTimedOut(_) => Some(ChatEventType::MemberTimedOut),
             ChatEventInternal::MessagePinned(_) => Some(ChatEventType::MessagePinned),
             ChatEventInternal::MessageUnpinned(_) => Some(ChatEventType::MessageUnpinned),
             ChatEventInternal::PermissionsChanged(_) => Some(ChatEventType::PermissionsChanged),
@@ -17,15 +17,15 @@ use std::mem;
 use std::ops::DerefMut;
 use tracing::error;
 use types::{
-    BlobReference, BotChatEvent, BotNotification, CallParticipant, CanisterId, Chat, ChatEvent, ChatEventCategory,
-    ChatEventType, ChatType, CompletedCryptoTransaction, DirectChatCreated, EventContext, EventIndex, EventMetaData,
-    EventWrapper, EventWrapperInternal, EventsTimeToLiveUpdated, GroupCanisterThreadDetails, GroupCreated, GroupFrozen,
-    GroupUnfrozen, HydratedMention, Mention, Message, MessageEditedEventPayload, MessageEventPayload, MessageId, MessageIndex,
-    MessageMatch, MessageTippedEventPayload, Milliseconds, MultiUserChat, OCResult, OptionUpdate, P2PSwapAccepted,
-    P2PSwapCompleted, P2PSwapCompletedEventPayload, P2PSwapContent, P2PSwapStatus, PendingCryptoTransaction, PollVotes,
-    ProposalRewardStatus, ProposalUpdate, Reaction, ReactionAddedEventPayload, RegisterVoteResult, ReserveP2PSwapSuccess,
-    SenderContext, Tally, TimestampMillis, TimestampNanos, Timestamped, Tips, UserId, VideoCall, VideoCallEndedEventPayload,
-    VideoCallParticipants, VideoCallPresence, VideoCallType, VoteOperation,
+    BlobReference, BotChatEvent, BotMessageComponent, BotNotification, CallParticipant, CanisterId, Chat, ChatEvent,
+    ChatEventCategory, ChatEventType, ChatType, CompletedCryptoTransaction, DirectChatCreated, EventContext, EventIndex,
+    EventMetaData, EventWrapper, EventWrapperInternal, EventsTimeToLiveUpdated, GroupCanisterThreadDetails, GroupCreated,
+    GroupFrozen, GroupUnfrozen, HydratedMention, Mention, Message, MessageEditedEventPayload, MessageEventPayload, MessageId,
+    MessageIndex, MessageMatch, MessageTippedEventPayload, Milliseconds, MultiUserChat, OCResult, OptionUpdate,
+    P2PSwapAccepted, P2PSwapCompleted, P2PSwapCompletedEventPayload, P2PSwapContent, P2PSwapStatus, PendingCryptoTransaction,
+    PollVotes, ProposalRewardStatus, ProposalUpdate, Reaction, ReactionAddedEventPayload, RegisterVoteResult,
+    ReserveP2PSwapSuccess, SenderContext, Tally, TimestampMillis, TimestampNanos, Timestamped, Tips, UserId, VideoCall,
+    VideoCallEndedEventPayload, VideoCallParticipants, VideoCallPresence, VideoCallType, VoteOperation,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -965,20 +965,7 @@ impl ChatEvents {
         anonymized_id: String,
         mut event_pusher: Option<P>,
     ) -> Result<(), UpdateEventError<OCError>> {
-        if message.sender == args.user_id {
-            return Err(UpdateEventError::NoChange(OCErrorCode::CannotTipSelf.into()));
-        }
-        if message.sender != args.recipient {
-            error!(
-                user = %args.user_id,
-                recipient = %args.recipient,
-                sender = %message.sender,
-                message_index = ?message.message_index,
-                message_id = ?message.message_id,
-                "Tip failed due to recipient mismatch"
-            );
-            return Err(UpdateEventError::NoChange(OCErrorCode::RecipientMismatch.into()));
-        }
+        Self::check_tip(message, args.user_id, args.recipient).map_err(UpdateEventError::NoChange)?;
 
         message.tips.push(args.ledger, args.user_id, args.amount);
 
@@ -1004,6 +991,42 @@ impl ChatEvents {
         Ok(())
     }
 
+    // Runs the same checks as `tip_message` without recording the tip, so that a tip which is funded by a transfer can be
+    // validated before the transfer is made
+    pub fn validate_tip(
+        &self,
+        user_id: UserId,
+        recipient: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        min_visible_event_index: EventIndex,
+    ) -> OCResult {
+        let Some((message, _)) = self.message_internal(min_visible_event_index, thread_root_message_index, message_id.into())
+        else {
+            return Err(OCErrorCode::MessageNotFound.into());
+        };
+
+        Self::check_tip(&message, user_id, recipient)
+    }
+
+    fn check_tip(message: &MessageInternal, user_id: UserId, recipient: UserId) -> OCResult {
+        if message.sender == user_id {
+            return Err(OCErrorCode::CannotTipSelf.into());
+        }
+        if message.sender != recipient {
+            error!(
+                user = %user_id,
+                recipient = %recipient,
+                sender = %message.sender,
+                message_index = ?message.message_index,
+                message_id = ?message.message_id,
+                "Tip failed due to recipient mismatch"
+            );
+            return Err(OCErrorCode::RecipientMismatch.into());
+        }
+        Ok(())
+    }
+
     pub fn reserve_prize(
         &mut self,
         user_id: UserId,
@@ -2251,6 +2274,31 @@ impl ChatEvents {
             .and_then(|e| e.event.into_message().map(|m| (m, e.index)))
     }
 
+    pub fn set_bot_message_components(
+        &mut self,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        components: Vec<BotMessageComponent>,
+        now: TimestampMillis,
+    ) -> bool {
+        self.update_event(
+            thread_root_message_index,
+            message_id.into(),
+            EventIndex::default(),
+            Some(now),
+            |event| {
+                Self::update_message_inner(event, |message, _| match message.bot_context_mut() {
+                    Some(bot_context) => {
+                        bot_context.components = components;
+                        Ok(())
+                    }
+                    None => Err(UpdateEventError::NoChange(())),
+                })
+            },
+        )
+        .is_ok()
+    }
+
     fn expiry_date(&self, event: &ChatEventInternal, is_thread_event: bool, now: TimestampMillis) -> Option<TimestampMillis> {
         if let Some(ttl) = self.events_ttl.value {
             if is_thread_event
@@ -1352,6 +1352,9 @@ pub struct PrizeContentInternal {
     pub fee_percent: u8,
     #[serde(rename = "rc", default, skip_serializing_if = "is_default")]
     pub requires_captcha: bool,
+    // Set when the prize was funded from an account other than the sender's, eg. by a bot
+    #[serde(rename = "ra", default, skip_serializing_if = "Option::is_none")]
+    pub refund_account: Option<icrc1::AccountInternal>,
 }
 
 impl PrizeContentInternal {
@@ -1372,9 +1375,14 @@ impl PrizeContentInternal {
             prizes_paid: 0,
             fee_percent: PRIZE_FEE_PERCENT,
             requires_captcha: content.requires_captcha,
+            refund_account: None,
         }
     }
 
+    pub fn set_refund_account(&mut self, account: Account) {
+        self.refund_account = Some(account.into());
+    }
+
     pub fn final_payments(&mut self, sender: UserId, now_nanos: TimestampNanos) -> Vec<PendingCryptoTransaction> {
         if self.final_payments_started {
             return Vec::new();
@@ -1410,15 +1418,27 @@ impl PrizeContentInternal {
         }
 
         if refund > transaction_fee {
-            payments.push(create_pending_transaction(
-                token_symbol,
-                ledger,
-                refund - transaction_fee,
-                transaction_fee,
-                sender,
-                Some(&MEMO_PRIZE_REFUND),
-                now_nanos,
-            ));
+            if let Some(refund_account) = self.refund_account.clone() {
+                payments.push(PendingCryptoTransaction::ICRC1(types::icrc1::PendingCryptoTransaction {
+                    ledger,
+                    fee: transaction_fee,
+                    token_symbol,
+                    amount: refund - transaction_fee,
+                    to: refund_account.into(),
+                    memo: Some(MEMO_PRIZE_REFUND.to_vec().into()),
+                    created: now_nanos,
+                }));
+            } else {
+                payments.push(create_pending_transaction(
+                    token_symbol,
+                    ledger,
+                    refund - transaction_fee,
+                    transaction_fee,
+                    sender,
+                    Some(&MEMO_PRIZE_REFUND),
+                    now_nanos,
+                ));
+            }
         }
 
         payments
@@ -183,6 +183,26 @@ pub enum OCErrorCode {
     WebhookNotFound = 339,
     InvalidWebhook = 340,
     InvalidOriginatingCanister = 341,
+    ChatPublic = 342,
+    EncryptionNotEnabled = 343,
+    EncryptionAlreadyEnabled = 344,
+    WebAuthnCredentialNotFound = 345,
+    WebAuthnCredentialAlreadyExists = 346,
+    ReauthenticationRequired = 347,
+    LinkedIdentitiesLimitReached = 348,
+    MessageBlockedByAutomod = 349,
+    InitiatorTimedOut = 350,
+    InvalidAutomodRules = 351,
+    ReportNotFound = 352,
+    BotSpendAllowanceExceeded = 353,
+    BotStorageQuotaExceeded = 354,
+    TooManyScheduledTasks = 355,
+    BotReviewPending = 356,
+    BotAlreadyPublic = 357,
+    BotVersionMismatch = 358,
+    IdentityLinkNotFound = 359,
+    ModerationQueueFull = 360,
+    TooManyPendingReports = 361,
 
     // InternalError
     C2CError = 500,
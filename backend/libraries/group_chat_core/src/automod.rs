@@ -0,0 +1,492 @@
+use chat_events::MessageContentInternal;
+use lazy_static::lazy_static;
+use regex_lite::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use types::{
+    AutomodAction, AutomodCondition, AutomodDetails, AutomodLinkFilter, AutomodRule, AutomodTriggered, HeldMessage,
+    MAX_AUTOMOD_PATTERN_LENGTH, MAX_AUTOMOD_PATTERNS_PER_RULE, MAX_AUTOMOD_RULES, MessageId, MessageIndex, Milliseconds,
+    TimestampMillis, UserId,
+};
+
+const MAX_TRIGGERED_LOG_LENGTH: usize = 1000;
+const MAX_HELD_MESSAGES: usize = 500;
+const MAX_RECENT_MESSAGES_PER_USER: usize = 20;
+const MAX_USERS_WITH_RECENT_MESSAGES: usize = 1000;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Automod {
+    #[serde(rename = "r", default, skip_serializing_if = "Vec::is_empty")]
+    rules: Vec<AutomodRule>,
+    #[serde(rename = "h", default, skip_serializing_if = "VecDeque::is_empty")]
+    held: VecDeque<HeldMessage>,
+    #[serde(rename = "l", default, skip_serializing_if = "VecDeque::is_empty")]
+    triggered: VecDeque<AutomodTriggered>,
+    #[serde(rename = "m", default, skip_serializing_if = "HashMap::is_empty")]
+    recent_messages: HashMap<UserId, VecDeque<(TimestampMillis, u64)>>,
+    #[serde(rename = "u", default)]
+    last_updated: TimestampMillis,
+    #[serde(skip)]
+    compiled_regexes: Option<HashMap<u32, Vec<Regex>>>,
+}
+
+pub struct AutomodMessage<'a> {
+    pub sender: UserId,
+    pub date_joined: TimestampMillis,
+    pub content: &'a MessageContentInternal,
+    pub mention_count: usize,
+    pub is_edit: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AutomodVerdict {
+    pub rule_id: u32,
+    pub action: AutomodAction,
+}
+
+impl AutomodVerdict {
+    // Blocked messages are never added to the chat, all other actions are applied once the message is in place
+    pub fn blocks_message(&self) -> bool {
+        matches!(self.action, AutomodAction::Block | AutomodAction::Timeout(_))
+    }
+}
+
+impl Automod {
+    pub fn set_rules(&mut self, rules: Vec<AutomodRule>, now: TimestampMillis) -> Result<(), String> {
+        validate_rules(&rules)?;
+
+        self.rules = rules;
+        self.compiled_regexes = None;
+        if !self
+            .rules
+            .iter()
+            .any(|r| r.enabled && matches!(r.condition, AutomodCondition::RepeatedMessages(_)))
+        {
+            self.recent_messages.clear();
+        }
+        self.last_updated = now;
+        Ok(())
+    }
+
+    pub fn rules(&self) -> &[AutomodRule] {
+        &self.rules
+    }
+
+    // Rules are evaluated in order and the first rule to trigger determines the action taken
+    pub fn evaluate(&mut self, message: AutomodMessage, now: TimestampMillis) -> Option<AutomodVerdict> {
+        if !self.rules.iter().any(|r| r.enabled) {
+            return None;
+        }
+
+        let text = message.content.text().unwrap_or_default();
+        let previous_repeats = if message.is_edit { HashMap::new() } else { self.record_message(message.sender, text, now) };
+
+        if self.compiled_regexes.is_none() {
+            self.compiled_regexes = Some(compile_regexes(&self.rules));
+        }
+        let regexes = self.compiled_regexes.as_ref().unwrap();
+
+        self.rules
+            .iter()
+            .filter(|r| r.enabled)
+            .find(|r| match &r.condition {
+                AutomodCondition::Keywords(keywords) => {
+                    let lowercase = text.to_lowercase();
+                    keywords.iter().any(|k| contains_word(&lowercase, &k.to_lowercase()))
+                }
+                AutomodCondition::Regex(_) => regexes.get(&r.id).is_some_and(|r| r.iter().any(|r| r.is_match(text))),
+                AutomodCondition::Links(filter) => links_triggered(text, filter),
+                AutomodCondition::MentionSpam(max_mentions) => message.mention_count > *max_mentions as usize,
+                AutomodCondition::RepeatedMessages(repeated) => {
+                    !message.is_edit
+                        && previous_repeats.get(&repeated.window).copied().unwrap_or_default() >= repeated.max_repeats
+                }
+                AutomodCondition::NewMembers(min_membership) => now < message.date_joined.saturating_add(*min_membership),
+            })
+            .map(|r| AutomodVerdict {
+                rule_id: r.id,
+                action: r.action,
+            })
+    }
+
+    pub fn record_triggered(
+        &mut self,
+        verdict: AutomodVerdict,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        now: TimestampMillis,
+    ) {
+        // Timeouts are applied to the member by the caller. Once the held queue is full the oldest held messages
+        // drop out of it, leaving them deleted.
+        if matches!(verdict.action, AutomodAction::HoldForReview) {
+            if self.held.len() >= MAX_HELD_MESSAGES {
+                self.held.pop_front();
+            }
+            self.held.push_back(HeldMessage {
+                rule_id: verdict.rule_id,
+                sender: user_id,
+                thread_root_message_index,
+                message_id,
+                timestamp: now,
+            });
+        }
+
+        if self.triggered.len() >= MAX_TRIGGERED_LOG_LENGTH {
+            self.triggered.pop_front();
+        }
+        self.triggered.push_back(AutomodTriggered {
+            rule_id: verdict.rule_id,
+            user_id,
+            thread_root_message_index,
+            message_id,
+            action: verdict.action,
+            timestamp: now,
+        });
+        self.last_updated = now;
+    }
+
+    pub fn take_held_message(
+        &mut self,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        now: TimestampMillis,
+    ) -> Option<HeldMessage> {
+        let index = self
+            .held
+            .iter()
+            .position(|h| h.thread_root_message_index == thread_root_message_index && h.message_id == message_id)?;
+
+        self.last_updated = now;
+        self.held.remove(index)
+    }
+
+    pub fn details(&self) -> AutomodDetails {
+        AutomodDetails {
+            rules: self.rules.clone(),
+            held_messages: self.held.iter().cloned().collect(),
+            triggered: self.triggered.iter().rev().cloned().collect(),
+            last_updated: self.last_updated,
+        }
+    }
+
+    pub fn last_updated(&self) -> TimestampMillis {
+        self.last_updated
+    }
+
+    // Records the message and returns, for each repeated message window, how many identical messages
+    // the sender had already sent within that window
+    fn record_message(&mut self, sender: UserId, text: &str, now: TimestampMillis) -> HashMap<Milliseconds, u32> {
+        let windows: HashSet<_> = self
+            .rules
+            .iter()
+            .filter(|r| r.enabled)
+            .filter_map(|r| {
+                if let AutomodCondition::RepeatedMessages(repeated) = &r.condition {
+                    Some(repeated.window)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let Some(max_window) = windows.iter().max().copied() else {
+            return HashMap::new();
+        };
+
+        if self.recent_messages.len() >= MAX_USERS_WITH_RECENT_MESSAGES {
+            self.recent_messages
+                .retain(|_, messages| messages.back().is_some_and(|(ts, _)| now.saturating_sub(*ts) < max_window));
+        }
+
+        let hash = hash_text(text);
+        let messages = self.recent_messages.entry(sender).or_default();
+        while messages.front().is_some_and(|(ts, _)| now.saturating_sub(*ts) >= max_window) {
+            messages.pop_front();
+        }
+
+        let counts = windows
+            .into_iter()
+            .map(|window| {
+                let count = messages
+                    .iter()
+                    .filter(|(ts, h)| *h == hash && now.saturating_sub(*ts) < window)
+                    .count();
+                (window, count as u32)
+            })
+            .collect();
+
+        if messages.len() >= MAX_RECENT_MESSAGES_PER_USER {
+            messages.pop_front();
+        }
+        messages.push_back((now, hash));
+        counts
+    }
+}
+
+fn validate_rules(rules: &[AutomodRule]) -> Result<(), String> {
+    if rules.len() > MAX_AUTOMOD_RULES {
+        return Err(format!("Too many rules, max: {MAX_AUTOMOD_RULES}"));
+    }
+
+    let mut ids = HashSet::new();
+    for rule in rules {
+        if !ids.insert(rule.id) {
+            return Err(format!("Duplicate rule id: {}", rule.id));
+        }
+
+        let patterns = match &rule.condition {
+            AutomodCondition::Keywords(patterns) | AutomodCondition::Regex(patterns) => patterns.iter().collect(),
+            AutomodCondition::Links(filter) => filter.allowed_domains.iter().chain(filter.blocked_domains.iter()).collect(),
+            AutomodCondition::MentionSpam(_) | AutomodCondition::NewMembers(_) => Vec::new(),
+            AutomodCondition::RepeatedMessages(repeated) => {
+                if repeated.max_repeats == 0 || repeated.window == 0 {
+                    return Err(format!("Invalid repeated messages condition, rule: {}", rule.id));
+                }
+                Vec::new()
+            }
+        };
+
+        if patterns.len() > MAX_AUTOMOD_PATTERNS_PER_RULE {
+            return Err(format!(
+                "Too many patterns, rule: {}, max: {MAX_AUTOMOD_PATTERNS_PER_RULE}",
+                rule.id
+            ));
+        }
+
+        if let Some(pattern) = patterns
+            .iter()
+            .find(|p| p.trim().is_empty() || p.len() > MAX_AUTOMOD_PATTERN_LENGTH)
+        {
+            return Err(format!("Invalid pattern, rule: {}, pattern: {pattern}", rule.id));
+        }
+
+        if let AutomodCondition::Regex(patterns) = &rule.condition {
+            if let Some(error) = patterns.iter().find_map(|p| Regex::new(p).err()) {
+                return Err(format!("Invalid regex, rule: {}, error: {error}", rule.id));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn compile_regexes(rules: &[AutomodRule]) -> HashMap<u32, Vec<Regex>> {
+    rules
+        .iter()
+        .filter_map(|r| {
+            if let AutomodCondition::Regex(patterns) = &r.condition {
+                Some((r.id, patterns.iter().filter_map(|p| Regex::new(p).ok()).collect()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_boundary = |c: Option<char>| c.is_none_or(|c| !c.is_alphanumeric());
+
+    text.match_indices(word)
+        .any(|(i, _)| is_boundary(text[..i].chars().next_back()) && is_boundary(text[i + word.len()..].chars().next()))
+}
+
+fn links_triggered(text: &str, filter: &AutomodLinkFilter) -> bool {
+    let matches_domain = |domain: &str, candidates: &[String]| {
+        candidates.iter().any(|c| {
+            let c = c.trim_start_matches("www.").to_lowercase();
+            domain == c || domain.strip_suffix(&c).is_some_and(|prefix| prefix.ends_with('.'))
+        })
+    };
+
+    LINK_REGEX.captures_iter(text).any(|captures| {
+        let domain = captures[1].to_lowercase();
+        let domain = domain.trim_start_matches("www.");
+
+        matches_domain(domain, &filter.blocked_domains)
+            || (!filter.allowed_domains.is_empty() && !matches_domain(domain, &filter.allowed_domains))
+    })
+}
+
+fn hash_text(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.trim().to_lowercase().hash(&mut hasher);
+    hasher.finish()
+}
+
+lazy_static! {
+    static ref LINK_REGEX: Regex = Regex::new(r"(?i)(?:https?://|\bwww\.)([a-z0-9][a-z0-9.-]*[a-z0-9])").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chat_events::TextContentInternal;
+    use types::AutomodRepeatedMessages;
+
+    fn rule(id: u32, condition: AutomodCondition, action: AutomodAction) -> AutomodRule {
+        AutomodRule {
+            id,
+            name: format!("rule{id}"),
+            enabled: true,
+            condition,
+            action,
+        }
+    }
+
+    fn text(text: &str) -> MessageContentInternal {
+        MessageContentInternal::Text(TextContentInternal { text: text.to_string() })
+    }
+
+    fn evaluate(automod: &mut Automod, content: &str, now: TimestampMillis) -> Option<AutomodVerdict> {
+        automod.evaluate(
+            AutomodMessage {
+                sender: UserId::new(candid::Principal::from_slice(&[1])),
+                date_joined: 0,
+                content: &text(content),
+                mention_count: 0,
+                is_edit: false,
+            },
+            now,
+        )
+    }
+
+    #[test]
+    fn keywords_match_whole_words_only() {
+        let mut automod = Automod::default();
+        automod
+            .set_rules(
+                vec![rule(
+                    1,
+                    AutomodCondition::Keywords(vec!["Scam".to_string()]),
+                    AutomodAction::Block,
+                )],
+                0,
+            )
+            .unwrap();
+
+        assert!(evaluate(&mut automod, "this is a SCAM!", 0).is_some());
+        assert!(evaluate(&mut automod, "scampi for dinner", 0).is_none());
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let mut automod = Automod::default();
+        automod
+            .set_rules(
+                vec![
+                    rule(
+                        1,
+                        AutomodCondition::Regex(vec![r"\d{4}".to_string()]),
+                        AutomodAction::HoldForReview,
+                    ),
+                    rule(2, AutomodCondition::Keywords(vec!["pin".to_string()]), AutomodAction::Block),
+                ],
+                0,
+            )
+            .unwrap();
+
+        let verdict = evaluate(&mut automod, "my pin is 1234", 0).unwrap();
+        assert_eq!(verdict.rule_id, 1);
+        assert_eq!(verdict.action, AutomodAction::HoldForReview);
+    }
+
+    #[test]
+    fn links_respect_allow_and_deny_lists() {
+        let filter = AutomodLinkFilter {
+            allowed_domains: vec!["oc.app".to_string()],
+            blocked_domains: vec![],
+        };
+        assert!(!links_triggered("see https://www.oc.app/community", &filter));
+        assert!(!links_triggered("see https://docs.oc.app", &filter));
+        assert!(links_triggered("see https://evil.com", &filter));
+
+        let filter = AutomodLinkFilter {
+            allowed_domains: vec![],
+            blocked_domains: vec!["evil.com".to_string()],
+        };
+        assert!(links_triggered("go to www.Evil.com now", &filter));
+        assert!(!links_triggered("go to https://notevil.com", &filter));
+    }
+
+    #[test]
+    fn repeated_messages_trigger_within_window() {
+        let mut automod = Automod::default();
+        automod
+            .set_rules(
+                vec![rule(
+                    1,
+                    AutomodCondition::RepeatedMessages(AutomodRepeatedMessages {
+                        max_repeats: 2,
+                        window: 1000,
+                    }),
+                    AutomodAction::Timeout(60_000),
+                )],
+                0,
+            )
+            .unwrap();
+
+        assert!(evaluate(&mut automod, "buy now", 0).is_none());
+        assert!(evaluate(&mut automod, "Buy now ", 100).is_none());
+        assert!(evaluate(&mut automod, "buy now", 200).is_some());
+        assert!(evaluate(&mut automod, "buy now", 5000).is_none());
+    }
+
+    #[test]
+    fn invalid_rules_rejected() {
+        let mut automod = Automod::default();
+        assert!(
+            automod
+                .set_rules(
+                    vec![rule(1, AutomodCondition::Regex(vec!["(".to_string()]), AutomodAction::Block)],
+                    0
+                )
+                .is_err()
+        );
+        assert!(
+            automod
+                .set_rules(
+                    vec![
+                        rule(1, AutomodCondition::MentionSpam(5), AutomodAction::Block),
+                        rule(1, AutomodCondition::NewMembers(1000), AutomodAction::Block)
+                    ],
+                    0
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn held_message_recorded_when_triggered() {
+        let mut automod = Automod::default();
+        let user_id = UserId::new(candid::Principal::from_slice(&[1]));
+        let verdict = AutomodVerdict {
+            rule_id: 1,
+            action: AutomodAction::HoldForReview,
+        };
+        automod.record_triggered(verdict, user_id, None, MessageId::from(1u64), 10);
+
+        assert_eq!(automod.details().held_messages.len(), 1);
+        assert_eq!(automod.details().triggered.len(), 1);
+        assert!(automod.take_held_message(None, MessageId::from(1u64), 20).is_some());
+        assert!(automod.details().held_messages.is_empty());
+    }
+
+    #[test]
+    fn held_messages_capped() {
+        let mut automod = Automod::default();
+        let user_id = UserId::new(candid::Principal::from_slice(&[1]));
+        let verdict = AutomodVerdict {
+            rule_id: 1,
+            action: AutomodAction::HoldForReview,
+        };
+        for i in 0..(MAX_HELD_MESSAGES as u64 + 10) {
+            automod.record_triggered(verdict, user_id, None, MessageId::from(i), 10);
+        }
+
+        let held = automod.details().held_messages;
+        assert_eq!(held.len(), MAX_HELD_MESSAGES);
+        assert_eq!(held[0].message_id, MessageId::from(10u64));
+    }
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use types::{EncryptionKeyEpoch, TimestampMillis, UserId};
+
+#[derive(Serialize, Deserialize)]
+pub struct ChatEncryption {
+    #[serde(rename = "b")]
+    enabled_by: UserId,
+    #[serde(rename = "e")]
+    epochs: Vec<EncryptionKeyEpochInternal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct EncryptionKeyEpochInternal {
+    #[serde(rename = "s")]
+    started: TimestampMillis,
+    #[serde(rename = "r")]
+    reason: KeyRotationReason,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyRotationReason {
+    #[serde(rename = "e")]
+    EncryptionEnabled,
+    #[serde(rename = "l")]
+    MemberLeft,
+    #[serde(rename = "r")]
+    MemberRemoved,
+}
+
+impl ChatEncryption {
+    pub fn new(enabled_by: UserId, now: TimestampMillis) -> ChatEncryption {
+        ChatEncryption {
+            enabled_by,
+            epochs: vec![EncryptionKeyEpochInternal {
+                started: now,
+                reason: KeyRotationReason::EncryptionEnabled,
+            }],
+        }
+    }
+
+    pub fn enabled_by(&self) -> UserId {
+        self.enabled_by
+    }
+
+    pub fn rotate(&mut self, reason: KeyRotationReason, now: TimestampMillis) -> u32 {
+        self.epochs.push(EncryptionKeyEpochInternal { started: now, reason });
+        self.current_epoch()
+    }
+
+    pub fn current_epoch(&self) -> u32 {
+        (self.epochs.len() - 1) as u32
+    }
+
+    pub fn last_updated(&self) -> TimestampMillis {
+        self.epochs.last().map(|e| e.started).unwrap_or_default()
+    }
+
+    // Returns the epochs which were active at any point at or after `since`
+    pub fn epochs_since(&self, since: TimestampMillis) -> Vec<EncryptionKeyEpoch> {
+        let first = self.epochs.partition_point(|e| e.started <= since).saturating_sub(1);
+
+        self.epochs
+            .iter()
+            .enumerate()
+            .skip(first)
+            .map(|(epoch, e)| EncryptionKeyEpoch {
+                epoch: epoch as u32,
+                started: e.started,
+            })
+            .collect()
+    }
+
+    pub fn is_epoch_visible(&self, epoch: u32, since: TimestampMillis) -> bool {
+        let epoch = epoch as usize;
+        if epoch >= self.epochs.len() {
+            return false;
+        }
+
+        // An epoch is visible if it had not been superseded by the time `since` was reached
+        self.epochs.get(epoch + 1).is_none_or(|next| next.started > since)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn epochs_since_includes_epoch_active_at_that_time() {
+        let mut encryption = ChatEncryption::new(Principal::anonymous().into(), 10);
+        encryption.rotate(KeyRotationReason::MemberLeft, 20);
+        encryption.rotate(KeyRotationReason::MemberRemoved, 30);
+
+        let epochs: Vec<_> = encryption.epochs_since(25).into_iter().map(|e| e.epoch).collect();
+
+        assert_eq!(epochs, vec![1, 2]);
+        assert!(!encryption.is_epoch_visible(0, 25));
+        assert!(encryption.is_epoch_visible(1, 25));
+        assert!(encryption.is_epoch_visible(2, 25));
+        assert!(!encryption.is_epoch_visible(3, 25));
+    }
+}
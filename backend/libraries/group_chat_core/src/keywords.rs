@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use types::UserId;
+
+// The keyword alerts of every member of a chat. Keywords are indexed by their first token so that a message can be
+// checked against all of them in a single pass over its words, rather than searching the message once per keyword.
+#[derive(Serialize, Deserialize, Default)]
+pub struct KeywordAlertsIndex {
+    by_user: BTreeMap<UserId, Vec<String>>,
+    by_first_token: BTreeMap<String, BTreeMap<String, BTreeSet<UserId>>>,
+}
+
+impl KeywordAlertsIndex {
+    pub fn is_empty(&self) -> bool {
+        self.by_user.is_empty()
+    }
+
+    pub fn get(&self, user_id: &UserId) -> Option<&Vec<String>> {
+        self.by_user.get(user_id)
+    }
+
+    // Keywords are expected to already be lowercase
+    pub fn set(&mut self, user_id: UserId, keywords: Vec<String>) {
+        self.remove(&user_id);
+
+        if keywords.is_empty() {
+            return;
+        }
+
+        for keyword in keywords.iter() {
+            self.by_first_token
+                .entry(first_token(keyword).to_string())
+                .or_default()
+                .entry(keyword.clone())
+                .or_default()
+                .insert(user_id);
+        }
+        self.by_user.insert(user_id, keywords);
+    }
+
+    pub fn remove(&mut self, user_id: &UserId) {
+        let Some(keywords) = self.by_user.remove(user_id) else {
+            return;
+        };
+
+        for keyword in keywords {
+            let token = first_token(&keyword);
+            if let Some(keywords_for_token) = self.by_first_token.get_mut(token) {
+                if let Some(users) = keywords_for_token.get_mut(&keyword) {
+                    users.remove(user_id);
+                    if users.is_empty() {
+                        keywords_for_token.remove(&keyword);
+                    }
+                }
+                if keywords_for_token.is_empty() {
+                    self.by_first_token.remove(token);
+                }
+            }
+        }
+    }
+
+    // Returns the users who have a keyword which appears in the text as a whole word (or phrase), ignoring case
+    pub fn matches(&self, text_lowercase: &str) -> BTreeSet<UserId> {
+        let mut matched = BTreeSet::new();
+        let mut previous = None;
+
+        for (index, c) in text_lowercase.char_indices() {
+            if !previous.is_some_and(char::is_alphanumeric) {
+                let remaining = &text_lowercase[index..];
+                if let Some(keywords) = self.by_first_token.get(first_token(remaining)) {
+                    for (keyword, users) in keywords {
+                        if is_prefix_ending_at_word_boundary(remaining, keyword) {
+                            matched.extend(users.iter().copied());
+                        }
+                    }
+                }
+            }
+            previous = Some(c);
+        }
+
+        matched
+    }
+}
+
+// The leading run of alphanumeric characters, or the first character if that isn't alphanumeric
+fn first_token(text: &str) -> &str {
+    match text.char_indices().find(|(_, c)| !c.is_alphanumeric()) {
+        Some((0, c)) => &text[..c.len_utf8()],
+        Some((index, _)) => &text[..index],
+        None => text,
+    }
+}
+
+fn is_prefix_ending_at_word_boundary(text: &str, prefix: &str) -> bool {
+    text.strip_prefix(prefix)
+        .is_some_and(|rest| !rest.chars().next().is_some_and(char::is_alphanumeric))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    fn user(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+
+    fn index_with(keywords: &[&str]) -> KeywordAlertsIndex {
+        let mut index = KeywordAlertsIndex::default();
+        index.set(user(1), keywords.iter().map(|k| k.to_string()).collect());
+        index
+    }
+
+    #[test]
+    fn keywords_must_match_whole_words() {
+        let index = index_with(&["openchat", "open chat", "café", "c++", "#release"]);
+        let matches = |text: &str| index.matches(text).contains(&user(1));
+
+        assert!(matches("the openchat release is out"));
+        assert!(matches("openchat!"));
+        assert!(matches("have you tried open chat?"));
+        assert!(matches("café au lait"));
+        assert!(matches("written in c++"));
+        assert!(matches("see the #release notes"));
+        assert!(!matches("reopenchat is a different word"));
+        assert!(!matches("openchats"));
+        assert!(!matches("open chats"));
+        assert!(!matches("cafés"));
+        assert!(!matches("pre#release"));
+    }
+
+    #[test]
+    fn matches_every_user_with_a_matching_keyword() {
+        let mut index = index_with(&["openchat"]);
+        index.set(user(2), vec!["open chat".to_string(), "openchat".to_string()]);
+        index.set(user(3), vec!["chat".to_string()]);
+        index.set(user(4), vec!["release".to_string()]);
+
+        let matched: Vec<_> = index.matches("openchat open chat").into_iter().collect();
+        assert_eq!(matched, vec![user(1), user(2), user(3)]);
+    }
+
+    #[test]
+    fn replacing_and_removing_keywords_updates_index() {
+        let mut index = index_with(&["openchat", "release"]);
+        index.set(user(2), vec!["openchat".to_string()]);
+
+        index.set(user(1), vec!["release".to_string()]);
+        assert_eq!(index.matches("openchat").into_iter().collect::<Vec<_>>(), vec![user(2)]);
+        assert_eq!(index.get(&user(1)), Some(&vec!["release".to_string()]));
+
+        index.remove(&user(2));
+        assert!(index.matches("openchat").is_empty());
+        assert!(!index.by_first_token.contains_key("openchat"));
+
+        index.set(user(1), Vec::new());
+        assert!(index.is_empty());
+        assert!(index.by_first_token.is_empty());
+    }
+}
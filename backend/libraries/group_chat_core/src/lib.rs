@@ -1,9 +1,11 @@
 use chat_events::{
-    AddRemoveReactionArgs, ChatEventInternal, ChatEvents, ChatEventsListReader, DeleteMessageSuccess,
-    DeleteUndeleteMessagesArgs, EditMessageArgs, EventPusher, GroupGateUpdatedInternal, MessageContentInternal,
-    NullEventPusher, PushEventResultInternal, PushMessageArgs, Reader, RegisterPollVoteArgs, RegisterPollVoteSuccess,
-    RemoveExpiredEventsResult, ReservePrizeSuccess, TipMessageArgs, UndeleteMessageSuccess, UpdateMessageSuccess,
+    AddRemoveReactionArgs, ChatEventInternal, ChatEvents, ChatEventsListReader, CryptoContentInternal, DeleteMessageSuccess,
+    DeleteUndeleteMessagesArgs, EditMessageArgs, EditMessageSuccess, EventPusher, GroupGateUpdatedInternal,
+    MessageContentInternal, NullEventPusher, PushEventResultInternal, PushMessageArgs, Reader, RegisterPollVoteArgs,
+    RegisterPollVoteSuccess, RemoveExpiredEventsResult, ReservePrizeSuccess, TextContentInternal, TipMessageArgs,
+    UndeleteMessageSuccess, UpdateMessageSuccess,
 };
+use constants::OPENCHAT_BOT_USER_ID;
 use group_community_common::MemberUpdate;
 use itertools::Itertools;
 use lazy_static::lazy_static;
@@ -14,30 +16,40 @@ use serde::{Deserialize, Serialize};
 use std::cmp::{Reverse, max, min};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use types::{
-    AccessGateConfig, AccessGateConfigInternal, AvatarChanged, BotMessageContext, BotNotification, Caller, Chat,
-    CustomPermission, Document, EventIndex, EventOrExpiredRange, EventWrapper, EventsCaller, EventsResponse,
-    ExternalUrlUpdated, GroupDescriptionChanged, GroupMember, GroupNameChanged, GroupPermissions, GroupReplyContext, GroupRole,
-    GroupRulesChanged, GroupSubtype, GroupVisibilityChanged, HydratedMention, MAX_RETURNED_MENTIONS, MemberLeft,
-    MembersRemoved, Message, MessageContent, MessageId, MessageIndex, MessageMatch, MessagePermissions, MessagePinned,
-    MessageUnpinned, MessagesResponse, Milliseconds, MultiUserChat, OCResult, OptionUpdate, OptionalGroupPermissions,
-    OptionalMessagePermissions, PermissionsChanged, Reaction, ReserveP2PSwapSuccess, RoleChanged, Rules, SelectedGroupUpdates,
-    SenderContext, ThreadPreview, TimestampMillis, Timestamped, UpdatedRules, UserId, UserType, UsersBlocked, UsersInvited,
-    Version, Versioned, VersionedRules, VideoCall, VideoCallPresence, VoteOperation, WebhookDetails,
+    AccessGateConfig, AccessGateConfigInternal, AutomodAction, AutomodDetails, AutomodRule, AvatarChanged, BotCaller,
+    BotMessageComponent, BotMessageContext, BotNotification, Caller, Chat, CustomPermission, Document, EncryptionKeyEpoch,
+    EventIndex, EventOrExpiredRange, EventWrapper, EventsCaller, EventsResponse, ExternalUrlUpdated, GroupDescriptionChanged,
+    GroupMember, GroupNameChanged, GroupPermissions, GroupReplyContext, GroupRole, GroupRulesChanged, GroupSubtype,
+    GroupVisibilityChanged, HydratedMention, MAX_REPORT_REASON_LENGTH, MAX_RETURNED_MENTIONS, MemberLeft, MemberTimedOut,
+    MembersRemoved, Message, MessageContent, MessageContentType, MessageId, MessageIndex, MessageMatch, MessagePermissions,
+    MessagePinned, MessageUnpinned, MessagesResponse, Milliseconds, ModerationAction, ModerationQueueItem, MultiUserChat,
+    OCResult, OptionUpdate, OptionalGroupPermissions, OptionalMessagePermissions, PermissionsChanged, Reaction,
+    ReserveP2PSwapSuccess, ResolvedModerationItem, RoleChanged, Rules, SelectedGroupUpdates, SenderContext, ThreadPreview,
+    TimestampMillis, Timestamped, UpdatedRules, UserId, UserType, UsersBlocked, UsersInvited, UsersUnblocked, Version,
+    Versioned, VersionedRules, VideoCall, VideoCallPresence, VoteOperation, WebhookDetails,
 };
 use utils::document::validate_avatar;
 use utils::text_validation::{
     StringLengthValidationError, validate_channel_name, validate_description, validate_group_name, validate_rules,
 };
 
+mod automod;
+mod encryption;
 mod invited_users;
+mod keywords;
 mod members;
 mod mentions;
+mod moderation_queue;
 mod roles;
 mod webhooks;
 
+pub use automod::*;
+pub use encryption::*;
 pub use invited_users::*;
+pub use keywords::*;
 pub use members::*;
 pub use mentions::*;
+pub use moderation_queue::*;
 pub use roles::*;
 pub use webhooks::*;
 
@@ -65,6 +77,12 @@ pub struct GroupChatCore {
     pub external_url: Timestamped<Option<String>>,
     at_everyone_mentions: BTreeMap<TimestampMillis, AtEveryoneMention>,
     pub webhooks: Webhooks,
+    #[serde(default)]
+    pub encryption: Option<ChatEncryption>,
+    #[serde(default)]
+    pub automod: Automod,
+    #[serde(default)]
+    pub moderation_queue: ModerationQueue,
 }
 
 #[expect(clippy::too_many_arguments)]
@@ -122,6 +140,9 @@ impl GroupChatCore {
             external_url: Timestamped::new(external_url, now),
             at_everyone_mentions: BTreeMap::new(),
             webhooks: Webhooks::default(),
+            encryption: None,
+            automod: Automod::default(),
+            moderation_queue: ModerationQueue::default(),
         }
     }
 
@@ -161,12 +182,56 @@ impl GroupChatCore {
         }
     }
 
+    pub fn is_message_visible_to(
+        &self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_index: MessageIndex,
+        message_id: MessageId,
+    ) -> bool {
+        let Ok(member) = self.members.get_verified_member(user_id) else {
+            return false;
+        };
+
+        self.events
+            .message_internal(
+                member.min_visible_event_index(),
+                thread_root_message_index,
+                message_index.into(),
+            )
+            .is_some_and(|(message, _)| message.message_id == message_id && message.deleted_by.is_none())
+    }
+
+    // Returns the component with the given id if it is attached to a message sent by the bot which
+    // is visible to the user
+    pub fn bot_message_component(
+        &self,
+        user_id: UserId,
+        bot_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        component_id: &str,
+    ) -> Option<BotMessageComponent> {
+        let min_visible_event_index = self.members.get_verified_member(user_id).ok()?.min_visible_event_index();
+
+        let (message, _) =
+            self.events
+                .message_internal(min_visible_event_index, thread_root_message_index, message_id.into())?;
+
+        if message.sender != bot_id || message.deleted_by.is_some() {
+            return None;
+        }
+
+        message.bot_context()?.component(component_id).cloned()
+    }
+
     pub fn details_last_updated(&self) -> TimestampMillis {
         [
             self.events.last_updated().unwrap_or_default(),
             self.invited_users.last_updated(),
             self.members.last_updated().unwrap_or_default(),
             self.webhooks.last_updated(),
+            self.encryption.as_ref().map(|e| e.last_updated()).unwrap_or_default(),
         ]
         .into_iter()
         .max()
@@ -322,7 +387,11 @@ impl GroupChatCore {
         let mut users_blocked_or_unblocked = HashSet::new();
         for (user_id, update) in self.members.iter_latest_updates(since) {
             match update {
-                MemberUpdate::Added | MemberUpdate::RoleChanged | MemberUpdate::Lapsed | MemberUpdate::Unlapsed => {
+                MemberUpdate::Added
+                | MemberUpdate::RoleChanged
+                | MemberUpdate::Lapsed
+                | MemberUpdate::Unlapsed
+                | MemberUpdate::TimedOut => {
                     if users_added_updated_or_removed.insert(user_id) {
                         if let Some(member) = self.members.get(&user_id) {
                             result.members_added_or_updated.push(GroupMember::from(&member));
@@ -570,8 +639,7 @@ impl GroupChatCore {
             if let Caller::BotV2(bot_now) = &caller {
                 if let Some(bot_message) = message.bot_context() {
                     if bot_now.bot == message.sender
-                        && bot_now.initiator.user() == bot_message.command.as_ref().map(|c| c.initiator)
-                        && bot_now.initiator.command() == bot_message.command.as_ref()
+                        && bot_message.matches_initiator(&bot_now.initiator)
                         && !bot_message.finalised
                     {
                         return self.update_bot_message(
@@ -610,6 +678,12 @@ impl GroupChatCore {
 
         let sender = caller.agent();
 
+        let automod_verdict = self.evaluate_automod(caller, &content, mentioned.len(), false, now);
+        if let Some(verdict) = automod_verdict.filter(|v| v.blocks_message()) {
+            self.record_automod_trigger(verdict, sender, thread_root_message_index, message_id, now);
+            return Err(OCErrorCode::MessageBlockedByAutomod.into());
+        }
+
         let sender_context = match caller {
             Caller::BotV2(bot) => Some(SenderContext::Bot(BotMessageContext::from(bot, finalised))),
             Caller::Webhook(_) => Some(SenderContext::Webhook),
@@ -632,10 +706,24 @@ impl GroupChatCore {
 
         let (message_event, bot_notification) = self.events.push_message(push_message_args, Some(event_pusher));
 
+        if let Some(verdict) = automod_verdict {
+            // The message is deleted (or held for review, which also deletes it until it is released). The caller
+            // skips all post-send processing so no one is notified about it.
+            self.apply_automod_verdict(verdict, sender, thread_root_message_index, message_id, now);
+            return Ok(SendMessageSuccess {
+                message_event,
+                users_to_notify: Vec::new(),
+                keyword_matches: Vec::new(),
+                unfinalised_bot_message: false,
+                removed_by_automod: true,
+                bot_notification: None,
+            });
+        }
+
         let unfinalised_bot_message = if let Caller::BotV2(_) = caller { !finalised } else { false };
 
-        let users_to_notify = if unfinalised_bot_message {
-            vec![]
+        let (users_to_notify, keyword_matches) = if unfinalised_bot_message {
+            (Vec::new(), Vec::new())
         } else {
             self.build_users_to_notify(
                 thread_root_message_index,
@@ -652,11 +740,76 @@ impl GroupChatCore {
         Ok(SendMessageSuccess {
             message_event,
             users_to_notify,
+            keyword_matches,
             unfinalised_bot_message,
+            removed_by_automod: false,
             bot_notification,
         })
     }
 
+    // Checks that a message which is to be funded by a crypto transfer could be sent, so that this can be verified before
+    // the transfer is made. Funded messages are exempt from automod, so the caption is checked against the initiator here.
+    pub fn check_can_send_funded_message(
+        &mut self,
+        caller: &Caller,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        message_type: MessageContentType,
+        caption: Option<&str>,
+        now: TimestampMillis,
+    ) -> OCResult {
+        if self
+            .events
+            .message_internal(EventIndex::default(), thread_root_message_index, message_id.into())
+            .is_some()
+        {
+            return Err(OCErrorCode::MessageIdAlreadyExists.into());
+        }
+
+        let Some(initiator) = caller.initiator() else {
+            return Ok(());
+        };
+
+        let member = self.members.get_verified_member(initiator)?;
+
+        if self.members.timed_out_until(&initiator, now).is_some() {
+            return Err(OCErrorCode::InitiatorTimedOut.into());
+        }
+
+        if !member.check_rules(&self.rules.value) {
+            return Err(OCErrorCode::ChatRulesNotAccepted.into());
+        }
+
+        if !member
+            .role()
+            .can_send_message(message_type, thread_root_message_index.is_some(), &self.permissions)
+        {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        let min_visible_event_index = member.min_visible_event_index();
+        if let Some(root_message_index) = thread_root_message_index {
+            if !self
+                .events
+                .is_accessible(min_visible_event_index, None, root_message_index.into())
+            {
+                return Err(OCErrorCode::ThreadNotFound.into());
+            }
+        }
+
+        if let Some(text) = caption {
+            let content = MessageContentInternal::Text(TextContentInternal { text: text.to_string() });
+            if let Some(verdict) = self.evaluate_automod(&Caller::User(initiator), &content, 0, true, now) {
+                if verdict.blocks_message() {
+                    self.record_automod_trigger(verdict, initiator, thread_root_message_index, message_id, now);
+                }
+                return Err(OCErrorCode::MessageBlockedByAutomod.into());
+            }
+        }
+
+        Ok(())
+    }
+
     fn update_bot_message(
         &mut self,
         caller: &Caller,
@@ -696,7 +849,7 @@ impl GroupChatCore {
 
         let message_event = reader.message_event(message_id.into(), Some(caller.agent())).unwrap();
 
-        let users_to_notify = if finalise {
+        let (users_to_notify, keyword_matches) = if finalise {
             self.build_users_to_notify(
                 thread_root_message_index,
                 min_visible_event_index,
@@ -708,13 +861,15 @@ impl GroupChatCore {
                 now,
             )
         } else {
-            vec![]
+            (Vec::new(), Vec::new())
         };
 
         Ok(SendMessageSuccess {
             message_event,
             users_to_notify,
+            keyword_matches,
             unfinalised_bot_message: !finalise,
+            removed_by_automod: false,
             bot_notification: result.and_then(|r| r.bot_notification),
         })
     }
@@ -729,7 +884,7 @@ impl GroupChatCore {
         everyone_mentioned: bool,
         suppressed: bool,
         now: TimestampMillis,
-    ) -> Vec<UserId> {
+    ) -> (Vec<UserId>, Vec<UserId>) {
         let message = &message_event.event;
         let message_index = message.message_index;
         let initiator = message
@@ -799,6 +954,19 @@ impl GroupChatCore {
             }
         }
 
+        // Keyword alerts apply even if the member has muted the chat, since they have explicitly asked for them
+        let mut keyword_matches = Vec::new();
+        if !suppressed && !self.members.keyword_alerts().is_empty() {
+            if let Some(text) = message.content.text().map(|t| t.to_lowercase()) {
+                for user_id in self.members.keyword_alerts().matches(&text) {
+                    if !mentions.contains(&user_id) {
+                        keyword_matches.push(user_id);
+                        users_to_notify.insert(user_id);
+                    }
+                }
+            }
+        }
+
         // Exclude the sender, bots, lapsed members, and suspended members from notifications
         users_to_notify.remove(&message.sender);
         users_to_notify.remove(&initiator);
@@ -812,7 +980,9 @@ impl GroupChatCore {
             users_to_notify.remove(user_id);
         }
 
-        users_to_notify.iter().copied().collect()
+        keyword_matches.retain(|u| users_to_notify.contains(u));
+
+        (users_to_notify.into_iter().collect(), keyword_matches)
     }
 
     fn prepare_send_message(
@@ -840,6 +1010,10 @@ impl GroupChatCore {
         let (min_visible_event_index, can_mention) = if let Some(initiator) = caller.initiator() {
             let member = self.members.get_verified_member(initiator)?;
 
+            if self.members.timed_out_until(&initiator, now).is_some() {
+                return Err(OCErrorCode::InitiatorTimedOut.into());
+            }
+
             if !matches!(content, MessageContentInternal::VideoCall(_)) && !member.check_rules(&self.rules.value) {
                 return Err(OCErrorCode::ChatRulesNotAccepted.into());
             }
@@ -867,6 +1041,390 @@ impl GroupChatCore {
         })
     }
 
+    pub fn edit_message<P: EventPusher>(
+        &mut self,
+        args: EditMessageArgs,
+        event_pusher: Option<P>,
+    ) -> OCResult<EditMessageSuccess> {
+        let sender = args.sender;
+        let thread_root_message_index = args.thread_root_message_index;
+        let message_id = args.message_id;
+        let now = args.now;
+
+        if self.members.timed_out_until(&sender, now).is_some() {
+            return Err(OCErrorCode::InitiatorTimedOut.into());
+        }
+
+        let automod_verdict = self.evaluate_automod(&Caller::User(sender), &args.content, 0, true, now);
+        if let Some(verdict) = automod_verdict.filter(|v| v.blocks_message()) {
+            self.record_automod_trigger(verdict, sender, thread_root_message_index, message_id, now);
+            return Err(OCErrorCode::MessageBlockedByAutomod.into());
+        }
+
+        let result = self.events.edit_message(args, event_pusher)?;
+
+        if let Some(verdict) = automod_verdict {
+            self.apply_automod_verdict(verdict, sender, thread_root_message_index, message_id, now);
+        }
+
+        Ok(result)
+    }
+
+    pub fn set_automod_rules(&mut self, user_id: UserId, rules: Vec<AutomodRule>, now: TimestampMillis) -> OCResult {
+        let member = self.members.get_verified_member(user_id)?;
+
+        if !member.role().is_same_or_senior(GroupRoleInternal::Admin) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        self.automod
+            .set_rules(rules, now)
+            .map_err(|error| OCErrorCode::InvalidAutomodRules.with_message(error))
+    }
+
+    pub fn automod_details(&self, user_id: UserId) -> OCResult<AutomodDetails> {
+        let member = self.members.get_verified_member(user_id)?;
+
+        if !member.role().can_delete_messages(&self.permissions) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        Ok(self.automod.details())
+    }
+
+    // Releasing a held message restores it, otherwise it remains deleted
+    pub fn review_held_message(
+        &mut self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        release: bool,
+        now: TimestampMillis,
+    ) -> OCResult<Option<BotNotification>> {
+        let member = self.members.get_verified_member(user_id)?;
+
+        if !member.role().can_delete_messages(&self.permissions) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        if self
+            .automod
+            .take_held_message(thread_root_message_index, message_id, now)
+            .is_none()
+        {
+            return Err(OCErrorCode::MessageNotFound.into());
+        }
+
+        if !release {
+            return Ok(None);
+        }
+
+        let (_, result) = self
+            .events
+            .undelete_messages(DeleteUndeleteMessagesArgs {
+                caller: OPENCHAT_BOT_USER_ID,
+                is_admin: true,
+                min_visible_event_index: EventIndex::default(),
+                thread_root_message_index,
+                message_ids: vec![message_id],
+                now,
+            })
+            .pop()
+            .ok_or(OCErrorCode::MessageNotFound)?;
+
+        result
+    }
+
+    pub fn report_message(
+        &mut self,
+        reporter: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        reason: Option<String>,
+        now: TimestampMillis,
+    ) -> OCResult<ReportMessageSuccess> {
+        let member = self.members.get_verified_member(reporter)?;
+
+        if reason.as_ref().is_some_and(|r| r.len() > MAX_REPORT_REASON_LENGTH) {
+            return Err(OCErrorCode::TextTooLong.with_message(MAX_REPORT_REASON_LENGTH));
+        }
+
+        let (message, _) = self
+            .events
+            .message_internal(member.min_visible_event_index(), thread_root_message_index, message_id.into())
+            .filter(|(m, _)| m.deleted_by.is_none())
+            .ok_or(OCErrorCode::MessageNotFound)?;
+
+        let snapshot = || ModerationQueueItemInternal {
+            thread_root_message_index,
+            message_id,
+            message_index: message.message_index,
+            sender: message.sender,
+            content: message.content.clone(),
+            reports: Vec::new(),
+        };
+
+        let (report_id, moderators_to_notify) =
+            match self
+                .moderation_queue
+                .add_report(reporter, reason, snapshot, thread_root_message_index, message_id, now)
+            {
+                AddReportResult::NewItem(id) => (id, self.moderators().into_iter().filter(|u| *u != reporter).collect()),
+                AddReportResult::AddedToExistingItem(id) => (id, Vec::new()),
+                AddReportResult::AlreadyReported => return Err(OCErrorCode::AlreadyReported.into()),
+                AddReportResult::QueueFull => return Err(OCErrorCode::ModerationQueueFull.into()),
+                AddReportResult::TooManyPendingReports => return Err(OCErrorCode::TooManyPendingReports.into()),
+            };
+
+        Ok(ReportMessageSuccess {
+            report_id,
+            message_index: message.message_index,
+            pending_reports: self.moderation_queue.pending_count() as u32,
+            moderators_to_notify,
+        })
+    }
+
+    pub fn moderation_queue(
+        &self,
+        user_id: UserId,
+        pending_after: Option<u32>,
+        max_pending: usize,
+        resolved_before: Option<TimestampMillis>,
+        max_resolved: usize,
+    ) -> OCResult<ModerationQueueSummary> {
+        let member = self.members.get_verified_member(user_id)?;
+
+        if !member.role().can_delete_messages(&self.permissions) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        Ok(ModerationQueueSummary {
+            pending: self.moderation_queue.pending(pending_after, max_pending),
+            pending_count: self.moderation_queue.pending_count() as u32,
+            resolved: self.moderation_queue.resolved(resolved_before, max_resolved),
+        })
+    }
+
+    // Returns the sender of the reported message, so that they can be removed by the caller if required
+    pub fn reported_message_sender(&self, user_id: UserId, report_id: u32) -> OCResult<UserId> {
+        let member = self.members.get_verified_member(user_id)?;
+
+        if !member.role().can_delete_messages(&self.permissions) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        let item = self.moderation_queue.get(report_id).ok_or(OCErrorCode::ReportNotFound)?;
+        Ok(item.sender)
+    }
+
+    // `ModerationAction::RemoveMember` only records the resolution, the member must already have been removed
+    pub fn resolve_report(
+        &mut self,
+        user_id: UserId,
+        report_id: u32,
+        action: ModerationAction,
+        now: TimestampMillis,
+    ) -> OCResult<Option<BotNotification>> {
+        let sender = self.reported_message_sender(user_id, report_id)?;
+
+        if matches!(action, ModerationAction::RemoveMember) && self.members.get(&sender).is_some() {
+            return Err(OCErrorCode::InvalidRequest.with_message("The sender must be removed before the report is resolved"));
+        }
+        let item = self.moderation_queue.get(report_id).cloned().unwrap();
+
+        let bot_notification = match action {
+            ModerationAction::Dismiss | ModerationAction::RemoveMember => None,
+            ModerationAction::DeleteMessage => {
+                match self
+                    .delete_messages(
+                        Caller::User(user_id),
+                        item.thread_root_message_index,
+                        vec![item.message_id],
+                        false,
+                        now,
+                    )?
+                    .pop()
+                {
+                    Some((_, Ok(success))) => success.bot_notification,
+                    Some((_, Err(error))) => return Err(error),
+                    None => return Err(OCErrorCode::MessageNotFound.into()),
+                }
+            }
+            ModerationAction::Timeout(duration) => self.timeout_member(user_id, sender, Some(duration), now)?,
+        };
+
+        self.moderation_queue.resolve(report_id, action, user_id, now)?;
+        Ok(bot_notification)
+    }
+
+    // Passing `None` as the duration lifts any existing timeout
+    pub fn timeout_member(
+        &mut self,
+        user_id: UserId,
+        target_user_id: UserId,
+        duration: Option<Milliseconds>,
+        now: TimestampMillis,
+    ) -> OCResult<Option<BotNotification>> {
+        let role = self.members.get_verified_member(user_id)?.role();
+        let target_role = self
+            .members
+            .get(&target_user_id)
+            .map(|m| m.role().value)
+            .ok_or(OCErrorCode::TargetUserNotInChat)?;
+
+        if !role.can_delete_messages(&self.permissions) || target_role.is_same_or_senior(role) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        let until = duration.map(|d| now.saturating_add(d));
+        Ok(self.set_member_timeout(user_id, target_user_id, until, now))
+    }
+
+    // Lifts any timeouts and temporary blocks which have expired, returning the result of each event pushed
+    pub fn lift_expired_restrictions(&mut self, now: TimestampMillis) -> Vec<Option<BotNotification>> {
+        let result = self.members.lift_expired_restrictions(now);
+        let mut bot_notifications = Vec::new();
+
+        for user_id in result.timeouts_lifted {
+            let event = MemberTimedOut {
+                user_id,
+                timed_out_by: OPENCHAT_BOT_USER_ID,
+                until: None,
+            };
+            bot_notifications.push(
+                self.events
+                    .push_main_event(ChatEventInternal::MemberTimedOut(Box::new(event)), now)
+                    .bot_notification,
+            );
+        }
+
+        if !result.users_unblocked.is_empty() {
+            let event = UsersUnblocked {
+                user_ids: result.users_unblocked,
+                unblocked_by: OPENCHAT_BOT_USER_ID,
+            };
+            bot_notifications.push(
+                self.events
+                    .push_main_event(ChatEventInternal::UsersUnblocked(Box::new(event)), now)
+                    .bot_notification,
+            );
+        }
+
+        bot_notifications
+    }
+
+    fn set_member_timeout(
+        &mut self,
+        timed_out_by: UserId,
+        target_user_id: UserId,
+        until: Option<TimestampMillis>,
+        now: TimestampMillis,
+    ) -> Option<BotNotification> {
+        if !self.members.set_timeout(target_user_id, until, now) {
+            return None;
+        }
+
+        let event = MemberTimedOut {
+            user_id: target_user_id,
+            timed_out_by,
+            until,
+        };
+        self.events
+            .push_main_event(ChatEventInternal::MemberTimedOut(Box::new(event)), now)
+            .bot_notification
+    }
+
+    // The members who are able to act on reported messages
+    fn moderators(&self) -> Vec<UserId> {
+        let roles = [
+            (self.members.owners(), GroupRoleInternal::Owner),
+            (self.members.admins(), GroupRoleInternal::Admin),
+            (self.members.moderators(), GroupRoleInternal::Moderator),
+        ];
+
+        roles
+            .into_iter()
+            .filter(|(_, role)| role.can_delete_messages(&self.permissions))
+            .flat_map(|(user_ids, _)| user_ids.iter().copied())
+            .collect()
+    }
+
+    // Moderators and above are exempt from automod, as are non-user callers such as bots and webhooks. Messages
+    // containing funds which have already been transferred are also exempt since they can no longer be blocked
+    // or removed without the funds being lost.
+    fn evaluate_automod(
+        &mut self,
+        caller: &Caller,
+        content: &MessageContentInternal,
+        mention_count: usize,
+        is_edit: bool,
+        now: TimestampMillis,
+    ) -> Option<AutomodVerdict> {
+        let Caller::User(user_id) = caller else {
+            return None;
+        };
+
+        if matches!(
+            content,
+            MessageContentInternal::Crypto(_) | MessageContentInternal::Prize(_) | MessageContentInternal::P2PSwap(_)
+        ) {
+            return None;
+        }
+
+        let member = self.members.get_verified_member(*user_id).ok()?;
+        if member.user_type().is_bot() || member.role().is_same_or_senior(GroupRoleInternal::Moderator) {
+            return None;
+        }
+        let date_joined = member.date_added();
+
+        self.automod.evaluate(
+            AutomodMessage {
+                sender: *user_id,
+                date_joined,
+                content,
+                mention_count,
+                is_edit,
+            },
+            now,
+        )
+    }
+
+    fn record_automod_trigger(
+        &mut self,
+        verdict: AutomodVerdict,
+        sender: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        now: TimestampMillis,
+    ) {
+        self.automod
+            .record_triggered(verdict, sender, thread_root_message_index, message_id, now);
+
+        if let AutomodAction::Timeout(duration) = verdict.action {
+            self.set_member_timeout(OPENCHAT_BOT_USER_ID, sender, Some(now.saturating_add(duration)), now);
+        }
+    }
+
+    fn apply_automod_verdict(
+        &mut self,
+        verdict: AutomodVerdict,
+        sender: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        now: TimestampMillis,
+    ) {
+        self.record_automod_trigger(verdict, sender, thread_root_message_index, message_id, now);
+
+        self.events.delete_messages(DeleteUndeleteMessagesArgs {
+            caller: OPENCHAT_BOT_USER_ID,
+            is_admin: true,
+            min_visible_event_index: EventIndex::default(),
+            thread_root_message_index,
+            message_ids: vec![message_id],
+            now,
+        });
+    }
+
     pub fn add_reaction<P: EventPusher>(
         &mut self,
         caller: Caller,
@@ -888,6 +1446,9 @@ impl GroupChatCore {
                 if !member.role().can_react_to_messages(&self.permissions) {
                     return Err(OCErrorCode::InitiatorNotAuthorized.into());
                 }
+                if self.members.timed_out_until(&initiator, now).is_some() {
+                    return Err(OCErrorCode::InitiatorTimedOut.into());
+                }
                 min_visible_event_index = member.min_visible_event_index()
             }
         }
@@ -931,16 +1492,73 @@ impl GroupChatCore {
         })
     }
 
-    pub fn tip_message<P: EventPusher>(&mut self, args: TipMessageArgs, event_pusher: P) -> OCResult<UpdateMessageSuccess> {
-        let member = self.members.get_verified_member(args.user_id)?;
+    pub fn tip_message<P: EventPusher>(
+        &mut self,
+        caller: &Caller,
+        args: TipMessageArgs,
+        event_pusher: P,
+    ) -> OCResult<UpdateMessageSuccess> {
+        let min_visible_event_index =
+            self.verify_caller(caller, |role, permissions| role.can_react_to_messages(permissions))?;
 
-        if !member.role().can_react_to_messages(&self.permissions) {
-            return Err(OCErrorCode::InitiatorNotAuthorized.into());
-        }
+        self.events.tip_message(args, min_visible_event_index, Some(event_pusher))
+    }
 
-        let min_visible_event_index = member.min_visible_event_index();
+    pub fn validate_tip(
+        &self,
+        caller: &Caller,
+        recipient: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+    ) -> OCResult {
+        let min_visible_event_index =
+            self.verify_caller(caller, |role, permissions| role.can_react_to_messages(permissions))?;
 
-        self.events.tip_message(args, min_visible_event_index, Some(event_pusher))
+        self.events.validate_tip(
+            caller.agent(),
+            recipient,
+            thread_root_message_index,
+            message_id,
+            min_visible_event_index,
+        )
+    }
+
+    // Records a tip which has already been validated by `validate_tip` and paid for. The caller isn't verified again so
+    // that a change to the bot's permissions while the transfer was in flight doesn't leave the transfer unrecorded.
+    pub fn record_prevalidated_tip<P: EventPusher>(
+        &mut self,
+        args: TipMessageArgs,
+        event_pusher: P,
+    ) -> OCResult<UpdateMessageSuccess> {
+        self.events.tip_message(args, EventIndex::default(), Some(event_pusher))
+    }
+
+    // Records a completed bot transfer as a crypto message in the main chat. This is the fallback used when the
+    // message or tip which the transfer was made for can no longer be recorded, so that every transfer made from a
+    // bot's funds appears in the chat events.
+    pub fn push_bot_transfer_message<P: EventPusher>(
+        &mut self,
+        bot: &BotCaller,
+        message_id: MessageId,
+        content: CryptoContentInternal,
+        event_pusher: P,
+        now: TimestampMillis,
+    ) -> EventWrapper<Message> {
+        let push_message_args = PushMessageArgs {
+            sender: bot.bot,
+            thread_root_message_index: None,
+            message_id,
+            content: MessageContentInternal::Crypto(content),
+            sender_context: Some(SenderContext::Bot(BotMessageContext::from(bot, true))),
+            mentioned: Vec::new(),
+            replies_to: None,
+            forwarded: false,
+            sender_is_bot: true,
+            block_level_markdown: false,
+            now,
+        };
+
+        self.events.push_message(push_message_args, Some(event_pusher)).0
     }
 
     pub fn delete_messages(
@@ -1058,15 +1676,26 @@ impl GroupChatCore {
 
     pub fn change_role(
         &mut self,
-        caller: UserId,
+        caller: Caller,
         target_user: UserId,
         new_role: GroupRole,
         is_caller_platform_moderator: bool,
         is_user_platform_moderator: bool,
         now: TimestampMillis,
     ) -> OCResult<ChangeRoleSuccess> {
+        if matches!(caller, Caller::Webhook(_)) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        // Bots can't make users owners or change the role of existing owners
+        if matches!(caller, Caller::BotV2(_))
+            && (matches!(new_role, GroupRole::Owner) || self.members.get(&target_user).is_some_and(|m| m.role().is_owner()))
+        {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
         let prev_role = self.members.change_role(
-            caller,
+            caller.initiator(),
             target_user,
             new_role.into(),
             &self.permissions,
@@ -1079,7 +1708,7 @@ impl GroupChatCore {
             user_ids: vec![target_user],
             old_role: prev_role.into(),
             new_role,
-            changed_by: caller,
+            changed_by: caller.agent(),
         };
 
         let result = self
@@ -1094,18 +1723,11 @@ impl GroupChatCore {
 
     pub fn pin_message(
         &mut self,
-        user_id: UserId,
+        caller: Caller,
         message_index: MessageIndex,
         now: TimestampMillis,
     ) -> OCResult<PushEventResultInternal> {
-        let member = self.members.get_verified_member(user_id)?;
-
-        if !member.role().can_pin_messages(&self.permissions) {
-            return Err(OCErrorCode::InitiatorNotAuthorized.into());
-        }
-
-        let min_visible_event_index = member.min_visible_event_index();
-        let user_id = member.user_id();
+        let min_visible_event_index = self.verify_caller(&caller, |role, permissions| role.can_pin_messages(permissions))?;
 
         if !self.events.is_accessible(min_visible_event_index, None, message_index.into()) {
             return Err(OCErrorCode::MessageNotFound.into());
@@ -1115,7 +1737,7 @@ impl GroupChatCore {
             let push_event_result = self.events.push_main_event(
                 ChatEventInternal::MessagePinned(Box::new(MessagePinned {
                     message_index,
-                    pinned_by: user_id,
+                    pinned_by: caller.agent(),
                 })),
                 now,
             );
@@ -1129,30 +1751,21 @@ impl GroupChatCore {
 
     pub fn unpin_message(
         &mut self,
-        user_id: UserId,
+        caller: Caller,
         message_index: MessageIndex,
         now: TimestampMillis,
     ) -> OCResult<PushEventResultInternal> {
-        let member = self.members.get_verified_member(user_id)?;
+        let min_visible_event_index = self.verify_caller(&caller, |role, permissions| role.can_pin_messages(permissions))?;
 
-        if !member.role().can_pin_messages(&self.permissions) {
-            return Err(OCErrorCode::InitiatorNotAuthorized.into());
-        }
-
-        if !self
-            .events
-            .is_accessible(member.min_visible_event_index(), None, message_index.into())
-        {
+        if !self.events.is_accessible(min_visible_event_index, None, message_index.into()) {
             return Err(OCErrorCode::MessageNotFound.into());
         }
 
-        let user_id = member.user_id();
-
         if self.remove_pinned_message(message_index, now) {
             let push_event_result = self.events.push_main_event(
                 ChatEventInternal::MessageUnpinned(Box::new(MessageUnpinned {
                     message_index,
-                    unpinned_by: user_id,
+                    unpinned_by: caller.agent(),
                     due_to_message_deleted: false,
                 })),
                 now,
@@ -1314,6 +1927,7 @@ impl GroupChatCore {
         self.can_leave(user_id)?;
 
         let removed = self.members.remove(user_id, now).unwrap();
+        self.rotate_encryption_key(KeyRotationReason::MemberLeft, now);
 
         let result = self
             .events
@@ -1325,11 +1939,19 @@ impl GroupChatCore {
         })
     }
 
+    // Removes a user whose account has been deleted, rotating the encryption key as for any other member leaving
+    pub fn remove_deleted_user(&mut self, user_id: UserId, now: TimestampMillis) -> Option<GroupMemberInternal> {
+        let removed = self.members.remove(user_id, now)?;
+        self.rotate_encryption_key(KeyRotationReason::MemberLeft, now);
+        Some(removed)
+    }
+
     pub fn remove_member(
         &mut self,
         caller: Caller,
         target_user_id: UserId,
         block: bool,
+        block_until: Option<TimestampMillis>,
         now: TimestampMillis,
     ) -> OCResult<Option<BotNotification>> {
         let agent = caller.agent();
@@ -1360,11 +1982,19 @@ impl GroupChatCore {
         }
 
         // Remove the user from the group
-        self.members.remove(target_user_id, now);
+        if self.members.remove(target_user_id, now).is_some() {
+            self.rotate_encryption_key(KeyRotationReason::MemberRemoved, now);
+        }
 
-        if block && !self.members.block(target_user_id, now) {
-            // Return Ok if the user was already blocked
-            return Ok(None);
+        if block {
+            let blocked = match block_until {
+                Some(until) => self.members.block_until(target_user_id, until, now),
+                None => self.members.block(target_user_id, now),
+            };
+            if !blocked {
+                // Return Ok if the user was already blocked with the same expiry
+                return Ok(None);
+            }
         }
 
         // Push relevant event
@@ -1372,6 +2002,7 @@ impl GroupChatCore {
             let event = UsersBlocked {
                 user_ids: vec![target_user_id],
                 blocked_by: agent,
+                expires: block_until,
             };
 
             ChatEventInternal::UsersBlocked(Box::new(event))
@@ -1388,9 +2019,78 @@ impl GroupChatCore {
         Ok(result.bot_notification)
     }
 
+    pub fn enable_encryption(&mut self, user_id: UserId, now: TimestampMillis) -> OCResult<EncryptionKeyEpoch> {
+        let member = self.members.get_verified_member(user_id)?;
+
+        if !member.role().is_owner() {
+            Err(OCErrorCode::InitiatorNotAuthorized.into())
+        } else if self.is_public.value {
+            Err(OCErrorCode::ChatPublic.into())
+        } else if self.encryption.is_some() {
+            Err(OCErrorCode::EncryptionAlreadyEnabled.into())
+        } else {
+            self.encryption = Some(ChatEncryption::new(user_id, now));
+            Ok(EncryptionKeyEpoch { epoch: 0, started: now })
+        }
+    }
+
+    pub fn encryption_key_epochs(&self, user_id: UserId) -> OCResult<Vec<EncryptionKeyEpoch>> {
+        let member = self.members.get_verified_member(user_id)?;
+        let encryption = self.encryption.as_ref().ok_or(OCErrorCode::EncryptionNotEnabled)?;
+
+        Ok(encryption.epochs_since(self.encryption_visible_from(member.date_added())))
+    }
+
+    pub fn can_access_encryption_key(&self, user_id: UserId, epoch: u32) -> bool {
+        let Some(encryption) = self.encryption.as_ref() else {
+            return false;
+        };
+
+        self.members
+            .get_verified_member(user_id)
+            .is_ok_and(|m| encryption.is_epoch_visible(epoch, self.encryption_visible_from(m.date_added())))
+    }
+
+    // Members can only derive the keys of epochs that were still in use when they joined, unless the history
+    // is visible to new joiners, in which case they can derive the keys for every epoch
+    fn encryption_visible_from(&self, date_added: TimestampMillis) -> TimestampMillis {
+        if self.history_visible_to_new_joiners { 0 } else { date_added }
+    }
+
+    // Checks the caller's initiator is a member whose role permits the action and returns the
+    // initiator's min visible event index. Autonomous bots have no initiator so must have already
+    // been checked against the permissions granted to the bot.
+    pub fn verify_caller<F: FnOnce(GroupRoleInternal, &GroupPermissions) -> bool>(
+        &self,
+        caller: &Caller,
+        is_permitted: F,
+    ) -> OCResult<EventIndex> {
+        if matches!(caller, Caller::Webhook(_)) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        let Some(initiator) = caller.initiator() else {
+            return Ok(EventIndex::default());
+        };
+
+        let member = self.members.get_verified_member(initiator)?;
+
+        if is_permitted(member.role(), &self.permissions) {
+            Ok(member.min_visible_event_index())
+        } else {
+            Err(OCErrorCode::InitiatorNotAuthorized.into())
+        }
+    }
+
+    fn rotate_encryption_key(&mut self, reason: KeyRotationReason, now: TimestampMillis) {
+        if let Some(encryption) = self.encryption.as_mut() {
+            encryption.rotate(reason, now);
+        }
+    }
+
     pub fn update(
         &mut self,
-        user_id: UserId,
+        caller: Caller,
         name: Option<String>,
         description: Option<String>,
         rules: Option<UpdatedRules>,
@@ -1403,10 +2103,10 @@ impl GroupChatCore {
         external_url: OptionUpdate<String>,
         now: TimestampMillis,
     ) -> OCResult<UpdateSuccessResult> {
-        self.can_update(user_id, &name, &description, &rules, &avatar, permissions.as_ref(), &public)?;
+        self.can_update(&caller, &name, &description, &rules, &avatar, permissions.as_ref(), &public)?;
 
         Ok(self.do_update(
-            user_id,
+            caller.agent(),
             name,
             description,
             rules,
@@ -1423,7 +2123,7 @@ impl GroupChatCore {
 
     pub fn can_update(
         &self,
-        user_id: UserId,
+        caller: &Caller,
         name: &Option<String>,
         description: &Option<String>,
         rules: &Option<UpdatedRules>,
@@ -1462,17 +2162,22 @@ impl GroupChatCore {
             return Err(OCErrorCode::AvatarTooBig.with_json(&error));
         }
 
-        let member = self.members.get_verified_member(user_id)?;
+        if matches!(public, Some(true)) && self.encryption.is_some() {
+            return Err(OCErrorCode::ChatPublic.with_message("Encrypted chats cannot be made public"));
+        }
 
-        let group_permissions = &self.permissions;
-        if !member.role().can_update_group(group_permissions)
-            || (permissions.is_some() && !member.role().can_change_permissions())
-            || (public.is_some() && !member.role().can_change_group_visibility())
-        {
-            Err(OCErrorCode::InitiatorNotAuthorized.into())
-        } else {
-            Ok(())
+        // Bots can only update the name, description, rules and avatar
+        if caller.is_bot() && (permissions.is_some() || public.is_some()) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
         }
+
+        self.verify_caller(caller, |role, group_permissions| {
+            role.can_update_group(group_permissions)
+                && (permissions.is_none() || role.can_change_permissions())
+                && (public.is_none() || role.can_change_group_visibility())
+        })?;
+
+        Ok(())
     }
 
     pub fn do_update(
@@ -1673,16 +2378,12 @@ impl GroupChatCore {
         result
     }
 
-    pub fn follow_thread(
-        &mut self,
-        user_id: UserId,
-        thread_root_message_index: MessageIndex,
-        now: TimestampMillis,
-    ) -> OCResult {
-        let member = self.members.get_verified_member(user_id)?;
+    pub fn follow_thread(&mut self, caller: Caller, thread_root_message_index: MessageIndex, now: TimestampMillis) -> OCResult {
+        let min_visible_event_index = self.verify_caller(&caller, |_, _| true)?;
+        let user_id = caller.agent();
 
         self.events
-            .follow_thread(thread_root_message_index, user_id, member.min_visible_event_index(), now)?;
+            .follow_thread(thread_root_message_index, user_id, min_visible_event_index, now)?;
 
         self.members.update_member(&user_id, |m| {
             m.followed_threads.insert(thread_root_message_index, now);
@@ -1694,14 +2395,15 @@ impl GroupChatCore {
 
     pub fn unfollow_thread(
         &mut self,
-        user_id: UserId,
+        caller: Caller,
         thread_root_message_index: MessageIndex,
         now: TimestampMillis,
     ) -> OCResult {
-        let member = self.members.get_verified_member(user_id)?;
+        let min_visible_event_index = self.verify_caller(&caller, |_, _| true)?;
+        let user_id = caller.agent();
 
         self.events
-            .unfollow_thread(thread_root_message_index, user_id, member.min_visible_event_index(), now)?;
+            .unfollow_thread(thread_root_message_index, user_id, min_visible_event_index, now)?;
 
         self.members.update_member(&user_id, |m| {
             m.followed_threads.remove(thread_root_message_index);
@@ -1986,10 +2688,20 @@ impl GroupChatCore {
     }
 }
 
+pub struct ModerationQueueSummary {
+    pub pending: Vec<ModerationQueueItem>,
+    pub pending_count: u32,
+    pub resolved: Vec<ResolvedModerationItem>,
+}
+
 pub struct SendMessageSuccess {
     pub message_event: EventWrapper<Message>,
     pub users_to_notify: Vec<UserId>,
+    // Users who weren't mentioned but who have a keyword alert which matches the message
+    pub keyword_matches: Vec<UserId>,
     pub unfinalised_bot_message: bool,
+    // The message was deleted or held for review by automod
+    pub removed_by_automod: bool,
     pub bot_notification: Option<BotNotification>,
 }
 
@@ -2133,7 +2845,230 @@ impl AtEveryoneMention {
     }
 }
 
+pub struct ReportMessageSuccess {
+    pub report_id: u32,
+    pub message_index: MessageIndex,
+    pub pending_reports: u32,
+    pub moderators_to_notify: Vec<UserId>,
+}
+
 pub struct LeaveGroupSuccess {
     pub member: GroupMemberInternal,
     pub bot_notification: Option<BotNotification>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use ic_stable_structures::DefaultMemoryImpl;
+    use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
+
+    const NOW: TimestampMillis = 1_000_000;
+
+    fn user(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+
+    // User 1 creates the group, users 2 to 6 are members with notifications muted
+    fn setup_group() -> GroupChatCore {
+        let memory = MemoryManager::init(DefaultMemoryImpl::default());
+        stable_memory_map::init(memory.get(MemoryId::new(1)));
+
+        let mut chat = GroupChatCore::new(
+            MultiUserChat::Group(Principal::from_slice(&[100]).into()),
+            user(1),
+            true,
+            "Test".to_string(),
+            String::new(),
+            Rules::default(),
+            None,
+            None,
+            true,
+            true,
+            GroupPermissions::default(),
+            None,
+            None,
+            UserType::User,
+            0,
+            None,
+            NOW,
+        );
+
+        for index in 2..=6 {
+            chat.members.add(
+                user(index),
+                NOW,
+                EventIndex::default(),
+                MessageIndex::default(),
+                true,
+                UserType::User,
+            );
+        }
+        chat
+    }
+
+    fn set_keywords(chat: &mut GroupChatCore, user_id: UserId, keywords: &[&str]) {
+        assert!(
+            chat.members
+                .set_keyword_alerts(user_id, keywords.iter().map(|k| k.to_string()).collect())
+        );
+    }
+
+    fn send(
+        chat: &mut GroupChatCore,
+        sender: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: u64,
+        text: &str,
+        mentioned: &[UserId],
+    ) -> SendMessageSuccess {
+        chat.send_message(
+            &Caller::User(sender),
+            thread_root_message_index,
+            message_id.into(),
+            MessageContentInternal::Text(TextContentInternal { text: text.to_string() }),
+            None,
+            mentioned,
+            false,
+            None,
+            false,
+            false,
+            NullEventPusher,
+            true,
+            NOW,
+        )
+        .unwrap()
+    }
+
+    fn sorted(mut users: Vec<UserId>) -> Vec<UserId> {
+        users.sort();
+        users
+    }
+
+    #[test]
+    fn muted_members_are_notified_of_keyword_matches() {
+        let mut chat = setup_group();
+        set_keywords(&mut chat, user(2), &["openchat"]);
+        set_keywords(&mut chat, user(3), &["release"]);
+
+        let result = send(&mut chat, user(1), None, 1, "OpenChat is great", &[]);
+
+        assert_eq!(result.keyword_matches, vec![user(2)]);
+        assert_eq!(result.users_to_notify, vec![user(2)]);
+    }
+
+    #[test]
+    fn keyword_matches_exclude_mentioned_members() {
+        let mut chat = setup_group();
+        set_keywords(&mut chat, user(2), &["openchat"]);
+        set_keywords(&mut chat, user(3), &["openchat"]);
+
+        let result = send(&mut chat, user(1), None, 2, "openchat", &[user(2)]);
+
+        // User 2 is notified of the mention instead
+        assert_eq!(result.keyword_matches, vec![user(3)]);
+        assert_eq!(sorted(result.users_to_notify), vec![user(2), user(3)]);
+    }
+
+    #[test]
+    fn keyword_matches_exclude_sender_lapsed_and_suspended_members() {
+        let mut chat = setup_group();
+        for index in 2..=5 {
+            set_keywords(&mut chat, user(index), &["openchat"]);
+        }
+        chat.members.update_lapsed(user(3), true, NOW);
+        chat.members.set_suspended(user(4), true, NOW);
+
+        let result = send(&mut chat, user(5), None, 3, "openchat", &[]);
+
+        // User 1 created the group so hasn't muted it
+        assert_eq!(result.keyword_matches, vec![user(2)]);
+        assert_eq!(sorted(result.users_to_notify), vec![user(1), user(2)]);
+    }
+
+    #[test]
+    fn keyword_matches_apply_to_thread_replies() {
+        let mut chat = setup_group();
+        set_keywords(&mut chat, user(2), &["openchat"]);
+
+        let root = send(&mut chat, user(1), None, 4, "hello", &[]);
+        let result = send(
+            &mut chat,
+            user(3),
+            Some(root.message_event.event.message_index),
+            10,
+            "what about openchat?",
+            &[],
+        );
+
+        assert_eq!(result.keyword_matches, vec![user(2)]);
+        assert!(result.users_to_notify.contains(&user(2)));
+    }
+
+    #[test]
+    fn removing_deleted_user_rotates_encryption_key() {
+        let mut chat = setup_group();
+        chat.is_public = Timestamped::new(false, NOW);
+        chat.enable_encryption(user(1), NOW).unwrap();
+
+        assert!(chat.remove_deleted_user(user(2), NOW + 1).is_some());
+        assert!(chat.remove_deleted_user(user(2), NOW + 2).is_none());
+
+        let epochs = chat.encryption_key_epochs(user(1)).unwrap();
+        assert_eq!(epochs.len(), 2);
+        assert_eq!(epochs[1].started, NOW + 1);
+        assert!(!chat.can_access_encryption_key(user(2), 1));
+    }
+
+    #[test]
+    fn permanent_block_replaces_temporary_ban() {
+        let mut chat = setup_group();
+        let caller = Caller::User(user(1));
+
+        chat.remove_member(caller.clone(), user(2), true, Some(NOW + 1000), NOW)
+            .unwrap();
+        assert_eq!(chat.members.blocked_until(&user(2)), Some(NOW + 1000));
+
+        // Re-banning replaces the existing expiry
+        chat.remove_member(caller.clone(), user(2), true, Some(NOW + 5000), NOW + 1)
+            .unwrap();
+        assert_eq!(chat.members.blocked_until(&user(2)), Some(NOW + 5000));
+
+        chat.remove_member(caller, user(2), true, None, NOW + 2).unwrap();
+        assert_eq!(chat.members.blocked_until(&user(2)), None);
+        assert_eq!(chat.members.next_restriction_expiry(), None);
+
+        chat.lift_expired_restrictions(NOW + 10_000);
+        assert!(chat.members.is_blocked(&user(2)));
+    }
+
+    #[test]
+    fn no_keyword_matches_when_notifications_suppressed() {
+        let mut chat = setup_group();
+        set_keywords(&mut chat, user(2), &["openchat"]);
+
+        let result = chat
+            .send_message(
+                &Caller::User(user(1)),
+                None,
+                1u64.into(),
+                MessageContentInternal::Text(TextContentInternal {
+                    text: "openchat".to_string(),
+                }),
+                None,
+                &[],
+                false,
+                None,
+                true,
+                false,
+                NullEventPusher,
+                true,
+                NOW,
+            )
+            .unwrap();
+
+        assert!(result.keyword_matches.is_empty());
+        assert!(result.users_to_notify.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::AccessRulesInternal;
+use crate::keywords::KeywordAlertsIndex;
 use crate::members::stable_memory::MembersStableStorage;
 use crate::mentions::Mentions;
 use crate::roles::GroupRoleInternal;
@@ -23,6 +24,8 @@ use utils::timestamped_set::TimestampedSet;
 mod proptests;
 mod stable_memory;
 
+pub use stable_memory::{MEMBER_VALUE_VERSION, migrate_member_bytes};
+
 const MAX_MEMBERS_PER_GROUP: u32 = 100_000;
 
 #[derive(Serialize, Deserialize)]
@@ -37,9 +40,15 @@ pub struct GroupMembers {
     notifications_unmuted: BTreeSet<UserId>,
     lapsed: BTreeSet<UserId>,
     blocked: BTreeSet<UserId>,
+    #[serde(default)]
+    blocked_until: BTreeMap<UserId, TimestampMillis>,
+    #[serde(default)]
+    timed_out: BTreeMap<UserId, TimestampMillis>,
     suspended: BTreeSet<UserId>,
     updates: BTreeSet<(TimestampMillis, UserId, MemberUpdate)>,
     latest_update_removed: TimestampMillis,
+    #[serde(default)]
+    keyword_alerts: KeywordAlertsIndex,
 }
 
 #[expect(clippy::too_many_arguments)]
@@ -61,6 +70,7 @@ impl GroupMembers {
             rules_accepted: Some(Timestamped::new(Version::zero(), now)),
             user_type,
             lapsed: Timestamped::default(),
+            timed_out_until: Timestamped::default(),
         };
 
         GroupMembers {
@@ -70,6 +80,8 @@ impl GroupMembers {
             admins: BTreeSet::new(),
             moderators: BTreeSet::new(),
             blocked: BTreeSet::new(),
+            blocked_until: BTreeMap::new(),
+            timed_out: BTreeMap::new(),
             bots: if user_type.is_bot() {
                 [(creator_user_id, user_type)].into_iter().collect()
             } else {
@@ -80,6 +92,7 @@ impl GroupMembers {
             suspended: BTreeSet::new(),
             updates: BTreeSet::new(),
             latest_update_removed: 0,
+            keyword_alerts: KeywordAlertsIndex::default(),
         }
     }
 
@@ -125,6 +138,7 @@ impl GroupMembers {
                 rules_accepted: None,
                 user_type,
                 lapsed: Timestamped::default(),
+                timed_out_until: Timestamped::default(),
             };
             self.members_map.insert(member.user_id, member.clone());
             if user_type.is_bot() {
@@ -164,13 +178,33 @@ impl GroupMembers {
         if member.suspended.value {
             self.suspended.remove(&user_id);
         }
+        self.timed_out.remove(&user_id);
+        self.keyword_alerts.remove(&user_id);
         self.member_ids.remove(&user_id);
         self.prune_then_insert_member_update(user_id, MemberUpdate::Removed, now);
         Some(member)
     }
 
+    // Blocks the user permanently, replacing any temporary ban they may already have
     pub fn block(&mut self, user_id: UserId, now: TimestampMillis) -> bool {
-        if self.blocked.insert(user_id) {
+        self.set_blocked(user_id, None, now)
+    }
+
+    // Blocks the user until the given timestamp, after which `lift_expired_restrictions` will unblock them. If the user
+    // is already blocked, the new expiry replaces the existing one.
+    pub fn block_until(&mut self, user_id: UserId, until: TimestampMillis, now: TimestampMillis) -> bool {
+        self.set_blocked(user_id, Some(until), now)
+    }
+
+    // Returns true if the user was not already blocked with the same expiry
+    fn set_blocked(&mut self, user_id: UserId, until: Option<TimestampMillis>, now: TimestampMillis) -> bool {
+        let newly_blocked = self.blocked.insert(user_id);
+        let previous_until = match until {
+            Some(until) => self.blocked_until.insert(user_id, until),
+            None => self.blocked_until.remove(&user_id),
+        };
+
+        if newly_blocked || previous_until != until {
             self.prune_then_insert_member_update(user_id, MemberUpdate::Blocked, now);
             true
         } else {
@@ -180,6 +214,7 @@ impl GroupMembers {
 
     pub fn unblock(&mut self, user_id: UserId, now: TimestampMillis) -> bool {
         if self.blocked.remove(&user_id) {
+            self.blocked_until.remove(&user_id);
             self.prune_then_insert_member_update(user_id, MemberUpdate::Unblocked, now);
             true
         } else {
@@ -191,6 +226,67 @@ impl GroupMembers {
         self.blocked.iter().copied().collect()
     }
 
+    pub fn blocked_until(&self, user_id: &UserId) -> Option<TimestampMillis> {
+        self.blocked_until.get(user_id).copied()
+    }
+
+    // Passing `None` lifts any existing timeout
+    pub fn set_timeout(&mut self, user_id: UserId, until: Option<TimestampMillis>, now: TimestampMillis) -> bool {
+        if !self.member_ids.contains(&user_id) {
+            return false;
+        }
+
+        let updated = match until {
+            Some(until) => self.timed_out.insert(user_id, until) != Some(until),
+            None => self.timed_out.remove(&user_id).is_some(),
+        };
+
+        if updated {
+            self.update_member(&user_id, |m| {
+                m.timed_out_until = Timestamped::new(until, now);
+                true
+            });
+            self.prune_then_insert_member_update(user_id, MemberUpdate::TimedOut, now);
+        }
+        updated
+    }
+
+    pub fn timed_out_until(&self, user_id: &UserId, now: TimestampMillis) -> Option<TimestampMillis> {
+        self.timed_out.get(user_id).copied().filter(|until| *until > now)
+    }
+
+    pub fn next_restriction_expiry(&self) -> Option<TimestampMillis> {
+        self.timed_out.values().chain(self.blocked_until.values()).min().copied()
+    }
+
+    pub fn lift_expired_restrictions(&mut self, now: TimestampMillis) -> LiftExpiredRestrictionsResult {
+        let timeouts_lifted: Vec<_> = self
+            .timed_out
+            .iter()
+            .filter(|(_, until)| **until <= now)
+            .map(|(user_id, _)| *user_id)
+            .collect();
+
+        let users_unblocked: Vec<_> = self
+            .blocked_until
+            .iter()
+            .filter(|(_, until)| **until <= now)
+            .map(|(user_id, _)| *user_id)
+            .collect();
+
+        for user_id in timeouts_lifted.iter() {
+            self.set_timeout(*user_id, None, now);
+        }
+        for user_id in users_unblocked.iter() {
+            self.unblock(*user_id, now);
+        }
+
+        LiftExpiredRestrictionsResult {
+            timeouts_lifted,
+            users_unblocked,
+        }
+    }
+
     pub fn get_verified_member(&self, user_id: UserId) -> Result<VerifiedGroupMember, OCErrorCode> {
         if !self.member_ids.contains(&user_id) {
             Err(OCErrorCode::InitiatorNotFound)
@@ -259,7 +355,7 @@ impl GroupMembers {
 
     pub fn change_role(
         &mut self,
-        caller_id: UserId,
+        caller_id: Option<UserId>,
         user_id: UserId,
         new_role: GroupRoleInternal,
         permissions: &GroupPermissions,
@@ -267,12 +363,15 @@ impl GroupMembers {
         is_user_platform_moderator: bool,
         now: TimestampMillis,
     ) -> OCResult<GroupRoleInternal> {
-        // Is the caller authorized to change the user to this role
-        let member = self.get_verified_member(caller_id)?;
-
-        // Platform moderators can always promote themselves to owner
-        if !(member.role.can_change_roles(new_role, permissions) || (is_caller_platform_moderator && new_role.is_owner())) {
-            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        // Is the caller authorized to change the user to this role. If there is no caller then the
+        // role is being changed by an autonomous bot whose permissions have already been checked.
+        if let Some(caller_id) = caller_id {
+            let member = self.get_verified_member(caller_id)?;
+
+            // Platform moderators can always promote themselves to owner
+            if !(member.role.can_change_roles(new_role, permissions) || (is_caller_platform_moderator && new_role.is_owner())) {
+                return Err(OCErrorCode::InitiatorNotAuthorized.into());
+            }
         }
 
         let member = match self.members_map.get(&user_id) {
@@ -281,7 +380,7 @@ impl GroupMembers {
         };
 
         // Platform moderators cannot be demoted from owner except by themselves
-        if is_user_platform_moderator && member.role.is_owner() && user_id != caller_id {
+        if is_user_platform_moderator && member.role.is_owner() && caller_id != Some(user_id) {
             return Err(OCErrorCode::InitiatorNotAuthorized.into());
         }
 
@@ -437,6 +536,20 @@ impl GroupMembers {
         &self.bots
     }
 
+    // Keywords are expected to already be lowercase. Returns false if the user is not a member.
+    pub fn set_keyword_alerts(&mut self, user_id: UserId, keywords: Vec<String>) -> bool {
+        if !self.member_ids.contains(&user_id) {
+            false
+        } else {
+            self.keyword_alerts.set(user_id, keywords);
+            true
+        }
+    }
+
+    pub fn keyword_alerts(&self) -> &KeywordAlertsIndex {
+        &self.keyword_alerts
+    }
+
     pub fn notifications_unmuted(&self) -> &BTreeSet<UserId> {
         &self.notifications_unmuted
     }
@@ -449,6 +562,10 @@ impl GroupMembers {
         &self.suspended
     }
 
+    pub fn timed_out(&self) -> &BTreeMap<UserId, TimestampMillis> {
+        &self.timed_out
+    }
+
     pub fn has_membership_changed(&self, since: TimestampMillis) -> bool {
         self.iter_latest_updates(since)
             .any(|(_, u)| matches!(u, MemberUpdate::Added | MemberUpdate::Removed))
@@ -499,6 +616,7 @@ impl GroupMembers {
         let mut notifications_unmuted = BTreeSet::new();
         let mut lapsed = BTreeSet::new();
         let mut suspended = BTreeSet::new();
+        let mut timed_out = BTreeMap::new();
 
         let all_members = self.members_map.all_members();
 
@@ -523,6 +641,10 @@ impl GroupMembers {
             if member.suspended.value {
                 suspended.insert(member.user_id);
             }
+
+            if let Some(until) = member.timed_out_until.value {
+                timed_out.insert(member.user_id, until);
+            }
         }
 
         assert_eq!(member_ids, self.member_ids);
@@ -532,6 +654,8 @@ impl GroupMembers {
         assert_eq!(notifications_unmuted, self.notifications_unmuted);
         assert_eq!(lapsed, self.lapsed);
         assert_eq!(suspended, self.suspended);
+        assert_eq!(timed_out, self.timed_out);
+        assert!(self.blocked_until.keys().all(|user_id| self.blocked.contains(user_id)));
     }
 }
 
@@ -569,6 +693,11 @@ pub struct AddMemberSuccess {
     pub bot_notification: Option<BotNotification>,
 }
 
+pub struct LiftExpiredRestrictionsResult {
+    pub timeouts_lifted: Vec<UserId>,
+    pub users_unblocked: Vec<UserId>,
+}
+
 pub struct ChangeRoleSuccess {
     pub prev_role: GroupRoleInternal,
     pub bot_notification: Option<BotNotification>,
@@ -591,6 +720,7 @@ pub struct GroupMemberInternal {
     min_visible_event_index: EventIndex,
     min_visible_message_index: MessageIndex,
     lapsed: Timestamped<bool>,
+    timed_out_until: Timestamped<Option<TimestampMillis>>,
 }
 
 impl GroupMemberInternal {
@@ -622,6 +752,10 @@ impl GroupMemberInternal {
         &self.suspended
     }
 
+    pub fn timed_out_until(&self) -> &Timestamped<Option<TimestampMillis>> {
+        &self.timed_out_until
+    }
+
     pub fn last_updated(&self) -> TimestampMillis {
         [
             self.date_added,
@@ -630,6 +764,7 @@ impl GroupMemberInternal {
             self.suspended.timestamp,
             self.rules_accepted.as_ref().map(|r| r.timestamp).unwrap_or_default(),
             self.lapsed.timestamp,
+            self.timed_out_until.timestamp,
         ]
         .into_iter()
         .max()
@@ -739,6 +874,7 @@ impl From<&GroupMemberInternal> for GroupMember {
             date_added: m.date_added,
             role: m.role.value.into(),
             lapsed: m.lapsed.value,
+            timed_out_until: m.timed_out_until.value,
         }
     }
 }
@@ -824,6 +960,8 @@ pub struct GroupMemberStableStorage {
     min_visible_message_index: MessageIndex,
     #[serde(rename = "la", default, skip_serializing_if = "is_default")]
     lapsed: Timestamped<bool>,
+    #[serde(rename = "to", default, skip_serializing_if = "is_default")]
+    timed_out_until: Timestamped<Option<TimestampMillis>>,
 }
 
 impl GroupMemberStableStorage {
@@ -844,6 +982,7 @@ impl GroupMemberStableStorage {
             min_visible_event_index: self.min_visible_event_index,
             min_visible_message_index: self.min_visible_message_index,
             lapsed: self.lapsed,
+            timed_out_until: self.timed_out_until,
         }
     }
 }
@@ -865,6 +1004,7 @@ impl From<GroupMemberInternal> for GroupMemberStableStorage {
             min_visible_event_index: value.min_visible_event_index,
             min_visible_message_index: value.min_visible_message_index,
             lapsed: value.lapsed,
+            timed_out_until: value.timed_out_until,
         }
     }
 }
@@ -904,6 +1044,7 @@ mod tests {
             rules_accepted: None,
             user_type: UserType::User,
             lapsed: Timestamped::default(),
+            timed_out_until: Timestamped::default(),
         };
 
         let member2 = GroupMemberInternal2 {
@@ -939,12 +1080,13 @@ mod tests {
             rules_accepted: Some(Timestamped::new(Version::zero(), 1)),
             user_type: UserType::Bot,
             lapsed: Timestamped::new(false, 1),
+            timed_out_until: Timestamped::new(Some(1), 1),
         };
 
         let member_bytes = msgpack::serialize_then_unwrap(&member);
         let member_bytes_len = member_bytes.len();
 
-        assert_eq!(member_bytes_len, 157);
+        assert_eq!(member_bytes_len, 167);
 
         let _deserialized: GroupMemberStableStorage = msgpack::deserialize_then_unwrap(&member_bytes);
     }
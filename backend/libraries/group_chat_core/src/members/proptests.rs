@@ -43,6 +43,15 @@ enum Operation {
         user_index: usize,
         suspended: bool,
     },
+    SetTimeout {
+        user_index: usize,
+        duration: Option<u64>,
+    },
+    BlockUntil {
+        user_index: usize,
+        duration: u64,
+    },
+    LiftExpiredRestrictions,
 }
 
 fn operation_strategy() -> impl Strategy<Value = Operation> {
@@ -59,6 +68,9 @@ fn operation_strategy() -> impl Strategy<Value = Operation> {
         1 => Just(Operation::UnlapseAll),
         2 => any::<usize>().prop_map(|user_index| Operation::SetSuspended { user_index, suspended: true }),
         1 => any::<usize>().prop_map(|user_index| Operation::SetSuspended { user_index, suspended: false }),
+        3 => (any::<usize>(), proptest::option::of(0..10_000u64)).prop_map(|(user_index, duration)| Operation::SetTimeout { user_index, duration }),
+        2 => (any::<usize>(), 0..10_000u64).prop_map(|(user_index, duration)| Operation::BlockUntil { user_index, duration }),
+        2 => Just(Operation::LiftExpiredRestrictions),
     ]
 }
 
@@ -102,7 +114,15 @@ fn execute_operation(members: &mut GroupMembers, op: Operation, timestamp: Times
         } => {
             let owner = get(&members.owners, owner_index);
             let user_id = get(&members.member_ids, user_index);
-            let _ = members.change_role(owner, user_id, role, &GroupPermissions::default(), false, false, timestamp);
+            let _ = members.change_role(
+                Some(owner),
+                user_id,
+                role,
+                &GroupPermissions::default(),
+                false,
+                false,
+                timestamp,
+            );
         }
         Operation::ToggleMuteNotifications { user_index, mute } => {
             let user_id = get(&members.member_ids, user_index);
@@ -149,6 +169,20 @@ fn execute_operation(members: &mut GroupMembers, op: Operation, timestamp: Times
                 members.set_suspended(user_id, false, timestamp);
             }
         }
+        Operation::SetTimeout { user_index, duration } => {
+            let user_id = get(&members.member_ids, user_index);
+            members.set_timeout(user_id, duration.map(|d| timestamp + d), timestamp);
+        }
+        Operation::BlockUntil { user_index, duration } => {
+            let user_id = get(&members.member_ids, user_index);
+            if members.owners.len() != 1 || members.owners.first() != Some(&user_id) {
+                members.remove(user_id, timestamp);
+                members.block_until(user_id, timestamp + duration, timestamp);
+            }
+        }
+        Operation::LiftExpiredRestrictions => {
+            members.lift_expired_restrictions(timestamp);
+        }
     };
 }
 
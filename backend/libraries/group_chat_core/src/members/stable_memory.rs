@@ -2,7 +2,9 @@ use crate::{GroupMemberInternal, GroupMemberStableStorage};
 use candid::{Deserialize, Principal};
 use serde::Serialize;
 use serde_bytes::ByteBuf;
-use stable_memory_map::{Key, KeyPrefix, StableMemoryMap, UserIdKeyPrefix, with_map, with_map_mut};
+use stable_memory_map::{
+    Key, KeyPrefix, StableMemoryMap, UserIdKeyPrefix, unwrap_versioned, with_map, with_map_mut, wrap_versioned,
+};
 use types::{MultiUserChat, UserId};
 
 #[derive(Serialize, Deserialize)]
@@ -56,7 +58,8 @@ impl MembersStableStorage {
                     total_bytes += v.len();
                     total_bytes < max_bytes
                 })
-                .map(|(k, v)| (k.user_id(), ByteBuf::from(v)))
+                // Strip the envelope so that the bytes can be read by canisters which predate it
+                .map(|(k, v)| (k.user_id(), ByteBuf::from(unwrap_versioned(&v).1.to_vec())))
                 .collect()
         })
     }
@@ -78,9 +81,8 @@ pub fn write_members_from_bytes(chat: MultiUserChat, members: Vec<(UserId, ByteB
     let mut latest = None;
     with_map_mut(|m| {
         for (user_id, byte_buf) in members {
-            let bytes = byte_buf.into_vec();
-            // Check that the bytes are valid
-            let _ = bytes_to_member(&bytes);
+            // Rewrite the member in the latest format, which also checks that the bytes are valid
+            let bytes = member_to_bytes(bytes_to_member(byte_buf.as_slice()));
             latest = Some(user_id);
             m.insert(prefix.create_key(&user_id), bytes);
         }
@@ -88,10 +90,19 @@ pub fn write_members_from_bytes(chat: MultiUserChat, members: Vec<(UserId, ByteB
     latest
 }
 
-fn member_to_bytes(member: GroupMemberStableStorage) -> Vec<u8> {
+// Version 0 covers members written before the versioned envelope, some of which still use the long field names
+pub const MEMBER_VALUE_VERSION: u8 = 1;
+
+// Rewrites a member in the latest format, after which the aliases for the old field names are no longer needed
+pub fn migrate_member_bytes(_version: u8, payload: &[u8]) -> Vec<u8> {
+    let member: GroupMemberStableStorage = msgpack::deserialize_then_unwrap(payload);
     msgpack::serialize_then_unwrap(member)
 }
 
+fn member_to_bytes(member: GroupMemberStableStorage) -> Vec<u8> {
+    wrap_versioned(MEMBER_VALUE_VERSION, msgpack::serialize_then_unwrap(member))
+}
+
 fn bytes_to_member(bytes: &[u8]) -> GroupMemberStableStorage {
-    msgpack::deserialize_then_unwrap(bytes)
+    msgpack::deserialize_then_unwrap(unwrap_versioned(bytes).1)
 }
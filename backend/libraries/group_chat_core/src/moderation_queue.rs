@@ -0,0 +1,343 @@
+use chat_events::MessageContentInternal;
+use oc_error_codes::OCErrorCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::ops::Bound;
+use types::{
+    MessageId, MessageIndex, MessageReport, ModerationAction, ModerationQueueItem, OCResult, ResolvedModerationItem,
+    TimestampMillis, UserId,
+};
+
+const MAX_RESOLVED_HISTORY_LENGTH: usize = 1000;
+const MAX_PENDING_ITEMS: usize = 1000;
+const MAX_PENDING_REPORTS_PER_USER: usize = 20;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ModerationQueue {
+    #[serde(rename = "p", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pending: BTreeMap<u32, ModerationQueueItemInternal>,
+    #[serde(rename = "r", default, skip_serializing_if = "VecDeque::is_empty")]
+    resolved: VecDeque<ResolvedModerationItemInternal>,
+    #[serde(rename = "n", default)]
+    next_id: u32,
+    #[serde(rename = "u", default)]
+    last_updated: TimestampMillis,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ModerationQueueItemInternal {
+    #[serde(rename = "t", default, skip_serializing_if = "Option::is_none")]
+    pub thread_root_message_index: Option<MessageIndex>,
+    #[serde(rename = "m")]
+    pub message_id: MessageId,
+    #[serde(rename = "i")]
+    pub message_index: MessageIndex,
+    #[serde(rename = "s")]
+    pub sender: UserId,
+    #[serde(rename = "c")]
+    pub content: MessageContentInternal,
+    #[serde(rename = "r")]
+    pub reports: Vec<MessageReportInternal>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MessageReportInternal {
+    #[serde(rename = "u")]
+    reporter: UserId,
+    #[serde(rename = "r", default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(rename = "t")]
+    timestamp: TimestampMillis,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ResolvedModerationItemInternal {
+    #[serde(rename = "i")]
+    id: u32,
+    #[serde(rename = "q")]
+    item: ModerationQueueItemInternal,
+    #[serde(rename = "a")]
+    action: ModerationAction,
+    #[serde(rename = "b")]
+    resolved_by: UserId,
+    #[serde(rename = "t")]
+    timestamp: TimestampMillis,
+}
+
+pub enum AddReportResult {
+    NewItem(u32),
+    AddedToExistingItem(u32),
+    AlreadyReported,
+    QueueFull,
+    TooManyPendingReports,
+}
+
+impl ModerationQueue {
+    pub fn add_report(
+        &mut self,
+        reporter: UserId,
+        reason: Option<String>,
+        snapshot: impl FnOnce() -> ModerationQueueItemInternal,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        now: TimestampMillis,
+    ) -> AddReportResult {
+        let report = MessageReportInternal {
+            reporter,
+            reason,
+            timestamp: now,
+        };
+
+        let pending_reports_by_user = self
+            .pending
+            .values()
+            .filter(|i| i.reports.iter().any(|r| r.reporter == reporter))
+            .count();
+
+        let existing = self
+            .pending
+            .iter_mut()
+            .find(|(_, i)| i.thread_root_message_index == thread_root_message_index && i.message_id == message_id);
+
+        let result = if let Some((id, item)) = existing {
+            if item.reports.iter().any(|r| r.reporter == reporter) {
+                return AddReportResult::AlreadyReported;
+            }
+            if pending_reports_by_user >= MAX_PENDING_REPORTS_PER_USER {
+                return AddReportResult::TooManyPendingReports;
+            }
+            item.reports.push(report);
+            AddReportResult::AddedToExistingItem(*id)
+        } else {
+            if pending_reports_by_user >= MAX_PENDING_REPORTS_PER_USER {
+                return AddReportResult::TooManyPendingReports;
+            }
+            if self.pending.len() >= MAX_PENDING_ITEMS {
+                return AddReportResult::QueueFull;
+            }
+
+            let id = self.next_id;
+            self.next_id += 1;
+
+            let mut item = snapshot();
+            item.reports.push(report);
+            self.pending.insert(id, item);
+            AddReportResult::NewItem(id)
+        };
+
+        self.last_updated = now;
+        result
+    }
+
+    pub fn get(&self, id: u32) -> Option<&ModerationQueueItemInternal> {
+        self.pending.get(&id)
+    }
+
+    pub fn resolve(&mut self, id: u32, action: ModerationAction, resolved_by: UserId, now: TimestampMillis) -> OCResult {
+        let item = self.pending.remove(&id).ok_or(OCErrorCode::ReportNotFound)?;
+
+        if self.resolved.len() >= MAX_RESOLVED_HISTORY_LENGTH {
+            self.resolved.pop_front();
+        }
+        self.resolved.push_back(ResolvedModerationItemInternal {
+            id,
+            item,
+            action,
+            resolved_by,
+            timestamp: now,
+        });
+        self.last_updated = now;
+        Ok(())
+    }
+
+    // Returns the pending items in the order they were first reported, starting after the given id
+    pub fn pending(&self, after: Option<u32>, max_results: usize) -> Vec<ModerationQueueItem> {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+
+        self.pending
+            .range((start, Bound::Unbounded))
+            .take(max_results)
+            .map(|(id, item)| item.hydrate(*id))
+            .collect()
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    // Returns the resolved items, most recent first
+    pub fn resolved(&self, before: Option<TimestampMillis>, max_results: usize) -> Vec<ResolvedModerationItem> {
+        self.resolved
+            .iter()
+            .rev()
+            .filter(|r| before.is_none_or(|ts| r.timestamp < ts))
+            .take(max_results)
+            .map(|r| ResolvedModerationItem {
+                item: r.item.hydrate(r.id),
+                action: r.action,
+                resolved_by: r.resolved_by,
+                timestamp: r.timestamp,
+            })
+            .collect()
+    }
+
+    pub fn last_updated(&self) -> TimestampMillis {
+        self.last_updated
+    }
+}
+
+impl ModerationQueueItemInternal {
+    fn hydrate(&self, id: u32) -> ModerationQueueItem {
+        ModerationQueueItem {
+            id,
+            thread_root_message_index: self.thread_root_message_index,
+            message_id: self.message_id,
+            message_index: self.message_index,
+            sender: self.sender,
+            content: self.content.clone().hydrate(None),
+            reports: self
+                .reports
+                .iter()
+                .map(|r| MessageReport {
+                    reporter: r.reporter,
+                    reason: r.reason.clone(),
+                    timestamp: r.timestamp,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use chat_events::TextContentInternal;
+
+    fn user(id: u8) -> UserId {
+        UserId::new(Principal::from_slice(&[id]))
+    }
+
+    fn snapshot() -> ModerationQueueItemInternal {
+        ModerationQueueItemInternal {
+            thread_root_message_index: None,
+            message_id: 1u64.into(),
+            message_index: 0u32.into(),
+            sender: user(1),
+            content: MessageContentInternal::Text(TextContentInternal {
+                text: "spam".to_string(),
+            }),
+            reports: Vec::new(),
+        }
+    }
+
+    fn snapshot_for(message_id: u64) -> ModerationQueueItemInternal {
+        ModerationQueueItemInternal {
+            message_id: message_id.into(),
+            ..snapshot()
+        }
+    }
+
+    #[test]
+    fn reports_for_same_message_are_grouped() {
+        let mut queue = ModerationQueue::default();
+
+        assert!(matches!(
+            queue.add_report(user(2), None, snapshot, None, 1u64.into(), 1),
+            AddReportResult::NewItem(0)
+        ));
+        assert!(matches!(
+            queue.add_report(user(3), Some("spam".to_string()), snapshot, None, 1u64.into(), 2),
+            AddReportResult::AddedToExistingItem(0)
+        ));
+        assert!(matches!(
+            queue.add_report(user(3), None, snapshot, None, 1u64.into(), 3),
+            AddReportResult::AlreadyReported
+        ));
+
+        let pending = queue.pending(None, 10);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].reports.len(), 2);
+    }
+
+    #[test]
+    fn resolved_items_move_to_history() {
+        let mut queue = ModerationQueue::default();
+        queue.add_report(user(2), None, snapshot, None, 1u64.into(), 1);
+
+        queue.resolve(0, ModerationAction::DeleteMessage, user(4), 10).unwrap();
+        assert!(queue.resolve(0, ModerationAction::Dismiss, user(4), 11).is_err());
+
+        assert_eq!(queue.pending_count(), 0);
+        let resolved = queue.resolved(None, 10);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].action, ModerationAction::DeleteMessage);
+        assert!(queue.resolved(Some(10), 10).is_empty());
+
+        // Reporting the message again opens a new item
+        assert!(matches!(
+            queue.add_report(user(2), None, snapshot, None, 1u64.into(), 12),
+            AddReportResult::NewItem(1)
+        ));
+    }
+
+    #[test]
+    fn pending_items_paginated() {
+        let mut queue = ModerationQueue::default();
+        for i in 0..5u64 {
+            queue.add_report(user(2), None, || snapshot_for(i), None, i.into(), 1);
+        }
+
+        let first_page = queue.pending(None, 2);
+        assert_eq!(first_page.iter().map(|i| i.id).collect::<Vec<_>>(), vec![0, 1]);
+
+        let second_page = queue.pending(Some(1), 2);
+        assert_eq!(second_page.iter().map(|i| i.id).collect::<Vec<_>>(), vec![2, 3]);
+
+        assert_eq!(queue.pending(Some(3), 2).len(), 1);
+    }
+
+    #[test]
+    fn pending_reports_per_user_capped() {
+        let mut queue = ModerationQueue::default();
+        for i in 0..MAX_PENDING_REPORTS_PER_USER as u64 {
+            assert!(matches!(
+                queue.add_report(user(2), None, || snapshot_for(i), None, i.into(), 1),
+                AddReportResult::NewItem(_)
+            ));
+        }
+
+        assert!(matches!(
+            queue.add_report(user(2), None, || snapshot_for(1000), None, 1000u64.into(), 1),
+            AddReportResult::TooManyPendingReports
+        ));
+
+        // Other users can still report messages, and once an item is resolved the user can report again
+        assert!(matches!(
+            queue.add_report(user(3), None, || snapshot_for(1000), None, 1000u64.into(), 1),
+            AddReportResult::NewItem(_)
+        ));
+        queue.resolve(0, ModerationAction::Dismiss, user(4), 2).unwrap();
+        assert!(matches!(
+            queue.add_report(user(2), None, || snapshot_for(1000), None, 1000u64.into(), 3),
+            AddReportResult::AddedToExistingItem(_)
+        ));
+    }
+
+    #[test]
+    fn pending_items_capped() {
+        let mut queue = ModerationQueue::default();
+        for i in 0..MAX_PENDING_ITEMS as u64 {
+            let reporter = user((i / MAX_PENDING_REPORTS_PER_USER as u64) as u8 + 1);
+            assert!(matches!(
+                queue.add_report(reporter, None, || snapshot_for(i), None, i.into(), 1),
+                AddReportResult::NewItem(_)
+            ));
+        }
+
+        assert!(matches!(
+            queue.add_report(user(255), None, || snapshot_for(100_000), None, 100_000u64.into(), 1),
+            AddReportResult::QueueFull
+        ));
+    }
+}
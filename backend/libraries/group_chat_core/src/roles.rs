@@ -149,6 +149,14 @@ impl GroupRoleInternal {
         self.is_owner()
     }
 
+    pub fn can_view_audit_log(&self) -> bool {
+        self.has_admin_rights()
+    }
+
+    pub fn can_set_audit_log_retention(&self) -> bool {
+        self.is_owner()
+    }
+
     pub fn can_invite_users(&self, permissions: &GroupPermissions) -> bool {
         self.is_permitted(permissions.invite_users)
     }
@@ -194,6 +202,7 @@ impl GroupRoleInternal {
             (role_permissions.pin_messages, ChatPermission::PinMessages),
             (role_permissions.react_to_messages, ChatPermission::ReactToMessages),
             (role_permissions.remove_members, ChatPermission::RemoveMembers),
+            (role_permissions.remove_members, ChatPermission::BlockUsers),
             (role_permissions.start_video_call, ChatPermission::StartVideoCall),
             (role_permissions.update_group, ChatPermission::UpdateGroup),
         ];
@@ -206,6 +215,7 @@ impl GroupRoleInternal {
         permissions.insert(ChatPermission::ReadSummary);
         permissions.insert(ChatPermission::ReadMembership);
         permissions.insert(ChatPermission::ReadMessages);
+        permissions.insert(ChatPermission::FollowThreads);
         permissions
     }
 
@@ -0,0 +1,325 @@
+use constants::WEEK_IN_MS;
+use oc_error_codes::OCErrorCode;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use types::{
+    AuditLogAction, AuditLogEntry, AuditLogRetention, ChannelId, MAX_AUDIT_LOG_REASON_LENGTH, Milliseconds, OCResult,
+    TimestampMillis, UserId, is_default,
+};
+
+// These apply regardless of the retention configured by the owners. Entries for actions taken within channels have
+// their own cap so that busy channels can't push out the community level entries.
+const MAX_AUDIT_LOG_ENTRIES: usize = 10_000;
+const MAX_CHANNEL_AUDIT_LOG_ENTRIES: usize = 10_000;
+
+// The owners can't configure a retention which would allow entries to be pruned sooner than this
+const MIN_RETENTION_ENTRIES: u32 = 100;
+const MIN_RETENTION_AGE: Milliseconds = WEEK_IN_MS;
+
+// An append-only log of privileged actions. Entries are only ever removed from the front once they fall outside of
+// the retention limits.
+#[derive(Serialize, Deserialize, Default)]
+pub struct AuditLog {
+    #[serde(rename = "e", default, skip_serializing_if = "VecDeque::is_empty")]
+    entries: VecDeque<AuditLogEntryInternal>,
+    #[serde(rename = "n", default)]
+    next_index: u32,
+    #[serde(rename = "r", default, skip_serializing_if = "is_default")]
+    retention: AuditLogRetention,
+    // The index of the latest `RetentionUpdated` entry, which is never pruned
+    #[serde(rename = "l", default, skip_serializing_if = "Option::is_none")]
+    latest_retention_update: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AuditLogEntryInternal {
+    #[serde(rename = "i")]
+    index: u32,
+    #[serde(rename = "c", default, skip_serializing_if = "Option::is_none")]
+    channel_id: Option<ChannelId>,
+    #[serde(rename = "a")]
+    actor: UserId,
+    #[serde(rename = "t", default, skip_serializing_if = "Option::is_none")]
+    target: Option<UserId>,
+    #[serde(rename = "x")]
+    action: AuditLogAction,
+    #[serde(rename = "r", default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(rename = "ts")]
+    timestamp: TimestampMillis,
+}
+
+impl AuditLog {
+    pub fn record(
+        &mut self,
+        channel_id: Option<ChannelId>,
+        actor: UserId,
+        target: Option<UserId>,
+        action: AuditLogAction,
+        reason: Option<String>,
+        now: TimestampMillis,
+    ) {
+        let reason = reason
+            .map(|r| r.trim().chars().take(MAX_AUDIT_LOG_REASON_LENGTH).collect::<String>())
+            .filter(|r| !r.is_empty());
+
+        if matches!(action, AuditLogAction::RetentionUpdated(_)) {
+            self.latest_retention_update = Some(self.next_index);
+        }
+
+        self.entries.push_back(AuditLogEntryInternal {
+            index: self.next_index,
+            channel_id,
+            actor,
+            target,
+            action,
+            reason,
+            timestamp: now,
+        });
+        self.next_index += 1;
+        self.prune(now);
+    }
+
+    // Returns the entries matching the filter, most recent first
+    pub fn entries<F: Fn(Option<ChannelId>) -> bool>(
+        &self,
+        filter: F,
+        before: Option<u32>,
+        max_results: usize,
+        now: TimestampMillis,
+    ) -> Vec<AuditLogEntry> {
+        let cutoff = self.age_cutoff(now);
+
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| before.is_none_or(|b| e.index < b))
+            .filter(|e| cutoff.is_none_or(|c| e.timestamp >= c) || self.latest_retention_update == Some(e.index))
+            .filter(|e| filter(e.channel_id))
+            .take(max_results)
+            .map(|e| AuditLogEntry {
+                index: e.index,
+                channel_id: e.channel_id,
+                actor: e.actor,
+                target: e.target,
+                action: e.action.clone(),
+                reason: e.reason.clone(),
+                timestamp: e.timestamp,
+            })
+            .collect()
+    }
+
+    pub fn retention(&self) -> AuditLogRetention {
+        self.retention
+    }
+
+    pub fn set_retention(&mut self, retention: AuditLogRetention, now: TimestampMillis) -> OCResult {
+        if retention.max_entries.is_some_and(|m| m < MIN_RETENTION_ENTRIES) {
+            return Err(
+                OCErrorCode::InvalidRequest.with_message(format!("Must retain at least {MIN_RETENTION_ENTRIES} entries"))
+            );
+        }
+        if retention.max_age.is_some_and(|a| a < MIN_RETENTION_AGE) {
+            return Err(
+                OCErrorCode::InvalidRequest.with_message(format!("Must retain entries for at least {MIN_RETENTION_AGE}ms"))
+            );
+        }
+
+        self.retention = retention;
+        self.prune(now);
+        Ok(())
+    }
+
+    pub fn latest_index(&self) -> Option<u32> {
+        self.next_index.checked_sub(1)
+    }
+
+    // Removes the oldest entries beyond the configured (and absolute) limits, counting community level and channel
+    // entries separately, but always keeps the latest `RetentionUpdated` entry
+    fn prune(&mut self, now: TimestampMillis) {
+        let max_entries = |cap: usize| self.retention.max_entries.map_or(cap, |m| (m as usize).min(cap));
+        let channel_entries = self.entries.iter().filter(|e| e.channel_id.is_some()).count();
+        let community_entries = self.entries.len() - channel_entries;
+
+        let mut community_excess = community_entries.saturating_sub(max_entries(MAX_AUDIT_LOG_ENTRIES));
+        let mut channel_excess = channel_entries.saturating_sub(max_entries(MAX_CHANNEL_AUDIT_LOG_ENTRIES));
+        let cutoff = self.age_cutoff(now);
+
+        if community_excess == 0
+            && channel_excess == 0
+            && cutoff.is_none_or(|c| self.entries.front().is_none_or(|e| e.timestamp >= c))
+        {
+            return;
+        }
+
+        let latest_retention_update = self.latest_retention_update;
+        self.entries.retain(|e| {
+            if latest_retention_update == Some(e.index) {
+                return true;
+            }
+            let excess = if e.channel_id.is_some() { &mut channel_excess } else { &mut community_excess };
+            if *excess > 0 {
+                *excess -= 1;
+                return false;
+            }
+            cutoff.is_none_or(|c| e.timestamp >= c)
+        });
+    }
+
+    fn age_cutoff(&self, now: TimestampMillis) -> Option<TimestampMillis> {
+        self.retention.max_age.map(|age| now.saturating_sub(age))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use constants::DAY_IN_MS;
+
+    fn user(id: u8) -> UserId {
+        UserId::new(Principal::from_slice(&[id]))
+    }
+
+    #[test]
+    fn entries_are_paginated_most_recent_first() {
+        let mut audit_log = AuditLog::default();
+        for i in 0..10u64 {
+            let channel_id = if i % 2 == 0 { Some(ChannelId::from(1u32)) } else { None };
+            audit_log.record(channel_id, user(1), Some(user(2)), AuditLogAction::MemberRemoved, None, i);
+        }
+
+        let page1 = audit_log.entries(|_| true, None, 4, 10);
+        assert_eq!(page1.iter().map(|e| e.index).collect::<Vec<_>>(), vec![9, 8, 7, 6]);
+
+        let page2 = audit_log.entries(|_| true, Some(6), 4, 10);
+        assert_eq!(page2.iter().map(|e| e.index).collect::<Vec<_>>(), vec![5, 4, 3, 2]);
+
+        let channel_only = audit_log.entries(|c| c.is_some(), None, 100, 10);
+        assert_eq!(channel_only.len(), 5);
+        assert_eq!(audit_log.latest_index(), Some(9));
+    }
+
+    #[test]
+    fn retention_limits_are_applied() {
+        let mut audit_log = AuditLog::default();
+        for i in 0..200 {
+            audit_log.record(
+                None,
+                user(1),
+                None,
+                AuditLogAction::RulesUpdated,
+                Some(" ".to_string()),
+                i * DAY_IN_MS,
+            );
+        }
+        let now = 200 * DAY_IN_MS;
+        assert!(
+            audit_log
+                .entries(|_| true, None, 1000, now)
+                .iter()
+                .all(|e| e.reason.is_none())
+        );
+
+        audit_log
+            .set_retention(
+                AuditLogRetention {
+                    max_entries: Some(150),
+                    max_age: None,
+                },
+                now,
+            )
+            .unwrap();
+        assert_eq!(audit_log.entries(|_| true, None, 1000, now).len(), 150);
+
+        audit_log
+            .set_retention(
+                AuditLogRetention {
+                    max_entries: None,
+                    max_age: Some(10 * DAY_IN_MS),
+                },
+                now,
+            )
+            .unwrap();
+        let entries = audit_log.entries(|_| true, None, 1000, now);
+        assert_eq!(entries.len(), 10);
+        assert_eq!(entries[0].index, 199);
+
+        // Entries older than the max age are hidden even before they are pruned
+        assert_eq!(audit_log.entries(|_| true, None, 1000, now + DAY_IN_MS).len(), 9);
+    }
+
+    #[test]
+    fn retention_below_minimum_rejected() {
+        let mut audit_log = AuditLog::default();
+
+        assert!(
+            audit_log
+                .set_retention(
+                    AuditLogRetention {
+                        max_entries: Some(MIN_RETENTION_ENTRIES - 1),
+                        max_age: None,
+                    },
+                    0,
+                )
+                .is_err()
+        );
+        assert!(
+            audit_log
+                .set_retention(
+                    AuditLogRetention {
+                        max_entries: None,
+                        max_age: Some(MIN_RETENTION_AGE - 1),
+                    },
+                    0,
+                )
+                .is_err()
+        );
+        assert_eq!(audit_log.retention(), AuditLogRetention::default());
+    }
+
+    #[test]
+    fn latest_retention_update_is_never_pruned() {
+        let mut audit_log = AuditLog::default();
+        let retention = AuditLogRetention {
+            max_entries: Some(MIN_RETENTION_ENTRIES),
+            max_age: Some(MIN_RETENTION_AGE),
+        };
+        audit_log.set_retention(retention, 0).unwrap();
+        audit_log.record(None, user(1), None, AuditLogAction::RetentionUpdated(retention), None, 0);
+
+        for i in 0..(2 * MIN_RETENTION_ENTRIES as u64) {
+            audit_log.record(None, user(1), None, AuditLogAction::RulesUpdated, None, i);
+        }
+        let entries = audit_log.entries(|_| true, None, 1000, 10);
+        assert_eq!(entries.len(), MIN_RETENTION_ENTRIES as usize);
+        assert!(matches!(entries.last().unwrap().action, AuditLogAction::RetentionUpdated(_)));
+
+        let later = 2 * MIN_RETENTION_AGE;
+        audit_log.record(None, user(1), None, AuditLogAction::RulesUpdated, None, later);
+        let entries = audit_log.entries(|_| true, None, 1000, later);
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(entries[1].action, AuditLogAction::RetentionUpdated(_)));
+    }
+
+    #[test]
+    fn channel_entries_capped_separately() {
+        let mut audit_log = AuditLog::default();
+        audit_log.record(None, user(1), None, AuditLogAction::RulesUpdated, None, 0);
+
+        for i in 0..(MAX_CHANNEL_AUDIT_LOG_ENTRIES as u64 + 10) {
+            audit_log.record(
+                Some(ChannelId::from(1u32)),
+                user(1),
+                None,
+                AuditLogAction::RulesUpdated,
+                None,
+                i,
+            );
+        }
+
+        let entries = audit_log.entries(|_| true, None, usize::MAX, 0);
+        assert_eq!(entries.len(), MAX_CHANNEL_AUDIT_LOG_ENTRIES + 1);
+        assert_eq!(entries.iter().filter(|e| e.channel_id.is_none()).count(), 1);
+    }
+}
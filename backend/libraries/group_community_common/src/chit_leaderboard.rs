@@ -0,0 +1,234 @@
+use constants::DAY_IN_MS;
+use oc_error_codes::OCErrorCode;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use types::{ChitLeaderboardEntry, ChitLeaderboardWindow, OCResult, TimestampMillis, UserId};
+use utils::time::MonthKey;
+
+// Daily totals are retained for long enough to cover a quarterly season
+const MAX_DAYS_RETAINED: u32 = 120;
+pub const MAX_CHIT_LEADERBOARD_SIZE: usize = 100;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ChitLeaderboard {
+    members: BTreeMap<UserId, Vec<DailyChit>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+struct DailyChit {
+    #[serde(rename = "d")]
+    day: u32,
+    #[serde(rename = "a")]
+    amount: i32,
+}
+
+impl ChitLeaderboard {
+    pub fn record(&mut self, user_id: UserId, amount: i32, timestamp: TimestampMillis, now: TimestampMillis) {
+        let oldest_day_retained = day_index(now).saturating_sub(MAX_DAYS_RETAINED);
+        let day = day_index(timestamp);
+        if amount == 0 || day < oldest_day_retained {
+            return;
+        }
+
+        let days = self.members.entry(user_id).or_default();
+        match days.binary_search_by_key(&day, |d| d.day) {
+            Ok(index) => days[index].amount += amount,
+            Err(index) => days.insert(index, DailyChit { day, amount }),
+        }
+
+        let expired = days.partition_point(|d| d.day < oldest_day_retained);
+        if expired > 0 {
+            days.drain(..expired);
+        }
+    }
+
+    pub fn remove_user(&mut self, user_id: &UserId) {
+        self.members.remove(user_id);
+    }
+
+    pub fn leaderboard<F: Fn(&UserId) -> bool>(
+        &self,
+        range: Range<TimestampMillis>,
+        max_results: usize,
+        include: F,
+    ) -> Vec<ChitLeaderboardEntry> {
+        let days = day_index(range.start)..day_index(range.end.saturating_add(DAY_IN_MS - 1));
+
+        let mut entries: Vec<_> = self
+            .members
+            .iter()
+            .filter(|(user_id, _)| include(user_id))
+            .filter_map(|(user_id, daily)| {
+                let start = daily.partition_point(|d| d.day < days.start);
+                let end = daily.partition_point(|d| d.day < days.end);
+                let chit_earned: i32 = daily[start..end].iter().map(|d| d.amount).sum();
+
+                (chit_earned > 0).then_some(ChitLeaderboardEntry {
+                    user_id: *user_id,
+                    chit_earned,
+                })
+            })
+            .collect();
+
+        entries.sort_unstable_by_key(|e| (Reverse(e.chit_earned), e.user_id));
+        entries.truncate(max_results.min(MAX_CHIT_LEADERBOARD_SIZE));
+        entries
+    }
+}
+
+// Seasons are rejected if they start before the oldest daily total retained, since otherwise the leaderboard would
+// silently only include part of the season
+pub fn chit_leaderboard_range(window: ChitLeaderboardWindow, now: TimestampMillis) -> OCResult<Range<TimestampMillis>> {
+    let range = match window {
+        ChitLeaderboardWindow::ThisWeek => {
+            let start = week_start(now);
+            start..(start + 7 * DAY_IN_MS)
+        }
+        ChitLeaderboardWindow::LastWeek => {
+            let end = week_start(now);
+            end.saturating_sub(7 * DAY_IN_MS)..end
+        }
+        ChitLeaderboardWindow::ThisMonth => MonthKey::from_timestamp(now).timestamp_range(),
+        ChitLeaderboardWindow::LastMonth => MonthKey::from_timestamp(now).previous().timestamp_range(),
+        ChitLeaderboardWindow::Season(season) => {
+            let oldest_retained = (day_index(now).saturating_sub(MAX_DAYS_RETAINED) as TimestampMillis) * DAY_IN_MS;
+            if season.end <= season.start {
+                return Err(OCErrorCode::InvalidRequest.with_message("Season must end after it starts"));
+            }
+            if season.start < oldest_retained {
+                return Err(OCErrorCode::InvalidRequest
+                    .with_message(format!("Seasons cannot start more than {MAX_DAYS_RETAINED} days ago")));
+            }
+            season.start..season.end
+        }
+    };
+    Ok(range)
+}
+
+fn day_index(timestamp: TimestampMillis) -> u32 {
+    (timestamp / DAY_IN_MS) as u32
+}
+
+// Weeks start on Monday (UTC). The Unix epoch fell on a Thursday, hence the offset of 3 days.
+fn week_start(timestamp: TimestampMillis) -> TimestampMillis {
+    let day = timestamp / DAY_IN_MS;
+    (day - ((day + 3) % 7)) * DAY_IN_MS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use types::ChitLeaderboardSeason;
+
+    // 2024-06-05 (a Wednesday)
+    const NOW: TimestampMillis = 1717545600000;
+
+    #[test]
+    fn leaderboard_in_expected_order() {
+        let mut leaderboard = ChitLeaderboard::default();
+        leaderboard.record(user(1), 100, NOW, NOW);
+        leaderboard.record(user(2), 400, NOW, NOW);
+        leaderboard.record(user(3), 200, NOW, NOW);
+        leaderboard.record(user(1), 50, NOW - DAY_IN_MS, NOW);
+
+        let entries = leaderboard.leaderboard(
+            chit_leaderboard_range(ChitLeaderboardWindow::ThisWeek, NOW).unwrap(),
+            10,
+            |_| true,
+        );
+
+        assert_eq!(
+            entries.iter().map(|e| (e.user_id, e.chit_earned)).collect::<Vec<_>>(),
+            vec![(user(2), 400), (user(3), 200), (user(1), 150)]
+        );
+    }
+
+    #[test]
+    fn only_chit_within_window_is_counted() {
+        let mut leaderboard = ChitLeaderboard::default();
+        leaderboard.record(user(1), 100, NOW, NOW);
+        leaderboard.record(user(1), 500, NOW - 7 * DAY_IN_MS, NOW);
+
+        let this_week = leaderboard.leaderboard(
+            chit_leaderboard_range(ChitLeaderboardWindow::ThisWeek, NOW).unwrap(),
+            10,
+            |_| true,
+        );
+        let last_week = leaderboard.leaderboard(
+            chit_leaderboard_range(ChitLeaderboardWindow::LastWeek, NOW).unwrap(),
+            10,
+            |_| true,
+        );
+        let season = leaderboard.leaderboard(
+            chit_leaderboard_range(
+                ChitLeaderboardWindow::Season(ChitLeaderboardSeason {
+                    start: NOW - 30 * DAY_IN_MS,
+                    end: NOW,
+                }),
+                NOW,
+            )
+            .unwrap(),
+            10,
+            |_| true,
+        );
+
+        assert_eq!(this_week[0].chit_earned, 100);
+        assert_eq!(last_week[0].chit_earned, 500);
+        assert_eq!(season[0].chit_earned, 600);
+    }
+
+    #[test]
+    fn filtered_users_excluded() {
+        let mut leaderboard = ChitLeaderboard::default();
+        leaderboard.record(user(1), 100, NOW, NOW);
+        leaderboard.record(user(2), 200, NOW, NOW);
+
+        let entries = leaderboard.leaderboard(
+            chit_leaderboard_range(ChitLeaderboardWindow::ThisMonth, NOW).unwrap(),
+            10,
+            |u| *u == user(1),
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].user_id, user(1));
+    }
+
+    #[test]
+    fn old_days_are_pruned() {
+        let mut leaderboard = ChitLeaderboard::default();
+        let user_id = user(1);
+        leaderboard.record(user_id, 100, NOW, NOW);
+
+        let later = NOW + (MAX_DAYS_RETAINED as u64 + 1) * DAY_IN_MS;
+        leaderboard.record(user_id, 10, later, later);
+
+        assert_eq!(leaderboard.members[&user_id].len(), 1);
+    }
+
+    #[test]
+    fn seasons_older_than_retained_history_rejected() {
+        let season = |start| {
+            ChitLeaderboardWindow::Season(ChitLeaderboardSeason {
+                start,
+                end: NOW + DAY_IN_MS,
+            })
+        };
+
+        assert!(chit_leaderboard_range(season(NOW - MAX_DAYS_RETAINED as u64 * DAY_IN_MS), NOW).is_ok());
+        assert!(chit_leaderboard_range(season(NOW - (MAX_DAYS_RETAINED as u64 + 1) * DAY_IN_MS), NOW).is_err());
+        assert!(chit_leaderboard_range(season(NOW + 2 * DAY_IN_MS), NOW).is_err());
+    }
+
+    #[test]
+    fn week_starts_on_monday() {
+        // 2024-06-03 (a Monday)
+        assert_eq!(week_start(NOW), 1717372800000);
+    }
+
+    fn user(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+}
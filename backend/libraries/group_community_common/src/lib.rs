@@ -1,4 +1,6 @@
 mod achievements;
+mod audit_log;
+mod chit_leaderboard;
 mod expiring_member_actions;
 mod expiring_members;
 mod member;
@@ -7,6 +9,8 @@ mod pending_payments_queue;
 mod user_cache;
 
 pub use achievements::*;
+pub use audit_log::*;
+pub use chit_leaderboard::*;
 pub use expiring_member_actions::*;
 pub use expiring_members::*;
 pub use member::*;
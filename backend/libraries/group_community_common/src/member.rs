@@ -33,4 +33,5 @@ pub enum MemberUpdate {
     Lapsed = 6,
     Unlapsed = 7,
     DisplayNameChanged = 8,
+    TimedOut = 9,
 }
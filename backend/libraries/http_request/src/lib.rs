@@ -1,5 +1,6 @@
 mod document_handler;
 mod logs_handler;
+mod metrics_handler;
 mod router;
 
 use serde::Serialize;
@@ -7,6 +8,7 @@ use types::{HeaderField, HttpResponse};
 
 pub use document_handler::*;
 pub use logs_handler::*;
+pub use metrics_handler::*;
 pub use router::*;
 
 pub fn build_json_response<T: Serialize>(body: &T) -> HttpResponse {
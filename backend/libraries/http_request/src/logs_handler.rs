@@ -1,12 +1,12 @@
 use crate::build_response;
-use canister_logger::LogEntry;
+use canister_logger::{LogEntry, LogQuery};
 use std::io::Write;
-use types::{HttpResponse, TimestampMillis};
+use types::HttpResponse;
 
-pub fn encode_logs(entries: Vec<LogEntry>, since: TimestampMillis) -> HttpResponse {
+pub fn encode_logs(entries: Vec<LogEntry>, query: LogQuery) -> HttpResponse {
     let mut body = Vec::new();
 
-    for entry in entries.into_iter().filter(|e| e.timestamp > since) {
+    for entry in query.apply(entries) {
         writeln!(&mut body, "{}", entry.message).unwrap();
     }
 
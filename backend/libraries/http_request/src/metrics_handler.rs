@@ -0,0 +1,320 @@
+use crate::{build_json_response, build_response};
+use instruction_counts_log::{INSTRUCTION_COUNT_BUCKETS, InstructionCountEntry};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write;
+use tracing::error;
+use types::{HttpRequest, HttpResponse};
+
+const OPEN_METRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Exposes a canister's metrics in the OpenMetrics text format. The default implementation derives a gauge for each
+/// numeric or boolean field and an info metric from the string fields, using the struct's `Serialize` impl.
+pub trait OpenMetrics: Serialize {
+    fn encode_open_metrics(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.encode_serialized(self);
+    }
+}
+
+/// Returns the metrics as OpenMetrics text if requested via the `Accept` header or `?format=prometheus`, else as JSON.
+pub fn encode_metrics<T: OpenMetrics>(request: &HttpRequest, metrics: &T) -> HttpResponse {
+    if open_metrics_requested(request) {
+        let mut encoder = OpenMetricsEncoder::default();
+        metrics.encode_open_metrics(&mut encoder);
+        match encoder.finish() {
+            Ok(text) => build_response(text.into_bytes(), OPEN_METRICS_CONTENT_TYPE),
+            Err(message) => {
+                error!(message, "Failed to encode metrics");
+                HttpResponse {
+                    body: message.into_bytes(),
+                    ..HttpResponse::status_code(500)
+                }
+            }
+        }
+    } else {
+        build_json_response(metrics)
+    }
+}
+
+fn open_metrics_requested(request: &HttpRequest) -> bool {
+    let query = request.url.split_once('?').map(|(_, qs)| qs).unwrap_or_default();
+    if query
+        .split('&')
+        .any(|p| p.eq_ignore_ascii_case("format=prometheus") || p.eq_ignore_ascii_case("format=openmetrics"))
+    {
+        return true;
+    }
+
+    request.headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case("accept")
+            && (value.contains("application/openmetrics-text") || value.contains("version=0.0.4"))
+    })
+}
+
+#[derive(Default)]
+pub struct OpenMetricsEncoder {
+    families: Vec<MetricFamily>,
+    family_indexes: HashMap<String, usize>,
+    histograms: Vec<Histogram>,
+    info: Vec<(String, String)>,
+    errors: Vec<String>,
+}
+
+struct MetricFamily {
+    name: String,
+    metric_type: &'static str,
+    samples: Vec<String>,
+}
+
+struct Histogram {
+    name: String,
+    labels: Vec<(String, String)>,
+    buckets: Vec<(f64, u64)>,
+    sum: f64,
+    count: u64,
+}
+
+impl OpenMetricsEncoder {
+    pub fn gauge(&mut self, name: &str, labels: &[(String, String)], value: impl ToString) {
+        let sample = format!("{name}{} {}", format_labels(labels), value.to_string());
+        self.family(name, "gauge").samples.push(sample);
+    }
+
+    /// Records an observation in the histogram identified by `name` and `labels`, creating it with the given bucket
+    /// upper bounds if this is the first observation.
+    pub fn observe(&mut self, name: &str, labels: &[(String, String)], buckets: &[f64], value: f64) {
+        let index = match self.histograms.iter().position(|h| h.name == name && h.labels == labels) {
+            Some(index) => index,
+            None => {
+                self.histograms.push(Histogram {
+                    name: name.to_string(),
+                    labels: labels.to_vec(),
+                    buckets: buckets.iter().map(|b| (*b, 0)).collect(),
+                    sum: 0.0,
+                    count: 0,
+                });
+                self.histograms.len() - 1
+            }
+        };
+
+        let histogram = &mut self.histograms[index];
+        for (upper_bound, count) in histogram.buckets.iter_mut() {
+            if value <= *upper_bound {
+                *count += 1;
+            }
+        }
+        histogram.sum += value;
+        histogram.count += 1;
+    }
+
+    /// Records each entry from a canister's `InstructionCountsLog` in a histogram labelled by function.
+    pub fn instruction_counts(&mut self, entries: &[InstructionCountEntry]) {
+        for entry in entries {
+            let labels = [("function".to_string(), format!("{:?}", entry.function_id()))];
+            self.observe(
+                "instruction_count",
+                &labels,
+                &INSTRUCTION_COUNT_BUCKETS,
+                entry.instruction_count() as f64,
+            );
+        }
+    }
+
+    /// Maps each numeric or boolean field to a gauge named after the field's path. Maps whose keys aren't valid
+    /// metric names (eg. ids) are instead exposed as a single gauge with a `key` label. Arrays are skipped.
+    pub fn encode_serialized<T: Serialize>(&mut self, value: &T) {
+        match serde_json::to_value(value) {
+            Ok(value) => self.encode_value("", &[], &value),
+            Err(error) => self.errors.push(format!("Failed to serialize metrics: {error}")),
+        }
+    }
+
+    /// Returns the encoded metrics, or an error if any of them could not be encoded, rather than silently returning
+    /// an incomplete set.
+    pub fn finish(mut self) -> Result<String, String> {
+        if !self.errors.is_empty() {
+            return Err(self.errors.join("\n"));
+        }
+
+        for histogram in std::mem::take(&mut self.histograms) {
+            let name = histogram.name.clone();
+            let family = self.family(&name, "histogram");
+            for (upper_bound, count) in histogram.buckets {
+                let labels = with_label(&histogram.labels, "le", upper_bound.to_string());
+                family
+                    .samples
+                    .push(format!("{name}_bucket{} {count}", format_labels(&labels)));
+            }
+            let labels = with_label(&histogram.labels, "le", "+Inf".to_string());
+            family
+                .samples
+                .push(format!("{name}_bucket{} {}", format_labels(&labels), histogram.count));
+            family
+                .samples
+                .push(format!("{name}_sum{} {}", format_labels(&histogram.labels), histogram.sum));
+            family.samples.push(format!(
+                "{name}_count{} {}",
+                format_labels(&histogram.labels),
+                histogram.count
+            ));
+        }
+
+        let mut text = String::new();
+        if !self.info.is_empty() {
+            writeln!(text, "# TYPE canister info").unwrap();
+            writeln!(text, "canister_info{} 1", format_labels(&self.info)).unwrap();
+        }
+        for family in self.families {
+            writeln!(text, "# TYPE {} {}", family.name, family.metric_type).unwrap();
+            for sample in family.samples {
+                writeln!(text, "{sample}").unwrap();
+            }
+        }
+        text.push_str("# EOF\n");
+        Ok(text)
+    }
+
+    fn encode_value(&mut self, name: &str, labels: &[(String, String)], value: &Value) {
+        match value {
+            Value::Number(n) => self.gauge(name, labels, n),
+            Value::Bool(b) => self.gauge(name, labels, u8::from(*b)),
+            Value::String(s) if labels.is_empty() => self.info.push((name.to_string(), s.clone())),
+            Value::Object(map) => {
+                let keys_are_names = map.keys().all(|k| is_valid_name(k));
+                for (key, value) in map {
+                    if keys_are_names {
+                        let name = if name.is_empty() { key.clone() } else { format!("{name}_{key}") };
+                        self.encode_value(&name, labels, value);
+                    } else if !name.is_empty() {
+                        self.encode_value(name, &with_label(labels, "key", key.clone()), value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn family(&mut self, name: &str, metric_type: &'static str) -> &mut MetricFamily {
+        let index = *self.family_indexes.entry(name.to_string()).or_insert_with(|| {
+            self.families.push(MetricFamily {
+                name: name.to_string(),
+                metric_type,
+                samples: Vec::new(),
+            });
+            self.families.len() - 1
+        });
+        &mut self.families[index]
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn with_label(labels: &[(String, String)], name: &str, value: String) -> Vec<(String, String)> {
+    let mut labels = labels.to_vec();
+    labels.push((name.to_string(), value));
+    labels
+}
+
+fn format_labels(labels: &[(String, String)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+
+    let labels: Vec<_> = labels
+        .iter()
+        .map(|(name, value)| {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            format!("{name}=\"{value}\"")
+        })
+        .collect();
+
+    format!("{{{}}}", labels.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Metrics {
+        heap_memory_used: u64,
+        frozen: bool,
+        git_commit_id: String,
+        durations: Durations,
+        stable_memory_sizes: BTreeMap<u8, u64>,
+        entries: Vec<u32>,
+    }
+
+    #[derive(Serialize)]
+    struct Durations {
+        min: u64,
+        max: u64,
+    }
+
+    impl OpenMetrics for Metrics {}
+
+    fn request(url: &str, headers: Vec<(String, String)>) -> HttpRequest {
+        HttpRequest {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            headers,
+            body: Vec::new(),
+        }
+    }
+
+    fn metrics() -> Metrics {
+        Metrics {
+            heap_memory_used: 1024,
+            frozen: true,
+            git_commit_id: "abc".to_string(),
+            durations: Durations { min: 1, max: 5 },
+            stable_memory_sizes: BTreeMap::from([(0, 10), (1, 20)]),
+            entries: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn format_is_selected_from_query_or_accept_header() {
+        assert!(open_metrics_requested(&request("/metrics?format=prometheus", Vec::new())));
+        assert!(open_metrics_requested(&request(
+            "/metrics",
+            vec![("Accept".to_string(), "application/openmetrics-text;version=1.0.0".to_string())]
+        )));
+        assert!(!open_metrics_requested(&request("/metrics", Vec::new())));
+    }
+
+    #[test]
+    fn fields_are_encoded_as_gauges() {
+        let mut encoder = OpenMetricsEncoder::default();
+        metrics().encode_open_metrics(&mut encoder);
+        encoder.observe("instructions", &[], &[10.0, 100.0], 50.0);
+        let text = encoder.finish().unwrap();
+
+        assert!(text.contains("canister_info{git_commit_id=\"abc\"} 1\n"));
+        assert!(text.contains("# TYPE heap_memory_used gauge\nheap_memory_used 1024\n"));
+        assert!(text.contains("frozen 1\n"));
+        assert!(text.contains("durations_min 1\n"));
+        assert!(text.contains("stable_memory_sizes{key=\"0\"} 10\nstable_memory_sizes{key=\"1\"} 20\n"));
+        assert!(!text.contains("entries"));
+        assert!(text.contains("instructions_bucket{le=\"10\"} 0\ninstructions_bucket{le=\"100\"} 1\n"));
+        assert!(text.contains("instructions_count 1\n"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn serialization_failure_is_returned_as_error() {
+        // JSON maps must have string keys, so serializing this map fails
+        let invalid = BTreeMap::from([((1, 2), 3)]);
+
+        let mut encoder = OpenMetricsEncoder::default();
+        encoder.encode_serialized(&metrics());
+        encoder.encode_serialized(&invalid);
+
+        assert!(encoder.finish().is_err());
+    }
+}
@@ -1,17 +1,18 @@
 use candid::Principal;
+use canister_logger::{LogLevel, LogQuery};
 use std::{
     collections::{HashMap, VecDeque},
     str::FromStr,
 };
-use types::{ChannelId, FileId, TimestampMillis, UserId};
+use types::{ChannelId, FileId, UserId};
 
 pub enum Route {
     Avatar(AvatarRoute),
     Banner(Option<u128>),
     File(u128),
-    Logs(Option<TimestampMillis>),
-    Errors(Option<TimestampMillis>),
-    Traces(Option<TimestampMillis>),
+    Logs(LogQuery),
+    Errors(LogQuery),
+    Traces(LogQuery),
     Metrics,
     Other(String, HashMap<String, String>),
     Webhook(WebhookRoute),
@@ -65,19 +66,10 @@ pub fn extract_route(path: &str) -> Route {
                 }
             }
         }
-        "errors" => {
-            let since = parts.pop_front().and_then(|p| u64::from_str(p).ok());
-            return Route::Errors(since);
-        }
-        "logs" => {
-            let since = parts.pop_front().and_then(|p| u64::from_str(p).ok());
-            return Route::Logs(since);
-        }
+        "errors" => return Route::Errors(parse_log_query(&mut parts, qs)),
+        "logs" => return Route::Logs(parse_log_query(&mut parts, qs)),
         "metrics" => return Route::Metrics,
-        "trace" => {
-            let since = parts.pop_front().and_then(|p| u64::from_str(p).ok());
-            return Route::Traces(since);
-        }
+        "trace" => return Route::Traces(parse_log_query(&mut parts, qs)),
         "webhook" => {
             if let Some(route) = parse_webhook(&mut parts, None) {
                 return route;
@@ -125,6 +117,59 @@ fn parse_webhook(parts: &mut VecDeque<&str>, channel_id: Option<ChannelId>) -> O
     None
 }
 
+// eg. "/logs/1633649663014?level=warn&target=group_canister&contains=timeout&field.user_id=xyz&offset=50&limit=50"
+fn parse_log_query(parts: &mut VecDeque<&str>, qs: &str) -> LogQuery {
+    let mut query = LogQuery {
+        since: parts.pop_front().and_then(|p| u64::from_str(p).ok()),
+        ..Default::default()
+    };
+
+    for (key, value) in parse_query(qs) {
+        let value = percent_decode(&value);
+        match key.as_str() {
+            "since" => query.since = u64::from_str(&value).ok(),
+            "level" => query.min_level = LogLevel::from_str(&value).ok(),
+            "target" => query.target = Some(value),
+            "contains" => query.contains = Some(value),
+            "offset" => query.offset = usize::from_str(&value).unwrap_or_default(),
+            "limit" => query.limit = usize::from_str(&value).ok(),
+            _ => {
+                if let Some(field) = key.strip_prefix("field.") {
+                    query.fields.push((percent_decode(field), value));
+                }
+            }
+        }
+    }
+
+    query
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                match std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
 fn parse_query(query: &str) -> HashMap<String, String> {
     query
         .split('&')
@@ -197,6 +242,22 @@ mod tests {
         assert!(matches!(extract_route("/logs/1633649663014109000"), Route::Logs(_)));
     }
 
+    #[test]
+    fn logs_query() {
+        match extract_route("/errors/1000?level=WARN&contains=transfer%20failed&field.user_id=abc&offset=20&limit=10") {
+            Route::Errors(q) => {
+                assert_eq!(q.since, Some(1000));
+                assert_eq!(q.min_level, Some(LogLevel::Warn));
+                assert_eq!(q.contains.as_deref(), Some("transfer failed"));
+                assert_eq!(q.fields, vec![("user_id".to_string(), "abc".to_string())]);
+                assert_eq!(q.offset, 20);
+                assert_eq!(q.limit, Some(10));
+                assert!(q.target.is_none());
+            }
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn other() {
         assert!(matches!(extract_route("blah"), Route::Other(_, _)));
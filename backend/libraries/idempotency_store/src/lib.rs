@@ -0,0 +1,270 @@
+//! Stores the responses to idempotent requests in stable memory so that if a caller retries a
+//! request (eg. after receiving an uncertain reply), the stored response is returned rather than
+//! the request being applied a second time.
+//!
+//! Endpoints opt in via `#[update(idempotent = true)]`, which wraps the endpoint in calls to
+//! [`start`] and [`IdempotencyGuard::complete`]. The canister must have initialized
+//! `stable_memory_map`.
+
+use constants::DAY_IN_MS;
+use ic_principal::Principal;
+use serde::{Deserialize, Serialize};
+use stable_memory_map::{
+    IdempotentResponseExpiryKey, IdempotentResponseExpiryKeyPrefix, IdempotentResponseKeyPrefix, KeyPrefix, with_map,
+    with_map_mut,
+};
+use types::{Milliseconds, TimestampMillis};
+
+// Responses are retained for this long, after which a retry will be processed as a new request
+pub const RETENTION_PERIOD: Milliseconds = DAY_IN_MS;
+
+// Caps the work done pruning expired responses each time a new request is started
+const MAX_EXPIRED_REMOVED_PER_CALL: usize = 100;
+
+// Responses larger than this are not stored, so requests resulting in them are not deduplicated
+pub const MAX_RESPONSE_BYTES: usize = 4 * 1024;
+
+#[derive(Serialize, Deserialize)]
+struct StoredResponse {
+    #[serde(rename = "m")]
+    method: String,
+    #[serde(rename = "c")]
+    created_at: TimestampMillis,
+    #[serde(rename = "r", default, skip_serializing_if = "Option::is_none")]
+    response: Option<serde_bytes::ByteBuf>,
+}
+
+pub enum StartResult {
+    Started(IdempotencyGuard),
+    Completed(Vec<u8>),
+    InProgress,
+}
+
+// Called at the start of an idempotent endpoint. If a response has already been stored for this
+// caller and key it is returned, otherwise the request is marked as in progress. If the same
+// request is received while the original is still in progress then `InProgress` is returned, in
+// which case the endpoint responds with `AlreadyInProgress` so that the caller can retry once the
+// original has completed.
+pub fn start(method: &'static str, idempotency_key: Option<u64>) -> StartResult {
+    let Some(idempotency_key) = idempotency_key else {
+        return StartResult::Started(IdempotencyGuard::default());
+    };
+
+    let caller = ic_cdk::api::msg_caller();
+    let now = canister_time::now_millis();
+
+    start_inner(caller, method, idempotency_key, now)
+}
+
+fn start_inner(caller: Principal, method: &'static str, idempotency_key: u64, now: TimestampMillis) -> StartResult {
+    remove_expired(now);
+
+    if let Some(stored) = get(caller, idempotency_key).filter(|s| s.method == method) {
+        return match stored.response {
+            Some(response) => StartResult::Completed(response.into_vec()),
+            None => StartResult::InProgress,
+        };
+    }
+
+    let stored = StoredResponse {
+        method: method.to_string(),
+        created_at: now,
+        response: None,
+    };
+    insert(caller, idempotency_key, &stored);
+
+    StartResult::Started(IdempotencyGuard {
+        request: Some(InProgressRequest {
+            caller,
+            method,
+            idempotency_key,
+            created_at: now,
+        }),
+    })
+}
+
+// Removes the in progress marker if dropped without `complete` being called (eg. if the request
+// traps after an await), so that retries are processed rather than being rejected until expiry.
+#[derive(Default)]
+pub struct IdempotencyGuard {
+    request: Option<InProgressRequest>,
+}
+
+struct InProgressRequest {
+    caller: Principal,
+    method: &'static str,
+    idempotency_key: u64,
+    created_at: TimestampMillis,
+}
+
+impl IdempotencyGuard {
+    pub fn complete(mut self, response: Vec<u8>) {
+        if response.len() > MAX_RESPONSE_BYTES {
+            // Dropping the guard removes the in progress marker, so that retries are processed again
+            return;
+        }
+        if let Some(request) = self.request.take() {
+            let stored = StoredResponse {
+                method: request.method.to_string(),
+                created_at: request.created_at,
+                response: Some(serde_bytes::ByteBuf::from(response)),
+            };
+            insert(request.caller, request.idempotency_key, &stored);
+        }
+    }
+}
+
+impl Drop for IdempotencyGuard {
+    fn drop(&mut self) {
+        if let Some(request) = self.request.take() {
+            remove(request.caller, request.idempotency_key, request.created_at);
+        }
+    }
+}
+
+fn get(caller: Principal, idempotency_key: u64) -> Option<StoredResponse> {
+    let key = IdempotentResponseKeyPrefix::new().create_key(&(caller, idempotency_key));
+    with_map(|m| m.get(key)).map(|bytes| msgpack::deserialize_then_unwrap(&bytes))
+}
+
+fn insert(caller: Principal, idempotency_key: u64, stored: &StoredResponse) {
+    let key = IdempotentResponseKeyPrefix::new().create_key(&(caller, idempotency_key));
+    let expiry_key = IdempotentResponseExpiryKeyPrefix::new().create_key(&(stored.created_at, caller, idempotency_key));
+    let bytes = msgpack::serialize_then_unwrap(stored);
+
+    with_map_mut(|m| {
+        m.insert(key, bytes);
+        m.insert(expiry_key, Vec::new());
+    });
+}
+
+fn remove(caller: Principal, idempotency_key: u64, created_at: TimestampMillis) {
+    // Only remove the response if it hasn't since been overwritten by a newer request with the same key
+    if get(caller, idempotency_key).is_some_and(|s| s.created_at == created_at) {
+        let key = IdempotentResponseKeyPrefix::new().create_key(&(caller, idempotency_key));
+        with_map_mut(|m| m.remove(key));
+    }
+    let expiry_key = IdempotentResponseExpiryKeyPrefix::new().create_key(&(created_at, caller, idempotency_key));
+    with_map_mut(|m| m.remove(expiry_key));
+}
+
+fn remove_expired(now: TimestampMillis) {
+    let cutoff = now.saturating_sub(RETENTION_PERIOD);
+
+    let expired: Vec<IdempotentResponseExpiryKey> = with_map(|m| {
+        m.range(expiry_keys_start()..)
+            .map(|(k, _)| k)
+            .take_while(|k: &IdempotentResponseExpiryKey| k.created_at() < cutoff)
+            .take(MAX_EXPIRED_REMOVED_PER_CALL)
+            .collect()
+    });
+
+    for key in expired {
+        remove(key.caller(), key.idempotency_key(), key.created_at());
+    }
+}
+
+fn expiry_keys_start() -> IdempotentResponseExpiryKey {
+    IdempotentResponseExpiryKeyPrefix::new().create_key(&(0, Principal::management_canister(), 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_stable_structures::DefaultMemoryImpl;
+    use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
+
+    fn init() {
+        let memory = MemoryManager::init(DefaultMemoryImpl::default());
+        stable_memory_map::init(memory.get(MemoryId::new(1)));
+    }
+
+    #[test]
+    fn completed_response_is_returned_on_retry() {
+        init();
+        let caller = Principal::from_slice(&[1]);
+
+        let StartResult::Started(guard) = start_inner(caller, "c2c_bot_send_message", 1, 1000) else {
+            panic!();
+        };
+        assert!(matches!(
+            start_inner(caller, "c2c_bot_send_message", 1, 1001),
+            StartResult::InProgress
+        ));
+        guard.complete(vec![1, 2, 3]);
+
+        let StartResult::Completed(response) = start_inner(caller, "c2c_bot_send_message", 1, 1002) else {
+            panic!();
+        };
+        assert_eq!(response, vec![1, 2, 3]);
+
+        // Requests from other callers, with other keys or to other methods are not matched
+        assert!(matches!(
+            start_inner(Principal::from_slice(&[2]), "c2c_bot_send_message", 1, 1003),
+            StartResult::Started(_)
+        ));
+        assert!(matches!(
+            start_inner(caller, "c2c_bot_send_message", 2, 1004),
+            StartResult::Started(_)
+        ));
+        assert!(matches!(
+            start_inner(caller, "c2c_tip_message", 1, 1005),
+            StartResult::Started(_)
+        ));
+    }
+
+    #[test]
+    fn dropped_guard_allows_retry() {
+        init();
+        let caller = Principal::from_slice(&[1]);
+
+        let result = start_inner(caller, "c2c_bot_send_message", 1, 1000);
+        drop(result);
+
+        assert!(matches!(
+            start_inner(caller, "c2c_bot_send_message", 1, 1001),
+            StartResult::Started(_)
+        ));
+    }
+
+    #[test]
+    fn responses_expire() {
+        init();
+        let caller = Principal::from_slice(&[1]);
+
+        let StartResult::Started(guard) = start_inner(caller, "c2c_bot_send_message", 1, 1000) else {
+            panic!();
+        };
+        guard.complete(vec![1]);
+
+        assert!(matches!(
+            start_inner(caller, "c2c_bot_send_message", 1, 1000 + RETENTION_PERIOD),
+            StartResult::Completed(_)
+        ));
+
+        // The expired response is removed and the request is processed again
+        let result = start_inner(caller, "c2c_bot_send_message", 1, 1001 + RETENTION_PERIOD);
+        assert!(matches!(result, StartResult::Started(_)));
+
+        let expiry_keys: Vec<IdempotentResponseExpiryKey> =
+            with_map(|m| m.range(expiry_keys_start()..).map(|(k, _)| k).collect());
+        assert_eq!(expiry_keys.len(), 1);
+        assert_eq!(expiry_keys[0].created_at(), 1001 + RETENTION_PERIOD);
+    }
+
+    #[test]
+    fn large_responses_are_not_stored() {
+        init();
+        let caller = Principal::from_slice(&[1]);
+
+        let StartResult::Started(guard) = start_inner(caller, "c2c_bot_send_message", 1, 1000) else {
+            panic!();
+        };
+        guard.complete(vec![0; MAX_RESPONSE_BYTES + 1]);
+
+        assert!(matches!(
+            start_inner(caller, "c2c_bot_send_message", 1, 1001),
+            StartResult::Started(_)
+        ));
+    }
+}
@@ -1,13 +1,34 @@
-use ic_cbor::{CborValue, CertificateToCbor, parse_cbor};
-use ic_certification::Certificate;
+use ic_cbor::{CborValue, CertificateToCbor, parse_cbor, parsed_cbor_to_tree};
+use ic_certification::{Certificate, HashTree};
+use std::collections::HashMap;
 
 pub fn extract_certificate(signature: &[u8]) -> Result<Certificate, String> {
+    let map = parse_signature(signature)?;
+    extract_certificate_from_map(&map)
+}
+
+// Returns the certificate along with the hash tree which proves that the canister produced the signature
+pub fn extract_certificate_and_tree(signature: &[u8]) -> Result<(Certificate, HashTree), String> {
+    let map = parse_signature(signature)?;
+    let certificate = extract_certificate_from_map(&map)?;
+    let Some(tree) = map.get("tree") else {
+        return Err("Couldn't find tree".to_string());
+    };
+    let tree = parsed_cbor_to_tree(tree).map_err(|_| "Unable to parse tree".to_string())?;
+    Ok((certificate, tree))
+}
+
+fn parse_signature(signature: &[u8]) -> Result<HashMap<String, CborValue>, String> {
     let Ok(cbor) = parse_cbor(signature) else {
         return Err("Unable to parse signature as CBOR".to_string());
     };
     let CborValue::Map(map) = cbor else {
         return Err("Expected CBOR map".to_string());
     };
+    Ok(map)
+}
+
+fn extract_certificate_from_map(map: &HashMap<String, CborValue>) -> Result<Certificate, String> {
     let Some(CborValue::ByteString(certificate_bytes)) = map.get("certificate") else {
         return Err("Couldn't find certificate".to_string());
     };
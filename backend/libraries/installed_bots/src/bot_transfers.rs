@@ -0,0 +1,46 @@
+use crate::BotSpendReservation;
+use ledger_utils::icrc2::process_transaction;
+use oc_error_codes::OCErrorCode;
+use tracing::error;
+use types::icrc2::{CompletedCryptoTransaction, PendingCryptoTransaction};
+use types::{CanisterId, OCResult};
+
+pub struct PreparedBotTransfer {
+    pub transaction: PendingCryptoTransaction,
+    pub reservation: BotSpendReservation,
+    pub this_canister_id: CanisterId,
+}
+
+// Funds a bot action from the bot's own account once the request has been validated and the amount reserved from the
+// bot's spend allowance. The transfer is made using the allowance the bot has approved for this canister, then `commit`
+// performs the action. If the transfer fails the reservation is handed to `release`, whereas if `commit` fails it is up
+// to `commit` to refund any funds held by this canister.
+pub async fn process_bot_transfer<R>(
+    prepared: PreparedBotTransfer,
+    release: impl FnOnce(BotSpendReservation),
+    commit: impl FnOnce(CompletedCryptoTransaction, BotSpendReservation) -> OCResult<R>,
+) -> OCResult<R> {
+    let PreparedBotTransfer {
+        transaction,
+        reservation,
+        this_canister_id,
+    } = prepared;
+
+    let completed = match process_transaction(transaction, this_canister_id).await {
+        Ok(Ok(completed)) => completed,
+        Ok(Err(failed)) => {
+            release(reservation);
+            return Err(OCErrorCode::TransferFailed.with_message(failed.error_message));
+        }
+        Err(error) => {
+            release(reservation);
+            return Err(error.into());
+        }
+    };
+
+    let result = commit(completed.clone(), reservation);
+    if let Err(error) = &result {
+        error!(?error, ?completed, "Failed to complete bot action after transfer");
+    }
+    result
+}
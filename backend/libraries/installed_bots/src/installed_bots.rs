@@ -1,9 +1,20 @@
 use candid::Principal;
-use constants::calculate_summary_updates_data_removal_cutoff;
+use constants::{DAY_IN_MS, MINUTE_IN_MS, calculate_summary_updates_data_removal_cutoff};
+use oc_error_codes::OCErrorCode;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use std::collections::{btree_map::Entry, BTreeMap, BTreeSet};
-use types::{BotPermissions, BotSubscriptions, TimestampMillis, UserId};
+use std::collections::{BTreeMap, BTreeSet, btree_map::Entry};
+use types::{
+    BotPermissions, BotScheduledTask, BotSpendAllowance, BotStorageEntry, BotSubscriptions, CanisterId, ChannelId,
+    Milliseconds, OCResult, TimestampMillis, UserId,
+};
+
+const MAX_STORAGE_BYTES_PER_BOT: usize = 64 * 1024;
+const MAX_STORAGE_KEY_LENGTH: usize = 128;
+const MAX_SCHEDULED_TASKS_PER_BOT: usize = 10;
+const MAX_SCHEDULED_TASK_NAME_LENGTH: usize = 100;
+const MIN_SCHEDULED_TASK_INTERVAL: Milliseconds = 5 * MINUTE_IN_MS;
+const MAX_SCHEDULED_TASK_INTERVAL: Milliseconds = 365 * DAY_IN_MS;
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct InstalledBots {
@@ -33,6 +44,9 @@ impl InstalledBots {
                 permissions,
                 autonomous_permissions,
                 default_subscriptions,
+                spend_allowances: BTreeMap::new(),
+                storage: BTreeMap::new(),
+                scheduled_tasks: BTreeMap::new(),
             },
         );
         self.prune_then_insert_member_update(bot_id, BotUpdate::Added, now);
@@ -59,6 +73,201 @@ impl InstalledBots {
         }
     }
 
+    // Replaces the bot's spend allowances, retaining the amounts already spent within the current period for any ledgers
+    // which are still allowed
+    pub fn set_spend_allowances(&mut self, bot_id: UserId, allowances: Vec<BotSpendAllowance>, now: TimestampMillis) -> bool {
+        let Some(bot) = self.bots.get_mut(&bot_id) else {
+            return false;
+        };
+
+        let mut previous = std::mem::take(&mut bot.spend_allowances);
+        for allowance in allowances {
+            let (period_start, spent) = previous
+                .remove(&allowance.ledger)
+                .map_or((now, 0), |a| (a.period_start, a.spent));
+
+            bot.spend_allowances.insert(
+                allowance.ledger,
+                BotSpendAllowanceInternal {
+                    amount: allowance.amount,
+                    period: allowance.period,
+                    period_start,
+                    spent,
+                },
+            );
+        }
+
+        self.prune_then_insert_member_update(bot_id, BotUpdate::Updated, now);
+        true
+    }
+
+    // Records the amount as spent if it fits within the bot's remaining allowance for the ledger
+    pub fn reserve_spend(
+        &mut self,
+        bot_id: &UserId,
+        ledger: CanisterId,
+        amount: u128,
+        now: TimestampMillis,
+    ) -> Option<BotSpendReservation> {
+        let allowance = self.bots.get_mut(bot_id)?.spend_allowances.get_mut(&ledger)?;
+
+        if now >= allowance.period_start.saturating_add(allowance.period) {
+            allowance.period_start = now;
+            allowance.spent = 0;
+        }
+
+        allowance.spent = allowance.spent.checked_add(amount).filter(|s| *s <= allowance.amount)?;
+
+        Some(BotSpendReservation {
+            bot_id: *bot_id,
+            ledger,
+            amount,
+            period_start: allowance.period_start,
+        })
+    }
+
+    // Returns a previously reserved amount to the allowance, eg. if the transfer failed. If the period has since been
+    // reset then the amount no longer counts towards the current period so there is nothing to return.
+    pub fn release_spend(&mut self, reservation: BotSpendReservation) {
+        if let Some(allowance) = self
+            .bots
+            .get_mut(&reservation.bot_id)
+            .and_then(|b| b.spend_allowances.get_mut(&reservation.ledger))
+        {
+            if allowance.period_start == reservation.period_start {
+                allowance.spent = allowance.spent.saturating_sub(reservation.amount);
+            }
+        }
+    }
+
+    // Returns the requested entries, or all of the bot's entries if no keys are specified
+    pub fn storage_entries(&self, bot_id: &UserId, keys: &[String]) -> OCResult<Vec<BotStorageEntry>> {
+        let bot = self.bots.get(bot_id).ok_or(OCErrorCode::BotNotFound)?;
+
+        let entries = if keys.is_empty() {
+            bot.storage
+                .iter()
+                .map(|(key, value)| BotStorageEntry {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect()
+        } else {
+            keys.iter()
+                .filter_map(|key| {
+                    bot.storage.get(key).map(|value| BotStorageEntry {
+                        key: key.clone(),
+                        value: value.clone(),
+                    })
+                })
+                .collect()
+        };
+
+        Ok(entries)
+    }
+
+    // Applies the changes only if the bot's storage remains within its quota afterwards
+    pub fn update_storage(&mut self, bot_id: &UserId, set: Vec<BotStorageEntry>, remove: Vec<String>) -> OCResult {
+        let bot = self.bots.get_mut(bot_id).ok_or(OCErrorCode::BotNotFound)?;
+
+        if set.iter().any(|e| e.key.is_empty() || e.key.len() > MAX_STORAGE_KEY_LENGTH) {
+            return Err(
+                OCErrorCode::InvalidRequest.with_message(format!("Keys must be between 1 and {MAX_STORAGE_KEY_LENGTH} bytes"))
+            );
+        }
+
+        let mut storage = bot.storage.clone();
+        for key in remove {
+            storage.remove(&key);
+        }
+        for entry in set {
+            storage.insert(entry.key, entry.value);
+        }
+
+        let total_bytes: usize = storage.iter().map(|(k, v)| k.len() + v.len()).sum();
+        if total_bytes > MAX_STORAGE_BYTES_PER_BOT {
+            return Err(OCErrorCode::BotStorageQuotaExceeded
+                .with_message(format!("Storage is limited to {MAX_STORAGE_BYTES_PER_BOT} bytes per bot")));
+        }
+
+        bot.storage = storage;
+        Ok(())
+    }
+
+    // Adds or replaces the task with the given name. The first run is one interval from now.
+    pub fn schedule_task(
+        &mut self,
+        bot_id: &UserId,
+        name: String,
+        interval: Milliseconds,
+        channel_id: Option<ChannelId>,
+        now: TimestampMillis,
+    ) -> OCResult<TimestampMillis> {
+        let bot = self.bots.get_mut(bot_id).ok_or(OCErrorCode::BotNotFound)?;
+
+        if name.is_empty() || name.len() > MAX_SCHEDULED_TASK_NAME_LENGTH {
+            return Err(OCErrorCode::InvalidRequest.with_message(format!(
+                "Task names must be between 1 and {MAX_SCHEDULED_TASK_NAME_LENGTH} bytes"
+            )));
+        }
+
+        if !(MIN_SCHEDULED_TASK_INTERVAL..=MAX_SCHEDULED_TASK_INTERVAL).contains(&interval) {
+            return Err(OCErrorCode::InvalidRequest.with_message(format!(
+                "The interval must be between {MIN_SCHEDULED_TASK_INTERVAL}ms and {MAX_SCHEDULED_TASK_INTERVAL}ms"
+            )));
+        }
+
+        if !bot.scheduled_tasks.contains_key(&name) && bot.scheduled_tasks.len() >= MAX_SCHEDULED_TASKS_PER_BOT {
+            return Err(OCErrorCode::TooManyScheduledTasks.into());
+        }
+
+        let next_run = now.saturating_add(interval);
+        bot.scheduled_tasks.insert(
+            name,
+            BotScheduledTaskInternal {
+                interval,
+                channel_id,
+                next_run,
+            },
+        );
+        Ok(next_run)
+    }
+
+    pub fn cancel_task(&mut self, bot_id: &UserId, name: &str) -> bool {
+        self.bots
+            .get_mut(bot_id)
+            .is_some_and(|b| b.scheduled_tasks.remove(name).is_some())
+    }
+
+    // If the task is due, advances it to its next run and returns the time it was due along with the updated task
+    pub fn take_due_task(
+        &mut self,
+        bot_id: &UserId,
+        name: &str,
+        now: TimestampMillis,
+    ) -> Option<(TimestampMillis, BotScheduledTask)> {
+        let task = self.bots.get_mut(bot_id)?.scheduled_tasks.get_mut(name)?;
+        if task.next_run > now {
+            return None;
+        }
+
+        let scheduled_at = task.next_run;
+
+        // Skip any runs which were missed rather than firing them all at once
+        let missed = (now - task.next_run) / task.interval;
+        task.next_run = task.next_run.saturating_add((missed + 1).saturating_mul(task.interval));
+
+        Some((
+            scheduled_at,
+            BotScheduledTask {
+                name: name.to_string(),
+                interval: task.interval,
+                channel_id: task.channel_id,
+                next_run: task.next_run,
+            },
+        ))
+    }
+
     pub fn remove(&mut self, bot_id: UserId, now: TimestampMillis) -> bool {
         let removed = self.bots.remove(&bot_id).is_some();
 
@@ -130,4 +339,324 @@ pub struct BotInternal {
     pub autonomous_permissions: Option<BotPermissions>,
     #[serde(default)]
     pub default_subscriptions: Option<BotSubscriptions>,
+    #[serde(default)]
+    pub spend_allowances: BTreeMap<CanisterId, BotSpendAllowanceInternal>,
+    #[serde(default)]
+    pub storage: BTreeMap<String, String>,
+    #[serde(default)]
+    pub scheduled_tasks: BTreeMap<String, BotScheduledTaskInternal>,
+}
+
+impl BotInternal {
+    pub fn spend_allowances(&self) -> Vec<BotSpendAllowance> {
+        self.spend_allowances
+            .iter()
+            .map(|(ledger, a)| BotSpendAllowance {
+                ledger: *ledger,
+                amount: a.amount,
+                period: a.period,
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BotSpendAllowanceInternal {
+    pub amount: u128,
+    pub period: Milliseconds,
+    pub period_start: TimestampMillis,
+    pub spent: u128,
+}
+
+pub struct BotSpendReservation {
+    pub bot_id: UserId,
+    pub ledger: CanisterId,
+    pub amount: u128,
+    period_start: TimestampMillis,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BotScheduledTaskInternal {
+    pub interval: Milliseconds,
+    pub channel_id: Option<ChannelId>,
+    pub next_run: TimestampMillis,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use constants::DAY_IN_MS;
+
+    const NOW: TimestampMillis = 1_000_000;
+
+    fn user(id: u8) -> UserId {
+        UserId::new(Principal::from_slice(&[id]))
+    }
+
+    fn ledger(id: u8) -> CanisterId {
+        Principal::from_slice(&[100, id])
+    }
+
+    fn setup(allowances: Vec<BotSpendAllowance>) -> (InstalledBots, UserId) {
+        let mut bots = InstalledBots::default();
+        let bot_id = user(1);
+        bots.add(bot_id, user(2), BotPermissions::default(), None, None, NOW);
+        bots.set_spend_allowances(bot_id, allowances, NOW);
+        (bots, bot_id)
+    }
+
+    fn allowance(ledger_id: u8, amount: u128) -> BotSpendAllowance {
+        BotSpendAllowance {
+            ledger: ledger(ledger_id),
+            amount,
+            period: DAY_IN_MS,
+        }
+    }
+
+    fn spent(bots: &InstalledBots, bot_id: &UserId, ledger_id: u8) -> u128 {
+        bots.get(bot_id).unwrap().spend_allowances[&ledger(ledger_id)].spent
+    }
+
+    #[test]
+    fn reserve_spend_within_allowance() {
+        let (mut bots, bot_id) = setup(vec![allowance(1, 100)]);
+
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 60, NOW).is_some());
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 40, NOW).is_some());
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 1, NOW).is_none());
+        assert_eq!(spent(&bots, &bot_id, 1), 100);
+    }
+
+    #[test]
+    fn reserve_spend_rejects_unknown_ledger_or_bot() {
+        let (mut bots, bot_id) = setup(vec![allowance(1, 100)]);
+
+        assert!(bots.reserve_spend(&bot_id, ledger(2), 1, NOW).is_none());
+        assert!(bots.reserve_spend(&user(3), ledger(1), 1, NOW).is_none());
+    }
+
+    #[test]
+    fn reserve_spend_rejects_overflow() {
+        let (mut bots, bot_id) = setup(vec![allowance(1, u128::MAX)]);
+
+        assert!(bots.reserve_spend(&bot_id, ledger(1), u128::MAX, NOW).is_some());
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 1, NOW).is_none());
+        assert_eq!(spent(&bots, &bot_id, 1), u128::MAX);
+    }
+
+    #[test]
+    fn reserve_spend_resets_after_period() {
+        let (mut bots, bot_id) = setup(vec![allowance(1, 100)]);
+
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 100, NOW).is_some());
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 1, NOW + DAY_IN_MS - 1).is_none());
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 30, NOW + DAY_IN_MS).is_some());
+        assert_eq!(spent(&bots, &bot_id, 1), 30);
+    }
+
+    #[test]
+    fn release_spend_returns_amount() {
+        let (mut bots, bot_id) = setup(vec![allowance(1, 100)]);
+
+        let reservation = bots.reserve_spend(&bot_id, ledger(1), 70, NOW).unwrap();
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 40, NOW).is_none());
+
+        bots.release_spend(reservation);
+        assert_eq!(spent(&bots, &bot_id, 1), 0);
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 40, NOW).is_some());
+    }
+
+    #[test]
+    fn release_spend_after_period_reset_leaves_new_period_untouched() {
+        let (mut bots, bot_id) = setup(vec![allowance(1, 100)]);
+
+        let reservation = bots.reserve_spend(&bot_id, ledger(1), 70, NOW).unwrap();
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 50, NOW + DAY_IN_MS).is_some());
+
+        bots.release_spend(reservation);
+        assert_eq!(spent(&bots, &bot_id, 1), 50);
+    }
+
+    #[test]
+    fn set_spend_allowances_retains_spent_for_remaining_ledgers() {
+        let (mut bots, bot_id) = setup(vec![allowance(1, 100), allowance(2, 100)]);
+
+        bots.reserve_spend(&bot_id, ledger(1), 60, NOW).unwrap();
+        bots.reserve_spend(&bot_id, ledger(2), 60, NOW).unwrap();
+
+        assert!(bots.set_spend_allowances(bot_id, vec![allowance(1, 80), allowance(3, 10)], NOW + 1));
+
+        let bot = bots.get(&bot_id).unwrap();
+        assert_eq!(bot.spend_allowances.len(), 2);
+        assert_eq!(bot.spend_allowances[&ledger(1)].amount, 80);
+        assert_eq!(bot.spend_allowances[&ledger(1)].spent, 60);
+        assert_eq!(bot.spend_allowances[&ledger(1)].period_start, NOW);
+        assert_eq!(bot.spend_allowances[&ledger(3)].spent, 0);
+        assert_eq!(bot.spend_allowances[&ledger(3)].period_start, NOW + 1);
+        assert!(!bot.spend_allowances.contains_key(&ledger(2)));
+
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 21, NOW + 1).is_none());
+        assert!(bots.reserve_spend(&bot_id, ledger(1), 20, NOW + 1).is_some());
+    }
+
+    #[test]
+    fn set_spend_allowances_rejects_unknown_bot() {
+        let (mut bots, _) = setup(Vec::new());
+
+        assert!(!bots.set_spend_allowances(user(3), vec![allowance(1, 100)], NOW));
+    }
+
+    fn entry(key: &str, value: &str) -> BotStorageEntry {
+        BotStorageEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn update_storage_sets_and_removes_entries() {
+        let (mut bots, bot_id) = setup(Vec::new());
+
+        bots.update_storage(&bot_id, vec![entry("a", "1"), entry("b", "2")], Vec::new())
+            .unwrap();
+        bots.update_storage(&bot_id, vec![entry("a", "3")], vec!["b".to_string()])
+            .unwrap();
+
+        let entries = bots.storage_entries(&bot_id, &[]).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "a");
+        assert_eq!(entries[0].value, "3");
+    }
+
+    #[test]
+    fn storage_entries_returns_requested_keys() {
+        let (mut bots, bot_id) = setup(Vec::new());
+
+        bots.update_storage(&bot_id, vec![entry("a", "1"), entry("b", "2")], Vec::new())
+            .unwrap();
+
+        let entries = bots.storage_entries(&bot_id, &["b".to_string(), "c".to_string()]).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "b");
+        assert!(bots.storage_entries(&user(3), &[]).is_err());
+    }
+
+    #[test]
+    fn update_storage_rejects_invalid_keys() {
+        let (mut bots, bot_id) = setup(Vec::new());
+
+        assert!(bots.update_storage(&bot_id, vec![entry("", "1")], Vec::new()).is_err());
+        assert!(
+            bots.update_storage(&bot_id, vec![entry(&"k".repeat(MAX_STORAGE_KEY_LENGTH + 1), "1")], Vec::new())
+                .is_err()
+        );
+        assert!(
+            bots.update_storage(&bot_id, vec![entry(&"k".repeat(MAX_STORAGE_KEY_LENGTH), "1")], Vec::new())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn update_storage_enforces_quota() {
+        let (mut bots, bot_id) = setup(Vec::new());
+
+        let half = "v".repeat(MAX_STORAGE_BYTES_PER_BOT / 2);
+        bots.update_storage(&bot_id, vec![entry("a", &half)], Vec::new()).unwrap();
+
+        // Exceeding the quota leaves the existing entries unchanged
+        assert!(bots.update_storage(&bot_id, vec![entry("b", &half)], Vec::new()).is_err());
+        assert_eq!(bots.storage_entries(&bot_id, &[]).unwrap().len(), 1);
+
+        // Removing an entry in the same update frees up its space
+        assert!(
+            bots.update_storage(&bot_id, vec![entry("b", &half)], vec!["a".to_string()])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn schedule_task_validates_args() {
+        let (mut bots, bot_id) = setup(Vec::new());
+        let interval = MIN_SCHEDULED_TASK_INTERVAL;
+
+        assert!(bots.schedule_task(&bot_id, String::new(), interval, None, NOW).is_err());
+        assert!(
+            bots.schedule_task(&bot_id, "t".repeat(MAX_SCHEDULED_TASK_NAME_LENGTH + 1), interval, None, NOW)
+                .is_err()
+        );
+        assert!(
+            bots.schedule_task(&bot_id, "t".to_string(), MIN_SCHEDULED_TASK_INTERVAL - 1, None, NOW)
+                .is_err()
+        );
+        assert!(
+            bots.schedule_task(&bot_id, "t".to_string(), MAX_SCHEDULED_TASK_INTERVAL + 1, None, NOW)
+                .is_err()
+        );
+        assert!(bots.schedule_task(&user(3), "t".to_string(), interval, None, NOW).is_err());
+        assert_eq!(
+            bots.schedule_task(&bot_id, "t".to_string(), interval, None, NOW),
+            Ok(NOW + interval)
+        );
+    }
+
+    #[test]
+    fn schedule_task_saturates_next_run() {
+        let (mut bots, bot_id) = setup(Vec::new());
+
+        assert_eq!(
+            bots.schedule_task(&bot_id, "t".to_string(), MAX_SCHEDULED_TASK_INTERVAL, None, u64::MAX - 1),
+            Ok(u64::MAX)
+        );
+        assert!(bots.take_due_task(&bot_id, "t", u64::MAX).is_some());
+    }
+
+    #[test]
+    fn schedule_task_limits_number_of_tasks() {
+        let (mut bots, bot_id) = setup(Vec::new());
+        let interval = MIN_SCHEDULED_TASK_INTERVAL;
+
+        for i in 0..MAX_SCHEDULED_TASKS_PER_BOT {
+            bots.schedule_task(&bot_id, i.to_string(), interval, None, NOW).unwrap();
+        }
+
+        assert!(bots.schedule_task(&bot_id, "new".to_string(), interval, None, NOW).is_err());
+
+        // Replacing an existing task is still allowed
+        assert!(bots.schedule_task(&bot_id, "0".to_string(), interval * 2, None, NOW).is_ok());
+
+        assert!(bots.cancel_task(&bot_id, "0"));
+        assert!(!bots.cancel_task(&bot_id, "0"));
+        assert!(bots.schedule_task(&bot_id, "new".to_string(), interval, None, NOW).is_ok());
+    }
+
+    #[test]
+    fn take_due_task_advances_next_run() {
+        let (mut bots, bot_id) = setup(Vec::new());
+        let interval = MIN_SCHEDULED_TASK_INTERVAL;
+
+        bots.schedule_task(&bot_id, "t".to_string(), interval, None, NOW).unwrap();
+
+        assert!(bots.take_due_task(&bot_id, "t", NOW + interval - 1).is_none());
+
+        let (scheduled_at, task) = bots.take_due_task(&bot_id, "t", NOW + interval).unwrap();
+        assert_eq!(scheduled_at, NOW + interval);
+        assert_eq!(task.next_run, NOW + 2 * interval);
+
+        assert!(bots.take_due_task(&bot_id, "t", NOW + interval).is_none());
+        assert!(bots.take_due_task(&bot_id, "unknown", NOW + interval).is_none());
+    }
+
+    #[test]
+    fn take_due_task_skips_missed_runs() {
+        let (mut bots, bot_id) = setup(Vec::new());
+        let interval = MIN_SCHEDULED_TASK_INTERVAL;
+
+        bots.schedule_task(&bot_id, "t".to_string(), interval, None, NOW).unwrap();
+
+        // Three runs were missed, so only the first is reported and the next run is the one after now
+        let now = NOW + 3 * interval + 1;
+        let (scheduled_at, task) = bots.take_due_task(&bot_id, "t", now).unwrap();
+        assert_eq!(scheduled_at, NOW + interval);
+        assert_eq!(task.next_run, NOW + 4 * interval);
+    }
 }
@@ -1,3 +1,5 @@
+mod bot_transfers;
 mod installed_bots;
 
+pub use bot_transfers::*;
 pub use installed_bots::*;
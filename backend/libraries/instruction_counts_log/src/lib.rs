@@ -8,6 +8,9 @@ use types::{BuildVersion, TimestampMillis};
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
+/// Upper bounds used when exposing instruction counts as a histogram.
+pub const INSTRUCTION_COUNT_BUCKETS: [f64; 8] = [1e8, 5e8, 1e9, 5e9, 1e10, 5e10, 1e11, 3e11];
+
 pub struct InstructionCountsLog {
     log: StableLog<InstructionCountEntry, Memory, Memory>,
 }
@@ -47,6 +50,16 @@ pub struct InstructionCountEntry {
     instruction_count: u64,
 }
 
+impl InstructionCountEntry {
+    pub fn function_id(&self) -> InstructionCountFunctionId {
+        self.function_id
+    }
+
+    pub fn instruction_count(&self) -> u64 {
+        self.instruction_count
+    }
+}
+
 #[repr(u8)]
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
 pub enum InstructionCountFunctionId {
@@ -5,6 +5,7 @@ use std::borrow::Cow;
 
 mod chat_event;
 mod community_event;
+mod idempotency;
 mod macros;
 mod principal;
 mod storage;
@@ -12,6 +13,7 @@ mod user_id;
 
 pub use chat_event::*;
 pub use community_event::*;
+pub use idempotency::*;
 pub use principal::*;
 pub use storage::*;
 pub use user_id::*;
@@ -53,6 +55,11 @@ impl From<BaseKeyPrefix> for BaseKey {
 pub struct BaseKeyPrefix(#[serde(with = "serde_bytes")] Vec<u8>);
 
 impl BaseKeyPrefix {
+    // Matches every key of the given type
+    pub fn from_key_type(key_type: KeyType) -> BaseKeyPrefix {
+        BaseKeyPrefix(vec![key_type as u8])
+    }
+
     pub fn as_slice(&self) -> &[u8] {
         self.0.as_slice()
     }
@@ -98,6 +105,8 @@ pub enum KeyType {
     FilesPerAccessor = 14,
     UserStorageRecord = 15,
     BlockedUsers = 16,
+    IdempotentResponse = 17,
+    IdempotentResponseExpiry = 18,
 }
 
 fn extract_key_type(bytes: &[u8]) -> Option<KeyType> {
@@ -125,6 +134,8 @@ impl TryFrom<u8> for KeyType {
             14 => Ok(KeyType::FilesPerAccessor),
             15 => Ok(KeyType::UserStorageRecord),
             16 => Ok(KeyType::BlockedUsers),
+            17 => Ok(KeyType::IdempotentResponse),
+            18 => Ok(KeyType::IdempotentResponseExpiry),
             _ => Err(()),
         }
     }
@@ -0,0 +1,162 @@
+use crate::keys::macros::key;
+use crate::{KeyPrefix, KeyType};
+use ic_principal::Principal;
+use types::TimestampMillis;
+
+key!(
+    IdempotentResponseKey,
+    IdempotentResponseKeyPrefix,
+    KeyType::IdempotentResponse
+);
+
+impl IdempotentResponseKeyPrefix {
+    pub fn new() -> Self {
+        // KeyType::IdempotentResponse  1 byte
+        IdempotentResponseKeyPrefix(vec![KeyType::IdempotentResponse as u8])
+    }
+}
+
+impl Default for IdempotentResponseKeyPrefix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyPrefix for IdempotentResponseKeyPrefix {
+    type Key = IdempotentResponseKey;
+    type Suffix = (Principal, u64);
+
+    fn create_key(&self, (caller, idempotency_key): &(Principal, u64)) -> IdempotentResponseKey {
+        let caller_bytes = caller.as_slice();
+        let mut bytes = Vec::with_capacity(self.0.len() + 1 + caller_bytes.len() + 8);
+        bytes.extend_from_slice(self.0.as_slice());
+        bytes.push(caller_bytes.len() as u8);
+        bytes.extend_from_slice(caller_bytes);
+        bytes.extend_from_slice(&idempotency_key.to_be_bytes());
+        IdempotentResponseKey(bytes)
+    }
+}
+
+impl IdempotentResponseKey {
+    pub fn caller(&self) -> Principal {
+        let caller_len = self.0[1] as usize;
+        Principal::from_slice(&self.0[2..2 + caller_len])
+    }
+
+    pub fn idempotency_key(&self) -> u64 {
+        let start = self.0.len() - 8;
+        u64::from_be_bytes(self.0[start..].try_into().unwrap())
+    }
+}
+
+key!(
+    IdempotentResponseExpiryKey,
+    IdempotentResponseExpiryKeyPrefix,
+    KeyType::IdempotentResponseExpiry
+);
+
+impl IdempotentResponseExpiryKeyPrefix {
+    pub fn new() -> Self {
+        // KeyType::IdempotentResponseExpiry    1 byte
+        IdempotentResponseExpiryKeyPrefix(vec![KeyType::IdempotentResponseExpiry as u8])
+    }
+}
+
+impl Default for IdempotentResponseExpiryKeyPrefix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyPrefix for IdempotentResponseExpiryKeyPrefix {
+    type Key = IdempotentResponseExpiryKey;
+    type Suffix = (TimestampMillis, Principal, u64);
+
+    fn create_key(
+        &self,
+        (created_at, caller, idempotency_key): &(TimestampMillis, Principal, u64),
+    ) -> IdempotentResponseExpiryKey {
+        // The timestamp comes first so that iterating over the keys visits the oldest responses first
+        let caller_bytes = caller.as_slice();
+        let mut bytes = Vec::with_capacity(self.0.len() + 8 + 1 + caller_bytes.len() + 8);
+        bytes.extend_from_slice(self.0.as_slice());
+        bytes.extend_from_slice(&created_at.to_be_bytes());
+        bytes.push(caller_bytes.len() as u8);
+        bytes.extend_from_slice(caller_bytes);
+        bytes.extend_from_slice(&idempotency_key.to_be_bytes());
+        IdempotentResponseExpiryKey(bytes)
+    }
+}
+
+impl IdempotentResponseExpiryKey {
+    pub fn created_at(&self) -> TimestampMillis {
+        u64::from_be_bytes(self.0[1..9].try_into().unwrap())
+    }
+
+    pub fn caller(&self) -> Principal {
+        let caller_len = self.0[9] as usize;
+        Principal::from_slice(&self.0[10..10 + caller_len])
+    }
+
+    pub fn idempotency_key(&self) -> u64 {
+        let start = self.0.len() - 8;
+        u64::from_be_bytes(self.0[start..].try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BaseKey, Key};
+    use rand::{Rng, RngCore, thread_rng};
+
+    #[test]
+    fn idempotent_response_key_e2e() {
+        for _ in 0..100 {
+            let caller_bytes: [u8; 10] = thread_rng().r#gen();
+            let caller = Principal::from_slice(&caller_bytes);
+            let idempotency_key = thread_rng().next_u64();
+            let prefix = IdempotentResponseKeyPrefix::new();
+            let key = BaseKey::from(prefix.create_key(&(caller, idempotency_key)));
+            let response_key = IdempotentResponseKey::try_from(key.clone()).unwrap();
+
+            assert_eq!(*response_key.0.first().unwrap(), KeyType::IdempotentResponse as u8);
+            assert_eq!(response_key.0.len(), 20);
+            assert!(response_key.matches_prefix(&prefix));
+            assert_eq!(response_key.caller(), caller);
+            assert_eq!(response_key.idempotency_key(), idempotency_key);
+
+            let serialized = msgpack::serialize_then_unwrap(&response_key);
+            assert_eq!(serialized.len(), response_key.0.len() + 2);
+            let deserialized: IdempotentResponseKey = msgpack::deserialize_then_unwrap(&serialized);
+            assert_eq!(deserialized, response_key);
+            assert_eq!(deserialized.0, key.0);
+        }
+    }
+
+    #[test]
+    fn idempotent_response_expiry_key_e2e() {
+        for _ in 0..100 {
+            let caller_bytes: [u8; 29] = thread_rng().r#gen();
+            let caller = Principal::from_slice(&caller_bytes);
+            let created_at = thread_rng().next_u64();
+            let idempotency_key = thread_rng().next_u64();
+            let prefix = IdempotentResponseExpiryKeyPrefix::new();
+            let key = BaseKey::from(prefix.create_key(&(created_at, caller, idempotency_key)));
+            let expiry_key = IdempotentResponseExpiryKey::try_from(key.clone()).unwrap();
+
+            assert_eq!(*expiry_key.0.first().unwrap(), KeyType::IdempotentResponseExpiry as u8);
+            assert_eq!(expiry_key.0.len(), 47);
+            assert!(expiry_key.matches_prefix(&prefix));
+            assert_eq!(expiry_key.created_at(), created_at);
+            assert_eq!(expiry_key.caller(), caller);
+            assert_eq!(expiry_key.idempotency_key(), idempotency_key);
+
+            let serialized = msgpack::serialize_then_unwrap(&expiry_key);
+            assert_eq!(serialized.len(), expiry_key.0.len() + 2);
+            let deserialized: IdempotentResponseExpiryKey = msgpack::deserialize_then_unwrap(&serialized);
+            assert_eq!(deserialized, expiry_key);
+            assert_eq!(deserialized.0, key.0);
+        }
+    }
+}
@@ -9,8 +9,10 @@ use std::marker::PhantomData;
 use std::ops::{Bound, RangeBounds};
 
 mod keys;
+mod versioned;
 
 pub use keys::*;
+pub use versioned::*;
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
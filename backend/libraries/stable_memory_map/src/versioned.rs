@@ -0,0 +1,209 @@
+use crate::{BaseKey, BaseKeyPrefix, with_map_mut};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use types::TimestampMillis;
+
+// 0xc1 is never used as a msgpack marker, so values written before the envelope was introduced (which are plain
+// msgpack) can never start with it. Those values are treated as version 0.
+const ENVELOPE_MARKER: u8 = 0xc1;
+
+pub fn wrap_versioned(version: u8, payload: Vec<u8>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(payload.len() + 2);
+    bytes.push(ENVELOPE_MARKER);
+    bytes.push(version);
+    bytes.extend(payload);
+    bytes
+}
+
+pub fn unwrap_versioned(bytes: &[u8]) -> (u8, &[u8]) {
+    match bytes {
+        [ENVELOPE_MARKER, version, payload @ ..] => (*version, payload),
+        _ => (0, bytes),
+    }
+}
+
+/// Rewrites every value under a key prefix which is below `target_version`, a batch at a time, tracking its
+/// position so that it can resume where it left off, including after an upgrade. Once a migration is complete,
+/// every value under the prefix is at the target version, so any fields or aliases only needed to read older
+/// versions can be removed.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ValueMigration {
+    prefix: BaseKeyPrefix,
+    target_version: u8,
+    last_key: Option<BaseKey>,
+    values_checked: u64,
+    values_migrated: u64,
+    started: TimestampMillis,
+    completed: Option<TimestampMillis>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ValueMigrationMetrics {
+    pub target_version: u8,
+    pub values_checked: u64,
+    pub values_migrated: u64,
+    pub started: TimestampMillis,
+    pub complete: bool,
+}
+
+impl ValueMigration {
+    pub fn new(prefix: BaseKeyPrefix, target_version: u8, now: TimestampMillis) -> ValueMigration {
+        ValueMigration {
+            prefix,
+            target_version,
+            last_key: None,
+            values_checked: 0,
+            values_migrated: 0,
+            started: now,
+            completed: None,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed.is_some()
+    }
+
+    // `migrate` takes the current version of a value and its payload and returns the payload at the target version.
+    // Returns true if the migration is now complete.
+    pub fn run_batch<F: Fn(u8, &[u8]) -> Vec<u8>>(&mut self, migrate: F, batch_size: usize, now: TimestampMillis) -> bool {
+        if self.is_complete() {
+            return true;
+        }
+
+        with_map_mut(|m| {
+            let start = match self.last_key.clone() {
+                Some(key) => Bound::Excluded(key),
+                None => Bound::Included(BaseKey::from(self.prefix.clone())),
+            };
+
+            let batch: Vec<_> = m
+                .map
+                .range((start, Bound::Unbounded))
+                .take_while(|(k, _)| k.matches_prefix(&self.prefix))
+                .take(batch_size)
+                .collect();
+
+            let batch_count = batch.len();
+            for (key, bytes) in batch {
+                let (version, payload) = unwrap_versioned(&bytes);
+                if version < self.target_version {
+                    let migrated = migrate(version, payload);
+                    m.map.insert(key.clone(), wrap_versioned(self.target_version, migrated));
+                    self.values_migrated += 1;
+                }
+                self.values_checked += 1;
+                self.last_key = Some(key);
+            }
+
+            if batch_count < batch_size {
+                self.completed = Some(now);
+            }
+        });
+
+        self.is_complete()
+    }
+
+    pub fn metrics(&self) -> ValueMigrationMetrics {
+        ValueMigrationMetrics {
+            target_version: self.target_version,
+            values_checked: self.values_checked,
+            values_migrated: self.values_migrated,
+            started: self.started,
+            complete: self.is_complete(),
+        }
+    }
+}
+
+/// The set of value migrations a canister has started, keyed by name, which are persisted across upgrades.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ValueMigrations {
+    migrations: BTreeMap<String, ValueMigration>,
+}
+
+impl ValueMigrations {
+    // Starts the migration unless one with the same name and target version has already been started
+    pub fn start(&mut self, name: &str, prefix: BaseKeyPrefix, target_version: u8, now: TimestampMillis) -> bool {
+        if self.migrations.get(name).is_some_and(|m| m.target_version >= target_version) {
+            return false;
+        }
+
+        self.migrations
+            .insert(name.to_string(), ValueMigration::new(prefix, target_version, now));
+        true
+    }
+
+    pub fn is_complete(&self, name: &str) -> bool {
+        self.migrations.get(name).is_some_and(|m| m.is_complete())
+    }
+
+    pub fn has_pending(&self) -> bool {
+        self.migrations.values().any(|m| !m.is_complete())
+    }
+
+    pub fn next_pending_mut(&mut self) -> Option<(&str, &mut ValueMigration)> {
+        self.migrations
+            .iter_mut()
+            .find(|(_, m)| !m.is_complete())
+            .map(|(n, m)| (n.as_str(), m))
+    }
+
+    pub fn metrics(&self) -> BTreeMap<String, ValueMigrationMetrics> {
+        self.migrations.iter().map(|(n, m)| (n.clone(), m.metrics())).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KeyPrefix, UserIdKeyPrefix, with_map};
+    use ic_principal::Principal;
+    use ic_stable_structures::DefaultMemoryImpl;
+    use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
+
+    #[test]
+    fn envelope_round_trip() {
+        let bytes = wrap_versioned(3, vec![1, 2, 3]);
+        assert_eq!(unwrap_versioned(&bytes), (3, [1, 2, 3].as_slice()));
+
+        let legacy = msgpack::serialize_then_unwrap(("a", 1));
+        assert_eq!(unwrap_versioned(&legacy), (0, legacy.as_slice()));
+    }
+
+    #[test]
+    fn migration_resumes_from_last_key() {
+        let memory_manager = MemoryManager::init(DefaultMemoryImpl::default());
+        crate::init(memory_manager.get(MemoryId::new(1)));
+
+        let prefix = UserIdKeyPrefix::new_from_group();
+        with_map_mut(|m| {
+            for i in 0..25u8 {
+                let key = prefix.create_key(&Principal::from_slice(&[i]).into());
+                let value = if i % 5 == 0 { wrap_versioned(1, vec![i]) } else { vec![i] };
+                m.insert(key, value);
+            }
+        });
+
+        let mut migration = ValueMigration::new(prefix.clone().into(), 1, 0);
+        let migrate = |version: u8, payload: &[u8]| {
+            assert_eq!(version, 0);
+            payload.to_vec()
+        };
+
+        assert!(!migration.run_batch(migrate, 10, 1));
+        assert!(!migration.run_batch(migrate, 10, 2));
+        assert!(migration.run_batch(migrate, 10, 3));
+
+        let metrics = migration.metrics();
+        assert_eq!(metrics.values_checked, 25);
+        assert_eq!(metrics.values_migrated, 20);
+        assert!(metrics.complete);
+
+        with_map(|m| {
+            for i in 0..25u8 {
+                let bytes = m.get(prefix.create_key(&Principal::from_slice(&[i]).into())).unwrap();
+                assert_eq!(unwrap_versioned(&bytes), (1, [i].as_slice()));
+            }
+        });
+    }
+}
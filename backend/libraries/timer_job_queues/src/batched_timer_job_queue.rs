@@ -1,4 +1,4 @@
-use crate::{GroupedTimerJobQueue, TimerJobItemBatch, TimerJobItemGroup};
+use crate::{DeadLetter, GroupedTimerJobQueue, RetryPolicy, TimerJobItemBatch, TimerJobItemGroup, TimerJobQueueMetrics};
 use serde::{Deserialize, Serialize};
 
 // Use this to process batches of events (eg. sending events to the UserIndex)
@@ -34,6 +34,14 @@ impl<T: TimerJobItemBatch> BatchedTimerJobQueue<T> {
         self.0.set_defer_processing(value);
     }
 
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.0.retry_policy()
+    }
+
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.0.set_retry_policy(policy);
+    }
+
     pub fn clear(&mut self) {
         self.0.clear()
     }
@@ -49,6 +57,22 @@ impl<T: TimerJobItemBatch> BatchedTimerJobQueue<T> {
     pub fn in_progress(&self) -> usize {
         self.0.in_progress()
     }
+
+    pub fn metrics(&self) -> TimerJobQueueMetrics {
+        self.0.metrics()
+    }
+
+    pub fn take_dead_letters(&mut self) -> Vec<DeadLetter<Vec<<T as TimerJobItemGroup>::Item>>> {
+        self.0
+            .take_dead_letters()
+            .into_iter()
+            .map(|d| DeadLetter {
+                item: d.item.1,
+                attempts: d.attempts,
+                failed_at: d.failed_at,
+            })
+            .collect()
+    }
 }
 
 impl<T: TimerJobItemBatch + 'static> BatchedTimerJobQueue<T>
@@ -1,4 +1,5 @@
-use crate::TimerJobItemGroup;
+use crate::retry::{RetryState, TimestampMillis, now_millis};
+use crate::{DeadLetter, RetryPolicy, TimerJobItemGroup, TimerJobOutcome, TimerJobQueueMetrics};
 use ic_cdk_timers::TimerId;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::btree_map::Entry::{Occupied, Vacant};
@@ -36,7 +37,10 @@ impl<T: TimerJobItemGroup> GroupedTimerJobQueue<T> {
                 in_progress: BTreeSet::new(),
                 max_concurrency,
                 defer_processing,
+                retrying: BTreeMap::new(),
+                retry_state: RetryState::default(),
                 timer_id: None,
+                retry_timer: None,
             })),
             phantom: PhantomData,
         }
@@ -62,6 +66,14 @@ impl<T: TimerJobItemGroup> GroupedTimerJobQueue<T> {
         self.within_lock(|i| i.defer_processing = value)
     }
 
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.within_lock(|i| i.retry_state.policy.clone())
+    }
+
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.within_lock(|i| i.retry_state.policy = policy)
+    }
+
     pub fn clear(&mut self) {
         self.within_lock(|i| i.queue.clear())
     }
@@ -78,6 +90,20 @@ impl<T: TimerJobItemGroup> GroupedTimerJobQueue<T> {
         self.within_lock(|i| i.in_progress.len())
     }
 
+    pub fn metrics(&self) -> TimerJobQueueMetrics {
+        self.within_lock(|i| TimerJobQueueMetrics {
+            depth: i.items_map.values().map(|v| v.len()).sum(),
+            in_progress: i.in_progress.len(),
+            retrying: i.retrying.values().filter(|r| r.retry_at.is_some()).count(),
+            failures: i.retry_state.failures,
+            dead_letters: i.retry_state.dead_letters.len(),
+        })
+    }
+
+    pub fn take_dead_letters(&mut self) -> Vec<DeadLetter<(T::Key, Vec<T::Item>)>> {
+        self.within_lock(|i| i.retry_state.dead_letters.drain(..).collect())
+    }
+
     fn within_lock<F: FnOnce(&mut GroupedTimerJobQueueInner<T::SharedState, T::Key, T::Item>) -> R, R>(&self, f: F) -> R {
         let mut inner = self.inner.try_lock().unwrap();
         f(inner.deref_mut())
@@ -92,8 +118,28 @@ struct GroupedTimerJobQueueInner<S: Clone, K: Clone + Ord, I> {
     in_progress: BTreeSet<K>,
     max_concurrency: usize,
     defer_processing: bool,
+    #[serde(default)]
+    retrying: BTreeMap<K, RetryingKey>,
+    #[serde(default)]
+    retry_state: RetryState<(K, Vec<I>)>,
     #[serde(skip)]
     timer_id: Option<TimerId>,
+    #[serde(skip)]
+    retry_timer: Option<(TimerId, TimestampMillis)>,
+}
+
+impl<S: Clone, K: Clone + Ord, I> GroupedTimerJobQueueInner<S, K, I> {
+    fn is_awaiting_retry(&self, key: &K) -> bool {
+        self.retrying.get(key).is_some_and(|r| r.retry_at.is_some())
+    }
+}
+
+// Tracks keys whose last batch failed. `retry_at` is cleared once the key is due and has been re-added to the queue,
+// but the entry is kept until the key's next batch completes so that the attempt count carries over.
+#[derive(Serialize, Deserialize)]
+struct RetryingKey {
+    attempts: u32,
+    retry_at: Option<TimestampMillis>,
 }
 
 impl<T: TimerJobItemGroup + 'static> GroupedTimerJobQueue<T>
@@ -131,11 +177,26 @@ where
 
     pub fn flush(&self) {
         let mut batches = Vec::new();
+        let now = now_millis();
 
         self.within_lock(|i| {
+            for (grouping_key, retrying) in i.retrying.iter_mut() {
+                if retrying.retry_at.is_some_and(|t| t <= now) {
+                    retrying.retry_at = None;
+                    i.queue.push_front(grouping_key.clone());
+                }
+            }
+
             let max_to_start = i.max_concurrency.saturating_sub(i.in_progress.len());
             while batches.len() < max_to_start {
                 if let Some(grouping_key) = i.queue.pop_front() {
+                    // If this key is awaiting a retry, skip it, it will be re-added to the queue once due
+                    if i.is_awaiting_retry(&grouping_key) {
+                        continue;
+                    }
+
+                    let attempts = i.retrying.get(&grouping_key).map_or(0, |r| r.attempts);
+
                     if let Occupied(mut e) = i.items_map.entry(grouping_key.clone()) {
                         // If this key is already being processed, skip it
                         if !i.in_progress.insert(grouping_key.clone()) {
@@ -160,7 +221,7 @@ where
                             e.remove();
                         }
                         if !empty_batch {
-                            batches.push(batch);
+                            batches.push((batch, attempts));
                         } else {
                             i.in_progress.remove(&batch.key());
                         }
@@ -195,29 +256,77 @@ where
         }
     }
 
-    async fn process_all_batches(self, batches: Vec<T>) {
-        futures::future::join_all(batches.into_iter().map(|b| self.process_batch(b))).await;
+    // Sets a one-off timer which fires when the next key awaiting a retry is due
+    fn set_retry_timer_if_required(&self) {
+        let now = now_millis();
+        let next_retry_at = self.within_lock(|i| {
+            let next_retry_at = i.retrying.values().filter_map(|r| r.retry_at).min()?;
+            match i.retry_timer {
+                Some((_, timer_at)) if timer_at <= next_retry_at => None,
+                Some((timer_id, _)) => {
+                    ic_cdk_timers::clear_timer(timer_id);
+                    Some(next_retry_at)
+                }
+                None => Some(next_retry_at),
+            }
+        });
+
+        if let Some(retry_at) = next_retry_at {
+            let clone = self.clone();
+            let delay = Duration::from_millis(retry_at.saturating_sub(now));
+            let timer_id = ic_cdk_timers::set_timer(delay, move || {
+                clone.within_lock(|i| i.retry_timer = None);
+                clone.flush();
+                clone.set_timer_if_required();
+                clone.set_retry_timer_if_required();
+            });
+            self.within_lock(|i| i.retry_timer = Some((timer_id, retry_at)));
+        }
+    }
+
+    async fn process_all_batches(self, batches: Vec<(T, u32)>) {
+        futures::future::join_all(batches.into_iter().map(|(b, attempts)| self.process_batch(b, attempts))).await;
     }
 
-    async fn process_batch(&self, batch: T) {
-        let result = batch.process().await;
-        let retry = matches!(result, Err(true));
+    async fn process_batch(&self, batch: T, previous_attempts: u32) {
+        let outcome = batch.process().await;
         let key = batch.key();
+        let now = now_millis();
 
         self.within_lock(|i| {
             i.in_progress.remove(&key);
-            if retry {
-                let queue = i.items_map.entry(key.clone()).or_default();
-                for item in batch.into_items() {
-                    queue.push_front(item);
+            if outcome == TimerJobOutcome::Success {
+                i.retrying.remove(&key);
+            } else {
+                let attempts = previous_attempts + 1;
+                match i.retry_state.next_attempt(outcome, attempts, now) {
+                    Some(retry_at) => {
+                        let queue = i.items_map.entry(key.clone()).or_default();
+                        for item in batch.into_items().into_iter().rev() {
+                            queue.push_front(item);
+                        }
+                        i.retrying.insert(
+                            key.clone(),
+                            RetryingKey {
+                                attempts,
+                                retry_at: Some(retry_at),
+                            },
+                        );
+                    }
+                    None => {
+                        i.retrying.remove(&key);
+                        i.retry_state.dead_letter((key.clone(), batch.into_items()), attempts, now);
+                    }
                 }
             }
-            // If there are still any items in the map for this key, re-add it to the queue
-            if i.items_map.contains_key(&key) {
+            // If there are still any items in the map for this key, re-add it to the queue, unless it is awaiting a
+            // retry, in which case it will be re-added once the retry is due
+            if i.items_map.contains_key(&key) && !i.is_awaiting_retry(&key) {
                 i.queue.push_back(key);
             }
         });
         self.set_timer_if_required();
+        self.set_retry_timer_if_required();
     }
 }
 
@@ -254,6 +363,7 @@ where
             phantom: PhantomData,
         };
         value.set_timer_if_required();
+        value.set_retry_timer_if_required();
         Ok(value)
     }
 }
@@ -1,13 +1,43 @@
+use std::time::Duration;
+
 mod batched_timer_job_queue;
 mod grouped_timer_job_queue;
+mod retry;
 mod timer_job_queue;
 
 pub use batched_timer_job_queue::BatchedTimerJobQueue;
 pub use grouped_timer_job_queue::GroupedTimerJobQueue;
+pub use retry::{DeadLetter, RetryPolicy, TimerJobQueueMetrics};
 pub use timer_job_queue::TimerJobQueue;
 
 pub trait TimerJobItem {
-    fn process(&self) -> impl std::future::Future<Output = Result<(), bool>> + Send;
+    fn process(&self) -> impl std::future::Future<Output = TimerJobOutcome> + Send;
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TimerJobOutcome {
+    Success,
+    // Retry once the queue's backoff period has elapsed
+    Retry,
+    // Retry after the given delay rather than the queue's backoff period
+    RetryAfter(Duration),
+    // Give up and move the item to the queue's dead-letter list
+    Failed,
+}
+
+impl TimerJobOutcome {
+    pub fn retry_if(retry: bool) -> TimerJobOutcome {
+        if retry { TimerJobOutcome::Retry } else { TimerJobOutcome::Failed }
+    }
+}
+
+impl From<Result<(), bool>> for TimerJobOutcome {
+    fn from(value: Result<(), bool>) -> Self {
+        match value {
+            Ok(()) => TimerJobOutcome::Success,
+            Err(retry) => TimerJobOutcome::retry_if(retry),
+        }
+    }
 }
 
 pub trait TimerJobItemBatch: TimerJobItem {
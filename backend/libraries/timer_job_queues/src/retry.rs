@@ -0,0 +1,143 @@
+use crate::TimerJobOutcome;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+// Timestamps are in milliseconds since the Unix epoch
+pub type TimestampMillis = u64;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RetryPolicy {
+    // If `None`, items are retried until they succeed or return `TimerJobOutcome::Failed`
+    pub max_attempts: Option<u32>,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub max_dead_letters: usize,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: None,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10 * 60),
+            max_dead_letters: 100,
+        }
+    }
+}
+
+impl RetryPolicy {
+    // The delay doubles after each failed attempt, up to `max_backoff`
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let multiplier = 1u32.checked_shl(attempts.saturating_sub(1)).unwrap_or(u32::MAX);
+        self.initial_backoff.saturating_mul(multiplier).min(self.max_backoff)
+    }
+
+    fn attempts_exhausted(&self, attempts: u32) -> bool {
+        self.max_attempts.is_some_and(|max| attempts >= max)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DeadLetter<T> {
+    pub item: T,
+    pub attempts: u32,
+    pub failed_at: TimestampMillis,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct TimerJobQueueMetrics {
+    pub depth: usize,
+    pub in_progress: usize,
+    pub retrying: usize,
+    pub failures: u64,
+    pub dead_letters: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RetryState<T> {
+    pub policy: RetryPolicy,
+    pub dead_letters: VecDeque<DeadLetter<T>>,
+    pub failures: u64,
+}
+
+impl<T> Default for RetryState<T> {
+    fn default() -> Self {
+        RetryState {
+            policy: RetryPolicy::default(),
+            dead_letters: VecDeque::new(),
+            failures: 0,
+        }
+    }
+}
+
+impl<T> RetryState<T> {
+    // Records a failed attempt and returns when the item should next be attempted, or `None` if it should instead
+    // be moved to the dead-letter list
+    pub fn next_attempt(&mut self, outcome: TimerJobOutcome, attempts: u32, now: TimestampMillis) -> Option<TimestampMillis> {
+        self.failures += 1;
+
+        let delay = match outcome {
+            TimerJobOutcome::Retry => self.policy.backoff(attempts),
+            TimerJobOutcome::RetryAfter(delay) => delay,
+            TimerJobOutcome::Success | TimerJobOutcome::Failed => return None,
+        };
+
+        if self.policy.attempts_exhausted(attempts) {
+            None
+        } else {
+            Some(now.saturating_add(delay.as_millis() as u64))
+        }
+    }
+
+    pub fn dead_letter(&mut self, item: T, attempts: u32, now: TimestampMillis) {
+        self.dead_letters.push_back(DeadLetter {
+            item,
+            attempts,
+            failed_at: now,
+        });
+        while self.dead_letters.len() > self.policy.max_dead_letters {
+            self.dead_letters.pop_front();
+        }
+    }
+}
+
+pub(crate) fn now_millis() -> TimestampMillis {
+    ic_cdk::api::time() / 1_000_000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(5), Duration::from_secs(16));
+        assert_eq!(policy.backoff(50), policy.max_backoff);
+    }
+
+    #[test]
+    fn items_are_dead_lettered_once_attempts_exhausted() {
+        let mut state = RetryState::default();
+        state.policy.max_attempts = Some(3);
+        state.policy.max_dead_letters = 1;
+
+        assert_eq!(state.next_attempt(TimerJobOutcome::Retry, 1, 1000), Some(2000));
+        assert_eq!(
+            state.next_attempt(TimerJobOutcome::RetryAfter(Duration::from_millis(10)), 2, 1000),
+            Some(1010)
+        );
+        assert_eq!(state.next_attempt(TimerJobOutcome::Retry, 3, 1000), None);
+        assert_eq!(state.next_attempt(TimerJobOutcome::Failed, 1, 1000), None);
+        assert_eq!(state.failures, 4);
+
+        state.dead_letter(1, 3, 1000);
+        state.dead_letter(2, 1, 1000);
+        assert_eq!(state.dead_letters.len(), 1);
+        assert_eq!(state.dead_letters[0].item, 2);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::TimerJobItem;
+use crate::retry::{RetryState, TimestampMillis, now_millis};
+use crate::{DeadLetter, RetryPolicy, TimerJobItem, TimerJobOutcome, TimerJobQueueMetrics};
 use ic_cdk_timers::TimerId;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::VecDeque;
@@ -19,7 +20,10 @@ impl<T> TimerJobQueue<T> {
                 in_progress: 0,
                 max_concurrency,
                 defer_processing,
+                retrying: Vec::new(),
+                retry_state: RetryState::default(),
                 timer_id: None,
+                retry_timer: None,
             })),
         }
     }
@@ -40,8 +44,19 @@ impl<T> TimerJobQueue<T> {
         self.within_lock(|i| i.defer_processing = value)
     }
 
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.within_lock(|i| i.retry_state.policy.clone())
+    }
+
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.within_lock(|i| i.retry_state.policy = policy)
+    }
+
     pub fn clear(&mut self) {
-        self.within_lock(|i| i.queue.clear())
+        self.within_lock(|i| {
+            i.queue.clear();
+            i.retrying.clear();
+        })
     }
 
     pub fn len(&self) -> usize {
@@ -56,6 +71,20 @@ impl<T> TimerJobQueue<T> {
         self.within_lock(|i| i.in_progress)
     }
 
+    pub fn metrics(&self) -> TimerJobQueueMetrics {
+        self.within_lock(|i| TimerJobQueueMetrics {
+            depth: i.queue.len(),
+            in_progress: i.in_progress,
+            retrying: i.retrying.len(),
+            failures: i.retry_state.failures,
+            dead_letters: i.retry_state.dead_letters.len(),
+        })
+    }
+
+    pub fn take_dead_letters(&mut self) -> Vec<DeadLetter<T>> {
+        self.within_lock(|i| i.retry_state.dead_letters.drain(..).collect())
+    }
+
     fn within_lock<F: FnOnce(&mut TimerJobQueueInner<T>) -> R, R>(&self, f: F) -> R {
         let mut inner = self.inner.try_lock().unwrap();
         f(inner.deref_mut())
@@ -68,8 +97,27 @@ struct TimerJobQueueInner<T> {
     in_progress: usize,
     max_concurrency: usize,
     defer_processing: bool,
+    #[serde(default)]
+    retrying: Vec<RetryingItem<T>>,
+    #[serde(default)]
+    retry_state: RetryState<T>,
     #[serde(skip)]
     timer_id: Option<TimerId>,
+    #[serde(skip)]
+    retry_timer: Option<(TimerId, TimestampMillis)>,
+}
+
+impl<T> TimerJobQueueInner<T> {
+    fn has_work_ready(&self, now: TimestampMillis) -> bool {
+        !self.queue.is_empty() || self.retrying.iter().any(|r| r.retry_at <= now)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RetryingItem<T> {
+    item: T,
+    attempts: u32,
+    retry_at: TimestampMillis,
 }
 
 impl<T> TimerJobQueue<T>
@@ -106,7 +154,8 @@ where
     }
 
     fn set_timer_if_required(&self) -> bool {
-        let should_set_timer = self.within_lock(|i| i.timer_id.is_none() && !i.queue.is_empty());
+        let now = now_millis();
+        let should_set_timer = self.within_lock(|i| i.timer_id.is_none() && i.has_work_ready(now));
         if should_set_timer {
             let clone = self.clone();
             let timer_id = ic_cdk_timers::set_timer_interval(Duration::ZERO, move || clone.run());
@@ -117,14 +166,53 @@ where
         }
     }
 
+    // Sets a one-off timer which fires when the next item awaiting a retry is due. Items which are already due are
+    // picked up by the regular timer.
+    fn set_retry_timer_if_required(&self) {
+        let now = now_millis();
+        let next_retry_at = self.within_lock(|i| {
+            let next_retry_at = i.retrying.iter().map(|r| r.retry_at).filter(|t| *t > now).min()?;
+            match i.retry_timer {
+                Some((_, timer_at)) if timer_at <= next_retry_at => None,
+                Some((timer_id, _)) => {
+                    ic_cdk_timers::clear_timer(timer_id);
+                    Some(next_retry_at)
+                }
+                None => Some(next_retry_at),
+            }
+        });
+
+        if let Some(retry_at) = next_retry_at {
+            let clone = self.clone();
+            let delay = Duration::from_millis(retry_at.saturating_sub(now));
+            let timer_id = ic_cdk_timers::set_timer(delay, move || {
+                clone.within_lock(|i| i.retry_timer = None);
+                clone.run();
+                clone.set_timer_if_required();
+                clone.set_retry_timer_if_required();
+            });
+            self.within_lock(|i| i.retry_timer = Some((timer_id, retry_at)));
+        }
+    }
+
     fn run(&self) {
         let mut items = Vec::new();
+        let now = now_millis();
 
         self.within_lock(|i| {
             let max_to_start = i.max_concurrency.saturating_sub(i.in_progress);
+            // Items whose retry is due take priority over new items
+            while items.len() < max_to_start {
+                if let Some(index) = i.retrying.iter().position(|r| r.retry_at <= now) {
+                    let retrying = i.retrying.remove(index);
+                    items.push((retrying.item, retrying.attempts));
+                } else {
+                    break;
+                }
+            }
             while items.len() < max_to_start {
                 if let Some(item) = i.queue.pop_front() {
-                    items.push(item);
+                    items.push((item, 0));
                 } else {
                     break;
                 }
@@ -132,7 +220,7 @@ where
             let count = items.len();
             i.in_progress = i.in_progress.saturating_add(count);
 
-            if i.queue.is_empty() {
+            if !i.has_work_ready(now) {
                 if let Some(timer_id) = i.timer_id.take() {
                     ic_cdk_timers::clear_timer(timer_id);
                 }
@@ -145,21 +233,30 @@ where
         }
     }
 
-    async fn process_batch(self, batch: Vec<T>) {
-        futures::future::join_all(batch.into_iter().map(|i| self.process_single(i))).await;
+    async fn process_batch(self, batch: Vec<(T, u32)>) {
+        futures::future::join_all(batch.into_iter().map(|(i, attempts)| self.process_single(i, attempts))).await;
     }
 
-    async fn process_single(&self, item: T) {
-        let result = item.process().await;
-        let retry = matches!(result, Err(true));
+    async fn process_single(&self, item: T, previous_attempts: u32) {
+        let outcome = item.process().await;
+        let now = now_millis();
 
         self.within_lock(|i| {
-            if retry {
-                i.queue.push_front(item);
+            if outcome != TimerJobOutcome::Success {
+                let attempts = previous_attempts + 1;
+                match i.retry_state.next_attempt(outcome, attempts, now) {
+                    Some(retry_at) => i.retrying.push(RetryingItem {
+                        item,
+                        attempts,
+                        retry_at,
+                    }),
+                    None => i.retry_state.dead_letter(item, attempts, now),
+                }
             }
             i.in_progress = i.in_progress.saturating_sub(1);
         });
         self.set_timer_if_required();
+        self.set_retry_timer_if_required();
     }
 }
 
@@ -184,6 +281,7 @@ impl<'de, T: Deserialize<'de> + TimerJobItem + 'static> Deserialize<'de> for Tim
             inner: Rc::new(Mutex::new(inner)),
         };
         value.set_timer_if_required();
+        value.set_retry_timer_if_required();
         Ok(value)
     }
 }
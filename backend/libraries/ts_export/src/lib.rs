@@ -1,5 +1,12 @@
+use schemars::JsonSchema;
+use schemars::r#gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{InstanceType, Schema, SchemaObject};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
 
+pub use schemars;
 pub use ts_export_macros::generate_ts_method;
 pub use ts_export_macros::ts_export;
 
@@ -37,3 +44,60 @@ pub struct TSBoolWithDefault {}
 pub fn is_default<T: Default + PartialEq>(t: &T) -> bool {
     *t == Default::default()
 }
+
+// Writes the JSON schemas of a method's Args and Response types, along with every type they reference, to
+// `{dir}/schema.json`. The `encodings` are those which the method is exposed with in addition to Candid (eg.
+// "msgpack" and "json"). These files are combined into per canister schemas by the `json_schema_exporter`.
+pub fn export_json_schema<A: JsonSchema, R: JsonSchema>(dir: impl AsRef<Path>, encodings: &[&str]) -> std::io::Result<()> {
+    let mut generator = SchemaSettings::draft2019_09().into_generator();
+    let args = generator.subschema_for::<A>();
+    let response = generator.subschema_for::<R>();
+    let definitions = generator.take_definitions();
+
+    let value = json!({
+        "encodings": encodings,
+        "args": args,
+        "response": response,
+        "definitions": definitions,
+    });
+
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join("schema.json"), serde_json::to_string_pretty(&value)? + "\n")
+}
+
+// The types above are only used to override how other types are represented in TS, so rather than deriving
+// `JsonSchema` for them (which would describe an empty struct), we describe what they actually serialize to.
+macro_rules! json_schema {
+    ($name:ident, $instance_type:expr, $format:expr) => {
+        impl JsonSchema for $name {
+            fn schema_name() -> String {
+                stringify!($name).to_string()
+            }
+
+            fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                SchemaObject {
+                    instance_type: Some($instance_type.into()),
+                    format: $format.map(String::from),
+                    ..Default::default()
+                }
+                .into()
+            }
+        }
+    };
+}
+
+json_schema!(TSPrincipal, InstanceType::String, Some("principal"));
+json_schema!(TSNumberWithDefault, InstanceType::Number, None::<&str>);
+json_schema!(TSBigIntWithDefault, InstanceType::Integer, None::<&str>);
+json_schema!(TSBoolWithDefault, InstanceType::Boolean, None::<&str>);
+
+impl JsonSchema for TSBytes {
+    fn schema_name() -> String {
+        "TSBytes".to_string()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <Vec<u8>>::json_schema(generator)
+    }
+}
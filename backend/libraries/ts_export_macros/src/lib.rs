@@ -2,11 +2,15 @@ use proc_macro::TokenStream;
 use quote::{ToTokens, format_ident, quote};
 use std::fmt::Write;
 use syn::punctuated::Punctuated;
-use syn::{Attribute, Field, Fields, Ident, Item, PathSegment, Token, Type, parse_macro_input, parse_quote};
+use syn::{
+    Attribute, Field, Fields, GenericArgument, Generics, Ident, Item, Lit, PathArguments, PathSegment, Token, Type,
+    parse_macro_input, parse_quote,
+};
 
 struct MethodAttribute {
     canister_name: String,
     method_name: String,
+    encodings: Vec<String>,
 }
 
 #[proc_macro_attribute]
@@ -30,34 +34,70 @@ pub fn ts_export(attr: TokenStream, item: TokenStream) -> TokenStream {
         ("shared/".to_string(), None)
     };
 
-    match &mut item {
+    let json_schema = match &mut item {
         Item::Struct(s) => {
             insert_container_attributes(&mut s.attrs, &s.ident, export_to, prefix);
+            let json_schema = insert_json_schema_attributes(&mut s.attrs, &s.ident, &s.generics);
             let derives_serde = any_derives_serde(&s.attrs);
 
             let is_tuple = matches!(s.fields, Fields::Unnamed(_));
             for field in s.fields.iter_mut() {
                 insert_field_attributes(field, is_tuple, derives_serde);
+                if matches!(json_schema, JsonSchema::Derived) {
+                    insert_json_schema_field_attributes(field);
+                }
             }
+            json_schema
         }
         Item::Enum(e) => {
             insert_container_attributes(&mut e.attrs, &e.ident, export_to, prefix);
+            let json_schema = insert_json_schema_attributes(&mut e.attrs, &e.ident, &e.generics);
             let derives_serde = any_derives_serde(&e.attrs);
 
             for variant in e.variants.iter_mut() {
                 for field in variant.fields.iter_mut() {
                     insert_field_attributes(field, true, derives_serde);
+                    if matches!(json_schema, JsonSchema::Derived) {
+                        insert_json_schema_field_attributes(field);
+                    }
                 }
             }
+            json_schema
         }
         _ => unimplemented!(),
-    }
+    };
+
+    let json_schema_impl = match json_schema {
+        JsonSchema::DelegatedTo(ident, as_type) => quote! {
+            impl ::ts_export::schemars::JsonSchema for #ident {
+                fn schema_name() -> String {
+                    <#as_type as ::ts_export::schemars::JsonSchema>::schema_name()
+                }
+
+                fn is_referenceable() -> bool {
+                    <#as_type as ::ts_export::schemars::JsonSchema>::is_referenceable()
+                }
+
+                fn json_schema(
+                    generator: &mut ::ts_export::schemars::r#gen::SchemaGenerator,
+                ) -> ::ts_export::schemars::schema::Schema {
+                    <#as_type as ::ts_export::schemars::JsonSchema>::json_schema(generator)
+                }
+            }
+        },
+        _ => quote! {},
+    };
 
     TokenStream::from(quote! {
         #item
+        #json_schema_impl
     })
 }
 
+// Exports the TS bindings and the JSON schema for a method's Args and Response types. Any additional arguments
+// specify the encodings the method is exposed with (other than Candid), these default to `msgpack`, eg.
+// `generate_ts_method!(local_user_index, bot_send_message, msgpack, json)`. Use `candid` for methods which are
+// only exposed via Candid.
 #[proc_macro]
 pub fn generate_ts_method(input: TokenStream) -> TokenStream {
     let inputs = parse_macro_input!(input with Punctuated::<Ident, Token![,]>::parse_terminated)
@@ -69,10 +109,21 @@ pub fn generate_ts_method(input: TokenStream) -> TokenStream {
 
     let canister_module = format_ident!("{}_canister", attribute.canister_name);
     let method_name = format_ident!("{}", attribute.method_name);
+    let schema_dir = format!(
+        "tsBindings/{}/{}",
+        convert_case(&attribute.canister_name, false),
+        convert_case(&attribute.method_name, false)
+    );
+    let encodings = attribute.encodings;
 
     let tokens = quote! {
         <#canister_module::#method_name::Args as ::ts_rs::TS>::export_all_to("tsBindings").unwrap();
         <#canister_module::#method_name::Response as ::ts_rs::TS>::export_all_to("tsBindings").unwrap();
+        ::ts_export::export_json_schema::<#canister_module::#method_name::Args, #canister_module::#method_name::Response>(
+            #schema_dir,
+            &[#(#encodings),*],
+        )
+        .unwrap();
     };
 
     TokenStream::from(tokens)
@@ -93,6 +144,133 @@ fn insert_container_attributes(attrs: &mut Vec<Attribute>, ident: &Ident, export
     }
 }
 
+enum JsonSchema {
+    Derived,
+    DelegatedTo(Ident, Type),
+    Skipped,
+}
+
+// Derives `JsonSchema` using the same name as the TS type. If the TS type is overridden at the container level,
+// then the JSON schema is also taken from the overriding type.
+fn insert_json_schema_attributes(attrs: &mut Vec<Attribute>, ident: &Ident, generics: &Generics) -> JsonSchema {
+    if find_ts_attribute(attrs, "type").is_some() {
+        return JsonSchema::Skipped;
+    }
+    if let Some(Some(as_type)) = find_ts_attribute(attrs, "as") {
+        return JsonSchema::DelegatedTo(ident.clone(), syn::parse_str(&as_type).unwrap());
+    }
+
+    attrs.insert(0, parse_quote!( #[derive(::ts_export::schemars::JsonSchema)] ));
+    attrs.insert(1, parse_quote!( #[schemars(crate = "::ts_export::schemars")] ));
+    if generics.params.is_empty() {
+        if let Some(Some(rename)) = find_ts_attribute(attrs, "rename") {
+            attrs.insert(2, parse_quote!( #[schemars(rename = #rename)] ));
+        }
+    }
+    JsonSchema::Derived
+}
+
+// Mirrors the field level TS overrides, so that the JSON schema matches the TS type
+fn insert_json_schema_field_attributes(field: &mut Field) {
+    if find_ts_attribute(&field.attrs, "skip").is_some() {
+        field.attrs.push(parse_quote!( #[schemars(skip)] ));
+        return;
+    }
+
+    let with = if let Some(Some(as_type)) = find_ts_attribute(&field.attrs, "as") {
+        as_type
+    } else if field.attrs.iter().any(is_using_serde_bytes) {
+        if is_option(&field.ty) {
+            "Option<::ts_export::TSBytes>".to_string()
+        } else {
+            "::ts_export::TSBytes".to_string()
+        }
+    } else {
+        let mut ty = field.ty.clone();
+        if !replace_principals(&mut ty) {
+            return;
+        }
+        ty.into_token_stream().to_string()
+    };
+    field.attrs.push(parse_quote!( #[schemars(with = #with)] ));
+}
+
+// Replaces any principals nested within the type (eg. `Vec<CanisterId>`) with `TSPrincipal`, returning true if any
+// were replaced
+fn replace_principals(ty: &mut Type) -> bool {
+    if is_principal(ty) {
+        *ty = parse_quote!(::ts_export::TSPrincipal);
+        return true;
+    }
+    match ty {
+        Type::Path(type_path) => {
+            let mut replaced = false;
+            for segment in type_path.path.segments.iter_mut() {
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    for arg in args.args.iter_mut() {
+                        if let GenericArgument::Type(t) = arg {
+                            replaced = replace_principals(t) || replaced;
+                        }
+                    }
+                }
+            }
+            replaced
+        }
+        Type::Tuple(tuple) => tuple
+            .elems
+            .iter_mut()
+            .fold(false, |replaced, t| replace_principals(t) || replaced),
+        Type::Array(array) => replace_principals(&mut array.elem),
+        Type::Slice(slice) => replace_principals(&mut slice.elem),
+        Type::Reference(reference) => replace_principals(&mut reference.elem),
+        Type::Paren(paren) => replace_principals(&mut paren.elem),
+        Type::Group(group) => replace_principals(&mut group.elem),
+        _ => false,
+    }
+}
+
+fn is_principal(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty {
+        if type_path.qself.is_none() {
+            if let Some(last) = type_path.path.segments.last() {
+                return last.arguments.is_empty() && PRINCIPAL_ALIASES.iter().any(|a| last.ident == a);
+            }
+        }
+    }
+    false
+}
+
+fn is_option(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty {
+        type_path.path.segments.last().is_some_and(|s| s.ident == "Option")
+    } else {
+        false
+    }
+}
+
+// Returns `Some(Some(value))` for `#[ts(key = "value")]`, `Some(None)` for `#[ts(key)]`, otherwise `None`
+fn find_ts_attribute(attrs: &[Attribute], key: &str) -> Option<Option<String>> {
+    let mut result = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("ts")) {
+        attr.parse_nested_meta(|meta| {
+            let value = if meta.input.peek(Token![=]) {
+                match meta.value()?.parse()? {
+                    Lit::Str(s) => Some(s.value()),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            if meta.path.is_ident(key) {
+                result = Some(value);
+            }
+            Ok(())
+        })
+        .unwrap();
+    }
+    result
+}
+
 const PRINCIPAL_ALIASES: [&str; 3] = ["Principal", "CanisterId", "AccessorId"];
 
 fn insert_field_attributes(field: &mut Field, is_tuple: bool, derives_serde: bool) {
@@ -220,10 +398,16 @@ fn get_method_attribute(inputs: Vec<String>) -> MethodAttribute {
 
     let canister_name = first_arg.to_string();
     let method_name = second_arg.to_string();
+    let encodings = match &inputs[2..] {
+        [] => vec!["msgpack".to_string()],
+        [candid] if candid == "candid" => Vec::new(),
+        encodings => encodings.to_vec(),
+    };
 
     MethodAttribute {
         canister_name,
         method_name,
+        encodings,
     }
 }
 
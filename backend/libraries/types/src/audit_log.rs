@@ -0,0 +1,71 @@
+use crate::{
+    ChannelId, CommunityRole, GroupRole, MessageId, MessageIndex, Milliseconds, ModerationAction, TimestampMillis, UserId,
+};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+
+pub const MAX_AUDIT_LOG_REASON_LENGTH: usize = 500;
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AuditLogEntry {
+    pub index: u32,
+    pub channel_id: Option<ChannelId>,
+    pub actor: UserId,
+    pub target: Option<UserId>,
+    pub action: AuditLogAction,
+    pub reason: Option<String>,
+    pub timestamp: TimestampMillis,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum AuditLogAction {
+    GroupRoleChanged(GroupRole),
+    CommunityRoleChanged(CommunityRole),
+    MessageDeleted(AuditLogMessage),
+    MessageUndeleted(AuditLogMessage),
+    MessagePinned(MessageIndex),
+    MessageUnpinned(MessageIndex),
+    MemberRemoved,
+    UserBlocked(Option<TimestampMillis>),
+    UserUnblocked,
+    MemberTimedOut(Option<TimestampMillis>),
+    DetailsUpdated,
+    RulesUpdated,
+    PermissionsUpdated,
+    AutomodRulesUpdated,
+    HeldMessageReviewed(AuditLogHeldMessageReview),
+    ReportResolved(AuditLogReportResolution),
+    RetentionUpdated(AuditLogRetention),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuditLogMessage {
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuditLogHeldMessageReview {
+    pub message: AuditLogMessage,
+    pub released: bool,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuditLogReportResolution {
+    pub report_id: u32,
+    pub action: ModerationAction,
+}
+
+// Entries are pruned once either limit is exceeded
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AuditLogRetention {
+    pub max_entries: Option<u32>,
+    pub max_age: Option<Milliseconds>,
+}
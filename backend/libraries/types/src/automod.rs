@@ -0,0 +1,86 @@
+use crate::{MessageId, MessageIndex, Milliseconds, TimestampMillis, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+
+pub const MAX_AUTOMOD_RULES: usize = 25;
+pub const MAX_AUTOMOD_PATTERNS_PER_RULE: usize = 100;
+pub const MAX_AUTOMOD_PATTERN_LENGTH: usize = 200;
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AutomodRule {
+    pub id: u32,
+    pub name: String,
+    pub enabled: bool,
+    pub condition: AutomodCondition,
+    pub action: AutomodAction,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum AutomodCondition {
+    // Case-insensitive match on any of the keywords as whole words
+    Keywords(Vec<String>),
+    Regex(Vec<String>),
+    Links(AutomodLinkFilter),
+    // Triggered if a message mentions more than this many users
+    MentionSpam(u32),
+    RepeatedMessages(AutomodRepeatedMessages),
+    // Triggered if the sender joined less than this long ago
+    NewMembers(Milliseconds),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AutomodLinkFilter {
+    // If not empty, links to any other domain trigger the rule
+    pub allowed_domains: Vec<String>,
+    pub blocked_domains: Vec<String>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct AutomodRepeatedMessages {
+    pub max_repeats: u32,
+    pub window: Milliseconds,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AutomodAction {
+    Block,
+    HoldForReview,
+    Delete,
+    Timeout(Milliseconds),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AutomodTriggered {
+    pub rule_id: u32,
+    pub user_id: UserId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub action: AutomodAction,
+    pub timestamp: TimestampMillis,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct HeldMessage {
+    pub rule_id: u32,
+    pub sender: UserId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub timestamp: TimestampMillis,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AutomodDetails {
+    pub rules: Vec<AutomodRule>,
+    pub held_messages: Vec<HeldMessage>,
+    pub triggered: Vec<AutomodTriggered>,
+    pub last_updated: TimestampMillis,
+}
@@ -0,0 +1,204 @@
+use crate::{BotCommandArg, BotCommandMeta, BotCommandParamType, BotPermissions, GroupRole, MessageId, MessageIndex, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use ts_export::ts_export;
+
+const MAX_COMPONENTS_PER_MESSAGE: usize = 10;
+const MAX_COMPONENT_ID_LENGTH: usize = 100;
+const MAX_LABEL_LENGTH: usize = 100;
+const MAX_SELECT_OPTIONS: usize = 25;
+const MAX_FORM_FIELDS: usize = 10;
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub enum BotMessageComponent {
+    Button(BotButton),
+    Select(BotSelect),
+    Form(BotForm),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotButton {
+    pub id: String,
+    pub label: String,
+    pub style: BotButtonStyle,
+    pub permissions: BotPermissions,
+    pub default_role: Option<GroupRole>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotButtonStyle {
+    Primary,
+    Secondary,
+    Danger,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotSelect {
+    pub id: String,
+    pub placeholder: Option<String>,
+    pub options: Vec<BotSelectOption>,
+    pub min_values: u8,
+    pub max_values: u8,
+    pub permissions: BotPermissions,
+    pub default_role: Option<GroupRole>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotSelectOption {
+    pub value: String,
+    pub label: String,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotForm {
+    pub id: String,
+    pub title: String,
+    pub fields: Vec<BotFormField>,
+    pub submit_label: String,
+    pub permissions: BotPermissions,
+    pub default_role: Option<GroupRole>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotFormField {
+    pub name: String,
+    pub label: String,
+    pub required: bool,
+    pub field_type: BotCommandParamType,
+}
+
+// The value submitted when a user interacts with a component
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum BotComponentValue {
+    Button,
+    Select(Vec<String>),
+    Form(Vec<BotCommandArg>),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotComponentInteraction {
+    pub component_id: String,
+    pub thread: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub value: BotComponentValue,
+    pub initiator: UserId,
+    pub meta: Option<BotCommandMeta>,
+}
+
+impl BotMessageComponent {
+    pub fn id(&self) -> &str {
+        match self {
+            BotMessageComponent::Button(b) => &b.id,
+            BotMessageComponent::Select(s) => &s.id,
+            BotMessageComponent::Form(f) => &f.id,
+        }
+    }
+
+    pub fn permissions(&self) -> &BotPermissions {
+        match self {
+            BotMessageComponent::Button(b) => &b.permissions,
+            BotMessageComponent::Select(s) => &s.permissions,
+            BotMessageComponent::Form(f) => &f.permissions,
+        }
+    }
+
+    pub fn default_role(&self) -> Option<GroupRole> {
+        match self {
+            BotMessageComponent::Button(b) => b.default_role,
+            BotMessageComponent::Select(s) => s.default_role,
+            BotMessageComponent::Form(f) => f.default_role,
+        }
+    }
+
+    pub fn is_valid_value(&self, value: &BotComponentValue) -> bool {
+        match (self, value) {
+            (BotMessageComponent::Button(_), BotComponentValue::Button) => true,
+            (BotMessageComponent::Select(select), BotComponentValue::Select(values)) => {
+                let count = values.len();
+                let unique: HashSet<_> = values.iter().collect();
+                unique.len() == count
+                    && count >= select.min_values as usize
+                    && count <= select.max_values as usize
+                    && values.iter().all(|v| select.options.iter().any(|o| &o.value == v))
+            }
+            (BotMessageComponent::Form(form), BotComponentValue::Form(args)) => {
+                args.iter().all(|a| form.fields.iter().any(|f| f.name == a.name))
+                    && form
+                        .fields
+                        .iter()
+                        .all(|field| match args.iter().find(|a| a.name == field.name) {
+                            Some(arg) => field.field_type.is_valid_value(&arg.value),
+                            None => !field.required,
+                        })
+            }
+            _ => false,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let id = self.id();
+        if id.is_empty() || id.len() > MAX_COMPONENT_ID_LENGTH {
+            return Err(format!(
+                "Component id must be between 1 and {MAX_COMPONENT_ID_LENGTH} characters"
+            ));
+        }
+
+        let labels: Vec<&str> = match self {
+            BotMessageComponent::Button(button) => vec![button.label.as_str()],
+            BotMessageComponent::Select(select) => {
+                if select.options.is_empty() || select.options.len() > MAX_SELECT_OPTIONS {
+                    return Err(format!("Select must have between 1 and {MAX_SELECT_OPTIONS} options"));
+                }
+                if select.min_values > select.max_values || select.max_values as usize > select.options.len() {
+                    return Err("Select min/max values are invalid".to_string());
+                }
+                let unique: HashSet<_> = select.options.iter().map(|o| &o.value).collect();
+                if unique.len() != select.options.len() {
+                    return Err("Select option values must be unique".to_string());
+                }
+                select.options.iter().map(|o| o.label.as_str()).collect()
+            }
+            BotMessageComponent::Form(form) => {
+                if form.fields.is_empty() || form.fields.len() > MAX_FORM_FIELDS {
+                    return Err(format!("Form must have between 1 and {MAX_FORM_FIELDS} fields"));
+                }
+                let unique: HashSet<_> = form.fields.iter().map(|f| &f.name).collect();
+                if unique.len() != form.fields.len() {
+                    return Err("Form field names must be unique".to_string());
+                }
+                let mut labels = vec![form.title.as_str(), form.submit_label.as_str()];
+                labels.extend(form.fields.iter().map(|f| f.label.as_str()));
+                labels
+            }
+        };
+
+        if labels.iter().any(|l| l.is_empty() || l.chars().count() > MAX_LABEL_LENGTH) {
+            return Err(format!("Labels must be between 1 and {MAX_LABEL_LENGTH} characters"));
+        }
+
+        Ok(())
+    }
+}
+
+pub fn validate_bot_message_components(components: &[BotMessageComponent]) -> Result<(), String> {
+    if components.len() > MAX_COMPONENTS_PER_MESSAGE {
+        return Err(format!("A message can have at most {MAX_COMPONENTS_PER_MESSAGE} components"));
+    }
+
+    let unique: HashSet<_> = components.iter().map(|c| c.id()).collect();
+    if unique.len() != components.len() {
+        return Err("Component ids must be unique".to_string());
+    }
+
+    components.iter().try_for_each(|c| c.validate())
+}
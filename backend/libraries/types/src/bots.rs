@@ -1,8 +1,9 @@
 use crate::bitflags::{decode_from_bitflags, encode_as_bitflags};
 use crate::{
-    AudioContent, CanisterId, Chat, ChatEventCategory, ChatEventType, ChatId, ChatPermission, CommunityEventCategory,
-    CommunityEventType, CommunityId, CommunityOrGroup, CommunityPermission, FileContent, GiphyContent, GroupRole, ImageContent,
-    MessageContentInitial, MessageId, MessagePermission, PollContent, TextContent, TimestampMillis, UserId, VideoContent,
+    AudioContent, BlobReference, BotComponentInteraction, CanisterId, ChannelId, Chat, ChatEventCategory, ChatEventType,
+    ChatId, ChatPermission, CommunityEventCategory, CommunityEventType, CommunityId, CommunityOrGroup, CommunityPermission,
+    FileContent, GiphyContent, GroupRole, ImageContent, MessageContentInitial, MessageId, MessageIndex, MessagePermission,
+    Milliseconds, PollContent, TextContent, TimestampMillis, UserId, VideoContent,
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -30,6 +31,67 @@ pub struct BotCommandDefinition {
     pub permissions: BotPermissions,
     pub default_role: Option<GroupRole>,
     pub direct_messages: Option<bool>,
+    // Path, relative to the bot's endpoint, which the client calls to fetch choices for params marked as `autocomplete`
+    pub autocomplete_endpoint: Option<String>,
+}
+
+const MAX_PARAM_VALUES: u8 = 20;
+const MAX_AUTOCOMPLETE_ENDPOINT_LENGTH: usize = 200;
+
+impl BotCommandDefinition {
+    // Checks that each arg matches one of the command's params and that every required param has a value
+    pub fn are_valid_args(&self, args: &[BotCommandArg]) -> bool {
+        let names: HashSet<_> = args.iter().map(|a| a.name.as_str()).collect();
+
+        names.len() == args.len()
+            && args
+                .iter()
+                .all(|a| self.params.iter().any(|p| p.name == a.name && p.is_valid_value(&a.value)))
+            && self
+                .params
+                .iter()
+                .filter(|p| p.required)
+                .all(|p| names.contains(p.name.as_str()))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(endpoint) = &self.autocomplete_endpoint {
+            if !endpoint.starts_with('/') || endpoint.len() > MAX_AUTOCOMPLETE_ENDPOINT_LENGTH {
+                return Err(format!("Command '{}' has an invalid autocomplete endpoint", self.name));
+            }
+        }
+
+        for param in self.params.iter() {
+            if param.max_values.is_some_and(|m| m == 0 || m > MAX_PARAM_VALUES) {
+                return Err(format!(
+                    "Param '{}' of command '{}' must accept between 1 and {MAX_PARAM_VALUES} values",
+                    param.name, self.name
+                ));
+            }
+            if param.autocomplete.unwrap_or_default() && self.autocomplete_endpoint.is_none() {
+                return Err(format!(
+                    "Param '{}' of command '{}' uses autocomplete but the command has no autocomplete endpoint",
+                    param.name, self.name
+                ));
+            }
+            if let BotCommandParamType::TokenAmountParam(p) = &param.param_type {
+                if let (Some(min), Some(max)) = (p.min_amount, p.max_amount) {
+                    if min > max {
+                        return Err(format!(
+                            "Param '{}' of command '{}' has min_amount > max_amount",
+                            param.name, self.name
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn validate_bot_commands(commands: &[BotCommandDefinition]) -> Result<(), String> {
+    commands.iter().try_for_each(|c| c.validate())
 }
 
 #[ts_export]
@@ -46,6 +108,22 @@ pub struct BotCommandParam {
     pub placeholder: Option<String>,
     pub required: bool,
     pub param_type: BotCommandParamType,
+    // If set, the param accepts up to this many values, passed as `BotCommandArgValue::Multiple`
+    pub max_values: Option<u8>,
+    pub autocomplete: Option<bool>,
+}
+
+impl BotCommandParam {
+    pub fn is_valid_value(&self, value: &BotCommandArgValue) -> bool {
+        match (self.max_values, value) {
+            (Some(max_values), BotCommandArgValue::Multiple(values)) => {
+                !values.is_empty()
+                    && values.len() <= max_values as usize
+                    && values.iter().all(|v| self.param_type.is_valid_value(v))
+            }
+            (_, value) => self.param_type.is_valid_value(value),
+        }
+    }
 }
 
 #[ts_export]
@@ -57,6 +135,45 @@ pub enum BotCommandParamType {
     IntegerParam(IntegerParam),
     DecimalParam(DecimalParam),
     DateTimeParam(DateTimeParam),
+    ChatParam,
+    MessageParam,
+    FileParam(FileParam),
+    TokenAmountParam(TokenAmountParam),
+}
+
+impl BotCommandParamType {
+    pub fn is_valid_value(&self, value: &BotCommandArgValue) -> bool {
+        match (self, value) {
+            (BotCommandParamType::UserParam, BotCommandArgValue::User(_)) => true,
+            (BotCommandParamType::BooleanParam, BotCommandArgValue::Boolean(_)) => true,
+            (BotCommandParamType::StringParam(p), BotCommandArgValue::String(s)) => {
+                let length = s.chars().count();
+                length >= p.min_length as usize
+                    && length <= p.max_length as usize
+                    && (p.choices.is_empty() || p.choices.iter().any(|c| &c.value == s))
+            }
+            (BotCommandParamType::IntegerParam(p), BotCommandArgValue::Integer(i)) => {
+                let i = *i as i128;
+                i >= p.min_value && i <= p.max_value && (p.choices.is_empty() || p.choices.iter().any(|c| c.value == i))
+            }
+            (BotCommandParamType::DecimalParam(p), BotCommandArgValue::Decimal(d)) => {
+                *d >= p.min_value && *d <= p.max_value && (p.choices.is_empty() || p.choices.iter().any(|c| c.value == *d))
+            }
+            (BotCommandParamType::DateTimeParam(_), BotCommandArgValue::DateTime(_)) => true,
+            (BotCommandParamType::ChatParam, BotCommandArgValue::Chat(_)) => true,
+            (BotCommandParamType::MessageParam, BotCommandArgValue::Message(_)) => true,
+            (BotCommandParamType::FileParam(p), BotCommandArgValue::File(f)) => {
+                p.max_size_bytes.is_none_or(|max| f.file_size <= max)
+                    && (p.mime_types.is_empty() || p.mime_types.contains(&f.mime_type))
+            }
+            (BotCommandParamType::TokenAmountParam(p), BotCommandArgValue::TokenAmount(t)) => {
+                (p.ledgers.is_empty() || p.ledgers.contains(&t.ledger))
+                    && p.min_amount.is_none_or(|min| t.amount >= min)
+                    && p.max_amount.is_none_or(|max| t.amount <= max)
+            }
+            _ => false,
+        }
+    }
 }
 
 #[ts_export]
@@ -93,6 +210,22 @@ pub struct DateTimeParam {
     pub future_only: bool,
 }
 
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct FileParam {
+    pub max_size_bytes: Option<u64>,
+    pub mime_types: Vec<String>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct TokenAmountParam {
+    // Ledgers of tokens in the registry which may be selected. If empty, any token in the registry may be selected.
+    pub ledgers: Vec<CanisterId>,
+    pub min_amount: Option<u128>,
+    pub max_amount: Option<u128>,
+}
+
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
 pub struct BotCommandOptionChoice<T> {
@@ -180,6 +313,15 @@ impl BotPermissions {
         }
     }
 
+    // Returns the permissions in `self` which are not in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        Self {
+            community: difference_bits(self.community, other.community),
+            chat: difference_bits(self.chat, other.chat),
+            message: difference_bits(self.message, other.message),
+        }
+    }
+
     pub fn text_only() -> Self {
         Self::from_message_permission(MessagePermission::Text)
     }
@@ -221,6 +363,8 @@ impl BotPermissions {
                 ChatPermission::ReadMessages,
                 ChatPermission::ReadMembership,
                 ChatPermission::ReadSummary,
+                ChatPermission::FollowThreads,
+                ChatPermission::BlockUsers,
             ]))
             .with_message(&HashSet::from_iter([
                 MessagePermission::Text,
@@ -294,6 +438,14 @@ fn union_bits(x: u32, y: u32) -> u32 {
     u32::from_be_bytes(union)
 }
 
+fn difference_bits(x: u32, y: u32) -> u32 {
+    let mut difference = [0; 4];
+    for (i, (x_byte, y_byte)) in x.to_be_bytes().into_iter().zip(y.to_be_bytes()).enumerate() {
+        difference[i] = x_byte & !y_byte;
+    }
+    u32::from_be_bytes(difference)
+}
+
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct BotMessage {
@@ -339,6 +491,34 @@ pub struct InstalledBotDetails {
     pub added_by: UserId,
     pub permissions: BotPermissions,
     pub autonomous_permissions: Option<BotPermissions>,
+    pub spend_allowances: Vec<BotSpendAllowance>,
+}
+
+// The maximum amount of a token a bot may transfer out of its own account within each period
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BotSpendAllowance {
+    pub ledger: CanisterId,
+    pub amount: u128,
+    pub period: Milliseconds,
+}
+
+// A value which OpenChat stores on behalf of a bot, scoped to the community or group the bot is installed in
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotStorageEntry {
+    pub key: String,
+    pub value: String,
+}
+
+// A task which wakes the bot at a fixed interval by sending it a `ScheduledTask` lifecycle event
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotScheduledTask {
+    pub name: String,
+    pub interval: Milliseconds,
+    pub channel_id: Option<ChannelId>,
+    pub next_run: TimestampMillis,
 }
 
 #[ts_export]
@@ -387,12 +567,71 @@ pub enum BotCommandArgValue {
     Boolean(bool),
     User(UserId),
     DateTime(TimestampMillis),
+    Chat(Chat),
+    Message(BotCommandMessageRef),
+    File(BotCommandFileRef),
+    TokenAmount(BotCommandTokenAmount),
+    Multiple(Vec<BotCommandArgValue>),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotCommandMessageRef {
+    pub thread: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub message_id: MessageId,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotCommandFileRef {
+    pub name: String,
+    pub mime_type: String,
+    pub file_size: u64,
+    pub blob_reference: BlobReference,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotCommandTokenAmount {
+    pub ledger: CanisterId,
+    pub amount: u128,
 }
 
 impl BotCommandArgValue {
     pub fn as_string(&self) -> Option<&str> {
         if let Self::String(s) = self { Some(s) } else { None }
     }
+
+    // Returns the individual values, unwrapping `Multiple` (which can't be nested)
+    pub fn values(&self) -> &[BotCommandArgValue] {
+        if let Self::Multiple(values) = self { values } else { std::slice::from_ref(self) }
+    }
+}
+
+// Sent to the bot's autocomplete endpoint (within a signed token) as the user types a value for a param
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotAutocompleteQuery {
+    pub command: String,
+    pub param: String,
+    pub partial_input: String,
+    pub args: Vec<BotCommandArg>,
+    pub initiator: UserId,
+    pub meta: Option<BotCommandMeta>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotAutocompleteResponse {
+    pub choices: Vec<BotAutocompleteChoice>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
+pub struct BotAutocompleteChoice {
+    pub name: String,
+    pub value: BotCommandArgValue,
 }
 
 #[ts_export]
@@ -439,6 +678,7 @@ pub enum BotMessageContent {
 #[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
 pub enum BotInitiator {
     Command(BotCommand),
+    Component(BotComponentInteraction),
     Autonomous,
 }
 
@@ -446,6 +686,7 @@ impl BotInitiator {
     pub fn user(&self) -> Option<UserId> {
         match self {
             BotInitiator::Command(bot_command) => Some(bot_command.initiator),
+            BotInitiator::Component(interaction) => Some(interaction.initiator),
             _ => None,
         }
     }
@@ -456,6 +697,13 @@ impl BotInitiator {
             _ => None,
         }
     }
+
+    pub fn component_interaction(&self) -> Option<&BotComponentInteraction> {
+        match self {
+            BotInitiator::Component(interaction) => Some(interaction),
+            _ => None,
+        }
+    }
 }
 
 impl From<BotMessageContent> for MessageContentInitial {
@@ -493,6 +741,24 @@ pub enum BotRegistrationStatus {
     Private(Option<BotInstallationLocation>),
 }
 
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BotReview {
+    pub version: u32,
+    pub status: BotReviewStatus,
+    pub submitted_at: TimestampMillis,
+    pub reviewed_by: Option<UserId>,
+    pub reviewed_at: Option<TimestampMillis>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum BotReviewStatus {
+    Submitted,
+    Approved,
+    Rejected(String),
+}
+
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
 pub struct BotSubscriptions {
@@ -556,3 +822,119 @@ pub enum BotDataEncoding {
     Json,
     Candid,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(params: Vec<BotCommandParam>) -> BotCommandDefinition {
+        BotCommandDefinition {
+            name: "command".to_string(),
+            description: None,
+            placeholder: None,
+            params,
+            permissions: BotPermissions::default(),
+            default_role: None,
+            direct_messages: None,
+            autocomplete_endpoint: None,
+        }
+    }
+
+    fn param(name: &str, required: bool, param_type: BotCommandParamType, max_values: Option<u8>) -> BotCommandParam {
+        BotCommandParam {
+            name: name.to_string(),
+            description: None,
+            placeholder: None,
+            required,
+            param_type,
+            max_values,
+            autocomplete: None,
+        }
+    }
+
+    fn arg(name: &str, value: BotCommandArgValue) -> BotCommandArg {
+        BotCommandArg {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    fn string_param() -> BotCommandParamType {
+        BotCommandParamType::StringParam(StringParam {
+            min_length: 1,
+            max_length: 5,
+            choices: Vec::new(),
+            multi_line: false,
+        })
+    }
+
+    #[test]
+    fn valid_args_accepted() {
+        let command = command(vec![
+            param("text", true, string_param(), None),
+            param("flag", false, BotCommandParamType::BooleanParam, None),
+        ]);
+
+        assert!(command.are_valid_args(&[arg("text", BotCommandArgValue::String("abc".to_string()))]));
+        assert!(command.are_valid_args(&[
+            arg("flag", BotCommandArgValue::Boolean(true)),
+            arg("text", BotCommandArgValue::String("abc".to_string())),
+        ]));
+    }
+
+    #[test]
+    fn missing_required_arg_rejected() {
+        let command = command(vec![
+            param("text", true, string_param(), None),
+            param("flag", false, BotCommandParamType::BooleanParam, None),
+        ]);
+
+        assert!(!command.are_valid_args(&[arg("flag", BotCommandArgValue::Boolean(true))]));
+        assert!(!command.are_valid_args(&[]));
+    }
+
+    #[test]
+    fn unknown_or_duplicate_args_rejected() {
+        let command = command(vec![param("text", false, string_param(), None)]);
+
+        assert!(!command.are_valid_args(&[arg("other", BotCommandArgValue::String("abc".to_string()))]));
+        assert!(!command.are_valid_args(&[
+            arg("text", BotCommandArgValue::String("abc".to_string())),
+            arg("text", BotCommandArgValue::String("def".to_string())),
+        ]));
+    }
+
+    #[test]
+    fn invalid_values_rejected() {
+        let command = command(vec![param("text", true, string_param(), None)]);
+
+        assert!(!command.are_valid_args(&[arg("text", BotCommandArgValue::String("abcdef".to_string()))]));
+        assert!(!command.are_valid_args(&[arg("text", BotCommandArgValue::Integer(1))]));
+    }
+
+    #[test]
+    fn multiple_values_checked_against_max_values() {
+        let single = command(vec![param("text", true, string_param(), None)]);
+        let multiple = command(vec![param("text", true, string_param(), Some(2))]);
+        let values = |count: usize| BotCommandArgValue::Multiple(vec![BotCommandArgValue::String("abc".to_string()); count]);
+
+        assert!(!single.are_valid_args(&[arg("text", values(1))]));
+        assert!(multiple.are_valid_args(&[arg("text", values(1))]));
+        assert!(multiple.are_valid_args(&[arg("text", values(2))]));
+        assert!(!multiple.are_valid_args(&[arg("text", values(0))]));
+        assert!(!multiple.are_valid_args(&[arg("text", values(3))]));
+        assert!(!multiple.are_valid_args(&[arg(
+            "text",
+            BotCommandArgValue::Multiple(vec![BotCommandArgValue::String("abcdef".to_string())])
+        )]));
+    }
+
+    #[test]
+    fn values_unwraps_multiple() {
+        let single = BotCommandArgValue::Boolean(true);
+        let multiple = BotCommandArgValue::Multiple(vec![BotCommandArgValue::Integer(1), BotCommandArgValue::Integer(2)]);
+
+        assert_eq!(single.values(), &[BotCommandArgValue::Boolean(true)]);
+        assert_eq!(multiple.values().len(), 2);
+    }
+}
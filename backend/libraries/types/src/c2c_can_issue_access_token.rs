@@ -1,4 +1,6 @@
-use crate::{BotPermissions, GroupRole, UserId, VideoCallType};
+use crate::{
+    BotCommandMessageRef, BotComponentValue, BotPermissions, Chat, GroupRole, MessageId, MessageIndex, UserId, VideoCallType,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -7,6 +9,7 @@ pub enum AccessTypeArgs {
     JoinVideoCall(JoinVideoCallArgs),
     MarkVideoCallAsEnded(MarkVideoCallAsEndedArgs),
     BotActionByCommand(BotActionByCommandArgs),
+    BotActionByComponent(BotActionByComponentArgs),
 }
 
 impl AccessTypeArgs {
@@ -23,6 +26,7 @@ impl AccessTypeArgs {
             AccessTypeArgs::JoinVideoCall(args) => args.initiator,
             AccessTypeArgs::MarkVideoCallAsEnded(args) => args.initiator,
             AccessTypeArgs::BotActionByCommand(args) => args.initiator,
+            AccessTypeArgs::BotActionByComponent(args) => args.initiator,
         }
     }
 }
@@ -30,6 +34,8 @@ impl AccessTypeArgs {
 #[derive(Serialize, Deserialize, Debug)]
 pub enum Response {
     Success,
+    // Returned for component interactions, since only the chat knows which permissions the component requires
+    SuccessWithPermissions(BotPermissions),
     Failure,
 }
 
@@ -57,4 +63,19 @@ pub struct BotActionByCommandArgs {
     pub initiator: UserId,
     pub initiator_role: GroupRole,
     pub requested_permissions: BotPermissions,
+    // Chats and messages passed as command args, each of which must be visible to the initiator
+    #[serde(default)]
+    pub referenced_chats: Vec<Chat>,
+    #[serde(default)]
+    pub referenced_messages: Vec<BotCommandMessageRef>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BotActionByComponentArgs {
+    pub bot_id: UserId,
+    pub initiator: UserId,
+    pub thread: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub component_id: String,
+    pub value: BotComponentValue,
 }
@@ -78,3 +78,27 @@ pub struct StreakInsuranceClaim {
     pub streak_length: u16,
     pub new_days_claimed: u8,
 }
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum ChitLeaderboardWindow {
+    ThisWeek,
+    LastWeek,
+    ThisMonth,
+    LastMonth,
+    Season(ChitLeaderboardSeason),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ChitLeaderboardSeason {
+    pub start: TimestampMillis,
+    pub end: TimestampMillis,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ChitLeaderboardEntry {
+    pub user_id: UserId,
+    pub chit_earned: i32,
+}
@@ -1,4 +1,7 @@
-use crate::{BotActionScope, BotCommand, BotPermissions, CanisterId, Chat, UserId, VideoCallType};
+use crate::{
+    BotActionScope, BotAutocompleteQuery, BotCommand, BotComponentInteraction, BotPermissions, CanisterId, Chat, UserId,
+    VideoCallType,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -23,3 +26,20 @@ pub struct BotActionByCommandClaims {
     pub granted_permissions: BotPermissions,
     pub command: BotCommand,
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct BotActionByComponentClaims {
+    pub bot_api_gateway: CanisterId,
+    pub bot: UserId,
+    pub scope: BotActionScope,
+    pub granted_permissions: BotPermissions,
+    pub interaction: BotComponentInteraction,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BotAutocompleteClaims {
+    pub bot_api_gateway: CanisterId,
+    pub bot: UserId,
+    pub scope: BotActionScope,
+    pub query: BotAutocompleteQuery,
+}
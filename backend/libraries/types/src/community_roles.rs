@@ -28,6 +28,7 @@ pub enum CommunityPermission {
     ManageUserGroups = 6,
     ReadMembership = 7,
     ReadSummary = 8,
+    BlockUsers = 9,
 }
 
 impl From<CommunityPermission> for u8 {
@@ -50,6 +51,7 @@ impl TryFrom<u8> for CommunityPermission {
             6 => Ok(CommunityPermission::ManageUserGroups),
             7 => Ok(CommunityPermission::ReadMembership),
             8 => Ok(CommunityPermission::ReadSummary),
+            9 => Ok(CommunityPermission::BlockUsers),
             _ => Err(()),
         }
     }
@@ -158,6 +160,14 @@ impl CommunityRole {
         self.has_owner_rights()
     }
 
+    pub fn can_view_audit_log(&self) -> bool {
+        self.has_admin_rights()
+    }
+
+    pub fn can_set_audit_log_retention(&self) -> bool {
+        self.has_owner_rights()
+    }
+
     pub fn is_permitted(&self, permission_role: CommunityPermissionRole) -> bool {
         match permission_role {
             CommunityPermissionRole::Owners => self.has_owner_rights(),
@@ -194,6 +204,7 @@ impl CommunityRole {
             (rps.invite_users, CommunityPermission::InviteUsers),
             (rps.manage_user_groups, CommunityPermission::ManageUserGroups),
             (rps.remove_members, CommunityPermission::RemoveMembers),
+            (rps.remove_members, CommunityPermission::BlockUsers),
             (rps.update_details, CommunityPermission::UpdateDetails),
         ];
 
@@ -1,3 +1,4 @@
+use crate::TimestampMillis;
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
@@ -18,3 +19,10 @@ impl From<EncryptionKey> for Vec<u8> {
         val.0
     }
 }
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EncryptionKeyEpoch {
+    pub epoch: u32,
+    pub started: TimestampMillis,
+}
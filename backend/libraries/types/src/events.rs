@@ -24,6 +24,7 @@ pub enum ChatEvent {
     RoleChanged(RoleChanged),
     UsersBlocked(UsersBlocked),
     UsersUnblocked(UsersUnblocked),
+    MemberTimedOut(MemberTimedOut),
     MessagePinned(MessagePinned),
     MessageUnpinned(MessageUnpinned),
     PermissionsChanged(PermissionsChanged),
@@ -93,6 +94,7 @@ pub enum ChatEventType {
     UsersInvited,
     UsersBlocked,
     UsersUnblocked,
+    MemberTimedOut,
     BotAdded,   // Not publishing a bot notification for this event
     BotRemoved, // Not publishing a bot notification for this event
     BotUpdated, // Not publishing a bot notification for this event
@@ -137,7 +139,8 @@ impl From<ChatEventType> for ChatEventCategory {
             | ChatEventType::BotRemoved
             | ChatEventType::BotUpdated
             | ChatEventType::UsersBlocked
-            | ChatEventType::UsersUnblocked => ChatEventCategory::Membership,
+            | ChatEventType::UsersUnblocked
+            | ChatEventType::MemberTimedOut => ChatEventCategory::Membership,
         }
     }
 }
@@ -266,6 +269,9 @@ pub struct MembersRemoved {
 pub struct UsersBlocked {
     pub user_ids: Vec<UserId>,
     pub blocked_by: UserId,
+    // Set if the users are only blocked temporarily
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<TimestampMillis>,
 }
 
 #[ts_export]
@@ -275,6 +281,16 @@ pub struct UsersUnblocked {
     pub unblocked_by: UserId,
 }
 
+// `until` is `None` if an existing timeout has been lifted
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MemberTimedOut {
+    pub user_id: UserId,
+    pub timed_out_by: UserId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<TimestampMillis>,
+}
+
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct MemberJoined {
@@ -540,6 +556,7 @@ impl ChatEvent {
             ChatEvent::RoleChanged(_) => Some(ChatEventType::RoleChanged),
             ChatEvent::UsersBlocked(_) => Some(ChatEventType::UsersBlocked),
             ChatEvent::UsersUnblocked(_) => Some(ChatEventType::UsersUnblocked),
+            ChatEvent::MemberTimedOut(_) => Some(ChatEventType::MemberTimedOut),
             ChatEvent::MessagePinned(_) => Some(ChatEventType::MessagePinned),
             ChatEvent::MessageUnpinned(_) => Some(ChatEventType::MessageUnpinned),
             ChatEvent::PermissionsChanged(_) => Some(ChatEventType::PermissionsChanged),
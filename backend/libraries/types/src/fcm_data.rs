@@ -1,4 +1,4 @@
-use crate::{ChannelId, Chat, ChatId, CommunityId, MessageId, UserId};
+use crate::{CanisterId, ChannelId, Chat, ChatId, CommunityId, MessageId, UserId};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -42,6 +42,13 @@ impl FcmData {
         }
     }
 
+    pub fn for_chat(chat: Chat) -> Self {
+        match chat {
+            Chat::Direct(chat_id) => Self::for_direct_chat(CanisterId::from(chat_id).into()),
+            _ => Self::default(chat),
+        }
+    }
+
     pub fn for_group(group_chat_id: ChatId) -> Self {
         Self::default(Chat::Group(group_chat_id))
     }
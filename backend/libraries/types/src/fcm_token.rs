@@ -1,8 +1,10 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
+use ts_export::schemars::JsonSchema;
 use ts_rs::TS;
 
-#[derive(Debug, CandidType, Serialize, Deserialize, Clone, Hash, Eq, PartialEq, PartialOrd, Ord, TS)]
+#[derive(Debug, CandidType, Serialize, Deserialize, Clone, Hash, Eq, PartialEq, PartialOrd, Ord, TS, JsonSchema)]
+#[schemars(crate = "ts_export::schemars")]
 pub struct FcmToken(pub String);
 
 impl From<String> for FcmToken {
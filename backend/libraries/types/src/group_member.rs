@@ -12,4 +12,5 @@ pub struct GroupMember {
     pub date_added: TimestampMillis,
     pub role: GroupRole,
     pub lapsed: bool,
+    pub timed_out_until: Option<TimestampMillis>,
 }
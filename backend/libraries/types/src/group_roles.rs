@@ -189,6 +189,8 @@ pub enum ChatPermission {
     ReadMessages = 10,
     ReadMembership = 11,
     ReadSummary = 12,
+    FollowThreads = 13,
+    BlockUsers = 14,
 }
 
 impl From<ChatPermission> for u8 {
@@ -215,6 +217,8 @@ impl TryFrom<u8> for ChatPermission {
             10 => Ok(ChatPermission::ReadMessages),
             11 => Ok(ChatPermission::ReadMembership),
             12 => Ok(ChatPermission::ReadSummary),
+            13 => Ok(ChatPermission::FollowThreads),
+            14 => Ok(ChatPermission::BlockUsers),
             _ => Err(()),
         }
     }
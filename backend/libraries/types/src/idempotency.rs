@@ -1,5 +1,7 @@
 use crate::TimestampMillis;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::hash::{Hash, Hasher};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct IdempotentEnvelope<T> {
@@ -18,3 +20,50 @@ impl<T> From<T> for IdempotentEnvelope<T> {
         }
     }
 }
+
+// Implemented by the args of endpoints which opt in to idempotency (`#[update(idempotent = true)]`).
+// The key must be derived (using `idempotency_key`) from the values which identify the request as
+// supplied by the original caller (eg. the bot's message id), so that a retry maps to the same key
+// whether it comes from an intermediate canister or from the original caller. If a key is returned,
+// the callee stores the response against `(caller, key)` and if the same request is received again
+// it returns the stored response rather than applying it a second time.
+pub trait IdempotentArgs {
+    fn idempotency_key(&self) -> Option<u64>;
+}
+
+// Hashes the values identifying a request into an idempotency key. This uses SHA256 rather than
+// `DefaultHasher` so that keys remain stable across upgrades.
+pub fn idempotency_key(identity: impl Hash) -> u64 {
+    let mut hasher = Sha256Hasher::default();
+    identity.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Default)]
+struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+    fn finish(&self) -> u64 {
+        let hash = self.0.clone().finalize();
+        u64::from_be_bytes(hash[..8].try_into().unwrap())
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageId;
+
+    #[test]
+    fn idempotency_key_is_stable() {
+        let message_id = MessageId::from(1u128);
+
+        assert_eq!(idempotency_key(message_id), idempotency_key(message_id));
+        assert_ne!(idempotency_key(message_id), idempotency_key(MessageId::from(2u128)));
+        assert_ne!(idempotency_key((message_id, 1u8)), idempotency_key((message_id, 2u8)));
+    }
+}
@@ -5,8 +5,11 @@ use serde::{Deserialize, Serialize};
 mod access_tokens;
 mod achievement;
 mod airdrop_config;
+mod audit_log;
+mod automod;
 mod avatar;
 mod bitflags;
+mod bot_components;
 mod bots;
 mod build_version;
 pub mod c2c_can_issue_access_token;
@@ -61,6 +64,7 @@ mod message_content;
 mod message_id;
 mod message_index;
 mod message_match;
+mod moderation;
 mod notifications;
 mod option;
 mod p2p_swaps;
@@ -91,7 +95,10 @@ mod video_calls;
 pub use access_tokens::*;
 pub use achievement::*;
 pub use airdrop_config::*;
+pub use audit_log::*;
+pub use automod::*;
 pub use avatar::*;
+pub use bot_components::*;
 pub use bots::*;
 pub use build_version::*;
 pub use caller::*;
@@ -143,6 +150,7 @@ pub use message_content::*;
 pub use message_id::*;
 pub use message_index::*;
 pub use message_match::*;
+pub use moderation::*;
 pub use notifications::*;
 use oc_error_codes::{OCError, OCErrorCode};
 pub use option::*;
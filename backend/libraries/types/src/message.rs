@@ -1,6 +1,6 @@
 use crate::{
-    Achievement, BotCaller, BotCommand, CanisterId, Chat, EventIndex, MessageContent, MessageId, MessageIndex, Reaction,
-    ThreadSummary, UserId,
+    Achievement, BotCaller, BotCommand, BotComponentInteraction, BotInitiator, BotMessageComponent, CanisterId, Chat,
+    EventIndex, MessageContent, MessageId, MessageIndex, Reaction, ThreadSummary, UserId,
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -277,14 +277,27 @@ impl SenderContext {
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct BotMessageContext {
     pub command: Option<BotCommand>,
+    pub interaction: Option<BotComponentInteraction>,
     pub finalised: bool,
+    pub components: Vec<BotMessageComponent>,
 }
 
 impl BotMessageContext {
     pub fn from(caller: &BotCaller, finalised: bool) -> Self {
         BotMessageContext {
             command: caller.initiator.command().cloned(),
+            interaction: caller.initiator.component_interaction().cloned(),
             finalised,
+            components: Vec::new(),
         }
     }
+
+    pub fn component(&self, component_id: &str) -> Option<&BotMessageComponent> {
+        self.components.iter().find(|c| c.id() == component_id)
+    }
+
+    // Whether the message was sent by the bot in response to the same command or component interaction
+    pub fn matches_initiator(&self, initiator: &BotInitiator) -> bool {
+        initiator.command() == self.command.as_ref() && initiator.component_interaction() == self.interaction.as_ref()
+    }
 }
@@ -246,6 +246,15 @@ impl MessageContent {
         self.into()
     }
 
+    // The notification body to use when there is no text to display
+    pub fn notification_alt_text(&self) -> String {
+        if let MessageContent::Encrypted(_) = self {
+            "Encrypted message".to_string()
+        } else {
+            self.content_type().to_string()
+        }
+    }
+
     pub fn notification_crypto_transfer_details(&self, mentioned: &[User]) -> Option<CryptoTransferDetails> {
         if let MessageContent::Crypto(c) = self {
             Some(CryptoTransferDetails {
@@ -0,0 +1,44 @@
+use crate::{MessageContent, MessageId, MessageIndex, Milliseconds, TimestampMillis, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+
+pub const MAX_REPORT_REASON_LENGTH: usize = 500;
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ModerationQueueItem {
+    pub id: u32,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub message_index: MessageIndex,
+    pub sender: UserId,
+    pub content: MessageContent,
+    pub reports: Vec<MessageReport>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MessageReport {
+    pub reporter: UserId,
+    pub reason: Option<String>,
+    pub timestamp: TimestampMillis,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModerationAction {
+    Dismiss,
+    DeleteMessage,
+    Timeout(Milliseconds),
+    RemoveMember,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ResolvedModerationItem {
+    pub item: ModerationQueueItem,
+    pub action: ModerationAction,
+    pub resolved_by: UserId,
+    pub timestamp: TimestampMillis,
+}
@@ -1,6 +1,7 @@
 use crate::{
     BotDataEncoding, BotInstallationLocation, BotPermissions, CanisterId, ChannelId, Chat, ChatEvent, ChatId, CommunityEvent,
-    CommunityId, EventIndex, FcmData, MessageIndex, Reaction, TimestampMillis, UserId,
+    CommunityId, EventIndex, FcmData, MessageIndex, NotificationCategory, NotificationDestination, Reaction, TimestampMillis,
+    UserId,
 };
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,10 @@ pub enum Notification {
     User(UserNotification),
     #[serde(rename = "b")]
     Bot(BotNotification),
+    #[serde(rename = "o")]
+    Outbound(OutboundNotification),
+    #[serde(rename = "e")]
+    EmailConfirmation(EmailConfirmation),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -97,6 +102,8 @@ pub enum BotLifecycleEvent {
     Installed(BotInstalledEvent),
     #[serde(rename = "u")]
     Uninstalled(BotUninstalledEvent),
+    #[serde(rename = "s")]
+    ScheduledTask(BotScheduledTaskEvent),
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -121,6 +128,18 @@ pub struct BotUninstalledEvent {
     pub location: BotInstallationLocation,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BotScheduledTaskEvent {
+    #[serde(rename = "n")]
+    pub name: String,
+    #[serde(rename = "l")]
+    pub location: BotInstallationLocation,
+    #[serde(rename = "c")]
+    pub channel_id: Option<ChannelId>,
+    #[serde(rename = "s")]
+    pub scheduled_at: TimestampMillis,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct BotRegisteredEvent {
     #[serde(rename = "i")]
@@ -143,6 +162,40 @@ impl Debug for UserNotification {
 pub enum NotificationEnvelope {
     User(UserNotificationEnvelope),
     Bot(BotNotificationEnvelope),
+    Outbound(OutboundNotification),
+    EmailConfirmation(EmailConfirmation),
+}
+
+// A personal notification routed to the destination the recipient has configured
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct OutboundNotification {
+    pub destination: NotificationDestination,
+    pub payload: OutboundNotificationPayload,
+    // The recipient's secret used to sign webhook requests, so that their endpoint can verify they came from OpenChat
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+}
+
+// Sent to an email address before notifications are routed to it, so the user can prove they own the address
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct EmailConfirmation {
+    pub recipient: UserId,
+    pub email: String,
+    pub code: String,
+    pub timestamp: TimestampMillis,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct OutboundNotificationPayload {
+    pub recipient: UserId,
+    pub category: NotificationCategory,
+    pub chat: Chat,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_index: MessageIndex,
+    pub sender: Option<UserId>,
+    pub sender_name: Option<String>,
+    pub message_text: Option<String>,
+    pub timestamp: TimestampMillis,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone)]
@@ -155,6 +208,9 @@ pub struct UserNotificationEnvelope {
     pub timestamp: TimestampMillis,
     #[serde(default, rename = "f")]
     pub fcm_data: Option<FcmData>,
+    // Low priority notifications (eg. digests) are pushed with low urgency so they don't wake the recipient's device
+    #[serde(default, rename = "l")]
+    pub low_priority: bool,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -174,10 +230,33 @@ impl NotificationEnvelope {
         match self {
             NotificationEnvelope::User(n) => n.approx_size(),
             NotificationEnvelope::Bot(n) => n.approx_size(),
+            NotificationEnvelope::Outbound(n) => n.approx_size(),
+            NotificationEnvelope::EmailConfirmation(n) => n.approx_size(),
         }
     }
 }
 
+impl OutboundNotification {
+    pub fn approx_size(&self) -> usize {
+        let destination_len = match &self.destination {
+            NotificationDestination::Webhook(url) => url.len(),
+            NotificationDestination::Email(address) => address.len(),
+        };
+        let payload = &self.payload;
+
+        100 + destination_len
+            + payload.sender_name.as_ref().map_or(0, |s| s.len())
+            + payload.message_text.as_ref().map_or(0, |s| s.len())
+            + self.signing_secret.as_ref().map_or(0, |s| s.len())
+    }
+}
+
+impl EmailConfirmation {
+    pub fn approx_size(&self) -> usize {
+        30 + self.email.len() + self.code.len()
+    }
+}
+
 impl UserNotificationEnvelope {
     pub fn approx_size(&self) -> usize {
         CANISTER_PRINCIPAL_LEN * self.recipients.len() + self.notification_bytes.len() + 7
@@ -213,6 +292,33 @@ pub enum UserNotificationPayload {
     GroupMessageTipped(GroupMessageTipped),
     #[serde(rename = "ct")]
     ChannelMessageTipped(ChannelMessageTipped),
+    #[serde(rename = "gp")]
+    GroupMessageReported(GroupMessageReported),
+    #[serde(rename = "cp")]
+    ChannelMessageReported(ChannelMessageReported),
+    #[serde(rename = "dg")]
+    Digest(DigestNotification),
+}
+
+// A summary of the notifications which were held back due to the recipient's quiet hours or digest settings
+#[ts_export]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DigestNotification {
+    #[serde(rename = "c")]
+    pub chats: Vec<DigestChatSummary>,
+    #[serde(rename = "s")]
+    pub since: TimestampMillis,
+}
+
+#[ts_export]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DigestChatSummary {
+    #[serde(rename = "c")]
+    pub chat: Chat,
+    #[serde(rename = "n")]
+    pub notification_count: u32,
+    #[serde(rename = "l")]
+    pub latest: TimestampMillis,
 }
 
 #[ts_export]
@@ -487,6 +593,46 @@ pub struct ChannelMessageTipped {
     pub channel_avatar_id: Option<u128>,
 }
 
+#[ts_export]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GroupMessageReported {
+    #[serde(rename = "c")]
+    pub chat_id: ChatId,
+    #[serde(rename = "tr")]
+    pub thread_root_message_index: Option<MessageIndex>,
+    #[serde(rename = "m")]
+    pub message_index: MessageIndex,
+    #[serde(rename = "g")]
+    pub group_name: String,
+    #[serde(rename = "p")]
+    pub pending_reports: u32,
+    #[serde(rename = "a")]
+    pub group_avatar_id: Option<u128>,
+}
+
+#[ts_export]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChannelMessageReported {
+    #[serde(rename = "ci")]
+    pub community_id: CommunityId,
+    #[serde(rename = "chi")]
+    pub channel_id: ChannelId,
+    #[serde(rename = "tr")]
+    pub thread_root_message_index: Option<MessageIndex>,
+    #[serde(rename = "m")]
+    pub message_index: MessageIndex,
+    #[serde(rename = "cn")]
+    pub community_name: String,
+    #[serde(rename = "chn")]
+    pub channel_name: String,
+    #[serde(rename = "p")]
+    pub pending_reports: u32,
+    #[serde(rename = "ca")]
+    pub community_avatar_id: Option<u128>,
+    #[serde(rename = "cha")]
+    pub channel_avatar_id: Option<u128>,
+}
+
 #[ts_export]
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CryptoTransferDetails {
@@ -95,6 +95,7 @@ option_update!(OptionUpdateDocument, crate::Document);
 option_update!(OptionUpdateFrozenGroupInfo, crate::FrozenGroupInfo);
 option_update!(OptionUpdateGroupPermissionRole, crate::GroupPermissionRole);
 option_update!(OptionUpdateGroupSubtype, crate::GroupSubtype);
+option_update!(OptionUpdateNotificationRouting, crate::NotificationRouting);
 option_update!(OptionUpdateOptionalMessagePermissions, crate::OptionalMessagePermissions);
 option_update!(OptionUpdatePinNumberSettings, crate::PinNumberSettings);
 option_update!(OptionUpdateStreakInsurance, crate::StreakInsurance);
@@ -1,6 +1,8 @@
-use crate::FcmToken;
+use crate::{Chat, FcmToken, Milliseconds, TimestampMillis};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr};
 use ts_export::ts_export;
 
 #[ts_export]
@@ -35,3 +37,195 @@ impl SubscriptionKeys {
         self.p256dh.len() + self.auth.len() + 24
     }
 }
+
+// Where to deliver a user's personal notifications in addition to their web push and FCM subscriptions
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct NotificationRouting {
+    pub destination: NotificationDestination,
+    pub categories: Vec<NotificationCategory>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum NotificationDestination {
+    // The endpoint of a bot (or any HTTPS service) owned by the user, which will be sent a POST request per notification
+    Webhook(String),
+    // An email address, delivered via the SMTP relay configured in the notification pusher
+    Email(String),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum NotificationCategory {
+    Mention,
+    Reply,
+    DirectMessage,
+    Keyword,
+}
+
+impl NotificationRouting {
+    pub fn routes(&self, category: NotificationCategory) -> Option<&NotificationDestination> {
+        self.categories.contains(&category).then_some(&self.destination)
+    }
+}
+
+// Webhooks may only be delivered to publicly routable addresses, otherwise users could direct requests at services on the
+// notification pusher's private network
+pub fn is_publicly_routable(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_publicly_routable_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_publicly_routable_v4(ip),
+            None => {
+                !(ip.is_unspecified()
+                    || ip.is_loopback()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+fn is_publicly_routable_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        // Shared address space (100.64.0.0/10)
+        || (a == 100 && (b & 0xC0) == 64))
+}
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct NotificationPreferences {
+    pub quiet_hours: Option<QuietHours>,
+    pub digest: Option<NotificationDigestSettings>,
+}
+
+// Notifications received during quiet hours are held back and delivered as a digest once the quiet hours end.
+// Both values are minutes after midnight in the user's local time. If `end_mins` is earlier than `start_mins` then the
+// quiet hours span midnight.
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QuietHours {
+    pub start_mins: u16,
+    pub end_mins: u16,
+}
+
+// Notifications for low priority chats (and muted direct chats) are batched into a single summary notification which
+// is pushed at most once per `interval`. Muted group chats and channels aren't included, since groups and communities
+// don't send notifications to members who have muted them, so to receive a digest for a group or channel it should be
+// added to `low_priority_chats` rather than muted.
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct NotificationDigestSettings {
+    pub interval: Milliseconds,
+    pub low_priority_chats: Vec<Chat>,
+}
+
+// The subset of a user's notification preferences needed by the LocalUserIndexes to decide whether to push a
+// notification immediately or to hold it back for the user's next digest
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct UserNotificationPreferences {
+    pub quiet_hours: Option<QuietHours>,
+    pub utc_offset_mins: i16,
+    pub digest_interval: Option<Milliseconds>,
+    pub digest_chats: HashSet<Chat>,
+}
+
+impl QuietHours {
+    pub fn is_valid(&self) -> bool {
+        self.start_mins < MINUTES_PER_DAY && self.end_mins < MINUTES_PER_DAY && self.start_mins != self.end_mins
+    }
+
+    pub fn contains(&self, now: TimestampMillis, utc_offset_mins: i16) -> bool {
+        let utc_mins = ((now / 60_000) % MINUTES_PER_DAY as u64) as i32;
+        let local_mins = (utc_mins + utc_offset_mins as i32).rem_euclid(MINUTES_PER_DAY as i32) as u16;
+
+        if self.start_mins <= self.end_mins {
+            local_mins >= self.start_mins && local_mins < self.end_mins
+        } else {
+            local_mins >= self.start_mins || local_mins < self.end_mins
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("8.8.8.8", true)]
+    #[test_case("2606:4700::1111", true)]
+    #[test_case("127.0.0.1", false)]
+    #[test_case("10.1.2.3", false)]
+    #[test_case("172.16.0.1", false)]
+    #[test_case("192.168.1.1", false)]
+    #[test_case("169.254.169.254", false)]
+    #[test_case("100.64.0.1", false)]
+    #[test_case("0.0.0.0", false)]
+    #[test_case("::1", false)]
+    #[test_case("fd00::1", false)]
+    #[test_case("fe80::1", false)]
+    #[test_case("::ffff:127.0.0.1", false)]
+    fn publicly_routable(ip: &str, expected: bool) {
+        assert_eq!(is_publicly_routable(ip.parse().unwrap()), expected);
+    }
+
+    const HOUR: TimestampMillis = 60 * 60 * 1000;
+
+    // 09:00 - 17:00
+    #[test_case(9, 17, 0, 9 * HOUR, true)]
+    #[test_case(9, 17, 0, 17 * HOUR - 1, true)]
+    #[test_case(9, 17, 0, 17 * HOUR, false)]
+    #[test_case(9, 17, 0, 9 * HOUR - 1, false)]
+    // 22:00 - 07:00, spanning midnight
+    #[test_case(22, 7, 0, 22 * HOUR, true)]
+    #[test_case(22, 7, 0, 23 * HOUR, true)]
+    #[test_case(22, 7, 0, 3 * HOUR, true)]
+    #[test_case(22, 7, 0, 7 * HOUR, false)]
+    #[test_case(22, 7, 0, 12 * HOUR, false)]
+    // 22:00 - 07:00 in UTC-5, so 03:00 - 12:00 UTC
+    #[test_case(22, 7, -300, 3 * HOUR, true)]
+    #[test_case(22, 7, -300, 11 * HOUR, true)]
+    #[test_case(22, 7, -300, 12 * HOUR, false)]
+    #[test_case(22, 7, -300, 23 * HOUR, false)]
+    // 22:00 - 07:00 in UTC+5:30, so 16:30 - 01:30 UTC
+    #[test_case(22, 7, 330, 17 * HOUR, true)]
+    #[test_case(22, 7, 330, HOUR, true)]
+    #[test_case(22, 7, 330, 2 * HOUR, false)]
+    fn quiet_hours_contains(
+        start_hour: u16,
+        end_hour: u16,
+        utc_offset_mins: i16,
+        time_of_day: TimestampMillis,
+        expected: bool,
+    ) {
+        let quiet_hours = QuietHours {
+            start_mins: start_hour * 60,
+            end_mins: end_hour * 60,
+        };
+        // Use a date well after the epoch to check that only the time of day matters
+        let now = 20_000 * 24 * HOUR + time_of_day;
+
+        assert_eq!(quiet_hours.contains(now, utc_offset_mins), expected);
+    }
+
+    #[test_case(9 * 60, 17 * 60, true)]
+    #[test_case(22 * 60, 7 * 60, true)]
+    #[test_case(60, 60, false)]
+    #[test_case(0, 24 * 60, false)]
+    fn quiet_hours_validation(start_mins: u16, end_mins: u16, expected: bool) {
+        assert_eq!(QuietHours { start_mins, end_mins }.is_valid(), expected);
+    }
+}
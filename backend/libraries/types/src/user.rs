@@ -73,6 +73,8 @@ pub struct UserDetails {
     pub is_platform_moderator: bool,
     pub is_platform_operator: bool,
     pub is_diamond_member: bool,
+    #[serde(default)]
+    pub total_chit_earned: i32,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -4,6 +4,8 @@ use async_channel::Sender;
 
 mod pusher;
 
+pub(crate) use pusher::LocalHostResolver;
+
 pub fn start_bot_notifications_processor(is_production: bool) -> Sender<BotNotification> {
     let (sender, receiver) = async_channel::bounded::<BotNotification>(200_000);
 
@@ -67,7 +67,7 @@ impl Pusher {
     }
 }
 
-struct LocalHostResolver;
+pub(crate) struct LocalHostResolver;
 
 impl reqwest::dns::Resolve for LocalHostResolver {
     fn resolve(&self, name: Name) -> Resolving {
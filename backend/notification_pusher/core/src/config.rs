@@ -28,6 +28,13 @@ pub struct EnvConfig {
 
     #[envconfig(from = "GCLOUD_SA_JSON_PATH")]
     pub gcloud_sa_json_path: String,
+
+    // Address (host:port) of the SMTP relay used to deliver notifications routed to email
+    #[envconfig(from = "SMTP_RELAY_ADDRESS")]
+    pub smtp_relay_address: Option<String>,
+
+    #[envconfig(from = "SMTP_FROM_ADDRESS", default = "notifications@oc.app")]
+    pub smtp_from_address: String,
 }
 
 pub struct Config<I> {
@@ -38,6 +45,13 @@ pub struct Config<I> {
     pub pusher_count: u32,
     pub index_store: I,
     pub fcm_service: Arc<FcmService>,
+    pub smtp_relay: Option<SmtpRelayConfig>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SmtpRelayConfig {
+    pub address: String,
+    pub from: String,
 }
 
 impl<I: IndexStore + 'static> Config<I> {
@@ -59,6 +73,10 @@ impl<I: IndexStore + 'static> Config<I> {
             is_production: env_config.is_production,
             pusher_count: env_config.pusher_count,
             fcm_service: Arc::new(FcmService::new(env_config.gcloud_sa_json_path)),
+            smtp_relay: env_config.smtp_relay_address.map(|address| SmtpRelayConfig {
+                address,
+                from: env_config.smtp_from_address,
+            }),
         })
     }
 }
@@ -1,6 +1,7 @@
 use crate::bot_notifications::start_bot_notifications_processor;
 use crate::config::Config;
 use crate::metrics::{Metrics, collect_metrics};
+use crate::outbound_notifications::start_outbound_notifications_processor;
 use crate::reader::Reader;
 use crate::user_notifications::start_user_notifications_processor;
 use index_store::IndexStore;
@@ -9,13 +10,14 @@ use std::io::Write;
 use std::sync::Arc;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tracing::info;
-use types::{CanisterId, FcmData, FcmToken, Payload, SubscriptionInfo, TimestampMillis, UserId};
+use types::{CanisterId, EmailConfirmation, FcmData, FcmToken, Payload, SubscriptionInfo, TimestampMillis, UserId};
 use web_push::WebPushMessage;
 
 mod bot_notifications;
 pub mod config;
 pub mod ic_agent;
 mod metrics;
+mod outbound_notifications;
 mod reader;
 mod user_notifications;
 
@@ -40,6 +42,8 @@ pub async fn run_notifications_pusher<I: IndexStore + 'static>(config: Config<I>
 
     let bot_notifications_sender = start_bot_notifications_processor(config.is_production);
 
+    let outbound_notifications_sender = start_outbound_notifications_processor(config.is_production, config.smtp_relay);
+
     for notification_canister_id in notification_canister_ids {
         let reader = Reader::new(
             config.ic_agent.clone(),
@@ -47,6 +51,7 @@ pub async fn run_notifications_pusher<I: IndexStore + 'static>(config: Config<I>
             config.index_store.clone(),
             user_notifications_sender.clone(),
             bot_notifications_sender.clone(),
+            outbound_notifications_sender.clone(),
         );
         tokio::spawn(reader.run());
     }
@@ -80,6 +85,7 @@ pub struct FcmNotification {
 pub struct UserNotification {
     payload: Arc<Vec<u8>>,
     subscription_info: SubscriptionInfo,
+    low_priority: bool,
     metadata: NotificationMetadata,
 }
 
@@ -115,6 +121,21 @@ pub struct BotNotification {
     first_read_at: Instant,
 }
 
+// Personal notifications routed to the destination chosen by the recipient, along with the emails sent to confirm those
+// destinations
+pub struct OutboundNotification {
+    notifications_canister: CanisterId,
+    index: u64,
+    timestamp: TimestampMillis,
+    content: OutboundNotificationContent,
+    first_read_at: Instant,
+}
+
+pub enum OutboundNotificationContent {
+    Notification(types::OutboundNotification),
+    EmailConfirmation(EmailConfirmation),
+}
+
 fn timestamp() -> TimestampMillis {
     SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
 }
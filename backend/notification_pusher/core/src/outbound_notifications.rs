@@ -0,0 +1,24 @@
+use crate::OutboundNotification;
+use crate::config::SmtpRelayConfig;
+use crate::outbound_notifications::pusher::Pusher;
+use async_channel::Sender;
+
+mod pusher;
+mod smtp;
+
+const PUSHER_COUNT: usize = 10;
+
+pub fn start_outbound_notifications_processor(
+    is_production: bool,
+    smtp_relay: Option<SmtpRelayConfig>,
+) -> Sender<OutboundNotification> {
+    let (sender, receiver) = async_channel::bounded::<OutboundNotification>(20_000);
+
+    // Each pusher handles one notification at a time, so this bounds the number of concurrent outbound requests
+    for _ in 0..PUSHER_COUNT {
+        let pusher = Pusher::new(receiver.clone(), is_production, smtp_relay.clone());
+        tokio::spawn(pusher.run());
+    }
+
+    sender
+}
@@ -0,0 +1,224 @@
+use crate::bot_notifications::LocalHostResolver;
+use crate::config::SmtpRelayConfig;
+use crate::metrics::write_metrics;
+use crate::outbound_notifications::smtp;
+use crate::{OutboundNotification, OutboundNotificationContent, timestamp};
+use async_channel::Receiver;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::redirect::Policy;
+use reqwest::{Client, ClientBuilder, Url};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::error;
+use types::{
+    EmailConfirmation, NotificationCategory, NotificationDestination, OutboundNotificationPayload, is_publicly_routable,
+};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Pusher {
+    receiver: Receiver<OutboundNotification>,
+    http_client: Client,
+    smtp_relay: Option<SmtpRelayConfig>,
+    is_production: bool,
+}
+
+impl Pusher {
+    pub fn new(receiver: Receiver<OutboundNotification>, is_production: bool, smtp_relay: Option<SmtpRelayConfig>) -> Self {
+        // Redirects aren't followed since they could point the request at a private address
+        let client_builder = ClientBuilder::new()
+            .redirect(Policy::none())
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT);
+
+        Self {
+            receiver,
+            http_client: if is_production {
+                client_builder.dns_resolver(Arc::new(PublicAddressResolver))
+            } else {
+                client_builder.dns_resolver(Arc::new(LocalHostResolver))
+            }
+            .build()
+            .unwrap(),
+            smtp_relay,
+            is_production,
+        }
+    }
+
+    pub async fn run(self) {
+        while let Ok(notification) = self.receiver.recv().await {
+            let start = Instant::now();
+            let (push_result, payload_size) = match &notification.content {
+                OutboundNotificationContent::Notification(n) => {
+                    let body = serde_json::to_vec(&n.payload).unwrap();
+                    let payload_size = body.len() as u64;
+                    let push_result = match &n.destination {
+                        NotificationDestination::Webhook(url) => {
+                            self.push_to_webhook(url, body, n.signing_secret.as_deref()).await
+                        }
+                        NotificationDestination::Email(address) => self.push_to_email(address, &n.payload).await,
+                    };
+                    (push_result, payload_size)
+                }
+                OutboundNotificationContent::EmailConfirmation(confirmation) => {
+                    let body = email_confirmation_body(confirmation);
+                    let payload_size = body.len() as u64;
+                    let push_result = self
+                        .send_email(
+                            &confirmation.email,
+                            "Confirm your email address for OpenChat notifications",
+                            &body,
+                        )
+                        .await;
+                    (push_result, payload_size)
+                }
+            };
+
+            if let Err(error) = &push_result {
+                error!(?error, "Failed to push outbound notification");
+            }
+
+            let success = push_result.is_ok();
+            let end = Instant::now();
+            let push_duration = end.saturating_duration_since(start).as_millis() as u64;
+            let timestamp = timestamp();
+            let end_to_end_latency = timestamp.saturating_sub(notification.timestamp);
+            let end_to_end_internal_latency = end.saturating_duration_since(notification.first_read_at).as_millis() as u64;
+
+            write_metrics(|m| {
+                if success {
+                    m.observe_notification_payload_size(payload_size, false);
+                    m.set_latest_notification_index_pushed(notification.index, notification.notifications_canister);
+                }
+                m.observe_end_to_end_latency(end_to_end_latency, false, notification.notifications_canister);
+                m.observe_end_to_end_internal_latency(end_to_end_internal_latency, false);
+                m.observe_http_post_notification_duration(push_duration, false, success);
+            });
+        }
+    }
+
+    async fn push_to_webhook(&self, url: &str, body: Vec<u8>, signing_secret: Option<&str>) -> Result<(), String> {
+        let Some(signing_secret) = signing_secret else {
+            return Err("Webhook has no signing secret".to_string());
+        };
+
+        let url = Url::parse(url).map_err(|e| e.to_string())?;
+
+        // Hostnames are checked by the resolver, but IP addresses bypass it so must be checked here
+        if self.is_production {
+            if let Some(ip) = url.host_str().and_then(|h| h.trim_matches(['[', ']']).parse().ok()) {
+                if !is_publicly_routable(ip) {
+                    return Err("Webhook address is not publicly routable".to_string());
+                }
+            }
+        }
+
+        let timestamp = timestamp().to_string();
+        let signature = sign(signing_secret, &timestamp, &body);
+
+        let response = self
+            .http_client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .header("X-OC-Timestamp", timestamp)
+            .header("X-OC-Signature", signature)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Webhook responded with status {}", response.status()))
+        }
+    }
+
+    async fn push_to_email(&self, address: &str, payload: &OutboundNotificationPayload) -> Result<(), String> {
+        self.send_email(address, &email_subject(payload), &email_body(payload)).await
+    }
+
+    async fn send_email(&self, address: &str, subject: &str, body: &str) -> Result<(), String> {
+        let Some(relay) = &self.smtp_relay else {
+            return Err("No SMTP relay configured".to_string());
+        };
+
+        smtp::send(relay, address, subject, body).await
+    }
+}
+
+// Signs "<timestamp>.<body>" so that the recipient can verify the request came from OpenChat and reject replayed requests
+fn sign(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let mut hmac = hmac_sha256::HMAC::new(secret);
+    hmac.update(timestamp);
+    hmac.update(".");
+    hmac.update(body);
+    format!("v1={}", hex::encode(hmac.finalize()))
+}
+
+// Only resolves hostnames to publicly routable addresses, so that webhooks can't target the pusher's private network
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let domain_name = name.as_str().to_owned();
+        Box::pin(async move {
+            let public: Vec<SocketAddr> = tokio::net::lookup_host((domain_name, 0))
+                .await?
+                .filter(|a| is_publicly_routable(a.ip()))
+                .collect();
+
+            if public.is_empty() {
+                return Err("Host does not resolve to a publicly routable address".into());
+            }
+
+            let addrs: Addrs = Box::new(public.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+fn email_subject(payload: &OutboundNotificationPayload) -> String {
+    let sender = payload.sender_name.as_deref().unwrap_or("Someone");
+    match payload.category {
+        NotificationCategory::Mention => format!("{sender} mentioned you on OpenChat"),
+        NotificationCategory::Reply => format!("{sender} replied to you on OpenChat"),
+        NotificationCategory::DirectMessage => format!("New message from {sender} on OpenChat"),
+        NotificationCategory::Keyword => format!("{sender} mentioned one of your keywords on OpenChat"),
+    }
+}
+
+fn email_body(payload: &OutboundNotificationPayload) -> String {
+    let mut body = payload.message_text.clone().unwrap_or_default();
+    if !body.is_empty() {
+        body.push_str("\r\n\r\n");
+    }
+    body.push_str("Open OpenChat to view the message.");
+    body
+}
+
+fn email_confirmation_body(confirmation: &EmailConfirmation) -> String {
+    format!(
+        "Your confirmation code is {}\r\n\r\nEnter this code in OpenChat to start receiving notifications at this address. If you didn't request this, you can ignore this email.",
+        confirmation.code
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let signature = sign("secret", "1000", b"{}");
+
+        assert!(signature.starts_with("v1="));
+        assert_eq!(signature, sign("secret", "1000", b"{}"));
+        assert_ne!(signature, sign("secret", "1001", b"{}"));
+        assert_ne!(signature, sign("secret", "1000", b"{ }"));
+        assert_ne!(signature, sign("other", "1000", b"{}"));
+    }
+}
@@ -0,0 +1,214 @@
+use crate::config::SmtpRelayConfig;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::time::timeout;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+// A minimal SMTP client which hands emails to a trusted relay, the relay is responsible for TLS, auth and delivery
+pub async fn send(relay: &SmtpRelayConfig, to: &str, subject: &str, body: &str) -> Result<(), String> {
+    timeout(SESSION_TIMEOUT, send_inner(relay, to, subject, body))
+        .await
+        .map_err(|_| "SMTP session timed out".to_string())?
+}
+
+async fn send_inner(relay: &SmtpRelayConfig, to: &str, subject: &str, body: &str) -> Result<(), String> {
+    let stream = timeout(CONNECT_TIMEOUT, TcpStream::connect(&relay.address))
+        .await
+        .map_err(|_| "Timed out connecting to SMTP relay".to_string())?
+        .map_err(|e| e.to_string())?;
+    let (reader, writer) = stream.into_split();
+    let mut session = Session {
+        reader: BufReader::new(reader),
+        writer,
+    };
+
+    session.expect(220).await?;
+    session.command("HELO oc.app", 250).await?;
+    session
+        .command(&format!("MAIL FROM:<{}>", sanitize(&relay.from)), 250)
+        .await?;
+    session.command(&format!("RCPT TO:<{}>", sanitize(to)), 250).await?;
+    session.command("DATA", 354).await?;
+
+    let message = format!(
+        "From: <{}>\r\nTo: <{}>\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n.",
+        sanitize(&relay.from),
+        sanitize(to),
+        sanitize(subject),
+        dot_stuff(body),
+    );
+    session.command(&message, 250).await?;
+    session.command("QUIT", 221).await
+}
+
+struct Session {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl Session {
+    async fn command(&mut self, line: &str, expected_code: u16) -> Result<(), String> {
+        self.writer
+            .write_all(format!("{line}\r\n").as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+        self.expect(expected_code).await
+    }
+
+    async fn expect(&mut self, expected_code: u16) -> Result<(), String> {
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line).await.map_err(|e| e.to_string())? == 0 {
+                return Err("SMTP connection closed unexpectedly".to_string());
+            }
+
+            // Multi-line replies use a '-' after the code on every line except the last
+            let code = line.get(..3).and_then(|c| c.parse::<u16>().ok());
+            if line.as_bytes().get(3) == Some(&b'-') {
+                continue;
+            }
+
+            return if code == Some(expected_code) {
+                Ok(())
+            } else {
+                Err(format!("Unexpected SMTP reply: {}", line.trim_end()))
+            };
+        }
+    }
+}
+
+// Prevents header injection by stripping any line breaks from header values
+fn sanitize(value: &str) -> String {
+    value.chars().filter(|c| *c != '\r' && *c != '\n').collect()
+}
+
+// Normalises line endings and escapes lines starting with '.' so they can't terminate the DATA section early
+fn dot_stuff(body: &str) -> String {
+    body.replace("\r\n", "\n")
+        .split('\n')
+        .map(|line| if line.starts_with('.') { format!(".{line}") } else { line.to_string() })
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn sanitize_strips_line_breaks() {
+        assert_eq!(sanitize("Hello\r\nBcc: victim@example.com\n"), "HelloBcc: victim@example.com");
+    }
+
+    #[test]
+    fn dot_stuff_escapes_leading_dots_and_normalises_line_endings() {
+        assert_eq!(
+            dot_stuff("first\n.\r\n..second\nthird."),
+            "first\r\n..\r\n...second\r\nthird."
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_to_relay() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let relay = SmtpRelayConfig {
+            address: listener.local_addr().unwrap().to_string(),
+            from: "noreply@oc.app".to_string(),
+        };
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut reader = BufReader::new(reader);
+            let mut lines = Vec::new();
+            let mut in_data = false;
+
+            writer.write_all(b"220 relay ready\r\n").await.unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end_matches("\r\n").to_string();
+                let reply: &[u8] = if in_data {
+                    if line == "." {
+                        in_data = false;
+                        b"250 queued\r\n"
+                    } else {
+                        lines.push(line);
+                        continue;
+                    }
+                } else if line.starts_with("HELO") {
+                    b"250-relay greets oc.app\r\n250-SIZE 1000000\r\n250 HELP\r\n"
+                } else if line == "DATA" {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if line == "QUIT" {
+                    writer.write_all(b"221 bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 ok\r\n"
+                };
+                writer.write_all(reply).await.unwrap();
+            }
+            lines
+        });
+
+        send(
+            &relay,
+            "user@example.com",
+            "Hi\r\nBcc: victim@example.com",
+            "line 1\n.\n.line 3",
+        )
+        .await
+        .unwrap();
+
+        let data = server.await.unwrap();
+        assert_eq!(
+            data,
+            vec![
+                "From: <noreply@oc.app>",
+                "To: <user@example.com>",
+                "Subject: HiBcc: victim@example.com",
+                "Content-Type: text/plain; charset=utf-8",
+                "",
+                "line 1",
+                "..",
+                "..line 3",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn send_fails_on_rejected_recipient() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let relay = SmtpRelayConfig {
+            address: listener.local_addr().unwrap().to_string(),
+            from: "noreply@oc.app".to_string(),
+        };
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut reader = BufReader::new(reader);
+
+            writer.write_all(b"220 relay ready\r\n").await.unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                let reply: &[u8] = if line.starts_with("RCPT") { b"550 no such user\r\n" } else { b"250 ok\r\n" };
+                writer.write_all(reply).await.unwrap();
+            }
+        });
+
+        let error = send(&relay, "unknown@example.com", "Hi", "Body").await.unwrap_err();
+        assert_eq!(error, "Unexpected SMTP reply: 550 no such user");
+    }
+}
@@ -1,6 +1,9 @@
 use crate::ic_agent::IcAgent;
 use crate::metrics::write_metrics;
-use crate::{BotNotification, FcmNotification, NotificationMetadata, PushNotification, UserNotification};
+use crate::{
+    BotNotification, FcmNotification, NotificationMetadata, OutboundNotification, OutboundNotificationContent,
+    PushNotification, UserNotification,
+};
 use async_channel::Sender;
 use base64::Engine;
 use index_store::IndexStore;
@@ -16,6 +19,7 @@ pub struct Reader<I: IndexStore> {
     index_store: I,
     user_notification_sender: Sender<PushNotification>,
     bot_notification_sender: Sender<BotNotification>,
+    outbound_notification_sender: Sender<OutboundNotification>,
 }
 
 impl<I: IndexStore> Reader<I> {
@@ -25,6 +29,7 @@ impl<I: IndexStore> Reader<I> {
         index_store: I,
         user_notification_sender: Sender<PushNotification>,
         bot_notification_sender: Sender<BotNotification>,
+        outbound_notification_sender: Sender<OutboundNotification>,
     ) -> Self {
         Self {
             ic_agent,
@@ -32,6 +37,7 @@ impl<I: IndexStore> Reader<I> {
             index_store,
             user_notification_sender,
             bot_notification_sender,
+            outbound_notification_sender,
         }
     }
 
@@ -107,6 +113,7 @@ impl<I: IndexStore> Reader<I> {
                                         PushNotification::UserNotification(UserNotification {
                                             payload: payload.clone(),
                                             subscription_info,
+                                            low_priority: notification.low_priority,
                                             metadata,
                                         })
                                     }
@@ -149,6 +156,30 @@ impl<I: IndexStore> Reader<I> {
                         }
                     }
                 }
+                NotificationEnvelope::Outbound(notification) => {
+                    self.outbound_notification_sender
+                        .send(OutboundNotification {
+                            notifications_canister: self.notifications_canister_id,
+                            index: indexed_notification.index,
+                            timestamp: notification.payload.timestamp,
+                            content: OutboundNotificationContent::Notification(notification),
+                            first_read_at,
+                        })
+                        .await
+                        .unwrap();
+                }
+                NotificationEnvelope::EmailConfirmation(confirmation) => {
+                    self.outbound_notification_sender
+                        .send(OutboundNotification {
+                            notifications_canister: self.notifications_canister_id,
+                            index: indexed_notification.index,
+                            timestamp: confirmation.timestamp,
+                            content: OutboundNotificationContent::EmailConfirmation(confirmation),
+                            first_read_at,
+                        })
+                        .await
+                        .unwrap();
+                }
             }
 
             latest_index_processed = Some(indexed_notification.index);
@@ -101,8 +101,13 @@ impl Processor {
             .build_vapid_signature(&notification.subscription_info)
             .map_err(ProcessNotificationError::FailedToBuildSignature)?;
 
-        let message = build_web_push_message(payload_bytes, &notification.subscription_info, vapid_signature.clone())
-            .map_err(ProcessNotificationError::FailedToBuildMessage)?;
+        let message = build_web_push_message(
+            payload_bytes,
+            &notification.subscription_info,
+            vapid_signature.clone(),
+            notification.low_priority,
+        )
+        .map_err(ProcessNotificationError::FailedToBuildMessage)?;
 
         let length = message.payload.as_ref().map_or(0, |p| p.content.len()) as u32;
         if length <= MAX_PAYLOAD_LENGTH_BYTES {
@@ -133,13 +138,20 @@ fn build_web_push_message(
     payload: &[u8],
     subscription: &SubscriptionInfo,
     vapid_signature: VapidSignature,
+    low_priority: bool,
 ) -> Result<WebPushMessage, WebPushError> {
     let subscription: web_push::SubscriptionInfo = to_web_push_subscription_info(subscription);
     let mut message_builder = WebPushMessageBuilder::new(&subscription);
     message_builder.set_payload(ContentEncoding::Aes128Gcm, payload);
     message_builder.set_vapid_signature(vapid_signature);
-    message_builder.set_ttl(3600); // 1 hour
-    message_builder.set_urgency(Urgency::High);
+    if low_priority {
+        // Digests summarise notifications which were deliberately held back, so there's no need to wake the device
+        message_builder.set_ttl(12 * 3600); // 12 hours
+        message_builder.set_urgency(Urgency::Low);
+    } else {
+        message_builder.set_ttl(3600); // 1 hour
+        message_builder.set_urgency(Urgency::High);
+    }
     message_builder.build()
 }
 
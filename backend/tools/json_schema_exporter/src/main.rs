@@ -0,0 +1,189 @@
+use serde_json::{Map, Value, json};
+use std::fs;
+use std::path::Path;
+
+// Combines the per method schemas written by `generate_ts_method!` (see `ts_export::export_json_schema`) into an
+// OpenAPI spec and a JSON schema for each canister.
+struct Endpoint {
+    name: String,
+    method_type: &'static str,
+    encodings: Vec<String>,
+    args: Value,
+    response: Value,
+}
+
+fn main() {
+    let current_dir = std::env::current_dir().unwrap();
+    let ts_bindings_dir = current_dir.join("tsBindings");
+    let canisters_dir = current_dir.join("backend/canisters");
+    let output_dir = current_dir.join("jsonSchemas");
+
+    if output_dir.exists() {
+        fs::remove_dir_all(&output_dir).unwrap();
+    }
+    fs::create_dir_all(&output_dir).unwrap();
+
+    let mut canister_names: Vec<_> = fs::read_dir(&canisters_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+
+    canister_names.sort();
+
+    for canister_name in canister_names {
+        let bindings_dir = ts_bindings_dir.join(to_camel_case(&canister_name));
+        let api_src_dir = canisters_dir.join(&canister_name).join("api/src");
+        let mut definitions = Map::new();
+        let mut endpoints = Vec::new();
+
+        for (dir, method_type) in [("queries", "query"), ("updates", "update")] {
+            for name in module_names(&api_src_dir.join(dir)) {
+                let path = bindings_dir.join(to_camel_case(&name)).join("schema.json");
+                let Ok(contents) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                let mut schema: Value = serde_json::from_str(&contents).unwrap();
+                if let Value::Object(method_definitions) = schema["definitions"].take() {
+                    for (key, value) in method_definitions {
+                        definitions.entry(key).or_insert(value);
+                    }
+                }
+                let encodings = schema["encodings"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|e| e.as_str().map(|e| e.to_string()))
+                    .filter(|e| e == "msgpack" || e == "json")
+                    .collect::<Vec<_>>();
+
+                if !encodings.is_empty() {
+                    endpoints.push(Endpoint {
+                        name,
+                        method_type,
+                        encodings,
+                        args: schema["args"].take(),
+                        response: schema["response"].take(),
+                    });
+                }
+            }
+        }
+
+        if endpoints.is_empty() {
+            continue;
+        }
+
+        endpoints.sort_by(|a, b| a.name.cmp(&b.name));
+        let definitions = Value::Object(definitions);
+
+        let open_api = build_open_api(&canister_name, &endpoints, &definitions);
+        let schema = build_json_schema(&definitions);
+
+        write_json(&output_dir.join(format!("{canister_name}.openapi.json")), &open_api);
+        write_json(&output_dir.join(format!("{canister_name}.schema.json")), &schema);
+    }
+}
+
+fn build_open_api(canister_name: &str, endpoints: &[Endpoint], definitions: &Value) -> Value {
+    let mut paths = Map::new();
+
+    for endpoint in endpoints {
+        let args = to_open_api_refs(&endpoint.args);
+        let response = to_open_api_refs(&endpoint.response);
+
+        for encoding in endpoint.encodings.iter() {
+            let content_type = format!("application/{encoding}");
+            let operation_id = format!("{}_{encoding}", endpoint.name);
+            let operation = json!({
+                "operationId": operation_id,
+                "tags": [endpoint.method_type],
+                "requestBody": {
+                    "required": true,
+                    "content": { &content_type: { "schema": args } }
+                },
+                "responses": {
+                    "200": {
+                        "description": "Success",
+                        "content": { &content_type: { "schema": response } }
+                    }
+                }
+            });
+            paths.insert(format!("/{operation_id}"), json!({ "post": operation }));
+        }
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": format!("{canister_name} canister"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "tags": [
+            { "name": "query", "description": "Query calls. Fast but not certified." },
+            { "name": "update", "description": "Update calls. Go through consensus." },
+        ],
+        "paths": paths,
+        "components": { "schemas": to_open_api_refs(definitions) },
+    })
+}
+
+fn build_json_schema(definitions: &Value) -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2019-09/schema",
+        "$defs": definitions,
+    })
+}
+
+// The per method schemas reference definitions via "#/$defs/", whereas OpenAPI expects "#/components/schemas/"
+fn to_open_api_refs(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let v = match (k.as_str(), v) {
+                        ("$ref", Value::String(r)) => Value::String(r.replace("#/$defs/", "#/components/schemas/")),
+                        _ => to_open_api_refs(v),
+                    };
+                    (k.clone(), v)
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.iter().map(to_open_api_refs).collect()),
+        _ => value.clone(),
+    }
+}
+
+// Returns the names of the modules in the given directory, each of which corresponds to an endpoint
+fn module_names(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_string_lossy().strip_suffix(".rs").map(|n| n.to_string()))
+        .filter(|n| n != "mod")
+        .collect()
+}
+
+fn to_camel_case(value: &str) -> String {
+    let mut result = String::new();
+    let mut capitalize_next = false;
+    for c in value.chars() {
+        if c == '_' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            result.extend(c.to_uppercase());
+            capitalize_next = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn write_json(path: &Path, value: &Value) {
+    let mut contents = serde_json::to_string_pretty(value).unwrap();
+    contents.push('\n');
+    fs::write(path, contents).unwrap();
+}
@@ -0,0 +1,63 @@
+use canister_agent_utils::{build_ic_agent, get_dfx_identity};
+use std::{collections::HashMap, error::Error};
+use translations_canister::export::{Response, SuccessResponse};
+use translations_canister::pending_deployment::Translation;
+use types::{CanisterId, Empty};
+
+use crate::Config;
+use crate::mark_deployed::read_latest_approval;
+use crate::merge_latest_approved::{
+    merge_translations, read_translations_from_files, write_latest_approval, write_translation_files,
+};
+
+// Pulls every approved translation (both deployed and pending deployment) in a single call and merges those approved
+// since the translation files were last synced. Older approvals will already have been merged, so if the local value
+// differs it has since been edited locally and must not be reverted.
+pub async fn run(config: Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut translations = read_translations_from_files(&config.directory).await?;
+    let last_synced = read_latest_approval(&config.directory).await?;
+
+    let export = export_approved_translations(&config.url, &config.controller, &config.translations_canister_id).await?;
+
+    let mut latest_approval = last_synced.max(export.latest_approval);
+    let mut approved = Vec::new();
+    for bundle in export.bundles {
+        let values: HashMap<String, String> = serde_json::from_str(&bundle.json)?;
+        for (key, value) in values {
+            let approved_at = bundle.approved_at.get(&key).copied().unwrap_or_default();
+            if approved_at > last_synced {
+                latest_approval = latest_approval.max(approved_at);
+                approved.push(Translation {
+                    locale: bundle.locale.clone(),
+                    key,
+                    value,
+                });
+            }
+        }
+    }
+
+    merge_translations(&mut translations, approved)?;
+
+    write_translation_files(&config.directory, translations).await?;
+
+    if latest_approval > last_synced {
+        write_latest_approval(&config.directory, latest_approval).await?;
+    }
+
+    Ok(())
+}
+
+async fn export_approved_translations(
+    url: &str,
+    controller: &str,
+    translations_canister_id: &CanisterId,
+) -> Result<SuccessResponse, Box<dyn Error + Send + Sync>> {
+    let identity = get_dfx_identity(controller);
+    let agent = build_ic_agent(url.to_string(), identity).await;
+
+    translations_canister_client::export(&agent, translations_canister_id, &Empty {})
+        .await
+        .map(|response| match response {
+            Response::Success(result) => Ok(result),
+        })?
+}
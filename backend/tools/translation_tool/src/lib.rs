@@ -2,12 +2,13 @@ use clap::Parser;
 use std::error::Error;
 use types::CanisterId;
 
+mod export_approved;
 mod mark_deployed;
 mod merge_latest_approved;
 
 #[derive(Parser, Debug)]
 pub struct Config {
-    /// "merge" or "mark-deployed"
+    /// "merge", "export" or "mark-deployed"
     #[arg(long)]
     action: String,
 
@@ -31,6 +32,8 @@ pub struct Config {
 pub async fn run(config: Config) -> Result<(), Box<dyn Error + Send + Sync>> {
     if config.action == "merge" {
         merge_latest_approved::run(config).await
+    } else if config.action == "export" {
+        export_approved::run(config).await
     } else if config.action == "mark-deployed" {
         mark_deployed::run(config).await
     } else {
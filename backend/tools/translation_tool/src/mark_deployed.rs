@@ -1,24 +1,30 @@
 use canister_agent_utils::{build_ic_agent, get_dfx_identity};
-use std::{error::Error, fs};
+use std::{collections::BTreeSet, error::Error, fs};
 use types::{CanisterId, TimestampMillis};
 
 use crate::Config;
+use crate::merge_latest_approved::read_translations_from_files;
 
 pub async fn run(config: Config) -> Result<(), Box<dyn Error + Send + Sync>> {
     let latest_approval = read_latest_approval(&config.directory).await?;
 
+    // The full set of keys is sent so that the canister can calculate coverage against it
+    let translations = read_translations_from_files(&config.directory).await?;
+    let keys: BTreeSet<String> = translations.into_values().flat_map(|t| t.into_keys()).collect();
+
     mark_approved_translations_as_deployed(
         &config.url,
         &config.controller,
         &config.translations_canister_id,
         latest_approval,
+        keys.into_iter().collect(),
     )
     .await?;
 
     Ok(())
 }
 
-async fn read_latest_approval(directory_path: &str) -> Result<TimestampMillis, Box<dyn Error + Send + Sync>> {
+pub(crate) async fn read_latest_approval(directory_path: &str) -> Result<TimestampMillis, Box<dyn Error + Send + Sync>> {
     let text = fs::read_to_string(format!("{directory_path}/latest-approval.txt")).unwrap_or("0".to_string());
     let timestamp: TimestampMillis = serde_json::from_str(&text)?;
     Ok(timestamp)
@@ -29,10 +35,14 @@ async fn mark_approved_translations_as_deployed(
     controller: &str,
     translations_canister_id: &CanisterId,
     latest_approval: TimestampMillis,
+    keys: Vec<String>,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     let identity = get_dfx_identity(controller);
     let agent = build_ic_agent(url.to_string(), identity).await;
-    let args = translations_canister::mark_deployed::Args { latest_approval };
+    let args = translations_canister::mark_deployed::Args {
+        latest_approval,
+        keys: Some(keys),
+    };
 
     translations_canister_client::mark_deployed(&agent, translations_canister_id, &args).await?;
 
@@ -33,7 +33,7 @@ pub async fn run(config: Config) -> Result<(), Box<dyn Error + Send + Sync>> {
     Ok(())
 }
 
-async fn read_translations_from_files(
+pub(crate) async fn read_translations_from_files(
     path: &str,
 ) -> Result<HashMap<String, HashMap<String, String>>, Box<dyn Error + Send + Sync>> {
     let mut files = HashMap::new();
@@ -108,7 +108,7 @@ async fn read_latest_translation_corrections(
         })?
 }
 
-fn merge_translations(
+pub(crate) fn merge_translations(
     translations: &mut HashMap<String, HashMap<String, String>>,
     corrections: Vec<Translation>,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
@@ -132,7 +132,7 @@ fn merge_translations(
     Ok(())
 }
 
-async fn write_translation_files(
+pub(crate) async fn write_translation_files(
     path: &str,
     data: HashMap<String, HashMap<String, String>>,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
@@ -185,7 +185,7 @@ fn to_string_pretty<T: Serialize>(value: T) -> String {
     String::from_utf8(buf).unwrap()
 }
 
-async fn write_latest_approval(path: &str, timestamp: TimestampMillis) -> Result<(), Box<dyn Error + Send + Sync>> {
+pub(crate) async fn write_latest_approval(path: &str, timestamp: TimestampMillis) -> Result<(), Box<dyn Error + Send + Sync>> {
     let text = serde_json::to_string_pretty(&timestamp)?;
     fs::write(format!("{path}/latest-approval.txt"), text)?;
     Ok(())
@@ -0,0 +1,17 @@
+#!/bin/bash
+
+SCRIPT=$(readlink -f "$0")
+SCRIPT_DIR=$(dirname "$SCRIPT")
+cd $SCRIPT_DIR/..
+
+rm -rf ./tsBindings
+
+for canister_path in ./backend/canisters/*/
+do
+  canister_path=${canister_path%*/}
+  canister_name=${canister_path##*/}
+
+  cargo run -p ${canister_name}_canister > /dev/null
+done
+
+cargo run -p json_schema_exporter